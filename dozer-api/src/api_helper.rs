@@ -39,7 +39,7 @@ pub fn get_records(
         .map_err(ApiError::QueryFailed)
 }
 
-fn get_access_filter(access: Option<Access>) -> Result<AccessFilter, ApiError> {
+pub fn get_access_filter(access: Option<Access>) -> Result<AccessFilter, ApiError> {
     match access {
         None | Some(Access::All) => Ok(AccessFilter {
             filter: None,
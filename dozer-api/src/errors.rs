@@ -37,6 +37,8 @@ pub enum ApiError {
     TypeError(#[from] TypeError),
     #[error("Failed to bind to address {0}: {1}")]
     FailedToBindToAddress(String, #[source] std::io::Error),
+    #[error("Failed to stream events: {}", .0.message())]
+    EventStreamFailed(tonic::Status),
 }
 
 impl ApiError {
@@ -142,6 +144,12 @@ impl actix_web::error::ResponseError for ApiError {
             ApiError::NoPrimaryKey | ApiError::MultiIndexFetch(_) => {
                 StatusCode::UNPROCESSABLE_ENTITY
             }
+            ApiError::EventStreamFailed(ref status) => match status.code() {
+                tonic::Code::InvalidArgument | tonic::Code::OutOfRange => StatusCode::BAD_REQUEST,
+                tonic::Code::FailedPrecondition => StatusCode::GONE,
+                tonic::Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
             ApiError::InternalError(_)
             | ApiError::OpenCache(_)
             | ApiError::CacheNotFound(_)
@@ -42,6 +42,7 @@ impl<'a> OpenApiGenerator<'a> {
 
                     FieldType::Text => Value::from("lorem ipsum".to_string()),
                     FieldType::Date => Value::from("2022-11-24"),
+                    FieldType::Interval => Value::from("PT1H"),
                     FieldType::Point => {
                         let mut m = Map::new();
                         m.insert("x".to_string(), Value::from(3.3));
@@ -116,7 +116,8 @@ fn convert_cache_type_to_schema_type(field_type: dozer_types::types::FieldType)
         | FieldType::Text
         | FieldType::Decimal
         | FieldType::Timestamp
-        | FieldType::Date => {
+        | FieldType::Date
+        | FieldType::Interval => {
             let (format, pattern) = if field_type == FieldType::Timestamp {
                 (
                     VariantOrUnknownOrEmpty::Item(StringFormat::DateTime),
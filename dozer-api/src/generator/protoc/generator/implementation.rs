@@ -187,6 +187,7 @@ impl<'a> ProtoGeneratorImpl<'a> {
                                 lo: get_field(&dv, "lo")?,
                                 mid: get_field(&dv, "mid")?,
                                 hi: get_field(&dv, "hi")?,
+                                scale: get_field(&dv, "scale")?,
                             },
                         })
                     } else {
@@ -259,6 +260,7 @@ impl<'a> ProtoGeneratorImpl<'a> {
                     let old_field = get_field(&message, "old")?;
                     let new_field = get_field(&message, "new")?;
                     let new_id_field = get_field(&message, "new_id")?;
+                    let seq_no_field = get_field(&message, "seq_no")?;
                     let old_field_kind = old_field.kind();
                     let Kind::Message(record_message) = old_field_kind else {
                         return Err(GenerationError::ExpectedMessageField {
@@ -274,6 +276,7 @@ impl<'a> ProtoGeneratorImpl<'a> {
                             old_field,
                             new_field,
                             new_id_field,
+                            seq_no_field,
                             record_desc: record_desc_from_message(record_message)?,
                         },
                     });
@@ -371,5 +374,6 @@ fn convert_dozer_type_to_proto_type(field_type: FieldType) -> Result<String, Gen
         FieldType::Date => Ok("string".to_owned()),
         FieldType::Bson => Ok("bytes".to_owned()),
         FieldType::Point => Ok(POINT_TYPE_CLASS.to_owned()),
+        FieldType::Interval => Ok("string".to_owned()),
     }
 }
@@ -72,6 +72,7 @@ pub struct DecimalDesc {
     pub lo: FieldDescriptor,
     pub mid: FieldDescriptor,
     pub hi: FieldDescriptor,
+    pub scale: FieldDescriptor,
 }
 
 #[derive(Debug, Clone)]
@@ -96,6 +97,7 @@ pub struct EventDesc {
     pub old_field: FieldDescriptor,
     pub new_field: FieldDescriptor,
     pub new_id_field: FieldDescriptor,
+    pub seq_no_field: FieldDescriptor,
     pub record_desc: RecordDesc,
 }
 
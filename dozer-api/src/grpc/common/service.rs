@@ -109,17 +109,13 @@ impl CommonGrpcService for CommonService {
             .ok_or_else(|| Status::invalid_argument(endpoint))?;
 
         shared_impl::on_event(
-            &cache_endpoint.cache_reader(),
+            Arc::clone(&cache_endpoint.cache_reader()),
+            query_request.endpoint.clone(),
             query_request.filter.as_deref(),
+            query_request.resume_from,
             self.event_notifier.as_ref().map(|r| r.resubscribe()),
             access.cloned(),
-            move |op| {
-                if op.endpoint_name == query_request.endpoint {
-                    Some(Ok(op))
-                } else {
-                    None
-                }
-            },
+            |op| Some(Ok(op)),
         )
     }
 
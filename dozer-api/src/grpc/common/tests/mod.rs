@@ -1,7 +1,15 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
-use crate::grpc::typed::tests::{
-    fake_internal_pipeline_server::start_fake_internal_grpc_pipeline, service::setup_pipeline,
+use crate::{
+    auth::Access,
+    grpc::typed::tests::{
+        fake_internal_pipeline_server::start_fake_internal_grpc_pipeline, service::setup_pipeline,
+    },
+};
+
+use dozer_cache::{
+    cache::expression::{FilterExpression, Operator},
+    AccessFilter,
 };
 
 use dozer_types::grpc_types::{
@@ -12,8 +20,9 @@ use dozer_types::grpc_types::{
     types::{value, EventType, FieldDefinition, OperationType, RecordWithId, Type, Value},
 };
 use dozer_types::models::api_config::default_api_config;
-use tokio::sync::oneshot;
-use tonic::Request;
+use dozer_types::serde_json::json;
+use tokio::{sync::oneshot, time::timeout};
+use tonic::{Code, Request};
 
 use super::CommonService;
 
@@ -151,6 +160,7 @@ async fn test_grpc_common_on_event() {
             endpoint: "films".to_string(),
             r#type: EventType::All as i32,
             filter: Some(r#"{ "film_id": 32 }"#.to_string()),
+            resume_from: None,
         }))
         .await
         .unwrap()
@@ -168,3 +178,84 @@ async fn test_grpc_common_on_event() {
         }
     );
 }
+
+#[tokio::test]
+async fn test_grpc_common_on_event_with_access_filter() {
+    // start fake internal pipeline
+    let (sender_shutdown_internal, rx_internal) = oneshot::channel::<()>();
+    let default_pipeline_internal = default_api_config().app_grpc.unwrap_or_default();
+    let _jh = tokio::spawn(start_fake_internal_grpc_pipeline(
+        default_pipeline_internal.host,
+        default_pipeline_internal.port,
+        rx_internal,
+    ));
+    tokio::time::sleep(Duration::from_millis(100)).await; // wait for the mock server to start.
+    let service = setup_common_service().await;
+    let on_event = |film_id: u64| {
+        let mut request = Request::new(OnEventRequest {
+            endpoint: "films".to_string(),
+            r#type: EventType::All as i32,
+            filter: None,
+            resume_from: None,
+        });
+        request
+            .extensions_mut()
+            .insert(Access::Custom(HashMap::from([(
+                "get_records".to_string(),
+                AccessFilter {
+                    filter: Some(FilterExpression::Simple(
+                        "film_id".to_string(),
+                        Operator::EQ,
+                        json!(film_id),
+                    )),
+                    fields: vec!["film_id".to_string()],
+                },
+            )])));
+        service.on_event(request)
+    };
+
+    // Fields outside the access filter are hidden.
+    let mut rx = on_event(32).await.unwrap().into_inner().into_inner();
+    let operation = rx.recv().await.unwrap().unwrap();
+    drop(rx);
+    let values = operation.new.unwrap().values;
+    assert_eq!(
+        values[0],
+        Value {
+            value: Some(value::Value::UintValue(32))
+        }
+    );
+    assert_eq!(values[1], Value { value: None });
+
+    // Records outside the access filter are not streamed.
+    let mut rx = on_event(0).await.unwrap().into_inner().into_inner();
+    let error_timeout = timeout(Duration::from_secs(1), rx.recv()).await;
+    assert!(error_timeout.is_err() || error_timeout.unwrap().is_none());
+    _ = sender_shutdown_internal.send(());
+}
+
+#[tokio::test]
+async fn test_grpc_common_on_event_resume_from() {
+    let service = setup_common_service().await;
+    let on_event = |resume_from: u64| {
+        service.on_event(Request::new(OnEventRequest {
+            endpoint: "films".to_string(),
+            r#type: EventType::All as i32,
+            filter: None,
+            resume_from: Some(resume_from),
+        }))
+    };
+
+    // The cache has 52 inserts, so events after 50 are replayed from the operation log.
+    let mut rx = on_event(50).await.unwrap().into_inner().into_inner();
+    for seq_no in [51, 52] {
+        let operation = rx.recv().await.unwrap().unwrap();
+        assert_eq!(operation.typ, OperationType::Insert as i32);
+        assert_eq!(operation.seq_no, seq_no);
+    }
+    drop(rx);
+
+    // Resuming beyond the operation log fails.
+    let error = on_event(1000).await.unwrap_err();
+    assert_eq!(error.code(), Code::OutOfRange);
+}
@@ -4,7 +4,7 @@ pub mod health;
 pub mod internal;
 // pub mod dynamic;
 mod auth_middleware;
-mod shared_impl;
+pub(crate) mod shared_impl;
 pub mod typed;
 pub mod types_helper;
 
@@ -1,8 +1,11 @@
-use dozer_cache::cache::expression::{FilterExpression, Operator};
+use dozer_cache::{
+    cache::expression::{FilterExpression, Operator, SpatialFilter},
+    AccessFilter,
+};
 use dozer_types::{
     json_value_to_field,
     ordered_float::OrderedFloat,
-    types::{Field, Schema},
+    types::{DozerPoint, Field, Schema},
 };
 
 use dozer_types::grpc_types::types::{value, Operation, OperationType, Record, Value};
@@ -26,11 +29,61 @@ pub fn op_satisfies_filter(
     }
 }
 
+/// Returns what a subscriber with `access_filter` may see of `op`, or `None` if it may see nothing.
+///
+/// An update that moves a record into or out of the access filter is seen as an insert or a delete.
+/// Fields that are not in `access_filter.fields` are set to null, unless it's empty.
+pub fn apply_access_filter(
+    mut op: Operation,
+    access_filter: &AccessFilter,
+    schema: &Schema,
+) -> Option<Operation> {
+    if let Some(filter) = &access_filter.filter {
+        if op.typ == OperationType::Insert as i32 || op.typ == OperationType::Delete as i32 {
+            if !record_satisfies_filter(op.new.as_ref().unwrap(), filter, schema) {
+                return None;
+            }
+        } else if op.typ == OperationType::Update as i32 {
+            let old_satisfies = record_satisfies_filter(op.old.as_ref().unwrap(), filter, schema);
+            let new_satisfies = record_satisfies_filter(op.new.as_ref().unwrap(), filter, schema);
+            match (old_satisfies, new_satisfies) {
+                (true, true) => (),
+                (true, false) => {
+                    op.typ = OperationType::Delete as i32;
+                    op.new = op.old.take();
+                }
+                (false, true) => {
+                    op.typ = OperationType::Insert as i32;
+                    op.old = None;
+                }
+                (false, false) => return None,
+            }
+        } else {
+            return None;
+        }
+    }
+
+    if !access_filter.fields.is_empty() {
+        for record in op.old.iter_mut().chain(op.new.iter_mut()) {
+            for (field, value) in schema.fields.iter().zip(record.values.iter_mut()) {
+                if !access_filter.fields.contains(&field.name) {
+                    value.value = None;
+                }
+            }
+        }
+    }
+    Some(op)
+}
+
 fn record_satisfies_filter(record: &Record, filter: &FilterExpression, schema: &Schema) -> bool {
     match filter {
         FilterExpression::And(filters) => filters
             .iter()
             .all(|filter| record_satisfies_filter(record, filter, schema)),
+        FilterExpression::Or(filters) => filters
+            .iter()
+            .any(|filter| record_satisfies_filter(record, filter, schema)),
+        FilterExpression::Not(filter) => !record_satisfies_filter(record, filter, schema),
         FilterExpression::Simple(field_name, operator, value) => {
             let Some((field_index, field_definition)) = schema
                 .fields
@@ -44,6 +97,16 @@ fn record_satisfies_filter(record: &Record, filter: &FilterExpression, schema: &
             let Some(filed_value) = record.values.get(field_index) else {
                 return false;
             };
+            if filed_value.value.is_none() {
+                return false;
+            }
+
+            if operator.supported_by_spatial() {
+                let Ok(filter) = SpatialFilter::new(*operator, value) else {
+                    return false;
+                };
+                return field_satisfies_spatial_filter(filed_value, &filter);
+            }
 
             let Ok(value) = json_value_to_field(value.clone(), field_definition.typ, field_definition.nullable) else {
                 return false;
@@ -106,6 +169,16 @@ fn field_satisfies_op(field: &Value, operator: Operator, value: &Field) -> bool
             _ => false,
         },
         Operator::MatchesAll | Operator::MatchesAny => unimplemented!(),
+        Operator::Near | Operator::Within => {
+            unreachable!("spatial filters are checked by `field_satisfies_spatial_filter`")
+        }
+    }
+}
+
+fn field_satisfies_spatial_filter(field: &Value, filter: &SpatialFilter) -> bool {
+    match field.value.as_ref().unwrap() {
+        value::Value::PointValue(point) => filter.matches(&DozerPoint::from((point.x, point.y))),
+        _ => false,
     }
 }
 
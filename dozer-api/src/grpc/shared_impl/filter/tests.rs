@@ -766,6 +766,62 @@ fn test_record_satisfies_filter() {
     );
 }
 
+#[test]
+fn test_record_satisfies_spatial_filter() {
+    use dozer_types::grpc_types::types::PointType;
+    use dozer_types::types::{FieldDefinition, FieldType, SourceDefinition};
+
+    let schema = Schema {
+        identifier: None,
+        fields: vec![FieldDefinition {
+            name: "location".into(),
+            typ: FieldType::Point,
+            nullable: true,
+            source: SourceDefinition::Dynamic,
+        }],
+        primary_index: vec![],
+    };
+    // The Empire State Building.
+    let record = Record {
+        values: vec![Value {
+            value: Some(value::Value::PointValue(PointType {
+                x: -73.9857,
+                y: 40.7484,
+            })),
+        }],
+        version: 1,
+    };
+
+    let check = |operator, value, expected| {
+        let filter = FilterExpression::Simple("location".into(), operator, value);
+        assert_eq!(record_satisfies_filter(&record, &filter, &schema), expected);
+    };
+
+    // Times Square is about 1 km away.
+    let times_square = json!({"x": -73.9855, "y": 40.7580});
+    check(
+        Operator::Near,
+        json!({"point": times_square, "radius": 1500}),
+        true,
+    );
+    check(
+        Operator::Near,
+        json!({"point": times_square, "radius": 500}),
+        false,
+    );
+    check(
+        Operator::Within,
+        json!({"min": {"x": -74.1, "y": 40.6}, "max": {"x": -73.8, "y": 40.9}}),
+        true,
+    );
+    check(
+        Operator::Within,
+        json!({"min": {"x": 2.2, "y": 48.8}, "max": {"x": 2.5, "y": 48.9}}),
+        false,
+    );
+    check(Operator::Near, json!({"radius": 500}), false);
+}
+
 #[test]
 fn test_op_satisfies_filter() {
     let schema = schema_1().0;
@@ -809,7 +865,8 @@ fn test_op_satisfies_filter() {
                     old: old.cloned(),
                     new: Some(new.clone()),
                     new_id: None,
-                    endpoint_name: "".into()
+                    endpoint_name: "".into(),
+                    seq_no: 0,
                 },
                 filter,
                 &schema
@@ -844,3 +901,91 @@ fn test_op_satisfies_filter() {
         false,
     );
 }
+
+#[test]
+fn test_apply_access_filter() {
+    let schema = schema_1().0;
+    let record = |a, c| Record {
+        values: vec![
+            Value {
+                value: Some(value::Value::IntValue(a)),
+            },
+            Value {
+                value: Some(value::Value::StringValue("b".into())),
+            },
+            Value {
+                value: Some(value::Value::IntValue(c)),
+            },
+        ],
+        version: 1,
+    };
+    let operation = |typ: OperationType, old: Option<Record>, new: Record| Operation {
+        typ: typ as _,
+        old,
+        new: Some(new),
+        new_id: None,
+        endpoint_name: "".into(),
+        seq_no: 0,
+    };
+    let access_filter = AccessFilter {
+        filter: Some(FilterExpression::Simple("a".into(), Operator::EQ, json!(1))),
+        fields: vec![],
+    };
+
+    let insert = operation(OperationType::Insert, None, record(1, 3));
+    assert_eq!(
+        apply_access_filter(insert.clone(), &access_filter, &schema),
+        Some(insert)
+    );
+    let delete = operation(OperationType::Delete, None, record(2, 3));
+    assert_eq!(apply_access_filter(delete, &access_filter, &schema), None);
+
+    // Updates moving records into or out of the filter are seen as inserts or deletes.
+    let update = operation(OperationType::Update, Some(record(1, 3)), record(1, 4));
+    assert_eq!(
+        apply_access_filter(update.clone(), &access_filter, &schema),
+        Some(update)
+    );
+    assert_eq!(
+        apply_access_filter(
+            operation(OperationType::Update, Some(record(1, 3)), record(2, 3)),
+            &access_filter,
+            &schema
+        ),
+        Some(operation(OperationType::Delete, None, record(1, 3)))
+    );
+    assert_eq!(
+        apply_access_filter(
+            operation(OperationType::Update, Some(record(2, 3)), record(1, 3)),
+            &access_filter,
+            &schema
+        ),
+        Some(operation(OperationType::Insert, None, record(1, 3)))
+    );
+    assert_eq!(
+        apply_access_filter(
+            operation(OperationType::Update, Some(record(2, 3)), record(3, 3)),
+            &access_filter,
+            &schema
+        ),
+        None
+    );
+
+    // Fields not in the access filter are nulled, and can't be filtered on.
+    let access_filter = AccessFilter {
+        filter: None,
+        fields: vec!["a".into(), "b".into()],
+    };
+    let op = apply_access_filter(
+        operation(OperationType::Update, Some(record(1, 3)), record(1, 4)),
+        &access_filter,
+        &schema,
+    )
+    .unwrap();
+    for record in [op.old.as_ref().unwrap(), op.new.as_ref().unwrap()] {
+        assert_eq!(record.values[0].value, Some(value::Value::IntValue(1)));
+        assert_eq!(record.values[2].value, None);
+    }
+    let filter = FilterExpression::Simple("c".into(), Operator::EQ, json!(3));
+    assert!(!op_satisfies_filter(&op, Some(&filter), &schema));
+}
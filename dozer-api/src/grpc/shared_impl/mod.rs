@@ -1,16 +1,21 @@
-use dozer_cache::cache::expression::{default_limit_for_query, QueryExpression};
-use dozer_cache::cache::RecordWithId;
-use dozer_cache::CacheReader;
+use std::sync::Arc;
+
+use dozer_cache::cache::expression::{default_limit_for_query, FilterExpression, QueryExpression};
+use dozer_cache::cache::{LogOperation, RecordWithId, SeqNoOffset};
+use dozer_cache::{AccessFilter, CacheReader};
 use dozer_types::grpc_types::types::Operation;
-use dozer_types::log::warn;
+use dozer_types::log::{error, warn};
 use dozer_types::serde_json;
+use dozer_types::types::Schema;
 use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
+use tokio::sync::mpsc::Sender;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Code, Response, Status};
 
-use crate::api_helper::{get_records, get_records_count};
+use crate::api_helper::{get_access_filter, get_records, get_records_count};
 use crate::auth::Access;
+use crate::grpc::types_helper;
 
 mod filter;
 
@@ -57,59 +62,204 @@ pub fn query(
 }
 
 pub fn on_event<T: Send + 'static>(
-    reader: &CacheReader,
+    reader: Arc<CacheReader>,
+    endpoint_name: String,
     filter: Option<&str>,
-    mut broadcast_receiver: Option<Receiver<Operation>>,
-    _access: Option<Access>,
+    resume_from: Option<u64>,
+    broadcast_receiver: Option<Receiver<Operation>>,
+    access: Option<Access>,
     event_mapper: impl Fn(Operation) -> Option<T> + Send + Sync + 'static,
 ) -> Result<Response<ReceiverStream<T>>, Status> {
-    // TODO: Use access.
-
-    if broadcast_receiver.is_none() {
+    let Some(mut broadcast_receiver) = broadcast_receiver else {
         return Err(Status::unavailable(
             "on_event is not enabled. This is currently an experimental feature. Enable it in the config.",
         ));
-    }
+    };
 
     let filter = match filter {
         Some(filter) => {
             if filter.is_empty() {
                 None
             } else {
-                Some(serde_json::from_str(filter).map_err(from_error)?)
+                Some(
+                    serde_json::from_str(filter)
+                        .map_err(|e| Status::invalid_argument(e.to_string()))?,
+                )
             }
         }
         None => None,
     };
+    let access_filter = get_access_filter(access)?;
+    let seq_no_offset = reader.seq_no_offset().map_err(from_error)?;
+    if let Some(resume_from) = resume_from {
+        if resume_from < seq_no_offset.first_resumable {
+            return Err(Status::failed_precondition(format!(
+                "resume_from {resume_from} is before the first resumable event {}. The endpoint was rebuilt, so it must be queried again",
+                seq_no_offset.first_resumable
+            )));
+        }
+        let last_seq_no = seq_no_offset.offset + reader.operation_log_len().map_err(from_error)?;
+        if resume_from > last_seq_no {
+            return Err(Status::out_of_range(format!(
+                "resume_from {resume_from} is after the last event {last_seq_no}"
+            )));
+        }
+    }
     let schema = reader.get_schema().0.clone();
 
     let (tx, rx) = tokio::sync::mpsc::channel(1);
 
     tokio::spawn(async move {
+        let mut sender = EventSender {
+            reader,
+            endpoint_name,
+            filter,
+            access_filter,
+            schema,
+            event_mapper,
+            tx,
+            seq_no_offset: seq_no_offset.offset,
+            last_seq_no: resume_from,
+        };
+
+        // `broadcast_receiver` was subscribed before the replay, so no event is missed in between.
+        if !sender.replay().await {
+            return;
+        }
+
         loop {
-            if let Some(broadcast_receiver) = broadcast_receiver.as_mut() {
-                let event = broadcast_receiver.recv().await;
-                match event {
-                    Ok(op) => {
-                        if filter::op_satisfies_filter(&op, filter.as_ref(), &schema) {
-                            if let Some(event) = event_mapper(op) {
-                                if (tx.send(event).await).is_err() {
-                                    // receiver dropped
-                                    break;
-                                }
-                            }
-                        }
+            let event = broadcast_receiver.recv().await;
+            match event {
+                Ok(op) => {
+                    if !sender.send_live(op).await {
+                        // receiver dropped
+                        break;
                     }
-                    Err(e) => {
-                        warn!("Failed to receive event from broadcast channel: {}", e);
-                        if e == RecvError::Closed {
-                            break;
-                        }
+                }
+                Err(RecvError::Lagged(num_skipped)) => {
+                    warn!("Subscriber lagged behind by {num_skipped} events");
+                    if !sender.replay().await {
+                        break;
                     }
                 }
+                Err(RecvError::Closed) => break,
             }
         }
     });
 
     Ok(Response::new(ReceiverStream::new(rx)))
 }
+
+/// Number of operations read from the operation log at a time.
+const REPLAY_BATCH_SIZE: usize = 1000;
+
+struct EventSender<T, F> {
+    reader: Arc<CacheReader>,
+    endpoint_name: String,
+    filter: Option<FilterExpression>,
+    access_filter: AccessFilter,
+    schema: Schema,
+    event_mapper: F,
+    tx: Sender<T>,
+    /// `seq_no` of the events before the first operation in the operation log.
+    seq_no_offset: u64,
+    /// `seq_no` of the last event of the endpoint that was received, or `resume_from`.
+    last_seq_no: Option<u64>,
+}
+
+impl<T, F: Fn(Operation) -> Option<T>> EventSender<T, F> {
+    /// Sends the events after `last_seq_no` from the operation log. Returns `false` if the stream should end.
+    async fn replay(&mut self) -> bool {
+        let Some(last_seq_no) = self.last_seq_no else {
+            // Nothing was received and `resume_from` was not given, so there's no position to replay from.
+            return true;
+        };
+        let mut start = last_seq_no - self.seq_no_offset;
+        loop {
+            let operations = match self.reader.get_operations(start, REPLAY_BATCH_SIZE) {
+                Ok(operations) => operations,
+                Err(e) => {
+                    error!(
+                        "Failed to read operation log of {}: {e}",
+                        self.endpoint_name
+                    );
+                    return false;
+                }
+            };
+            let Some((last_operation_id, _)) = operations.last() else {
+                return true;
+            };
+            start = last_operation_id + 1;
+            for op in map_log_operations(&self.endpoint_name, self.seq_no_offset, operations) {
+                if !self.send(op).await {
+                    return false;
+                }
+            }
+        }
+    }
+
+    /// Sends an event from the broadcast channel, unless it's from another endpoint or has been replayed.
+    /// Returns `false` if the receiver is dropped.
+    async fn send_live(&mut self, op: Operation) -> bool {
+        if op.endpoint_name != self.endpoint_name
+            || self.last_seq_no.map_or(false, |seq_no| op.seq_no <= seq_no)
+        {
+            return true;
+        }
+        self.send(op).await
+    }
+
+    async fn send(&mut self, op: Operation) -> bool {
+        self.last_seq_no = Some(op.seq_no);
+        // Fields hidden by the access filter are nulled before `filter` sees them.
+        let op = filter::apply_access_filter(op, &self.access_filter, &self.schema)
+            .filter(|op| filter::op_satisfies_filter(op, self.filter.as_ref(), &self.schema));
+        match op.and_then(&self.event_mapper) {
+            Some(event) => self.tx.send(event).await.is_ok(),
+            None => true,
+        }
+    }
+}
+
+/// Maps operations from the operation log to events. An event's `seq_no` is `seq_no_offset` plus the length of the operation log after its operations.
+///
+/// A `Delete` immediately followed by an `Insert` of the same record is an update.
+fn map_log_operations(
+    endpoint_name: &str,
+    seq_no_offset: u64,
+    operations: Vec<(u64, LogOperation)>,
+) -> Vec<Operation> {
+    let mut events = vec![];
+    let mut operations = operations.into_iter().peekable();
+    while let Some((operation_id, operation)) = operations.next() {
+        let event = match operation {
+            LogOperation::Insert(new) => types_helper::map_insert_operation(
+                endpoint_name.to_string(),
+                new.record,
+                new.id,
+                seq_no_offset + operation_id + 1,
+            ),
+            LogOperation::Delete(old) => match operations.next_if(|(next_id, next)| {
+                *next_id == operation_id + 1
+                    && matches!(next, LogOperation::Insert(new) if new.id == old.id)
+            }) {
+                Some((insert_id, LogOperation::Insert(new))) => types_helper::map_update_operation(
+                    endpoint_name.to_string(),
+                    old.record,
+                    new.record,
+                    seq_no_offset + insert_id + 1,
+                ),
+                _ => types_helper::map_delete_operation(
+                    endpoint_name.to_string(),
+                    old.record,
+                    seq_no_offset + operation_id + 1,
+                ),
+            },
+        };
+        events.push(event);
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests;
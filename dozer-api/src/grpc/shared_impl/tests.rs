@@ -0,0 +1,39 @@
+use dozer_cache::cache::{LogOperation, RecordWithId};
+use dozer_types::grpc_types::types::OperationType;
+use dozer_types::types::{Field, Record};
+
+use super::map_log_operations;
+
+fn record(id: u64, value: i64) -> RecordWithId {
+    RecordWithId::new(id, Record::new(None, vec![Field::Int(value)], Some(1)))
+}
+
+#[test]
+fn test_map_log_operations() {
+    let operations = vec![
+        (0, LogOperation::Insert(record(0, 1))),
+        (1, LogOperation::Insert(record(1, 2))),
+        (2, LogOperation::Delete(record(0, 1))),
+        (3, LogOperation::Insert(record(0, 3))),
+        (4, LogOperation::Delete(record(1, 2))),
+        (5, LogOperation::Insert(record(2, 4))),
+    ];
+    let events = map_log_operations("films", 10, operations);
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| (event.typ, event.seq_no))
+            .collect::<Vec<_>>(),
+        vec![
+            (OperationType::Insert as i32, 11),
+            (OperationType::Insert as i32, 12),
+            (OperationType::Update as i32, 14),
+            (OperationType::Delete as i32, 15),
+            (OperationType::Insert as i32, 16),
+        ]
+    );
+    assert!(events.iter().all(|event| event.endpoint_name == "films"));
+    assert_eq!(events[1].new_id, Some(1));
+    assert!(events[2].old.is_some());
+    assert!(events[2].new.is_some());
+}
@@ -37,6 +37,11 @@ pub fn on_event_to_typed_response(
         event.try_set_field(&event_desc.new_id_field, prost_reflect::Value::U64(new_id))?;
     }
 
+    event.try_set_field(
+        &event_desc.seq_no_field,
+        prost_reflect::Value::U64(op.seq_no),
+    )?;
+
     Ok(TypedResponse::new(event))
 }
 
@@ -90,11 +95,13 @@ fn interval_value_to_pb(
             let lo_field_desc = &descriptor.decimal_field.lo;
             let mid_field_desc = &descriptor.decimal_field.mid;
             let hi_field_desc = &descriptor.decimal_field.hi;
+            let scale_field_desc = &descriptor.decimal_field.scale;
             let mut decimal = DynamicMessage::new(decimal_type_desc);
             decimal.set_field(flags_field_desc, prost_reflect::Value::U32(d.flags));
             decimal.set_field(lo_field_desc, prost_reflect::Value::U32(d.lo));
             decimal.set_field(mid_field_desc, prost_reflect::Value::U32(d.mid));
             decimal.set_field(hi_field_desc, prost_reflect::Value::U32(d.hi));
+            decimal.set_field(scale_field_desc, prost_reflect::Value::U32(d.scale));
             Value::Message(decimal)
         }
         GrpcTypes::value::Value::TimestampValue(ts) => Value::Message(ts.transcode_to_dynamic()),
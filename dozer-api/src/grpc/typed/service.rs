@@ -185,7 +185,7 @@ impl TypedService {
                     fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
                         future::ready(on_event(
                             request,
-                            &self.cache_endpoint.cache_reader(),
+                            Arc::clone(&self.cache_endpoint.cache_reader()),
                             &self.cache_endpoint.endpoint.name,
                             self.event_desc
                                 .take()
@@ -334,7 +334,7 @@ fn query(
 
 fn on_event(
     request: Request<DynamicMessage>,
-    reader: &CacheReader,
+    reader: Arc<CacheReader>,
     endpoint_name: &str,
     event_desc: EventDesc,
     event_notifier: Option<tokio::sync::broadcast::Receiver<Operation>>,
@@ -352,21 +352,33 @@ fn on_event(
                 .ok_or_else(|| Status::new(Code::InvalidArgument, "filter must be a string"))
         })
         .transpose()?;
+    let resume_from = if query_request.has_field_by_name("resume_from") {
+        let resume_from = query_request.get_field_by_name("resume_from");
+        Some(
+            resume_from
+                .as_ref()
+                .and_then(|resume_from| resume_from.as_u64())
+                .ok_or_else(|| Status::new(Code::InvalidArgument, "resume_from must be a u64"))?,
+        )
+    } else {
+        None
+    };
 
-    let endpoint_to_be_streamed = endpoint_name.to_string();
-    shared_impl::on_event(reader, filter, event_notifier, access.cloned(), move |op| {
-        if endpoint_to_be_streamed == op.endpoint_name {
-            match on_event_to_typed_response(op, event_desc.clone()) {
-                Ok(event) => Some(Ok(event)),
-                Err(e) => {
-                    error!("On event error: {:?}", e);
-                    None
-                }
+    shared_impl::on_event(
+        reader,
+        endpoint_name.to_string(),
+        filter,
+        resume_from,
+        event_notifier,
+        access.cloned(),
+        move |op| match on_event_to_typed_response(op, event_desc.clone()) {
+            Ok(event) => Some(Ok(event)),
+            Err(e) => {
+                error!("On event error: {:?}", e);
+                None
             }
-        } else {
-            None
-        }
-    })
+        },
+    )
 }
 
 fn token(
@@ -21,28 +21,31 @@ impl InternalPipelineService for FakeInternalPipelineServer {
         _request: tonic::Request<OperationsRequest>,
     ) -> Result<Response<OperationsStream>, Status> {
         let (tx, rx) = tokio::sync::mpsc::channel(1000);
-        thread::spawn(move || loop {
-            thread::sleep(time::Duration::from_millis(100));
-            let op = Operation {
-                typ: OperationType::Insert as i32,
-                old: None,
-                new: Some(Record {
-                    values: vec![
-                        Value {
-                            value: Some(value::Value::UintValue(32)),
-                        },
-                        Value {
-                            value: Some(value::Value::StringValue("description".to_string())),
-                        },
-                        Value { value: None },
-                        Value { value: None },
-                    ],
-                    version: 1,
-                }),
-                new_id: Some(0),
-                endpoint_name: "films".to_string(),
-            };
-            tx.try_send(Ok(op)).unwrap();
+        thread::spawn(move || {
+            for seq_no in 1.. {
+                thread::sleep(time::Duration::from_millis(100));
+                let op = Operation {
+                    typ: OperationType::Insert as i32,
+                    old: None,
+                    new: Some(Record {
+                        values: vec![
+                            Value {
+                                value: Some(value::Value::UintValue(32)),
+                            },
+                            Value {
+                                value: Some(value::Value::StringValue("description".to_string())),
+                            },
+                            Value { value: None },
+                            Value { value: None },
+                        ],
+                        version: 1,
+                    }),
+                    new_id: Some(0),
+                    endpoint_name: "films".to_string(),
+                    seq_no,
+                };
+                tx.try_send(Ok(op)).unwrap();
+            }
         });
         let output_stream = ReceiverStream::new(rx);
         Ok(Response::new(Box::pin(output_stream)))
@@ -10,7 +10,10 @@ use crate::{
     },
     RoCacheEndpoint,
 };
-use dozer_cache::cache::expression::{FilterExpression, QueryExpression};
+use dozer_cache::{
+    cache::expression::{FilterExpression, QueryExpression},
+    AccessFilter,
+};
 use dozer_types::grpc_types::{
     generated::films::FilmEventRequest,
     generated::films::{
@@ -21,7 +24,7 @@ use dozer_types::grpc_types::{
 };
 use dozer_types::models::{api_config::default_api_config, api_security::ApiSecurity};
 use futures_util::FutureExt;
-use std::{env, str::FromStr, sync::Arc, time::Duration};
+use std::{collections::HashMap, env, str::FromStr, sync::Arc, time::Duration};
 
 use crate::test_utils;
 use tokio::{
@@ -244,6 +247,7 @@ async fn test_typed_streaming1() {
     let request = FilmEventRequest {
         r#type: EventType::All as i32,
         filter: None,
+        resume_from: None,
     };
     let stream = client
         .on_event(Request::new(request))
@@ -281,6 +285,7 @@ async fn test_typed_streaming2() {
     let request = FilmEventRequest {
         r#type: EventType::All as i32,
         filter: Some(r#"{ "film_id": 32 }"#.into()),
+        resume_from: None,
     };
     let mut client = FilmsClient::connect(address.to_owned()).await.unwrap();
     let stream = client
@@ -320,6 +325,7 @@ async fn test_typed_streaming3() {
     let request = FilmEventRequest {
         r#type: EventType::All as i32,
         filter: Some(r#"{ "film_id": 0 }"#.into()),
+        resume_from: None,
     };
     let mut stream = client
         .on_event(Request::new(request))
@@ -330,3 +336,81 @@ async fn test_typed_streaming3() {
     assert!(error_timeout.is_err() || error_timeout.unwrap().is_none());
     _ = sender_shutdown_internal.send(());
 }
+
+#[tokio::test]
+async fn test_typed_streaming_with_access_filter() {
+    let (sender_shutdown_internal, rx_internal) = oneshot::channel::<()>();
+    let default_pipeline_internal = default_api_config().app_grpc.unwrap_or_default();
+    let _jh1 = tokio::spawn(start_fake_internal_grpc_pipeline(
+        default_pipeline_internal.host,
+        default_pipeline_internal.port,
+        rx_internal,
+    ));
+    let api_security = ApiSecurity::Jwt("DXkzrlnTy6".to_owned());
+    let layer = tower::ServiceBuilder::new()
+        .layer(AuthMiddlewareLayer::new(Some(api_security.clone())))
+        .into_inner();
+    let typed_service = setup_typed_service(Some(api_security.clone())).await;
+    let (_tx, rx) = oneshot::channel::<()>();
+    let _jh = tokio::spawn(async move {
+        Server::builder()
+            .layer(layer)
+            .add_service(typed_service)
+            .serve_with_shutdown("127.0.0.1:14324".parse().unwrap(), rx.map(drop))
+            .await
+            .unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(1001)).await;
+    let channel = Endpoint::from_str("http://127.0.0.1:14324")
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+
+    let authorizer = Authorizer::from(&api_security);
+    let on_event = |film_id: u64| {
+        let access = Access::Custom(HashMap::from([(
+            "get_records".to_string(),
+            AccessFilter {
+                filter: Some(FilterExpression::Simple(
+                    "film_id".to_string(),
+                    dozer_cache::cache::expression::Operator::EQ,
+                    dozer_types::serde_json::Value::from(film_id),
+                )),
+                fields: vec!["film_id".to_string()],
+            },
+        )]));
+        let token = authorizer.generate_token(access, None).unwrap();
+        let mut client =
+            FilmsClient::with_interceptor(channel.clone(), move |mut req: Request<()>| {
+                let token: MetadataValue<_> = format!("Bearer {token:}").parse().unwrap();
+                req.metadata_mut().insert("authorization", token);
+                Ok(req)
+            });
+        async move {
+            client
+                .on_event(Request::new(FilmEventRequest {
+                    r#type: EventType::All as i32,
+                    filter: None,
+                    resume_from: None,
+                }))
+                .await
+                .unwrap()
+                .into_inner()
+        }
+    };
+
+    // Fields outside the access filter are hidden.
+    let mut stream = on_event(32).await;
+    let response: FilmEvent = stream.next().await.unwrap().unwrap();
+    let film = response.new.unwrap();
+    assert_eq!(film.film_id, 32);
+    assert_eq!(film.description, None);
+    drop(stream);
+
+    // Records outside the access filter are not streamed.
+    let mut stream = on_event(0).await;
+    let error_timeout = timeout(Duration::from_secs(1), stream.next()).await;
+    assert!(error_timeout.is_err() || error_timeout.unwrap().is_none());
+    _ = sender_shutdown_internal.send(());
+}
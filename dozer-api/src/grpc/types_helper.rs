@@ -8,23 +8,30 @@ use dozer_types::grpc_types::types::{
     value, Operation, OperationType, PointType, Record, RecordWithId, RustDecimal, Type, Value,
 };
 
-pub fn map_insert_operation(endpoint_name: String, record: DozerRecord, id: u64) -> Operation {
+pub fn map_insert_operation(
+    endpoint_name: String,
+    record: DozerRecord,
+    id: u64,
+    seq_no: u64,
+) -> Operation {
     Operation {
         typ: OperationType::Insert as i32,
         old: None,
         new: Some(record_to_internal_record(record)),
         new_id: Some(id),
         endpoint_name,
+        seq_no,
     }
 }
 
-pub fn map_delete_operation(endpoint_name: String, record: DozerRecord) -> Operation {
+pub fn map_delete_operation(endpoint_name: String, record: DozerRecord, seq_no: u64) -> Operation {
     Operation {
         typ: OperationType::Delete as i32,
         old: None,
         new: Some(record_to_internal_record(record)),
         new_id: None,
         endpoint_name,
+        seq_no,
     }
 }
 
@@ -32,6 +39,7 @@ pub fn map_update_operation(
     endpoint_name: String,
     old: DozerRecord,
     new: DozerRecord,
+    seq_no: u64,
 ) -> Operation {
     Operation {
         typ: OperationType::Update as i32,
@@ -39,6 +47,7 @@ pub fn map_update_operation(
         new: Some(record_to_internal_record(new)),
         new_id: None,
         endpoint_name,
+        seq_no,
     }
 }
 
@@ -77,6 +86,7 @@ fn map_decimal(d: Decimal) -> Value {
             lo: d.unpack().lo,
             mid: d.unpack().mid,
             hi: d.unpack().hi,
+            scale: d.scale(),
         })),
     }
 }
@@ -123,6 +133,9 @@ fn field_to_prost_value(f: Field) -> Value {
             )),
         },
         Field::Point(point) => map_x_y_to_prost_coord_map(point.0.x_y()),
+        Field::Interval(interval) => Value {
+            value: Some(value::Value::StringValue(interval.to_string())),
+        },
     }
 }
 
@@ -153,5 +166,6 @@ fn field_type_to_internal_type(typ: FieldType) -> Type {
         FieldType::Bson => Type::Bson,
         FieldType::Date => Type::String,
         FieldType::Point => Type::Point,
+        FieldType::Interval => Type::String,
     }
 }
@@ -1,21 +1,32 @@
+use std::convert::Infallible;
 use std::sync::Arc;
 
-use actix_web::web::ReqData;
-use actix_web::{web, HttpResponse};
+use actix_web::web::{Bytes, ReqData};
+use actix_web::{rt, web, HttpRequest, HttpResponse};
+use actix_ws::Message;
 use dozer_cache::cache::expression::{default_limit_for_query, QueryExpression, Skip};
 use dozer_cache::cache::{index, RecordWithId};
 use dozer_cache::CacheReader;
-use dozer_types::chrono::SecondsFormat;
+use dozer_types::chrono::{NaiveDate, SecondsFormat, TimeZone, Utc};
 use dozer_types::errors::types::TypeError;
+use dozer_types::grpc_types::types::{
+    value, Operation, OperationType, Record as GrpcRecord, Value as GrpcValue,
+};
 use dozer_types::indexmap::IndexMap;
 use dozer_types::log::info;
 use dozer_types::models::api_endpoint::ApiEndpoint;
 use dozer_types::ordered_float::OrderedFloat;
-use dozer_types::types::{Field, Schema, DATE_FORMAT};
+use dozer_types::rust_decimal::Decimal;
+use dozer_types::serde::Deserialize;
+use dozer_types::types::{DozerPoint, Field, FieldType, Schema, DATE_FORMAT};
+use futures_util::StreamExt;
 use openapiv3::OpenAPI;
+use tokio::sync::broadcast::Receiver;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::api_helper::{get_record, get_records, get_records_count};
 use crate::generator::oapi::generator::OpenApiGenerator;
+use crate::grpc::shared_impl;
 use crate::RoCacheEndpoint;
 use crate::{auth::Access, errors::ApiError};
 use dozer_types::grpc_types::health::health_check_response::ServingStatus;
@@ -139,6 +150,117 @@ pub async fn query(
         .map(|maps| HttpResponse::Ok().json(maps))
 }
 
+#[derive(Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct EventsQuery {
+    /// Same filter JSON as `OnEvent`.
+    filter: Option<String>,
+    /// `seq_no` of the last event the client received.
+    resume_from: Option<u64>,
+}
+
+// Generated events function to stream changes as Server-Sent Events
+pub async fn events(
+    req: HttpRequest,
+    access: Option<ReqData<Access>>,
+    cache_endpoint: ReqData<Arc<RoCacheEndpoint>>,
+    event_notifier: web::Data<Option<Receiver<Operation>>>,
+    query: web::Query<EventsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    // `EventSource` sends the id of the last event it received when it reconnects.
+    let last_event_id = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|id| id.to_str().ok()?.parse().ok());
+    let events = event_stream(
+        access,
+        cache_endpoint,
+        event_notifier,
+        query.filter.as_deref(),
+        last_event_id.or(query.resume_from),
+    )?;
+
+    let events = events.map(|event| {
+        Ok::<_, Infallible>(Bytes::from(format!(
+            "id: {}\ndata: {}\n\n",
+            event["seq_no"], event
+        )))
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(events))
+}
+
+// Generated events function to stream changes over a WebSocket, one text message per event
+pub async fn events_ws(
+    req: HttpRequest,
+    body: web::Payload,
+    access: Option<ReqData<Access>>,
+    cache_endpoint: ReqData<Arc<RoCacheEndpoint>>,
+    event_notifier: web::Data<Option<Receiver<Operation>>>,
+    query: web::Query<EventsQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let (response, mut session, mut messages) = actix_ws::handle(&req, body)?;
+    let mut events = event_stream(
+        access,
+        cache_endpoint,
+        event_notifier,
+        query.filter.as_deref(),
+        query.resume_from,
+    )?;
+
+    rt::spawn(async move {
+        loop {
+            tokio::select! {
+                event = events.next() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    if session.text(event.to_string()).await.is_err() {
+                        return;
+                    }
+                }
+                message = messages.next() => match message {
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
+
+/// Subscribes to the endpoint's events, after replaying the ones after `resume_from`.
+fn event_stream(
+    access: Option<ReqData<Access>>,
+    cache_endpoint: ReqData<Arc<RoCacheEndpoint>>,
+    event_notifier: web::Data<Option<Receiver<Operation>>>,
+    filter: Option<&str>,
+    resume_from: Option<u64>,
+) -> Result<ReceiverStream<Value>, ApiError> {
+    let reader = Arc::clone(&cache_endpoint.cache_reader());
+    let schema = reader.get_schema().0.clone();
+    shared_impl::on_event(
+        reader,
+        cache_endpoint.endpoint.name.clone(),
+        filter,
+        resume_from,
+        Option::as_ref(&event_notifier).map(Receiver::resubscribe),
+        access.map(|a| a.into_inner()),
+        move |op| Some(operation_to_json(op, &schema)),
+    )
+    .map(|response| response.into_inner())
+    .map_err(ApiError::EventStreamFailed)
+}
+
 /// Get multiple records
 fn get_records_map(
     access: Option<ReqData<Access>>,
@@ -177,6 +299,71 @@ fn record_to_map(
     Ok(map)
 }
 
+/// Used in REST event streams. Records have the same keys as `record_to_map`, except the record id.
+fn operation_to_json(op: Operation, schema: &Schema) -> Value {
+    let typ = OperationType::from_i32(op.typ).map_or("", |typ| typ.as_str_name());
+    json!({
+        "typ": typ,
+        "old": op.old.map(|record| event_record_to_map(record, schema)),
+        "new": op.new.map(|record| event_record_to_map(record, schema)),
+        "new_id": op.new_id,
+        "seq_no": op.seq_no,
+    })
+}
+
+fn event_record_to_map(record: GrpcRecord, schema: &Schema) -> IndexMap<String, Value> {
+    let mut map = IndexMap::new();
+
+    for (field_def, value) in schema.fields.iter().zip(record.values) {
+        let field = event_value_to_field(value, field_def.typ);
+        map.insert(field_def.name.clone(), field_to_json_value(field));
+    }
+
+    map.insert(
+        "__dozer_record_version".to_string(),
+        Value::from(record.version),
+    );
+
+    map
+}
+
+/// Recovers the field an event value was mapped from, so events are converted to JSON the same way as records.
+///
+/// Should be consistent with `field_to_prost_value`.
+fn event_value_to_field(value: GrpcValue, typ: FieldType) -> Field {
+    match value.value {
+        Some(value::Value::UintValue(n)) => Field::UInt(n),
+        Some(value::Value::IntValue(n)) => Field::Int(n),
+        Some(value::Value::FloatValue(n)) => Field::Float(OrderedFloat(n)),
+        Some(value::Value::BoolValue(b)) => Field::Boolean(b),
+        Some(value::Value::StringValue(s)) | Some(value::Value::DateValue(s)) => match typ {
+            FieldType::Text => Field::Text(s),
+            FieldType::Date => {
+                NaiveDate::parse_from_str(&s, DATE_FORMAT).map_or(Field::String(s), Field::Date)
+            }
+            FieldType::Interval => s.parse().map_or(Field::String(s), Field::Interval),
+            _ => Field::String(s),
+        },
+        Some(value::Value::BytesValue(b)) => match typ {
+            FieldType::Bson => Field::Bson(b),
+            _ => Field::Binary(b),
+        },
+        Some(value::Value::DecimalValue(d)) => Field::Decimal(Decimal::from_parts(
+            d.lo,
+            d.mid,
+            d.hi,
+            d.flags != 0,
+            d.scale,
+        )),
+        Some(value::Value::TimestampValue(ts)) => Utc
+            .timestamp_opt(ts.seconds, ts.nanos as u32)
+            .single()
+            .map_or(Field::Null, |ts| Field::Timestamp(ts.into())),
+        Some(value::Value::PointValue(point)) => Field::Point(DozerPoint::from((point.x, point.y))),
+        None => Field::Null,
+    }
+}
+
 fn convert_x_y_to_object((x, y): &(OrderedFloat<f64>, OrderedFloat<f64>)) -> Value {
     let mut m = Map::new();
     m.insert("x".to_string(), Value::from(x.0));
@@ -201,6 +388,7 @@ fn field_to_json_value(field: Field) -> Value {
         Field::Date(n) => Value::String(n.format(DATE_FORMAT).to_string()),
         Field::Bson(b) => Value::from(b),
         Field::Point(point) => convert_x_y_to_object(&point.0.x_y()),
+        Field::Interval(interval) => Value::String(interval.to_string()),
         Field::Null => Value::Null,
     }
 }
@@ -212,9 +400,13 @@ mod tests {
         json_value_to_field,
         ordered_float::OrderedFloat,
         rust_decimal::Decimal,
-        types::{DozerPoint, Field, FieldType},
+        types::{
+            DozerInterval, DozerPoint, Field, FieldDefinition, FieldType, Record, SourceDefinition,
+        },
     };
 
+    use crate::grpc::types_helper;
+
     use super::*;
 
     fn test_field_conversion(field_type: FieldType, field: Field) {
@@ -227,9 +419,8 @@ mod tests {
         assert_eq!(deserialized, field, "must be equal");
     }
 
-    #[test]
-    fn test_field_types_json_conversion() {
-        let fields = vec![
+    fn typed_fields() -> Vec<(FieldType, Field)> {
+        vec![
             (FieldType::Int, Field::Int(-1)),
             (FieldType::UInt, Field::UInt(1)),
             (FieldType::Float, Field::Float(OrderedFloat(1.1))),
@@ -257,9 +448,52 @@ mod tests {
                 FieldType::Point,
                 Field::Point(DozerPoint::from((3.234, 4.567))),
             ),
-        ];
-        for (field_type, field) in fields {
+            (
+                FieldType::Interval,
+                Field::Interval(DozerInterval::new(1, 2, 3_500_000_000)),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_field_types_json_conversion() {
+        for (field_type, field) in typed_fields() {
             test_field_conversion(field_type, field);
         }
     }
+
+    #[test]
+    fn test_event_json_conversion() {
+        let (fields, values): (Vec<_>, Vec<_>) = typed_fields()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (typ, field))| {
+                let definition = FieldDefinition::new(
+                    format!("field_{index}"),
+                    typ,
+                    false,
+                    SourceDefinition::Dynamic,
+                );
+                (definition, field)
+            })
+            .unzip();
+        let schema = Schema {
+            identifier: None,
+            fields,
+            primary_index: vec![],
+        };
+        let event = types_helper::map_insert_operation(
+            "films".to_string(),
+            Record::new(None, values.clone(), Some(1)),
+            0,
+            1,
+        );
+
+        // Events have the same JSON values as records.
+        let json = operation_to_json(event, &schema);
+        for (definition, field) in schema.fields.iter().zip(values) {
+            assert_eq!(json["new"][&definition.name], field_to_json_value(field));
+        }
+        assert_eq!(json["new"]["field_6"], "2.02");
+    }
 }
@@ -17,9 +17,11 @@ use actix_web::{
 use actix_web_httpauth::middleware::HttpAuthentication;
 use dozer_types::{crossbeam::channel::Sender, log::info, models::api_config::RestApiOptions};
 use dozer_types::{
+    grpc_types::types::Operation,
     models::api_security::ApiSecurity,
     serde::{self, Deserialize, Serialize},
 };
+use tokio::sync::broadcast::Receiver;
 use tracing_actix_web::TracingLogger;
 
 mod api_generator;
@@ -76,6 +78,7 @@ impl ApiServer {
         security: Option<ApiSecurity>,
         cors: CorsOptions,
         cache_endpoints: Vec<Arc<RoCacheEndpoint>>,
+        event_notifier: Option<Receiver<Operation>>,
     ) -> App<
         impl ServiceFactory<
             ServiceRequest,
@@ -87,7 +90,9 @@ impl ApiServer {
     > {
         let mut app = App::new()
             .wrap(Logger::default())
-            .wrap(TracingLogger::default());
+            .wrap(TracingLogger::default())
+            // Events are only available when the pipeline streams them.
+            .app_data(web::Data::new(event_notifier));
 
         let is_auth_configured = if let Some(api_security) = security {
             // Injecting API Security
@@ -116,6 +121,9 @@ impl ApiServer {
                         .route("/count", web::post().to(api_generator::count))
                         .route("/query", web::post().to(api_generator::query))
                         .route("/oapi", web::post().to(api_generator::generate_oapi))
+                        // Must come before `/{id}`, which would match it.
+                        .route("/events", web::get().to(api_generator::events))
+                        .route("/events/ws", web::get().to(api_generator::events_ws))
                         .route("/{id}", web::get().to(api_generator::get))
                         .route("/", web::get().to(api_generator::list))
                         .route("", web::get().to(api_generator::list)),
@@ -134,6 +142,7 @@ impl ApiServer {
     pub async fn run(
         &self,
         cache_endpoints: Vec<Arc<RoCacheEndpoint>>,
+        event_notifier: Option<Receiver<Operation>>,
         tx: Sender<ServerHandle>,
     ) -> Result<(), ApiError> {
         info!(
@@ -149,8 +158,15 @@ impl ApiServer {
         let cors = self.cors.clone();
         let security = self.security.clone();
         let address = format!("{}:{}", self.host, self.port);
+        // Every worker gets its own app, so it resubscribes to the event notifier.
+        let event_notifier = Arc::new(event_notifier);
         let server = HttpServer::new(move || {
-            ApiServer::create_app_entry(security.clone(), cors.clone(), cache_endpoints.clone())
+            ApiServer::create_app_entry(
+                security.clone(),
+                cors.clone(),
+                cache_endpoints.clone(),
+                Option::as_ref(&event_notifier).map(Receiver::resubscribe),
+            )
         })
         .bind(&address)
         .map_err(|e| ApiError::FailedToBindToAddress(address, e))?
@@ -72,6 +72,7 @@ async fn check_status(
         vec![Arc::new(
             RoCacheEndpoint::new(&*cache_manager, endpoint.clone()).unwrap(),
         )],
+        None,
     );
     let app = actix_web::test::init_service(api_server).await;
 
@@ -100,6 +101,7 @@ async fn _call_auth_token_api(
         vec![Arc::new(
             RoCacheEndpoint::new(&*cache_manager, endpoint).unwrap(),
         )],
+        None,
     );
     let app = actix_web::test::init_service(api_server).await;
 
@@ -2,9 +2,16 @@ use std::{fmt::Debug, sync::Arc};
 
 use super::super::{ApiServer, CorsOptions};
 use crate::{generator::oapi::generator::OpenApiGenerator, test_utils, RoCacheEndpoint};
-use actix_http::{body::MessageBody, Request};
-use actix_web::dev::{Service, ServiceResponse};
-use dozer_types::serde_json::{json, Value};
+use actix_http::{body::MessageBody, error::PayloadError, Payload, Request};
+use actix_web::{
+    dev::{Service, ServiceResponse},
+    http::StatusCode,
+    web::Bytes,
+};
+use dozer_types::grpc_types::types::Operation;
+use dozer_types::serde_json::{self, json, Value};
+use futures_util::stream;
+use tokio::sync::broadcast;
 
 #[test]
 fn test_generate_oapi() {
@@ -32,6 +39,7 @@ async fn list_route() {
         vec![Arc::new(
             RoCacheEndpoint::new(&*cache_manager, endpoint.clone()).unwrap(),
         )],
+        None,
     );
     let app = actix_web::test::init_service(api_server).await;
 
@@ -91,6 +99,7 @@ async fn count_and_query_route() {
         vec![Arc::new(
             RoCacheEndpoint::new(&*cache_manager, endpoint.clone()).unwrap(),
         )],
+        None,
     );
     let app = actix_web::test::init_service(api_server).await;
 
@@ -136,6 +145,7 @@ async fn get_route() {
         vec![Arc::new(
             RoCacheEndpoint::new(&*cache_manager, endpoint.clone()).unwrap(),
         )],
+        None,
     );
     let app = actix_web::test::init_service(api_server).await;
     let req = actix_web::test::TestRequest::get()
@@ -154,3 +164,124 @@ async fn get_route() {
         "Must be equal"
     );
 }
+
+#[actix_web::test]
+async fn events_route() {
+    let endpoint = test_utils::get_endpoint();
+    let cache_manager = test_utils::initialize_cache(&endpoint.name, None);
+    // The sender is dropped, so streams end after replaying the operation log.
+    let (_, event_notifier) = broadcast::channel::<Operation>(16);
+    let api_server = ApiServer::create_app_entry(
+        None,
+        CorsOptions::Permissive,
+        vec![Arc::new(
+            RoCacheEndpoint::new(&*cache_manager, endpoint.clone()).unwrap(),
+        )],
+        Some(event_notifier),
+    );
+    let app = actix_web::test::init_service(api_server).await;
+
+    // The cache has 52 inserts.
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("{}/events?resume_from=50", endpoint.path))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert!(res.status().is_success());
+    let body = actix_web::test::read_body(res).await;
+    let events = std::str::from_utf8(&body)
+        .unwrap()
+        .split_terminator("\n\n")
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 2);
+    assert!(events[0].starts_with("id: 51\ndata: "));
+    let event: Value =
+        serde_json::from_str(events[1].strip_prefix("id: 52\ndata: ").unwrap()).unwrap();
+    assert_eq!(event["typ"], "INSERT");
+    assert_eq!(event["seq_no"], 52);
+    assert!(event["new"]["film_id"].is_u64());
+
+    // `Last-Event-ID` takes precedence over `resume_from`.
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("{}/events?resume_from=50", endpoint.path))
+        .append_header(("Last-Event-ID", "51"))
+        .to_request();
+    let body = actix_web::test::call_and_read_body(&app, req).await;
+    assert!(std::str::from_utf8(&body).unwrap().starts_with("id: 52\n"));
+
+    // Resuming beyond the operation log is a bad request.
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("{}/events?resume_from=1000", endpoint.path))
+        .to_request();
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn events_ws_route() {
+    let endpoint = test_utils::get_endpoint();
+    let cache_manager = test_utils::initialize_cache(&endpoint.name, None);
+    // The sender is dropped, so the socket is closed after replaying the operation log.
+    let (_, event_notifier) = broadcast::channel::<Operation>(16);
+    let api_server = ApiServer::create_app_entry(
+        None,
+        CorsOptions::Permissive,
+        vec![Arc::new(
+            RoCacheEndpoint::new(&*cache_manager, endpoint.clone()).unwrap(),
+        )],
+        Some(event_notifier),
+    );
+    let app = actix_web::test::init_service(api_server).await;
+
+    let mut req = actix_web::test::TestRequest::get()
+        .uri(&format!("{}/events/ws?resume_from=50", endpoint.path))
+        .insert_header(("Upgrade", "websocket"))
+        .insert_header(("Connection", "Upgrade"))
+        .insert_header(("Sec-WebSocket-Version", "13"))
+        .insert_header(("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+        .to_request();
+    // Keep the client side open, so the socket is only closed once the events end.
+    *req.payload() = Payload::Stream {
+        payload: Box::pin(stream::pending::<Result<Bytes, PayloadError>>()),
+    };
+    let res = actix_web::test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+    // The cache has 52 inserts.
+    let body = actix_web::test::read_body(res).await;
+    let frames = server_frames(&body);
+    assert_eq!(frames.len(), 3);
+    let events = frames[..2]
+        .iter()
+        .map(|(opcode, payload)| {
+            assert_eq!(*opcode, TEXT_OPCODE);
+            serde_json::from_slice::<Value>(payload).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(events[0]["seq_no"], 51);
+    assert_eq!(events[1]["typ"], "INSERT");
+    assert_eq!(events[1]["seq_no"], 52);
+    assert!(events[1]["new"]["film_id"].is_u64());
+    assert_eq!(frames[2].0, CLOSE_OPCODE);
+}
+
+const TEXT_OPCODE: u8 = 0x1;
+const CLOSE_OPCODE: u8 = 0x8;
+
+/// Splits the body of a WebSocket response into the opcodes and payloads of its frames, which are not masked.
+fn server_frames(mut bytes: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut frames = vec![];
+    while !bytes.is_empty() {
+        let opcode = bytes[0] & 0x0f;
+        let (len, header_len) = match bytes[1] & 0x7f {
+            126 => (u16::from_be_bytes([bytes[2], bytes[3]]) as usize, 4),
+            127 => (
+                u64::from_be_bytes(bytes[2..10].try_into().unwrap()) as usize,
+                10,
+            ),
+            len => (len as usize, 2),
+        };
+        frames.push((opcode, &bytes[header_len..header_len + len]));
+        bytes = &bytes[header_len + len..];
+    }
+    frames
+}
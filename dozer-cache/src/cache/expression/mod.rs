@@ -2,6 +2,9 @@ use dozer_types::serde::{Deserialize, Serialize};
 use dozer_types::serde_json::Value;
 mod query_helper;
 mod query_serde;
+mod spatial;
+
+pub use spatial::SpatialFilter;
 
 #[cfg(test)]
 mod tests;
@@ -77,6 +80,8 @@ pub enum FilterExpression {
     // a = 1, a containts "s", a > 4
     Simple(String, Operator, Value),
     And(Vec<FilterExpression>),
+    Or(Vec<FilterExpression>),
+    Not(Box<FilterExpression>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -98,29 +103,52 @@ pub enum Operator {
     MatchesAny,
     #[serde(rename = "$matches_all")]
     MatchesAll,
+    /// Points within `radius` meters of `point`, like `{"point": {"x": -73.98, "y": 40.75}, "radius": 5000}`.
+    #[serde(rename = "$near")]
+    Near,
+    /// Points within the bounding box from `min` to `max`, like `{"min": {"x": -74, "y": 40.6}, "max": {"x": -73.8, "y": 40.9}}`.
+    #[serde(rename = "$within")]
+    Within,
 }
 
 impl Operator {
     pub fn supported_by_sorted_inverted(&self) -> bool {
         match self {
             Operator::LT | Operator::LTE | Operator::EQ | Operator::GT | Operator::GTE => true,
-            Operator::Contains | Operator::MatchesAny | Operator::MatchesAll => false,
+            Operator::Contains
+            | Operator::MatchesAny
+            | Operator::MatchesAll
+            | Operator::Near
+            | Operator::Within => false,
         }
     }
 
     pub fn supported_by_full_text(&self) -> bool {
         match self {
-            Operator::LT | Operator::LTE | Operator::EQ | Operator::GT | Operator::GTE => false,
+            Operator::LT
+            | Operator::LTE
+            | Operator::EQ
+            | Operator::GT
+            | Operator::GTE
+            | Operator::Near
+            | Operator::Within => false,
             Operator::Contains | Operator::MatchesAny | Operator::MatchesAll => true,
         }
     }
 
+    pub fn supported_by_spatial(&self) -> bool {
+        matches!(self, Operator::Near | Operator::Within)
+    }
+
     pub fn is_range_operator(&self) -> bool {
         match self {
             Operator::LT | Operator::LTE | Operator::GT | Operator::GTE => true,
-            Operator::EQ | Operator::Contains | Operator::MatchesAny | Operator::MatchesAll => {
-                false
-            }
+            Operator::EQ
+            | Operator::Contains
+            | Operator::MatchesAny
+            | Operator::MatchesAll
+            | Operator::Near
+            | Operator::Within => false,
         }
     }
 }
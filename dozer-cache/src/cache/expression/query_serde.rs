@@ -29,6 +29,10 @@ impl<'de> Deserialize<'de> for FilterExpression {
                 while let Some(key) = map.next_key::<String>()? {
                     if key == "$and" {
                         expressions.push(FilterExpression::And(map.next_value()?));
+                    } else if key == "$or" {
+                        expressions.push(FilterExpression::Or(map.next_value()?));
+                    } else if key == "$not" {
+                        expressions.push(FilterExpression::Not(Box::new(map.next_value()?)));
                     } else {
                         let operator_and_value = map.next_value::<OperatorAndValue>()?;
                         expressions.push(FilterExpression::Simple(
@@ -65,6 +69,16 @@ impl Serialize for FilterExpression {
                 state.serialize_entry("$and", &expressions)?;
                 state.end()
             }
+            FilterExpression::Or(expressions) => {
+                let mut state = serializer.serialize_map(Some(1))?;
+                state.serialize_entry("$or", &expressions)?;
+                state.end()
+            }
+            FilterExpression::Not(expression) => {
+                let mut state = serializer.serialize_map(Some(1))?;
+                state.serialize_entry("$not", expression)?;
+                state.end()
+            }
         }
     }
 }
@@ -0,0 +1,163 @@
+use dozer_types::{
+    errors::types::{DeserializationError, TypeError},
+    geo::GeodesicDistance,
+    ordered_float::OrderedFloat,
+    serde::Deserialize,
+    serde_json::{self, Value},
+    types::DozerPoint,
+};
+
+use crate::cache::index::BoundingBox;
+
+use super::Operator;
+
+/// Meters in a degree of latitude, at the equator where it's the shortest.
+const MIN_METERS_PER_DEGREE_OF_LATITUDE: f64 = 110_574.0;
+/// Meters in a degree of longitude at the equator.
+const METERS_PER_DEGREE_OF_LONGITUDE_AT_EQUATOR: f64 = 111_319.0;
+/// Bounding boxes of `$near` are enlarged by this ratio, so they contain every point within the radius.
+const NEAR_BOUNDING_BOX_MARGIN: f64 = 1.01;
+
+/// The value of a `$near` or `$within` filter on a `Point` field. `x` is the longitude and `y` the latitude, in degrees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpatialFilter {
+    /// Points whose geodesic distance to `point` is at most `radius` meters.
+    Near {
+        point: DozerPoint,
+        radius: OrderedFloat<f64>,
+    },
+    /// Points within the box from `min`, its south west corner, to `max`, its north east corner.
+    /// The box crosses the antimeridian if `min.x` is greater than `max.x`.
+    Within { min: DozerPoint, max: DozerPoint },
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+struct NearValue {
+    point: DozerPoint,
+    radius: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+struct WithinValue {
+    min: DozerPoint,
+    max: DozerPoint,
+}
+
+impl SpatialFilter {
+    /// Parses the value of a filter whose operator is supported by spatial index.
+    pub fn new(operator: Operator, value: &Value) -> Result<Self, TypeError> {
+        let json_error = |e| TypeError::DeserializationError(DeserializationError::Json(e));
+        match operator {
+            Operator::Near => {
+                let NearValue { point, radius } =
+                    serde_json::from_value(value.clone()).map_err(json_error)?;
+                if !(radius >= 0.0 && radius.is_finite()) {
+                    return Err(TypeError::DeserializationError(
+                        DeserializationError::Custom(
+                            format!("`$near` radius must be a non-negative number, got {radius}")
+                                .into(),
+                        ),
+                    ));
+                }
+                Ok(SpatialFilter::Near {
+                    point,
+                    radius: OrderedFloat(radius),
+                })
+            }
+            Operator::Within => {
+                let WithinValue { min, max } =
+                    serde_json::from_value(value.clone()).map_err(json_error)?;
+                Ok(SpatialFilter::Within { min, max })
+            }
+            other => panic!("operator {other:?} is not supported by spatial index"),
+        }
+    }
+
+    pub fn matches(&self, point: &DozerPoint) -> bool {
+        match self {
+            SpatialFilter::Near {
+                point: center,
+                radius,
+            } => center.geodesic_distance(point) <= *radius,
+            SpatialFilter::Within { .. } => self
+                .bounding_boxes()
+                .iter()
+                .any(|bounding_box| bounding_box.contains(point)),
+        }
+    }
+
+    /// Returns boxes containing every point that matches the filter. A box crossing the antimeridian is split in two.
+    pub fn bounding_boxes(&self) -> Vec<BoundingBox> {
+        match self {
+            SpatialFilter::Near { point, radius } => {
+                let (x, y) = (point.0.x().0, point.0.y().0);
+                let delta_y =
+                    radius.0 / MIN_METERS_PER_DEGREE_OF_LATITUDE * NEAR_BOUNDING_BOX_MARGIN;
+                let (min_y, max_y) = (y - delta_y, y + delta_y);
+                if min_y <= -90.0 || max_y >= 90.0 {
+                    // The circle contains a pole, so it spans every longitude.
+                    return vec![BoundingBox {
+                        min_x: -180.0,
+                        min_y: min_y.max(-90.0),
+                        max_x: 180.0,
+                        max_y: max_y.min(90.0),
+                    }];
+                }
+
+                // Degrees of longitude are the shortest at the latitude that's the closest to a pole.
+                let max_abs_y = min_y.abs().max(max_y.abs());
+                let delta_x = radius.0
+                    / (METERS_PER_DEGREE_OF_LONGITUDE_AT_EQUATOR * max_abs_y.to_radians().cos())
+                    * NEAR_BOUNDING_BOX_MARGIN;
+                if delta_x >= 180.0 {
+                    return vec![BoundingBox {
+                        min_x: -180.0,
+                        min_y,
+                        max_x: 180.0,
+                        max_y,
+                    }];
+                }
+                split_at_antimeridian(x - delta_x, min_y, x + delta_x, max_y)
+            }
+            SpatialFilter::Within { min, max } => {
+                let (min_x, min_y) = (min.0.x().0, min.0.y().0);
+                let (max_x, max_y) = (max.0.x().0, max.0.y().0);
+                if min_x <= max_x {
+                    vec![BoundingBox {
+                        min_x,
+                        min_y,
+                        max_x,
+                        max_y,
+                    }]
+                } else {
+                    split_at_antimeridian(min_x, min_y, max_x + 360.0, max_y)
+                }
+            }
+        }
+    }
+}
+
+/// Splits a box whose longitudes may go beyond `[-180, 180]` into boxes within that range.
+fn split_at_antimeridian(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Vec<BoundingBox> {
+    let bounding_box = |min_x, max_x| BoundingBox {
+        min_x,
+        min_y,
+        max_x,
+        max_y,
+    };
+    if min_x < -180.0 {
+        vec![
+            bounding_box(min_x + 360.0, 180.0),
+            bounding_box(-180.0, max_x),
+        ]
+    } else if max_x > 180.0 {
+        vec![
+            bounding_box(min_x, 180.0),
+            bounding_box(-180.0, max_x - 360.0),
+        ]
+    } else {
+        vec![bounding_box(min_x, max_x)]
+    }
+}
@@ -22,6 +22,8 @@ fn test_operators() {
         (Operator::Contains, "$contains"),
         (Operator::MatchesAny, "$matches_any"),
         (Operator::MatchesAll, "$matches_all"),
+        (Operator::Near, "$near"),
+        (Operator::Within, "$within"),
     ];
     for (op, op_str) in operators {
         let fetched = serde_json::from_value(Value::String(op_str.to_string())).unwrap();
@@ -84,6 +86,14 @@ fn test_filter_query_deserialize_simple() {
         json!({ "a": null }),
         FilterExpression::Simple("a".to_string(), Operator::EQ, Value::Null),
     );
+    test_deserialize_filter(
+        json!({"a":  {"$near": {"point": {"x": 1.0, "y": 2.0}, "radius": 100}}}),
+        FilterExpression::Simple(
+            "a".to_string(),
+            Operator::Near,
+            json!({"point": {"x": 1.0, "y": 2.0}, "radius": 100}),
+        ),
+    );
 
     test_deserialize_filter_error(json!({"a":  []}));
     test_deserialize_filter_error(json!({"a":  {}}));
@@ -129,6 +139,38 @@ fn test_filter_query_deserialize_complex() {
     test_deserialize_filter_error(json!({"and": [{"a":  {"$lt": 1}}]}));
 }
 
+#[test]
+fn test_filter_query_deserialize_or_not() {
+    test_deserialize_filter(
+        json!({"$or": [{"a":  1}, {"b":  {"$gt": 2}}]}),
+        FilterExpression::Or(vec![
+            FilterExpression::Simple("a".to_string(), Operator::EQ, Value::from(1)),
+            FilterExpression::Simple("b".to_string(), Operator::GT, Value::from(2)),
+        ]),
+    );
+    test_deserialize_filter(
+        json!({"$not": {"a":  1}}),
+        FilterExpression::Not(Box::new(FilterExpression::Simple(
+            "a".to_string(),
+            Operator::EQ,
+            Value::from(1),
+        ))),
+    );
+    test_deserialize_filter(
+        json!({"a": 1, "$not": {"$or": [{"b": 2}, {"c": 3}]}}),
+        FilterExpression::And(vec![
+            FilterExpression::Simple("a".to_string(), Operator::EQ, Value::from(1)),
+            FilterExpression::Not(Box::new(FilterExpression::Or(vec![
+                FilterExpression::Simple("b".to_string(), Operator::EQ, Value::from(2)),
+                FilterExpression::Simple("c".to_string(), Operator::EQ, Value::from(3)),
+            ]))),
+        ]),
+    );
+
+    test_deserialize_filter_error(json!({"$or": {}}));
+    test_deserialize_filter_error(json!({"$not": []}));
+}
+
 #[test]
 fn test_sort_options_query_deserialize() {
     test_deserialize_sort_options(json!({}), vec![]);
@@ -1,2 +1,3 @@
 pub mod deserialize;
 mod serialize;
+mod spatial;
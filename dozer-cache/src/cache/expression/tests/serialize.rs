@@ -78,6 +78,18 @@ fn test_serialize_filter_complex() {
         json!({"$and":[{"a":  {"$lt": 1}}, {"b":  {"$gte": 3}}, {"c": 3}]}),
         three_fields,
     );
+
+    test_serialize_filter(
+        json!({"$or": [{"a": 1}, {"$not": {"b": {"$gt": 2}}}]}),
+        FilterExpression::Or(vec![
+            FilterExpression::Simple("a".to_string(), Operator::EQ, Value::from(1)),
+            FilterExpression::Not(Box::new(FilterExpression::Simple(
+                "b".to_string(),
+                Operator::GT,
+                Value::from(2),
+            ))),
+        ]),
+    );
 }
 
 #[test]
@@ -0,0 +1,114 @@
+use crate::cache::expression::{Operator, SpatialFilter};
+use crate::cache::index::BoundingBox;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::serde_json::json;
+use dozer_types::types::DozerPoint;
+
+#[test]
+fn test_spatial_filter_new() {
+    assert_eq!(
+        SpatialFilter::new(
+            Operator::Near,
+            &json!({"point": {"x": 1.0, "y": 2.0}, "radius": 100})
+        )
+        .unwrap(),
+        SpatialFilter::Near {
+            point: DozerPoint::from((1.0, 2.0)),
+            radius: OrderedFloat(100.0),
+        }
+    );
+    assert_eq!(
+        SpatialFilter::new(
+            Operator::Within,
+            &json!({"min": {"x": 1.0, "y": 2.0}, "max": {"x": 3.0, "y": 4.0}})
+        )
+        .unwrap(),
+        SpatialFilter::Within {
+            min: DozerPoint::from((1.0, 2.0)),
+            max: DozerPoint::from((3.0, 4.0)),
+        }
+    );
+    assert!(SpatialFilter::new(
+        Operator::Near,
+        &json!({"point": {"x": 1.0, "y": 2.0}, "radius": -1})
+    )
+    .is_err());
+    assert!(SpatialFilter::new(Operator::Within, &json!({"min": {"x": 1.0, "y": 2.0}})).is_err());
+}
+
+#[test]
+fn test_near_matches_points_within_radius() {
+    // The Empire State Building and Times Square are about 1 km apart.
+    let empire_state = DozerPoint::from((-73.9857, 40.7484));
+    let times_square = DozerPoint::from((-73.9855, 40.7580));
+    let near = |radius| SpatialFilter::Near {
+        point: empire_state,
+        radius: OrderedFloat(radius),
+    };
+
+    assert!(near(1500.0).matches(&times_square));
+    assert!(!near(500.0).matches(&times_square));
+    for radius in [500.0, 1500.0, 5_000_000.0] {
+        let filter = near(radius);
+        assert!(filter
+            .bounding_boxes()
+            .iter()
+            .any(|bounding_box| bounding_box.contains(&empire_state)));
+    }
+    assert!(near(1500.0)
+        .bounding_boxes()
+        .iter()
+        .any(|bounding_box| bounding_box.contains(&times_square)));
+}
+
+#[test]
+fn test_bounding_boxes_split_at_antimeridian() {
+    let near = SpatialFilter::Near {
+        point: DozerPoint::from((179.99, 0.0)),
+        radius: OrderedFloat(10_000.0),
+    };
+    let bounding_boxes = near.bounding_boxes();
+    assert_eq!(bounding_boxes.len(), 2);
+    assert!(near.matches(&DozerPoint::from((-179.99, 0.0))));
+    assert!(bounding_boxes
+        .iter()
+        .any(|bounding_box| bounding_box.contains(&DozerPoint::from((-179.99, 0.0)))));
+
+    let within = SpatialFilter::Within {
+        min: DozerPoint::from((170.0, -10.0)),
+        max: DozerPoint::from((-170.0, 10.0)),
+    };
+    assert_eq!(
+        within.bounding_boxes(),
+        vec![
+            BoundingBox {
+                min_x: 170.0,
+                min_y: -10.0,
+                max_x: 180.0,
+                max_y: 10.0,
+            },
+            BoundingBox {
+                min_x: -180.0,
+                min_y: -10.0,
+                max_x: -170.0,
+                max_y: 10.0,
+            },
+        ]
+    );
+    assert!(within.matches(&DozerPoint::from((175.0, 0.0))));
+    assert!(within.matches(&DozerPoint::from((-175.0, 0.0))));
+    assert!(!within.matches(&DozerPoint::from((0.0, 0.0))));
+}
+
+#[test]
+fn test_near_pole_spans_every_longitude() {
+    let near = SpatialFilter::Near {
+        point: DozerPoint::from((0.0, 89.99)),
+        radius: OrderedFloat(10_000.0),
+    };
+    let bounding_boxes = near.bounding_boxes();
+    assert_eq!(bounding_boxes.len(), 1);
+    assert_eq!(bounding_boxes[0].min_x, -180.0);
+    assert_eq!(bounding_boxes[0].max_x, 180.0);
+    assert_eq!(bounding_boxes[0].max_y, 90.0);
+}
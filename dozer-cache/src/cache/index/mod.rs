@@ -13,6 +13,13 @@ use dozer_types::types::Field;
 
 use crate::errors::CompareError;
 
+mod spatial;
+
+pub use spatial::{
+    decode_spatial_secondary_index, get_spatial_key_ranges, get_spatial_secondary_index,
+    BoundingBox,
+};
+
 pub fn get_primary_key(primary_index: &[usize], values: &[Field]) -> Vec<u8> {
     debug_assert!(
         !primary_index.is_empty(),
@@ -0,0 +1,216 @@
+//! Keys of the spatial secondary index.
+//!
+//! A point is keyed by the Morton code of its quantized coordinates, which is the integer form of its geohash,
+//! followed by its exact coordinates. Points close to each other share key prefixes, so a bounding box is covered by a few key ranges,
+//! and the exact coordinates let a scan drop the points of those ranges that are outside the box.
+
+use dozer_types::{ordered_float::OrderedFloat, types::DozerPoint};
+
+const MORTON_CODE_LEN: usize = 8;
+const COORDINATE_LEN: usize = 8;
+const KEY_LEN: usize = MORTON_CODE_LEN + 2 * COORDINATE_LEN;
+
+/// Maximum number of cells a bounding box spans on each axis, when it's covered by key ranges.
+const MAX_CELLS_PER_AXIS: u32 = 4;
+
+/// A longitude and latitude box, in degrees. `min` is the south west corner and `max` the north east one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, point: &DozerPoint) -> bool {
+        let (x, y) = (point.0.x().0, point.0.y().0);
+        self.min_x <= x && x <= self.max_x && self.min_y <= y && y <= self.max_y
+    }
+}
+
+pub fn get_spatial_secondary_index(point: &DozerPoint) -> Vec<u8> {
+    let (x, y) = (point.0.x().0, point.0.y().0);
+    let mut key = Vec::with_capacity(KEY_LEN);
+    key.extend_from_slice(&morton_code(quantize_x(x), quantize_y(y)).to_be_bytes());
+    key.extend_from_slice(&x.to_be_bytes());
+    key.extend_from_slice(&y.to_be_bytes());
+    key
+}
+
+/// Returns the Morton code and the point of a spatial secondary index key.
+pub fn decode_spatial_secondary_index(key: &[u8]) -> Option<(u64, DozerPoint)> {
+    if key.len() != KEY_LEN {
+        return None;
+    }
+    let code = u64::from_be_bytes(key[..MORTON_CODE_LEN].try_into().ok()?);
+    let x = f64::from_be_bytes(
+        key[MORTON_CODE_LEN..MORTON_CODE_LEN + COORDINATE_LEN]
+            .try_into()
+            .ok()?,
+    );
+    let y = f64::from_be_bytes(key[MORTON_CODE_LEN + COORDINATE_LEN..].try_into().ok()?);
+    Some((code, DozerPoint::from((x, y))))
+}
+
+/// Returns inclusive ranges of Morton codes whose keys cover `bounding_boxes`, in ascending order and without overlap.
+pub fn get_spatial_key_ranges(bounding_boxes: &[BoundingBox]) -> Vec<(u64, u64)> {
+    let mut ranges = bounding_boxes
+        .iter()
+        .flat_map(get_bounding_box_key_ranges)
+        .collect::<Vec<_>>();
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = vec![];
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// The box is covered by grid cells of the smallest size that keeps it within `MAX_CELLS_PER_AXIS` cells on each axis.
+/// The Morton codes of a cell are contiguous.
+fn get_bounding_box_key_ranges(bounding_box: &BoundingBox) -> Vec<(u64, u64)> {
+    let (min_x, max_x) = (
+        quantize_x(bounding_box.min_x),
+        quantize_x(bounding_box.max_x),
+    );
+    let (min_y, max_y) = (
+        quantize_y(bounding_box.min_y),
+        quantize_y(bounding_box.max_y),
+    );
+    if min_x > max_x || min_y > max_y {
+        return vec![];
+    }
+
+    let mut shift = 0;
+    while shift < u32::BITS
+        && ((max_x >> shift) - (min_x >> shift) >= MAX_CELLS_PER_AXIS
+            || (max_y >> shift) - (min_y >> shift) >= MAX_CELLS_PER_AXIS)
+    {
+        shift += 1;
+    }
+    let cell_mask = if shift == u32::BITS {
+        u32::MAX
+    } else {
+        (1u32 << shift) - 1
+    };
+
+    let mut ranges = vec![];
+    for x in cells(min_x, max_x, shift) {
+        for y in cells(min_y, max_y, shift) {
+            ranges.push((morton_code(x, y), morton_code(x | cell_mask, y | cell_mask)));
+        }
+    }
+    ranges
+}
+
+/// The first quantized coordinate of every cell between `min` and `max`, when cells are `1 << shift` wide.
+fn cells(min: u32, max: u32, shift: u32) -> impl Iterator<Item = u32> {
+    let (first, last) = if shift == u32::BITS {
+        (0, 0)
+    } else {
+        (min >> shift, max >> shift)
+    };
+    (first..=last).map(move |cell| cell.checked_shl(shift).unwrap_or(0))
+}
+
+fn quantize_x(x: f64) -> u32 {
+    quantize(x, -180.0, 180.0)
+}
+
+fn quantize_y(y: f64) -> u32 {
+    quantize(y, -90.0, 90.0)
+}
+
+/// Maps `value` in `[min, max]` to the full `u32` range. Values outside the range are clamped.
+fn quantize(value: f64, min: f64, max: f64) -> u32 {
+    let value = OrderedFloat(value)
+        .clamp(OrderedFloat(min), OrderedFloat(max))
+        .0;
+    // `as` saturates, so `max` maps to `u32::MAX`.
+    ((value - min) / (max - min) * u32::MAX as f64) as u32
+}
+
+/// Interleaves the bits of `x` and `y`, with `x` in the lower bit of each pair.
+fn morton_code(x: u32, y: u32) -> u64 {
+    spread_bits(x) | (spread_bits(y) << 1)
+}
+
+fn spread_bits(value: u32) -> u64 {
+    let mut value = value as u64;
+    value = (value | (value << 16)) & 0x0000_FFFF_0000_FFFF;
+    value = (value | (value << 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value | (value << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | (value << 2)) & 0x3333_3333_3333_3333;
+    value = (value | (value << 1)) & 0x5555_5555_5555_5555;
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morton_code() {
+        assert_eq!(morton_code(0, 0), 0);
+        assert_eq!(morton_code(1, 0), 1);
+        assert_eq!(morton_code(0, 1), 2);
+        assert_eq!(morton_code(3, 3), 15);
+        assert_eq!(morton_code(u32::MAX, u32::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_spatial_secondary_index_round_trip() {
+        let point = DozerPoint::from((-73.9857, 40.7484));
+        let key = get_spatial_secondary_index(&point);
+        let (code, decoded) = decode_spatial_secondary_index(&key).unwrap();
+        assert_eq!(decoded, point);
+        assert_eq!(code, morton_code(quantize_x(-73.9857), quantize_y(40.7484)));
+        assert!(decode_spatial_secondary_index(&key[1..]).is_none());
+    }
+
+    #[test]
+    fn test_spatial_key_ranges_cover_bounding_box() {
+        let bounding_box = BoundingBox {
+            min_x: -74.1,
+            min_y: 40.6,
+            max_x: -73.8,
+            max_y: 40.9,
+        };
+        let ranges = get_spatial_key_ranges(&[bounding_box]);
+        assert!(!ranges.is_empty());
+        assert!(ranges.len() <= (MAX_CELLS_PER_AXIS * MAX_CELLS_PER_AXIS) as usize);
+        assert!(ranges.windows(2).all(|pair| pair[0].1 < pair[1].0));
+
+        let covered = |x: f64, y: f64| {
+            let code = morton_code(quantize_x(x), quantize_y(y));
+            ranges
+                .iter()
+                .any(|(start, end)| *start <= code && code <= *end)
+        };
+        for x in [-74.1, -74.0, -73.95, -73.8] {
+            for y in [40.6, 40.7, 40.75, 40.9] {
+                assert!(covered(x, y));
+            }
+        }
+        assert!(!covered(2.35, 48.85));
+    }
+
+    #[test]
+    fn test_spatial_key_ranges_of_whole_world() {
+        let bounding_box = BoundingBox {
+            min_x: -180.0,
+            min_y: -90.0,
+            max_x: 180.0,
+            max_y: 90.0,
+        };
+        assert_eq!(
+            get_spatial_key_ranges(&[bounding_box, bounding_box]),
+            vec![(0, u64::MAX)]
+        );
+    }
+}
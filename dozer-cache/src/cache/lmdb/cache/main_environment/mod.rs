@@ -2,13 +2,15 @@ use std::path::{Path, PathBuf};
 
 use dozer_storage::{
     lmdb_storage::{RoLmdbEnvironment, RwLmdbEnvironment},
-    LmdbEnvironment, LmdbOption,
+    LmdbCounter, LmdbEnvironment, LmdbOption,
 };
 use dozer_types::models::api_endpoint::{
     ConflictResolution, OnInsertResolutionTypes, OnUpdateResolutionTypes,
 };
 use dozer_types::{
-    borrow::IntoOwned,
+    bincode,
+    borrow::{Borrow, IntoOwned},
+    node::SourceStates,
     types::{Field, FieldType, Record, Schema, SchemaWithIndex},
 };
 use tempdir::TempDir;
@@ -17,7 +19,7 @@ use crate::{
     cache::{
         index,
         lmdb::utils::{create_env, open_env},
-        RecordWithId,
+        LogOperation, RecordWithId, SeqNoOffset,
     },
     errors::CacheError,
 };
@@ -62,6 +64,43 @@ pub trait MainEnvironment: LmdbEnvironment {
             .get_record(&txn, key)?
             .ok_or(CacheError::PrimaryKeyNotFound)
     }
+
+    fn operation_log_len(&self) -> Result<u64, CacheError> {
+        let txn = self.begin_txn()?;
+        self.operation_log()
+            .next_operation_id(&txn)
+            .map_err(Into::into)
+    }
+
+    fn get_operations(
+        &self,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, LogOperation)>, CacheError> {
+        let txn = self.begin_txn()?;
+        self.operation_log()
+            .get_operations(&txn, start, limit)
+            .map_err(Into::into)
+    }
+
+    fn seq_no_offset(&self) -> Result<SeqNoOffset, CacheError> {
+        let txn = self.begin_txn()?;
+        Ok(SeqNoOffset {
+            offset: self.common().seq_no_offset.load(&txn)?,
+            first_resumable: self.common().first_resumable_seq_no.load(&txn)?,
+        })
+    }
+
+    fn checkpoint(&self) -> Result<Option<SourceStates>, CacheError> {
+        let txn = self.begin_txn()?;
+        self.common()
+            .checkpoint
+            .load(&txn)?
+            .map(|bytes| {
+                bincode::deserialize(bytes.borrow()).map_err(CacheError::map_deserialization_error)
+            })
+            .transpose()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -72,6 +111,12 @@ pub struct MainEnvironmentCommon {
     name: String,
     /// The operation log.
     operation_log: OperationLog,
+    /// See `SeqNoOffset::offset`.
+    seq_no_offset: LmdbCounter,
+    /// See `SeqNoOffset::first_resumable`.
+    first_resumable_seq_no: LmdbCounter,
+    /// Source states of the last epoch committed with the operations, serialized.
+    checkpoint: LmdbOption<Vec<u8>>,
     intersection_chunk_size: usize,
 }
 
@@ -111,6 +156,9 @@ impl RwMainEnvironment {
 
         let operation_log = OperationLog::create(&mut env)?;
         let schema_option = LmdbOption::create(&mut env, Some("schema"))?;
+        let seq_no_offset = LmdbCounter::create(&mut env, Some("seq_no_offset"))?;
+        let first_resumable_seq_no = LmdbCounter::create(&mut env, Some("first_resumable_seq_no"))?;
+        let checkpoint = LmdbOption::create(&mut env, Some("checkpoint"))?;
 
         let old_schema = schema_option
             .load(&env.begin_txn()?)?
@@ -142,6 +190,9 @@ impl RwMainEnvironment {
                 base_path,
                 name,
                 operation_log,
+                seq_no_offset,
+                first_resumable_seq_no,
+                checkpoint,
                 intersection_chunk_size: options.intersection_chunk_size,
             },
             schema,
@@ -163,6 +214,21 @@ impl RwMainEnvironment {
     ///
     /// Every time a record with the same primary key is inserted, its version number gets increased by 1.
     pub fn insert(&mut self, record: &mut Record) -> Result<u64, CacheError> {
+        self.insert_impl(record, None)
+    }
+
+    /// Inserts a record copied from another cache, keeping its version. Returns the record id.
+    pub fn insert_copy(&mut self, record: &Record) -> Result<u64, CacheError> {
+        let mut record = record.clone();
+        let version = record.version;
+        self.insert_impl(&mut record, version)
+    }
+
+    fn insert_impl(
+        &mut self,
+        record: &mut Record,
+        first_version: Option<u32>,
+    ) -> Result<u64, CacheError> {
         debug_check_schema_record_consistency(&self.schema.0, record);
 
         let upsert_on_duplicate = self.insert_resolution == OnInsertResolutionTypes::Update;
@@ -176,10 +242,20 @@ impl RwMainEnvironment {
         };
 
         let txn = self.env.txn_mut()?;
-        self.common
-            .operation_log
-            .insert(txn, record, primary_key.as_deref(), upsert_on_duplicate)?
-            .ok_or(CacheError::PrimaryKeyExists)
+        let operation_log = &self.common.operation_log;
+        match first_version {
+            Some(first_version) => operation_log.insert_with_first_version(
+                txn,
+                record,
+                primary_key.as_deref(),
+                upsert_on_duplicate,
+                first_version,
+            )?,
+            None => {
+                operation_log.insert(txn, record, primary_key.as_deref(), upsert_on_duplicate)?
+            }
+        }
+        .ok_or(CacheError::PrimaryKeyExists)
     }
 
     /// Deletes the record and returns the record version.
@@ -219,6 +295,36 @@ impl RwMainEnvironment {
         }
     }
 
+    pub fn uncommitted_operation_log_len(&mut self) -> Result<u64, CacheError> {
+        let txn = self.env.txn_mut()?;
+        self.common
+            .operation_log
+            .next_operation_id(txn)
+            .map_err(Into::into)
+    }
+
+    pub fn set_seq_no_offset(&mut self, seq_no_offset: SeqNoOffset) -> Result<(), CacheError> {
+        let txn = self.env.txn_mut()?;
+        self.common.seq_no_offset.store(txn, seq_no_offset.offset)?;
+        self.common
+            .first_resumable_seq_no
+            .store(txn, seq_no_offset.first_resumable)?;
+        Ok(())
+    }
+
+    pub fn set_checkpoint(&mut self, checkpoint: Option<&SourceStates>) -> Result<(), CacheError> {
+        let txn = self.env.txn_mut()?;
+        match checkpoint {
+            Some(checkpoint) => {
+                let bytes =
+                    bincode::serialize(checkpoint).map_err(CacheError::map_serialization_error)?;
+                self.common.checkpoint.store(txn, &bytes)?;
+            }
+            None => self.common.checkpoint.clear(txn)?,
+        }
+        Ok(())
+    }
+
     pub fn commit(&mut self) -> Result<(), CacheError> {
         self.env.commit().map_err(Into::into)
     }
@@ -253,6 +359,9 @@ impl RoMainEnvironment {
 
         let operation_log = OperationLog::open(&env)?;
         let schema_option = LmdbOption::open(&env, Some("schema"))?;
+        let seq_no_offset = LmdbCounter::open(&env, Some("seq_no_offset"))?;
+        let first_resumable_seq_no = LmdbCounter::open(&env, Some("first_resumable_seq_no"))?;
+        let checkpoint = LmdbOption::open(&env, Some("checkpoint"))?;
 
         let schema = schema_option
             .load(&env.begin_txn()?)?
@@ -265,6 +374,9 @@ impl RoMainEnvironment {
                 base_path: base_path.to_path_buf(),
                 name: name.to_string(),
                 operation_log,
+                seq_no_offset,
+                first_resumable_seq_no,
+                checkpoint,
                 intersection_chunk_size: options.intersection_chunk_size,
             },
             schema,
@@ -298,6 +410,7 @@ fn debug_check_schema_record_consistency(schema: &Schema, record: &Record) {
             FieldType::Date => debug_assert!(value.as_date().is_some()),
             FieldType::Bson => debug_assert!(value.as_bson().is_some()),
             FieldType::Point => debug_assert!(value.as_point().is_some()),
+            FieldType::Interval => debug_assert!(value.as_interval().is_some()),
         }
     }
 }
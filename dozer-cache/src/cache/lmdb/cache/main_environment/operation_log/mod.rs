@@ -9,7 +9,7 @@ use dozer_types::{
     types::Record,
 };
 
-use crate::cache::RecordWithId;
+use crate::cache::{LogOperation, RecordWithId};
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(crate = "dozer_types::serde")]
@@ -142,10 +142,9 @@ impl OperationLog {
         txn: &T,
         operation_id: u64,
     ) -> Result<RecordWithId, StorageError> {
-        let Some(Cow::Owned(Operation::Insert {
-            record_id,
-            record,
-        })) = self.operation_id_to_operation.get(txn, &operation_id)? else {
+        let Some(Cow::Owned(Operation::Insert { record_id, record })) =
+            self.operation_id_to_operation.get(txn, &operation_id)?
+        else {
             panic!(
                 "Inconsistent state: primary_key_to_metadata or present_operation_ids contains an insert operation id that is not an Insert operation"
             );
@@ -164,6 +163,37 @@ impl OperationLog {
             .map(IntoOwned::into_owned))
     }
 
+    /// Returns at most `limit` operations with their ids, starting from operation id `start`.
+    ///
+    /// `Delete`s are returned with the record they deleted.
+    pub fn get_operations<T: Transaction>(
+        &self,
+        txn: &T,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, LogOperation)>, StorageError> {
+        let end = self
+            .next_operation_id(txn)?
+            .min(start.saturating_add(limit as u64));
+        // Operation ids are contiguous, and operations are never removed.
+        (start..end)
+            .map(|operation_id| {
+                let Some(operation) = self.get_operation(txn, operation_id)? else {
+                    panic!("Inconsistent state: operation id {operation_id} is less than next operation id but not found");
+                };
+                let operation = match operation {
+                    Operation::Insert { record_id, record } => {
+                        LogOperation::Insert(RecordWithId::new(record_id, record))
+                    }
+                    Operation::Delete { operation_id } => LogOperation::Delete(
+                        self.get_record_by_operation_id_unchecked(txn, operation_id)?,
+                    ),
+                };
+                Ok((operation_id, operation))
+            })
+            .collect()
+    }
+
     /// Inserts the record and sets the record version. Returns the record id.
     ///
     /// If the record's primary key collides with an existing record, returns `None`.
@@ -175,6 +205,24 @@ impl OperationLog {
         record: &mut Record,
         primary_key: Option<&[u8]>,
         upsert_on_collision: bool,
+    ) -> Result<Option<u64>, StorageError> {
+        self.insert_with_first_version(
+            txn,
+            record,
+            primary_key,
+            upsert_on_collision,
+            INITIAL_RECORD_VERSION,
+        )
+    }
+
+    /// Same as `insert`, but a record whose primary key was never inserted before, or that has no primary key, gets version `first_version`.
+    pub fn insert_with_first_version(
+        &self,
+        txn: &mut RwTransaction,
+        record: &mut Record,
+        primary_key: Option<&[u8]>,
+        upsert_on_collision: bool,
+        first_version: u32,
     ) -> Result<Option<u64>, StorageError> {
         // Calculate operation id and record id.
         let (operation_id, record_id, record_version) = if let Some(primary_key) = primary_key {
@@ -184,7 +232,7 @@ impl OperationLog {
                 // Primary key is never inserted before. Generate new id from `primary_key_to_metadata`.
                 None => (
                     self.primary_key_to_metadata.count(txn)? as u64,
-                    INITIAL_RECORD_VERSION,
+                    first_version,
                     None,
                 ),
                 Some(metadata) => {
@@ -226,7 +274,7 @@ impl OperationLog {
             // Generation operation id.
             let operation_id = self.next_operation_id.fetch_add(txn, 1)?;
             // If the record has no primary key, record id is operation id.
-            (operation_id, operation_id, INITIAL_RECORD_VERSION)
+            (operation_id, operation_id, first_version)
         };
 
         record.version = Some(record_version);
@@ -472,5 +520,30 @@ mod tests {
                 record: record.clone(),
             }
         );
+
+        // Deletes are resolved to the records they deleted.
+        let first_record = Record {
+            version: Some(INITIAL_RECORD_VERSION),
+            ..record.clone()
+        };
+        assert_eq!(
+            log.get_operations(txn, 0, 10).unwrap(),
+            vec![
+                (
+                    0,
+                    LogOperation::Insert(RecordWithId::new(record_id, first_record.clone()))
+                ),
+                (
+                    1,
+                    LogOperation::Delete(RecordWithId::new(record_id, first_record))
+                ),
+                (
+                    2,
+                    LogOperation::Insert(RecordWithId::new(record_id, record.clone()))
+                ),
+            ]
+        );
+        assert_eq!(log.get_operations(txn, 1, 1).unwrap().len(), 1);
+        assert!(log.get_operations(txn, 3, 10).unwrap().is_empty());
     }
 }
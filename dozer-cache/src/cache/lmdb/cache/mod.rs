@@ -3,6 +3,7 @@ use dozer_types::parking_lot::Mutex;
 use std::path::PathBuf;
 use std::{fmt::Debug, sync::Arc};
 
+use dozer_types::node::SourceStates;
 use dozer_types::types::{Record, SchemaWithIndex};
 
 use super::{
@@ -10,7 +11,7 @@ use super::{
     indexing::IndexingThreadPool,
 };
 use crate::cache::expression::QueryExpression;
-use crate::cache::RecordWithId;
+use crate::cache::{LogOperation, RecordWithId, SeqNoOffset};
 use crate::errors::CacheError;
 
 mod main_environment;
@@ -141,6 +142,26 @@ impl<C: LmdbCache> RoCache for C {
     fn get_schema(&self) -> &SchemaWithIndex {
         self.main_env().schema()
     }
+
+    fn operation_log_len(&self) -> Result<u64, CacheError> {
+        self.main_env().operation_log_len()
+    }
+
+    fn get_operations(
+        &self,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, LogOperation)>, CacheError> {
+        self.main_env().get_operations(start, limit)
+    }
+
+    fn seq_no_offset(&self) -> Result<SeqNoOffset, CacheError> {
+        self.main_env().seq_no_offset()
+    }
+
+    fn checkpoint(&self) -> Result<Option<SourceStates>, CacheError> {
+        self.main_env().checkpoint()
+    }
 }
 
 impl RwCache for LmdbRwCache {
@@ -151,6 +172,10 @@ impl RwCache for LmdbRwCache {
         Ok(record_id)
     }
 
+    fn insert_copy(&mut self, record: &Record) -> Result<u64, CacheError> {
+        self.main_env.insert_copy(record)
+    }
+
     fn delete(&mut self, key: &[u8]) -> Result<u32, CacheError> {
         let version = self.main_env.delete(key)?;
         Ok(version)
@@ -165,6 +190,18 @@ impl RwCache for LmdbRwCache {
         Ok(version)
     }
 
+    fn uncommitted_operation_log_len(&mut self) -> Result<u64, CacheError> {
+        self.main_env.uncommitted_operation_log_len()
+    }
+
+    fn set_seq_no_offset(&mut self, seq_no_offset: SeqNoOffset) -> Result<(), CacheError> {
+        self.main_env.set_seq_no_offset(seq_no_offset)
+    }
+
+    fn set_checkpoint(&mut self, checkpoint: Option<&SourceStates>) -> Result<(), CacheError> {
+        self.main_env.set_checkpoint(checkpoint)
+    }
+
     fn commit(&mut self) -> Result<(), CacheError> {
         self.main_env.commit()?;
         self.indexing_thread_pool.lock().wake(self.name());
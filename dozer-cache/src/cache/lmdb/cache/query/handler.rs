@@ -1,12 +1,15 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+
 use super::intersection::intersection;
-use crate::cache::expression::Skip;
+use crate::cache::expression::{Skip, SortDirection};
 use crate::cache::lmdb::cache::main_environment::MainEnvironment;
 use crate::cache::lmdb::cache::query::secondary::build_index_scan;
 use crate::cache::lmdb::cache::LmdbCache;
 use crate::cache::RecordWithId;
 use crate::cache::{
     expression::QueryExpression,
-    plan::{IndexScan, Plan, QueryPlanner},
+    plan::{IndexScan, Plan, QueryPlanner, SeqScan},
 };
 use crate::errors::{CacheError, PlanError};
 use dozer_storage::errors::StorageError;
@@ -32,6 +35,18 @@ impl<'a, C: LmdbCache> LmdbQueryHandler<'a, C> {
                 let ids = self.combine_secondary_queries(&index_scans, &secondary_txns)?;
                 self.count_secondary_queries(ids)
             }
+            Plan::Union(branches) => {
+                let ids = self.union_ids(&branches)?;
+                self.count_secondary_queries(self.skip_and_limit(ids))
+            }
+            Plan::Difference { include, exclude } => {
+                let ids = self.difference_ids(&include, &exclude)?;
+                self.count_secondary_queries(self.skip_and_limit(ids))
+            }
+            Plan::SeqScan(seq_scan) if !seq_scan.is_full_scan() => {
+                let main_txn = self.cache.main_env().begin_txn()?;
+                Ok(self.filtered_scan(&main_txn, &seq_scan)?.len())
+            }
             Plan::SeqScan(_) => Ok(match self.query.skip {
                 Skip::Skip(skip) => self
                     .cache
@@ -57,6 +72,28 @@ impl<'a, C: LmdbCache> LmdbQueryHandler<'a, C> {
                 );
                 result
             }
+            Plan::Union(branches) => {
+                let ids = self.union_ids(&branches)?;
+                let main_txn = self.cache.main_env().begin_txn()?;
+                #[allow(clippy::let_and_return)] // Must do let binding unless won't compile
+                let result = self.collect_records(&main_txn, self.skip_and_limit(ids));
+                result
+            }
+            Plan::Difference { include, exclude } => {
+                let ids = self.difference_ids(&include, &exclude)?;
+                let main_txn = self.cache.main_env().begin_txn()?;
+                #[allow(clippy::let_and_return)] // Must do let binding unless won't compile
+                let result = self.collect_records(&main_txn, self.skip_and_limit(ids));
+                result
+            }
+            Plan::SeqScan(seq_scan) if !seq_scan.is_full_scan() => {
+                let main_txn = self.cache.main_env().begin_txn()?;
+                Ok(self
+                    .filtered_scan(&main_txn, &seq_scan)?
+                    .into_iter()
+                    .map(|(_, record)| record)
+                    .collect())
+            }
             Plan::SeqScan(_seq_scan) => {
                 let main_txn = self.cache.main_env().begin_txn()?;
                 #[allow(clippy::let_and_return)] // Must do let binding unless won't compile
@@ -81,9 +118,16 @@ impl<'a, C: LmdbCache> LmdbQueryHandler<'a, C> {
     fn all_ids<'txn, T: Transaction>(
         &self,
         main_txn: &'txn T,
+    ) -> Result<impl Iterator<Item = Result<u64, CacheError>> + 'txn, CacheError> {
+        Ok(self.skip_and_limit(self.present_ids(main_txn)?))
+    }
+
+    fn present_ids<'txn, T: Transaction>(
+        &self,
+        main_txn: &'txn T,
     ) -> Result<impl Iterator<Item = Result<u64, CacheError>> + 'txn, CacheError> {
         let schema_is_append_only = self.cache.main_env().schema().0.is_append_only();
-        let all_ids = self
+        Ok(self
             .cache
             .main_env()
             .operation_log()
@@ -92,8 +136,99 @@ impl<'a, C: LmdbCache> LmdbQueryHandler<'a, C> {
                 result
                     .map(|id| id.into_owned())
                     .map_err(CacheError::Storage)
+            }))
+    }
+
+    fn skip_and_limit(
+        &self,
+        ids: impl Iterator<Item = Result<u64, CacheError>>,
+    ) -> impl Iterator<Item = Result<u64, CacheError>> {
+        skip(ids, self.query.skip).take(self.query.limit.unwrap_or(usize::MAX))
+    }
+
+    /// Scans all records, keeping the ones that satisfy the residual filter, sorted as requested.
+    ///
+    /// Returns operation ids along with the records, after applying skip and limit.
+    fn filtered_scan<T: Transaction>(
+        &self,
+        main_txn: &T,
+        seq_scan: &SeqScan,
+    ) -> Result<Vec<(u64, RecordWithId)>, CacheError> {
+        let operation_log = self.cache.main_env().operation_log();
+        let mut records = vec![];
+        for id in self.present_ids(main_txn)? {
+            let id = id?;
+            let record = operation_log.get_record_by_operation_id_unchecked(main_txn, id)?;
+            if seq_scan
+                .filter
+                .as_ref()
+                .map_or(true, |filter| filter.matches(&record.record.values))
+            {
+                records.push((id, record));
+            }
+        }
+
+        if !seq_scan.order_by.is_empty() {
+            records.sort_by(|(_, a), (_, b)| {
+                for (field_index, direction) in &seq_scan.order_by {
+                    let a = &a.record.values[*field_index];
+                    let b = &b.record.values[*field_index];
+                    let ordering = match direction {
+                        SortDirection::Ascending => a.cmp(b),
+                        SortDirection::Descending => b.cmp(a),
+                    };
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                Ordering::Equal
             });
-        Ok(skip(all_ids, self.query.skip).take(self.query.limit.unwrap_or(usize::MAX)))
+        }
+
+        let start = match self.query.skip {
+            Skip::Skip(skip) => skip,
+            Skip::After(after) => records
+                .iter()
+                .position(|(id, _)| *id == after)
+                .map_or(records.len(), |position| position + 1),
+        };
+        Ok(records
+            .into_iter()
+            .skip(start)
+            .take(self.query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    /// Collects the ids matching an intersection of index scans, in index order.
+    fn index_scan_ids(&self, index_scans: &[IndexScan]) -> Result<Vec<u64>, CacheError> {
+        let secondary_txns = self.create_secondary_txns(index_scans)?;
+        let ids = self.scan_secondary_indexes(index_scans, &secondary_txns)?;
+        ids.collect()
+    }
+
+    fn union_ids(
+        &self,
+        branches: &[Vec<IndexScan>],
+    ) -> Result<impl Iterator<Item = Result<u64, CacheError>>, CacheError> {
+        let mut ids = BTreeSet::new();
+        for index_scans in branches {
+            ids.extend(self.index_scan_ids(index_scans)?);
+        }
+        Ok(ids.into_iter().map(Ok))
+    }
+
+    fn difference_ids(
+        &self,
+        include: &[IndexScan],
+        exclude: &[Vec<IndexScan>],
+    ) -> Result<impl Iterator<Item = Result<u64, CacheError>>, CacheError> {
+        let mut excluded = HashSet::new();
+        for index_scans in exclude {
+            excluded.extend(self.index_scan_ids(index_scans)?);
+        }
+        let mut ids = self.index_scan_ids(include)?;
+        ids.retain(|id| !excluded.contains(id));
+        Ok(ids.into_iter().map(Ok))
     }
 
     fn create_secondary_txns(
@@ -110,6 +245,14 @@ impl<'a, C: LmdbCache> LmdbQueryHandler<'a, C> {
         &self,
         index_scans: &[IndexScan],
         secondary_txns: &'txn [T],
+    ) -> Result<impl Iterator<Item = Result<u64, CacheError>> + 'txn, CacheError> {
+        Ok(self.skip_and_limit(self.scan_secondary_indexes(index_scans, secondary_txns)?))
+    }
+
+    fn scan_secondary_indexes<'txn, T: Transaction>(
+        &self,
+        index_scans: &[IndexScan],
+        secondary_txns: &'txn [T],
     ) -> Result<impl Iterator<Item = Result<u64, CacheError>> + 'txn, CacheError> {
         debug_assert!(
             !index_scans.is_empty(),
@@ -140,7 +283,7 @@ impl<'a, C: LmdbCache> LmdbQueryHandler<'a, C> {
                 self.cache.main_env().intersection_chunk_size(),
             ))
         };
-        Ok(skip(combined, self.query.skip).take(self.query.limit.unwrap_or(usize::MAX)))
+        Ok(combined)
     }
 
     fn filter_secondary_queries<'txn, T: Transaction>(
@@ -5,10 +5,11 @@ use dozer_types::{
     borrow::{Borrow, IntoOwned},
     types::{Field, IndexDefinition},
 };
+use itertools::Either;
 
 use crate::{
     cache::{
-        expression::{Operator, SortDirection},
+        expression::{Operator, SortDirection, SpatialFilter},
         index,
         lmdb::cache::secondary_environment::SecondaryEnvironment,
         plan::{IndexScanKind, SortedInvertedRangeQuery},
@@ -23,9 +24,21 @@ pub fn build_index_scan<'txn, T: Transaction, S: SecondaryEnvironment>(
     secondary_env: &S,
     index_scan_kind: &IndexScanKind,
 ) -> Result<impl Iterator<Item = Result<u64, CacheError>> + 'txn, CacheError> {
-    let is_single_field_sorted_inverted =
-        is_single_field_sorted_inverted(secondary_env.index_definition());
-    let range = get_range_spec(index_scan_kind, is_single_field_sorted_inverted)?;
+    if let IndexScanKind::Spatial { filter, .. } = index_scan_kind {
+        return Ok(Either::Right(build_spatial_index_scan(
+            secondary_txn,
+            secondary_env,
+            filter,
+        )?));
+    }
+
+    let index_definition = secondary_env.index_definition();
+    let is_single_field_sorted_inverted = is_single_field_sorted_inverted(index_definition);
+    let range = get_range_spec(
+        index_scan_kind,
+        is_single_field_sorted_inverted,
+        num_sorted_inverted_fields(index_definition),
+    )?;
 
     let start = match &range.start {
         Some(KeyEndpoint::Including(key)) => Bound::Included(key.as_slice()),
@@ -34,36 +47,67 @@ pub fn build_index_scan<'txn, T: Transaction, S: SecondaryEnvironment>(
     };
 
     let database = secondary_env.database().database();
-    Ok(secondary_env
-        .database()
-        .range(
-            secondary_txn,
-            start,
-            range.direction == SortDirection::Ascending,
-        )?
-        .take_while(move |result| match result {
-            Ok((key, _)) => {
-                if let Some(end_key) = &range.end {
-                    match lmdb_cmp(secondary_txn, database, key.borrow(), end_key.key()) {
-                        Ordering::Less => {
-                            matches!(range.direction, SortDirection::Ascending)
-                        }
-                        Ordering::Equal => matches!(end_key, KeyEndpoint::Including(_)),
-                        Ordering::Greater => {
-                            matches!(range.direction, SortDirection::Descending)
+    Ok(Either::Left(
+        secondary_env
+            .database()
+            .range(
+                secondary_txn,
+                start,
+                range.direction == SortDirection::Ascending,
+            )?
+            .take_while(move |result| match result {
+                Ok((key, _)) => {
+                    if let Some(end_key) = &range.end {
+                        match lmdb_cmp(secondary_txn, database, key.borrow(), end_key.key()) {
+                            Ordering::Less => {
+                                matches!(range.direction, SortDirection::Ascending)
+                            }
+                            Ordering::Equal => matches!(end_key, KeyEndpoint::Including(_)),
+                            Ordering::Greater => {
+                                matches!(range.direction, SortDirection::Descending)
+                            }
                         }
+                    } else {
+                        true
                     }
-                } else {
-                    true
                 }
-            }
-            Err(_) => true,
-        })
-        .map(|result| {
-            result
-                .map(|(_, id)| id.into_owned())
-                .map_err(CacheError::Storage)
-        }))
+                Err(_) => true,
+            })
+            .map(|result| {
+                result
+                    .map(|(_, id)| id.into_owned())
+                    .map_err(CacheError::Storage)
+            }),
+    ))
+}
+
+/// Scans the key ranges covering the bounding boxes of `filter`, keeping the points that match it.
+fn build_spatial_index_scan<'txn, T: Transaction, S: SecondaryEnvironment>(
+    secondary_txn: &'txn T,
+    secondary_env: &S,
+    filter: &SpatialFilter,
+) -> Result<impl Iterator<Item = Result<u64, CacheError>> + 'txn, CacheError> {
+    let mut scans = vec![];
+    for (start, end) in index::get_spatial_key_ranges(&filter.bounding_boxes()) {
+        let filter = filter.clone();
+        let start = start.to_be_bytes();
+        let scan = secondary_env
+            .database()
+            .range(secondary_txn, Bound::Included(start.as_slice()), true)?
+            .take_while(move |result| match result {
+                Ok((key, _)) => index::decode_spatial_secondary_index(key.borrow())
+                    .map_or(false, |(code, _)| code <= end),
+                Err(_) => true,
+            })
+            .filter_map(move |result| match result {
+                Ok((key, id)) => index::decode_spatial_secondary_index(key.borrow())
+                    .filter(|(_, point)| filter.matches(point))
+                    .map(|_| Ok(id.into_owned())),
+                Err(e) => Some(Err(CacheError::Storage(e))),
+            });
+        scans.push(scan);
+    }
+    Ok(scans.into_iter().flatten())
 }
 
 fn is_single_field_sorted_inverted(index: &IndexDefinition) -> bool {
@@ -74,6 +118,13 @@ fn is_single_field_sorted_inverted(index: &IndexDefinition) -> bool {
     }
 }
 
+fn num_sorted_inverted_fields(index: &IndexDefinition) -> usize {
+    match index {
+        IndexDefinition::SortedInverted(fields) => fields.len(),
+        _ => 0,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum KeyEndpoint {
     Including(Vec<u8>),
@@ -99,6 +150,7 @@ struct RangeSpec {
 fn get_range_spec(
     index_scan_kind: &IndexScanKind,
     is_single_field_sorted_inverted: bool,
+    num_sorted_inverted_fields: usize,
 ) -> Result<RangeSpec, CacheError> {
     match &index_scan_kind {
         IndexScanKind::SortedInverted {
@@ -177,10 +229,31 @@ fn get_range_spec(
                 // Here we respond to case 3, examples are `a = 1` or `a = 1 && b = 2`.
                 let comparison_key = comparison_key
                     .expect("here's at least a eq filter because there's no range query");
-                RangeSpec {
-                    start: Some(KeyEndpoint::Including(comparison_key.clone())),
-                    end: Some(KeyEndpoint::Including(comparison_key)),
-                    direction: SortDirection::Ascending, // doesn't matter
+                if eq_filters.len() < num_sorted_inverted_fields {
+                    // The `Eq` filters are a prefix of a composite index, like `a = 1` on index `(a, b)`.
+                    // We use `a = 1 && b = null` as a sentinel, using the invariant that `null` is greater than anything.
+                    let mut null_filters = eq_filters.clone();
+                    null_filters.extend(
+                        std::iter::repeat((0, Field::Null))
+                            .take(num_sorted_inverted_fields - eq_filters.len()),
+                    );
+                    let null_key = build_sorted_inverted_comparison_key(
+                        &null_filters,
+                        None,
+                        is_single_field_sorted_inverted,
+                    )
+                    .expect("we provided eq filters");
+                    RangeSpec {
+                        start: Some(KeyEndpoint::Excluding(comparison_key)),
+                        end: Some(KeyEndpoint::Including(null_key)),
+                        direction: SortDirection::Ascending, // doesn't matter
+                    }
+                } else {
+                    RangeSpec {
+                        start: Some(KeyEndpoint::Including(comparison_key.clone())),
+                        end: Some(KeyEndpoint::Including(comparison_key)),
+                        direction: SortDirection::Ascending, // doesn't matter
+                    }
                 }
             })
         }
@@ -203,6 +276,9 @@ fn get_range_spec(
             }
             other => panic!("operator {other:?} is not supported by full text index"),
         },
+        IndexScanKind::Spatial { .. } => {
+            unreachable!("spatial index scans are built by `build_spatial_index_scan`")
+        }
     }
 }
 
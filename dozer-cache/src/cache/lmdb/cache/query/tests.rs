@@ -1,12 +1,14 @@
 use crate::cache::{
     expression::{FilterExpression, Operator, QueryExpression},
     lmdb::tests::utils::{create_cache, insert_rec_1},
-    test_utils::{query_from_filter, schema_1, schema_full_text, schema_multi_indices},
+    test_utils::{
+        query_from_filter, schema_1, schema_full_text, schema_multi_indices, schema_spatial,
+    },
     RecordWithId, RoCache, RwCache,
 };
 use dozer_types::{
     serde_json::{from_value, json, Value},
-    types::{Field, Record, Schema},
+    types::{DozerPoint, Field, IndexDefinition, Record, Schema},
 };
 
 #[test]
@@ -192,6 +194,95 @@ fn query_secondary_vars() {
     );
 }
 
+#[test]
+fn query_secondary_or_not() {
+    let (mut cache, indexing_thread_pool, schema, _) = create_cache(schema_1);
+
+    let items = vec![
+        (1, Some("yuri".to_string()), Some(521)),
+        (2, Some("mega".to_string()), Some(521)),
+        (3, Some("james".to_string()), Some(523)),
+        (4, Some("james".to_string()), Some(524)),
+        (5, Some("steff".to_string()), Some(526)),
+        (6, Some("mega".to_string()), Some(527)),
+        (7, Some("james".to_string()), Some(528)),
+        (8, Some("ava".to_string()), None),
+    ];
+    for val in items {
+        insert_rec_1(&mut cache, &schema, val);
+    }
+    cache.commit().unwrap();
+    indexing_thread_pool.lock().wait_until_catchup();
+
+    // Index union.
+    test_query(
+        json!({"$filter":{ "$or": [{"a": 1}, {"c": 523}]}}),
+        2,
+        &cache,
+    );
+
+    test_query(
+        json!({"$filter":{ "$or": [{"b": "mega"}, {"c": {"$lt": 522}}]}}),
+        3,
+        &cache,
+    );
+
+    test_query(
+        json!({"$filter":{ "$or": [{"b": "mega"}, {"c": {"$lt": 522}}]}, "$limit": 1}),
+        1,
+        &cache,
+    );
+
+    // Index difference.
+    test_query_record(
+        json!({"$filter":{ "b": "james", "$not": {"c": {"$gt": 523}}}}),
+        vec![(2, 3, "james".to_string(), 523)],
+        &schema,
+        &cache,
+    );
+
+    test_query(
+        json!({"$filter":{ "b": "james", "$not": {"$or": [{"c": 523}, {"c": 528}]}}}),
+        1,
+        &cache,
+    );
+
+    // Sequential scan with residual filter.
+    test_query(json!({"$filter":{ "$not": {"b": "james"}}}), 5, &cache);
+
+    test_query(
+        json!({"$filter":{ "$not": {"$or": [{"b": "james"}, {"c": {"$gte": 526}}]}}}),
+        3,
+        &cache,
+    );
+
+    test_query_record(
+        json!({
+            "$filter":{ "$or": [{"b": "mega"}, {"c": {"$lt": 522}}]},
+            "$order_by": { "c": "desc" }
+        }),
+        vec![
+            (5, 6, "mega".to_string(), 527),
+            (0, 1, "yuri".to_string(), 521),
+            (1, 2, "mega".to_string(), 521),
+        ],
+        &schema,
+        &cache,
+    );
+
+    test_query_record(
+        json!({
+            "$filter":{ "$or": [{"b": "mega"}, {"c": {"$lt": 522}}]},
+            "$order_by": { "c": "desc" },
+            "$skip": 1,
+            "$limit": 1
+        }),
+        vec![(0, 1, "yuri".to_string(), 521)],
+        &schema,
+        &cache,
+    );
+}
+
 #[test]
 fn query_secondary_multi_indices() {
     let (mut cache, indexing_thread_pool, schema, _) = create_cache(schema_multi_indices);
@@ -246,6 +337,115 @@ fn query_secondary_multi_indices() {
     );
 }
 
+#[test]
+fn query_secondary_composite_prefix() {
+    // Only a composite index on `(b, c)`, which answers `Eq` filters on `b` alone.
+    let (mut cache, indexing_thread_pool, schema, _) = create_cache(|| {
+        let (schema, _) = schema_1();
+        (schema, vec![IndexDefinition::SortedInverted(vec![1, 2])])
+    });
+
+    for (a, b, c) in [(1, "x", 1), (2, "x", 2), (3, "y", 1), (4, "x", 3)] {
+        let mut record = Record::new(
+            schema.identifier,
+            vec![Field::Int(a), Field::String(b.to_string()), Field::Int(c)],
+            None,
+        );
+        cache.insert(&mut record).unwrap();
+    }
+    let mut record = Record::new(
+        schema.identifier,
+        vec![Field::Int(5), Field::String("x".to_string()), Field::Null],
+        None,
+    );
+    cache.insert(&mut record).unwrap();
+    cache.commit().unwrap();
+    indexing_thread_pool.lock().wait_until_catchup();
+
+    test_query(json!({"$filter": {"b": "x"}}), 4, &cache);
+    test_query(json!({"$filter": {"b": "y"}}), 1, &cache);
+    test_query(json!({"$filter": {"b": "z"}}), 0, &cache);
+    test_query(json!({"$filter": {"b": "x", "c": 2}}), 1, &cache);
+    test_query_err(json!({"$filter": {"c": 1}}), &cache);
+}
+
+#[test]
+fn query_secondary_spatial() {
+    let (mut cache, indexing_thread_pool, schema, _) = create_cache(schema_spatial);
+
+    for (name, location) in [
+        ("empire_state", Some((-73.9857, 40.7484))),
+        ("times_square", Some((-73.9855, 40.7580))),
+        ("central_park", Some((-73.9654, 40.7829))),
+        ("eiffel_tower", Some((2.2945, 48.8584))),
+        ("fiji", Some((179.99, -17.0))),
+        ("samoa", Some((-179.99, -17.0))),
+        ("nowhere", None),
+    ] {
+        let mut record = Record::new(
+            schema.identifier,
+            vec![
+                Field::String(name.to_string()),
+                location.map_or(Field::Null, |location| {
+                    Field::Point(DozerPoint::from(location))
+                }),
+            ],
+            None,
+        );
+        cache.insert(&mut record).unwrap();
+    }
+    cache.commit().unwrap();
+    indexing_thread_pool.lock().wait_until_catchup();
+
+    let near = |radius| {
+        json!({"$filter": {"location": {"$near": {
+            "point": {"x": -73.9857, "y": 40.7484},
+            "radius": radius
+        }}}})
+    };
+    test_query(near(100), 1, &cache);
+    test_query(near(1500), 2, &cache);
+    test_query(near(5000), 3, &cache);
+    test_query(near(10_000_000), 4, &cache);
+
+    test_query(
+        json!({"$filter": {"location": {"$within": {
+            "min": {"x": -74.1, "y": 40.6},
+            "max": {"x": -73.8, "y": 40.9}
+        }}}}),
+        3,
+        &cache,
+    );
+    test_query(
+        json!({"$filter": {"location": {"$within": {
+            "min": {"x": 179.0, "y": -18.0},
+            "max": {"x": -179.0, "y": -16.0}
+        }}}}),
+        2,
+        &cache,
+    );
+    test_query(
+        json!({"$filter": {"location": {"$near": {
+            "point": {"x": 179.999, "y": -17.0},
+            "radius": 10_000
+        }}}}),
+        2,
+        &cache,
+    );
+    test_query(
+        json!({"$filter": {
+            "name": "central_park",
+            "location": {"$near": {"point": {"x": -73.9857, "y": 40.7484}, "radius": 5000}}
+        }}),
+        1,
+        &cache,
+    );
+    test_query_err(
+        json!({"$filter": {"name": {"$near": {"point": {"x": 0, "y": 0}, "radius": 1}}}}),
+        &cache,
+    );
+}
+
 fn test_query_err(query: Value, cache: &dyn RwCache) {
     let query = from_value::<QueryExpression>(query).unwrap();
     let count_result = cache.count(&query);
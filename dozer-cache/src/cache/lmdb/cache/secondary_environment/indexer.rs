@@ -29,6 +29,12 @@ pub fn build_index(
                 database.insert(txn, &secondary_key, &operation_id)?;
             }
         }
+        IndexDefinition::Spatial(field_index) => {
+            if let Some(secondary_key) = build_index_spatial(*field_index, &record.values)? {
+                // Ignore existing pair.
+                database.insert(txn, &secondary_key, &operation_id)?;
+            }
+        }
     }
     Ok(())
 }
@@ -52,6 +58,12 @@ pub fn delete_index(
                 database.remove(txn, &secondary_key, &operation_id)?;
             }
         }
+        IndexDefinition::Spatial(field_index) => {
+            if let Some(secondary_key) = build_index_spatial(*field_index, &record.values)? {
+                // Ignore if not found.
+                database.remove(txn, &secondary_key, &operation_id)?;
+            }
+        }
     }
     Ok(())
 }
@@ -92,6 +104,21 @@ fn build_indices_full_text(
         .collect())
 }
 
+/// `null` points are not indexed.
+fn build_index_spatial(
+    field_index: usize,
+    values: &[Field],
+) -> Result<Option<Vec<u8>>, CacheError> {
+    match values.get(field_index) {
+        Some(Field::Point(point)) => Ok(Some(index::get_spatial_secondary_index(point))),
+        Some(Field::Null) => Ok(None),
+        Some(_) => Err(CacheError::Index(IndexError::FieldNotCompatibleIndex(
+            field_index,
+        ))),
+        None => Err(CacheError::Index(IndexError::FieldIndexOutOfRange)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cache::{
@@ -181,6 +181,22 @@ impl CacheManager for LmdbCacheManager {
         env.commit()?;
         Ok(())
     }
+
+    fn delete_cache(&self, name: &str) -> Result<(), CacheError> {
+        self.indexing_thread_pool.lock().remove_cache(name);
+
+        for file_name in [name.to_string(), format!("{name}-lock")] {
+            let path = self.base_path.join(file_name);
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| CacheError::Io(path, e))?;
+            }
+        }
+        let index_path = self.base_path.join(format!("{name}_index"));
+        if index_path.exists() {
+            std::fs::remove_dir_all(&index_path).map_err(|e| CacheError::Io(index_path, e))?;
+        }
+        Ok(())
+    }
 }
 
 const LMDB_CACHE_MANAGER_ALIAS_ENV_NAME: &str = "__DOZER_CACHE_MANAGER_ALIAS__";
@@ -304,4 +320,24 @@ mod tests {
             real_name
         );
     }
+
+    #[test]
+    fn test_delete_cache() {
+        let cache_manager = LmdbCacheManager::new(Default::default()).unwrap();
+        let cache = cache_manager
+            .create_cache(Schema::empty(), vec![], ConflictResolution::default())
+            .unwrap();
+        let real_name = cache.name().to_string();
+        drop(cache);
+        let alias = "alias";
+        cache_manager.create_alias(&real_name, alias).unwrap();
+
+        cache_manager.delete_cache(&real_name).unwrap();
+        assert!(cache_manager.open_ro_cache(&real_name).unwrap().is_none());
+        assert!(cache_manager.open_ro_cache(alias).unwrap().is_none());
+        assert!(cache_manager
+            .open_rw_cache(&real_name, ConflictResolution::default())
+            .unwrap()
+            .is_none());
+    }
 }
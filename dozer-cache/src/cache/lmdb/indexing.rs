@@ -81,6 +81,13 @@ impl IndexingThreadPool {
         }
     }
 
+    /// Stops indexing the cache with name `name`, after its running indexing tasks finish.
+    pub fn remove_cache(&mut self, name: &str) {
+        // Tasks refer to caches by position, so no task may be running when positions change.
+        self.wait_until_catchup();
+        self.caches.retain(|cache| cache.main_env.name() != name);
+    }
+
     pub fn wait_until_catchup(&mut self) {
         while self
             .caches
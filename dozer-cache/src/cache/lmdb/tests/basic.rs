@@ -5,9 +5,10 @@ use crate::cache::{
     index,
     lmdb::{cache::LmdbRwCache, indexing::IndexingThreadPool},
     test_utils::{self, query_from_filter},
-    RoCache, RwCache,
+    RoCache, RwCache, SeqNoOffset,
 };
 use dozer_types::{
+    node::{NodeHandle, OpIdentifier, SourceStates},
     parking_lot::Mutex,
     serde_json::Value,
     types::{Field, Record, Schema},
@@ -71,6 +72,38 @@ fn insert_get_and_delete_record() {
     assert_eq!(cache.query(&QueryExpression::default()).unwrap(), vec![]);
 }
 
+#[test]
+fn set_seq_no_offset() {
+    let (mut cache, _, _) = _setup();
+    assert_eq!(cache.seq_no_offset().unwrap(), SeqNoOffset::default());
+
+    let seq_no_offset = SeqNoOffset {
+        offset: 10,
+        first_resumable: 12,
+    };
+    cache.set_seq_no_offset(seq_no_offset).unwrap();
+    cache.commit().unwrap();
+    assert_eq!(cache.seq_no_offset().unwrap(), seq_no_offset);
+}
+
+#[test]
+fn set_checkpoint() {
+    let (mut cache, _, _) = _setup();
+    assert_eq!(cache.checkpoint().unwrap(), None);
+
+    let checkpoint = SourceStates::from([(
+        NodeHandle::new(None, "source".to_string()),
+        OpIdentifier::new(10, 2),
+    )]);
+    cache.set_checkpoint(Some(&checkpoint)).unwrap();
+    cache.commit().unwrap();
+    assert_eq!(cache.checkpoint().unwrap(), Some(checkpoint));
+
+    cache.set_checkpoint(None).unwrap();
+    cache.commit().unwrap();
+    assert_eq!(cache.checkpoint().unwrap(), None);
+}
+
 #[test]
 fn insert_and_update_record() {
     let (mut cache, _, schema) = _setup();
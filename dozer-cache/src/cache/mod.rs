@@ -5,6 +5,7 @@ use self::expression::QueryExpression;
 use crate::errors::CacheError;
 use dozer_types::models::api_endpoint::ConflictResolution;
 use dozer_types::{
+    node::SourceStates,
     serde::{Deserialize, Serialize},
     types::{IndexDefinition, Record, Schema, SchemaWithIndex},
 };
@@ -27,6 +28,24 @@ impl RecordWithId {
     }
 }
 
+/// An operation in the operation log of a cache, with the record it inserted or deleted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogOperation {
+    Insert(RecordWithId),
+    Delete(RecordWithId),
+}
+
+/// Where the operation log of a cache is in the event stream of its endpoint.
+///
+/// An endpoint is served from a new cache whenever it's rebuilt or migrated, but `seq_no`s keep increasing across caches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeqNoOffset {
+    /// The `seq_no` of an event is `offset` plus the length of the operation log after its operations.
+    pub offset: u64,
+    /// Smallest `seq_no` events can be replayed after. Earlier events were in caches that were dropped.
+    pub first_resumable: u64,
+}
+
 pub trait CacheManager: Send + Sync + Debug {
     /// Opens a cache in read-write mode with given name or an alias with that name.
     ///
@@ -58,6 +77,11 @@ pub trait CacheManager: Send + Sync + Debug {
     ///
     /// If `alias` already exists, it's overwritten. If cache with name `name` doesn't exist, the alias is still recorded.
     fn create_alias(&self, name: &str, alias: &str) -> Result<(), CacheError>;
+
+    /// Deletes the cache with name `name` and all its files.
+    ///
+    /// Aliases of the cache are kept. The cache must not be open in read-write mode.
+    fn delete_cache(&self, name: &str) -> Result<(), CacheError>;
 }
 
 pub trait RoCache: Send + Sync + Debug {
@@ -71,12 +95,28 @@ pub trait RoCache: Send + Sync + Debug {
     fn get(&self, key: &[u8]) -> Result<RecordWithId, CacheError>;
     fn count(&self, query: &QueryExpression) -> Result<usize, CacheError>;
     fn query(&self, query: &QueryExpression) -> Result<Vec<RecordWithId>, CacheError>;
+
+    // Operation Log Operations
+    /// Returns the number of committed operations, which is also the id of the next operation.
+    fn operation_log_len(&self) -> Result<u64, CacheError>;
+    /// Returns at most `limit` committed operations with their ids, starting from operation id `start`.
+    fn get_operations(
+        &self,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, LogOperation)>, CacheError>;
+    /// Returns the committed offset of the operation log in the event stream.
+    fn seq_no_offset(&self) -> Result<SeqNoOffset, CacheError>;
+    /// Returns the source states of the last epoch committed with the operations, if any.
+    fn checkpoint(&self) -> Result<Option<SourceStates>, CacheError>;
 }
 
 pub trait RwCache: RoCache {
     // Record Operations
     /// Sets the version of the inserted record and inserts it into the cache. Returns the id of the newly inserted record.
     fn insert(&mut self, record: &mut Record) -> Result<u64, CacheError>;
+    /// Inserts a record read from another cache, keeping its version. Returns the id of the newly inserted record.
+    fn insert_copy(&mut self, record: &Record) -> Result<u64, CacheError>;
     /// Returns version of the deleted record.
     fn delete(&mut self, key: &[u8]) -> Result<u32, CacheError>;
     /// Sets the version of the updated record and updates it in the cache. Returns tuple (Option<version_id>, record_id).
@@ -84,6 +124,13 @@ pub trait RwCache: RoCache {
     /// Record id is from newly inserted record if old record didnt exist
     fn update(&mut self, key: &[u8], record: &mut Record)
         -> Result<(Option<u32>, u64), CacheError>;
+    /// Returns the number of operations, including the ones that are not committed yet.
+    fn uncommitted_operation_log_len(&mut self) -> Result<u64, CacheError>;
+    /// Sets the offset of the operation log in the event stream. It's stored with the current transaction.
+    fn set_seq_no_offset(&mut self, seq_no_offset: SeqNoOffset) -> Result<(), CacheError>;
+    /// Sets or clears the source states the operations so far are consistent with. It's stored with the current transaction,
+    /// so the pipeline resumes exactly after the committed operations.
+    fn set_checkpoint(&mut self, checkpoint: Option<&SourceStates>) -> Result<(), CacheError>;
     /// Commits the current transaction.
     fn commit(&mut self) -> Result<(), CacheError>;
 }
@@ -1,7 +1,7 @@
 use dozer_types::types::Field;
 use itertools::{Either, Itertools};
 
-use crate::cache::expression::{Operator, SortDirection};
+use crate::cache::expression::{Operator, SortDirection, SpatialFilter};
 
 use super::{IndexFilter, IndexScanKind, SortedInvertedRangeQuery};
 
@@ -31,31 +31,38 @@ pub enum RangeQueryKind {
 
 pub fn get_all_indexes(
     filters: Vec<(IndexFilter, Option<SortDirection>)>,
+    spatial_filters: Vec<(usize, SpatialFilter)>,
     range_query: Option<RangeQuery>,
 ) -> impl Iterator<Item = Vec<IndexScanKind>> {
-    // Create a full text index for every full text filter, and collect `Eq` filters.
-    let mut full_text_scans = vec![];
+    // Create a full text index for every full text filter, a spatial index for every spatial filter, and collect `Eq` filters.
+    let mut full_text_and_spatial_scans = vec![];
     let mut eq_filters = vec![];
     for filter in filters {
         if filter.0.op.supported_by_full_text() {
-            full_text_scans.push(IndexScanKind::FullText { filter: filter.0 });
+            full_text_and_spatial_scans.push(IndexScanKind::FullText { filter: filter.0 });
         } else {
             debug_assert!(filter.0.op == Operator::EQ);
             eq_filters.push((filter.0.field_index, filter.0.val));
         }
     }
+    for (field_index, filter) in spatial_filters {
+        full_text_and_spatial_scans.push(IndexScanKind::Spatial {
+            field_index,
+            filter,
+        });
+    }
 
     if eq_filters.is_empty() && range_query.is_none() {
-        // Only full text scans.
+        // Only full text and spatial scans.
         assert!(
-            !full_text_scans.is_empty(),
+            !full_text_and_spatial_scans.is_empty(),
             "Must have at least one filter or range query"
         );
-        Either::Left(std::iter::once(full_text_scans))
+        Either::Left(std::iter::once(full_text_and_spatial_scans))
     } else {
         Either::Right(
             get_sorted_inverted_scans(eq_filters, range_query).map(move |scan| {
-                let mut scans = full_text_scans.clone();
+                let mut scans = full_text_and_spatial_scans.clone();
                 scans.push(scan);
                 scans
             }),
@@ -157,7 +164,7 @@ fn get_sort_directions(
 #[test]
 #[should_panic]
 fn get_all_indexes_from_empty_query_should_panic() {
-    get_all_indexes(vec![], None).collect_vec();
+    get_all_indexes(vec![], vec![], None).collect_vec();
 }
 
 #[test]
@@ -167,7 +174,7 @@ fn test_get_all_indexes() {
         range_query: Option<RangeQuery>,
         expcected: Vec<Vec<IndexScanKind>>,
     ) {
-        let actual = get_all_indexes(filters, range_query).collect::<Vec<_>>();
+        let actual = get_all_indexes(filters, vec![], range_query).collect::<Vec<_>>();
         assert_eq!(actual, expcected);
     }
 
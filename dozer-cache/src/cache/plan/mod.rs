@@ -1,9 +1,11 @@
 mod helper;
 mod planner;
+mod residual;
 use dozer_types::types::Field;
 pub use planner::QueryPlanner;
+pub use residual::ResidualFilter;
 
-use super::expression::{Operator, SortDirection};
+use super::expression::{Operator, SortDirection, SpatialFilter};
 
 #[cfg(test)]
 mod tests;
@@ -11,6 +13,13 @@ mod tests;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Plan {
     IndexScans(Vec<IndexScan>),
+    /// Union of several index scan intersections, produced by `$or`.
+    Union(Vec<Vec<IndexScan>>),
+    /// Records found by `include`, minus the ones found by any of `exclude`, produced by `$not`.
+    Difference {
+        include: Vec<IndexScan>,
+        exclude: Vec<Vec<IndexScan>>,
+    },
     SeqScan(SeqScan),
     ReturnEmpty,
 }
@@ -29,6 +38,10 @@ pub enum IndexScanKind {
     FullText {
         filter: IndexFilter,
     },
+    Spatial {
+        field_index: usize,
+        filter: SpatialFilter,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -41,6 +54,16 @@ pub struct SortedInvertedRangeQuery {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SeqScan {
     pub direction: SortDirection,
+    /// Filter evaluated against every scanned record, if no index can answer the query.
+    pub filter: Option<ResidualFilter>,
+    /// Sort options applied in memory after filtering.
+    pub order_by: Vec<(usize, SortDirection)>,
+}
+
+impl SeqScan {
+    pub fn is_full_scan(&self) -> bool {
+        self.filter.is_none() && self.order_by.is_empty()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
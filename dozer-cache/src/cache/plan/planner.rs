@@ -1,11 +1,14 @@
-use crate::cache::expression::{FilterExpression, Operator, SortDirection, SortOptions};
+use crate::cache::expression::{
+    FilterExpression, Operator, SortDirection, SortOptions, SpatialFilter,
+};
 use crate::errors::PlanError;
 use dozer_types::json_value_to_field;
+use dozer_types::serde_json::Value;
 use dozer_types::types::{Field, FieldDefinition, Schema};
 use dozer_types::types::{FieldType, IndexDefinition};
 
 use super::helper::{RangeQuery, RangeQueryKind};
-use super::{helper, IndexScan, Plan, SeqScan};
+use super::{helper, IndexScan, Plan, ResidualFilter, SeqScan};
 use super::{IndexFilter, IndexScanKind};
 
 pub struct QueryPlanner<'a> {
@@ -30,17 +33,123 @@ impl<'a> QueryPlanner<'a> {
     }
 
     pub fn plan(&self) -> Result<Plan, PlanError> {
+        match self.filter {
+            Some(expression) if contains_or_not(expression) => self.plan_or_not(expression),
+            filter => self.plan_conjunction(filter, self.order_by),
+        }
+    }
+
+    /// Plans a filter containing `$or` or `$not`.
+    ///
+    /// Index unions and differences are used when possible, otherwise we fall back to a sequential scan
+    /// that evaluates the filter on every record.
+    fn plan_or_not(&self, expression: &FilterExpression) -> Result<Plan, PlanError> {
+        // Building the residual filter validates field names and value types.
+        let residual = build_residual_filter(self.schema, expression)?;
+
+        // Index set operations don't preserve order, so only try them when no sort is requested.
+        if self.order_by.0.is_empty() {
+            if let Some(plan) = self.plan_index_set_operation(expression) {
+                return Ok(plan);
+            }
+        }
+
+        let mut order_by = vec![];
+        for order in &self.order_by.0 {
+            let (field_index, _, _) =
+                get_field_index_and_type(&order.field_name, &self.schema.fields)
+                    .ok_or_else(|| PlanError::FieldNotFound(order.field_name.clone()))?;
+            order_by.push((field_index, order.direction));
+        }
+        Ok(Plan::SeqScan(SeqScan {
+            direction: SortDirection::Ascending,
+            filter: Some(residual),
+            order_by,
+        }))
+    }
+
+    fn plan_index_set_operation(&self, expression: &FilterExpression) -> Option<Plan> {
+        match expression {
+            FilterExpression::Or(branches) => {
+                let mut union = vec![];
+                for branch in branches {
+                    match self.plan_branch(branch)? {
+                        Plan::IndexScans(index_scans) => union.push(index_scans),
+                        Plan::ReturnEmpty => (),
+                        _ => return None,
+                    }
+                }
+                Some(if union.is_empty() {
+                    Plan::ReturnEmpty
+                } else {
+                    Plan::Union(union)
+                })
+            }
+            FilterExpression::And(expressions) => {
+                let mut positive = vec![];
+                let mut negated = vec![];
+                for expression in expressions {
+                    match expression {
+                        FilterExpression::Not(expression) => match expression.as_ref() {
+                            FilterExpression::Or(branches) => negated.extend(branches),
+                            expression => negated.push(expression),
+                        },
+                        expression => positive.push(expression.clone()),
+                    }
+                }
+                if positive.is_empty() || negated.is_empty() {
+                    return None;
+                }
+
+                let include = match self.plan_branch(&FilterExpression::And(positive))? {
+                    Plan::IndexScans(index_scans) => index_scans,
+                    Plan::ReturnEmpty => return Some(Plan::ReturnEmpty),
+                    _ => return None,
+                };
+                let mut exclude = vec![];
+                for expression in negated {
+                    match self.plan_branch(expression)? {
+                        Plan::IndexScans(index_scans) => exclude.push(index_scans),
+                        Plan::ReturnEmpty => (),
+                        _ => return None,
+                    }
+                }
+                Some(if exclude.is_empty() {
+                    Plan::IndexScans(include)
+                } else {
+                    Plan::Difference { include, exclude }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Plans a branch of `$or` or `$not` with indexes only, returns `None` if that's not possible.
+    fn plan_branch(&self, expression: &FilterExpression) -> Option<Plan> {
+        if contains_or_not(expression) {
+            return None;
+        }
+        self.plan_conjunction(Some(expression), &SortOptions(vec![]))
+            .ok()
+    }
+
+    fn plan_conjunction(
+        &self,
+        filter: Option<&FilterExpression>,
+        sort_options: &SortOptions,
+    ) -> Result<Plan, PlanError> {
         // Collect all the filters.
         // TODO: Handle filters like And([a > 0, a < 10]).
         let mut filters = vec![];
-        if let Some(expression) = &self.filter {
-            collect_filters(self.schema, expression, &mut filters)?;
+        let mut spatial_filters = vec![];
+        if let Some(expression) = filter {
+            collect_filters(self.schema, expression, &mut filters, &mut spatial_filters)?;
         }
 
         // Filter the sort options.
         // TODO: Handle duplicate fields.
         let mut order_by = vec![];
-        for order in &self.order_by.0 {
+        for order in &sort_options.0 {
             // Find the field index.
             let (field_index, _, _) =
                 get_field_index_and_type(&order.field_name, &self.schema.fields)
@@ -54,9 +163,11 @@ impl<'a> QueryPlanner<'a> {
         }
 
         // If no filter and sort is requested, return a SeqScan.
-        if filters.is_empty() && order_by.is_empty() {
+        if filters.is_empty() && spatial_filters.is_empty() && order_by.is_empty() {
             return Ok(Plan::SeqScan(SeqScan {
                 direction: SortDirection::Ascending,
+                filter: None,
+                order_by: vec![],
             }));
         }
 
@@ -72,7 +183,7 @@ impl<'a> QueryPlanner<'a> {
         let range_query = find_range_query(&mut filters, &order_by)?;
 
         // Generate some index scans that can answer this query, lazily.
-        let all_index_scans = helper::get_all_indexes(filters, range_query);
+        let all_index_scans = helper::get_all_indexes(filters, spatial_filters, range_query);
 
         // Check if existing secondary indexes can satisfy any of the scans.
         for index_scans in all_index_scans {
@@ -97,28 +208,102 @@ fn get_field_index_and_type(
         .map(|(i, f)| (i, f.typ, f.nullable))
 }
 
+fn get_index_filter(
+    schema: &Schema,
+    field_name: &str,
+    operator: Operator,
+    value: &Value,
+) -> Result<IndexFilter, PlanError> {
+    let (field_index, field_type, nullable) = get_field_index_and_type(field_name, &schema.fields)
+        .ok_or_else(|| PlanError::FieldNotFound(field_name.to_string()))?;
+    let field = json_value_to_field(value.clone(), field_type, nullable)?;
+    Ok(IndexFilter::new(field_index, operator, field))
+}
+
+fn get_spatial_filter(
+    schema: &Schema,
+    field_name: &str,
+    operator: Operator,
+    value: &Value,
+) -> Result<(usize, SpatialFilter), PlanError> {
+    let (field_index, field_type, _) = get_field_index_and_type(field_name, &schema.fields)
+        .ok_or_else(|| PlanError::FieldNotFound(field_name.to_string()))?;
+    if field_type != FieldType::Point {
+        return Err(PlanError::SpatialFilterOnNonPointField(
+            field_name.to_string(),
+        ));
+    }
+    Ok((field_index, SpatialFilter::new(operator, value)?))
+}
+
+fn contains_or_not(expression: &FilterExpression) -> bool {
+    match expression {
+        FilterExpression::Simple(..) => false,
+        FilterExpression::And(expressions) => expressions.iter().any(contains_or_not),
+        FilterExpression::Or(_) | FilterExpression::Not(_) => true,
+    }
+}
+
 fn collect_filters(
     schema: &Schema,
     expression: &FilterExpression,
     filters: &mut Vec<(IndexFilter, Option<SortDirection>)>,
+    spatial_filters: &mut Vec<(usize, SpatialFilter)>,
 ) -> Result<(), PlanError> {
     match expression {
         FilterExpression::Simple(field_name, operator, value) => {
-            let (field_index, field_type, nullable) =
-                get_field_index_and_type(field_name, &schema.fields)
-                    .ok_or_else(|| PlanError::FieldNotFound(field_name.clone()))?;
-            let field = json_value_to_field(value.clone(), field_type, nullable)?;
-            filters.push((IndexFilter::new(field_index, *operator, field), None));
+            if operator.supported_by_spatial() {
+                spatial_filters.push(get_spatial_filter(schema, field_name, *operator, value)?);
+            } else {
+                filters.push((
+                    get_index_filter(schema, field_name, *operator, value)?,
+                    None,
+                ));
+            }
         }
         FilterExpression::And(expressions) => {
             for expression in expressions {
-                collect_filters(schema, expression, filters)?;
+                collect_filters(schema, expression, filters, spatial_filters)?;
             }
         }
+        FilterExpression::Or(_) | FilterExpression::Not(_) => {
+            unreachable!("`$or` and `$not` are planned by `plan_or_not`")
+        }
     }
     Ok(())
 }
 
+fn build_residual_filter(
+    schema: &Schema,
+    expression: &FilterExpression,
+) -> Result<ResidualFilter, PlanError> {
+    let build_all = |expressions: &[FilterExpression]| {
+        expressions
+            .iter()
+            .map(|expression| build_residual_filter(schema, expression))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    Ok(match expression {
+        FilterExpression::Simple(field_name, operator, value) => {
+            if operator.supported_by_spatial() {
+                let (field_index, filter) =
+                    get_spatial_filter(schema, field_name, *operator, value)?;
+                ResidualFilter::Spatial {
+                    field_index,
+                    filter,
+                }
+            } else {
+                ResidualFilter::Filter(get_index_filter(schema, field_name, *operator, value)?)
+            }
+        }
+        FilterExpression::And(expressions) => ResidualFilter::And(build_all(expressions)?),
+        FilterExpression::Or(expressions) => ResidualFilter::Or(build_all(expressions)?),
+        FilterExpression::Not(expression) => {
+            ResidualFilter::Not(Box::new(build_residual_filter(schema, expression)?))
+        }
+    })
+}
+
 fn seen_in_sorted_inverted_filter(
     field_index: usize,
     sort_direction: SortDirection,
@@ -217,6 +402,31 @@ impl IndexScanKind {
             (IndexScanKind::FullText { filter }, IndexDefinition::FullText(field_index)) => {
                 filter.field_index == *field_index
             }
+            (
+                IndexScanKind::Spatial { field_index, .. },
+                IndexDefinition::Spatial(index_field_index),
+            ) => field_index == index_field_index,
+            _ => false,
+        }
+    }
+
+    /// Checks if this is a `SortedInverted` scan of only `Eq` filters on the leading fields of a longer composite index.
+    fn is_prefix_of_index(&self, index: &IndexDefinition) -> bool {
+        match (self, index) {
+            (
+                IndexScanKind::SortedInverted {
+                    eq_filters,
+                    range_query: None,
+                },
+                IndexDefinition::SortedInverted(fields),
+            ) => {
+                !eq_filters.is_empty()
+                    && fields.len() > eq_filters.len()
+                    && eq_filters
+                        .iter()
+                        .zip(fields)
+                        .all(|(filter, field)| filter.0 == *field)
+            }
             _ => false,
         }
     }
@@ -228,10 +438,17 @@ fn all_indexes_are_present(
 ) -> Option<Vec<IndexScan>> {
     let mut scans = vec![];
     for index_scan_kind in index_scan_kinds {
+        // Prefer an exact match, then fall back to a composite index the scan is a prefix of.
         let found = indexes
             .iter()
             .enumerate()
-            .find(|(_, i)| index_scan_kind.is_supported_by_index(i));
+            .find(|(_, i)| index_scan_kind.is_supported_by_index(i))
+            .or_else(|| {
+                indexes
+                    .iter()
+                    .enumerate()
+                    .find(|(_, i)| index_scan_kind.is_prefix_of_index(i))
+            });
 
         match found {
             Some((idx, _)) => {
@@ -301,4 +518,61 @@ mod tests {
         }
         .is_supported_by_index(&IndexDefinition::FullText(0)),);
     }
+
+    #[test]
+    fn test_is_prefix_of_index() {
+        let check = |eq_filters: Vec<usize>, range_query: Option<usize>, index, expected: bool| {
+            assert_eq!(
+                IndexScanKind::SortedInverted {
+                    eq_filters: eq_filters
+                        .into_iter()
+                        .map(|index| (index, Field::Null))
+                        .collect(),
+                    range_query: range_query.map(|index| SortedInvertedRangeQuery {
+                        field_index: index,
+                        sort_direction: SortDirection::Ascending,
+                        operator_and_value: None,
+                    })
+                }
+                .is_prefix_of_index(&IndexDefinition::SortedInverted(index)),
+                expected
+            );
+        };
+
+        check(vec![0], None, vec![0, 1], true);
+        check(vec![0, 1], None, vec![0, 1, 2], true);
+        check(vec![1], None, vec![0, 1], false);
+        check(vec![0], None, vec![0], false);
+        check(vec![0, 1], None, vec![0, 1], false);
+        check(vec![0], Some(1), vec![0, 1, 2], false);
+        check(vec![], Some(0), vec![0, 1], false);
+    }
+
+    #[test]
+    fn test_plan_prefers_exact_index_over_composite() {
+        let indexes = vec![
+            IndexDefinition::SortedInverted(vec![0, 1]),
+            IndexDefinition::SortedInverted(vec![0]),
+        ];
+        let scans = vec![IndexScanKind::SortedInverted {
+            eq_filters: vec![(0, Field::Int(1))],
+            range_query: None,
+        }];
+        assert_eq!(
+            all_indexes_are_present(&indexes, scans.clone()).unwrap()[0].index_id,
+            1
+        );
+        assert_eq!(
+            all_indexes_are_present(&indexes[..1], scans).unwrap()[0].index_id,
+            0
+        );
+        assert!(all_indexes_are_present(
+            &indexes[..1],
+            vec![IndexScanKind::SortedInverted {
+                eq_filters: vec![(1, Field::Int(1))],
+                range_query: None,
+            }]
+        )
+        .is_none());
+    }
 }
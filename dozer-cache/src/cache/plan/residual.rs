@@ -0,0 +1,145 @@
+use dozer_types::types::Field;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::cache::expression::{Operator, SpatialFilter};
+
+use super::IndexFilter;
+
+/// A filter that can't be answered by secondary indexes and is evaluated on the records directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ResidualFilter {
+    Filter(IndexFilter),
+    Spatial {
+        field_index: usize,
+        filter: SpatialFilter,
+    },
+    And(Vec<ResidualFilter>),
+    Or(Vec<ResidualFilter>),
+    Not(Box<ResidualFilter>),
+}
+
+impl ResidualFilter {
+    pub fn matches(&self, values: &[Field]) -> bool {
+        match self {
+            ResidualFilter::Filter(filter) => filter_matches(filter, &values[filter.field_index]),
+            ResidualFilter::Spatial {
+                field_index,
+                filter,
+            } => match &values[*field_index] {
+                Field::Point(point) => filter.matches(point),
+                _ => false,
+            },
+            ResidualFilter::And(filters) => filters.iter().all(|filter| filter.matches(values)),
+            ResidualFilter::Or(filters) => filters.iter().any(|filter| filter.matches(values)),
+            ResidualFilter::Not(filter) => !filter.matches(values),
+        }
+    }
+}
+
+fn filter_matches(filter: &IndexFilter, value: &Field) -> bool {
+    if filter.op != Operator::EQ && filter.val == Field::Null {
+        // Consistent with the planner, which returns empty result for such filters.
+        return false;
+    }
+    if filter.op.is_range_operator() && value == &Field::Null {
+        // Consistent with the sorted inverted index, where range queries exclude `null`.
+        return false;
+    }
+
+    match filter.op {
+        Operator::EQ => value == &filter.val,
+        Operator::LT => value < &filter.val,
+        Operator::LTE => value <= &filter.val,
+        Operator::GT => value > &filter.val,
+        Operator::GTE => value >= &filter.val,
+        Operator::Contains => {
+            let Some(token) = as_str(&filter.val) else {
+                return false;
+            };
+            tokens(value).any(|word| word == token)
+        }
+        Operator::MatchesAny => {
+            let Some(query) = as_str(&filter.val) else {
+                return false;
+            };
+            query
+                .unicode_words()
+                .any(|token| tokens(value).any(|word| word == token))
+        }
+        Operator::MatchesAll => {
+            let Some(query) = as_str(&filter.val) else {
+                return false;
+            };
+            query
+                .unicode_words()
+                .all(|token| tokens(value).any(|word| word == token))
+        }
+        Operator::Near | Operator::Within => {
+            unreachable!("spatial filters are `ResidualFilter::Spatial`")
+        }
+    }
+}
+
+fn as_str(field: &Field) -> Option<&str> {
+    match field {
+        Field::String(string) | Field::Text(string) => Some(string),
+        _ => None,
+    }
+}
+
+fn tokens(field: &Field) -> impl Iterator<Item = &str> {
+    as_str(field).unwrap_or_default().unicode_words()
+}
+
+#[cfg(test)]
+mod tests {
+    use dozer_types::{ordered_float::OrderedFloat, types::DozerPoint};
+
+    use super::*;
+
+    #[test]
+    fn test_residual_filter_matches() {
+        let values = vec![Field::Int(1), Field::String("hello world".to_string())];
+        let eq = |field_index, val| {
+            ResidualFilter::Filter(IndexFilter::new(field_index, Operator::EQ, val))
+        };
+
+        assert!(eq(0, Field::Int(1)).matches(&values));
+        assert!(!eq(0, Field::Int(2)).matches(&values));
+        assert!(
+            ResidualFilter::Or(vec![eq(0, Field::Int(2)), eq(0, Field::Int(1))]).matches(&values)
+        );
+        assert!(
+            !ResidualFilter::And(vec![eq(0, Field::Int(2)), eq(0, Field::Int(1))]).matches(&values)
+        );
+        assert!(ResidualFilter::Not(Box::new(eq(0, Field::Int(2)))).matches(&values));
+        assert!(
+            !ResidualFilter::Filter(IndexFilter::new(0, Operator::GT, Field::Null))
+                .matches(&values)
+        );
+        assert!(
+            !ResidualFilter::Filter(IndexFilter::new(0, Operator::GT, Field::Int(0)))
+                .matches(&[Field::Null])
+        );
+
+        let text = |op, val: &str| {
+            ResidualFilter::Filter(IndexFilter::new(1, op, Field::String(val.to_string())))
+        };
+        assert!(text(Operator::Contains, "world").matches(&values));
+        assert!(!text(Operator::Contains, "wor").matches(&values));
+        assert!(text(Operator::MatchesAny, "goodbye world").matches(&values));
+        assert!(!text(Operator::MatchesAll, "goodbye world").matches(&values));
+        assert!(text(Operator::MatchesAll, "world hello").matches(&values));
+
+        let near = ResidualFilter::Spatial {
+            field_index: 0,
+            filter: SpatialFilter::Near {
+                point: DozerPoint::from((0.0, 0.0)),
+                radius: OrderedFloat(200_000.0),
+            },
+        };
+        assert!(near.matches(&[Field::Point(DozerPoint::from((1.0, 1.0)))]));
+        assert!(!near.matches(&[Field::Point(DozerPoint::from((2.0, 2.0)))]));
+        assert!(!near.matches(&[Field::Null]));
+    }
+}
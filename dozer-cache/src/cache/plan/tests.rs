@@ -1,11 +1,17 @@
 use super::{Plan, QueryPlanner};
 use crate::cache::{
-    expression::{self, FilterExpression, Operator, SortDirection, SortOption, SortOptions},
+    expression::{
+        self, FilterExpression, Operator, SortDirection, SortOption, SortOptions, SpatialFilter,
+    },
     plan::{IndexScanKind, SortedInvertedRangeQuery},
     test_utils,
 };
 
-use dozer_types::{serde_json::Value, types::Field};
+use dozer_types::{
+    ordered_float::OrderedFloat,
+    serde_json::{json, Value},
+    types::{DozerPoint, Field},
+};
 
 #[test]
 fn test_generate_plan_simple() {
@@ -133,3 +139,185 @@ fn test_generate_plan_empty() {
     .unwrap();
     assert!(matches!(plan, Plan::ReturnEmpty));
 }
+
+#[test]
+fn test_generate_plan_or() {
+    let (schema, secondary_indexes) = test_utils::schema_1();
+
+    let filter = FilterExpression::Or(vec![
+        FilterExpression::Simple("a".to_string(), Operator::EQ, Value::from(1)),
+        FilterExpression::Simple("c".to_string(), Operator::EQ, Value::from(2)),
+        FilterExpression::Simple("c".to_string(), Operator::LT, Value::Null),
+    ]);
+    let plan = QueryPlanner::new(
+        &schema,
+        &secondary_indexes,
+        Some(&filter),
+        &Default::default(),
+    )
+    .plan()
+    .unwrap();
+    if let Plan::Union(branches) = plan {
+        // The `null` range filter matches nothing and is dropped.
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[0].len(), 1);
+        assert_eq!(branches[0][0].index_id, 0);
+        assert_eq!(branches[1].len(), 1);
+        assert_eq!(branches[1][0].index_id, 2);
+    } else {
+        panic!("Union expected")
+    }
+}
+
+#[test]
+fn test_generate_plan_not() {
+    let (schema, secondary_indexes) = test_utils::schema_1();
+
+    let filter = FilterExpression::And(vec![
+        FilterExpression::Simple("a".to_string(), Operator::EQ, Value::from(1)),
+        FilterExpression::Not(Box::new(FilterExpression::Simple(
+            "c".to_string(),
+            Operator::GT,
+            Value::from(2),
+        ))),
+    ]);
+    let plan = QueryPlanner::new(
+        &schema,
+        &secondary_indexes,
+        Some(&filter),
+        &Default::default(),
+    )
+    .plan()
+    .unwrap();
+    if let Plan::Difference { include, exclude } = plan {
+        assert_eq!(include.len(), 1);
+        assert_eq!(include[0].index_id, 0);
+        assert_eq!(exclude.len(), 1);
+        assert_eq!(exclude[0][0].index_id, 2);
+        match &exclude[0][0].kind {
+            IndexScanKind::SortedInverted { range_query, .. } => assert_eq!(
+                range_query,
+                &Some(SortedInvertedRangeQuery {
+                    field_index: 2,
+                    sort_direction: SortDirection::Ascending,
+                    operator_and_value: Some((Operator::GT, Field::Int(2))),
+                })
+            ),
+            _ => panic!("Must be sorted inverted"),
+        }
+    } else {
+        panic!("Difference expected")
+    }
+}
+
+#[test]
+fn test_generate_plan_or_not_fallback() {
+    let (schema, secondary_indexes) = test_utils::schema_1();
+
+    // A bare `$not` can't be answered by indexes.
+    let filter = FilterExpression::Not(Box::new(FilterExpression::Simple(
+        "a".to_string(),
+        Operator::EQ,
+        Value::from(1),
+    )));
+    let plan = QueryPlanner::new(
+        &schema,
+        &secondary_indexes,
+        Some(&filter),
+        &Default::default(),
+    )
+    .plan()
+    .unwrap();
+    match plan {
+        Plan::SeqScan(seq_scan) => {
+            assert!(seq_scan.filter.is_some());
+            assert!(seq_scan.order_by.is_empty());
+        }
+        _ => panic!("SeqScan expected"),
+    }
+
+    // Sorting is done in memory.
+    let filter = FilterExpression::Or(vec![
+        FilterExpression::Simple("a".to_string(), Operator::EQ, Value::from(1)),
+        FilterExpression::Simple("b".to_string(), Operator::EQ, Value::from("test")),
+    ]);
+    let order_by = SortOptions(vec![SortOption {
+        field_name: "c".to_string(),
+        direction: SortDirection::Descending,
+    }]);
+    let plan = QueryPlanner::new(&schema, &secondary_indexes, Some(&filter), &order_by)
+        .plan()
+        .unwrap();
+    match plan {
+        Plan::SeqScan(seq_scan) => {
+            assert!(seq_scan.filter.is_some());
+            assert_eq!(seq_scan.order_by, vec![(2, SortDirection::Descending)]);
+        }
+        _ => panic!("SeqScan expected"),
+    }
+
+    // Unknown fields are still reported.
+    let filter = FilterExpression::Or(vec![FilterExpression::Simple(
+        "d".to_string(),
+        Operator::EQ,
+        Value::from(1),
+    )]);
+    assert!(QueryPlanner::new(
+        &schema,
+        &secondary_indexes,
+        Some(&filter),
+        &Default::default(),
+    )
+    .plan()
+    .is_err());
+}
+
+#[test]
+fn test_generate_plan_spatial() {
+    let (schema, secondary_indexes) = test_utils::schema_spatial();
+
+    let filter = FilterExpression::Simple(
+        "location".to_string(),
+        Operator::Near,
+        json!({"point": {"x": 1.0, "y": 2.0}, "radius": 100}),
+    );
+    let plan = QueryPlanner::new(
+        &schema,
+        &secondary_indexes,
+        Some(&filter),
+        &Default::default(),
+    )
+    .plan()
+    .unwrap();
+    if let Plan::IndexScans(index_scans) = plan {
+        assert_eq!(index_scans.len(), 1);
+        assert_eq!(index_scans[0].index_id, 1);
+        assert_eq!(
+            index_scans[0].kind,
+            IndexScanKind::Spatial {
+                field_index: 1,
+                filter: SpatialFilter::Near {
+                    point: DozerPoint::from((1.0, 2.0)),
+                    radius: OrderedFloat(100.0),
+                },
+            }
+        );
+    } else {
+        panic!("IndexScan expected")
+    }
+
+    // Spatial filters only apply to points.
+    let filter = FilterExpression::Simple(
+        "name".to_string(),
+        Operator::Within,
+        json!({"min": {"x": 0.0, "y": 0.0}, "max": {"x": 1.0, "y": 1.0}}),
+    );
+    assert!(QueryPlanner::new(
+        &schema,
+        &secondary_indexes,
+        Some(&filter),
+        &Default::default(),
+    )
+    .plan()
+    .is_err());
+}
@@ -56,6 +56,33 @@ pub fn schema_1() -> SchemaWithIndex {
     )
 }
 
+pub fn schema_spatial() -> SchemaWithIndex {
+    (
+        Schema {
+            identifier: Some(SchemaIdentifier { id: 3, version: 1 }),
+            fields: vec![
+                FieldDefinition {
+                    name: "name".to_string(),
+                    typ: dozer_types::types::FieldType::String,
+                    nullable: false,
+                    source: SourceDefinition::Dynamic,
+                },
+                FieldDefinition {
+                    name: "location".to_string(),
+                    typ: dozer_types::types::FieldType::Point,
+                    nullable: true,
+                    source: SourceDefinition::Dynamic,
+                },
+            ],
+            primary_index: vec![0],
+        },
+        vec![
+            IndexDefinition::SortedInverted(vec![0]),
+            IndexDefinition::Spatial(1),
+        ],
+    )
+}
+
 pub fn schema_full_text() -> SchemaWithIndex {
     (
         Schema {
@@ -125,4 +125,6 @@ pub enum PlanError {
     RangeQueryLimit,
     #[error("Matching index not found")]
     MatchingIndexNotFound,
+    #[error("Spatial filter on field {0:?}, which is not a point")]
+    SpatialFilterOnNonPointField(String),
 }
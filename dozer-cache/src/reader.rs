@@ -1,4 +1,4 @@
-use crate::cache::{expression::QueryExpression, RecordWithId, RoCache};
+use crate::cache::{expression::QueryExpression, LogOperation, RecordWithId, RoCache, SeqNoOffset};
 
 use super::cache::expression::FilterExpression;
 use crate::errors::CacheError;
@@ -70,6 +70,26 @@ impl CacheReader {
         self.cache.count(query)
     }
 
+    /// Returns the number of committed operations. Access is checked by the caller on the operations.
+    pub fn operation_log_len(&self) -> Result<u64, CacheError> {
+        self.cache.operation_log_len()
+    }
+
+    /// Returns at most `limit` committed operations with their ids, starting from operation id `start`.
+    /// Access is checked by the caller on the operations.
+    pub fn get_operations(
+        &self,
+        start: u64,
+        limit: usize,
+    ) -> Result<Vec<(u64, LogOperation)>, CacheError> {
+        self.cache.get_operations(start, limit)
+    }
+
+    /// Returns the offset of the operation log in the event stream.
+    pub fn seq_no_offset(&self) -> Result<SeqNoOffset, CacheError> {
+        self.cache.seq_no_offset()
+    }
+
     // Apply filter if specified in access
     fn apply_access_filter(&self, query: &mut QueryExpression, access_filter: AccessFilter) {
         // TODO: Use `fields` in `access_filter`.
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dozer_core::channels::{ProcessorChannelForwarder, SourceChannelForwarder};
+use dozer_core::epoch::Epoch;
+use dozer_core::errors::ExecutionError;
+use dozer_core::executor::{DagExecutor, ExecutorOptions};
+use dozer_core::node::{
+    OutputPortDef, OutputPortType, PortHandle, Processor, ProcessorFactory, Sink, SinkFactory,
+    Source, SourceFactory,
+};
+use dozer_core::{Dag, Endpoint, DEFAULT_PORT_HANDLE};
+use dozer_types::ingestion_types::IngestionMessage;
+use dozer_types::node::NodeHandle;
+use dozer_types::types::{
+    Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
+};
+use tempdir::TempDir;
+
+#[derive(Debug, Clone)]
+struct NoneContext {}
+
+#[derive(Debug)]
+struct GeneratorSourceFactory {
+    count: u64,
+    running: Arc<AtomicBool>,
+}
+
+impl SourceFactory<NoneContext> for GeneratorSourceFactory {
+    fn get_output_schema(
+        &self,
+        _port: &PortHandle,
+    ) -> Result<(Schema, NoneContext), ExecutionError> {
+        Ok((
+            Schema::empty()
+                .field(
+                    FieldDefinition::new(
+                        "id".to_string(),
+                        FieldType::Int,
+                        false,
+                        SourceDefinition::Dynamic,
+                    ),
+                    true,
+                )
+                .field(
+                    FieldDefinition::new(
+                        "value".to_string(),
+                        FieldType::String,
+                        false,
+                        SourceDefinition::Dynamic,
+                    ),
+                    false,
+                )
+                .clone(),
+            NoneContext {},
+        ))
+    }
+
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            DEFAULT_PORT_HANDLE,
+            OutputPortType::Stateless,
+        )]
+    }
+
+    fn build(
+        &self,
+        _output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Source>, ExecutionError> {
+        Ok(Box::new(GeneratorSource {
+            count: self.count,
+            running: self.running.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct GeneratorSource {
+    count: u64,
+    running: Arc<AtomicBool>,
+}
+
+impl Source for GeneratorSource {
+    fn can_start_from(&self, _last_checkpoint: (u64, u64)) -> Result<bool, ExecutionError> {
+        Ok(false)
+    }
+
+    fn start(
+        &self,
+        fw: &mut dyn SourceChannelForwarder,
+        _last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ExecutionError> {
+        for n in 0..self.count {
+            fw.send(
+                IngestionMessage::new_op(
+                    n,
+                    0,
+                    Operation::Insert {
+                        new: Record::new(
+                            None,
+                            vec![Field::Int(n as i64), Field::String(format!("value_{n}"))],
+                            None,
+                        ),
+                    },
+                ),
+                DEFAULT_PORT_HANDLE,
+            )?;
+        }
+
+        while self.running.load(Ordering::Relaxed) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct PassthroughProcessorFactory {}
+
+impl ProcessorFactory<NoneContext> for PassthroughProcessorFactory {
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, (Schema, NoneContext)>,
+    ) -> Result<(Schema, NoneContext), ExecutionError> {
+        Ok(input_schemas.get(&DEFAULT_PORT_HANDLE).unwrap().clone())
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            DEFAULT_PORT_HANDLE,
+            OutputPortType::Stateless,
+        )]
+    }
+
+    fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        Ok(Box::new(PassthroughProcessor {}))
+    }
+}
+
+#[derive(Debug)]
+struct PassthroughProcessor {}
+
+impl Processor for PassthroughProcessor {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        fw.send(op, DEFAULT_PORT_HANDLE)
+    }
+}
+
+#[derive(Debug)]
+struct CountingSinkFactory {
+    count: u64,
+    running: Arc<AtomicBool>,
+}
+
+impl SinkFactory<NoneContext> for CountingSinkFactory {
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn prepare(
+        &self,
+        _input_schemas: HashMap<PortHandle, (Schema, NoneContext)>,
+    ) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Sink>, ExecutionError> {
+        Ok(Box::new(CountingSink {
+            expected: self.count,
+            current: 0,
+            running: self.running.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct CountingSink {
+    expected: u64,
+    current: u64,
+    running: Arc<AtomicBool>,
+}
+
+impl Sink for CountingSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn process(&mut self, _from_port: PortHandle, _op: Operation) -> Result<(), ExecutionError> {
+        self.current += 1;
+        if self.current == self.expected {
+            self.running.store(false, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+}
+
+/// Runs `count` inserts through source -> processor -> processor -> sink and waits for the sink to see all of them.
+fn run_pipeline(count: u64, batch_sz: usize) {
+    let running = Arc::new(AtomicBool::new(true));
+
+    let source_handle = NodeHandle::new(None, "source".to_string());
+    let proc_1_handle = NodeHandle::new(Some(1), "proc_1".to_string());
+    let proc_2_handle = NodeHandle::new(Some(1), "proc_2".to_string());
+    let sink_handle = NodeHandle::new(Some(1), "sink".to_string());
+
+    let mut dag = Dag::new();
+    dag.add_source(
+        source_handle.clone(),
+        Arc::new(GeneratorSourceFactory {
+            count,
+            running: running.clone(),
+        }),
+    );
+    dag.add_processor(
+        proc_1_handle.clone(),
+        Arc::new(PassthroughProcessorFactory {}),
+    );
+    dag.add_processor(
+        proc_2_handle.clone(),
+        Arc::new(PassthroughProcessorFactory {}),
+    );
+    dag.add_sink(
+        sink_handle.clone(),
+        Arc::new(CountingSinkFactory {
+            count,
+            running: running.clone(),
+        }),
+    );
+    for (from, to) in [
+        (source_handle, proc_1_handle.clone()),
+        (proc_1_handle, proc_2_handle.clone()),
+        (proc_2_handle, sink_handle),
+    ] {
+        dag.connect(
+            Endpoint::new(from, DEFAULT_PORT_HANDLE),
+            Endpoint::new(to, DEFAULT_PORT_HANDLE),
+        )
+        .unwrap();
+    }
+
+    let options = ExecutorOptions {
+        batch_sz,
+        ..Default::default()
+    };
+    let tmp_dir = TempDir::new("pipeline_bench").unwrap();
+    DagExecutor::new(dag, tmp_dir.path().to_path_buf(), options)
+        .unwrap()
+        .start(running)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn pipeline(c: &mut Criterion) {
+    let count: u64 = 200_000;
+
+    let mut group = c.benchmark_group("pipeline_throughput");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(count));
+    for batch_sz in [1, 64, 1024] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(batch_sz),
+            &batch_sz,
+            |b, &batch_sz| b.iter(|| run_pipeline(count, batch_sz)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pipeline);
+criterion_main!(benches);
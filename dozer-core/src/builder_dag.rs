@@ -1,13 +1,15 @@
-use std::{fmt::Debug, path::PathBuf};
+use std::{fmt::Debug, path::PathBuf, sync::Arc};
 
 use daggy::petgraph::visit::IntoNodeIdentifiers;
-use dozer_types::node::{NodeHandle, OpIdentifier};
+use dozer_types::log::info;
+use dozer_types::node::{NodeHandle, OpIdentifier, SourceStates};
 
 use crate::{
     dag_checkpoint::{DagCheckpoint, NodeKind as CheckpointNodeKind},
     dag_schemas::{DagHaveSchemas, DagSchemas, EdgeType},
     errors::ExecutionError,
-    node::{Processor, Sink, Source},
+    executor::ExecutorOptions,
+    node::{Partitioning, Processor, Sink, Source},
 };
 
 #[derive(Debug)]
@@ -21,33 +23,41 @@ pub struct NodeType {
 
 #[derive(Debug)]
 /// Node kind, source, processor or sink. Source has a checkpoint to start from.
+///
+/// Sources are shared with the sinks, which tell them when their data is checkpointed.
 pub enum NodeKind {
-    Source(Box<dyn Source>, Option<OpIdentifier>),
+    Source(Arc<dyn Source>, Option<OpIdentifier>),
     Processor(Box<dyn Processor>),
+    /// A processor running as several instances, with the partitioning of its input.
+    ParallelProcessor(Vec<Box<dyn Processor>>, Partitioning),
     Sink(Box<dyn Sink>),
 }
 
-/// Builder DAG builds all the sources, processors and sinks.
-/// It also asks each source if its possible to start from the given checkpoint.
-/// If not possible, it resets metadata and updates the checkpoint.
+/// Builder DAG builds all the processors, on top of the sources and sinks built by the checkpoint DAG.
+///
+/// The pipeline starts over if any processor state doesn't match the checkpoint.
 #[derive(Debug)]
 pub struct BuilderDag {
     graph: daggy::Dag<NodeType, EdgeType>,
 }
 
 impl BuilderDag {
-    pub fn new<T>(dag_schemas: DagSchemas<T>, path: PathBuf) -> Result<Self, ExecutionError> {
+    pub fn new<T>(
+        dag_schemas: DagSchemas<T>,
+        path: PathBuf,
+        options: &ExecutorOptions,
+    ) -> Result<Self, ExecutionError> {
         // Decide the checkpoint to start from.
-        let dag_checkpoint = DagCheckpoint::new(dag_schemas, path)?;
+        let mut dag_checkpoint = DagCheckpoint::new(dag_schemas, path)?;
 
-        // Create processors and sinks.
+        // Create processors.
         let mut nodes = vec![];
         let node_indexes = dag_checkpoint
             .graph()
             .node_identifiers()
             .collect::<Vec<_>>();
         for node_index in node_indexes.iter().copied() {
-            // Create and initialize source, processor or sink.
+            // Create and initialize processor.
             let input_schemas = dag_checkpoint.get_node_input_schemas(node_index);
             let output_schemas = dag_checkpoint.get_node_output_schemas(node_index);
 
@@ -55,18 +65,62 @@ impl BuilderDag {
             let kind = match &node.kind {
                 CheckpointNodeKind::Source(_) => None,
                 CheckpointNodeKind::Processor(processor) => {
-                    let processor = processor.build(input_schemas, output_schemas)?;
-                    Some(NodeKind::Processor(processor))
-                }
-                CheckpointNodeKind::Sink(sink) => {
-                    let sink = sink.build(input_schemas)?;
-                    Some(NodeKind::Sink(sink))
+                    let parallelism = options.parallelism_of(&node.handle);
+                    let partitioning = if parallelism > 1 {
+                        processor.partitioning(&input_schemas)?
+                    } else {
+                        Partitioning::Single
+                    };
+                    if let Partitioning::Single = partitioning {
+                        let processor = processor.build(input_schemas, output_schemas)?;
+                        Some(NodeKind::Processor(processor))
+                    } else {
+                        let processors = (0..parallelism)
+                            .map(|partition| {
+                                processor.build_partition(
+                                    input_schemas.clone(),
+                                    output_schemas.clone(),
+                                    partition,
+                                    parallelism,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        Some(NodeKind::ParallelProcessor(processors, partitioning))
+                    }
                 }
+                CheckpointNodeKind::Sink(_) => None,
             };
 
             nodes.push(kind);
         }
 
+        // Processors keep state across runs too, which must match the checkpoint.
+        if let Some(checkpoint) = dag_checkpoint.checkpoint() {
+            let stale = node_indexes.iter().copied().find(|node_index| {
+                matches!(&nodes[node_index.index()], Some(kind) if !is_resumable(kind, checkpoint))
+            });
+            if let Some(node_index) = stale {
+                info!(
+                    "[{}] Processor can't resume from the checkpoint, starting over",
+                    dag_checkpoint.graph()[node_index].handle
+                );
+                dag_checkpoint.start_over()?;
+            }
+        }
+        if dag_checkpoint.checkpoint().is_none() {
+            for kind in nodes.iter_mut().flatten() {
+                match kind {
+                    NodeKind::Processor(processor) => processor.reset()?,
+                    NodeKind::ParallelProcessor(processors, _) => {
+                        for processor in processors {
+                            processor.reset()?;
+                        }
+                    }
+                    NodeKind::Source(..) | NodeKind::Sink(_) => (),
+                }
+            }
+        }
+
         // Create new graph.
         let graph = dag_checkpoint.into_graph().map_owned(
             |node_index, node| {
@@ -80,10 +134,11 @@ impl BuilderDag {
                         handle: node.handle,
                         kind: match node.kind {
                             CheckpointNodeKind::Source((source, checkpoint)) => {
-                                NodeKind::Source(source, checkpoint)
+                                NodeKind::Source(source.into(), checkpoint)
                             }
-                            CheckpointNodeKind::Processor(_) | CheckpointNodeKind::Sink(_) => {
-                                unreachable!("We created all processors and sinks")
+                            CheckpointNodeKind::Sink(sink) => NodeKind::Sink(sink),
+                            CheckpointNodeKind::Processor(_) => {
+                                unreachable!("We created all processors")
                             }
                         },
                     }
@@ -102,3 +157,13 @@ impl BuilderDag {
         self.graph
     }
 }
+
+fn is_resumable(kind: &NodeKind, checkpoint: &SourceStates) -> bool {
+    match kind {
+        NodeKind::Processor(processor) => processor.is_resumable(checkpoint),
+        NodeKind::ParallelProcessor(processors, _) => processors
+            .iter()
+            .all(|processor| processor.is_resumable(checkpoint)),
+        NodeKind::Source(..) | NodeKind::Sink(_) => true,
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use dozer_types::node::{NodeHandle, SourceStates};
+use dozer_types::parking_lot::Mutex;
+
+use crate::epoch::Epoch;
+use crate::errors::ExecutionError;
+use crate::node::Source;
+
+/// Merges the checkpoints of all sinks into the position every source should resume from.
+///
+/// A source feeding multiple sinks resumes from the smallest position any of them has committed,
+/// so no sink misses data. If any sink has no checkpoint, the whole pipeline starts from scratch.
+pub fn merge_checkpoints(checkpoints: &[Option<SourceStates>]) -> SourceStates {
+    let mut result = SourceStates::new();
+    if checkpoints.iter().any(Option::is_none) {
+        return result;
+    }
+
+    for checkpoint in checkpoints.iter().flatten() {
+        for (source, op_id) in checkpoint {
+            result
+                .entry(source.clone())
+                .and_modify(|current| *current = (*current).min(*op_id))
+                .or_insert(*op_id);
+        }
+    }
+    result
+}
+
+/// Tells the sources which of their messages every sink has committed.
+#[derive(Debug)]
+pub struct CheckpointNotifier {
+    sources: Vec<(NodeHandle, Arc<dyn Source>)>,
+    num_sinks: usize,
+    /// The epochs not all sinks have committed yet, with the number of sinks that have.
+    pending: Mutex<BTreeMap<u64, usize>>,
+}
+
+impl CheckpointNotifier {
+    pub fn new(sources: Vec<(NodeHandle, Arc<dyn Source>)>, num_sinks: usize) -> Self {
+        Self {
+            sources,
+            num_sinks,
+            pending: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Called by every sink once it has committed `epoch`. The last sink to commit it notifies the sources.
+    pub fn on_sink_commit(&self, epoch: &Epoch) -> Result<(), ExecutionError> {
+        {
+            let mut pending = self.pending.lock();
+            let num_committed = pending.entry(epoch.id).or_insert(0);
+            *num_committed += 1;
+            if *num_committed < self.num_sinks {
+                return Ok(());
+            }
+            pending.remove(&epoch.id);
+        }
+
+        // Every source closes every epoch, so any sink downstream of a source has its position.
+        for (handle, source) in &self.sources {
+            if let Some(op_id) = epoch.details.get(handle) {
+                source.on_checkpoint((op_id.txid, op_id.seq_in_tx))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dozer_types::node::{NodeHandle, OpIdentifier};
+
+    use crate::channels::SourceChannelForwarder;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CheckpointedSource {
+        checkpoints: Mutex<Vec<(u64, u64)>>,
+    }
+
+    impl Source for CheckpointedSource {
+        fn can_start_from(&self, _last_checkpoint: (u64, u64)) -> Result<bool, ExecutionError> {
+            Ok(true)
+        }
+
+        fn start(
+            &self,
+            _fw: &mut dyn SourceChannelForwarder,
+            _last_checkpoint: Option<(u64, u64)>,
+        ) -> Result<(), ExecutionError> {
+            Ok(())
+        }
+
+        fn on_checkpoint(&self, checkpoint: (u64, u64)) -> Result<(), ExecutionError> {
+            self.checkpoints.lock().push(checkpoint);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_notifier() {
+        let handle = NodeHandle::new(None, "src".to_string());
+        let source = Arc::new(CheckpointedSource::default());
+        let notifier =
+            CheckpointNotifier::new(vec![(handle.clone(), source.clone() as Arc<dyn Source>)], 2);
+
+        let epoch0 = Epoch::from(0, handle.clone(), 3, 1);
+        let epoch1 = Epoch::from(1, handle, 7, 0);
+        notifier.on_sink_commit(&epoch0).unwrap();
+        notifier.on_sink_commit(&epoch1).unwrap();
+        // Not all sinks have committed the epochs.
+        assert!(source.checkpoints.lock().is_empty());
+
+        notifier.on_sink_commit(&epoch0).unwrap();
+        notifier.on_sink_commit(&epoch1).unwrap();
+        assert_eq!(*source.checkpoints.lock(), vec![(3, 1), (7, 0)]);
+    }
+
+    #[test]
+    fn test_merge_checkpoints() {
+        let src1 = NodeHandle::new(None, "src1".to_string());
+        let src2 = NodeHandle::new(None, "src2".to_string());
+        let checkpoint = |states: Vec<(NodeHandle, OpIdentifier)>| {
+            Some(states.into_iter().collect::<SourceStates>())
+        };
+
+        let merged = merge_checkpoints(&[
+            checkpoint(vec![
+                (src1.clone(), OpIdentifier::new(5, 0)),
+                (src2.clone(), OpIdentifier::new(3, 0)),
+            ]),
+            checkpoint(vec![(src1.clone(), OpIdentifier::new(4, 2))]),
+        ]);
+        assert_eq!(merged.get(&src1), Some(&OpIdentifier::new(4, 2)));
+        assert_eq!(merged.get(&src2), Some(&OpIdentifier::new(3, 0)));
+
+        let merged = merge_checkpoints(&[checkpoint(vec![(src1, OpIdentifier::new(5, 0))]), None]);
+        assert!(merged.is_empty());
+    }
+}
@@ -1,12 +1,14 @@
 use std::{path::PathBuf, sync::Arc};
 
 use daggy::petgraph::visit::IntoNodeIdentifiers;
-use dozer_types::node::{NodeHandle, OpIdentifier};
+use dozer_types::log::info;
+use dozer_types::node::{NodeHandle, OpIdentifier, SourceStates};
 
 use crate::{
+    checkpoint::merge_checkpoints,
     dag_schemas::{DagHaveSchemas, DagSchemas, EdgeType},
     errors::ExecutionError,
-    node::{ProcessorFactory, SinkFactory, Source},
+    node::{ProcessorFactory, Sink, Source},
     NodeKind as DagNodeKind,
 };
 
@@ -20,11 +22,12 @@ pub struct NodeType<T> {
 }
 
 #[derive(Debug)]
-/// Node kind, source, processor or sink. Source has a checkpoint to start from.
+/// Node kind, source, processor or sink. Source has a checkpoint to start from. Sinks are built
+/// here because whether they kept their data decides the checkpoint.
 pub enum NodeKind<T> {
     Source((Box<dyn Source>, Option<OpIdentifier>)),
     Processor(Arc<dyn ProcessorFactory<T>>),
-    Sink(Arc<dyn SinkFactory<T>>),
+    Sink(Box<dyn Sink>),
 }
 
 /// Checkpoint DAG determines the checkpoint to start the pipeline from.
@@ -33,21 +36,54 @@ pub enum NodeKind<T> {
 #[derive(Debug)]
 pub struct DagCheckpoint<T> {
     graph: daggy::Dag<NodeType<T>, EdgeType>,
+    /// The source states the pipeline resumes from, if it does.
+    checkpoint: Option<SourceStates>,
 }
 
 impl<T> DagCheckpoint<T> {
     pub fn new(dag_schemas: DagSchemas<T>, _path: PathBuf) -> Result<Self, ExecutionError> {
-        // Create node storages and sources.
-        let mut sources = vec![];
         let node_indexes = dag_schemas.graph().node_identifiers().collect::<Vec<_>>();
 
+        // Every sink persists the last epoch it committed together with its data. Sources resume
+        // from the merged state, as long as every sink has one.
+        let mut sinks = vec![];
+        let mut sink_checkpoints = vec![];
+        for node_index in node_indexes.iter().copied() {
+            let node = &dag_schemas.graph()[node_index];
+            if let DagNodeKind::Sink(sink) = &node.kind {
+                let input_schemas = dag_schemas.get_node_input_schemas(node_index);
+                let sink = sink.build(input_schemas)?;
+                let checkpoint = sink.checkpoint()?;
+                if checkpoint.is_none() {
+                    info!("[{}] Sink has no checkpoint to resume from", node.handle);
+                }
+                sink_checkpoints.push(checkpoint);
+                sinks.push(Some((node.handle.clone(), sink)));
+            } else {
+                sinks.push(None);
+            }
+        }
+        let source_states = merge_checkpoints(&sink_checkpoints);
+
+        // Create sources. The pipeline only resumes if every source can start from its state.
+        let mut sources = vec![];
+        let mut resume = !source_states.is_empty();
         for node_index in node_indexes.iter().copied() {
             let node = &dag_schemas.graph()[node_index];
             match &node.kind {
                 DagNodeKind::Source(source) => {
                     let output_schemas = dag_schemas.get_node_output_schemas(node_index);
                     let source = source.build(output_schemas)?;
-                    sources.push(Some(source));
+                    let checkpoint = match source_states.get(&node.handle) {
+                        Some(op_id) if source.can_start_from((op_id.txid, op_id.seq_in_tx))? => {
+                            Some(*op_id)
+                        }
+                        _ => {
+                            resume = false;
+                            None
+                        }
+                    };
+                    sources.push(Some((node.handle.clone(), source, checkpoint)));
                 }
                 DagNodeKind::Processor(_) | DagNodeKind::Sink(_) => {
                     sources.push(None);
@@ -55,28 +91,67 @@ impl<T> DagCheckpoint<T> {
             }
         }
 
+        if resume {
+            for (handle, _, checkpoint) in sources.iter().flatten() {
+                let op_id = checkpoint.expect("Every source has a checkpoint when resuming");
+                info!(
+                    "[{}] Resuming from checkpoint {}:{}",
+                    handle, op_id.txid, op_id.seq_in_tx
+                );
+            }
+        }
+
         // Create new graph.
         let graph = dag_schemas.into_graph().map_owned(
             |node_index, node| {
-                if let Some(source) = sources[node_index.index()].take() {
-                    NodeType {
-                        handle: node.handle,
-                        kind: NodeKind::Source((source, None)),
-                    }
+                let kind = if let Some((_, source, checkpoint)) = sources[node_index.index()].take()
+                {
+                    NodeKind::Source((source, checkpoint))
+                } else if let Some((_, sink)) = sinks[node_index.index()].take() {
+                    NodeKind::Sink(sink)
                 } else {
-                    NodeType {
-                        handle: node.handle,
-                        kind: match node.kind {
-                            DagNodeKind::Processor(processor) => NodeKind::Processor(processor),
-                            DagNodeKind::Sink(sink) => NodeKind::Sink(sink),
-                            DagNodeKind::Source(_) => unreachable!("We created all sources"),
-                        },
+                    match node.kind {
+                        DagNodeKind::Processor(processor) => NodeKind::Processor(processor),
+                        DagNodeKind::Source(_) | DagNodeKind::Sink(_) => {
+                            unreachable!("We created all sources and sinks")
+                        }
                     }
+                };
+                NodeType {
+                    handle: node.handle,
+                    kind,
                 }
             },
             |_, edge| edge,
         );
-        Ok(Self { graph })
+        let mut dag_checkpoint = Self {
+            graph,
+            checkpoint: Some(source_states),
+        };
+        if !resume {
+            dag_checkpoint.start_over()?;
+        }
+        Ok(dag_checkpoint)
+    }
+
+    /// Returns the source states the pipeline resumes from, or `None` if it starts over.
+    pub fn checkpoint(&self) -> Option<&SourceStates> {
+        self.checkpoint.as_ref()
+    }
+
+    /// Makes every source start from the beginning and resets every sink.
+    pub fn start_over(&mut self) -> Result<(), ExecutionError> {
+        self.checkpoint = None;
+        let node_indexes = self.graph.node_identifiers().collect::<Vec<_>>();
+        for node_index in node_indexes {
+            let node = &mut self.graph[node_index];
+            match &mut node.kind {
+                NodeKind::Source((_, checkpoint)) => *checkpoint = None,
+                NodeKind::Sink(sink) => sink.reset()?,
+                NodeKind::Processor(_) => (),
+            }
+        }
+        Ok(())
     }
 
     pub fn into_graph(self) -> daggy::Dag<NodeType<T>, EdgeType> {
@@ -50,6 +50,8 @@ pub enum ExecutionError {
     UnsupportedUpdateOperation(String),
     #[error("Delete operation not supported: {0}")]
     UnsupportedDeleteOperation(String),
+    #[error("Schema change not supported: {0}")]
+    UnsupportedSchemaChange(String),
     #[error("Invalid AppSource connection {0}. Already exists.")]
     AppSourceConnectionAlreadyExists(String),
     #[error("Failed to get primary key for `{0}`")]
@@ -60,6 +62,11 @@ pub enum ExecutionError {
         expected: Vec<String>,
         actual: Vec<String>,
     },
+    #[error("Invalid secondary index for `{endpoint_name}`: {reason}")]
+    InvalidSecondaryIndex {
+        endpoint_name: String,
+        reason: String,
+    },
 
     // Error forwarders
     #[error("Internal type error: {0}")]
@@ -144,6 +151,12 @@ pub enum SinkError {
 
     #[error("Failed to count the records during init in Cache: {0:?}, Error: {1:?}")]
     CacheCountFailed(String, #[source] BoxedError),
+
+    #[error("Failed to migrate Cache {0:?} to the new schema, Error: {1:?}")]
+    CacheMigrationFailed(String, #[source] BoxedError),
+
+    #[error("Failed to drop Cache: {0:?}, Error: {1:?}")]
+    CacheDropFailed(String, #[source] BoxedError),
 }
 
 #[derive(Error, Debug)]
@@ -1,11 +1,12 @@
 use crate::builder_dag::{BuilderDag, NodeKind};
+use crate::checkpoint::CheckpointNotifier;
 use crate::dag_schemas::DagSchemas;
 use crate::errors::ExecutionError;
 use crate::Dag;
 
 use daggy::petgraph::visit::IntoNodeIdentifiers;
 use dozer_types::node::NodeHandle;
-use dozer_types::types::Operation;
+use dozer_types::types::{Operation, Schema};
 
 use crate::epoch::Epoch;
 
@@ -25,6 +26,22 @@ pub struct ExecutorOptions {
     pub commit_sz: u32,
     pub channel_buffer_sz: usize,
     pub commit_time_threshold: Duration,
+    /// Maximum number of operations sent between nodes in one channel message. `1` sends every operation on its own.
+    pub batch_sz: usize,
+    /// Number of instances every processor that supports partitioning runs as. `1` runs each processor on a single thread.
+    pub parallelism: usize,
+    /// Overrides `parallelism` for individual processors, by node id.
+    pub processor_parallelism: HashMap<String, usize>,
+}
+
+impl ExecutorOptions {
+    /// Returns the number of instances the processor `handle` should run as.
+    pub fn parallelism_of(&self, handle: &NodeHandle) -> usize {
+        self.processor_parallelism
+            .get(&handle.id)
+            .copied()
+            .unwrap_or(self.parallelism)
+    }
 }
 
 impl Default for ExecutorOptions {
@@ -33,6 +50,9 @@ impl Default for ExecutorOptions {
             commit_sz: 10_000,
             channel_buffer_sz: 20_000,
             commit_time_threshold: Duration::from_millis(50),
+            batch_sz: 1,
+            parallelism: 1,
+            processor_parallelism: HashMap::new(),
         }
     }
 }
@@ -46,20 +66,24 @@ pub(crate) enum InputPortState {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExecutorOperation {
     Op { op: Operation },
+    OpBatch { ops: Vec<Operation> },
     Commit { epoch: Epoch },
     Terminate,
     SnapshottingDone {},
+    SchemaChanged { schema: Schema },
 }
 
 mod execution_dag;
 mod name;
 mod node;
+mod parallel_processor_node;
 mod processor_node;
 mod receiver_loop;
 mod sink_node;
 mod source_node;
 
 use node::Node;
+use parallel_processor_node::ParallelProcessorNode;
 use processor_node::ProcessorNode;
 use sink_node::SinkNode;
 
@@ -82,7 +106,7 @@ impl DagExecutor {
         options: ExecutorOptions,
     ) -> Result<Self, ExecutionError> {
         let dag_schemas = DagSchemas::new(dag)?;
-        let builder_dag = BuilderDag::new(dag_schemas, path)?;
+        let builder_dag = BuilderDag::new(dag_schemas, path, &options)?;
 
         Ok(Self {
             builder_dag,
@@ -101,6 +125,21 @@ impl DagExecutor {
             ExecutionDag::new(self.builder_dag, self.options.channel_buffer_sz)?;
         let node_indexes = execution_dag.graph().node_identifiers().collect::<Vec<_>>();
 
+        // The sinks tell the sources when they have all committed an epoch.
+        let mut sources = vec![];
+        let mut num_sinks = 0;
+        for node_index in node_indexes.iter().copied() {
+            let node = execution_dag.graph()[node_index]
+                .as_ref()
+                .expect("We created all nodes");
+            match &node.kind {
+                NodeKind::Source(source, _) => sources.push((node.handle.clone(), source.clone())),
+                NodeKind::Sink(_) => num_sinks += 1,
+                NodeKind::Processor(_) | NodeKind::ParallelProcessor(_, _) => (),
+            }
+        }
+        let checkpoint_notifier = Arc::new(CheckpointNotifier::new(sources, num_sinks));
+
         // Start the threads.
         let mut join_handles = HashMap::new();
         for node_index in node_indexes {
@@ -122,11 +161,18 @@ impl DagExecutor {
                     );
                 }
                 NodeKind::Processor(_) => {
-                    let processor_node = ProcessorNode::new(&mut execution_dag, node_index);
+                    let processor_node =
+                        ProcessorNode::new(&mut execution_dag, node_index, self.options.batch_sz);
                     join_handles.insert(node_handle, start_processor(processor_node)?);
                 }
+                NodeKind::ParallelProcessor(_, _) => {
+                    let processor_node =
+                        ParallelProcessorNode::new(&mut execution_dag, node_index, &self.options)?;
+                    join_handles.insert(node_handle, start_parallel_processor(processor_node)?);
+                }
                 NodeKind::Sink(_) => {
-                    let sink_node = SinkNode::new(&mut execution_dag, node_index);
+                    let sink_node =
+                        SinkNode::new(&mut execution_dag, node_index, checkpoint_notifier.clone());
                     join_handles.insert(node_handle, start_sink(sink_node)?);
                 }
             }
@@ -196,6 +242,18 @@ fn start_processor(processor: ProcessorNode) -> Result<JoinHandle<()>, Execution
         })?)
 }
 
+fn start_parallel_processor(
+    processor: ParallelProcessorNode,
+) -> Result<JoinHandle<()>, ExecutionError> {
+    Ok(Builder::new()
+        .name(processor.handle().to_string())
+        .spawn(move || {
+            if let Err(e) = processor.run() {
+                std::panic::panic_any(e);
+            }
+        })?)
+}
+
 fn start_sink(sink: SinkNode) -> Result<JoinHandle<()>, ExecutionError> {
     Ok(Builder::new().name(sink.handle().to_string()).spawn(|| {
         if let Err(e) = sink.run() {
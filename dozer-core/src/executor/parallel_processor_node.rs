@@ -0,0 +1,487 @@
+use std::collections::{BTreeMap, HashMap};
+use std::{borrow::Cow, mem::swap, thread::Builder, thread::JoinHandle};
+
+use crossbeam::channel::{bounded, Receiver, Sender};
+use daggy::NodeIndex;
+use dozer_types::log::warn;
+use dozer_types::node::NodeHandle;
+use dozer_types::types::{Operation, Record, Schema};
+
+use crate::{
+    builder_dag::NodeKind,
+    channels::ProcessorChannelForwarder,
+    epoch::Epoch,
+    errors::ExecutionError,
+    forwarder::{ProcessorChannelManager, StateWriter},
+    node::{Partitioning, PortHandle, Processor},
+};
+
+use super::{
+    execution_dag::ExecutionDag, name::Name, receiver_loop::ReceiverLoop, ExecutorOperation,
+    ExecutorOptions,
+};
+
+/// A processor running as several instances, each on its own thread.
+///
+/// The node thread routes every operation to one instance according to the processor's [`Partitioning`],
+/// tagging it with a sequence number. A merger thread puts the outputs of all instances back in input order
+/// before forwarding them, so downstream nodes see the same stream and epoch boundaries as from a single instance.
+#[derive(Debug)]
+pub struct ParallelProcessorNode {
+    /// Node handle in description DAG.
+    node_handle: NodeHandle,
+    /// Input port handles.
+    port_handles: Vec<PortHandle>,
+    /// Input data channels.
+    receivers: Vec<Receiver<ExecutorOperation>>,
+    /// Decides which instance processes an operation.
+    router: Router,
+    /// Input channels of the instances.
+    workers: Vec<Sender<WorkerInput>>,
+    /// Input channel of the merger, for the messages that don't go through the instances.
+    merger: Sender<(u64, WorkerOutput)>,
+    /// Sequence number of the next message.
+    next_seq: u64,
+    /// Threads of the instances and the merger.
+    join_handles: Vec<JoinHandle<Result<(), ExecutionError>>>,
+}
+
+impl ParallelProcessorNode {
+    pub fn new(
+        dag: &mut ExecutionDag,
+        node_index: NodeIndex,
+        options: &ExecutorOptions,
+    ) -> Result<Self, ExecutionError> {
+        let Some(node) = dag.node_weight_mut(node_index).take() else {
+            panic!("Must pass in a node")
+        };
+        let node_handle = node.handle;
+        let NodeKind::ParallelProcessor(processors, partitioning) = node.kind else {
+            panic!("Must pass in a parallel processor node");
+        };
+
+        let (port_handles, receivers) = dag.collect_receivers(node_index);
+
+        let (senders, record_writers) = dag.collect_senders_and_record_writers(node_index);
+
+        let state_writer = StateWriter::new(record_writers);
+        let channel_manager = ProcessorChannelManager::new(
+            node_handle.clone(),
+            senders,
+            state_writer,
+            true,
+            options.batch_sz,
+        );
+
+        let num_workers = processors.len();
+        let (merger, merger_receiver) = bounded(options.channel_buffer_sz);
+        let mut join_handles = vec![];
+        let mut workers = vec![];
+        for (partition, processor) in processors.into_iter().enumerate() {
+            let (worker, worker_receiver) = bounded(options.channel_buffer_sz);
+            let merger = merger.clone();
+            join_handles.push(
+                Builder::new()
+                    .name(format!("{node_handle}-{partition}"))
+                    .spawn(move || run_worker(processor, worker_receiver, merger))?,
+            );
+            workers.push(worker);
+        }
+        join_handles.push(
+            Builder::new()
+                .name(format!("{node_handle}-merger"))
+                .spawn(move || run_merger(num_workers, merger_receiver, channel_manager))?,
+        );
+
+        Ok(Self {
+            node_handle,
+            port_handles,
+            receivers,
+            router: Router::new(partitioning, num_workers),
+            workers,
+            merger,
+            next_seq: 0,
+            join_handles,
+        })
+    }
+
+    pub fn handle(&self) -> &NodeHandle {
+        &self.node_handle
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+
+    fn send_to_worker(
+        &mut self,
+        partition: usize,
+        port: PortHandle,
+        op: Operation,
+    ) -> Result<(), ExecutionError> {
+        let seq = self.next_seq();
+        self.workers[partition].send(WorkerInput::Op { seq, port, op })?;
+        Ok(())
+    }
+
+    fn broadcast(&mut self, input: impl Fn(u64) -> WorkerInput) -> Result<(), ExecutionError> {
+        let seq = self.next_seq();
+        for worker in &self.workers {
+            worker.send(input(seq))?;
+        }
+        Ok(())
+    }
+}
+
+impl Name for ParallelProcessorNode {
+    fn name(&self) -> Cow<str> {
+        Cow::Owned(self.node_handle.to_string())
+    }
+}
+
+impl ReceiverLoop for ParallelProcessorNode {
+    fn receivers(&mut self) -> Vec<Receiver<ExecutorOperation>> {
+        let mut result = vec![];
+        swap(&mut self.receivers, &mut result);
+        result
+    }
+
+    fn receiver_name(&self, index: usize) -> Cow<str> {
+        Cow::Owned(self.port_handles[index].to_string())
+    }
+
+    fn on_op(&mut self, index: usize, op: Operation) -> Result<(), ExecutionError> {
+        let port = self.port_handles[index];
+        match self.router.route(port, op) {
+            Route::One(partition, op) => self.send_to_worker(partition, port, op),
+            Route::Split { delete, insert } => {
+                self.send_to_worker(delete.0, port, delete.1)?;
+                self.send_to_worker(insert.0, port, insert.1)
+            }
+        }
+    }
+
+    fn on_commit(&mut self, epoch: &Epoch) -> Result<(), ExecutionError> {
+        self.broadcast(|seq| WorkerInput::Commit {
+            seq,
+            epoch: epoch.clone(),
+        })
+    }
+
+    fn on_terminate(&mut self) -> Result<(), ExecutionError> {
+        let seq = self.next_seq();
+        self.merger.send((seq, WorkerOutput::Terminate))?;
+        // Closing the input channels stops the instances.
+        self.workers.clear();
+
+        let mut result = Ok(());
+        for join_handle in self.join_handles.drain(..) {
+            let thread_result = join_handle
+                .join()
+                .unwrap_or(Err(ExecutionError::InternalThreadPanic));
+            if result.is_ok() {
+                result = thread_result;
+            }
+        }
+        result
+    }
+
+    fn on_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
+        let seq = self.next_seq();
+        self.merger.send((seq, WorkerOutput::SnapshottingDone))?;
+        Ok(())
+    }
+
+    fn on_schema_changed(&mut self, index: usize, schema: Schema) -> Result<(), ExecutionError> {
+        let port = self.port_handles[index];
+        self.broadcast(|seq| WorkerInput::SchemaChanged {
+            seq,
+            port,
+            schema: schema.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+enum WorkerInput {
+    Op {
+        seq: u64,
+        port: PortHandle,
+        op: Operation,
+    },
+    Commit {
+        seq: u64,
+        epoch: Epoch,
+    },
+    SchemaChanged {
+        seq: u64,
+        port: PortHandle,
+        schema: Schema,
+    },
+}
+
+#[derive(Debug)]
+enum WorkerOutput {
+    Ops(Vec<(Operation, PortHandle)>),
+    Commit(Epoch),
+    SchemaChanged(HashMap<PortHandle, Schema>),
+    SnapshottingDone,
+    Terminate,
+}
+
+impl WorkerOutput {
+    /// Number of messages with the same sequence number the merger waits for. Broadcasts are answered by every instance.
+    fn expected_count(&self, num_workers: usize) -> usize {
+        match self {
+            WorkerOutput::Commit(_) | WorkerOutput::SchemaChanged(_) => num_workers,
+            WorkerOutput::Ops(_) | WorkerOutput::SnapshottingDone | WorkerOutput::Terminate => 1,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CollectingForwarder {
+    ops: Vec<(Operation, PortHandle)>,
+}
+
+impl ProcessorChannelForwarder for CollectingForwarder {
+    fn send(&mut self, op: Operation, port: PortHandle) -> Result<(), ExecutionError> {
+        self.ops.push((op, port));
+        Ok(())
+    }
+}
+
+fn run_worker(
+    mut processor: Box<dyn Processor>,
+    receiver: Receiver<WorkerInput>,
+    merger: Sender<(u64, WorkerOutput)>,
+) -> Result<(), ExecutionError> {
+    let mut forwarder = CollectingForwarder::default();
+    for input in receiver {
+        let output = match input {
+            WorkerInput::Op { seq, port, op } => {
+                if let Err(e) = processor.process(port, op, &mut forwarder) {
+                    warn!("Processor error: {:?}", e);
+                }
+                (seq, WorkerOutput::Ops(std::mem::take(&mut forwarder.ops)))
+            }
+            WorkerInput::Commit { seq, epoch } => {
+                processor.commit(&epoch)?;
+                (seq, WorkerOutput::Commit(epoch))
+            }
+            WorkerInput::SchemaChanged { seq, port, schema } => {
+                let output_schemas = processor.on_schema_changed(port, &schema)?;
+                (seq, WorkerOutput::SchemaChanged(output_schemas))
+            }
+        };
+        merger.send(output)?;
+    }
+    Ok(())
+}
+
+fn run_merger(
+    num_workers: usize,
+    receiver: Receiver<(u64, WorkerOutput)>,
+    mut channel_manager: ProcessorChannelManager,
+) -> Result<(), ExecutionError> {
+    // Outputs that arrived ahead of their turn, with the number of instances that answered.
+    let mut pending = BTreeMap::<u64, (WorkerOutput, usize)>::new();
+    let mut next_seq = 0;
+    loop {
+        let (seq, output) = receiver
+            .recv()
+            .map_err(|_| ExecutionError::CannotReceiveFromChannel)?;
+        // All instances answer a broadcast with the same output, so we keep the first one.
+        pending.entry(seq).or_insert((output, 0)).1 += 1;
+
+        while let Some((output, count)) = pending.get(&next_seq) {
+            if *count < output.expected_count(num_workers) {
+                break;
+            }
+            let (output, _) = pending
+                .remove(&next_seq)
+                .expect("We just checked the entry exists");
+            next_seq += 1;
+
+            match output {
+                WorkerOutput::Ops(ops) => {
+                    for (op, port) in ops {
+                        channel_manager.send(op, port)?;
+                    }
+                }
+                WorkerOutput::Commit(epoch) => channel_manager.store_and_send_commit(&epoch)?,
+                WorkerOutput::SchemaChanged(output_schemas) => {
+                    for (port, schema) in output_schemas {
+                        channel_manager.send_schema_changed(schema, port)?;
+                    }
+                }
+                WorkerOutput::SnapshottingDone => channel_manager.send_snapshotting_done()?,
+                WorkerOutput::Terminate => return channel_manager.send_terminate(),
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    One(usize, Operation),
+    /// An update that moves a record to another partition becomes a delete and an insert.
+    Split {
+        delete: (usize, Operation),
+        insert: (usize, Operation),
+    },
+}
+
+#[derive(Debug)]
+struct Router {
+    partitioning: Partitioning,
+    num_partitions: usize,
+    next_partition: usize,
+}
+
+impl Router {
+    fn new(partitioning: Partitioning, num_partitions: usize) -> Self {
+        Self {
+            partitioning,
+            num_partitions,
+            next_partition: 0,
+        }
+    }
+
+    fn route(&mut self, port: PortHandle, op: Operation) -> Route {
+        match &self.partitioning {
+            Partitioning::Single => Route::One(0, op),
+            Partitioning::RoundRobin => {
+                let partition = self.next_partition;
+                self.next_partition = (self.next_partition + 1) % self.num_partitions;
+                Route::One(partition, op)
+            }
+            Partitioning::Hash(_) => match op {
+                Operation::Insert { new } => {
+                    Route::One(self.partition_of(port, &new), Operation::Insert { new })
+                }
+                Operation::Delete { old } => {
+                    Route::One(self.partition_of(port, &old), Operation::Delete { old })
+                }
+                Operation::Update { old, new } => {
+                    let old_partition = self.partition_of(port, &old);
+                    let new_partition = self.partition_of(port, &new);
+                    if old_partition == new_partition {
+                        Route::One(old_partition, Operation::Update { old, new })
+                    } else {
+                        Route::Split {
+                            delete: (old_partition, Operation::Delete { old }),
+                            insert: (new_partition, Operation::Insert { new }),
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    fn partition_of(&self, port: PortHandle, record: &Record) -> usize {
+        let Partitioning::Hash(key) = &self.partitioning else {
+            unreachable!("Only hash partitioning looks at the records")
+        };
+        match key.hash_key(port, record) {
+            Ok(hash) => (hash % self.num_partitions as u64) as usize,
+            // The processor will fail on this record too and report the error.
+            Err(_) => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use dozer_types::types::Field;
+
+    use crate::node::PartitionKey;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct FirstField;
+
+    impl PartitionKey for FirstField {
+        fn hash_key(&self, _port: PortHandle, record: &Record) -> Result<u64, ExecutionError> {
+            match record.values[0] {
+                Field::UInt(value) => Ok(value),
+                _ => Err(ExecutionError::InvalidType("UInt".to_string())),
+            }
+        }
+    }
+
+    fn record(key: u64) -> Record {
+        Record::new(None, vec![Field::UInt(key)], None)
+    }
+
+    #[test]
+    fn router_round_robin() {
+        let mut router = Router::new(Partitioning::RoundRobin, 3);
+        let partitions = (0..5)
+            .map(
+                |key| match router.route(0, Operation::Insert { new: record(key) }) {
+                    Route::One(partition, _) => partition,
+                    Route::Split { .. } => panic!("Round robin never splits"),
+                },
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(partitions, vec![0, 1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn router_hash() {
+        let mut router = Router::new(Partitioning::Hash(Arc::new(FirstField)), 3);
+        assert_eq!(
+            router.route(0, Operation::Insert { new: record(4) }),
+            Route::One(1, Operation::Insert { new: record(4) })
+        );
+        assert_eq!(
+            router.route(
+                0,
+                Operation::Update {
+                    old: record(4),
+                    new: record(7)
+                }
+            ),
+            Route::One(
+                1,
+                Operation::Update {
+                    old: record(4),
+                    new: record(7)
+                }
+            )
+        );
+        assert_eq!(
+            router.route(
+                0,
+                Operation::Update {
+                    old: record(4),
+                    new: record(5)
+                }
+            ),
+            Route::Split {
+                delete: (1, Operation::Delete { old: record(4) }),
+                insert: (2, Operation::Insert { new: record(5) }),
+            }
+        );
+        assert_eq!(
+            router.route(
+                0,
+                Operation::Delete {
+                    old: Record::new(None, vec![Field::Null], None)
+                }
+            ),
+            Route::One(
+                0,
+                Operation::Delete {
+                    old: Record::new(None, vec![Field::Null], None)
+                }
+            )
+        );
+    }
+}
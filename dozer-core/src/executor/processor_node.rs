@@ -4,6 +4,7 @@ use crossbeam::channel::Receiver;
 use daggy::NodeIndex;
 use dozer_types::log::warn;
 use dozer_types::node::NodeHandle;
+use dozer_types::types::Operation;
 
 use crate::{
     builder_dag::NodeKind,
@@ -32,7 +33,7 @@ pub struct ProcessorNode {
 }
 
 impl ProcessorNode {
-    pub fn new(dag: &mut ExecutionDag, node_index: NodeIndex) -> Self {
+    pub fn new(dag: &mut ExecutionDag, node_index: NodeIndex, batch_sz: usize) -> Self {
         let Some(node) = dag.node_weight_mut(node_index).take() else {
             panic!("Must pass in a node")
         };
@@ -46,8 +47,13 @@ impl ProcessorNode {
         let (senders, record_writers) = dag.collect_senders_and_record_writers(node_index);
 
         let state_writer = StateWriter::new(record_writers);
-        let channel_manager =
-            ProcessorChannelManager::new(node_handle.clone(), senders, state_writer, true);
+        let channel_manager = ProcessorChannelManager::new(
+            node_handle.clone(),
+            senders,
+            state_writer,
+            true,
+            batch_sz,
+        );
 
         Self {
             node_handle,
@@ -96,6 +102,16 @@ impl ReceiverLoop for ProcessorNode {
         Ok(())
     }
 
+    fn on_op_batch(&mut self, index: usize, ops: Vec<Operation>) -> Result<(), ExecutionError> {
+        let result =
+            self.processor
+                .process_batch(self.port_handles[index], ops, &mut self.channel_manager);
+        if let Err(e) = result {
+            warn!("Processor error: {:?}", e);
+        }
+        Ok(())
+    }
+
     fn on_commit(&mut self, epoch: &crate::epoch::Epoch) -> Result<(), ExecutionError> {
         self.processor.commit(epoch)?;
         self.channel_manager.store_and_send_commit(epoch)
@@ -108,4 +124,18 @@ impl ReceiverLoop for ProcessorNode {
     fn on_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
         self.channel_manager.send_snapshotting_done()
     }
+
+    fn on_schema_changed(
+        &mut self,
+        index: usize,
+        schema: dozer_types::types::Schema,
+    ) -> Result<(), ExecutionError> {
+        let output_schemas = self
+            .processor
+            .on_schema_changed(self.port_handles[index], &schema)?;
+        for (port, schema) in output_schemas {
+            self.channel_manager.send_schema_changed(schema, port)?;
+        }
+        Ok(())
+    }
 }
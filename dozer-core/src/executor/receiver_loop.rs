@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crossbeam::channel::{Receiver, Select};
 use dozer_types::log::debug;
-use dozer_types::types::Operation;
+use dozer_types::types::{Operation, Schema};
 
 use crate::{epoch::Epoch, errors::ExecutionError};
 
@@ -18,12 +18,21 @@ pub trait ReceiverLoop: Name {
     fn receiver_name(&self, index: usize) -> Cow<str>;
     /// Responds to `op` from the receiver at `index`.
     fn on_op(&mut self, index: usize, op: Operation) -> Result<(), ExecutionError>;
+    /// Responds to a batch of `ops` from the receiver at `index`. Defaults to calling [`on_op`] for each operation.
+    fn on_op_batch(&mut self, index: usize, ops: Vec<Operation>) -> Result<(), ExecutionError> {
+        for op in ops {
+            self.on_op(index, op)?;
+        }
+        Ok(())
+    }
     /// Responds to `commit` of `epoch`.
     fn on_commit(&mut self, epoch: &Epoch) -> Result<(), ExecutionError>;
     /// Responds to `terminate`.
     fn on_terminate(&mut self) -> Result<(), ExecutionError>;
     /// Responds to `SnapshottingDone`.
     fn on_snapshotting_done(&mut self) -> Result<(), ExecutionError>;
+    /// Responds to `SchemaChanged` from the receiver at `index`.
+    fn on_schema_changed(&mut self, index: usize, schema: Schema) -> Result<(), ExecutionError>;
 
     /// The loop implementation, calls [`on_op`], [`on_commit`] and [`on_terminate`] at appropriate times.
     fn receiver_loop(&mut self) -> Result<(), ExecutionError> {
@@ -48,6 +57,9 @@ pub trait ReceiverLoop: Name {
                 ExecutorOperation::Op { op } => {
                     self.on_op(index, op)?;
                 }
+                ExecutorOperation::OpBatch { ops } => {
+                    self.on_op_batch(index, ops)?;
+                }
                 ExecutorOperation::Commit { epoch } => {
                     assert_eq!(epoch.id, common_epoch.id);
                     commits_received += 1;
@@ -76,6 +88,9 @@ pub trait ReceiverLoop: Name {
                     }
                 }
                 ExecutorOperation::SnapshottingDone {} => self.on_snapshotting_done()?,
+                ExecutorOperation::SchemaChanged { schema } => {
+                    self.on_schema_changed(index, schema)?
+                }
             }
         }
     }
@@ -106,6 +121,7 @@ mod tests {
         ops: Vec<(usize, Operation)>,
         commits: Vec<Epoch>,
         snapshotting_done: Vec<()>,
+        schema_changes: Vec<(usize, Schema)>,
         num_terminations: usize,
     }
 
@@ -145,6 +161,15 @@ mod tests {
             self.snapshotting_done.push(());
             Ok(())
         }
+
+        fn on_schema_changed(
+            &mut self,
+            index: usize,
+            schema: Schema,
+        ) -> Result<(), ExecutionError> {
+            self.schema_changes.push((index, schema));
+            Ok(())
+        }
     }
 
     impl TestReceiverLoop {
@@ -156,6 +181,7 @@ mod tests {
                     ops: vec![],
                     commits: vec![],
                     snapshotting_done: vec![],
+                    schema_changes: vec![],
                     num_terminations: 0,
                 },
                 senders,
@@ -184,6 +210,21 @@ mod tests {
         assert_eq!(test_loop.snapshotting_done, vec![()])
     }
 
+    #[test]
+    fn receiver_loop_forwards_schema_changed() {
+        let (mut test_loop, senders) = TestReceiverLoop::new(2);
+        let schema = Schema::empty();
+        senders[1]
+            .send(ExecutorOperation::SchemaChanged {
+                schema: schema.clone(),
+            })
+            .unwrap();
+        senders[0].send(ExecutorOperation::Terminate).unwrap();
+        senders[1].send(ExecutorOperation::Terminate).unwrap();
+        test_loop.receiver_loop().unwrap();
+        assert_eq!(test_loop.schema_changes, vec![(1, schema)]);
+    }
+
     #[test]
     fn receiver_loop_forwards_op() {
         let (mut test_loop, senders) = TestReceiverLoop::new(2);
@@ -201,6 +242,26 @@ mod tests {
         assert_eq!(test_loop.ops, vec![(0, Operation::Insert { new: record })]);
     }
 
+    #[test]
+    fn receiver_loop_forwards_op_batch() {
+        let (mut test_loop, senders) = TestReceiverLoop::new(2);
+        let ops = (0..3)
+            .map(|i| Operation::Insert {
+                new: Record::new(None, vec![Field::Int(i)], None),
+            })
+            .collect::<Vec<_>>();
+        senders[1]
+            .send(ExecutorOperation::OpBatch { ops: ops.clone() })
+            .unwrap();
+        senders[0].send(ExecutorOperation::Terminate).unwrap();
+        senders[1].send(ExecutorOperation::Terminate).unwrap();
+        test_loop.receiver_loop().unwrap();
+        assert_eq!(
+            test_loop.ops,
+            ops.into_iter().map(|op| (1, op)).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn receiver_loop_merges_commit_epoch_and_increases_epoch_id() {
         let (mut test_loop, senders) = TestReceiverLoop::new(2);
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, mem::swap};
+use std::{borrow::Cow, collections::HashMap, mem::swap, sync::Arc};
 
 use crossbeam::channel::Receiver;
 use daggy::NodeIndex;
@@ -6,6 +6,7 @@ use dozer_types::{log::debug, node::NodeHandle};
 
 use crate::{
     builder_dag::NodeKind,
+    checkpoint::CheckpointNotifier,
     epoch::Epoch,
     errors::ExecutionError,
     forwarder::StateWriter,
@@ -28,10 +29,16 @@ pub struct SinkNode {
     sink: Box<dyn Sink>,
     /// This node's state writer, for writing metadata and port state.
     state_writer: StateWriter,
+    /// Tells the sources once all sinks have committed an epoch.
+    checkpoint_notifier: Arc<CheckpointNotifier>,
 }
 
 impl SinkNode {
-    pub fn new(dag: &mut ExecutionDag, node_index: NodeIndex) -> Self {
+    pub fn new(
+        dag: &mut ExecutionDag,
+        node_index: NodeIndex,
+        checkpoint_notifier: Arc<CheckpointNotifier>,
+    ) -> Self {
         let Some(node) = dag.node_weight_mut(node_index).take() else {
             panic!("Must pass in a node")
         };
@@ -50,6 +57,7 @@ impl SinkNode {
             receivers,
             sink,
             state_writer,
+            checkpoint_notifier,
         }
     }
 
@@ -83,10 +91,20 @@ impl ReceiverLoop for SinkNode {
         self.sink.process(self.port_handles[index], op)
     }
 
+    fn on_op_batch(
+        &mut self,
+        index: usize,
+        ops: Vec<dozer_types::types::Operation>,
+    ) -> Result<(), ExecutionError> {
+        self.sink.process_batch(self.port_handles[index], ops)
+    }
+
     fn on_commit(&mut self, epoch: &Epoch) -> Result<(), ExecutionError> {
         debug!("[{}] Checkpointing - {}", self.node_handle, epoch);
-        self.sink.commit()?;
-        self.state_writer.store_commit_info(epoch)
+        // The sink stores the epoch with its data, so the checkpoint is never ahead of or behind it.
+        self.sink.commit(epoch)?;
+        self.state_writer.store_commit_info(epoch)?;
+        self.checkpoint_notifier.on_sink_commit(epoch)
     }
 
     fn on_terminate(&mut self) -> Result<(), ExecutionError> {
@@ -96,4 +114,13 @@ impl ReceiverLoop for SinkNode {
     fn on_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
         self.sink.on_source_snapshotting_done()
     }
+
+    fn on_schema_changed(
+        &mut self,
+        index: usize,
+        schema: dozer_types::types::Schema,
+    ) -> Result<(), ExecutionError> {
+        self.sink
+            .on_schema_changed(self.port_handles[index], &schema)
+    }
 }
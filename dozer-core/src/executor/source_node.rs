@@ -35,7 +35,7 @@ pub struct SourceSenderNode {
     /// Node handle in description DAG.
     node_handle: NodeHandle,
     /// The source.
-    source: Box<dyn Source>,
+    source: Arc<dyn Source>,
     /// Last checkpointed output data sequence number.
     last_checkpoint: Option<OpIdentifier>,
     /// The forwarder that will be passed to the source for outputting data.
@@ -176,7 +176,9 @@ pub fn create_source_nodes(
         true,
         options.commit_sz,
         options.commit_time_threshold,
+        options.batch_sz,
         dag.epoch_manager().clone(),
+        last_checkpoint,
     );
     let source_listener_node = SourceListenerNode {
         node_handle,
@@ -9,8 +9,8 @@ use crate::record_store::RecordWriter;
 use crossbeam::channel::Sender;
 use dozer_types::ingestion_types::{IngestionMessage, IngestionMessageKind};
 use dozer_types::log::debug;
-use dozer_types::node::NodeHandle;
-use dozer_types::types::Operation;
+use dozer_types::node::{NodeHandle, OpIdentifier};
+use dozer_types::types::{Operation, Schema};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -44,6 +44,9 @@ struct ChannelManager {
     senders: HashMap<PortHandle, Vec<Sender<ExecutorOperation>>>,
     state_writer: StateWriter,
     stateful: bool,
+    batch_sz: usize,
+    /// Operations buffered per port, waiting to be sent as one `OpBatch`.
+    pending: HashMap<PortHandle, Vec<Operation>>,
 }
 
 impl ChannelManager {
@@ -53,13 +56,29 @@ impl ChannelManager {
             op = self.state_writer.store_op(op, &port_id)?;
         }
 
+        if self.batch_sz <= 1 {
+            return self.send_to_port(ExecutorOperation::Op { op }, port_id);
+        }
+
+        let pending = self.pending.entry(port_id).or_default();
+        pending.push(op);
+        if pending.len() >= self.batch_sz {
+            self.flush_port(port_id)?;
+        }
+
+        Ok(())
+    }
+
+    fn send_to_port(
+        &self,
+        exec_op: ExecutorOperation,
+        port_id: PortHandle,
+    ) -> Result<(), ExecutionError> {
         let senders = self
             .senders
             .get(&port_id)
             .ok_or(InvalidPortHandle(port_id))?;
 
-        let exec_op = ExecutorOperation::Op { op };
-
         if let Some((last_sender, senders)) = senders.split_last() {
             for sender in senders {
                 sender.send(exec_op.clone())?;
@@ -70,7 +89,28 @@ impl ChannelManager {
         Ok(())
     }
 
-    fn send_terminate(&self) -> Result<(), ExecutionError> {
+    fn flush_port(&mut self, port_id: PortHandle) -> Result<(), ExecutionError> {
+        let Some(pending) = self.pending.get_mut(&port_id) else {
+            return Ok(());
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let ops = std::mem::replace(pending, Vec::with_capacity(self.batch_sz));
+        self.send_to_port(ExecutorOperation::OpBatch { ops }, port_id)
+    }
+
+    /// Sends all buffered operations. Must be called before any control message, so that ordering is preserved.
+    fn flush(&mut self) -> Result<(), ExecutionError> {
+        let ports = self.pending.keys().copied().collect::<Vec<_>>();
+        for port_id in ports {
+            self.flush_port(port_id)?;
+        }
+        Ok(())
+    }
+
+    fn send_terminate(&mut self) -> Result<(), ExecutionError> {
+        self.flush()?;
         for senders in self.senders.values() {
             for sender in senders {
                 sender.send(ExecutorOperation::Terminate)?;
@@ -80,7 +120,8 @@ impl ChannelManager {
         Ok(())
     }
 
-    fn send_snapshotting_done(&self) -> Result<(), ExecutionError> {
+    fn send_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
+        self.flush()?;
         for senders in self.senders.values() {
             for sender in senders {
                 sender.send(ExecutorOperation::SnapshottingDone {})?;
@@ -90,7 +131,28 @@ impl ChannelManager {
         Ok(())
     }
 
+    fn send_schema_changed(
+        &mut self,
+        schema: Schema,
+        port_id: PortHandle,
+    ) -> Result<(), ExecutionError> {
+        self.flush_port(port_id)?;
+        let senders = self
+            .senders
+            .get(&port_id)
+            .ok_or(InvalidPortHandle(port_id))?;
+
+        for sender in senders {
+            sender.send(ExecutorOperation::SchemaChanged {
+                schema: schema.clone(),
+            })?;
+        }
+
+        Ok(())
+    }
+
     fn store_and_send_commit(&mut self, epoch: &Epoch) -> Result<(), ExecutionError> {
+        self.flush()?;
         debug!("[{}] Checkpointing - {}", self.owner, &epoch);
         self.state_writer.store_commit_info(epoch)?;
 
@@ -109,12 +171,15 @@ impl ChannelManager {
         senders: HashMap<PortHandle, Vec<Sender<ExecutorOperation>>>,
         state_writer: StateWriter,
         stateful: bool,
+        batch_sz: usize,
     ) -> Self {
         Self {
             owner,
             senders,
             state_writer,
             stateful,
+            batch_sz,
+            pending: HashMap::new(),
         }
     }
 }
@@ -141,13 +206,15 @@ impl SourceChannelManager {
         stateful: bool,
         commit_sz: u32,
         max_duration_between_commits: Duration,
+        batch_sz: usize,
         epoch_manager: Arc<EpochManager>,
+        last_checkpoint: Option<OpIdentifier>,
     ) -> Self {
+        let last_checkpoint = last_checkpoint.unwrap_or_default();
         Self {
-            manager: ChannelManager::new(owner.clone(), senders, state_writer, stateful),
-            // FIXME: Read curr_txid and curr_seq_in_tx from persisted state.
-            curr_txid: 0,
-            curr_seq_in_tx: 0,
+            manager: ChannelManager::new(owner.clone(), senders, state_writer, stateful, batch_sz),
+            curr_txid: last_checkpoint.txid,
+            curr_seq_in_tx: last_checkpoint.seq_in_tx,
             source_handle: owner,
             commit_sz,
             num_uncommitted_ops: 0,
@@ -210,6 +277,10 @@ impl SourceChannelManager {
                 self.manager.send_snapshotting_done()?;
                 self.commit(request_termination)
             }
+            IngestionMessageKind::SchemaChanged(schema) => {
+                self.manager.send_schema_changed(schema, port)?;
+                self.trigger_commit_if_needed(request_termination)
+            }
         }
     }
 
@@ -229,9 +300,10 @@ impl ProcessorChannelManager {
         senders: HashMap<PortHandle, Vec<Sender<ExecutorOperation>>>,
         state_writer: StateWriter,
         stateful: bool,
+        batch_sz: usize,
     ) -> Self {
         Self {
-            manager: ChannelManager::new(owner, senders, state_writer, stateful),
+            manager: ChannelManager::new(owner, senders, state_writer, stateful, batch_sz),
         }
     }
 
@@ -239,13 +311,21 @@ impl ProcessorChannelManager {
         self.manager.store_and_send_commit(epoch)
     }
 
-    pub fn send_terminate(&self) -> Result<(), ExecutionError> {
+    pub fn send_terminate(&mut self) -> Result<(), ExecutionError> {
         self.manager.send_terminate()
     }
 
-    pub fn send_snapshotting_done(&self) -> Result<(), ExecutionError> {
+    pub fn send_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
         self.manager.send_snapshotting_done()
     }
+
+    pub fn send_schema_changed(
+        &mut self,
+        schema: Schema,
+        port: PortHandle,
+    ) -> Result<(), ExecutionError> {
+        self.manager.send_schema_changed(schema, port)
+    }
 }
 
 impl ProcessorChannelForwarder for ProcessorChannelManager {
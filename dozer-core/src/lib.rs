@@ -2,6 +2,7 @@ pub mod app;
 pub mod appsource;
 mod builder_dag;
 pub mod channels;
+pub mod checkpoint;
 mod dag_impl;
 pub use dag_impl::*;
 mod dag_checkpoint;
@@ -2,9 +2,11 @@ use crate::channels::{ProcessorChannelForwarder, SourceChannelForwarder};
 use crate::epoch::Epoch;
 use crate::errors::ExecutionError;
 
-use dozer_types::types::{Operation, Schema};
+use dozer_types::node::SourceStates;
+use dozer_types::types::{Operation, Record, Schema};
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
 
 pub type PortHandle = u16;
 
@@ -55,6 +57,31 @@ pub trait Source: Send + Sync + Debug {
         fw: &mut dyn SourceChannelForwarder,
         last_checkpoint: Option<(u64, u64)>,
     ) -> Result<(), ExecutionError>;
+
+    /// Called once every sink has committed the data up to `checkpoint`, the identifier of the last message of this source in an epoch.
+    /// The pipeline doesn't resume from before it anymore.
+    ///
+    /// It's called from the sink threads, while [`Source::start`] may be running.
+    fn on_checkpoint(&self, _checkpoint: (u64, u64)) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+}
+
+/// Computes the key that operations are routed on when a processor runs as several instances.
+pub trait PartitionKey: Send + Sync + Debug {
+    /// Hashes the key of `record`, which arrived on `port`. Records that must be processed by the same instance must hash to the same value.
+    fn hash_key(&self, port: PortHandle, record: &Record) -> Result<u64, ExecutionError>;
+}
+
+/// How the input of a processor can be split across several instances of it.
+#[derive(Debug, Clone)]
+pub enum Partitioning {
+    /// The processor must run as a single instance.
+    Single,
+    /// The processor is stateless, so any instance can process any operation.
+    RoundRobin,
+    /// Operations are routed by the hash of their key, so all operations on the same key reach the same instance.
+    Hash(Arc<dyn PartitionKey>),
 }
 
 pub trait ProcessorFactory<T>: Send + Sync + Debug {
@@ -70,16 +97,84 @@ pub trait ProcessorFactory<T>: Send + Sync + Debug {
         input_schemas: HashMap<PortHandle, Schema>,
         output_schemas: HashMap<PortHandle, Schema>,
     ) -> Result<Box<dyn Processor>, ExecutionError>;
+
+    /// Returns how the input of this processor can be split when it's configured to run as several instances.
+    fn partitioning(
+        &self,
+        _input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Partitioning, ExecutionError> {
+        Ok(Partitioning::Single)
+    }
+
+    /// Builds the instance processing `partition` of the input, when the processor runs as `num_partitions` instances.
+    ///
+    /// Processors that keep named state must give every partition its own. Records are assigned to partitions depending on `num_partitions`,
+    /// so the state of a partition can't be reused with a different number of partitions.
+    fn build_partition(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        output_schemas: HashMap<PortHandle, Schema>,
+        _partition: usize,
+        _num_partitions: usize,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        self.build(input_schemas, output_schemas)
+    }
 }
 
 pub trait Processor: Send + Sync + Debug {
-    fn commit(&self, epoch_details: &Epoch) -> Result<(), ExecutionError>;
+    /// Whether the state of the processor is the one it had committed at the epoch with source states `checkpoint`, which the pipeline would resume from.
+    ///
+    /// Sources only resume from the checkpoint if every processor is. Otherwise the pipeline starts over and [`Processor::reset`] is called.
+    fn is_resumable(&self, _checkpoint: &SourceStates) -> bool {
+        true
+    }
+
+    /// Called before the pipeline starts, if it starts over instead of resuming from the checkpoint. The processor must drop the state of previous runs.
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn commit(&mut self, epoch_details: &Epoch) -> Result<(), ExecutionError>;
     fn process(
         &mut self,
         from_port: PortHandle,
         op: Operation,
         fw: &mut dyn ProcessorChannelForwarder,
     ) -> Result<(), ExecutionError>;
+
+    /// Processes a batch of operations received on `from_port`, in order.
+    ///
+    /// The default implementation calls [`Processor::process`] for every operation, even if some of them fail,
+    /// and returns the first error.
+    fn process_batch(
+        &mut self,
+        from_port: PortHandle,
+        ops: Vec<Operation>,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        let mut result = Ok(());
+        for op in ops {
+            let op_result = self.process(from_port, op, fw);
+            if result.is_ok() {
+                result = op_result;
+            }
+        }
+        result
+    }
+
+    /// Called when an upstream node changes the schema on `from_port`, before any operation following the new schema arrives.
+    ///
+    /// Returns the new schemas of the output ports that changed as a result, which are forwarded downstream.
+    /// Output ports that are not returned keep their schemas.
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        _schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        Err(ExecutionError::UnsupportedSchemaChange(
+            std::any::type_name::<Self>().to_string(),
+        ))
+    }
 }
 
 pub trait SinkFactory<T>: Send + Sync + Debug {
@@ -95,8 +190,48 @@ pub trait SinkFactory<T>: Send + Sync + Debug {
 }
 
 pub trait Sink: Send + Sync + Debug {
-    fn commit(&mut self) -> Result<(), ExecutionError>;
+    /// Returns the source states of the last epoch the sink committed, read from where it keeps its data.
+    ///
+    /// Returns `None` if the sink has no data it can resume with. Sources only resume if every sink has a checkpoint. Otherwise the pipeline starts over and [`Sink::reset`] is called.
+    fn checkpoint(&self) -> Result<Option<SourceStates>, ExecutionError> {
+        Ok(None)
+    }
+
+    /// Called before the pipeline starts, if it starts over instead of resuming from the checkpoint. The sink must drop the data of previous runs, and its checkpoint with it.
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    /// Makes the operations processed so far durable, together with the source states of `epoch_details` as the checkpoint.
+    ///
+    /// Both must be written atomically, or the pipeline may skip or repeat operations when it resumes after a crash.
+    fn commit(&mut self, epoch_details: &Epoch) -> Result<(), ExecutionError>;
     fn process(&mut self, from_port: PortHandle, op: Operation) -> Result<(), ExecutionError>;
 
+    /// Processes a batch of operations received on `from_port`, in order.
+    ///
+    /// The default implementation calls [`Sink::process`] for every operation and stops at the first error.
+    fn process_batch(
+        &mut self,
+        from_port: PortHandle,
+        ops: Vec<Operation>,
+    ) -> Result<(), ExecutionError> {
+        for op in ops {
+            self.process(from_port, op)?;
+        }
+        Ok(())
+    }
+
     fn on_source_snapshotting_done(&mut self) -> Result<(), ExecutionError>;
+
+    /// Called when an upstream node changes the schema on `from_port`, before any operation following the new schema arrives.
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        _schema: &Schema,
+    ) -> Result<(), ExecutionError> {
+        Err(ExecutionError::UnsupportedSchemaChange(
+            std::any::type_name::<Self>().to_string(),
+        ))
+    }
 }
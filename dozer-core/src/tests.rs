@@ -3,6 +3,8 @@ mod app;
 #[cfg(test)]
 mod checkpoint_ns;
 #[cfg(test)]
+mod checkpoint_recovery;
+#[cfg(test)]
 mod common;
 #[cfg(test)]
 mod dag_base_create_errors;
@@ -11,6 +13,8 @@ mod dag_base_errors;
 #[cfg(test)]
 mod dag_base_run;
 #[cfg(test)]
+mod dag_parallel_run;
+#[cfg(test)]
 mod dag_ports;
 #[cfg(test)]
 mod dag_schemas;
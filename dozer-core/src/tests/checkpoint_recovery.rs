@@ -0,0 +1,404 @@
+use crate::channels::ProcessorChannelForwarder;
+use crate::chk;
+use crate::epoch::Epoch;
+use crate::errors::ExecutionError;
+use crate::executor::{DagExecutor, ExecutorOptions};
+use crate::node::{
+    OutputPortDef, OutputPortType, PortHandle, Processor, ProcessorFactory, Sink, SinkFactory,
+};
+use crate::tests::app::NoneContext;
+use crate::tests::sources::{GeneratorSourceFactory, GENERATOR_SOURCE_OUTPUT_PORT};
+use crate::{Dag, Endpoint, DEFAULT_PORT_HANDLE};
+use dozer_types::node::{NodeHandle, SourceStates};
+use dozer_types::parking_lot::Mutex;
+use dozer_types::types::{Field, Operation, Schema};
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tempdir::TempDir;
+
+const MESSAGES_COUNT: u64 = 1_000;
+
+/// A sink that only makes records visible on commit, together with the checkpoint, like the cache does.
+#[derive(Debug)]
+struct TransactionalSinkFactory {
+    committed: Arc<Mutex<Vec<String>>>,
+    checkpoint: Arc<Mutex<Option<SourceStates>>>,
+    /// Fails on the n-th processed record, simulating a crash.
+    fail_after: Option<u64>,
+    /// Stops the pipeline after the n-th processed record.
+    stop_after: Option<u64>,
+    /// Whether the sink reports that it kept its committed records.
+    resumable: bool,
+    running: Arc<AtomicBool>,
+}
+
+impl SinkFactory<NoneContext> for TransactionalSinkFactory {
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn prepare(
+        &self,
+        _input_schemas: HashMap<PortHandle, (Schema, NoneContext)>,
+    ) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Sink>, ExecutionError> {
+        Ok(Box::new(TransactionalSink {
+            committed: self.committed.clone(),
+            checkpoint: self.checkpoint.clone(),
+            pending: vec![],
+            num_processed: 0,
+            fail_after: self.fail_after,
+            stop_after: self.stop_after,
+            resumable: self.resumable,
+            running: self.running.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct TransactionalSink {
+    committed: Arc<Mutex<Vec<String>>>,
+    checkpoint: Arc<Mutex<Option<SourceStates>>>,
+    pending: Vec<String>,
+    num_processed: u64,
+    fail_after: Option<u64>,
+    stop_after: Option<u64>,
+    resumable: bool,
+    running: Arc<AtomicBool>,
+}
+
+impl Sink for TransactionalSink {
+    fn checkpoint(&self) -> Result<Option<SourceStates>, ExecutionError> {
+        if self.resumable {
+            Ok(self.checkpoint.lock().clone())
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        let mut committed = self.committed.lock();
+        committed.clear();
+        *self.checkpoint.lock() = None;
+        Ok(())
+    }
+
+    fn commit(&mut self, epoch_details: &Epoch) -> Result<(), ExecutionError> {
+        let mut committed = self.committed.lock();
+        committed.append(&mut self.pending);
+        *self.checkpoint.lock() = Some(epoch_details.details.clone());
+        Ok(())
+    }
+
+    fn process(&mut self, _from_port: PortHandle, op: Operation) -> Result<(), ExecutionError> {
+        self.num_processed += 1;
+        if self.fail_after == Some(self.num_processed) {
+            return Err(ExecutionError::InternalStringError(
+                "Simulated crash".to_string(),
+            ));
+        }
+
+        let Operation::Insert { new } = op else {
+            panic!("Generator source only inserts");
+        };
+        let Field::String(key) = &new.values[0] else {
+            panic!("Generator source key must be a string");
+        };
+        self.pending.push(key.clone());
+
+        if self.stop_after == Some(self.num_processed) {
+            self.running.store(false, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+}
+
+/// A processor that forwards every operation and keeps the source states of the last epoch it
+/// committed as its state, like the processors that store their state on disk.
+#[derive(Debug)]
+struct StatefulProcessorFactory {
+    committed_epoch: Arc<Mutex<Option<SourceStates>>>,
+}
+
+impl ProcessorFactory<NoneContext> for StatefulProcessorFactory {
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, (Schema, NoneContext)>,
+    ) -> Result<(Schema, NoneContext), ExecutionError> {
+        Ok(input_schemas.get(&DEFAULT_PORT_HANDLE).unwrap().clone())
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            DEFAULT_PORT_HANDLE,
+            OutputPortType::Stateless,
+        )]
+    }
+
+    fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        Ok(Box::new(StatefulProcessor {
+            committed_epoch: self.committed_epoch.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct StatefulProcessor {
+    committed_epoch: Arc<Mutex<Option<SourceStates>>>,
+}
+
+impl Processor for StatefulProcessor {
+    fn is_resumable(&self, checkpoint: &SourceStates) -> bool {
+        self.committed_epoch.lock().as_ref() == Some(checkpoint)
+    }
+
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        *self.committed_epoch.lock() = None;
+        Ok(())
+    }
+
+    fn commit(&mut self, epoch_details: &Epoch) -> Result<(), ExecutionError> {
+        *self.committed_epoch.lock() = Some(epoch_details.details.clone());
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        fw.send(op, DEFAULT_PORT_HANDLE)
+    }
+}
+
+fn run_dag(
+    path: &Path,
+    running: Arc<AtomicBool>,
+    sink_factory: TransactionalSinkFactory,
+    processor_factory: Option<StatefulProcessorFactory>,
+) -> Result<(), ExecutionError> {
+    let mut dag = Dag::new();
+
+    let source_handle = NodeHandle::new(None, "source".to_string());
+    let sink_handle = NodeHandle::new(None, "sink".to_string());
+
+    dag.add_source(
+        source_handle.clone(),
+        Arc::new(GeneratorSourceFactory::new(
+            MESSAGES_COUNT,
+            running.clone(),
+            false,
+        )),
+    );
+    dag.add_sink(sink_handle.clone(), Arc::new(sink_factory));
+    if let Some(processor_factory) = processor_factory {
+        let processor_handle = NodeHandle::new(None, "processor".to_string());
+        dag.add_processor(processor_handle.clone(), Arc::new(processor_factory));
+        chk!(dag.connect(
+            Endpoint::new(source_handle, GENERATOR_SOURCE_OUTPUT_PORT),
+            Endpoint::new(processor_handle.clone(), DEFAULT_PORT_HANDLE),
+        ));
+        chk!(dag.connect(
+            Endpoint::new(processor_handle, DEFAULT_PORT_HANDLE),
+            Endpoint::new(sink_handle, DEFAULT_PORT_HANDLE),
+        ));
+    } else {
+        chk!(dag.connect(
+            Endpoint::new(source_handle, GENERATOR_SOURCE_OUTPUT_PORT),
+            Endpoint::new(sink_handle, DEFAULT_PORT_HANDLE),
+        ));
+    }
+
+    let options = ExecutorOptions {
+        commit_sz: 100,
+        ..Default::default()
+    };
+    DagExecutor::new(dag, path.to_path_buf(), options)?
+        .start(running)?
+        .join()
+}
+
+#[test]
+fn test_resume_from_checkpoint_after_crash() {
+    let tmp_dir = chk!(TempDir::new("test"));
+    let committed = Arc::new(Mutex::new(vec![]));
+    let checkpoint = Arc::new(Mutex::new(None));
+
+    // The first run crashes in the middle of the stream.
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: Some(550),
+        stop_after: None,
+        resumable: true,
+        running: running.clone(),
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_dag(tmp_dir.path(), running.clone(), sink_factory, None)
+    }));
+    assert!(result.is_err());
+    running.store(false, Ordering::Relaxed);
+
+    let num_committed = committed.lock().len() as u64;
+    assert!(num_committed > 0 && num_committed < 550);
+
+    // The second run resumes right after the last committed record.
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: None,
+        stop_after: Some(MESSAGES_COUNT),
+        resumable: true,
+        running: running.clone(),
+    };
+    chk!(run_dag(tmp_dir.path(), running, sink_factory, None));
+
+    // Every record is in the sink exactly once.
+    let committed = committed.lock();
+    assert_eq!(committed.len() as u64, num_committed + MESSAGES_COUNT);
+    for (index, key) in committed.iter().enumerate() {
+        assert_eq!(key, &format!("key_{}", index + 1));
+    }
+}
+
+#[test]
+fn test_start_over_when_sink_lost_its_data() {
+    let tmp_dir = chk!(TempDir::new("test"));
+    let committed = Arc::new(Mutex::new(vec![]));
+    let checkpoint = Arc::new(Mutex::new(None));
+
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: None,
+        stop_after: Some(500),
+        resumable: true,
+        running: running.clone(),
+    };
+    chk!(run_dag(tmp_dir.path(), running, sink_factory, None));
+    assert!(!committed.lock().is_empty());
+
+    // The sink can't resume, so the source starts over and the sink is reset.
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: None,
+        stop_after: Some(MESSAGES_COUNT),
+        resumable: false,
+        running: running.clone(),
+    };
+    chk!(run_dag(tmp_dir.path(), running, sink_factory, None));
+
+    let committed = committed.lock();
+    assert_eq!(committed.len() as u64, MESSAGES_COUNT);
+    for (index, key) in committed.iter().enumerate() {
+        assert_eq!(key, &format!("key_{}", index + 1));
+    }
+}
+
+#[test]
+fn test_start_over_when_processor_lost_its_state() {
+    let tmp_dir = chk!(TempDir::new("test"));
+    let committed = Arc::new(Mutex::new(vec![]));
+    let checkpoint = Arc::new(Mutex::new(None));
+    let committed_epoch = Arc::new(Mutex::new(None));
+
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: None,
+        stop_after: Some(500),
+        resumable: true,
+        running: running.clone(),
+    };
+    let processor_factory = StatefulProcessorFactory {
+        committed_epoch: committed_epoch.clone(),
+    };
+    chk!(run_dag(
+        tmp_dir.path(),
+        running,
+        sink_factory,
+        Some(processor_factory)
+    ));
+    let num_committed = committed.lock().len() as u64;
+    assert!(num_committed > 0);
+
+    // The processor kept its state, so the pipeline resumes.
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: None,
+        stop_after: Some(200),
+        resumable: true,
+        running: running.clone(),
+    };
+    let processor_factory = StatefulProcessorFactory {
+        committed_epoch: committed_epoch.clone(),
+    };
+    chk!(run_dag(
+        tmp_dir.path(),
+        running,
+        sink_factory,
+        Some(processor_factory)
+    ));
+    assert_eq!(
+        committed.lock()[num_committed as usize],
+        format!("key_{}", num_committed + 1)
+    );
+
+    // The processor lost its state, so the source starts over and the sink is reset.
+    let running = Arc::new(AtomicBool::new(true));
+    let sink_factory = TransactionalSinkFactory {
+        committed: committed.clone(),
+        checkpoint: checkpoint.clone(),
+        fail_after: None,
+        stop_after: Some(MESSAGES_COUNT),
+        resumable: true,
+        running: running.clone(),
+    };
+    let processor_factory = StatefulProcessorFactory {
+        committed_epoch: Arc::new(Mutex::new(None)),
+    };
+    chk!(run_dag(
+        tmp_dir.path(),
+        running,
+        sink_factory,
+        Some(processor_factory)
+    ));
+
+    let committed = committed.lock();
+    assert_eq!(committed.len() as u64, MESSAGES_COUNT);
+    for (index, key) in committed.iter().enumerate() {
+        assert_eq!(key, &format!("key_{}", index + 1));
+    }
+}
@@ -76,7 +76,7 @@ struct ErrorProcessor {
 }
 
 impl Processor for ErrorProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
@@ -466,7 +466,7 @@ pub(crate) struct ErrSink {
     panic: bool,
 }
 impl Sink for ErrSink {
-    fn commit(&mut self) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
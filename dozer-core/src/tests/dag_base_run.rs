@@ -60,7 +60,7 @@ impl ProcessorFactory<NoneContext> for NoopProcessorFactory {
 pub(crate) struct NoopProcessor {}
 
 impl Processor for NoopProcessor {
-    fn commit(&self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
@@ -118,6 +118,51 @@ fn test_run_dag() {
     .unwrap();
 }
 
+#[test]
+fn test_run_dag_batched() {
+    let count: u64 = 1_000;
+
+    let mut dag = Dag::new();
+    let latch = Arc::new(AtomicBool::new(true));
+
+    let source_handle = NodeHandle::new(Some(1), 1.to_string());
+    let proc_handle = NodeHandle::new(Some(1), 2.to_string());
+    let sink_handle = NodeHandle::new(Some(1), 3.to_string());
+
+    dag.add_source(
+        source_handle.clone(),
+        Arc::new(GeneratorSourceFactory::new(count, latch.clone(), false)),
+    );
+    dag.add_processor(proc_handle.clone(), Arc::new(NoopProcessorFactory {}));
+    dag.add_sink(
+        sink_handle.clone(),
+        Arc::new(CountingSinkFactory::new(count, latch)),
+    );
+
+    chk!(dag.connect(
+        Endpoint::new(source_handle, GENERATOR_SOURCE_OUTPUT_PORT),
+        Endpoint::new(proc_handle.clone(), DEFAULT_PORT_HANDLE),
+    ));
+
+    chk!(dag.connect(
+        Endpoint::new(proc_handle, DEFAULT_PORT_HANDLE),
+        Endpoint::new(sink_handle, COUNTING_SINK_INPUT_PORT),
+    ));
+
+    // `count` is not a multiple of the batch size, so the last batch is only flushed on commit.
+    let options = ExecutorOptions {
+        batch_sz: 64,
+        ..Default::default()
+    };
+    let tmp_dir = chk!(TempDir::new("test"));
+    DagExecutor::new(dag, tmp_dir.path().to_path_buf(), options)
+        .unwrap()
+        .start(Arc::new(AtomicBool::new(true)))
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
 #[test]
 fn test_run_dag_and_stop() {
     let count: u64 = 1_000_000;
@@ -206,7 +251,7 @@ impl ProcessorFactory<NoneContext> for NoopJoinProcessorFactory {
 pub(crate) struct NoopJoinProcessor {}
 
 impl Processor for NoopJoinProcessor {
-    fn commit(&self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
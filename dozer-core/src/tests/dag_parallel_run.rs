@@ -0,0 +1,209 @@
+use crate::chk;
+use crate::epoch::Epoch;
+use crate::errors::ExecutionError;
+use crate::executor::{DagExecutor, ExecutorOptions};
+use crate::node::{
+    OutputPortDef, PartitionKey, Partitioning, PortHandle, Processor, ProcessorFactory, Sink,
+    SinkFactory,
+};
+use crate::tests::app::NoneContext;
+use crate::tests::dag_base_run::NoopProcessorFactory;
+use crate::tests::sources::{GeneratorSourceFactory, GENERATOR_SOURCE_OUTPUT_PORT};
+use crate::{Dag, Endpoint, DEFAULT_PORT_HANDLE};
+use dozer_types::node::NodeHandle;
+use dozer_types::types::{Field, Operation, Record, Schema};
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tempdir::TempDir;
+
+#[derive(Debug)]
+struct PartitionedNoopProcessorFactory {
+    inner: NoopProcessorFactory,
+    partitioning: Partitioning,
+}
+
+impl ProcessorFactory<NoneContext> for PartitionedNoopProcessorFactory {
+    fn get_output_schema(
+        &self,
+        output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, (Schema, NoneContext)>,
+    ) -> Result<(Schema, NoneContext), ExecutionError> {
+        self.inner.get_output_schema(output_port, input_schemas)
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        self.inner.get_input_ports()
+    }
+
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        self.inner.get_output_ports()
+    }
+
+    fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        self.inner.build(input_schemas, output_schemas)
+    }
+
+    fn partitioning(
+        &self,
+        _input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Partitioning, ExecutionError> {
+        Ok(self.partitioning.clone())
+    }
+}
+
+#[derive(Debug)]
+struct FirstFieldKey;
+
+impl PartitionKey for FirstFieldKey {
+    fn hash_key(&self, _port: PortHandle, record: &Record) -> Result<u64, ExecutionError> {
+        let mut hasher = DefaultHasher::new();
+        record.values[0].hash(&mut hasher);
+        Ok(hasher.finish())
+    }
+}
+
+const ORDER_CHECKING_SINK_INPUT_PORT: PortHandle = 90;
+
+/// Checks that records arrive in the order the generator source produced them.
+#[derive(Debug)]
+struct OrderCheckingSinkFactory {
+    expected: u64,
+    running: Arc<AtomicBool>,
+}
+
+impl SinkFactory<NoneContext> for OrderCheckingSinkFactory {
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![ORDER_CHECKING_SINK_INPUT_PORT]
+    }
+
+    fn prepare(
+        &self,
+        _input_schemas: HashMap<PortHandle, (Schema, NoneContext)>,
+    ) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Sink>, ExecutionError> {
+        Ok(Box::new(OrderCheckingSink {
+            expected: self.expected,
+            current: 0,
+            running: self.running.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct OrderCheckingSink {
+    expected: u64,
+    current: u64,
+    running: Arc<AtomicBool>,
+}
+
+impl Sink for OrderCheckingSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn process(&mut self, _from_port: PortHandle, op: Operation) -> Result<(), ExecutionError> {
+        self.current += 1;
+        let Operation::Insert { new } = op else {
+            panic!("Generator source only inserts");
+        };
+        assert_eq!(
+            new.values[0],
+            Field::String(format!("key_{}", self.current))
+        );
+        if self.current == self.expected {
+            self.running.store(false, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+}
+
+fn run_parallel_dag(partitioning: Partitioning, options: ExecutorOptions) {
+    let count: u64 = 10_000;
+
+    let mut dag = Dag::new();
+    let latch = Arc::new(AtomicBool::new(true));
+
+    let source_handle = NodeHandle::new(Some(1), 1.to_string());
+    let proc_handle = NodeHandle::new(Some(1), 2.to_string());
+    let sink_handle = NodeHandle::new(Some(1), 3.to_string());
+
+    dag.add_source(
+        source_handle.clone(),
+        Arc::new(GeneratorSourceFactory::new(count, latch.clone(), false)),
+    );
+    dag.add_processor(
+        proc_handle.clone(),
+        Arc::new(PartitionedNoopProcessorFactory {
+            inner: NoopProcessorFactory {},
+            partitioning,
+        }),
+    );
+    dag.add_sink(
+        sink_handle.clone(),
+        Arc::new(OrderCheckingSinkFactory {
+            expected: count,
+            running: latch,
+        }),
+    );
+
+    chk!(dag.connect(
+        Endpoint::new(source_handle, GENERATOR_SOURCE_OUTPUT_PORT),
+        Endpoint::new(proc_handle.clone(), DEFAULT_PORT_HANDLE),
+    ));
+
+    chk!(dag.connect(
+        Endpoint::new(proc_handle, DEFAULT_PORT_HANDLE),
+        Endpoint::new(sink_handle, ORDER_CHECKING_SINK_INPUT_PORT),
+    ));
+
+    let tmp_dir = chk!(TempDir::new("test"));
+    DagExecutor::new(dag, tmp_dir.path().to_path_buf(), options)
+        .unwrap()
+        .start(Arc::new(AtomicBool::new(true)))
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn test_run_dag_parallel_round_robin_preserves_order() {
+    run_parallel_dag(
+        Partitioning::RoundRobin,
+        ExecutorOptions {
+            parallelism: 4,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_run_dag_parallel_hash_preserves_order() {
+    run_parallel_dag(
+        Partitioning::Hash(Arc::new(FirstFieldKey)),
+        ExecutorOptions {
+            commit_sz: 100,
+            batch_sz: 16,
+            processor_parallelism: [(2.to_string(), 3)].into_iter().collect(),
+            ..Default::default()
+        },
+    );
+}
@@ -1,3 +1,4 @@
+use crate::epoch::Epoch;
 use crate::errors::ExecutionError;
 use crate::node::{PortHandle, Sink, SinkFactory};
 use crate::DEFAULT_PORT_HANDLE;
@@ -58,7 +59,7 @@ pub(crate) struct CountingSink {
     running: Arc<AtomicBool>,
 }
 impl Sink for CountingSink {
-    fn commit(&mut self) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
         // if self.current == self.expected {
         //     info!(
         //         "Received {} messages. Notifying sender to exit!",
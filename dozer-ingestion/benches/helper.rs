@@ -54,7 +54,7 @@ pub async fn get_connection_iterator(config: TestConfig) -> IngestionIterator {
             .unwrap_or_else(|| grpc_connector.list_tables().unwrap());
         let tables = grpc_connector.list_columns(tables).unwrap();
 
-        let res = grpc_connector.start(&ingestor, tables);
+        let res = grpc_connector.start(&ingestor, tables, None);
         if let Err(e) = res {
             error!("Error: {:?}", e);
         }
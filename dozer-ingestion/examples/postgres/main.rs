@@ -19,13 +19,16 @@ fn main() {
             .user("postgres")
             .dbname("pagila")
             .to_owned(),
+        ..Default::default()
     };
 
     let connector = PostgresConnector::new(postgres_config);
     let tables = connector
         .list_columns(vec![TableIdentifier::from_table_name("users".to_string())])
         .unwrap();
-    thread::spawn(move || -> Result<(), ConnectorError> { connector.start(&ingestor, tables) });
+    thread::spawn(move || -> Result<(), ConnectorError> {
+        connector.start(&ingestor, tables, None)
+    });
 
     let before = Instant::now();
     const BACKSPACE: char = 8u8 as char;
@@ -0,0 +1,400 @@
+use crate::connectors::delta_lake::ConnectorResult;
+use crate::connectors::object_store::map_value_to_dozer_field;
+use crate::errors::DeltaLakeError::{
+    ColumnNotFound, FileReadError, ParquetError, PartitionedTableNotSupported, UnknownChangeType,
+};
+use crate::errors::{ConnectorError, ObjectStoreConnectorError};
+use crate::ingestion::Ingestor;
+use deltalake::action::Action;
+use deltalake::arrow::array::{Array, StringArray};
+use deltalake::arrow::record_batch::RecordBatch;
+use deltalake::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use deltalake::{DeltaTable, PeekCommit};
+use dozer_types::ingestion_types::IngestionMessage;
+use dozer_types::log::{info, warn};
+use dozer_types::types::{Field, Operation, Record, SchemaIdentifier};
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::time::Duration;
+
+/// Name of the column that holds the change type in change data feed files.
+const CHANGE_TYPE_COLUMN: &str = "_change_type";
+
+/// A file of a commit whose rows are changes to the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ChangeFile {
+    /// A change data feed file, whose rows carry their own change type.
+    ChangeData(String),
+    /// A data file that was added, all its rows are inserted.
+    Added(String),
+    /// A data file that was removed, all its rows are deleted.
+    Removed(String),
+}
+
+impl ChangeFile {
+    fn path(&self) -> &str {
+        match self {
+            ChangeFile::ChangeData(path) | ChangeFile::Added(path) | ChangeFile::Removed(path) => {
+                path
+            }
+        }
+    }
+}
+
+/// Lists the files that describe the changes of a commit.
+///
+/// As the delta protocol specifies, a commit with change data feed files is described by them
+/// alone. Otherwise the changes are derived from the added and removed data files, skipping
+/// files that only rearrange data, like the ones written by `OPTIMIZE`.
+fn change_files(actions: &[Action]) -> Vec<ChangeFile> {
+    let change_data: Vec<_> = actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::cdc(cdc) => Some(ChangeFile::ChangeData(cdc.path.clone())),
+            _ => None,
+        })
+        .collect();
+    if !change_data.is_empty() {
+        return change_data;
+    }
+
+    actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::remove(remove) if remove.data_change => {
+                Some(ChangeFile::Removed(remove.path.clone()))
+            }
+            Action::add(add) if add.data_change => Some(ChangeFile::Added(add.path.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Position of a transaction in the ingestion of a table.
+///
+/// The snapshot of version `v` is ingested as transaction `2v + 1`, and the changes of commit `v`
+/// as transaction `2v`, so transaction ids keep increasing as the table is tailed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TablePosition {
+    Snapshot(i64),
+    Commit(i64),
+}
+
+impl TablePosition {
+    pub fn from_txid(txid: u64) -> Self {
+        let version = (txid / 2) as i64;
+        if txid % 2 == 1 {
+            TablePosition::Snapshot(version)
+        } else {
+            TablePosition::Commit(version)
+        }
+    }
+
+    pub fn txid(&self) -> u64 {
+        match self {
+            TablePosition::Snapshot(version) => *version as u64 * 2 + 1,
+            TablePosition::Commit(version) => *version as u64 * 2,
+        }
+    }
+
+    pub fn version(&self) -> i64 {
+        match self {
+            TablePosition::Snapshot(version) | TablePosition::Commit(version) => *version,
+        }
+    }
+}
+
+/// Whether a pass over the files of a commit ingests the rows that leave the table or the rows
+/// that enter it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangePass {
+    Removals,
+    Additions,
+}
+
+/// Ingests a snapshot of a table and keeps polling `_delta_log` for new commits.
+pub struct TableTail<'a> {
+    pub id: u32,
+    pub table_name: String,
+    pub table_path: String,
+    pub interval: Duration,
+    pub columns: &'a [String],
+    pub ingestor: &'a Ingestor,
+}
+
+impl<'a> TableTail<'a> {
+    /// Starts from a snapshot of the current version, or resumes the transaction at `position`
+    /// after skipping its first `skip` operations.
+    pub async fn run(self, resume_from: Option<(TablePosition, u64)>) -> ConnectorResult<()> {
+        let delta_table = deltalake::open_table(&self.table_path).await?;
+        if !delta_table.get_metadata()?.partition_columns.is_empty() {
+            return Err(PartitionedTableNotSupported(self.table_name).into());
+        }
+
+        let (mut version, mut skip) = match resume_from {
+            None => {
+                let version = delta_table.version();
+                self.ingest_snapshot(version, 0).await?;
+                (version, 0)
+            }
+            Some((TablePosition::Snapshot(version), skip)) => {
+                self.ingest_snapshot(version, skip).await?;
+                (version, 0)
+            }
+            // The commit is ingested again, without the operations that were already ingested.
+            Some((TablePosition::Commit(version), skip)) => (version - 1, skip),
+        };
+
+        loop {
+            match delta_table.peek_next_commit(version).await? {
+                PeekCommit::New(next_version, actions) => {
+                    info!(
+                        "Ingesting version {next_version} of delta table {}",
+                        self.table_name
+                    );
+                    if actions
+                        .iter()
+                        .any(|action| matches!(action, Action::metaData(_)))
+                    {
+                        warn!(
+                            "Version {next_version} of delta table {} changes its metadata, which is ignored",
+                            self.table_name
+                        );
+                    }
+                    let mut sender = Sender::new(
+                        self.ingestor,
+                        TablePosition::Commit(next_version),
+                        std::mem::take(&mut skip),
+                    );
+                    self.ingest_commit(&delta_table, &actions, &mut sender)
+                        .await?;
+                    version = next_version;
+                }
+                PeekCommit::UpToDate => tokio::time::sleep(self.interval).await,
+            }
+        }
+    }
+
+    /// Ingests the rows of the table at `version`, reading its files in order of their paths so
+    /// the rows always come in the same order.
+    async fn ingest_snapshot(&self, version: i64, skip: u64) -> ConnectorResult<()> {
+        info!(
+            "Ingesting snapshot of version {version} of delta table {}",
+            self.table_name
+        );
+        let delta_table = deltalake::open_table_with_version(&self.table_path, version).await?;
+        let mut files = delta_table.get_files();
+        files.sort();
+
+        let mut sender = Sender::new(self.ingestor, TablePosition::Snapshot(version), skip);
+        for file in files {
+            let path = file.as_ref();
+            for batch in read_file(&delta_table, path).await? {
+                let column_indices = self.column_indices(&batch, path)?;
+                for row in 0..batch.num_rows() {
+                    let record = self.record(&batch, &column_indices, row)?;
+                    sender.send(Operation::Insert { new: record })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ingests the rows that leave the table in the commit before the rows that enter it, so a
+    /// row that's updated is deleted before its new version is inserted. Updates are ingested as
+    /// such a delete and insert, because delta tables have no primary key to pair their images by.
+    async fn ingest_commit(
+        &self,
+        delta_table: &DeltaTable,
+        actions: &[Action],
+        sender: &mut Sender<'_>,
+    ) -> ConnectorResult<()> {
+        let change_files = change_files(actions);
+        for pass in [ChangePass::Removals, ChangePass::Additions] {
+            for change_file in &change_files {
+                let path = change_file.path();
+                match (change_file, pass) {
+                    (ChangeFile::Removed(_), ChangePass::Additions)
+                    | (ChangeFile::Added(_), ChangePass::Removals) => continue,
+                    _ => {}
+                }
+
+                for batch in read_file(delta_table, path).await? {
+                    let column_indices = self.column_indices(&batch, path)?;
+                    let change_types = if matches!(change_file, ChangeFile::ChangeData(_)) {
+                        Some(change_type_column(&batch, path)?)
+                    } else {
+                        None
+                    };
+
+                    for row in 0..batch.num_rows() {
+                        let row_pass = match &change_types {
+                            None => pass,
+                            Some(change_types) => match change_types.value(row) {
+                                "insert" | "update_postimage" => ChangePass::Additions,
+                                "delete" | "update_preimage" => ChangePass::Removals,
+                                change_type => {
+                                    return Err(UnknownChangeType(change_type.to_string()).into())
+                                }
+                            },
+                        };
+                        if row_pass != pass {
+                            continue;
+                        }
+
+                        let record = self.record(&batch, &column_indices, row)?;
+                        sender.send(match pass {
+                            ChangePass::Removals => Operation::Delete { old: record },
+                            ChangePass::Additions => Operation::Insert { new: record },
+                        })?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Indices of the ingested columns in `batch`.
+    fn column_indices(&self, batch: &RecordBatch, path: &str) -> ConnectorResult<Vec<usize>> {
+        let schema = batch.schema();
+        self.columns
+            .iter()
+            .map(|name| {
+                schema
+                    .index_of(name)
+                    .map_err(|_| ColumnNotFound(name.clone(), path.to_string()).into())
+            })
+            .collect()
+    }
+
+    fn record(
+        &self,
+        batch: &RecordBatch,
+        column_indices: &[usize],
+        row: usize,
+    ) -> ConnectorResult<Record> {
+        let values = column_indices
+            .iter()
+            .zip(self.columns)
+            .map(|(index, name)| map_value_to_dozer_field(batch.column(*index), &row, name))
+            .collect::<Result<Vec<Field>, _>>()
+            .map_err(ObjectStoreConnectorError::DataFusionSchemaError)?;
+        Ok(Record {
+            schema_id: Some(SchemaIdentifier {
+                id: self.id,
+                version: 0,
+            }),
+            values,
+            version: None,
+        })
+    }
+}
+
+/// Sends the operations of one transaction, skipping the ones that were ingested before the
+/// connector was restarted.
+struct Sender<'a> {
+    ingestor: &'a Ingestor,
+    txid: u64,
+    seq_in_tx: u64,
+    skip: u64,
+}
+
+impl<'a> Sender<'a> {
+    fn new(ingestor: &'a Ingestor, position: TablePosition, skip: u64) -> Self {
+        Self {
+            ingestor,
+            txid: position.txid(),
+            seq_in_tx: 0,
+            skip,
+        }
+    }
+
+    fn send(&mut self, op: Operation) -> ConnectorResult<()> {
+        if self.seq_in_tx >= self.skip {
+            self.ingestor
+                .handle_message(IngestionMessage::new_op(self.txid, self.seq_in_tx, op))
+                .map_err(ConnectorError::IngestorError)?;
+        }
+        self.seq_in_tx += 1;
+        Ok(())
+    }
+}
+
+/// Reads a parquet file of the table, `path` being relative to the table root.
+async fn read_file(delta_table: &DeltaTable, path: &str) -> ConnectorResult<Vec<RecordBatch>> {
+    let location =
+        Path::from_url_path(path).map_err(|e| FileReadError(path.to_string(), e.into()))?;
+    let bytes = delta_table
+        .object_store()
+        .get(&location)
+        .await
+        .map_err(|e| FileReadError(path.to_string(), e))?
+        .bytes()
+        .await
+        .map_err(|e| FileReadError(path.to_string(), e))?;
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(bytes)
+        .and_then(|builder| builder.build())
+        .map_err(|e| ParquetError(path.to_string(), e))?;
+    let batches = reader
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ParquetError(path.to_string(), e.into()))?;
+    Ok(batches)
+}
+
+fn change_type_column(batch: &RecordBatch, path: &str) -> ConnectorResult<StringArray> {
+    let index = batch
+        .schema()
+        .index_of(CHANGE_TYPE_COLUMN)
+        .map_err(|_| ColumnNotFound(CHANGE_TYPE_COLUMN.to_string(), path.to_string()))?;
+    let column = batch
+        .column(index)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| ColumnNotFound(CHANGE_TYPE_COLUMN.to_string(), path.to_string()))?;
+    Ok(column.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deltalake::action::{Add, AddCDCFile, Remove};
+
+    #[test]
+    fn change_files_prefer_change_data() {
+        let add = |path: &str, data_change| {
+            Action::add(Add {
+                path: path.to_string(),
+                data_change,
+                ..Default::default()
+            })
+        };
+        let remove = |path: &str, data_change| {
+            Action::remove(Remove {
+                path: path.to_string(),
+                data_change,
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            change_files(&[remove("a", true), add("b", true), add("c", false)]),
+            vec![
+                ChangeFile::Removed("a".to_string()),
+                ChangeFile::Added("b".to_string())
+            ]
+        );
+        assert!(change_files(&[remove("a", false), add("b", false)]).is_empty());
+        assert_eq!(
+            change_files(&[
+                remove("a", true),
+                add("b", true),
+                Action::cdc(AddCDCFile {
+                    path: "_change_data/c".to_string(),
+                    ..Default::default()
+                })
+            ]),
+            vec![ChangeFile::ChangeData("_change_data/c".to_string())]
+        );
+    }
+}
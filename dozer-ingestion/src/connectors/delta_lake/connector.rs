@@ -100,8 +100,21 @@ impl Connector for DeltaLakeConnector {
         schema_helper.get_schemas(self.id, &table_infos)
     }
 
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> ConnectorResult<()> {
+    fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> ConnectorResult<bool> {
+        DeltaLakeReader::new(self.config.clone()).can_start_from(tables, last_checkpoint)
+    }
+
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> ConnectorResult<()> {
         let reader = DeltaLakeReader::new(self.config.clone());
-        reader.read(&tables, ingestor)
+        reader.read(&tables, ingestor, last_checkpoint)
     }
 }
@@ -1,5 +1,6 @@
 use crate::errors::ConnectorError;
 
+mod change_feed;
 mod connector;
 mod reader;
 mod schema_helper;
@@ -1,13 +1,16 @@
+use crate::connectors::delta_lake::change_feed::{TablePosition, TableTail};
 use crate::connectors::delta_lake::ConnectorResult;
 use crate::connectors::object_store::map_value_to_dozer_field;
 use crate::connectors::TableInfo;
 use crate::errors::ConnectorError;
 use crate::ingestion::Ingestor;
 use deltalake::datafusion::prelude::SessionContext;
-use dozer_types::ingestion_types::{DeltaLakeConfig, IngestionMessage};
+use dozer_types::ingestion_types::{DeltaLakeConfig, DeltaTable, IngestionMessage};
 use dozer_types::types::{Operation, Record, SchemaIdentifier};
+use futures::future::try_join_all;
 use futures::StreamExt;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
 pub struct DeltaLakeReader {
@@ -19,25 +22,81 @@ impl DeltaLakeReader {
         Self { config }
     }
 
-    pub fn read(&self, table: &[TableInfo], ingestor: &Ingestor) -> ConnectorResult<()> {
-        let mut seq_no = 0;
+    /// Whether the connection can resume from `last_checkpoint`. Only a single tailed table is
+    /// resumable, because the checkpoint can only tell the position of one table.
+    pub fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> ConnectorResult<bool> {
+        let [table] = tables else {
+            return Ok(false);
+        };
+        let delta_table = table_config(&self.config, &table.name)?;
+        if delta_table.watch.is_none() {
+            return Ok(false);
+        }
+
+        let (txid, _) = last_checkpoint;
+        let position = TablePosition::from_txid(txid);
+        // Commits are only tailed after a snapshot, so there's no commit of version 0.
+        if position == TablePosition::Commit(0) {
+            return Ok(false);
+        }
         let runtime = Runtime::new()?;
-        for (id, table) in table.iter().enumerate() {
-            runtime.block_on(self.read_impl(id as u32, &mut seq_no, table, ingestor))?;
+        let current_version = runtime
+            .block_on(deltalake::open_table(&delta_table.path))?
+            .version();
+        // The resumed version has to be readable again.
+        Ok(position.version() <= current_version)
+    }
+
+    pub fn read(
+        &self,
+        tables: &[TableInfo],
+        ingestor: &Ingestor,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> ConnectorResult<()> {
+        let runtime = Runtime::new()?;
+        let mut tails = vec![];
+        for (id, table) in tables.iter().enumerate() {
+            let delta_table = table_config(&self.config, &table.name)?;
+            let Some(watch) = &delta_table.watch else {
+                runtime.block_on(self.read_impl(id as u32, table, ingestor))?;
+                continue;
+            };
+
+            let tail = TableTail {
+                id: id as u32,
+                table_name: table.name.clone(),
+                table_path: delta_table.path.clone(),
+                interval: Duration::from_millis(watch.interval_ms),
+                columns: &table.column_names,
+                ingestor,
+            };
+            // A checkpoint is only resumed from with a single tailed table.
+            let resume_from = last_checkpoint
+                .map(|(txid, seq_in_tx)| (TablePosition::from_txid(txid), seq_in_tx + 1));
+            tails.push(tail.run(resume_from));
+        }
+
+        if !tails.is_empty() {
+            runtime.block_on(try_join_all(tails))?;
         }
         Ok(())
     }
 
+    /// Ingests the current snapshot of a table that isn't tailed.
     async fn read_impl(
         &self,
         id: u32,
-        seq_no: &mut u64,
         table: &TableInfo,
         ingestor: &Ingestor,
     ) -> ConnectorResult<()> {
         let table_path = table_path(&self.config, &table.name)?;
         let ctx = SessionContext::new();
         let delta_table = deltalake::open_table(table_path).await?;
+        let txid = TablePosition::Snapshot(delta_table.version()).txid();
         let cols: Vec<&str> = table.column_names.iter().map(|c| c.as_str()).collect();
         let data = ctx
             .read_table(Arc::new(delta_table))?
@@ -46,6 +105,7 @@ impl DeltaLakeReader {
             .await?;
 
         tokio::pin!(data);
+        let mut seq_no = 0;
         while let Some(Ok(batch)) = data.next().await {
             for row in 0..batch.num_rows() {
                 let fields = batch
@@ -57,8 +117,8 @@ impl DeltaLakeReader {
 
                 ingestor
                     .handle_message(IngestionMessage::new_op(
-                        0_u64,
-                        *seq_no,
+                        txid,
+                        seq_no,
                         Operation::Insert {
                             new: Record {
                                 schema_id: Some(SchemaIdentifier { id, version: 0 }),
@@ -69,7 +129,7 @@ impl DeltaLakeReader {
                     ))
                     .unwrap();
 
-                *seq_no += 1;
+                seq_no += 1;
             }
         }
         Ok(())
@@ -77,12 +137,18 @@ impl DeltaLakeReader {
 }
 
 pub fn table_path(config: &DeltaLakeConfig, table_name: &str) -> ConnectorResult<String> {
-    for delta_table in config.tables.iter() {
-        if delta_table.name == table_name {
-            return Ok(delta_table.path.clone());
-        }
-    }
-    Err(ConnectorError::TableNotFound(format!(
-        "Delta table: {table_name} can't find"
-    )))
+    table_config(config, table_name).map(|delta_table| delta_table.path.clone())
+}
+
+pub fn table_config<'a>(
+    config: &'a DeltaLakeConfig,
+    table_name: &str,
+) -> ConnectorResult<&'a DeltaTable> {
+    config
+        .tables
+        .iter()
+        .find(|delta_table| delta_table.name == table_name)
+        .ok_or_else(|| {
+            ConnectorError::TableNotFound(format!("Delta table: {table_name} can't find"))
+        })
 }
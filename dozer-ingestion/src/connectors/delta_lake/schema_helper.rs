@@ -1,4 +1,4 @@
-use crate::connectors::delta_lake::reader::table_path;
+use crate::connectors::delta_lake::reader::{table_config, table_path};
 use crate::connectors::delta_lake::ConnectorResult;
 use crate::connectors::object_store::schema_mapper::map_schema;
 use crate::connectors::{CdcType, ListOrFilterColumns, SourceSchema, SourceSchemaResult};
@@ -42,6 +42,12 @@ impl SchemaHelper {
             .clone()
             .into();
         let schema = map_schema(id as u32, arrow_schema, table)?;
-        Ok(SourceSchema::new(schema, CdcType::Nothing))
+        // Tailed tables emit the whole old record of updates and deletes.
+        let cdc_type = if table_config(&self.config, &table.name)?.watch.is_some() {
+            CdcType::FullChanges
+        } else {
+            CdcType::Nothing
+        };
+        Ok(SourceSchema::new(schema, cdc_type))
     }
 }
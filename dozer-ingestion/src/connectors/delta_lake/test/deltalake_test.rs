@@ -1,11 +1,18 @@
 use crate::connectors::delta_lake::DeltaLakeConnector;
 use crate::connectors::Connector;
-use crate::ingestion::{IngestionConfig, Ingestor};
+use crate::ingestion::{IngestionConfig, IngestionIterator, Ingestor};
+use dozer_types::arrow::array::{Int32Array, StringArray};
+use dozer_types::arrow::datatypes::{DataType, Field as ArrowField, Schema};
+use dozer_types::arrow::record_batch::RecordBatch;
 use dozer_types::ingestion_types::IngestionMessage;
-use dozer_types::ingestion_types::{DeltaLakeConfig, DeltaTable, IngestionMessageKind};
+use dozer_types::ingestion_types::{DeltaLakeConfig, DeltaTable, IngestionMessageKind, TableWatch};
 use dozer_types::types::SourceDefinition::Dynamic;
-use dozer_types::types::{Field, FieldType, Operation};
+use dozer_types::types::{Field, FieldType, Operation, Record};
+use parquet::arrow::ArrowWriter;
+use std::path::Path;
+use std::sync::Arc;
 use std::thread;
+use tempdir::TempDir;
 
 #[test]
 fn get_schema_from_deltalake() {
@@ -14,6 +21,7 @@ fn get_schema_from_deltalake() {
     let delta_table = DeltaTable {
         path: path.to_string(),
         name: table_name.to_string(),
+        watch: None,
     };
     let config = DeltaLakeConfig {
         tables: vec![delta_table],
@@ -35,6 +43,7 @@ fn read_deltalake() {
     let delta_table = DeltaTable {
         path: path.to_string(),
         name: table_name.to_string(),
+        watch: None,
     };
     let config = DeltaLakeConfig {
         tables: vec![delta_table],
@@ -48,7 +57,7 @@ fn read_deltalake() {
         .list_columns(connector.list_tables().unwrap())
         .unwrap();
     thread::spawn(move || {
-        let _ = connector.start(&ingestor, tables);
+        let _ = connector.start(&ingestor, tables, None);
     });
 
     let fields = vec![Field::Int(0), Field::Int(1), Field::Int(2), Field::Int(4)];
@@ -62,3 +71,207 @@ fn read_deltalake() {
     values.sort();
     assert_eq!(fields, values);
 }
+
+const TEST_TABLE_PATH: &str = "src/connectors/delta_lake/test/data/delta-0.8.0";
+
+/// Copies the data files of the test table and its log up to `last_version` into `dst`.
+fn copy_test_table(dst: &Path, last_version: usize) {
+    std::fs::create_dir_all(dst.join("_delta_log")).unwrap();
+    for entry in std::fs::read_dir(TEST_TABLE_PATH).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(false, |ext| ext == "parquet") {
+            std::fs::copy(&path, dst.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+    for version in 0..=last_version {
+        copy_commit(dst, version);
+    }
+}
+
+fn copy_commit(dst: &Path, version: usize) {
+    let name = format!("_delta_log/{version:020}.json");
+    std::fs::copy(Path::new(TEST_TABLE_PATH).join(&name), dst.join(&name)).unwrap();
+}
+
+fn tailing_connector(table_path: &Path) -> DeltaLakeConnector {
+    let config = DeltaLakeConfig {
+        tables: vec![DeltaTable {
+            path: table_path.to_str().unwrap().to_string(),
+            name: "test_table".to_string(),
+            watch: Some(TableWatch { interval_ms: 50 }),
+        }],
+    };
+    DeltaLakeConnector::new(1, config)
+}
+
+fn start_tailing(table_path: &Path, last_checkpoint: Option<(u64, u64)>) -> IngestionIterator {
+    let connector = tailing_connector(table_path);
+    let (ingestor, iterator) = Ingestor::initialize_channel(IngestionConfig::default());
+    let tables = connector
+        .list_columns(connector.list_tables().unwrap())
+        .unwrap();
+    thread::spawn(move || {
+        let _ = connector.start(&ingestor, tables, last_checkpoint);
+    });
+    iterator
+}
+
+fn next_op_with_seq(iterator: &mut IngestionIterator, expected_txid: u64) -> (u64, Operation) {
+    let Some(IngestionMessage {
+        identifier,
+        kind: IngestionMessageKind::OperationEvent(op),
+    }) = iterator.next()
+    else {
+        panic!("Unexpected message");
+    };
+    assert_eq!(identifier.txid, expected_txid);
+    (identifier.seq_in_tx, op)
+}
+
+fn next_op(iterator: &mut IngestionIterator, expected_txid: u64) -> Operation {
+    next_op_with_seq(iterator, expected_txid).1
+}
+
+fn value_of(record: &Record) -> Field {
+    record.values[0].clone()
+}
+
+#[test]
+fn tail_deltalake_without_change_data() {
+    let temp_dir = TempDir::new("delta_tail").unwrap();
+    copy_test_table(temp_dir.path(), 0);
+    let mut iterator = start_tailing(temp_dir.path(), None);
+
+    // The snapshot of version 0.
+    for _ in 0..5 {
+        assert!(matches!(
+            next_op(&mut iterator, 1),
+            Operation::Insert { .. }
+        ));
+    }
+
+    // Version 1 deletes `3` by rewriting the file that contained it. The rows of the removed file
+    // are deleted before the rows of the added file are inserted.
+    copy_commit(temp_dir.path(), 1);
+    let mut deleted = vec![];
+    for _ in 0..3 {
+        match next_op(&mut iterator, 2) {
+            Operation::Delete { old } => deleted.push(value_of(&old)),
+            op => panic!("Unexpected operation {op:?}"),
+        }
+    }
+    let mut inserted = vec![];
+    for _ in 0..2 {
+        match next_op(&mut iterator, 2) {
+            Operation::Insert { new } => inserted.push(value_of(&new)),
+            op => panic!("Unexpected operation {op:?}"),
+        }
+    }
+    deleted.sort();
+    inserted.sort();
+    assert_eq!(deleted, vec![Field::Int(2), Field::Int(3), Field::Int(4)]);
+    assert_eq!(inserted, vec![Field::Int(2), Field::Int(4)]);
+}
+
+#[test]
+fn tail_deltalake_change_data_feed() {
+    let temp_dir = TempDir::new("delta_cdf").unwrap();
+    copy_test_table(temp_dir.path(), 1);
+    let mut iterator = start_tailing(temp_dir.path(), None);
+
+    // The snapshot of version 1.
+    for _ in 0..4 {
+        assert!(matches!(
+            next_op(&mut iterator, 3),
+            Operation::Insert { .. }
+        ));
+    }
+
+    // Version 2 updates `4` to `40` and deletes `0`, described by a change data feed file.
+    let change_data_dir = temp_dir.path().join("_change_data");
+    std::fs::create_dir_all(&change_data_dir).unwrap();
+    let schema = Arc::new(Schema::new(vec![
+        ArrowField::new("value", DataType::Int32, true),
+        ArrowField::new("_change_type", DataType::Utf8, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from(vec![4, 40, 0])),
+            Arc::new(StringArray::from(vec![
+                "update_preimage",
+                "update_postimage",
+                "delete",
+            ])),
+        ],
+    )
+    .unwrap();
+    let file = std::fs::File::create(change_data_dir.join("cdc-00002.snappy.parquet")).unwrap();
+    let mut writer = ArrowWriter::try_new(file, schema, None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    std::fs::write(
+        temp_dir.path().join("_delta_log/00000000000000000002.json"),
+        concat!(
+            r#"{"commitInfo":{"timestamp":1615043786000,"operation":"MERGE","operationParameters":{},"readVersion":1,"isBlindAppend":false}}"#,
+            "\n",
+            r#"{"cdc":{"path":"_change_data/cdc-00002.snappy.parquet","partitionValues":{},"size":1,"dataChange":false}}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    // The update is ingested as a delete of the pre-image and an insert of the post-image.
+    match next_op(&mut iterator, 4) {
+        Operation::Delete { old } => assert_eq!(value_of(&old), Field::Int(4)),
+        op => panic!("Unexpected operation {op:?}"),
+    }
+    match next_op(&mut iterator, 4) {
+        Operation::Delete { old } => assert_eq!(value_of(&old), Field::Int(0)),
+        op => panic!("Unexpected operation {op:?}"),
+    }
+    match next_op(&mut iterator, 4) {
+        Operation::Insert { new } => assert_eq!(value_of(&new), Field::Int(40)),
+        op => panic!("Unexpected operation {op:?}"),
+    }
+}
+
+#[test]
+fn resume_deltalake_from_checkpoint() {
+    let temp_dir = TempDir::new("delta_resume").unwrap();
+    copy_test_table(temp_dir.path(), 0);
+    let connector = tailing_connector(temp_dir.path());
+    let tables = connector
+        .list_columns(connector.list_tables().unwrap())
+        .unwrap();
+    // Commit 1 doesn't exist yet.
+    assert!(connector.can_start_from(&tables, (1, 2)).unwrap());
+    assert!(!connector.can_start_from(&tables, (2, 0)).unwrap());
+    assert!(!connector.can_start_from(&tables, (0, 0)).unwrap());
+
+    // The snapshot of version 0 is resumed after its third row.
+    let mut iterator = start_tailing(temp_dir.path(), Some((1, 2)));
+    for seq_in_tx in 3..5 {
+        let (seq, op) = next_op_with_seq(&mut iterator, 1);
+        assert_eq!(seq, seq_in_tx);
+        assert!(matches!(op, Operation::Insert { .. }));
+    }
+    copy_commit(temp_dir.path(), 1);
+    assert!(matches!(
+        next_op(&mut iterator, 2),
+        Operation::Delete { .. }
+    ));
+
+    // Commit 1 is resumed after its deletes.
+    let mut iterator = start_tailing(temp_dir.path(), Some((2, 2)));
+    let mut inserted = vec![];
+    for seq_in_tx in 3..5 {
+        match next_op_with_seq(&mut iterator, 2) {
+            (seq, Operation::Insert { new }) if seq == seq_in_tx => inserted.push(value_of(&new)),
+            op => panic!("Unexpected operation {op:?}"),
+        }
+    }
+    inserted.sort();
+    assert_eq!(inserted, vec![Field::Int(2), Field::Int(4)]);
+}
@@ -231,7 +231,12 @@ impl Connector for EthLogConnector {
         Ok(result)
     }
 
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> Result<(), ConnectorError> {
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        _last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
         // Start a new thread that interfaces with ETH node
         let wss_url = self.config.wss_url.to_owned();
         let filter = self.config.filter.to_owned().unwrap_or_default();
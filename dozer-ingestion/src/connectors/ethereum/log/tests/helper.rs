@@ -83,7 +83,7 @@ pub fn get_eth_producer(
         );
     }
 
-    eth_connector.start(&ingestor, table_infos)
+    eth_connector.start(&ingestor, table_infos, None)
 }
 
 pub fn run_eth_sample(wss_url: String, my_account: H160) -> (Contract<WebSocket>, Vec<Operation>) {
@@ -94,7 +94,12 @@ impl Connector for EthTraceConnector {
         ))])
     }
 
-    fn start(&self, ingestor: &Ingestor, _tables: Vec<TableInfo>) -> Result<(), ConnectorError> {
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        _tables: Vec<TableInfo>,
+        _last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
         let config = self.config.clone();
         let conn_name = self.conn_name.clone();
         Runtime::new()
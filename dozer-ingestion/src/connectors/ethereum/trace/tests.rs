@@ -69,7 +69,7 @@ fn test_trace_iterator() {
         for s in schemas {
             s.schema.print().printstd();
         }
-        connector.start(&ingestor, tables).unwrap();
+        connector.start(&ingestor, tables, None).unwrap();
     });
 
     if let Some(IngestionMessage {
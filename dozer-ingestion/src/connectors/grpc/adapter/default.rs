@@ -144,6 +144,13 @@ fn map_record(rec: grpc_types::types::Record, schema: &Schema) -> Result<Record,
                 grpc_types::types::value::Value::BytesValue(a),
                 dozer_types::types::FieldType::Bson,
             ) => Ok(dozer_types::types::Field::Bson(a.clone())),
+            (
+                grpc_types::types::value::Value::StringValue(a),
+                dozer_types::types::FieldType::Interval,
+            ) => a
+                .parse::<dozer_types::types::DozerInterval>()
+                .map(dozer_types::types::Field::Interval)
+                .map_err(|e| ConnectorError::InitializationError(e.to_string())),
             (
                 grpc_types::types::value::Value::TimestampValue(a),
                 dozer_types::types::FieldType::Timestamp,
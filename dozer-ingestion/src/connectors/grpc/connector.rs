@@ -231,6 +231,7 @@ where
         &self,
         ingestor: &Ingestor,
         _table_names: Vec<TableInfo>,
+        _last_checkpoint: Option<(u64, u64)>,
     ) -> Result<(), ConnectorError> {
         self.serve(ingestor)
     }
@@ -45,7 +45,7 @@ async fn ingest_grpc(
         let tables = grpc_connector
             .list_columns(grpc_connector.list_tables().unwrap())
             .unwrap();
-        grpc_connector.start(&ingestor, tables).unwrap();
+        grpc_connector.start(&ingestor, tables, None).unwrap();
     });
 
     let retries = 10;
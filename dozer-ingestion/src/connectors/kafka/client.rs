@@ -0,0 +1,186 @@
+use crate::connectors::kafka::stream_consumer::decode_position;
+use crate::errors::KafkaConnectorError;
+use crate::errors::KafkaConnectorError::{
+    AssignmentError, ConsumerCreationError, MetadataError, OffsetCommitError, OffsetFetchError,
+    SubscriptionError, TopicNotFound, UnsupportedSecurityProtocol,
+};
+use dozer_types::ingestion_types::{KafkaConfig, KafkaSecurity};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer};
+use rdkafka::{Offset, TopicPartitionList};
+use std::time::Duration;
+
+const METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Creates a consumer in the group of the connection.
+///
+/// Offsets are never committed in the background. They are committed once the pipeline has
+/// checkpointed their messages, see [`is_resumable`].
+pub fn create_consumer(
+    config: &KafkaConfig,
+    group_id: &str,
+) -> Result<BaseConsumer, KafkaConnectorError> {
+    let mut client_config = ClientConfig::new();
+    client_config
+        .set("bootstrap.servers", &config.broker)
+        .set("group.id", group_id)
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest");
+    if let Some(security) = &config.security {
+        set_security(&mut client_config, security)?;
+    }
+
+    client_config.create().map_err(ConsumerCreationError)
+}
+
+/// Lists the partitions of `topics`.
+fn list_partitions(
+    consumer: &BaseConsumer,
+    topics: &[&str],
+) -> Result<Vec<(String, i32)>, KafkaConnectorError> {
+    let mut partitions = vec![];
+    for topic in topics {
+        let metadata = consumer
+            .fetch_metadata(Some(*topic), METADATA_TIMEOUT)
+            .map_err(MetadataError)?;
+        let topic_partitions = metadata
+            .topics()
+            .iter()
+            .filter(|metadata| metadata.name() == *topic && metadata.error().is_none())
+            .flat_map(|metadata| metadata.partitions())
+            .map(|partition| (topic.to_string(), partition.id()))
+            .collect::<Vec<_>>();
+        if topic_partitions.is_empty() {
+            return Err(TopicNotFound(topic.to_string()));
+        }
+        partitions.extend(topic_partitions);
+    }
+    Ok(partitions)
+}
+
+/// Whether ingestion can resume after `last_checkpoint` from the offsets committed to the group.
+///
+/// The group must be at the checkpoint, which the offset of its message tells, and the messages
+/// after the committed offsets must still be retained. The group is behind the checkpoint if
+/// Dozer stopped before committing it, see [`OffsetTracker::commit`].
+///
+/// [`OffsetTracker::commit`]: super::offset_tracker::OffsetTracker::commit
+pub fn is_resumable(
+    consumer: &BaseConsumer,
+    topics: &[&str],
+    last_checkpoint: (u64, u64),
+) -> Result<bool, KafkaConnectorError> {
+    let (txid, _) = last_checkpoint;
+    let (checkpoint_partition, checkpoint_offset) = decode_position(last_checkpoint.1);
+
+    let mut list = TopicPartitionList::new();
+    for (topic, partition) in list_partitions(consumer, topics)? {
+        list.add_partition(&topic, partition);
+    }
+    let committed = consumer
+        .committed_offsets(list, METADATA_TIMEOUT)
+        .map_err(OffsetFetchError)?;
+
+    let mut at_checkpoint = false;
+    for position in committed.elements() {
+        // Nothing was ingested from partitions without a committed offset.
+        let Offset::Offset(offset) = position.offset() else {
+            continue;
+        };
+        // Offsets committed when ingestion started over have no transaction id.
+        match position.metadata().parse::<u64>() {
+            Ok(committed_txid) if committed_txid > txid => return Ok(false),
+            Ok(committed_txid) if committed_txid == txid => {
+                at_checkpoint |=
+                    position.partition() == checkpoint_partition && offset == checkpoint_offset + 1;
+            }
+            _ => (),
+        }
+        let (low, _) = consumer
+            .fetch_watermarks(position.topic(), position.partition(), METADATA_TIMEOUT)
+            .map_err(MetadataError)?;
+        if low > offset {
+            return Ok(false);
+        }
+    }
+    Ok(at_checkpoint)
+}
+
+/// Commits the beginning of every partition of `topics` to the group, so ingestion starts over.
+pub fn reset_offsets(consumer: &BaseConsumer, topics: &[&str]) -> Result<(), KafkaConnectorError> {
+    let mut list = TopicPartitionList::new();
+    for (topic, partition) in list_partitions(consumer, topics)? {
+        let (low, _) = consumer
+            .fetch_watermarks(&topic, partition, METADATA_TIMEOUT)
+            .map_err(MetadataError)?;
+        list.add_partition_offset(&topic, partition, Offset::Offset(low))
+            .map_err(AssignmentError)?;
+    }
+    consumer
+        .commit(&list, CommitMode::Sync)
+        .map_err(OffsetCommitError)
+}
+
+/// Subscribes the consumer to `topics`. The group assigns it their partitions, which are read
+/// from the offsets committed to the group.
+pub fn subscribe(consumer: &BaseConsumer, topics: &[&str]) -> Result<(), KafkaConnectorError> {
+    consumer.subscribe(topics).map_err(SubscriptionError)
+}
+
+fn set_security(
+    client_config: &mut ClientConfig,
+    security: &KafkaSecurity,
+) -> Result<(), KafkaConnectorError> {
+    let protocol = security.protocol.to_lowercase();
+    if !["plaintext", "ssl", "sasl_plaintext", "sasl_ssl"].contains(&protocol.as_str()) {
+        return Err(UnsupportedSecurityProtocol(security.protocol.clone()));
+    }
+    client_config.set("security.protocol", protocol);
+
+    for (key, value) in [
+        ("sasl.mechanisms", &security.sasl_mechanism),
+        ("sasl.username", &security.sasl_username),
+        ("sasl.password", &security.sasl_password),
+        ("ssl.ca.location", &security.ssl_ca_location),
+        (
+            "ssl.certificate.location",
+            &security.ssl_certificate_location,
+        ),
+        ("ssl.key.location", &security.ssl_key_location),
+    ] {
+        if let Some(value) = value {
+            client_config.set(key, value);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_security() {
+        let mut client_config = ClientConfig::new();
+        let security = KafkaSecurity {
+            protocol: "SASL_SSL".to_string(),
+            sasl_mechanism: Some("PLAIN".to_string()),
+            sasl_username: Some("dozer".to_string()),
+            sasl_password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        set_security(&mut client_config, &security).unwrap();
+        assert_eq!(client_config.get("security.protocol"), Some("sasl_ssl"));
+        assert_eq!(client_config.get("sasl.mechanisms"), Some("PLAIN"));
+        assert_eq!(client_config.get("ssl.ca.location"), None);
+
+        let security = KafkaSecurity {
+            protocol: "kerberos".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            set_security(&mut client_config, &security),
+            Err(UnsupportedSecurityProtocol(_))
+        ));
+    }
+}
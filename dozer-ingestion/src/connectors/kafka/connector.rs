@@ -1,26 +1,62 @@
 use crate::connectors::{Connector, SourceSchema, SourceSchemaResult, TableIdentifier};
 use crate::ingestion::Ingestor;
 use crate::{connectors::TableInfo, errors::ConnectorError};
-use dozer_types::ingestion_types::KafkaConfig;
+use dozer_types::ingestion_types::{KafkaConfig, KafkaTopic};
+use dozer_types::parking_lot::Mutex;
+use std::sync::Arc;
 
-use kafka::consumer::{Consumer, FetchOffset, GroupOffsetStorage};
 use tokio::runtime::Runtime;
 
+use crate::connectors::kafka::client::{create_consumer, is_resumable, reset_offsets, subscribe};
 use crate::connectors::kafka::debezium::no_schema_registry::NoSchemaRegistry;
 use crate::connectors::kafka::debezium::schema_registry::SchemaRegistry;
 use crate::connectors::kafka::debezium::stream_consumer::DebeziumStreamConsumer;
+use crate::connectors::kafka::offset_tracker::OffsetTracker;
+use crate::connectors::kafka::plain::stream_consumer::{PlainStreamConsumer, PlainTopic};
+use crate::connectors::kafka::plain::{map_schema, TopicFormat};
 use crate::connectors::kafka::stream_consumer::StreamConsumer;
-use crate::errors::DebeziumError::{DebeziumConnectionError, TopicNotDefined};
+use crate::errors::DebeziumError::TopicNotDefined;
+use crate::errors::KafkaConnectorError::TopicNotDeclared;
 
 #[derive(Debug)]
 pub struct KafkaConnector {
     pub id: u64,
     config: KafkaConfig,
+    name: String,
+    /// The offsets of the running ingestion, committed on checkpoints.
+    offsets: Mutex<Option<Arc<OffsetTracker>>>,
 }
 
 impl KafkaConnector {
-    pub fn new(id: u64, config: KafkaConfig) -> Self {
-        Self { id, config }
+    pub fn new(id: u64, config: KafkaConfig, name: String) -> Self {
+        Self {
+            id,
+            config,
+            name,
+            offsets: Mutex::new(None),
+        }
+    }
+
+    fn group_id(&self) -> String {
+        self.config
+            .group_id
+            .clone()
+            .unwrap_or_else(|| format!("dozer-{}", self.name))
+    }
+
+    /// Topics carry plain messages when they are declared, Debezium envelopes otherwise.
+    fn is_plain(&self) -> bool {
+        !self.config.topics.is_empty()
+    }
+
+    /// The topics ingested for `tables`. Debezium envelopes are only read from the first one.
+    fn topic_names<'a>(&self, tables: &'a [TableInfo]) -> Result<Vec<&'a str>, ConnectorError> {
+        if self.is_plain() {
+            Ok(tables.iter().map(|table| table.name.as_str()).collect())
+        } else {
+            let table = tables.first().ok_or(TopicNotDefined)?;
+            Ok(vec![table.name.as_str()])
+        }
     }
 
     fn get_schemas_impl(
@@ -30,9 +66,49 @@ impl KafkaConnector {
         if let Some(schema_registry_url) = &self.config.schema_registry_url {
             SchemaRegistry::get_schema(table_names, schema_registry_url.clone())
         } else {
-            NoSchemaRegistry::get_schema(table_names, self.config.broker.clone())
+            NoSchemaRegistry::get_schema(table_names, &self.config, &self.group_id())
         }
     }
+
+    /// The declared topic `name`, with only `column_names` when they are given.
+    fn get_topic(
+        &self,
+        name: &str,
+        column_names: Option<&[String]>,
+    ) -> Result<KafkaTopic, ConnectorError> {
+        let mut topic = self
+            .config
+            .topics
+            .iter()
+            .find(|topic| topic.name == name)
+            .cloned()
+            .ok_or_else(|| TopicNotDeclared(name.to_string()))?;
+        TopicFormat::parse(&topic)?;
+
+        if let Some(column_names) = column_names {
+            topic.fields = column_names
+                .iter()
+                .filter_map(|column| topic.fields.iter().find(|field| &field.name == column))
+                .cloned()
+                .collect();
+            topic.primary_key.retain(|key| column_names.contains(key));
+        }
+        Ok(topic)
+    }
+
+    fn get_plain_schemas(
+        &self,
+        table_infos: &[TableInfo],
+    ) -> Result<Vec<SourceSchemaResult>, ConnectorError> {
+        Ok(table_infos
+            .iter()
+            .enumerate()
+            .map(|(index, table)| {
+                let topic = self.get_topic(&table.name, Some(&table.column_names))?;
+                map_schema(&topic, index as u32).map_err(Into::into)
+            })
+            .collect())
+    }
 }
 
 impl Connector for KafkaConnector {
@@ -48,10 +124,23 @@ impl Connector for KafkaConnector {
     }
 
     fn list_tables(&self) -> Result<Vec<TableIdentifier>, ConnectorError> {
-        Ok(vec![])
+        Ok(self
+            .config
+            .topics
+            .iter()
+            .map(|topic| TableIdentifier::from_table_name(topic.name.clone()))
+            .collect())
     }
 
     fn validate_tables(&self, tables: &[TableIdentifier]) -> Result<(), ConnectorError> {
+        if self.is_plain() {
+            for table in tables {
+                let topic = self.get_topic(&table.name, None)?;
+                map_schema(&topic, 0)?;
+            }
+            return Ok(());
+        }
+
         let table_names = tables
             .iter()
             .map(|table| table.name.clone())
@@ -60,6 +149,20 @@ impl Connector for KafkaConnector {
     }
 
     fn list_columns(&self, tables: Vec<TableIdentifier>) -> Result<Vec<TableInfo>, ConnectorError> {
+        if self.is_plain() {
+            return tables
+                .into_iter()
+                .map(|table| {
+                    let topic = self.get_topic(&table.name, None)?;
+                    Ok(TableInfo {
+                        schema: table.schema,
+                        name: table.name,
+                        column_names: topic.fields.into_iter().map(|field| field.name).collect(),
+                    })
+                })
+                .collect();
+        }
+
         let table_names = tables
             .iter()
             .map(|table| table.name.clone())
@@ -86,6 +189,10 @@ impl Connector for KafkaConnector {
         &self,
         table_infos: &[TableInfo],
     ) -> Result<Vec<SourceSchemaResult>, ConnectorError> {
+        if self.is_plain() {
+            return self.get_plain_schemas(table_infos);
+        }
+
         let table_names = table_infos
             .iter()
             .map(|table| table.name.clone())
@@ -97,26 +204,70 @@ impl Connector for KafkaConnector {
             .collect())
     }
 
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> Result<(), ConnectorError> {
-        let topic = tables
-            .get(0)
-            .map_or(Err(TopicNotDefined), |table| Ok(&table.name))?;
+    fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError> {
+        let con = create_consumer(&self.config, &self.group_id())?;
+        let topic_names = self.topic_names(tables)?;
+        Ok(is_resumable(&con, &topic_names, last_checkpoint)?)
+    }
+
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
+        let con = create_consumer(&self.config, &self.group_id())?;
+        let topic_names = self.topic_names(&tables)?;
+        // Resuming reads from the offsets of the checkpoint, which the group is at.
+        if last_checkpoint.is_none() {
+            reset_offsets(&con, &topic_names)?;
+        }
+        subscribe(&con, &topic_names)?;
+        let offsets = Arc::new(OffsetTracker::new(con));
+        *self.offsets.lock() = Some(offsets.clone());
+        let first_txid = last_checkpoint.map_or(0, |(txid, _)| txid + 1);
+
+        if self.is_plain() {
+            let topics = tables
+                .iter()
+                .zip(self.get_plain_schemas(&tables)?)
+                .map(|(table, schema)| {
+                    let topic = self.get_topic(&table.name, None)?;
+                    Ok(PlainTopic {
+                        name: table.name.clone(),
+                        format: TopicFormat::parse(&topic)?,
+                        schema: schema?.schema,
+                    })
+                })
+                .collect::<Result<Vec<_>, ConnectorError>>()?;
+            let consumer =
+                PlainStreamConsumer::new(topics, self.config.schema_registry_url.as_deref())?;
+            return consumer.run(&offsets, ingestor, first_txid);
+        }
 
-        let broker = self.config.broker.to_owned();
         Runtime::new()
             .unwrap()
-            .block_on(async { run(broker, topic, ingestor).await })
+            .block_on(async { run(&offsets, ingestor, first_txid).await })
     }
-}
 
-async fn run(broker: String, topic: &str, ingestor: &Ingestor) -> Result<(), ConnectorError> {
-    let con = Consumer::from_hosts(vec![broker])
-        .with_topic(topic.to_string())
-        .with_fallback_offset(FetchOffset::Earliest)
-        .with_offset_storage(GroupOffsetStorage::Kafka)
-        .create()
-        .map_err(DebeziumConnectionError)?;
+    fn on_checkpoint(&self, checkpoint: (u64, u64)) -> Result<(), ConnectorError> {
+        let offsets = self.offsets.lock().clone();
+        if let Some(offsets) = offsets {
+            offsets.commit(checkpoint)?;
+        }
+        Ok(())
+    }
+}
 
+async fn run(
+    offsets: &OffsetTracker,
+    ingestor: &Ingestor,
+    first_txid: u64,
+) -> Result<(), ConnectorError> {
     let consumer = DebeziumStreamConsumer::default();
-    consumer.run(con, ingestor)
+    consumer.run(offsets, ingestor, first_txid)
 }
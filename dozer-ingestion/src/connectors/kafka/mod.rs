@@ -1,5 +1,8 @@
+pub mod client;
 pub mod connector;
 pub mod debezium;
+pub mod offset_tracker;
+pub mod plain;
 pub mod stream_consumer;
 #[cfg(any(test, feature = "debezium_bench"))]
 pub mod test_utils;
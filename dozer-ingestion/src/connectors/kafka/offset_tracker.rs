@@ -0,0 +1,122 @@
+use crate::errors::KafkaConnectorError;
+use crate::errors::KafkaConnectorError::{AssignmentError, OffsetCommitError};
+use dozer_types::parking_lot::Mutex;
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer};
+use rdkafka::message::BorrowedMessage;
+use rdkafka::{Message, Offset, TopicPartitionList};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug, Formatter};
+
+/// The consumer of an ingestion, and the positions of the messages it ingested that the pipeline
+/// hasn't checkpointed yet.
+///
+/// Positions are committed to the consumer group once the pipeline has checkpointed their
+/// messages, so the group is never ahead of the checkpoint ingestion resumes from.
+pub struct OffsetTracker {
+    consumer: BaseConsumer,
+    /// Identifier, topic, partition and offset of the ingested messages, in ingestion order.
+    pending: Mutex<VecDeque<Position>>,
+}
+
+impl OffsetTracker {
+    pub fn new(consumer: BaseConsumer) -> Self {
+        Self {
+            consumer,
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn consumer(&self) -> &BaseConsumer {
+        &self.consumer
+    }
+
+    /// Records the position of `message`, ingested with `identifier`.
+    pub fn ingested(&self, identifier: (u64, u64), message: &BorrowedMessage) {
+        self.pending.lock().push_back((
+            identifier,
+            message.topic().to_string(),
+            message.partition(),
+            message.offset(),
+        ));
+    }
+
+    /// Commits the position after the last message up to `checkpoint` of every partition.
+    ///
+    /// The offsets carry the transaction id of `checkpoint` as metadata, which tells whether the
+    /// group is at the checkpoint when ingestion resumes, see [`super::client::is_resumable`].
+    pub fn commit(&self, checkpoint: (u64, u64)) -> Result<(), KafkaConnectorError> {
+        let positions = take_checkpointed(&mut self.pending.lock(), checkpoint);
+        if positions.is_empty() {
+            return Ok(());
+        }
+
+        let mut list = TopicPartitionList::new();
+        for ((topic, partition), offset) in positions {
+            let mut position = list.add_partition(&topic, partition);
+            position
+                .set_offset(Offset::Offset(offset + 1))
+                .map_err(AssignmentError)?;
+            position.set_metadata(checkpoint.0.to_string());
+        }
+        self.consumer
+            .commit(&list, CommitMode::Sync)
+            .map_err(OffsetCommitError)
+    }
+}
+
+type Position = ((u64, u64), String, i32, i64);
+
+/// Removes the positions up to `checkpoint` from `pending`, and returns the offset of the last
+/// message of each partition among them.
+fn take_checkpointed(
+    pending: &mut VecDeque<Position>,
+    checkpoint: (u64, u64),
+) -> HashMap<(String, i32), i64> {
+    let mut positions = HashMap::new();
+    while let Some((identifier, ..)) = pending.front() {
+        if *identifier > checkpoint {
+            break;
+        }
+        let (_, topic, partition, offset) = pending.pop_front().expect("front exists");
+        positions.insert((topic, partition), offset);
+    }
+    positions
+}
+
+impl Debug for OffsetTracker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OffsetTracker")
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_checkpointed() {
+        let position =
+            |txid, partition, offset| ((txid, 0), "trips".to_string(), partition, offset);
+        let mut pending = VecDeque::from([
+            position(0, 0, 10),
+            position(1, 1, 4),
+            position(2, 0, 11),
+            position(3, 1, 5),
+        ]);
+
+        let positions = take_checkpointed(&mut pending, (2, 0));
+        assert_eq!(
+            positions,
+            HashMap::from([
+                (("trips".to_string(), 0), 11),
+                (("trips".to_string(), 1), 4)
+            ])
+        );
+        assert_eq!(pending, VecDeque::from([position(3, 1, 5)]));
+
+        // A checkpoint of the same messages again has nothing left to commit.
+        assert!(take_checkpointed(&mut pending, (2, 0)).is_empty());
+    }
+}
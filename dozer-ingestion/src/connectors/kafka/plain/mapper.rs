@@ -0,0 +1,339 @@
+use crate::errors::KafkaConnectorError;
+use crate::errors::KafkaConnectorError::{MessageNotAnObject, NullValue, ValueConversionError};
+use apache_avro::types::Value as AvroValue;
+use base64::Engine;
+use dozer_types::chrono::{DateTime, FixedOffset, NaiveDate, Offset, TimeZone, Utc};
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::rust_decimal::prelude::FromPrimitive;
+use dozer_types::rust_decimal::Decimal;
+use dozer_types::serde_json::Value as JsonValue;
+use dozer_types::types::{Field, FieldDefinition, FieldType, Schema};
+use std::str::FromStr;
+
+/// Days from 0001-01-01 to 1970-01-01, for dates counted from the Unix epoch.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Reads the fields of `schema` from a JSON object. Missing keys are null.
+pub fn json_to_fields(
+    topic: &str,
+    value: JsonValue,
+    schema: &Schema,
+) -> Result<Vec<Field>, KafkaConnectorError> {
+    let JsonValue::Object(mut object) = value else {
+        return Err(MessageNotAnObject(topic.to_string()));
+    };
+    schema
+        .fields
+        .iter()
+        .map(|field| match object.remove(&field.name) {
+            None | Some(JsonValue::Null) => null(field),
+            Some(value) => json_to_field(value, field),
+        })
+        .collect()
+}
+
+/// Reads the fields of `schema` from an Avro record. Missing fields are null.
+pub fn avro_to_fields(
+    topic: &str,
+    value: AvroValue,
+    schema: &Schema,
+) -> Result<Vec<Field>, KafkaConnectorError> {
+    let AvroValue::Record(mut record) = value else {
+        return Err(MessageNotAnObject(topic.to_string()));
+    };
+    schema
+        .fields
+        .iter()
+        .map(|field| {
+            let value = record
+                .iter()
+                .position(|(name, _)| name == &field.name)
+                .map(|index| record.swap_remove(index).1);
+            match value.map(unwrap_union) {
+                None | Some(AvroValue::Null) => null(field),
+                Some(value) => avro_to_field(value, field),
+            }
+        })
+        .collect()
+}
+
+/// Reads the primary key of `schema` from the key of a tombstone, a JSON object with the primary
+/// key fields, or the value alone when the key has a single field. The other fields are null.
+pub fn json_key_to_fields(
+    topic: &str,
+    key: JsonValue,
+    schema: &Schema,
+) -> Result<Vec<Field>, KafkaConnectorError> {
+    let key_schema = key_schema(schema);
+    let key = match key {
+        JsonValue::Object(_) => key,
+        value if key_schema.fields.len() == 1 => JsonValue::Object(
+            [(key_schema.fields[0].name.clone(), value)]
+                .into_iter()
+                .collect(),
+        ),
+        _ => return Err(MessageNotAnObject(topic.to_string())),
+    };
+    let key_values = json_to_fields(topic, key, &key_schema)?;
+    Ok(with_primary_key(schema, key_values))
+}
+
+/// Reads the primary key of `schema` from the key of a tombstone, an Avro record with the primary
+/// key fields, or the value alone when the key has a single field. The other fields are null.
+pub fn avro_key_to_fields(
+    topic: &str,
+    key: AvroValue,
+    schema: &Schema,
+) -> Result<Vec<Field>, KafkaConnectorError> {
+    let key_schema = key_schema(schema);
+    let key = match unwrap_union(key) {
+        AvroValue::Record(fields) => AvroValue::Record(fields),
+        value if key_schema.fields.len() == 1 => {
+            AvroValue::Record(vec![(key_schema.fields[0].name.clone(), value)])
+        }
+        _ => return Err(MessageNotAnObject(topic.to_string())),
+    };
+    let key_values = avro_to_fields(topic, key, &key_schema)?;
+    Ok(with_primary_key(schema, key_values))
+}
+
+/// The primary key fields of `schema`, which a key must have.
+fn key_schema(schema: &Schema) -> Schema {
+    Schema {
+        identifier: None,
+        fields: schema
+            .primary_index
+            .iter()
+            .map(|index| FieldDefinition {
+                nullable: false,
+                ..schema.fields[*index].clone()
+            })
+            .collect(),
+        primary_index: vec![],
+    }
+}
+
+fn with_primary_key(schema: &Schema, key_values: Vec<Field>) -> Vec<Field> {
+    let mut values = vec![Field::Null; schema.fields.len()];
+    for (index, value) in schema.primary_index.iter().zip(key_values) {
+        values[*index] = value;
+    }
+    values
+}
+
+fn null(field: &FieldDefinition) -> Result<Field, KafkaConnectorError> {
+    if field.nullable {
+        Ok(Field::Null)
+    } else {
+        Err(NullValue(field.name.clone()))
+    }
+}
+
+fn json_to_field(value: JsonValue, field: &FieldDefinition) -> Result<Field, KafkaConnectorError> {
+    let error = |value: &JsonValue| {
+        ValueConversionError(
+            field.name.clone(),
+            format!("unexpected value {value} for {}", field.typ),
+        )
+    };
+
+    let result = match (field.typ, &value) {
+        (FieldType::UInt, JsonValue::Number(n)) => n.as_u64().map(Field::UInt),
+        (FieldType::Int, JsonValue::Number(n)) => n.as_i64().map(Field::Int),
+        (FieldType::Float, JsonValue::Number(n)) => {
+            n.as_f64().map(|f| Field::Float(OrderedFloat(f)))
+        }
+        (FieldType::Boolean, JsonValue::Bool(b)) => Some(Field::Boolean(*b)),
+        (FieldType::String, JsonValue::String(s)) => Some(Field::String(s.clone())),
+        (FieldType::Text, JsonValue::String(s)) => Some(Field::Text(s.clone())),
+        (FieldType::Binary, JsonValue::String(s)) => base64::engine::general_purpose::STANDARD
+            .decode(s)
+            .ok()
+            .map(Field::Binary),
+        (FieldType::Decimal, JsonValue::Number(n)) => {
+            Decimal::from_str(&n.to_string()).ok().map(Field::Decimal)
+        }
+        (FieldType::Decimal, JsonValue::String(s)) => Decimal::from_str(s).ok().map(Field::Decimal),
+        (FieldType::Timestamp, JsonValue::String(s)) => {
+            DateTime::parse_from_rfc3339(s).ok().map(Field::Timestamp)
+        }
+        (FieldType::Timestamp, JsonValue::Number(n)) => n
+            .as_i64()
+            .and_then(timestamp_from_millis)
+            .map(Field::Timestamp),
+        (FieldType::Date, JsonValue::String(s)) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .ok()
+            .map(Field::Date),
+        (FieldType::Bson, JsonValue::Object(_)) => bson::to_vec(&value).ok().map(Field::Bson),
+        _ => None,
+    };
+    result.ok_or_else(|| error(&value))
+}
+
+fn avro_to_field(value: AvroValue, field: &FieldDefinition) -> Result<Field, KafkaConnectorError> {
+    let error = |value: &AvroValue| {
+        ValueConversionError(
+            field.name.clone(),
+            format!("unexpected value {value:?} for {}", field.typ),
+        )
+    };
+
+    let result = match (field.typ, &value) {
+        (FieldType::UInt, AvroValue::Int(i)) => u64::try_from(*i).ok().map(Field::UInt),
+        (FieldType::UInt, AvroValue::Long(i)) => u64::try_from(*i).ok().map(Field::UInt),
+        (FieldType::Int, AvroValue::Int(i)) => Some(Field::Int(*i as i64)),
+        (FieldType::Int, AvroValue::Long(i)) => Some(Field::Int(*i)),
+        (FieldType::Float, AvroValue::Float(f)) => Some(Field::Float(OrderedFloat(*f as f64))),
+        (FieldType::Float, AvroValue::Double(f)) => Some(Field::Float(OrderedFloat(*f))),
+        (FieldType::Boolean, AvroValue::Boolean(b)) => Some(Field::Boolean(*b)),
+        (FieldType::String, AvroValue::String(s) | AvroValue::Enum(_, s)) => {
+            Some(Field::String(s.clone()))
+        }
+        (FieldType::String, AvroValue::Uuid(uuid)) => Some(Field::String(uuid.to_string())),
+        (FieldType::Text, AvroValue::String(s)) => Some(Field::Text(s.clone())),
+        (FieldType::Binary, AvroValue::Bytes(bytes) | AvroValue::Fixed(_, bytes)) => {
+            Some(Field::Binary(bytes.clone()))
+        }
+        (FieldType::Decimal, AvroValue::String(s)) => Decimal::from_str(s).ok().map(Field::Decimal),
+        (FieldType::Decimal, AvroValue::Double(f)) => Decimal::from_f64(*f).map(Field::Decimal),
+        (FieldType::Decimal, AvroValue::Long(i)) => Some(Field::Decimal(Decimal::from(*i))),
+        (FieldType::Timestamp, AvroValue::TimestampMillis(millis) | AvroValue::Long(millis)) => {
+            timestamp_from_millis(*millis).map(Field::Timestamp)
+        }
+        (FieldType::Timestamp, AvroValue::TimestampMicros(micros)) => Utc
+            .timestamp_opt(
+                micros.div_euclid(1_000_000),
+                (micros.rem_euclid(1_000_000) * 1_000) as u32,
+            )
+            .single()
+            .map(|timestamp| Field::Timestamp(timestamp.with_timezone(&Utc.fix()))),
+        (FieldType::Date, AvroValue::Date(days)) => days
+            .checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+            .map(Field::Date),
+        (FieldType::Bson, AvroValue::Record(_) | AvroValue::Map(_)) => {
+            JsonValue::try_from(value.clone())
+                .ok()
+                .and_then(|json| bson::to_vec(&json).ok())
+                .map(Field::Bson)
+        }
+        _ => None,
+    };
+    result.ok_or_else(|| error(&value))
+}
+
+/// Nullable Avro fields are unions with `null`.
+fn unwrap_union(value: AvroValue) -> AvroValue {
+    match value {
+        AvroValue::Union(_, value) => unwrap_union(*value),
+        value => value,
+    }
+}
+
+fn timestamp_from_millis(millis: i64) -> Option<DateTime<FixedOffset>> {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .map(|timestamp| timestamp.with_timezone(&Utc.fix()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dozer_types::serde_json::json;
+    use dozer_types::types::SourceDefinition;
+
+    fn schema() -> Schema {
+        let field = |name: &str, typ, nullable| FieldDefinition {
+            name: name.to_string(),
+            typ,
+            nullable,
+            source: SourceDefinition::Dynamic,
+        };
+        Schema {
+            identifier: None,
+            fields: vec![
+                field("id", FieldType::UInt, false),
+                field("fare", FieldType::Decimal, true),
+                field("started_at", FieldType::Timestamp, true),
+                field("day", FieldType::Date, true),
+            ],
+            primary_index: vec![0],
+        }
+    }
+
+    #[test]
+    fn test_json_to_fields() {
+        let fields = json_to_fields(
+            "trips",
+            json!({"id": 1, "fare": "12.50", "started_at": 0, "extra": true}),
+            &schema(),
+        )
+        .unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                Field::UInt(1),
+                Field::Decimal(Decimal::new(1250, 2)),
+                Field::Timestamp(timestamp_from_millis(0).unwrap()),
+                Field::Null,
+            ]
+        );
+
+        assert!(matches!(
+            json_to_fields("trips", json!({"fare": 1}), &schema()),
+            Err(NullValue(_))
+        ));
+        assert!(matches!(
+            json_to_fields("trips", json!([1]), &schema()),
+            Err(MessageNotAnObject(_))
+        ));
+    }
+
+    #[test]
+    fn test_key_to_fields() {
+        let expected = vec![Field::UInt(7), Field::Null, Field::Null, Field::Null];
+        assert_eq!(
+            json_key_to_fields("trips", json!({"id": 7, "fare": 1}), &schema()).unwrap(),
+            expected
+        );
+        assert_eq!(
+            json_key_to_fields("trips", json!(7), &schema()).unwrap(),
+            expected
+        );
+        assert_eq!(
+            avro_key_to_fields("trips", AvroValue::Long(7), &schema()).unwrap(),
+            expected
+        );
+        assert!(matches!(
+            json_key_to_fields("trips", json!({"fare": 1}), &schema()),
+            Err(NullValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_avro_to_fields() {
+        let record = AvroValue::Record(vec![
+            ("id".to_string(), AvroValue::Long(1)),
+            (
+                "fare".to_string(),
+                AvroValue::Union(1, Box::new(AvroValue::Double(12.5))),
+            ),
+            ("started_at".to_string(), AvroValue::TimestampMicros(1_500)),
+            ("day".to_string(), AvroValue::Date(1)),
+        ]);
+        let fields = avro_to_fields("trips", record, &schema()).unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                Field::UInt(1),
+                Field::Decimal(Decimal::new(125, 1)),
+                Field::Timestamp(
+                    Utc.timestamp_opt(0, 1_500_000)
+                        .unwrap()
+                        .with_timezone(&Utc.fix())
+                ),
+                Field::Date(NaiveDate::from_ymd_opt(1970, 1, 2).unwrap()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+pub mod mapper;
+pub mod stream_consumer;
+
+use crate::connectors::{CdcType, SourceSchema};
+use crate::errors::KafkaConnectorError;
+use crate::errors::KafkaConnectorError::{
+    FieldTypeNotSupported, PrimaryKeyNotFound, UnsupportedFormat,
+};
+use dozer_types::ingestion_types::KafkaTopic;
+use dozer_types::types::{FieldDefinition, FieldType, Schema, SchemaIdentifier, SourceDefinition};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopicFormat {
+    Json,
+    /// Avro in the Confluent wire format, with the writer schema id before the data.
+    Avro,
+}
+
+impl TopicFormat {
+    pub fn parse(topic: &KafkaTopic) -> Result<Self, KafkaConnectorError> {
+        match topic.format.to_lowercase().as_str() {
+            "json" => Ok(TopicFormat::Json),
+            "avro" => Ok(TopicFormat::Avro),
+            _ => Err(UnsupportedFormat(topic.name.clone(), topic.format.clone())),
+        }
+    }
+}
+
+/// Maps the declared fields of a topic. Messages are inserted, and with a primary key, tombstones
+/// delete the record of the key they carry, so deletes only have the primary key.
+pub fn map_schema(topic: &KafkaTopic, id: u32) -> Result<SourceSchema, KafkaConnectorError> {
+    let fields = topic
+        .fields
+        .iter()
+        .map(|field| {
+            let typ = match FieldType::try_from(field.typ.as_str()) {
+                Ok(FieldType::Interval) | Err(_) => {
+                    return Err(FieldTypeNotSupported(field.name.clone(), field.typ.clone()))
+                }
+                Ok(typ) => typ,
+            };
+            Ok(FieldDefinition {
+                name: field.name.clone(),
+                typ,
+                nullable: field.nullable,
+                source: SourceDefinition::Dynamic,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let primary_index = topic
+        .primary_key
+        .iter()
+        .map(|key| {
+            fields
+                .iter()
+                .position(|field| &field.name == key)
+                .ok_or_else(|| PrimaryKeyNotFound(topic.name.clone(), key.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let cdc_type = if primary_index.is_empty() {
+        CdcType::Nothing
+    } else {
+        CdcType::OnlyPK
+    };
+    let schema = Schema {
+        identifier: Some(SchemaIdentifier { id, version: 1 }),
+        fields,
+        primary_index,
+    };
+    Ok(SourceSchema::new(schema, cdc_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dozer_types::ingestion_types::KafkaField;
+
+    fn topic(typ: &str, primary_key: &str) -> KafkaTopic {
+        KafkaTopic {
+            name: "trips".to_string(),
+            format: "json".to_string(),
+            fields: vec![
+                KafkaField {
+                    name: "id".to_string(),
+                    typ: "uint".to_string(),
+                    nullable: false,
+                },
+                KafkaField {
+                    name: "fare".to_string(),
+                    typ: typ.to_string(),
+                    nullable: true,
+                },
+            ],
+            primary_key: vec![primary_key.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_map_schema() {
+        let schema = map_schema(&topic("decimal", "id"), 3).unwrap();
+        assert_eq!(schema.cdc_type, CdcType::OnlyPK);
+        assert_eq!(
+            schema.schema.identifier,
+            Some(SchemaIdentifier { id: 3, version: 1 })
+        );
+        assert_eq!(schema.schema.primary_index, vec![0]);
+        assert_eq!(schema.schema.fields[1].typ, FieldType::Decimal);
+        assert!(schema.schema.fields[1].nullable);
+
+        let mut appended = topic("decimal", "id");
+        appended.primary_key.clear();
+        assert_eq!(map_schema(&appended, 0).unwrap().cdc_type, CdcType::Nothing);
+
+        assert!(matches!(
+            map_schema(&topic("money", "id"), 0),
+            Err(FieldTypeNotSupported(_, _))
+        ));
+        assert!(matches!(
+            map_schema(&topic("decimal", "trip_id"), 0),
+            Err(PrimaryKeyNotFound(_, _))
+        ));
+    }
+}
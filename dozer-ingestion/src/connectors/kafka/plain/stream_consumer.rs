@@ -0,0 +1,145 @@
+use crate::connectors::kafka::offset_tracker::OffsetTracker;
+use crate::connectors::kafka::plain::mapper::{
+    avro_key_to_fields, avro_to_fields, json_key_to_fields, json_to_fields,
+};
+use crate::connectors::kafka::plain::TopicFormat;
+use crate::connectors::kafka::stream_consumer::{message_identifier, StreamConsumer};
+use crate::errors::KafkaConnectorError::{
+    AvroDecodeError, JsonDecodeError, PollingError, SchemaRegistryNotDefined, TombstoneWithoutKey,
+};
+use crate::errors::{ConnectorError, KafkaConnectorError};
+use crate::ingestion::Ingestor;
+use dozer_types::ingestion_types::IngestionMessage;
+use dozer_types::serde_json;
+use dozer_types::types::{Field, Operation, Record, Schema};
+use rdkafka::Message;
+use schema_registry_converter::blocking::avro::AvroDecoder;
+use schema_registry_converter::blocking::schema_registry::SrSettings;
+
+/// A topic and the schema its messages are read with.
+#[derive(Debug, Clone)]
+pub struct PlainTopic {
+    pub name: String,
+    pub format: TopicFormat,
+    pub schema: Schema,
+}
+
+/// Ingests every message of the topics as an insert. Tombstones of topics with a primary key are
+/// ingested as deletes of their key.
+pub struct PlainStreamConsumer<'a> {
+    topics: Vec<PlainTopic>,
+    avro_decoder: Option<AvroDecoder<'a>>,
+}
+
+impl<'a> PlainStreamConsumer<'a> {
+    pub fn new(
+        topics: Vec<PlainTopic>,
+        schema_registry_url: Option<&str>,
+    ) -> Result<Self, KafkaConnectorError> {
+        let avro_decoder = match schema_registry_url {
+            Some(url) => Some(AvroDecoder::new(SrSettings::new(url.to_string()))),
+            None => {
+                if let Some(topic) = topics
+                    .iter()
+                    .find(|topic| topic.format == TopicFormat::Avro)
+                {
+                    return Err(SchemaRegistryNotDefined(topic.name.clone()));
+                }
+                None
+            }
+        };
+        Ok(Self {
+            topics,
+            avro_decoder,
+        })
+    }
+
+    /// Decodes the message `payload`, or the primary key from its `key` if it's a tombstone.
+    fn decode(
+        &self,
+        topic: &PlainTopic,
+        payload: &[u8],
+        is_key: bool,
+    ) -> Result<Vec<Field>, KafkaConnectorError> {
+        match topic.format {
+            TopicFormat::Json => {
+                let value = serde_json::from_slice(payload)
+                    .map_err(|e| JsonDecodeError(topic.name.clone(), e))?;
+                if is_key {
+                    json_key_to_fields(&topic.name, value, &topic.schema)
+                } else {
+                    json_to_fields(&topic.name, value, &topic.schema)
+                }
+            }
+            TopicFormat::Avro => {
+                let decoder = self
+                    .avro_decoder
+                    .as_ref()
+                    .ok_or_else(|| SchemaRegistryNotDefined(topic.name.clone()))?;
+                let result = decoder
+                    .decode(Some(payload))
+                    .map_err(|e| AvroDecodeError(topic.name.clone(), e))?;
+                if is_key {
+                    avro_key_to_fields(&topic.name, result.value, &topic.schema)
+                } else {
+                    avro_to_fields(&topic.name, result.value, &topic.schema)
+                }
+            }
+        }
+    }
+}
+
+impl<'a> StreamConsumer for PlainStreamConsumer<'a> {
+    fn run(
+        &self,
+        offsets: &OffsetTracker,
+        ingestor: &Ingestor,
+        first_txid: u64,
+    ) -> Result<(), ConnectorError> {
+        let mut txid = first_txid;
+        loop {
+            let Some(message) = offsets.consumer().poll(None) else {
+                continue;
+            };
+            let message = message.map_err(PollingError)?;
+
+            let Some(topic) = self
+                .topics
+                .iter()
+                .find(|topic| topic.name == message.topic())
+            else {
+                continue;
+            };
+            let operation = match message.payload() {
+                Some(payload) => Operation::Insert {
+                    new: Record::new(
+                        topic.schema.identifier,
+                        self.decode(topic, payload, false)?,
+                        None,
+                    ),
+                },
+                // Tombstones carry no data without a primary key to delete.
+                None if topic.schema.primary_index.is_empty() => continue,
+                None => {
+                    let key = message
+                        .key()
+                        .ok_or_else(|| TombstoneWithoutKey(topic.name.clone()))?;
+                    Operation::Delete {
+                        old: Record::new(
+                            topic.schema.identifier,
+                            self.decode(topic, key, true)?,
+                            None,
+                        ),
+                    }
+                }
+            };
+
+            let (txn, seq_no) = message_identifier(txid, &message);
+            offsets.ingested((txn, seq_no), &message);
+            ingestor
+                .handle_message(IngestionMessage::new_op(txn, seq_no, operation))
+                .map_err(ConnectorError::IngestorError)?;
+            txid += 1;
+        }
+    }
+}
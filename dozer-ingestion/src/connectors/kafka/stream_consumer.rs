@@ -1,7 +1,52 @@
+use crate::connectors::kafka::offset_tracker::OffsetTracker;
 use crate::errors::ConnectorError;
 use crate::ingestion::Ingestor;
-use kafka::consumer::Consumer;
+use rdkafka::message::BorrowedMessage;
+use rdkafka::Message;
 
 pub trait StreamConsumer {
-    fn run(&self, con: Consumer, ingestor: &Ingestor) -> Result<(), ConnectorError>;
+    /// Ingests the messages of the partitions assigned to the consumer of `offsets`, recording
+    /// their positions in it. The first message is identified by transaction `first_txid`.
+    fn run(
+        &self,
+        offsets: &OffsetTracker,
+        ingestor: &Ingestor,
+        first_txid: u64,
+    ) -> Result<(), ConnectorError>;
+}
+
+/// Bits of a position that hold the offset, the partition is in the bits above.
+const OFFSET_BITS: u32 = 48;
+
+/// The message identifier of a Kafka message.
+///
+/// Messages of different partitions interleave, so the transaction id is the number of the
+/// message in the ingestion, which keeps identifiers monotonic across partitions. The sequence
+/// number is the partition and offset the message was read at, which ingestion resumes after.
+pub fn message_identifier(txid: u64, message: &BorrowedMessage) -> (u64, u64) {
+    (txid, encode_position(message.partition(), message.offset()))
+}
+
+fn encode_position(partition: i32, offset: i64) -> u64 {
+    ((partition as u64) << OFFSET_BITS) | offset as u64
+}
+
+/// The partition and offset of a message, from the sequence number of its identifier.
+pub fn decode_position(seq_in_tx: u64) -> (i32, i64) {
+    (
+        (seq_in_tx >> OFFSET_BITS) as i32,
+        (seq_in_tx & ((1 << OFFSET_BITS) - 1)) as i64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_encoding() {
+        assert_eq!(decode_position(encode_position(3, 42)), (3, 42));
+        assert_eq!(decode_position(encode_position(0, 0)), (0, 0));
+        assert!(encode_position(1, 0) > encode_position(0, 42));
+    }
 }
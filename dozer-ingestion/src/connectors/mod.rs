@@ -1,13 +1,16 @@
 pub mod ethereum;
 pub mod grpc;
 pub mod kafka;
+pub mod mysql;
 pub mod object_store;
 pub mod postgres;
 
-use crate::connectors::postgres::connection::helper::map_connection_config;
+use crate::connectors::postgres::connection::helper::{map_connection_config, map_tls_config};
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 use crate::connectors::kafka::connector::KafkaConnector;
+use crate::connectors::mysql::connector::MySQLConnector;
 use crate::connectors::postgres::connector::{PostgresConfig, PostgresConnector};
 use crate::errors::ConnectorError;
 use crate::ingestion::Ingestor;
@@ -105,8 +108,38 @@ pub trait Connector: Send + Sync + Debug {
         Ok((table_infos, schemas))
     }
 
-    /// Starts outputting data from `tables` to `ingestor`. This method should never return unless there is an unrecoverable error.
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> Result<(), ConnectorError>;
+    /// Gives the connector a directory of its own, where it can persist what it needs to resume ingestion.
+    /// It's only given when the connector runs in a pipeline, before the connector is started.
+    fn set_state_dir(&mut self, _state_dir: PathBuf) {}
+
+    /// Checks if the connector can resume outputting `tables` after `last_checkpoint`, the identifier of the last message that was fully processed.
+    /// If this function returns false, the connector is started from the beginning.
+    fn can_start_from(
+        &self,
+        _tables: &[TableInfo],
+        _last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError> {
+        Ok(false)
+    }
+
+    /// Starts outputting data from `tables` to `ingestor`, after `last_checkpoint` if it's given. This method should never return unless there is an unrecoverable error.
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError>;
+
+    /// Called once the pipeline has committed the messages up to `checkpoint`, so it won't resume from before it anymore.
+    /// Called while `start` is running, from another thread.
+    fn on_checkpoint(&self, _checkpoint: (u64, u64)) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+
+    /// Releases what the connector left behind in the source, like replication slots. Called by `dozer clean`.
+    fn clean(&self) -> Result<(), ConnectorError> {
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -147,11 +180,16 @@ pub fn get_connector(connection: Connection) -> Result<Box<dyn Connector>, Conne
         .config
         .ok_or_else(|| ConnectorError::MissingConfiguration(connection.name.clone()))?;
     match config {
-        ConnectionConfig::Postgres(_) => {
-            let config = map_connection_config(&config)?;
+        ConnectionConfig::Postgres(ref postgres) => {
             let postgres_config = PostgresConfig {
                 name: connection.name,
-                config,
+                config: map_connection_config(&config)?,
+                tls: map_tls_config(&config)?,
+                slot_name: postgres.slot_name.clone(),
+                publication_name: postgres.publication_name.clone(),
+                reuse_publication: postgres.reuse_publication,
+                schemas: postgres.schemas.clone(),
+                clean_slots: postgres.clean_slots.clone(),
             };
 
             if let Some(dbname) = postgres_config.config.get_dbname() {
@@ -159,6 +197,9 @@ pub fn get_connector(connection: Connection) -> Result<Box<dyn Connector>, Conne
             }
             Ok(Box::new(PostgresConnector::new(postgres_config)))
         }
+        ConnectionConfig::MySQL(mysql_config) => {
+            Ok(Box::new(MySQLConnector::new(connection.name, mysql_config)))
+        }
         ConnectionConfig::Ethereum(eth_config) => match eth_config.provider.unwrap() {
             dozer_types::ingestion_types::EthProviderConfig::Log(log_config) => Ok(Box::new(
                 EthLogConnector::new(2, log_config, connection.name),
@@ -191,7 +232,11 @@ pub fn get_connector(connection: Connection) -> Result<Box<dyn Connector>, Conne
                 snowflake_config,
             )))
         }
-        ConnectionConfig::Kafka(kafka_config) => Ok(Box::new(KafkaConnector::new(5, kafka_config))),
+        ConnectionConfig::Kafka(kafka_config) => Ok(Box::new(KafkaConnector::new(
+            5,
+            kafka_config,
+            connection.name,
+        ))),
         ConnectionConfig::S3Storage(object_store_config) => {
             Ok(Box::new(ObjectStoreConnector::new(5, object_store_config)))
         }
@@ -207,6 +252,7 @@ pub fn get_connector(connection: Connection) -> Result<Box<dyn Connector>, Conne
 pub fn get_connector_info_table(connection: &Connection) -> Option<Table> {
     match &connection.config {
         Some(ConnectionConfig::Postgres(config)) => Some(config.convert_to_table()),
+        Some(ConnectionConfig::MySQL(config)) => Some(config.convert_to_table()),
         Some(ConnectionConfig::Ethereum(config)) => Some(config.convert_to_table()),
         Some(ConnectionConfig::Snowflake(config)) => Some(config.convert_to_table()),
         Some(ConnectionConfig::Kafka(config)) => Some(config.convert_to_table()),
@@ -0,0 +1,357 @@
+use crate::connectors::mysql::conversion::binlog_value_to_field;
+use crate::connectors::mysql::gtid::{Gtid, GtidSet, GtidTracker};
+use crate::connectors::mysql::schema::TableDefinition;
+use crate::errors::MySQLConnectorError::{
+    BinlogError, BinlogFileNameError, BinlogReadError, BinlogStreamEnded, ConnectionFailure,
+    QueryExecutionError, ValueConversionError,
+};
+use crate::errors::{ConnectorError, MySQLConnectorError};
+use crate::ingestion::Ingestor;
+use dozer_types::ingestion_types::IngestionMessage;
+use dozer_types::log::info;
+use dozer_types::types::{Field, Operation, Record, SchemaIdentifier};
+use futures::StreamExt;
+use mysql_async::prelude::Queryable;
+use mysql_async::{BinlogRequest, BinlogStream, Conn, Opts, Row};
+use mysql_common::binlog::events::{EventData, RowsEventData};
+use mysql_common::binlog::row::BinlogRow;
+use std::collections::HashMap;
+
+/// A position in the binlog, between two events.
+///
+/// Transaction ids are binlog positions, with the index of the binlog file in the high 32 bits and
+/// the offset in the file in the low 32 bits. They grow along the binlog and tell where to resume
+/// reading it. Snapshot rows use transaction 0, and the end of the snapshot uses the position the
+/// binlog is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BinlogPosition {
+    pub file_index: u64,
+    pub offset: u32,
+}
+
+impl BinlogPosition {
+    pub fn new(filename: &str, offset: u32) -> Result<Self, MySQLConnectorError> {
+        Ok(Self {
+            file_index: file_index(filename)?,
+            offset,
+        })
+    }
+
+    pub fn from_txid(txid: u64) -> Self {
+        Self {
+            file_index: txid >> 32,
+            offset: txid as u32,
+        }
+    }
+
+    pub fn txid(&self) -> u64 {
+        (self.file_index << 32) | self.offset as u64
+    }
+}
+
+/// Parses the index of a binlog file, named like `binlog.000042`.
+fn file_index(filename: &str) -> Result<u64, MySQLConnectorError> {
+    filename
+        .rsplit_once('.')
+        .and_then(|(_, index)| index.parse::<u32>().ok())
+        .map(u64::from)
+        .ok_or_else(|| BinlogFileNameError(filename.to_string()))
+}
+
+/// Lists the binlog files of the server with their sizes.
+async fn list_binlog_files(conn: &mut Conn) -> Result<Vec<(String, u64)>, MySQLConnectorError> {
+    let query = "SHOW BINARY LOGS";
+    let rows: Vec<Row> = conn
+        .query(query)
+        .await
+        .map_err(|e| QueryExecutionError(query.to_string(), e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| Some((row.get(0)?, row.get(1)?)))
+        .collect())
+}
+
+/// Finds the binlog file that `position` is in, if it wasn't purged and the position is within it.
+pub async fn find_binlog_file(
+    conn: &mut Conn,
+    position: BinlogPosition,
+) -> Result<Option<String>, MySQLConnectorError> {
+    Ok(list_binlog_files(conn)
+        .await?
+        .into_iter()
+        .find(|(filename, size)| {
+            matches!(file_index(filename), Ok(index) if index == position.file_index)
+                && position.offset as u64 <= *size
+        })
+        .map(|(filename, _)| filename))
+}
+
+/// Where to start reading the binlog.
+#[derive(Debug, Clone)]
+pub enum BinlogStart {
+    /// At `position` of `filename`. Operations read before the next transaction belong to the
+    /// transaction at `position`, and the first `skip` of them are not forwarded.
+    Position {
+        filename: String,
+        position: BinlogPosition,
+        skip: u64,
+    },
+    /// At the first transaction that isn't in `executed`, wherever it is in the binlog of the
+    /// server. If it's `resume`, the first operations of it that were already ingested are not
+    /// forwarded.
+    Gtid {
+        executed: GtidSet,
+        resume: Option<(Gtid, u64)>,
+    },
+}
+
+/// Tails the binlog from the end of the snapshot, or from where a previous run stopped, and
+/// forwards the row events of `tables`.
+///
+/// Each transaction is identified by the binlog position right after its GTID event, or its
+/// anonymous GTID event when the server's GTID mode is off.
+pub struct BinlogIngestor<'a> {
+    name: &'a str,
+    ingestor: &'a Ingestor,
+    opts: Opts,
+    server_id: u32,
+    tables: &'a [TableDefinition],
+    gtids: &'a GtidTracker,
+    /// Index of each table in `tables` by database and table name.
+    table_indexes: HashMap<(String, String), usize>,
+    /// The binlog file being read.
+    filename: String,
+    position: BinlogPosition,
+    /// The transaction being read.
+    txn: u64,
+    seq_no: u64,
+    /// Operations of the transaction being read that were ingested before the connector was
+    /// restarted.
+    skip: u64,
+    /// GTIDs of the transactions to start after, when starting from a GTID set.
+    executed: Option<GtidSet>,
+    /// The transaction to skip the first operations of when it's read, and how many.
+    resume: Option<(Gtid, u64)>,
+}
+
+impl<'a> BinlogIngestor<'a> {
+    /// Reads the binlog from `start`, recording the GTID of each transaction in `gtids`.
+    pub fn new(
+        name: &'a str,
+        ingestor: &'a Ingestor,
+        opts: Opts,
+        server_id: u32,
+        tables: &'a [TableDefinition],
+        gtids: &'a GtidTracker,
+        start: BinlogStart,
+    ) -> Self {
+        let table_indexes = tables
+            .iter()
+            .enumerate()
+            .map(|(index, table)| ((table.database.clone(), table.name.clone()), index))
+            .collect();
+        let (filename, position, skip, executed, resume) = match start {
+            BinlogStart::Position {
+                filename,
+                position,
+                skip,
+            } => (filename, position, skip, None, None),
+            // The server tells the file and position in the first rotate event.
+            BinlogStart::Gtid { executed, resume } => (
+                String::new(),
+                BinlogPosition::from_txid(0),
+                0,
+                Some(executed),
+                resume,
+            ),
+        };
+        Self {
+            name,
+            ingestor,
+            opts,
+            server_id,
+            tables,
+            gtids,
+            table_indexes,
+            filename,
+            position,
+            txn: position.txid(),
+            seq_no: 0,
+            skip,
+            executed,
+            resume,
+        }
+    }
+
+    pub async fn run(&mut self) -> Result<(), ConnectorError> {
+        let request = match &self.executed {
+            Some(executed) => {
+                info!(
+                    "[{}] Starting replication after GTID set {executed}",
+                    self.name
+                );
+                BinlogRequest::new(self.server_id)
+                    .with_use_gtid(true)
+                    .with_sids(executed.sids())
+            }
+            None => {
+                info!(
+                    "[{}] Starting replication at {}:{}",
+                    self.name, self.filename, self.position.offset
+                );
+                BinlogRequest::new(self.server_id)
+                    .with_filename(self.filename.as_bytes().to_vec())
+                    .with_pos(self.position.offset)
+            }
+        };
+
+        let conn = Conn::new(self.opts.clone())
+            .await
+            .map_err(ConnectionFailure)?;
+        let mut stream = conn.get_binlog_stream(request).await.map_err(BinlogError)?;
+
+        while let Some(event) = stream.next().await {
+            let event = event.map_err(BinlogError)?;
+            let Some(data) = event.read_data().map_err(BinlogReadError)? else {
+                continue;
+            };
+            match data {
+                // Sent when the stream starts and when the server moves on to the next file.
+                EventData::RotateEvent(rotate) => {
+                    self.filename = rotate.name().into_owned();
+                    self.position = BinlogPosition::new(&self.filename, rotate.position() as u32)?;
+                }
+                // Each transaction starts with one of them, anonymous when GTID mode is off.
+                EventData::GtidEvent(gtid) => {
+                    let gtid = Gtid {
+                        sid: gtid.sid(),
+                        gno: gtid.gno(),
+                    };
+                    self.begin_transaction(event.header().log_pos(), Some(gtid));
+                }
+                EventData::AnonymousGtidEvent(_) => {
+                    self.begin_transaction(event.header().log_pos(), None);
+                }
+                EventData::RowsEvent(rows) => {
+                    for operation in self.map_rows_event(&stream, &rows)? {
+                        if self.seq_no >= self.skip {
+                            self.ingestor
+                                .handle_message(IngestionMessage::new_op(
+                                    self.txn,
+                                    self.seq_no,
+                                    operation,
+                                ))
+                                .map_err(ConnectorError::IngestorError)?;
+                        }
+                        self.seq_no += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(BinlogStreamEnded.into())
+    }
+
+    fn begin_transaction(&mut self, log_pos: u32, gtid: Option<Gtid>) {
+        self.position.offset = log_pos;
+        self.txn = self.position.txid();
+        self.seq_no = 0;
+        // Another server may have the transaction after others it's missing.
+        self.skip = match self.resume {
+            Some((resume, skip)) if gtid == Some(resume) => {
+                self.resume = None;
+                skip
+            }
+            _ => 0,
+        };
+        self.gtids.begin(self.txn, gtid);
+    }
+
+    fn map_rows_event(
+        &self,
+        stream: &BinlogStream,
+        rows: &RowsEventData<'_>,
+    ) -> Result<Vec<Operation>, ConnectorError> {
+        let Some(tme) = stream.get_tme(rows.table_id()) else {
+            return Ok(vec![]);
+        };
+        let key = (
+            tme.database_name().into_owned(),
+            tme.table_name().into_owned(),
+        );
+        let Some(&index) = self.table_indexes.get(&key) else {
+            return Ok(vec![]);
+        };
+
+        let mut operations = vec![];
+        for row in rows.rows(tme) {
+            let (before, after) = row.map_err(BinlogReadError)?;
+            let operation = match (before, after) {
+                (None, Some(after)) => Operation::Insert {
+                    new: self.map_row(index, after)?,
+                },
+                (Some(before), Some(after)) => Operation::Update {
+                    old: self.map_row(index, before)?,
+                    new: self.map_row(index, after)?,
+                },
+                (Some(before), None) => Operation::Delete {
+                    old: self.map_row(index, before)?,
+                },
+                (None, None) => continue,
+            };
+            operations.push(operation);
+        }
+        Ok(operations)
+    }
+
+    fn map_row(&self, index: usize, mut row: BinlogRow) -> Result<Record, ConnectorError> {
+        let table = &self.tables[index];
+        let values = table
+            .columns
+            .iter()
+            .map(|column| {
+                let value = row.take(column.position).ok_or_else(|| {
+                    ValueConversionError(
+                        column.name.clone(),
+                        "missing from row event, set binlog_row_image to FULL".to_string(),
+                    )
+                })?;
+                binlog_value_to_field(value, column)
+            })
+            .collect::<Result<Vec<Field>, _>>()?;
+        Ok(Record::new(
+            Some(SchemaIdentifier {
+                id: index as u32,
+                version: 1,
+            }),
+            values,
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binlog_position_txid() {
+        let position = BinlogPosition::new("binlog.000042", 1234).unwrap();
+        assert_eq!(
+            position,
+            BinlogPosition {
+                file_index: 42,
+                offset: 1234
+            }
+        );
+        assert_eq!(BinlogPosition::from_txid(position.txid()), position);
+
+        // Positions in later files have greater transaction ids.
+        let next_file = BinlogPosition::new("binlog.000043", 4).unwrap();
+        assert!(next_file.txid() > position.txid());
+
+        assert!(BinlogPosition::new("binlog", 4).is_err());
+        assert!(BinlogPosition::new("binlog.index", 4).is_err());
+    }
+}
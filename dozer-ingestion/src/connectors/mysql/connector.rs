@@ -0,0 +1,474 @@
+use crate::connectors::mysql::binlog::{
+    find_binlog_file, BinlogIngestor, BinlogPosition, BinlogStart,
+};
+use crate::connectors::mysql::conversion::value_to_field;
+use crate::connectors::mysql::gtid::{
+    self, gtid_executed, gtid_mode_on, GtidCheckpoint, GtidSet, GtidTracker,
+};
+use crate::connectors::mysql::schema::{self, TableDefinition};
+use crate::connectors::{
+    table_name, Connector, ListOrFilterColumns, SourceSchemaResult, TableIdentifier, TableInfo,
+};
+use crate::errors::MySQLConnectorError::{
+    BinlogFileNotFound, BinlogNotEnabled, ConnectionFailure, QueryExecutionError,
+    UnsupportedServerSetting,
+};
+use crate::errors::{ConnectorError, MySQLConnectorError};
+use crate::ingestion::Ingestor;
+use dozer_types::ingestion_types::{IngestionMessage, MySQLConfig};
+use dozer_types::log::info;
+use dozer_types::parking_lot::Mutex;
+use dozer_types::types::{FieldType, Operation, Record, SchemaIdentifier};
+use mysql_async::prelude::Queryable;
+use mysql_async::{Conn, Opts, OptsBuilder, Row};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+#[derive(Debug)]
+pub struct MySQLConnector {
+    name: String,
+    opts: Opts,
+    database: String,
+    /// Id the connector uses as a replica, must differ from those of the server and its other replicas.
+    server_id: u32,
+    state_dir: Option<PathBuf>,
+    /// The GTIDs of the running ingestion, stored on checkpoints.
+    gtids: Mutex<Option<Arc<GtidTracker>>>,
+}
+
+impl MySQLConnector {
+    pub fn new(name: String, config: MySQLConfig) -> MySQLConnector {
+        let opts = OptsBuilder::default()
+            .ip_or_hostname(config.host)
+            .tcp_port(config.port as u16)
+            .user(Some(config.user))
+            .pass(Some(config.password))
+            .db_name(Some(config.database.clone()));
+        let server_id = config.server_id.unwrap_or_else(|| {
+            let mut hasher = DefaultHasher::new();
+            name.hash(&mut hasher);
+            (hasher.finish() as u32).max(1)
+        });
+
+        MySQLConnector {
+            name,
+            opts: opts.into(),
+            database: config.database,
+            server_id,
+            state_dir: None,
+            gtids: Mutex::new(None),
+        }
+    }
+
+    fn gtid_state_path(&self) -> Option<PathBuf> {
+        self.state_dir
+            .as_ref()
+            .map(|dir| dir.join("gtid_checkpoint.json"))
+    }
+
+    /// The executed GTID set stored for `last_checkpoint`, if the server's GTID mode is on.
+    async fn gtid_checkpoint(
+        &self,
+        conn: &mut Conn,
+        last_checkpoint: (u64, u64),
+    ) -> Result<Option<GtidCheckpoint>, MySQLConnectorError> {
+        let Some(path) = self.gtid_state_path() else {
+            return Ok(None);
+        };
+        if !gtid_mode_on(conn).await {
+            return Ok(None);
+        }
+        GtidCheckpoint::load(&path, last_checkpoint.0)
+    }
+
+    async fn connect(&self) -> Result<Conn, MySQLConnectorError> {
+        Conn::new(self.opts.clone())
+            .await
+            .map_err(ConnectionFailure)
+    }
+
+    async fn get_tables(
+        &self,
+        tables: &[ListOrFilterColumns],
+    ) -> Result<Vec<TableDefinition>, ConnectorError> {
+        let mut conn = self.connect().await?;
+        let definitions = schema::get_tables(&mut conn, &self.database, tables).await?;
+        definitions
+            .into_iter()
+            .zip(tables)
+            .map(|(definition, table)| match definition {
+                Some(definition) => definition.map_err(Into::into),
+                None => Err(ConnectorError::TableNotFound(table_name(
+                    table.schema.as_deref(),
+                    &table.name,
+                ))),
+            })
+            .collect()
+    }
+
+    /// Reads `tables` in a consistent snapshot and returns the binlog file and position it was
+    /// taken at, and the executed GTID set at it if the server's GTID mode is on.
+    async fn snapshot(
+        &self,
+        ingestor: &Ingestor,
+        tables: &[TableDefinition],
+    ) -> Result<(String, BinlogPosition, Option<GtidSet>), ConnectorError> {
+        let mut conn = self.connect().await?;
+        execute(&mut conn, "SET time_zone = '+00:00'").await?;
+
+        // The lock makes sure no transaction commits between the start of the snapshot and reading
+        // the binlog position. It needs the `RELOAD` privilege.
+        execute(&mut conn, "FLUSH TABLES WITH READ LOCK").await?;
+        execute(
+            &mut conn,
+            "START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY",
+        )
+        .await?;
+        let query = "SHOW MASTER STATUS";
+        let status: Option<Row> = conn
+            .query_first(query)
+            .await
+            .map_err(|e| QueryExecutionError(query.to_string(), e))?;
+        let executed = match gtid_mode_on(&mut conn).await {
+            true => Some(gtid_executed(&mut conn).await?),
+            false => None,
+        };
+        execute(&mut conn, "UNLOCK TABLES").await?;
+        let (filename, offset): (String, u32) = status
+            .and_then(|row| Some((row.get(0)?, row.get(1)?)))
+            .ok_or(BinlogNotEnabled)?;
+        let position = BinlogPosition::new(&filename, offset)?;
+
+        info!("[{}] Starting snapshot", self.name);
+        let mut seq_no = 0;
+        for (index, table) in tables.iter().enumerate() {
+            let columns = table
+                .columns
+                .iter()
+                .map(|column| quote_identifier(&column.name))
+                .collect::<Vec<_>>();
+            let query = format!(
+                "SELECT {} FROM {}.{}",
+                columns.join(", "),
+                quote_identifier(&table.database),
+                quote_identifier(&table.name)
+            );
+            let mut result = conn
+                .query_iter(&query)
+                .await
+                .map_err(|e| QueryExecutionError(query.clone(), e))?;
+            while let Some(row) = result
+                .next()
+                .await
+                .map_err(|e| QueryExecutionError(query.clone(), e))?
+            {
+                let values = Row::unwrap(row)
+                    .into_iter()
+                    .zip(&table.columns)
+                    .map(|(value, column)| value_to_field(value, column))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let operation = Operation::Insert {
+                    new: Record::new(
+                        Some(SchemaIdentifier {
+                            id: index as u32,
+                            version: 1,
+                        }),
+                        values,
+                        None,
+                    ),
+                };
+                ingestor
+                    .handle_message(IngestionMessage::new_op(0, seq_no, operation))
+                    .map_err(ConnectorError::IngestorError)?;
+                seq_no += 1;
+            }
+        }
+        execute(&mut conn, "COMMIT").await?;
+
+        // The end of the snapshot is where ingestion resumes from, if it's restarted.
+        ingestor
+            .handle_message(IngestionMessage::new_snapshotting_done(position.txid(), 0))
+            .map_err(ConnectorError::IngestorError)?;
+        info!("[{}] Snapshot done, {seq_no} rows", self.name);
+        Ok((filename, position, executed))
+    }
+}
+
+impl Connector for MySQLConnector {
+    fn types_mapping() -> Vec<(String, Option<FieldType>)>
+    where
+        Self: Sized,
+    {
+        vec![
+            ("boolean".to_string(), Some(FieldType::Boolean)),
+            ("tinyint".to_string(), Some(FieldType::Int)),
+            ("smallint".to_string(), Some(FieldType::Int)),
+            ("mediumint".to_string(), Some(FieldType::Int)),
+            ("int".to_string(), Some(FieldType::Int)),
+            ("bigint".to_string(), Some(FieldType::Int)),
+            ("bigint unsigned".to_string(), Some(FieldType::UInt)),
+            ("year".to_string(), Some(FieldType::Int)),
+            ("float".to_string(), Some(FieldType::Float)),
+            ("double".to_string(), Some(FieldType::Float)),
+            ("decimal".to_string(), Some(FieldType::Decimal)),
+            ("char".to_string(), Some(FieldType::String)),
+            ("varchar".to_string(), Some(FieldType::String)),
+            ("enum".to_string(), Some(FieldType::String)),
+            ("text".to_string(), Some(FieldType::Text)),
+            ("binary".to_string(), Some(FieldType::Binary)),
+            ("varbinary".to_string(), Some(FieldType::Binary)),
+            ("blob".to_string(), Some(FieldType::Binary)),
+            ("date".to_string(), Some(FieldType::Date)),
+            ("datetime".to_string(), Some(FieldType::Timestamp)),
+            ("timestamp".to_string(), Some(FieldType::Timestamp)),
+            ("json".to_string(), Some(FieldType::Bson)),
+            ("time".to_string(), None),
+            ("bit".to_string(), None),
+            ("set".to_string(), None),
+            ("geometry".to_string(), None),
+            ("point".to_string(), None),
+        ]
+    }
+
+    fn set_state_dir(&mut self, state_dir: PathBuf) {
+        self.state_dir = Some(state_dir);
+    }
+
+    fn validate_connection(&self) -> Result<(), ConnectorError> {
+        Runtime::new()?.block_on(async {
+            let mut conn = self.connect().await?;
+            let query = "SELECT @@GLOBAL.binlog_format, @@GLOBAL.binlog_row_image";
+            let (binlog_format, binlog_row_image): (String, String) = conn
+                .query_first(query)
+                .await
+                .map_err(|e| QueryExecutionError(query.to_string(), e))?
+                .unwrap_or_default();
+            for (variable, expected, actual) in [
+                ("binlog_format", "ROW", binlog_format),
+                ("binlog_row_image", "FULL", binlog_row_image),
+            ] {
+                if actual != expected {
+                    return Err(UnsupportedServerSetting(
+                        variable.to_string(),
+                        expected.to_string(),
+                        actual,
+                    )
+                    .into());
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn list_tables(&self) -> Result<Vec<TableIdentifier>, ConnectorError> {
+        Runtime::new()?.block_on(async {
+            let mut conn = self.connect().await?;
+            Ok(schema::list_tables(&mut conn, &self.database)
+                .await?
+                .into_iter()
+                .map(|name| TableIdentifier::new(Some(self.database.clone()), name))
+                .collect())
+        })
+    }
+
+    fn validate_tables(&self, tables: &[TableIdentifier]) -> Result<(), ConnectorError> {
+        let tables = tables
+            .iter()
+            .map(|table| ListOrFilterColumns {
+                schema: table.schema.clone(),
+                name: table.name.clone(),
+                columns: None,
+            })
+            .collect::<Vec<_>>();
+        Runtime::new()?.block_on(self.get_tables(&tables))?;
+        Ok(())
+    }
+
+    fn list_columns(&self, tables: Vec<TableIdentifier>) -> Result<Vec<TableInfo>, ConnectorError> {
+        let tables = tables
+            .into_iter()
+            .map(|table| ListOrFilterColumns {
+                schema: table.schema,
+                name: table.name,
+                columns: None,
+            })
+            .collect::<Vec<_>>();
+        let definitions = Runtime::new()?.block_on(self.get_tables(&tables))?;
+        Ok(definitions
+            .into_iter()
+            .zip(tables)
+            .map(|(definition, table)| TableInfo {
+                schema: table.schema,
+                name: table.name,
+                column_names: definition
+                    .columns
+                    .into_iter()
+                    .map(|column| column.name)
+                    .collect(),
+            })
+            .collect())
+    }
+
+    fn get_schemas(
+        &self,
+        table_infos: &[TableInfo],
+    ) -> Result<Vec<SourceSchemaResult>, ConnectorError> {
+        let tables = table_infos
+            .iter()
+            .map(|table| ListOrFilterColumns {
+                schema: table.schema.clone(),
+                name: table.name.clone(),
+                columns: Some(table.column_names.clone()),
+            })
+            .collect::<Vec<_>>();
+        Runtime::new()?.block_on(async {
+            let mut conn = self.connect().await?;
+            let definitions = schema::get_tables(&mut conn, &self.database, &tables).await?;
+            Ok(definitions
+                .into_iter()
+                .zip(&tables)
+                .enumerate()
+                .map(|(index, (definition, table))| match definition {
+                    Some(Ok(definition)) => Ok(definition.source_schema(index as u32)),
+                    Some(Err(e)) => Err(e.into()),
+                    None => Err(ConnectorError::TableNotFound(table_name(
+                        table.schema.as_deref(),
+                        &table.name,
+                    ))),
+                })
+                .collect())
+        })
+    }
+
+    fn can_start_from(
+        &self,
+        _tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError> {
+        let (txid, _) = last_checkpoint;
+        // The snapshot can't be resumed.
+        if txid == 0 {
+            return Ok(false);
+        }
+        Runtime::new()?.block_on(async {
+            let mut conn = self.connect().await?;
+            if let Some(checkpoint) = self.gtid_checkpoint(&mut conn, last_checkpoint).await? {
+                let found = gtid::can_resume_from(&mut conn, &checkpoint.executed).await?;
+                if !found {
+                    info!(
+                        "[{}] Cannot continue ingestion from {}/{}, the binlog after {} was purged",
+                        self.name, last_checkpoint.0, last_checkpoint.1, checkpoint.executed
+                    );
+                }
+                return Ok(found);
+            }
+
+            let position = BinlogPosition::from_txid(txid);
+            let found = find_binlog_file(&mut conn, position).await?.is_some();
+            if !found {
+                info!(
+                    "[{}] Cannot continue ingestion from {}/{}, the binlog was purged",
+                    self.name, last_checkpoint.0, last_checkpoint.1
+                );
+            }
+            Ok(found)
+        })
+    }
+
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
+        let tables = tables
+            .into_iter()
+            .map(|table| ListOrFilterColumns {
+                schema: table.schema,
+                name: table.name,
+                columns: Some(table.column_names),
+            })
+            .collect::<Vec<_>>();
+        Runtime::new()?.block_on(async {
+            let tables = self.get_tables(&tables).await?;
+            let (start, executed) = match last_checkpoint {
+                None => {
+                    let (filename, position, executed) = self.snapshot(ingestor, &tables).await?;
+                    let start = match &executed {
+                        Some(executed) => BinlogStart::Gtid {
+                            executed: executed.clone(),
+                            resume: None,
+                        },
+                        None => BinlogStart::Position {
+                            filename,
+                            position,
+                            skip: 0,
+                        },
+                    };
+                    (start, executed)
+                }
+                Some((txid, seq_in_tx)) => {
+                    let mut conn = self.connect().await?;
+                    match self.gtid_checkpoint(&mut conn, (txid, seq_in_tx)).await? {
+                        Some(checkpoint) => {
+                            let start = BinlogStart::Gtid {
+                                executed: checkpoint.executed.clone(),
+                                resume: checkpoint.gtid.map(|gtid| (gtid, seq_in_tx + 1)),
+                            };
+                            (start, Some(checkpoint.executed))
+                        }
+                        // Without the executed GTID set at the checkpoint, GTIDs can't be tracked
+                        // until ingestion starts over.
+                        None => {
+                            let position = BinlogPosition::from_txid(txid);
+                            let filename = find_binlog_file(&mut conn, position)
+                                .await?
+                                .ok_or(BinlogFileNotFound(position.file_index))?;
+                            let start = BinlogStart::Position {
+                                filename,
+                                position,
+                                skip: seq_in_tx + 1,
+                            };
+                            (start, None)
+                        }
+                    }
+                }
+            };
+
+            let gtids = Arc::new(GtidTracker::new(self.gtid_state_path(), executed));
+            *self.gtids.lock() = Some(gtids.clone());
+            BinlogIngestor::new(
+                &self.name,
+                ingestor,
+                self.opts.clone(),
+                self.server_id,
+                &tables,
+                &gtids,
+                start,
+            )
+            .run()
+            .await
+        })
+    }
+
+    fn on_checkpoint(&self, checkpoint: (u64, u64)) -> Result<(), ConnectorError> {
+        let gtids = self.gtids.lock().clone();
+        if let Some(gtids) = gtids {
+            gtids.commit(checkpoint)?;
+        }
+        Ok(())
+    }
+}
+
+async fn execute(conn: &mut Conn, query: &str) -> Result<(), MySQLConnectorError> {
+    conn.query_drop(query)
+        .await
+        .map_err(|e| QueryExecutionError(query.to_string(), e))
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('`', "``"))
+}
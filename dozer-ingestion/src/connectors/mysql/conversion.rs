@@ -0,0 +1,285 @@
+use crate::connectors::mysql::schema::ColumnDefinition;
+use crate::errors::MySQLConnectorError;
+use crate::errors::MySQLConnectorError::ValueConversionError;
+use dozer_types::chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Utc,
+};
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::rust_decimal::prelude::FromPrimitive;
+use dozer_types::rust_decimal::Decimal;
+use dozer_types::serde_json;
+use dozer_types::types::{Field, FieldType};
+use mysql_common::binlog::value::BinlogValue;
+use mysql_common::value::Value;
+use std::str::FromStr;
+
+/// Maps a column to a Dozer type, from its `DATA_TYPE` and `COLUMN_TYPE` in
+/// `information_schema.COLUMNS`. `None` if the type is not supported.
+pub fn mysql_type_to_field_type(data_type: &str, column_type: &str) -> Option<FieldType> {
+    let unsigned = column_type.ends_with("unsigned") || column_type.contains("unsigned ");
+    match data_type {
+        // `BOOLEAN` is an alias of `TINYINT(1)`.
+        "tinyint" if column_type.starts_with("tinyint(1)") => Some(FieldType::Boolean),
+        "tinyint" | "smallint" | "mediumint" | "int" | "bigint" => Some(if unsigned {
+            FieldType::UInt
+        } else {
+            FieldType::Int
+        }),
+        "year" => Some(FieldType::Int),
+        "float" | "double" => Some(FieldType::Float),
+        "decimal" => Some(FieldType::Decimal),
+        "char" | "varchar" | "enum" => Some(FieldType::String),
+        "tinytext" | "text" | "mediumtext" | "longtext" => Some(FieldType::Text),
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+            Some(FieldType::Binary)
+        }
+        "date" => Some(FieldType::Date),
+        "datetime" | "timestamp" => Some(FieldType::Timestamp),
+        "json" => Some(FieldType::Bson),
+        _ => None,
+    }
+}
+
+/// Converts a value read from a binlog row event.
+pub fn binlog_value_to_field(
+    value: BinlogValue<'static>,
+    column: &ColumnDefinition,
+) -> Result<Field, MySQLConnectorError> {
+    match value {
+        BinlogValue::Value(value) => value_to_field(value, column),
+        BinlogValue::Jsonb(jsonb) => {
+            let json = serde_json::Value::try_from(jsonb)
+                .map_err(|e| ValueConversionError(column.name.clone(), e.to_string()))?;
+            json_to_field(&json, column)
+        }
+        BinlogValue::JsonDiff(_) => Err(ValueConversionError(
+            column.name.clone(),
+            "partial JSON updates are not supported, set binlog_row_value_options to ''"
+                .to_string(),
+        )),
+    }
+}
+
+/// Converts a value read from a query or from a binlog row event.
+///
+/// The text protocol returns every value as bytes, so all types are also parsed from their text
+/// representation.
+pub fn value_to_field(
+    value: Value,
+    column: &ColumnDefinition,
+) -> Result<Field, MySQLConnectorError> {
+    let error = |value: &dyn std::fmt::Debug| {
+        ValueConversionError(
+            column.name.clone(),
+            format!("unexpected value {value:?} for {}", column.column_type),
+        )
+    };
+
+    let field = match (column.typ, value) {
+        (_, Value::NULL) => Field::Null,
+        (FieldType::Boolean, Value::Int(i)) => Field::Boolean(i != 0),
+        (FieldType::Boolean, Value::UInt(u)) => Field::Boolean(u != 0),
+        (FieldType::Int, Value::Int(i)) => Field::Int(i),
+        (FieldType::Int, Value::UInt(u)) => Field::Int(i64::try_from(u).map_err(|_| error(&u))?),
+        (FieldType::UInt, Value::UInt(u)) => Field::UInt(u),
+        // Row events don't know the signedness of integers.
+        (FieldType::UInt, Value::Int(i)) => Field::UInt(column.reinterpret_unsigned(i)),
+        (FieldType::Float, Value::Float(f)) => Field::Float(OrderedFloat(f as f64)),
+        (FieldType::Float, Value::Double(f)) => Field::Float(OrderedFloat(f)),
+        (FieldType::Decimal, Value::Int(i)) => Field::Decimal(Decimal::from(i)),
+        (FieldType::Decimal, Value::UInt(u)) => Field::Decimal(Decimal::from(u)),
+        (FieldType::Decimal, Value::Double(f)) => {
+            Field::Decimal(Decimal::from_f64(f).ok_or_else(|| error(&f))?)
+        }
+        (FieldType::Date, Value::Date(year, month, day, ..)) => Field::Date(
+            NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+                .ok_or_else(|| error(&(year, month, day)))?,
+        ),
+        (FieldType::Timestamp, Value::Date(year, month, day, hour, minute, second, micros)) => {
+            let date = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32);
+            let time =
+                NaiveTime::from_hms_micro_opt(hour as u32, minute as u32, second as u32, micros);
+            let (Some(date), Some(time)) = (date, time) else {
+                return Err(error(&(year, month, day, hour, minute, second, micros)));
+            };
+            Field::Timestamp(utc(NaiveDateTime::new(date, time)))
+        }
+        (FieldType::Timestamp, Value::Int(seconds)) => Field::Timestamp(
+            Utc.timestamp_opt(seconds, 0)
+                .single()
+                .ok_or_else(|| error(&seconds))?
+                .with_timezone(&Utc.fix()),
+        ),
+        // `ENUM` values are written to the binlog as the index of the value.
+        (FieldType::String, Value::Int(index)) if column.data_type == "enum" => {
+            let value = usize::try_from(index)
+                .ok()
+                .and_then(|index| index.checked_sub(1))
+                .and_then(|index| column.enum_values().into_iter().nth(index))
+                .ok_or_else(|| error(&index))?;
+            Field::String(value)
+        }
+        (FieldType::Binary, Value::Bytes(bytes)) => Field::Binary(bytes),
+        (typ, Value::Bytes(bytes)) => {
+            let text = String::from_utf8(bytes).map_err(|e| error(&e))?;
+            text_to_field(typ, text, column).ok_or_else(|| {
+                ValueConversionError(
+                    column.name.clone(),
+                    format!("cannot parse {} value", column.column_type),
+                )
+            })?
+        }
+        (_, value) => return Err(error(&value)),
+    };
+    Ok(field)
+}
+
+fn text_to_field(typ: FieldType, text: String, column: &ColumnDefinition) -> Option<Field> {
+    Some(match typ {
+        FieldType::Boolean => Field::Boolean(text != "0"),
+        FieldType::Int => Field::Int(text.parse().ok()?),
+        FieldType::UInt => Field::UInt(text.parse().ok()?),
+        FieldType::Float => Field::Float(OrderedFloat(text.parse().ok()?)),
+        FieldType::Decimal => Field::Decimal(Decimal::from_str(&text).ok()?),
+        FieldType::String => Field::String(text),
+        FieldType::Text => Field::Text(text),
+        FieldType::Date => Field::Date(NaiveDate::parse_from_str(&text, "%Y-%m-%d").ok()?),
+        FieldType::Timestamp => Field::Timestamp(parse_timestamp(&text)?),
+        FieldType::Bson => {
+            let json: serde_json::Value = serde_json::from_str(&text).ok()?;
+            return json_to_field(&json, column).ok();
+        }
+        FieldType::Binary | FieldType::Point | FieldType::Interval => return None,
+    })
+}
+
+/// Parses `DATETIME` and `TIMESTAMP` values, either formatted or as seconds since the epoch with
+/// an optional fraction, which is how row events carry `TIMESTAMP` columns.
+fn parse_timestamp(text: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f") {
+        return Some(utc(date_time));
+    }
+    let (seconds, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let seconds = seconds.parse::<i64>().ok()?;
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<9}").get(..9)?.parse::<u32>().ok()?
+    };
+    Some(
+        Utc.timestamp_opt(seconds, nanos)
+            .single()?
+            .with_timezone(&Utc.fix()),
+    )
+}
+
+fn json_to_field(
+    json: &serde_json::Value,
+    column: &ColumnDefinition,
+) -> Result<Field, MySQLConnectorError> {
+    bson::to_vec(json)
+        .map(Field::Bson)
+        .map_err(|e| ValueConversionError(column.name.clone(), e.to_string()))
+}
+
+/// `DATETIME` values have no time zone, they are read as UTC like `TIMESTAMP` values are.
+fn utc(date_time: NaiveDateTime) -> DateTime<FixedOffset> {
+    DateTime::from_utc(date_time, Utc.fix())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(data_type: &str, column_type: &str) -> ColumnDefinition {
+        ColumnDefinition {
+            name: "column".to_string(),
+            data_type: data_type.to_string(),
+            column_type: column_type.to_string(),
+            typ: mysql_type_to_field_type(data_type, column_type).unwrap(),
+            nullable: true,
+            is_primary_key: false,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn test_type_mapping() {
+        assert_eq!(
+            mysql_type_to_field_type("tinyint", "tinyint(1)"),
+            Some(FieldType::Boolean)
+        );
+        assert_eq!(mysql_type_to_field_type("int", "int"), Some(FieldType::Int));
+        assert_eq!(
+            mysql_type_to_field_type("bigint", "bigint unsigned"),
+            Some(FieldType::UInt)
+        );
+        assert_eq!(
+            mysql_type_to_field_type("varchar", "varchar(255)"),
+            Some(FieldType::String)
+        );
+        assert_eq!(
+            mysql_type_to_field_type("json", "json"),
+            Some(FieldType::Bson)
+        );
+        assert_eq!(mysql_type_to_field_type("geometry", "geometry"), None);
+    }
+
+    #[test]
+    fn test_value_conversion() {
+        assert_eq!(
+            value_to_field(Value::Bytes(b"-12".to_vec()), &column("int", "int")).unwrap(),
+            Field::Int(-12)
+        );
+        // Row events carry unsigned integers as signed ones.
+        assert_eq!(
+            value_to_field(Value::Int(-1), &column("int", "int unsigned")).unwrap(),
+            Field::UInt(u32::MAX as u64)
+        );
+        assert_eq!(
+            value_to_field(
+                Value::Bytes(b"12.50".to_vec()),
+                &column("decimal", "decimal(10,2)")
+            )
+            .unwrap(),
+            Field::Decimal(Decimal::new(1250, 2))
+        );
+        assert_eq!(
+            value_to_field(Value::Int(2), &column("enum", "enum('a','it''s')")).unwrap(),
+            Field::String("it's".to_string())
+        );
+        assert_eq!(
+            value_to_field(Value::NULL, &column("text", "text")).unwrap(),
+            Field::Null
+        );
+
+        let expected = Field::Timestamp(utc(NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2023, 3, 1).unwrap(),
+            NaiveTime::from_hms_micro_opt(10, 20, 30, 500_000).unwrap(),
+        )));
+        assert_eq!(
+            value_to_field(
+                Value::Bytes(b"2023-03-01 10:20:30.5".to_vec()),
+                &column("datetime", "datetime(1)")
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            value_to_field(
+                Value::Date(2023, 3, 1, 10, 20, 30, 500_000),
+                &column("datetime", "datetime(1)")
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            value_to_field(
+                Value::Bytes(b"1677666030.5".to_vec()),
+                &column("timestamp", "timestamp(1)")
+            )
+            .unwrap(),
+            expected
+        );
+    }
+}
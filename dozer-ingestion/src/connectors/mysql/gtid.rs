@@ -0,0 +1,394 @@
+use crate::errors::MySQLConnectorError;
+use crate::errors::MySQLConnectorError::{
+    GtidStateReadFailed, GtidStateWriteFailed, InvalidGtid, InvalidGtidState, QueryExecutionError,
+};
+use dozer_types::parking_lot::Mutex;
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::serde_json;
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+use mysql_common::packets::{GnoInterval, Sid};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A global transaction identifier: the uuid of the server the transaction originated on, and
+/// the number of the transaction on that server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gtid {
+    pub sid: [u8; 16],
+    pub gno: u64,
+}
+
+/// A set of GTIDs, like `@@GLOBAL.gtid_executed`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GtidSet {
+    /// Sorted and disjoint intervals of transaction numbers of each server, end excluded.
+    intervals: BTreeMap<[u8; 16], Vec<(u64, u64)>>,
+}
+
+impl GtidSet {
+    pub fn contains(&self, gtid: &Gtid) -> bool {
+        self.intervals.get(&gtid.sid).map_or(false, |intervals| {
+            intervals
+                .iter()
+                .any(|(start, end)| (*start..*end).contains(&gtid.gno))
+        })
+    }
+
+    pub fn add(&mut self, gtid: Gtid) {
+        let intervals = self.intervals.entry(gtid.sid).or_default();
+        let index = intervals.partition_point(|(_, end)| *end < gtid.gno);
+        match intervals.get_mut(index) {
+            Some((start, end)) if *start <= gtid.gno && gtid.gno < *end => {}
+            // right after the interval, which may now touch the next one
+            Some((_, end)) if *end == gtid.gno => {
+                *end += 1;
+                if matches!(intervals.get(index + 1), Some((next, _)) if *next == gtid.gno + 1) {
+                    let (_, next_end) = intervals.remove(index + 1);
+                    intervals[index].1 = next_end;
+                }
+            }
+            // right before the interval
+            Some((start, _)) if *start == gtid.gno + 1 => *start = gtid.gno,
+            _ => intervals.insert(index, (gtid.gno, gtid.gno + 1)),
+        }
+    }
+
+    /// The set in the form a binlog request takes it.
+    pub fn sids(&self) -> Vec<Sid<'static>> {
+        self.intervals
+            .iter()
+            .map(|(sid, intervals)| {
+                intervals.iter().fold(Sid::new(*sid), |sid, (start, end)| {
+                    sid.with_interval(GnoInterval::new(*start, *end))
+                })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Gtid {
+    type Err = MySQLConnectorError;
+
+    /// Parses a GTID like `3e11fa47-71ca-11e1-9e33-c80aa9429562:23`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidGtid(s.to_string());
+        let (sid, gno) = s.trim().split_once(':').ok_or_else(invalid)?;
+        Ok(Gtid {
+            sid: parse_sid(sid).ok_or_else(invalid)?,
+            gno: gno.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl Display for Gtid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", format_sid(&self.sid), self.gno)
+    }
+}
+
+impl FromStr for GtidSet {
+    type Err = MySQLConnectorError;
+
+    /// Parses a GTID set like `3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:11,
+    /// 2174b383-5441-11e8-b90a-c80aa9429562:1-3`, where interval ends are included.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidGtid(s.to_string());
+        let mut set = GtidSet::default();
+        for server in s
+            .split(',')
+            .map(str::trim)
+            .filter(|server| !server.is_empty())
+        {
+            let mut parts = server.split(':');
+            let sid = parts.next().and_then(parse_sid).ok_or_else(invalid)?;
+            for interval in parts {
+                let (start, end) = interval.split_once('-').unwrap_or((interval, interval));
+                let start: u64 = start.parse().map_err(|_| invalid())?;
+                let end: u64 = end.parse().map_err(|_| invalid())?;
+                if start == 0 || end < start {
+                    return Err(invalid());
+                }
+                for gno in start..=end {
+                    set.add(Gtid { sid, gno });
+                }
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl Display for GtidSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, (sid, intervals)) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", format_sid(sid))?;
+            for (start, end) in intervals {
+                match end - start {
+                    1 => write!(f, ":{start}")?,
+                    _ => write!(f, ":{start}-{}", end - 1)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_sid(sid: &str) -> Option<[u8; 16]> {
+    let hex = sid.replace('-', "");
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0; 16];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(index * 2..index * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn format_sid(sid: &[u8; 16]) -> String {
+    let hex = sid
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Whether the server assigns GTIDs to transactions. MariaDB, whose GTIDs are different, has no
+/// `gtid_mode` and counts as off.
+pub async fn gtid_mode_on(conn: &mut Conn) -> bool {
+    matches!(
+        conn.query_first::<String, _>("SELECT @@GLOBAL.gtid_mode").await,
+        Ok(Some(mode)) if mode == "ON"
+    )
+}
+
+pub async fn gtid_executed(conn: &mut Conn) -> Result<GtidSet, MySQLConnectorError> {
+    let query = "SELECT @@GLOBAL.gtid_executed";
+    let executed: Option<String> = conn
+        .query_first(query)
+        .await
+        .map_err(|e| QueryExecutionError(query.to_string(), e))?;
+    executed.unwrap_or_default().parse()
+}
+
+/// Whether the server still has the binlog of every transaction that isn't in `executed`.
+pub async fn can_resume_from(
+    conn: &mut Conn,
+    executed: &GtidSet,
+) -> Result<bool, MySQLConnectorError> {
+    let query = "SELECT GTID_SUBSET(@@GLOBAL.gtid_purged, ?)";
+    let subset: Option<bool> = conn
+        .exec_first(query, (executed.to_string(),))
+        .await
+        .map_err(|e| QueryExecutionError(query.to_string(), e))?;
+    Ok(subset.unwrap_or(false))
+}
+
+/// The executed GTID set at a pipeline checkpoint, which ingestion resumes from on whichever
+/// server of the replication topology it connects to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GtidCheckpoint {
+    /// Transaction the checkpoint is in.
+    pub txid: u64,
+    /// GTIDs of the transactions before it.
+    pub executed: GtidSet,
+    /// GTID of the transaction, unless the checkpoint is between transactions.
+    pub gtid: Option<Gtid>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+struct StoredCheckpoint {
+    txid: u64,
+    executed: String,
+    gtid: Option<String>,
+}
+
+impl GtidCheckpoint {
+    /// Reads the checkpoint stored at `path`, if it's the one of the pipeline checkpoint in
+    /// transaction `txid`.
+    pub fn load(path: &Path, txid: u64) -> Result<Option<Self>, MySQLConnectorError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(GtidStateReadFailed(path.to_path_buf(), e)),
+        };
+        let stored: StoredCheckpoint =
+            serde_json::from_str(&json).map_err(|e| InvalidGtidState(path.to_path_buf(), e))?;
+        if stored.txid != txid {
+            return Ok(None);
+        }
+        Ok(Some(GtidCheckpoint {
+            txid,
+            executed: stored.executed.parse()?,
+            gtid: stored.gtid.as_deref().map(str::parse).transpose()?,
+        }))
+    }
+
+    fn store(&self, path: &Path) -> Result<(), MySQLConnectorError> {
+        let stored = StoredCheckpoint {
+            txid: self.txid,
+            executed: self.executed.to_string(),
+            gtid: self.gtid.as_ref().map(Gtid::to_string),
+        };
+        let json = serde_json::to_string(&stored).expect("checkpoint is serializable");
+        let tmp_path = path.with_extension("tmp");
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&tmp_path, json)?;
+            fs::File::open(&tmp_path)?.sync_all()?;
+            fs::rename(&tmp_path, path)
+        };
+        write().map_err(|e| GtidStateWriteFailed(path.to_path_buf(), e))
+    }
+}
+
+/// The GTIDs of the ingested transactions that the pipeline hasn't checkpointed yet, on top of
+/// the executed GTID set before them. The executed set of every pipeline checkpoint is stored to
+/// `state_path`.
+///
+/// Tracking needs a known executed set to start from, so it's disabled when the server's GTID
+/// mode is off, or when ingestion resumed from a binlog position.
+#[derive(Debug)]
+pub struct GtidTracker {
+    state_path: Option<PathBuf>,
+    state: Mutex<Option<TrackedGtids>>,
+}
+
+#[derive(Debug)]
+struct TrackedGtids {
+    /// GTIDs of the transactions before the first pending one.
+    executed: GtidSet,
+    /// Transaction id and GTID of the ingested transactions, in ingestion order.
+    pending: VecDeque<(u64, Option<Gtid>)>,
+}
+
+impl GtidTracker {
+    pub fn new(state_path: Option<PathBuf>, executed: Option<GtidSet>) -> Self {
+        Self {
+            state_path,
+            state: Mutex::new(executed.map(|executed| TrackedGtids {
+                executed,
+                pending: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Records the start of transaction `txid`, whose GTID is `gtid` unless it's anonymous.
+    pub fn begin(&self, txid: u64, gtid: Option<Gtid>) {
+        if let Some(state) = self.state.lock().as_mut() {
+            state.pending.push_back((txid, gtid));
+        }
+    }
+
+    /// Stores the executed GTID set at `checkpoint`.
+    pub fn commit(&self, checkpoint: (u64, u64)) -> Result<(), MySQLConnectorError> {
+        let (txid, _) = checkpoint;
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        let checkpoint = {
+            let mut state = self.state.lock();
+            let Some(state) = state.as_mut() else {
+                return Ok(());
+            };
+            state.take_checkpoint(txid)
+        };
+        checkpoint.store(path)
+    }
+}
+
+impl TrackedGtids {
+    /// Folds the transactions before `txid` into the executed set, and returns the checkpoint in
+    /// transaction `txid`.
+    fn take_checkpoint(&mut self, txid: u64) -> GtidCheckpoint {
+        while let Some((pending_txid, gtid)) = self.pending.front() {
+            if *pending_txid >= txid {
+                break;
+            }
+            if let Some(gtid) = gtid {
+                self.executed.add(*gtid);
+            }
+            self.pending.pop_front();
+        }
+        let gtid = match self.pending.front() {
+            Some((pending_txid, gtid)) if *pending_txid == txid => *gtid,
+            _ => None,
+        };
+        GtidCheckpoint {
+            txid,
+            executed: self.executed.clone(),
+            gtid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    fn gtid(gno: u64) -> Gtid {
+        format!("{SID}:{gno}").parse().unwrap()
+    }
+
+    #[test]
+    fn test_gtid_set_add() {
+        let mut set: GtidSet = format!("{SID}:1-3:7").parse().unwrap();
+        assert_eq!(set.to_string(), format!("{SID}:1-3:7"));
+
+        set.add(gtid(5));
+        assert_eq!(set.to_string(), format!("{SID}:1-3:5:7"));
+        set.add(gtid(4));
+        set.add(gtid(6));
+        assert_eq!(set.to_string(), format!("{SID}:1-7"));
+        set.add(gtid(2));
+        assert_eq!(set.to_string(), format!("{SID}:1-7"));
+        assert!(set.contains(&gtid(7)));
+        assert!(!set.contains(&gtid(8)));
+
+        let other = "2174b383-5441-11e8-b90a-c80aa9429562";
+        let set: GtidSet = format!("{SID}:1-7,\n{other}:2").parse().unwrap();
+        assert_eq!(set.to_string(), format!("{other}:2,{SID}:1-7"));
+
+        assert_eq!("".parse::<GtidSet>().unwrap(), GtidSet::default());
+        assert!(format!("{SID}:3-1").parse::<GtidSet>().is_err());
+        assert!("3e11fa47:1".parse::<GtidSet>().is_err());
+    }
+
+    #[test]
+    fn test_take_checkpoint() {
+        let mut state = TrackedGtids {
+            executed: format!("{SID}:1-3").parse().unwrap(),
+            pending: VecDeque::from([(10, Some(gtid(4))), (20, None), (30, Some(gtid(5)))]),
+        };
+
+        // In the middle of the third transaction.
+        let checkpoint = state.take_checkpoint(30);
+        assert_eq!(checkpoint.executed.to_string(), format!("{SID}:1-4"));
+        assert_eq!(checkpoint.gtid, Some(gtid(5)));
+
+        // Between transactions.
+        let checkpoint = state.take_checkpoint(40);
+        assert_eq!(checkpoint.executed.to_string(), format!("{SID}:1-5"));
+        assert_eq!(checkpoint.gtid, None);
+        assert!(state.pending.is_empty());
+    }
+}
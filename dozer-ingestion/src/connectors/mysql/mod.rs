@@ -0,0 +1,5 @@
+mod binlog;
+pub mod connector;
+mod conversion;
+mod gtid;
+mod schema;
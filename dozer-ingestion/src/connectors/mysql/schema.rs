@@ -0,0 +1,236 @@
+use crate::connectors::mysql::conversion::mysql_type_to_field_type;
+use crate::connectors::{CdcType, ListOrFilterColumns, SourceSchema};
+use crate::errors::MySQLConnectorError;
+use crate::errors::MySQLConnectorError::{
+    ColumnNotFound, ColumnTypeNotSupported, QueryExecutionError,
+};
+use dozer_types::types::{FieldDefinition, FieldType, Schema, SchemaIdentifier, SourceDefinition};
+use mysql_async::prelude::Queryable;
+use mysql_async::Conn;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDefinition {
+    pub name: String,
+    /// `DATA_TYPE` in `information_schema.COLUMNS`, like `int`.
+    pub data_type: String,
+    /// `COLUMN_TYPE` in `information_schema.COLUMNS`, like `int unsigned`.
+    pub column_type: String,
+    pub typ: FieldType,
+    pub nullable: bool,
+    pub is_primary_key: bool,
+    /// Zero based position of the column in the table, which is its index in row events.
+    pub position: usize,
+}
+
+impl ColumnDefinition {
+    /// Reads a signed integer of the column's width as an unsigned one.
+    pub fn reinterpret_unsigned(&self, value: i64) -> u64 {
+        let bits = match self.data_type.as_str() {
+            "tinyint" => 8,
+            "smallint" => 16,
+            "mediumint" => 24,
+            "int" => 32,
+            _ => 64,
+        };
+        if bits == 64 {
+            value as u64
+        } else {
+            (value as u64) & ((1u64 << bits) - 1)
+        }
+    }
+
+    /// Values of an `ENUM` column, in the order of their indexes.
+    pub fn enum_values(&self) -> Vec<String> {
+        let Some(values) = self
+            .column_type
+            .strip_prefix("enum(")
+            .and_then(|values| values.strip_suffix(')'))
+        else {
+            return vec![];
+        };
+
+        let mut result = vec![];
+        let mut current = String::new();
+        let mut in_quotes = false;
+        let mut chars = values.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' if in_quotes && chars.peek() == Some(&'\'') => {
+                    chars.next();
+                    current.push('\'');
+                }
+                '\'' => {
+                    if in_quotes {
+                        result.push(std::mem::take(&mut current));
+                    }
+                    in_quotes = !in_quotes;
+                }
+                c if in_quotes => current.push(c),
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableDefinition {
+    pub database: String,
+    pub name: String,
+    /// The ingested columns, in the order of the table.
+    pub columns: Vec<ColumnDefinition>,
+}
+
+impl TableDefinition {
+    pub fn source_schema(&self, id: u32) -> SourceSchema {
+        let schema = Schema {
+            identifier: Some(SchemaIdentifier { id, version: 1 }),
+            fields: self
+                .columns
+                .iter()
+                .map(|column| FieldDefinition {
+                    name: column.name.clone(),
+                    typ: column.typ,
+                    nullable: column.nullable,
+                    source: SourceDefinition::Dynamic,
+                })
+                .collect(),
+            primary_index: self
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| column.is_primary_key)
+                .map(|(index, _)| index)
+                .collect(),
+        };
+        // Row events carry full before images when `binlog_row_image` is `FULL`.
+        SourceSchema::new(schema, CdcType::FullChanges)
+    }
+}
+
+/// Lists the base tables of `database`.
+pub async fn list_tables(
+    conn: &mut Conn,
+    database: &str,
+) -> Result<Vec<String>, MySQLConnectorError> {
+    let query = "SELECT TABLE_NAME FROM information_schema.TABLES \
+                 WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE' ORDER BY TABLE_NAME";
+    conn.exec(query, (database,))
+        .await
+        .map_err(|e| QueryExecutionError(query.to_string(), e))
+}
+
+/// Reads the definition of each table, with the requested columns or all of them.
+///
+/// A table that doesn't exist gets `None`, a column whose type is not supported fails the table.
+pub async fn get_tables(
+    conn: &mut Conn,
+    default_database: &str,
+    tables: &[ListOrFilterColumns],
+) -> Result<Vec<Option<Result<TableDefinition, MySQLConnectorError>>>, MySQLConnectorError> {
+    let query = "SELECT COLUMN_NAME, DATA_TYPE, COLUMN_TYPE, IS_NULLABLE, COLUMN_KEY \
+                 FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
+                 ORDER BY ORDINAL_POSITION";
+
+    let mut result = vec![];
+    for table in tables {
+        let database = table.schema.as_deref().unwrap_or(default_database);
+        let rows: Vec<(String, String, String, String, String)> = conn
+            .exec(query, (database, table.name.as_str()))
+            .await
+            .map_err(|e| QueryExecutionError(query.to_string(), e))?;
+        if rows.is_empty() {
+            result.push(None);
+            continue;
+        }
+
+        let all_columns = rows
+            .into_iter()
+            .enumerate()
+            .map(
+                |(position, (name, data_type, column_type, is_nullable, column_key))| {
+                    (
+                        position,
+                        name,
+                        data_type.to_lowercase(),
+                        column_type.to_lowercase(),
+                        is_nullable == "YES",
+                        column_key == "PRI",
+                    )
+                },
+            )
+            .collect::<Vec<_>>();
+        let selected = match &table.columns {
+            Some(columns) if !columns.is_empty() => columns.clone(),
+            _ => all_columns.iter().map(|column| column.1.clone()).collect(),
+        };
+
+        let definition = selected
+            .iter()
+            .map(|name| {
+                let Some((position, name, data_type, column_type, nullable, is_primary_key)) =
+                    all_columns.iter().find(|column| &column.1 == name)
+                else {
+                    return Err(ColumnNotFound(name.clone(), table.name.clone()));
+                };
+                let typ = mysql_type_to_field_type(data_type, column_type)
+                    .ok_or_else(|| ColumnTypeNotSupported(name.clone(), column_type.clone()))?;
+                Ok(ColumnDefinition {
+                    name: name.clone(),
+                    data_type: data_type.clone(),
+                    column_type: column_type.clone(),
+                    typ,
+                    nullable: *nullable,
+                    is_primary_key: *is_primary_key,
+                    position: *position,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|mut columns| {
+                columns.sort_by_key(|column| column.position);
+                TableDefinition {
+                    database: database.to_string(),
+                    name: table.name.clone(),
+                    columns,
+                }
+            });
+        result.push(Some(definition));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enum_values() {
+        let column = ColumnDefinition {
+            name: "size".to_string(),
+            data_type: "enum".to_string(),
+            column_type: "enum('small','it''s','a,b')".to_string(),
+            typ: FieldType::String,
+            nullable: false,
+            is_primary_key: false,
+            position: 0,
+        };
+        assert_eq!(column.enum_values(), vec!["small", "it's", "a,b"]);
+    }
+
+    #[test]
+    fn test_reinterpret_unsigned() {
+        let column = |data_type: &str| ColumnDefinition {
+            name: "id".to_string(),
+            data_type: data_type.to_string(),
+            column_type: format!("{data_type} unsigned"),
+            typ: FieldType::UInt,
+            nullable: false,
+            is_primary_key: true,
+            position: 0,
+        };
+        assert_eq!(column("tinyint").reinterpret_unsigned(-1), 255);
+        assert_eq!(column("mediumint").reinterpret_unsigned(-2), (1 << 24) - 2);
+        assert_eq!(column("bigint").reinterpret_unsigned(-1), u64::MAX);
+        assert_eq!(column("int").reinterpret_unsigned(7), 7);
+    }
+}
@@ -37,6 +37,8 @@ pub struct DozerObjectStoreParams<'a, T: ObjectStore> {
     pub host: &'a str,
     pub object_store: T,
     pub table_path: String,
+    /// Root that object locations are relative to, used to build urls of single files.
+    pub base_path: String,
     pub data_fusion_table: &'a Table,
 }
 
@@ -62,6 +64,7 @@ impl DozerObjectStore for S3Storage {
             host: &details.bucket_name,
             object_store,
             table_path: format!("s3://{}/{}/", details.bucket_name, table.prefix),
+            base_path: format!("s3://{}", details.bucket_name),
             data_fusion_table: table,
         })
     }
@@ -88,6 +91,7 @@ impl DozerObjectStore for LocalStorage {
             host: path,
             object_store,
             table_path: format!("{path}/{}/", table.prefix),
+            base_path: path.to_string(),
             data_fusion_table: table,
         })
     }
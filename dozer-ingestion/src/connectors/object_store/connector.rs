@@ -6,6 +6,7 @@ use crate::connectors::{
 };
 use crate::errors::ConnectorError;
 use crate::ingestion::Ingestor;
+use std::path::PathBuf;
 
 use super::connection::validator::validate_connection;
 
@@ -15,11 +16,25 @@ type ConnectorResult<T> = Result<T, ConnectorError>;
 pub struct ObjectStoreConnector<T: Clone> {
     pub id: u64,
     config: T,
+    state_dir: Option<PathBuf>,
 }
 
 impl<T: DozerObjectStore> ObjectStoreConnector<T> {
     pub fn new(id: u64, config: T) -> Self {
-        Self { id, config }
+        Self {
+            id,
+            config,
+            state_dir: None,
+        }
+    }
+
+    fn table_reader(&self) -> TableReader<T> {
+        TableReader::new(
+            self.config.clone(),
+            self.state_dir
+                .as_ref()
+                .map(|state_dir| state_dir.join("consumed_files.jsonl")),
+        )
     }
 }
 
@@ -81,8 +96,26 @@ impl<T: DozerObjectStore> Connector for ObjectStoreConnector<T> {
         schema_mapper::get_schema(&self.config, &table_infos)
     }
 
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> ConnectorResult<()> {
-        TableReader::new(self.config.clone()).read_tables(&tables, ingestor)
+    fn set_state_dir(&mut self, state_dir: PathBuf) {
+        self.state_dir = Some(state_dir);
+    }
+
+    fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> ConnectorResult<bool> {
+        self.table_reader().can_start_from(tables, last_checkpoint)
+    }
+
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> ConnectorResult<()> {
+        self.table_reader()
+            .read_tables(&tables, ingestor, last_checkpoint)
     }
 }
 
@@ -1,26 +1,116 @@
 use crate::errors::ObjectStoreObjectError;
+use crate::errors::ObjectStoreObjectError::InvalidFormatOption;
+use deltalake::datafusion::datasource::file_format::avro::AvroFormat;
 use deltalake::datafusion::datasource::file_format::csv::CsvFormat;
+use deltalake::datafusion::datasource::file_format::file_type::FileCompressionType;
+use deltalake::datafusion::datasource::file_format::json::JsonFormat;
 use deltalake::datafusion::datasource::file_format::parquet::ParquetFormat;
+use deltalake::datafusion::datasource::file_format::FileFormat;
 use deltalake::datafusion::datasource::listing::ListingOptions;
-use dozer_types::ingestion_types::Table;
+use dozer_types::ingestion_types::{FileFormatOptions, Table};
 use std::sync::Arc;
 
 pub fn map_listing_options(
     data_fusion_table: &Table,
 ) -> Result<ListingOptions, ObjectStoreObjectError> {
-    match data_fusion_table.file_type.as_str() {
+    let options = data_fusion_table.format_options.clone().unwrap_or_default();
+
+    let format: Arc<dyn FileFormat> = match data_fusion_table.file_type.as_str() {
         "parquet" => {
-            let format = ParquetFormat::new();
-            Ok(ListingOptions::new(Arc::new(format))
-                .with_file_extension(data_fusion_table.extension.clone()))
+            reject_text_options(&options, "parquet")?;
+            Arc::new(ParquetFormat::new())
+        }
+        "avro" => {
+            reject_text_options(&options, "avro")?;
+            Arc::new(AvroFormat::default())
+        }
+        "csv" => Arc::new(map_csv_format(&options)?),
+        // DataFusion has no orc reader.
+        "orc" => {
+            return Err(ObjectStoreObjectError::FileFormatUnsupportedError(
+                "orc, convert the files to parquet or avro".to_string(),
+            ))
         }
-        "csv" => {
-            let format = CsvFormat::default();
-            Ok(ListingOptions::new(Arc::new(format))
-                .with_file_extension(data_fusion_table.extension.clone()))
+        // DataFusion only reads newline delimited json, one object per line.
+        "json" | "ndjson" => {
+            if options.delimiter.is_some() || options.has_header.is_some() {
+                return Err(InvalidFormatOption(
+                    "json".to_string(),
+                    "only `compression` and `schema_infer_max_records` apply".to_string(),
+                ));
+            }
+            Arc::new(
+                JsonFormat::default()
+                    .with_file_compression_type(map_compression(&options)?)
+                    .with_schema_infer_max_rec(map_schema_infer_max_records(&options)),
+            )
         }
-        format => Err(ObjectStoreObjectError::FileFormatUnsupportedError(
-            format.to_string(),
+        format => {
+            return Err(ObjectStoreObjectError::FileFormatUnsupportedError(
+                format.to_string(),
+            ))
+        }
+    };
+
+    Ok(ListingOptions::new(format).with_file_extension(data_fusion_table.extension.clone()))
+}
+
+fn map_csv_format(options: &FileFormatOptions) -> Result<CsvFormat, ObjectStoreObjectError> {
+    let mut format = CsvFormat::default()
+        .with_file_compression_type(map_compression(options)?)
+        .with_schema_infer_max_rec(map_schema_infer_max_records(options));
+
+    if let Some(delimiter) = &options.delimiter {
+        format = format.with_delimiter(single_byte("delimiter", delimiter)?);
+    }
+    if let Some(has_header) = options.has_header {
+        format = format.with_has_header(has_header);
+    }
+
+    Ok(format)
+}
+
+fn map_compression(
+    options: &FileFormatOptions,
+) -> Result<FileCompressionType, ObjectStoreObjectError> {
+    match options.compression.as_deref() {
+        None | Some("uncompressed") => Ok(FileCompressionType::UNCOMPRESSED),
+        Some("gzip") => Ok(FileCompressionType::GZIP),
+        Some("bzip2") => Ok(FileCompressionType::BZIP2),
+        Some("xz") => Ok(FileCompressionType::XZ),
+        Some(compression) => Err(InvalidFormatOption(
+            "compression".to_string(),
+            format!("unknown compression {compression}"),
         )),
     }
 }
+
+fn map_schema_infer_max_records(options: &FileFormatOptions) -> Option<usize> {
+    options
+        .schema_infer_max_records
+        .map(|max_records| max_records as usize)
+}
+
+fn single_byte(option: &str, value: &str) -> Result<u8, ObjectStoreObjectError> {
+    match value.as_bytes() {
+        [byte] => Ok(*byte),
+        _ => Err(InvalidFormatOption(
+            option.to_string(),
+            format!("expected a single character, got {value:?}"),
+        )),
+    }
+}
+
+/// Parquet and avro files carry their own schema and compression.
+fn reject_text_options(
+    options: &FileFormatOptions,
+    file_type: &str,
+) -> Result<(), ObjectStoreObjectError> {
+    if options != &FileFormatOptions::default() {
+        return Err(InvalidFormatOption(
+            file_type.to_string(),
+            "format options are not supported".to_string(),
+        ));
+    }
+    Ok(())
+}
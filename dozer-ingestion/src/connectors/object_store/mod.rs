@@ -7,5 +7,6 @@ pub mod schema_mapper;
 mod table_reader;
 #[cfg(test)]
 mod tests;
+mod watcher;
 
 pub use schema_helper::map_value_to_dozer_field;
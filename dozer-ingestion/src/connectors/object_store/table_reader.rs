@@ -1,122 +1,149 @@
 use crate::connectors::object_store::adapters::DozerObjectStore;
 use crate::connectors::object_store::helper::map_listing_options;
 use crate::connectors::object_store::schema_helper::map_value_to_dozer_field;
+use crate::connectors::object_store::watcher::{
+    list_files, FileTracker, TableSource, TableWatcher,
+};
 use crate::connectors::TableInfo;
 use crate::errors::ObjectStoreConnectorError::TableReaderError;
 use crate::errors::ObjectStoreObjectError::ListingPathParsingError;
 use crate::errors::ObjectStoreTableReaderError::{
     ColumnsSelectFailed, StreamExecutionError, TableReadFailed,
 };
+use crate::errors::ObjectStoreWatchError::CheckpointFileMissing;
 use crate::errors::{ConnectorError, ObjectStoreConnectorError};
 use crate::ingestion::Ingestor;
+use deltalake::datafusion::arrow::datatypes::SchemaRef;
 use deltalake::datafusion::datasource::listing::{
-    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+    ListingTable, ListingTableConfig, ListingTableUrl,
 };
 use deltalake::datafusion::prelude::SessionContext;
 use dozer_types::ingestion_types::IngestionMessage;
 use dozer_types::log::error;
 use dozer_types::types::{Operation, Record, SchemaIdentifier};
 use futures::StreamExt;
+use object_store::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
 pub struct TableReader<T: Clone + Send + Sync> {
     config: T,
+    /// Where the consumed files are recorded, see [`FileTracker`].
+    state_path: Option<PathBuf>,
 }
 
 impl<T: Clone + Send + Sync> TableReader<T> {
-    pub fn new(config: T) -> TableReader<T> {
-        Self { config }
+    pub fn new(config: T, state_path: Option<PathBuf>) -> TableReader<T> {
+        Self { config, state_path }
     }
+}
 
-    pub async fn read(
-        id: u32,
-        ctx: SessionContext,
-        table_path: ListingTableUrl,
-        listing_options: ListingOptions,
-        ingestor: &Ingestor,
-        table: &TableInfo,
-    ) -> Result<(), ObjectStoreConnectorError> {
-        let resolved_schema = listing_options
-            .infer_schema(&ctx.state(), &table_path)
-            .await
-            .map_err(ObjectStoreConnectorError::InternalDataFusionError)?;
-
-        let mut idx = 0;
-        let fields = resolved_schema.all_fields();
-
-        let config = ListingTableConfig::new(table_path.clone())
-            .with_listing_options(listing_options)
-            .with_schema(resolved_schema.clone());
-
-        let provider = Arc::new(
-            ListingTable::try_new(config)
-                .map_err(ObjectStoreConnectorError::InternalDataFusionError)?,
-        );
-
-        let cols: Vec<&str> = if table.column_names.is_empty() {
-            fields.iter().map(|f| f.name().as_str()).collect()
-        } else {
-            table.column_names.iter().map(|c| c.as_str()).collect()
+/// Reads every file of the listing and ingests its rows as inserts of transaction `txid`, leaving
+/// out the first `skip_rows` rows.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn read_listing(
+    txid: u64,
+    skip_rows: u64,
+    id: u32,
+    ctx: &SessionContext,
+    config: ListingTableConfig,
+    resolved_schema: SchemaRef,
+    ingestor: &Ingestor,
+    table: &TableInfo,
+) -> Result<(), ObjectStoreConnectorError> {
+    let table_path = config.table_paths.clone();
+    let mut idx = 0;
+    let fields = resolved_schema.all_fields();
+
+    let provider = Arc::new(
+        ListingTable::try_new(config)
+            .map_err(ObjectStoreConnectorError::InternalDataFusionError)?,
+    );
+
+    let cols: Vec<&str> = if table.column_names.is_empty() {
+        fields.iter().map(|f| f.name().as_str()).collect()
+    } else {
+        table.column_names.iter().map(|c| c.as_str()).collect()
+    };
+    let data = ctx
+        .read_table(provider.clone())
+        .map_err(|e| TableReaderError(TableReadFailed(e)))?
+        .select_columns(&cols)
+        .map_err(|e| TableReaderError(ColumnsSelectFailed(e)))?
+        .execute_stream()
+        .await
+        .map_err(|e| TableReaderError(StreamExecutionError(e)))?;
+
+    tokio::pin!(data);
+
+    while let Some(batch) = data.next().await {
+        let batch = match batch {
+            Ok(batch) => batch,
+            Err(e) => {
+                error!("Error reading record batch from {table_path:?}: {e}");
+                continue;
+            }
         };
-        let data = ctx
-            .read_table(provider.clone())
-            .map_err(|e| TableReaderError(TableReadFailed(e)))?
-            .select_columns(&cols)
-            .map_err(|e| TableReaderError(ColumnsSelectFailed(e)))?
-            .execute_stream()
-            .await
-            .map_err(|e| TableReaderError(StreamExecutionError(e)))?;
 
-        tokio::pin!(data);
-
-        while let Some(batch) = data.next().await {
-            let batch = match batch {
-                Ok(batch) => batch,
-                Err(e) => {
-                    error!("Error reading record batch from {table_path:?}: {e}");
-                    continue;
-                }
-            };
+        for row in 0..batch.num_rows() {
+            if idx < skip_rows {
+                idx += 1;
+                continue;
+            }
 
-            for row in 0..batch.num_rows() {
-                let fields = batch
-                    .columns()
-                    .iter()
-                    .enumerate()
-                    .map(|(col, column)| {
-                        map_value_to_dozer_field(column, &row, resolved_schema.field(col).name())
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                ingestor
-                    .handle_message(IngestionMessage::new_op(
-                        0_u64,
-                        idx,
-                        Operation::Insert {
-                            new: Record {
-                                schema_id: Some(SchemaIdentifier { id, version: 0 }),
-                                values: fields,
-                                version: None,
-                            },
+            let fields = batch
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(col, column)| {
+                    map_value_to_dozer_field(column, &row, resolved_schema.field(col).name())
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            ingestor
+                .handle_message(IngestionMessage::new_op(
+                    txid,
+                    idx,
+                    Operation::Insert {
+                        new: Record {
+                            schema_id: Some(SchemaIdentifier { id, version: 0 }),
+                            values: fields,
+                            version: None,
                         },
-                    ))
-                    .map_err(ObjectStoreConnectorError::IngestorError)?;
+                    },
+                ))
+                .map_err(ObjectStoreConnectorError::IngestorError)?;
 
-                idx += 1;
-            }
+            idx += 1;
         }
-
-        Ok(())
     }
+
+    Ok(())
 }
 
 pub trait Reader<T> {
-    fn read_tables(&self, tables: &[TableInfo], ingestor: &Ingestor) -> Result<(), ConnectorError>;
+    fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError>;
+
+    fn read_tables(
+        &self,
+        tables: &[TableInfo],
+        ingestor: &Ingestor,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError>;
 }
 
-impl<T: DozerObjectStore> Reader<T> for TableReader<T> {
-    fn read_tables(&self, tables: &[TableInfo], ingestor: &Ingestor) -> Result<(), ConnectorError> {
+impl<T: DozerObjectStore> TableReader<T> {
+    async fn table_sources<'a>(
+        &self,
+        tables: &'a [TableInfo],
+    ) -> Result<Vec<TableSource<'a>>, ConnectorError> {
+        let mut sources = vec![];
         for (id, table) in tables.iter().enumerate() {
             let params = self.config.table_params(&table.name)?;
 
@@ -130,26 +157,93 @@ impl<T: DozerObjectStore> Reader<T> for TableReader<T> {
             let listing_options = map_listing_options(params.data_fusion_table)
                 .map_err(ObjectStoreConnectorError::DataFusionStorageObjectError)?;
 
-            let rt = Runtime::new().map_err(|_| ObjectStoreConnectorError::RuntimeCreationError)?;
-
             let ctx = SessionContext::new();
 
+            let object_store = Arc::new(params.object_store);
             ctx.runtime_env().register_object_store(
                 params.scheme,
                 params.host,
-                Arc::new(params.object_store),
+                object_store.clone(),
             );
 
-            rt.block_on(Self::read(
-                id as u32,
+            let resolved_schema = listing_options
+                .infer_schema(&ctx.state(), &table_path)
+                .await
+                .map_err(ObjectStoreConnectorError::InternalDataFusionError)?;
+
+            sources.push(TableSource {
+                id: id as u32,
                 ctx,
-                table_path,
+                object_store,
+                base_path: params.base_path,
+                prefix: Path::from(params.data_fusion_table.prefix.as_str()),
                 listing_options,
-                ingestor,
+                resolved_schema,
+                watch: params
+                    .data_fusion_table
+                    .watch
+                    .as_ref()
+                    .map(|watch| Duration::from_millis(watch.interval_ms)),
                 table,
-            ))?;
+            });
         }
+        Ok(sources)
+    }
+}
+
+impl<T: DozerObjectStore> Reader<T> for TableReader<T> {
+    fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError> {
+        let rt = Runtime::new().map_err(|_| ObjectStoreConnectorError::RuntimeCreationError)?;
+        rt.block_on(async {
+            let sources = self.table_sources(tables).await?;
+            let files = list_files(&sources, false)
+                .await
+                .map_err(ObjectStoreConnectorError::WatchError)?;
+            // The file the checkpoint points into has to be recorded, and listed again.
+            match FileTracker::resume(self.state_path.clone(), &files, last_checkpoint) {
+                Ok(_) => Ok(true),
+                Err(CheckpointFileMissing(_)) => Ok(false),
+                Err(e) => Err(ObjectStoreConnectorError::WatchError(e).into()),
+            }
+        })
+    }
 
-        Ok(())
+    fn read_tables(
+        &self,
+        tables: &[TableInfo],
+        ingestor: &Ingestor,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
+        let rt = Runtime::new().map_err(|_| ObjectStoreConnectorError::RuntimeCreationError)?;
+        rt.block_on(async {
+            let sources = self.table_sources(tables).await?;
+            let files = list_files(&sources, false)
+                .await
+                .map_err(ObjectStoreConnectorError::WatchError)?;
+            let (tracker, resume_file) = match last_checkpoint {
+                Some(last_checkpoint) => {
+                    FileTracker::resume(self.state_path.clone(), &files, last_checkpoint)
+                        .map_err(ObjectStoreConnectorError::WatchError)?
+                }
+                None => (FileTracker::start_over(self.state_path.clone()), None),
+            };
+            tracker
+                .save()
+                .map_err(ObjectStoreConnectorError::WatchError)?;
+
+            TableWatcher {
+                tables: sources,
+                tracker,
+                resume_file,
+                ingestor,
+            }
+            .run(files)
+            .await
+            .map_err(ConnectorError::from)
+        })
     }
 }
@@ -3,12 +3,13 @@ use crate::connectors::Connector;
 use crate::ingestion::{IngestionConfig, Ingestor};
 use dozer_types::ingestion_types::IngestionMessage;
 use dozer_types::ingestion_types::IngestionMessageKind;
-use dozer_types::ingestion_types::LocalDetails;
+use dozer_types::ingestion_types::{FileFormatOptions, LocalDetails};
 use dozer_types::node::OpIdentifier;
+use dozer_types::ordered_float::OrderedFloat;
 use std::thread;
 
 use crate::connectors::object_store::helper::map_listing_options;
-use crate::connectors::object_store::tests::test_utils::get_local_storage_config;
+use crate::connectors::object_store::tests::test_utils::{get_local_storage_config, read_records};
 use crate::errors::ConnectorError::InitializationError;
 use crate::errors::ObjectStoreObjectError;
 use dozer_types::types::{Field, FieldType, Operation};
@@ -76,7 +77,7 @@ fn test_read_parquet_file() {
         .list_columns(connector.list_tables().unwrap())
         .unwrap();
     thread::spawn(move || {
-        let _ = connector.start(&ingestor, tables);
+        let _ = connector.start(&ingestor, tables, None);
     });
 
     let mut i = 0;
@@ -124,7 +125,7 @@ fn test_csv_read() {
         .unwrap();
 
     thread::spawn(move || {
-        let _ = connector.start(&ingestor, tables);
+        let _ = connector.start(&ingestor, tables, None);
     });
 
     let mut i = 0;
@@ -163,6 +164,124 @@ fn test_csv_read() {
     }
 }
 
+#[test]
+fn test_get_schema_of_json() {
+    let local_storage = get_local_storage_config("json");
+
+    let connector = ObjectStoreConnector::new(1, local_storage);
+    let (_, schemas) = connector.list_all_schemas().unwrap();
+    let schema = schemas.get(0).unwrap();
+
+    let fields = &schema.schema.fields;
+    let field_type = |name: &str| fields.iter().find(|f| f.name == name).unwrap().typ;
+    assert_eq!(field_type("id"), FieldType::Int);
+    assert_eq!(field_type("name"), FieldType::String);
+    assert_eq!(field_type("score"), FieldType::Float);
+    assert_eq!(field_type("active"), FieldType::Boolean);
+}
+
+#[test]
+fn test_json_read() {
+    let records = read_records(get_local_storage_config("json"), 3);
+    for values in records {
+        assert!(values.iter().any(|value| matches!(value, Field::String(_))));
+        assert!(values.iter().any(|value| matches!(value, Field::Float(_))));
+    }
+}
+
+#[test]
+fn test_gzipped_ndjson_read() {
+    let mut local_storage = get_local_storage_config("json_gz");
+    let table = &mut local_storage.tables[0];
+    table.file_type = "ndjson".to_string();
+    table.extension = ".json.gz".to_string();
+    table.format_options = Some(FileFormatOptions {
+        compression: Some("gzip".to_string()),
+        ..Default::default()
+    });
+
+    let records = read_records(local_storage, 3);
+    assert_eq!(records.len(), 3);
+    assert!(records.iter().all(|values| values
+        .iter()
+        .any(|value| matches!(value, Field::Boolean(_)))));
+}
+
+#[test]
+fn test_csv_read_with_format_options() {
+    let mut local_storage = get_local_storage_config("tsv");
+    let table = &mut local_storage.tables[0];
+    table.file_type = "csv".to_string();
+    table.format_options = Some(FileFormatOptions {
+        delimiter: Some("\t".to_string()),
+        has_header: Some(false),
+        ..Default::default()
+    });
+
+    let records = read_records(local_storage, 3);
+    assert_eq!(records[0][0], Field::Int(1));
+    assert_eq!(records[0][1], Field::String("alice".to_string()));
+    assert_eq!(records[2][1], Field::String("carol".to_string()));
+}
+
+#[test]
+fn test_avro_listing_options() {
+    let local_storage = get_local_storage_config("avro");
+    let table = local_storage.tables.get(0).unwrap();
+
+    let listing_options = map_listing_options(table).unwrap();
+    assert_eq!(listing_options.file_extension, "avro");
+}
+
+#[test]
+fn test_avro_read() {
+    let records = read_records(get_local_storage_config("avro"), 3);
+    assert_eq!(
+        records[0],
+        vec![
+            Field::Int(1),
+            Field::String("alice".to_string()),
+            Field::Float(OrderedFloat(1.5)),
+            Field::Boolean(true),
+        ]
+    );
+    assert_eq!(records[2][1], Field::String("carol".to_string()));
+}
+
+#[test]
+fn test_invalid_format_options() {
+    let mut local_storage = get_local_storage_config("csv");
+    let table = &mut local_storage.tables[0];
+
+    table.format_options = Some(FileFormatOptions {
+        delimiter: Some(";;".to_string()),
+        ..Default::default()
+    });
+    assert!(matches!(
+        map_listing_options(table),
+        Err(ObjectStoreObjectError::InvalidFormatOption(_, _))
+    ));
+
+    table.format_options = Some(FileFormatOptions {
+        compression: Some("zip".to_string()),
+        ..Default::default()
+    });
+    assert!(matches!(
+        map_listing_options(table),
+        Err(ObjectStoreObjectError::InvalidFormatOption(_, _))
+    ));
+
+    table.file_type = "parquet".to_string();
+    table.format_options = Some(FileFormatOptions {
+        has_header: Some(true),
+        ..Default::default()
+    });
+    assert!(matches!(
+        map_listing_options(table),
+        Err(ObjectStoreObjectError::InvalidFormatOption(_, _))
+    ));
+}
+
 #[test]
 fn test_unsupported_format() {
     let local_storage = get_local_storage_config("unsupported");
@@ -174,6 +293,12 @@ fn test_unsupported_format() {
         result,
         Err(ObjectStoreObjectError::FileFormatUnsupportedError(_))
     ));
+
+    let local_storage = get_local_storage_config("orc");
+    assert!(matches!(
+        map_listing_options(&local_storage.tables[0]),
+        Err(ObjectStoreObjectError::FileFormatUnsupportedError(_))
+    ));
 }
 
 #[test]
@@ -1,2 +1,3 @@
 mod local_storage_tests;
 mod test_utils;
+mod watcher_tests;
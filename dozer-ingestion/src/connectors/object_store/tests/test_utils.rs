@@ -1,5 +1,12 @@
-use dozer_types::ingestion_types::{LocalDetails, LocalStorage, Table};
+use crate::connectors::object_store::connector::ObjectStoreConnector;
+use crate::connectors::Connector;
+use crate::ingestion::{IngestionConfig, Ingestor};
+use dozer_types::ingestion_types::{
+    IngestionMessage, IngestionMessageKind, LocalDetails, LocalStorage, Table,
+};
+use dozer_types::types::{Field, Operation};
 use std::path::PathBuf;
+use std::thread;
 
 pub fn get_local_storage_config(typ: &str) -> LocalStorage {
     let p = PathBuf::from("src/connectors/object_store/tests/files".to_string());
@@ -12,6 +19,30 @@ pub fn get_local_storage_config(typ: &str) -> LocalStorage {
             prefix: format!("all_types_{typ}"),
             file_type: typ.to_string(),
             extension: typ.to_string(),
+            watch: None,
+            format_options: None,
         }],
     }
 }
+
+/// Starts the connector and collects the values of the first `count` inserted records.
+pub fn read_records(local_storage: LocalStorage, count: usize) -> Vec<Vec<Field>> {
+    let connector = ObjectStoreConnector::new(1, local_storage);
+    let (ingestor, mut iterator) = Ingestor::initialize_channel(IngestionConfig::default());
+    let tables = connector
+        .list_columns(connector.list_tables().unwrap())
+        .unwrap();
+    thread::spawn(move || {
+        let _ = connector.start(&ingestor, tables, None);
+    });
+
+    (0..count)
+        .map(|_| match iterator.next() {
+            Some(IngestionMessage {
+                kind: IngestionMessageKind::OperationEvent(Operation::Insert { new }),
+                ..
+            }) => new.values,
+            _ => panic!("Unexpected message"),
+        })
+        .collect()
+}
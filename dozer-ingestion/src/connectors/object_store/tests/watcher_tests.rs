@@ -0,0 +1,235 @@
+use crate::connectors::object_store::connector::ObjectStoreConnector;
+use crate::connectors::object_store::watcher::{FileKey, FileTracker};
+use crate::connectors::Connector;
+use crate::ingestion::{IngestionConfig, Ingestor};
+use dozer_types::ingestion_types::{
+    IngestionMessage, IngestionMessageKind, LocalDetails, LocalStorage, Table, TableWatch,
+};
+use dozer_types::node::OpIdentifier;
+use dozer_types::types::Operation;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use tempdir::TempDir;
+
+fn file(last_modified: i64, table: &str, path: &str, size: u64) -> FileKey {
+    FileKey {
+        last_modified,
+        table: table.to_string(),
+        path: path.to_string(),
+        size,
+    }
+}
+
+#[test]
+fn test_file_key_order() {
+    let mut files = vec![
+        file(2, "table", "table/d.csv", 10),
+        file(1, "other", "other/c.csv", 10),
+        file(1, "table", "table/a.csv", 10),
+    ];
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            file(1, "other", "other/c.csv", 10),
+            file(1, "table", "table/a.csv", 10),
+            file(2, "table", "table/d.csv", 10),
+        ]
+    );
+}
+
+#[test]
+fn test_file_tracker_lists_only_new_files() {
+    let mut tracker = FileTracker::start_over(None);
+    let files = vec![
+        file(2, "table", "table/a.csv", 10),
+        file(3, "table", "table/b.csv", 10),
+    ];
+    assert_eq!(tracker.new_files(files.clone()), files);
+
+    assert_eq!(tracker.start(files[0].clone()).unwrap(), 1);
+    assert_eq!(tracker.new_files(files.clone()), vec![files[1].clone()]);
+    assert_eq!(tracker.start(files[1].clone()).unwrap(), 2);
+
+    // A file older than the consumed ones is new all the same, and so is a modified file.
+    let mut files = files;
+    files.push(file(1, "table", "table/c.csv", 10));
+    files.push(file(4, "table", "table/a.csv", 20));
+    assert_eq!(
+        tracker.new_files(files),
+        vec![
+            file(1, "table", "table/c.csv", 10),
+            file(4, "table", "table/a.csv", 20)
+        ]
+    );
+}
+
+#[test]
+fn test_file_tracker_resumes_from_checkpoint() {
+    let state_dir = TempDir::new("file_tracker").unwrap();
+    let state_path = state_dir.path().join("consumed_files.jsonl");
+    let files = vec![
+        file(1, "table", "table/a.csv", 10),
+        file(2, "table", "table/b.csv", 10),
+        file(3, "table", "table/c.csv", 10),
+    ];
+
+    let mut tracker = FileTracker::start_over(Some(state_path.clone()));
+    tracker.save().unwrap();
+    for file in &files {
+        tracker.start(file.clone()).unwrap();
+    }
+
+    let (tracker, resume_file) =
+        FileTracker::resume(Some(state_path.clone()), &files, (0, 0)).unwrap();
+    assert_eq!(resume_file, None);
+    assert_eq!(tracker.new_files(files.clone()), files);
+
+    // Row 4 of the second file was the last one committed.
+    let (mut tracker, resume_file) =
+        FileTracker::resume(Some(state_path.clone()), &files, (2, 4)).unwrap();
+    assert_eq!(resume_file, Some((files[1].clone(), 5)));
+    assert_eq!(tracker.new_files(files.clone()), files[1..].to_vec());
+
+    // The files recorded after the checkpoint are forgotten once saved.
+    tracker.save().unwrap();
+    assert_eq!(tracker.start(files[1].clone()).unwrap(), 2);
+    assert!(FileTracker::resume(Some(state_path.clone()), &files, (3, 0)).is_err());
+
+    assert!(FileTracker::resume(Some(state_path.clone()), &files[..1], (2, 4)).is_err());
+    assert!(FileTracker::resume(None, &files, (2, 4)).is_err());
+}
+
+#[test]
+fn test_watch_ingests_new_files() {
+    let data_dir = TempDir::new("watch_data").unwrap();
+    let table_dir = data_dir.path().join("watched");
+    std::fs::create_dir_all(&table_dir).unwrap();
+    let sample = "src/connectors/object_store/tests/files/all_types_csv/sample.csv";
+    std::fs::copy(sample, table_dir.join("first.csv")).unwrap();
+
+    let local_storage = LocalStorage {
+        details: Some(LocalDetails {
+            path: data_dir.path().to_str().unwrap().to_string(),
+        }),
+        tables: vec![Table {
+            name: "watched".to_string(),
+            prefix: "watched".to_string(),
+            file_type: "csv".to_string(),
+            extension: "csv".to_string(),
+            watch: Some(TableWatch { interval_ms: 50 }),
+            format_options: None,
+        }],
+    };
+    let connector = ObjectStoreConnector::new(1, local_storage);
+
+    let (ingestor, mut iterator) = Ingestor::initialize_channel(IngestionConfig::default());
+    let tables = connector
+        .list_columns(connector.list_tables().unwrap())
+        .unwrap();
+    thread::spawn(move || {
+        let _ = connector.start(&ingestor, tables, None);
+    });
+
+    let mut expect_file = |expected_txid: u64, rows: u64| {
+        for i in 0..rows {
+            let Some(IngestionMessage {
+                identifier: OpIdentifier { txid, seq_in_tx },
+                kind: IngestionMessageKind::OperationEvent(Operation::Insert { .. }),
+            }) = iterator.next()
+            else {
+                panic!("Unexpected message");
+            };
+            assert_eq!(txid, expected_txid);
+            assert_eq!(seq_in_tx, i);
+        }
+    };
+
+    expect_file(1, 9);
+    std::fs::copy(sample, table_dir.join("second.csv")).unwrap();
+    expect_file(2, 9);
+
+    // A modified file is ingested again.
+    let last_row = std::fs::read_to_string(sample)
+        .unwrap()
+        .lines()
+        .last()
+        .unwrap()
+        .to_string();
+    let mut first = OpenOptions::new()
+        .append(true)
+        .open(table_dir.join("first.csv"))
+        .unwrap();
+    writeln!(first, "{last_row}").unwrap();
+    drop(first);
+    expect_file(3, 10);
+}
+
+#[test]
+fn test_resume_from_checkpoint() {
+    let data_dir = TempDir::new("resume_data").unwrap();
+    let table_dir = data_dir.path().join("table");
+    std::fs::create_dir_all(&table_dir).unwrap();
+    let sample = "src/connectors/object_store/tests/files/all_types_csv/sample.csv";
+    std::fs::copy(sample, table_dir.join("first.csv")).unwrap();
+    std::fs::copy(sample, table_dir.join("second.csv")).unwrap();
+
+    let local_storage = LocalStorage {
+        details: Some(LocalDetails {
+            path: data_dir.path().to_str().unwrap().to_string(),
+        }),
+        tables: vec![Table {
+            name: "table".to_string(),
+            prefix: "table".to_string(),
+            file_type: "csv".to_string(),
+            extension: "csv".to_string(),
+            watch: None,
+            format_options: None,
+        }],
+    };
+    let state_dir = TempDir::new("resume_state").unwrap();
+    let new_connector = |state_dir: Option<&Path>| {
+        let mut connector = ObjectStoreConnector::new(1, local_storage.clone());
+        if let Some(state_dir) = state_dir {
+            connector.set_state_dir(state_dir.to_path_buf());
+        }
+        connector
+    };
+    let tables = {
+        let connector = new_connector(None);
+        connector
+            .list_columns(connector.list_tables().unwrap())
+            .unwrap()
+    };
+
+    // Nothing was consumed yet.
+    assert!(!new_connector(Some(state_dir.path()))
+        .can_start_from(&tables, (1, 3))
+        .unwrap());
+
+    let (ingestor, iterator) = Ingestor::initialize_channel(IngestionConfig::default());
+    let first_run = new_connector(Some(state_dir.path()));
+    let run_tables = tables.clone();
+    thread::spawn(move || first_run.start(&ingestor, run_tables, None).unwrap());
+    assert_eq!(iterator.count(), 18);
+
+    let connector = new_connector(Some(state_dir.path()));
+    assert!(connector.can_start_from(&tables, (2, 8)).unwrap());
+    assert!(!connector.can_start_from(&tables, (3, 0)).unwrap());
+    assert!(!new_connector(None).can_start_from(&tables, (2, 8)).unwrap());
+
+    // Row 3 of the first file was the last one committed.
+    let (ingestor, iterator) = Ingestor::initialize_channel(IngestionConfig::default());
+    thread::spawn(move || connector.start(&ingestor, tables, Some((1, 3))).unwrap());
+
+    let identifiers = iterator
+        .map(|message| (message.identifier.txid, message.identifier.seq_in_tx))
+        .collect::<Vec<_>>();
+    let expected = (4..9)
+        .map(|seq_in_tx| (1, seq_in_tx))
+        .chain((0..9).map(|seq_in_tx| (2, seq_in_tx)))
+        .collect::<Vec<_>>();
+    assert_eq!(identifiers, expected);
+}
@@ -0,0 +1,294 @@
+use crate::connectors::object_store::table_reader::read_listing;
+use crate::connectors::TableInfo;
+use crate::errors::ObjectStoreObjectError::ListingPathParsingError;
+use crate::errors::ObjectStoreWatchError::{
+    CheckpointFileMissing, InvalidState, ListingFailed, StateReadFailed, StateWriteFailed,
+};
+use crate::errors::{ObjectStoreConnectorError, ObjectStoreWatchError};
+use crate::ingestion::Ingestor;
+use deltalake::datafusion::arrow::datatypes::SchemaRef;
+use deltalake::datafusion::datasource::listing::{
+    ListingOptions, ListingTableConfig, ListingTableUrl,
+};
+use deltalake::datafusion::prelude::SessionContext;
+use dozer_types::log::info;
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::serde_json;
+use futures::{future, TryStreamExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A file as it was listed. A file that is modified or replaced after it was ingested has a
+/// different key, and is ingested again as a new file.
+///
+/// Files are ingested in key order: oldest modification time first, ties broken by table and
+/// path.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct FileKey {
+    pub last_modified: i64,
+    pub table: String,
+    pub path: String,
+    pub size: u64,
+}
+
+/// A table of the connection, ready to be listed and read file by file.
+pub struct TableSource<'a> {
+    pub id: u32,
+    pub ctx: SessionContext,
+    pub object_store: Arc<dyn ObjectStore>,
+    pub base_path: String,
+    pub prefix: Path,
+    pub listing_options: ListingOptions,
+    pub resolved_schema: SchemaRef,
+    /// Polling interval of a watched table. Other tables are only listed once.
+    pub watch: Option<Duration>,
+    pub table: &'a TableInfo,
+}
+
+impl<'a> TableSource<'a> {
+    async fn list(&self) -> Result<Vec<FileKey>, ObjectStoreWatchError> {
+        let extension = &self.listing_options.file_extension;
+        self.object_store
+            .list(Some(&self.prefix))
+            .await
+            .map_err(ListingFailed)?
+            .try_filter(|meta| future::ready(meta.location.as_ref().ends_with(extension)))
+            .map_ok(|meta| FileKey {
+                last_modified: meta.last_modified.timestamp_millis(),
+                table: self.table.name.clone(),
+                path: meta.location.to_string(),
+                size: meta.size as u64,
+            })
+            .try_collect()
+            .await
+            .map_err(ListingFailed)
+    }
+}
+
+/// Lists the files of all `tables` in ingestion order, or only those of the watched tables.
+pub async fn list_files(
+    tables: &[TableSource<'_>],
+    watched_only: bool,
+) -> Result<Vec<FileKey>, ObjectStoreWatchError> {
+    let mut files = vec![];
+    for table in tables {
+        if !watched_only || table.watch.is_some() {
+            files.extend(table.list().await?);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// A file and the transaction it was ingested in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct ConsumedFile {
+    pub txid: u64,
+    pub file: FileKey,
+}
+
+/// Tracks which files were ingested, so every listing can be diffed against them.
+///
+/// The consumed files are persisted one JSON line each to `state_path`, before their rows are
+/// ingested. Without a state path they are only kept in memory, and ingestion can't resume.
+#[derive(Debug, Default)]
+pub struct FileTracker {
+    state_path: Option<PathBuf>,
+    files: Vec<ConsumedFile>,
+    consumed: HashSet<FileKey>,
+    last_txid: u64,
+}
+
+impl FileTracker {
+    /// A tracker that hasn't consumed any file.
+    pub fn start_over(state_path: Option<PathBuf>) -> Self {
+        Self {
+            state_path,
+            ..Default::default()
+        }
+    }
+
+    /// Picks up after the last operation of a pipeline checkpoint, from the files recorded in
+    /// `state_path`. Returns the tracker, and the file the checkpoint points into with the number
+    /// of its rows that were ingested already. That file has to be among the listed `files`.
+    ///
+    /// Files recorded after the checkpoint are forgotten. Call [`FileTracker::save`] before
+    /// consuming files again.
+    pub fn resume(
+        state_path: Option<PathBuf>,
+        files: &[FileKey],
+        last_checkpoint: (u64, u64),
+    ) -> Result<(Self, Option<(FileKey, u64)>), ObjectStoreWatchError> {
+        let (txid, seq_in_tx) = last_checkpoint;
+        if txid == 0 {
+            // Nothing was ingested before the checkpoint.
+            return Ok((Self::start_over(state_path), None));
+        }
+        let Some(path) = &state_path else {
+            return Err(CheckpointFileMissing(txid));
+        };
+
+        let mut consumed = read_consumed_files(path)?;
+        consumed.retain(|file| file.txid <= txid);
+        let partial = match consumed.pop() {
+            Some(file) if file.txid == txid && files.contains(&file.file) => file.file,
+            _ => return Err(CheckpointFileMissing(txid)),
+        };
+
+        let tracker = Self {
+            consumed: consumed.iter().map(|file| file.file.clone()).collect(),
+            files: consumed,
+            last_txid: txid - 1,
+            state_path,
+        };
+        Ok((tracker, Some((partial, seq_in_tx + 1))))
+    }
+
+    /// Overwrites the recorded files with the ones of this tracker.
+    pub fn save(&self) -> Result<(), ObjectStoreWatchError> {
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        let tmp_path = path.with_extension("tmp");
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            for file in &self.files {
+                writeln!(writer, "{}", consumed_file_line(file))?;
+            }
+            writer.into_inner()?.sync_all()?;
+            fs::rename(&tmp_path, path)
+        };
+        write().map_err(|e| StateWriteFailed(path.clone(), e))
+    }
+
+    /// Filters a listing down to the files that weren't consumed yet.
+    pub fn new_files(&self, files: Vec<FileKey>) -> Vec<FileKey> {
+        files
+            .into_iter()
+            .filter(|file| !self.consumed.contains(file))
+            .collect()
+    }
+
+    /// Records `file` as consumed by the next transaction, and returns its transaction id.
+    pub fn start(&mut self, file: FileKey) -> Result<u64, ObjectStoreWatchError> {
+        let consumed = ConsumedFile {
+            txid: self.last_txid + 1,
+            file,
+        };
+        if let Some(path) = &self.state_path {
+            let append = || -> std::io::Result<()> {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let mut state_file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(state_file, "{}", consumed_file_line(&consumed))?;
+                state_file.sync_data()
+            };
+            append().map_err(|e| StateWriteFailed(path.clone(), e))?;
+        }
+
+        self.last_txid = consumed.txid;
+        self.consumed.insert(consumed.file.clone());
+        self.files.push(consumed);
+        Ok(self.last_txid)
+    }
+}
+
+fn consumed_file_line(file: &ConsumedFile) -> String {
+    serde_json::to_string(file).expect("consumed file is serializable")
+}
+
+fn read_consumed_files(path: &std::path::Path) -> Result<Vec<ConsumedFile>, ObjectStoreWatchError> {
+    let state_file = match File::open(path) {
+        Ok(state_file) => state_file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(StateReadFailed(path.to_path_buf(), e)),
+    };
+    let mut files = vec![];
+    for line in BufReader::new(state_file).lines() {
+        let line = line.map_err(|e| StateReadFailed(path.to_path_buf(), e))?;
+        files.push(serde_json::from_str(&line).map_err(|e| InvalidState(path.to_path_buf(), e))?);
+    }
+    Ok(files)
+}
+
+/// Ingests the files of all tables one at a time, each file as its own transaction, and keeps
+/// polling the watched tables for new files.
+pub struct TableWatcher<'a> {
+    pub tables: Vec<TableSource<'a>>,
+    pub tracker: FileTracker,
+    /// File that was being ingested when the connector was restarted, and the number of its rows
+    /// that were ingested already.
+    pub resume_file: Option<(FileKey, u64)>,
+    pub ingestor: &'a Ingestor,
+}
+
+impl<'a> TableWatcher<'a> {
+    /// Runs until every table was read, or forever if a table is watched. `files` is the listing
+    /// of all tables.
+    pub async fn run(mut self, mut files: Vec<FileKey>) -> Result<(), ObjectStoreConnectorError> {
+        let interval = self.tables.iter().filter_map(|table| table.watch).min();
+
+        if let Some((file, skip_rows)) = self.resume_file.take() {
+            self.ingest(file, skip_rows).await?;
+        }
+        loop {
+            for file in self.tracker.new_files(files) {
+                self.ingest(file, 0).await?;
+            }
+
+            let Some(interval) = interval else {
+                return Ok(());
+            };
+            tokio::time::sleep(interval).await;
+            files = list_files(&self.tables, true).await?;
+        }
+    }
+
+    async fn ingest(
+        &mut self,
+        file: FileKey,
+        skip_rows: u64,
+    ) -> Result<(), ObjectStoreConnectorError> {
+        let table = self
+            .tables
+            .iter()
+            .find(|table| table.table.name == file.table)
+            .expect("files are listed from the tables");
+        let file_path = format!("{}/{}", table.base_path, file.path);
+        let file_url = ListingTableUrl::parse(&file_path).map_err(|e| {
+            ObjectStoreConnectorError::DataFusionStorageObjectError(ListingPathParsingError(
+                file_path.clone(),
+                e,
+            ))
+        })?;
+        let txid = self.tracker.start(file)?;
+        info!("Ingesting {file_path} as transaction {txid}");
+
+        let config = ListingTableConfig::new(file_url)
+            .with_listing_options(table.listing_options.clone())
+            .with_schema(table.resolved_schema.clone());
+        read_listing(
+            txid,
+            skip_rows,
+            table.id,
+            &table.ctx,
+            config,
+            table.resolved_schema.clone(),
+            self.ingestor,
+            table.table,
+        )
+        .await
+    }
+}
@@ -1,44 +1,195 @@
+use crate::errors::PostgresConnectorError::{RootCertReadError, TlsError, UnsupportedSslMode};
 use crate::errors::{ConnectorError, PostgresConnectorError};
 use dozer_types::log::error;
 use dozer_types::models::connection::ConnectionConfig;
+use native_tls::{Certificate, TlsConnector};
 use postgres::{Client, Config};
+use postgres_native_tls::MakeTlsConnector;
 use tokio_postgres::NoTls;
 
+/// How a connection is secured, with the same meaning as `sslmode` of libpq.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SslMode {
+    #[default]
+    Disable,
+    Prefer,
+    Require,
+    VerifyCa,
+    VerifyFull,
+}
+
+impl SslMode {
+    pub fn parse(sslmode: &str) -> Result<Self, PostgresConnectorError> {
+        match sslmode {
+            "disable" => Ok(SslMode::Disable),
+            "prefer" => Ok(SslMode::Prefer),
+            "require" => Ok(SslMode::Require),
+            "verify-ca" => Ok(SslMode::VerifyCa),
+            "verify-full" => Ok(SslMode::VerifyFull),
+            _ => Err(UnsupportedSslMode(sslmode.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    pub mode: SslMode,
+    /// Path to the PEM encoded certificate of the authority that signed the server certificate.
+    pub root_cert: Option<String>,
+}
+
+impl TlsConfig {
+    /// Builds the TLS connector, `None` when TLS is disabled.
+    ///
+    /// As in libpq, `prefer` and `require` don't verify the server certificate unless a root
+    /// certificate is given, and only `verify-full` checks that the host name matches it.
+    pub fn connector(&self) -> Result<Option<MakeTlsConnector>, PostgresConnectorError> {
+        if self.mode == SslMode::Disable {
+            return Ok(None);
+        }
+
+        let mut builder = TlsConnector::builder();
+        if let Some(path) = &self.root_cert {
+            let pem = std::fs::read(path).map_err(|e| RootCertReadError(path.clone(), e))?;
+            builder.add_root_certificate(Certificate::from_pem(&pem).map_err(TlsError)?);
+        } else if matches!(self.mode, SslMode::Prefer | SslMode::Require) {
+            builder.danger_accept_invalid_certs(true);
+        }
+        if self.mode != SslMode::VerifyFull {
+            builder.danger_accept_invalid_hostnames(true);
+        }
+
+        let connector = builder.build().map_err(TlsError)?;
+        Ok(Some(MakeTlsConnector::new(connector)))
+    }
+
+    fn tokio_postgres_ssl_mode(&self) -> tokio_postgres::config::SslMode {
+        match self.mode {
+            SslMode::Disable => tokio_postgres::config::SslMode::Disable,
+            SslMode::Prefer => tokio_postgres::config::SslMode::Prefer,
+            SslMode::Require | SslMode::VerifyCa | SslMode::VerifyFull => {
+                tokio_postgres::config::SslMode::Require
+            }
+        }
+    }
+}
+
 pub fn map_connection_config(
     auth_details: &ConnectionConfig,
 ) -> Result<tokio_postgres::Config, ConnectorError> {
     if let ConnectionConfig::Postgres(postgres) = auth_details {
+        let tls = map_tls_config(auth_details)?;
         let mut config = tokio_postgres::Config::new();
         config
             .host(&postgres.host)
             .port(postgres.port as u16)
             .user(&postgres.user)
             .dbname(&postgres.database)
-            .password(&postgres.password);
+            .password(&postgres.password)
+            .ssl_mode(tls.tokio_postgres_ssl_mode());
         Ok(config)
     } else {
         Err(ConnectorError::WrongConnectionConfiguration)
     }
 }
 
-pub fn connect(config: tokio_postgres::Config) -> Result<Client, PostgresConnectorError> {
-    Config::from(config)
-        .connect(NoTls)
-        .map_err(PostgresConnectorError::ConnectionFailure)
+pub fn map_tls_config(auth_details: &ConnectionConfig) -> Result<TlsConfig, ConnectorError> {
+    if let ConnectionConfig::Postgres(postgres) = auth_details {
+        let mode = match &postgres.sslmode {
+            Some(sslmode) => SslMode::parse(sslmode)?,
+            None => SslMode::default(),
+        };
+        Ok(TlsConfig {
+            mode,
+            root_cert: postgres.ssl_root_cert.clone(),
+        })
+    } else {
+        Err(ConnectorError::WrongConnectionConfiguration)
+    }
+}
+
+pub fn connect(
+    config: tokio_postgres::Config,
+    tls: &TlsConfig,
+) -> Result<Client, PostgresConnectorError> {
+    let config = Config::from(config);
+    match tls.connector()? {
+        Some(connector) => config.connect(connector),
+        None => config.connect(NoTls),
+    }
+    .map_err(PostgresConnectorError::ConnectionFailure)
 }
 
 pub async fn async_connect(
     config: tokio_postgres::Config,
+    tls: &TlsConfig,
 ) -> Result<tokio_postgres::Client, PostgresConnectorError> {
-    match config.connect(NoTls).await {
-        Ok((client, connection)) => {
+    let client = match tls.connector()? {
+        Some(connector) => {
+            let (client, connection) = config
+                .connect(connector)
+                .await
+                .map_err(PostgresConnectorError::ConnectionFailure)?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    error!("connection error: {}", e);
+                }
+            });
+            client
+        }
+        None => {
+            let (client, connection) = config
+                .connect(NoTls)
+                .await
+                .map_err(PostgresConnectorError::ConnectionFailure)?;
             tokio::spawn(async move {
                 if let Err(e) = connection.await {
                     error!("connection error: {}", e);
                 }
             });
-            Ok(client)
+            client
         }
-        Err(e) => Err(PostgresConnectorError::ConnectionFailure(e)),
+    };
+    Ok(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dozer_types::models::connection::PostgresConfig;
+
+    fn postgres_config(sslmode: Option<&str>) -> ConnectionConfig {
+        ConnectionConfig::Postgres(PostgresConfig {
+            user: "postgres".to_string(),
+            password: "postgres".to_string(),
+            host: "localhost".to_string(),
+            port: 5432,
+            database: "users".to_string(),
+            sslmode: sslmode.map(str::to_string),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_map_tls_config() {
+        let tls = map_tls_config(&postgres_config(None)).unwrap();
+        assert_eq!(tls, TlsConfig::default());
+        assert!(tls.connector().unwrap().is_none());
+
+        let config = map_connection_config(&postgres_config(Some("verify-full"))).unwrap();
+        assert_eq!(
+            config.get_ssl_mode(),
+            tokio_postgres::config::SslMode::Require
+        );
+        let tls = map_tls_config(&postgres_config(Some("prefer"))).unwrap();
+        assert_eq!(tls.mode, SslMode::Prefer);
+        assert!(tls.connector().unwrap().is_some());
+
+        assert!(matches!(
+            map_tls_config(&postgres_config(Some("allow"))),
+            Err(ConnectorError::PostgresConnectorError(UnsupportedSslMode(
+                _
+            )))
+        ));
     }
 }
@@ -8,6 +8,7 @@ use crate::errors::PostgresConnectorError::{
     WALLevelIsNotCorrect,
 };
 
+use crate::connectors::postgres::connection::helper::TlsConfig;
 use crate::connectors::postgres::connection::tables_validator::TablesValidator;
 use crate::errors::PostgresConnectorError;
 use dozer_types::indicatif::ProgressStyle;
@@ -27,6 +28,7 @@ pub enum Validations {
 pub fn validate_connection(
     name: &str,
     config: tokio_postgres::Config,
+    tls: &TlsConfig,
     tables: Option<&Vec<ListOrFilterColumns>>,
     replication_info: Option<ReplicationSlotInfo>,
 ) -> Result<(), PostgresConnectorError> {
@@ -47,7 +49,7 @@ pub fn validate_connection(
     );
     pb.set_message("Validating connection to source");
 
-    let mut client = super::helper::connect(config)?;
+    let mut client = super::helper::connect(config, tls)?;
 
     for validation_type in validations_order {
         match validation_type {
@@ -270,7 +272,8 @@ mod tests {
             let mut config = get_config(app_config);
             config.dbname("not_existing");
 
-            let result = validate_connection("pg_test_conn", config, None, None);
+            let result =
+                validate_connection("pg_test_conn", config, &TlsConfig::default(), None, None);
 
             assert!(result.is_err());
 
@@ -351,7 +354,13 @@ mod tests {
                 schema: Some("public".to_string()),
                 columns: None,
             }];
-            let result = validate_connection("pg_test_conn", config, Some(&tables), None);
+            let result = validate_connection(
+                "pg_test_conn",
+                config,
+                &TlsConfig::default(),
+                Some(&tables),
+                None,
+            );
 
             assert!(result.is_err());
 
@@ -395,7 +404,13 @@ mod tests {
                 columns: Some(columns),
             }];
 
-            let result = validate_connection("pg_test_conn", config, Some(&tables), None);
+            let result = validate_connection(
+                "pg_test_conn",
+                config,
+                &TlsConfig::default(),
+                Some(&tables),
+                None,
+            );
 
             assert!(result.is_err());
 
@@ -427,7 +442,13 @@ mod tests {
                 start_lsn: PgLsn::from(0),
             };
 
-            let result = validate_connection("pg_test_conn", config, None, Some(replication_info));
+            let result = validate_connection(
+                "pg_test_conn",
+                config,
+                &TlsConfig::default(),
+                None,
+                Some(replication_info),
+            );
 
             assert!(result.is_err());
 
@@ -508,7 +529,8 @@ mod tests {
             }
 
             // One replication slot is available
-            let result = validate_connection("pg_test_conn", config, None, None);
+            let result =
+                validate_connection("pg_test_conn", config, &TlsConfig::default(), None, None);
             assert!(result.is_ok());
         });
     }
@@ -545,7 +567,8 @@ mod tests {
                     .unwrap();
             }
 
-            let result = validate_connection("pg_test_conn", config, None, None);
+            let result =
+                validate_connection("pg_test_conn", config, &TlsConfig::default(), None, None);
 
             assert!(result.is_err());
 
@@ -11,16 +11,30 @@ use postgres_types::PgLsn;
 
 use crate::connectors::postgres::schema::helper::SchemaHelper;
 use crate::errors::ConnectorError::PostgresConnectorError;
-use crate::errors::PostgresConnectorError::{CreatePublicationError, DropPublicationError};
+use crate::errors::PostgresConnectorError::{
+    CreatePublicationError, DropPublicationError, DropSlotError, InvalidQueryError,
+    PublicationNotFound,
+};
 use tokio_postgres::config::ReplicationMode;
 use tokio_postgres::Config;
 
-use super::connection::helper;
+use super::connection::helper::{self, TlsConfig};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PostgresConfig {
     pub name: String,
     pub config: Config,
+    pub tls: TlsConfig,
+    /// Replication slot to use instead of `dozer_slot_{name}`.
+    pub slot_name: Option<String>,
+    /// Publication to use instead of `dozer_publication_{name}`.
+    pub publication_name: Option<String>,
+    /// Use the publication as it exists instead of recreating it.
+    pub reuse_publication: bool,
+    /// Schemas whose tables are listed, all of them when empty.
+    pub schemas: Vec<String>,
+    /// Other slots to drop on clean, if they're inactive.
+    pub clean_slots: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -28,6 +42,12 @@ pub struct PostgresConnector {
     name: String,
     replication_conn_config: Config,
     conn_config: Config,
+    tls: TlsConfig,
+    slot_name: String,
+    publication_name: String,
+    reuse_publication: bool,
+    schemas: Vec<String>,
+    clean_slots: Vec<String>,
     schema_helper: SchemaHelper,
 }
 
@@ -42,15 +62,25 @@ impl PostgresConnector {
         let mut replication_conn_config = config.config.clone();
         replication_conn_config.replication_mode(ReplicationMode::Logical);
 
-        let helper = SchemaHelper::new(config.config.clone());
+        let helper = SchemaHelper::new(config.config.clone(), config.tls.clone());
 
         // conn_str - replication_conn_config
         // conn_str_plain- conn_config
 
         PostgresConnector {
+            slot_name: config
+                .slot_name
+                .unwrap_or_else(|| format!("dozer_slot_{}", config.name)),
+            publication_name: config
+                .publication_name
+                .unwrap_or_else(|| format!("dozer_publication_{}", config.name)),
+            reuse_publication: config.reuse_publication,
+            schemas: config.schemas,
+            clean_slots: config.clean_slots,
             name: config.name,
             conn_config: config.config,
             replication_conn_config,
+            tls: config.tls,
             schema_helper: helper,
         }
     }
@@ -84,7 +114,8 @@ impl Connector for PostgresConnector {
     }
 
     fn validate_connection(&self) -> Result<(), ConnectorError> {
-        validate_connection(&self.name, self.conn_config.clone(), None, None).map_err(Into::into)
+        validate_connection(&self.name, self.conn_config.clone(), &self.tls, None, None)
+            .map_err(Into::into)
     }
 
     fn list_tables(&self) -> Result<Vec<TableIdentifier>, ConnectorError> {
@@ -92,6 +123,7 @@ impl Connector for PostgresConnector {
             .schema_helper
             .get_tables(None)?
             .into_iter()
+            .filter(|table| self.schemas.is_empty() || self.schemas.contains(&table.schema))
             .map(|table| TableIdentifier::new(Some(table.schema), table.name))
             .collect())
     }
@@ -105,8 +137,14 @@ impl Connector for PostgresConnector {
                 columns: None,
             })
             .collect::<Vec<_>>();
-        validate_connection(&self.name, self.conn_config.clone(), Some(&tables), None)
-            .map_err(Into::into)
+        validate_connection(
+            &self.name,
+            self.conn_config.clone(),
+            &self.tls,
+            Some(&tables),
+            None,
+        )
+        .map_err(Into::into)
     }
 
     fn list_columns(&self, tables: Vec<TableIdentifier>) -> Result<Vec<TableInfo>, ConnectorError> {
@@ -147,8 +185,13 @@ impl Connector for PostgresConnector {
             .map_err(Into::into)
     }
 
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> Result<(), ConnectorError> {
-        let client = helper::connect(self.replication_conn_config.clone())
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        _last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
+        let client = helper::connect(self.replication_conn_config.clone(), &self.tls)
             .map_err(PostgresConnectorError)?;
         let table_identifiers = tables
             .iter()
@@ -174,18 +217,51 @@ impl Connector for PostgresConnector {
             self.replication_conn_config.clone(),
             ingestor,
             self.conn_config.clone(),
+            self.tls.clone(),
         );
         iterator.start(lsn)
     }
+
+    fn clean(&self) -> Result<(), ConnectorError> {
+        self.drop_inactive_slots()
+    }
 }
 
 impl PostgresConnector {
     fn get_publication_name(&self) -> String {
-        format!("dozer_publication_{}", self.name)
+        self.publication_name.clone()
     }
 
     pub fn get_slot_name(&self) -> String {
-        format!("dozer_slot_{}", self.name)
+        self.slot_name.clone()
+    }
+
+    /// Drops the replication slot of this connection, and the slots listed in `clean_slots`,
+    /// unless a running pipeline is using them.
+    ///
+    /// A slot retains WAL on the server until it is consumed, so slots left behind by a deleted
+    /// pipeline eventually fill the disk of the database. Other slots are never dropped, as they
+    /// may hold the position of another app that is only stopped.
+    pub fn drop_inactive_slots(&self) -> Result<(), ConnectorError> {
+        let mut client =
+            helper::connect(self.conn_config.clone(), &self.tls).map_err(PostgresConnectorError)?;
+        let slot_names = std::iter::once(&self.slot_name)
+            .chain(&self.clean_slots)
+            .collect::<Vec<_>>();
+        let dropped = client
+            .query(
+                "SELECT slot_name::TEXT, pg_drop_replication_slot(slot_name) \
+                 FROM pg_replication_slots \
+                 WHERE NOT active AND slot_type = 'logical' AND database = current_database() \
+                 AND slot_name::TEXT = ANY($1)",
+                &[&slot_names],
+            )
+            .map_err(|e| PostgresConnectorError(DropSlotError(self.slot_name.clone(), e)))?;
+        for row in dropped {
+            let slot_name: String = row.get(0);
+            info!("[{}] Dropped replication slot {}", self.name, slot_name);
+        }
+        Ok(())
     }
 
     pub fn create_publication(
@@ -194,6 +270,26 @@ impl PostgresConnector {
         table_identifiers: Option<&[TableIdentifier]>,
     ) -> Result<(), ConnectorError> {
         let publication_name = self.get_publication_name();
+        if self.reuse_publication {
+            // `client` may be in replication mode, which doesn't support parameters.
+            let mut client = helper::connect(self.conn_config.clone(), &self.tls)
+                .map_err(PostgresConnectorError)?;
+            let exists = !client
+                .query(
+                    "SELECT pubname FROM pg_publication WHERE pubname = $1",
+                    &[&publication_name],
+                )
+                .map_err(|e| PostgresConnectorError(InvalidQueryError(e)))?
+                .is_empty();
+            return if !exists {
+                Err(PostgresConnectorError(PublicationNotFound(
+                    publication_name,
+                )))
+            } else {
+                Ok(())
+            };
+        }
+
         let table_str: String = match table_identifiers {
             None => "ALL TABLES".to_string(),
             Some(table_identifiers) => {
@@ -9,7 +9,7 @@ use std::str::FromStr;
 
 use std::sync::Arc;
 
-use crate::connectors::postgres::connection::helper;
+use crate::connectors::postgres::connection::helper::{self, TlsConfig};
 use crate::connectors::postgres::replication_slot_helper::ReplicationSlotHelper;
 use crate::connectors::postgres::replicator::CDCHandler;
 use crate::connectors::postgres::snapshotter::PostgresSnapshotter;
@@ -28,6 +28,7 @@ pub struct Details {
     tables: Vec<PostgresTableInfo>,
     replication_conn_config: tokio_postgres::Config,
     conn_config: tokio_postgres::Config,
+    tls: TlsConfig,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -52,6 +53,7 @@ impl<'a> PostgresIterator<'a> {
         replication_conn_config: tokio_postgres::Config,
         ingestor: &'a Ingestor,
         conn_config: tokio_postgres::Config,
+        tls: TlsConfig,
     ) -> Self {
         let details = Arc::new(Details {
             name,
@@ -60,6 +62,7 @@ impl<'a> PostgresIterator<'a> {
             tables,
             replication_conn_config,
             conn_config,
+            tls,
         });
         PostgresIterator { details, ingestor }
     }
@@ -106,7 +109,7 @@ impl<'a> PostgresIteratorHandler<'a> {
         let details = Arc::clone(&self.details);
         let replication_conn_config = details.replication_conn_config.to_owned();
         let client = Arc::new(RefCell::new(
-            helper::connect(replication_conn_config)
+            helper::connect(replication_conn_config, &details.tls)
                 .map_err(ConnectorError::PostgresConnectorError)?,
         ));
 
@@ -153,6 +156,7 @@ impl<'a> PostgresIteratorHandler<'a> {
 
             let snapshotter = PostgresSnapshotter {
                 conn_config: details.conn_config.to_owned(),
+                tls: details.tls.clone(),
                 ingestor: self.ingestor,
             };
             let tables = details
@@ -198,6 +202,7 @@ impl<'a> PostgresIteratorHandler<'a> {
         rt.block_on(async {
             let mut replicator = CDCHandler {
                 replication_conn_config: self.details.replication_conn_config.clone(),
+                tls: self.details.tls.clone(),
                 ingestor: self.ingestor,
                 start_lsn: *lsn,
                 begin_lsn: 0,
@@ -1,4 +1,4 @@
-use crate::connectors::postgres::connection::helper;
+use crate::connectors::postgres::connection::helper::{self, TlsConfig};
 use crate::connectors::postgres::xlog_mapper::XlogMapper;
 use crate::errors::ConnectorError;
 use crate::errors::ConnectorError::PostgresConnectorError;
@@ -27,6 +27,7 @@ pub struct CDCHandler<'a> {
     pub ingestor: &'a Ingestor,
 
     pub replication_conn_config: tokio_postgres::Config,
+    pub tls: TlsConfig,
     pub publication_name: String,
     pub slot_name: String,
 
@@ -42,7 +43,8 @@ pub struct CDCHandler<'a> {
 impl<'a> CDCHandler<'a> {
     pub async fn start(&mut self, tables: Vec<PostgresTableInfo>) -> Result<(), ConnectorError> {
         let replication_conn_config = self.replication_conn_config.clone();
-        let client: tokio_postgres::Client = helper::async_connect(replication_conn_config).await?;
+        let client: tokio_postgres::Client =
+            helper::async_connect(replication_conn_config, &self.tls).await?;
 
         info!(
             "[{}] Starting Replication: {:?}, {:?}",
@@ -139,6 +141,18 @@ impl<'a> CDCHandler<'a> {
                                 .map_err(ConnectorError::IngestorError)?;
                         }
                     }
+                    Some(MappedReplicationMessage::SchemaChanged(schema)) => {
+                        self.seq_no += 1;
+                        if self.begin_lsn != self.offset_lsn || self.offset < self.seq_no {
+                            self.ingestor
+                                .handle_message(IngestionMessage::new_schema_changed(
+                                    self.begin_lsn,
+                                    self.seq_no,
+                                    schema,
+                                ))
+                                .map_err(ConnectorError::IngestorError)?;
+                        }
+                    }
                     None => {}
                 }
 
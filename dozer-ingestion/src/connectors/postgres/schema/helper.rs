@@ -4,7 +4,7 @@ use crate::connectors::{CdcType, ListOrFilterColumns, SourceSchema, SourceSchema
 use crate::errors::{ConnectorError, PostgresConnectorError, PostgresSchemaError};
 use dozer_types::types::{FieldDefinition, Schema, SchemaIdentifier, SourceDefinition};
 
-use crate::connectors::postgres::connection::helper;
+use crate::connectors::postgres::connection::helper::{self, TlsConfig};
 use crate::connectors::postgres::helper::postgres_type_to_dozer_type;
 use crate::errors::PostgresSchemaError::{InvalidColumnType, ValueConversionError};
 
@@ -20,6 +20,7 @@ use PostgresSchemaError::TableTypeNotFound;
 #[derive(Debug)]
 pub struct SchemaHelper {
     conn_config: tokio_postgres::Config,
+    tls: TlsConfig,
 }
 
 struct PostgresTableRow {
@@ -93,8 +94,8 @@ pub struct PostgresTableInfo {
 type RowsWithColumnsMap = (Vec<Row>, HashMap<SchemaTableIdentifier, Vec<String>>);
 
 impl SchemaHelper {
-    pub fn new(conn_config: tokio_postgres::Config) -> SchemaHelper {
-        Self { conn_config }
+    pub fn new(conn_config: tokio_postgres::Config, tls: TlsConfig) -> SchemaHelper {
+        Self { conn_config, tls }
     }
 
     pub fn get_tables(
@@ -153,7 +154,7 @@ impl SchemaHelper {
         tables: Option<&[ListOrFilterColumns]>,
     ) -> Result<RowsWithColumnsMap, PostgresConnectorError> {
         let mut tables_columns_map: HashMap<SchemaTableIdentifier, Vec<String>> = HashMap::new();
-        let mut client = helper::connect(self.conn_config.clone())?;
+        let mut client = helper::connect(self.conn_config.clone(), &self.tls)?;
         let query = if let Some(tables) = tables {
             tables.iter().for_each(|t| {
                 if let Some(columns) = t.columns.clone() {
@@ -181,7 +182,8 @@ impl SchemaHelper {
             let sql = str::replace(
                 SQL,
                 ":tables_name_condition",
-                "t.table_schema = ANY($1) AND t.table_name = ANY($2)",
+                // Match schema and name pairs, tables of the same name can live in several schemas.
+                "(t.table_schema, t.table_name) IN (SELECT * FROM UNNEST($1::text[], $2::text[]))",
             );
             client.query(&sql, &[&schemas, &table_names])
         } else {
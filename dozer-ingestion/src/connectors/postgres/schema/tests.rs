@@ -34,7 +34,7 @@ fn test_connector_get_tables() {
         client.create_schema(&schema);
         client.create_simple_table(&schema, &table_name);
 
-        let schema_helper = SchemaHelper::new(client.postgres_config.clone());
+        let schema_helper = SchemaHelper::new(client.postgres_config.clone(), client.tls.clone());
         let result = schema_helper.get_tables(None).unwrap();
 
         let table = result.get(0).unwrap();
@@ -68,7 +68,7 @@ fn test_connector_get_schema_with_selected_columns() {
         client.create_schema(&schema);
         client.create_simple_table(&schema, &table_name);
 
-        let schema_helper = SchemaHelper::new(client.postgres_config.clone());
+        let schema_helper = SchemaHelper::new(client.postgres_config.clone(), client.tls.clone());
         let table_info = ListOrFilterColumns {
             schema: Some(schema.clone()),
             name: table_name.clone(),
@@ -102,7 +102,7 @@ fn test_connector_get_schema_without_selected_columns() {
         client.create_schema(&schema);
         client.create_simple_table(&schema, &table_name);
 
-        let schema_helper = SchemaHelper::new(client.postgres_config.clone());
+        let schema_helper = SchemaHelper::new(client.postgres_config.clone(), client.tls.clone());
         let table_info = ListOrFilterColumns {
             name: table_name.clone(),
             schema: Some(schema.clone()),
@@ -143,7 +143,7 @@ fn test_connector_view_cannot_be_used() {
         client.create_simple_table(&schema, &table_name);
         client.create_view(&schema, &table_name, &view_name);
 
-        let schema_helper = SchemaHelper::new(client.postgres_config.clone());
+        let schema_helper = SchemaHelper::new(client.postgres_config.clone(), client.tls.clone());
         let table_info = ListOrFilterColumns {
             name: view_name,
             schema: Some(schema.clone()),
@@ -2,7 +2,7 @@ use crate::connectors::{ListOrFilterColumns, SourceSchemaResult};
 use crate::ingestion::Ingestor;
 
 use super::helper;
-use crate::connectors::postgres::connection::helper as connection_helper;
+use crate::connectors::postgres::connection::helper::{self as connection_helper, TlsConfig};
 use crate::errors::ConnectorError;
 use crate::errors::PostgresConnectorError::{InvalidQueryError, PostgresSchemaError};
 use crate::errors::PostgresConnectorError::{SnapshotReadError, SyncWithSnapshotError};
@@ -21,6 +21,7 @@ use dozer_types::types::Operation;
 
 pub struct PostgresSnapshotter<'a> {
     pub conn_config: tokio_postgres::Config,
+    pub tls: TlsConfig,
     pub ingestor: &'a Ingestor,
 }
 
@@ -29,7 +30,7 @@ impl<'a> PostgresSnapshotter<'a> {
         &self,
         tables: &[ListOrFilterColumns],
     ) -> Result<Vec<SourceSchemaResult>, ConnectorError> {
-        let helper = SchemaHelper::new(self.conn_config.clone());
+        let helper = SchemaHelper::new(self.conn_config.clone(), self.tls.clone());
         helper.get_schemas(tables).map_err(PostgresConnectorError)
     }
 
@@ -38,10 +39,11 @@ impl<'a> PostgresSnapshotter<'a> {
         schema_name: String,
         name: String,
         conn_config: tokio_postgres::Config,
+        tls: TlsConfig,
         sender: Sender<Result<Option<Operation>, ConnectorError>>,
     ) -> Result<(), ConnectorError> {
         let mut client_plain =
-            connection_helper::connect(conn_config).map_err(PostgresConnectorError)?;
+            connection_helper::connect(conn_config, &tls).map_err(PostgresConnectorError)?;
 
         let column_str: Vec<String> = schema
             .fields
@@ -98,10 +100,11 @@ impl<'a> PostgresSnapshotter<'a> {
             let schema_name = table.schema.clone().unwrap_or("public".to_string());
             let name = table.name.clone();
             let conn_config = self.conn_config.clone();
+            let tls = self.tls.clone();
             let sender = tx.clone();
             thread::spawn(move || {
                 if let Err(e) =
-                    Self::sync_table(schema, schema_name, name, conn_config, sender.clone())
+                    Self::sync_table(schema, schema_name, name, conn_config, tls, sender.clone())
                 {
                     sender.send(Err(e)).unwrap();
                 }
@@ -142,7 +145,8 @@ mod tests {
     use crate::{
         connectors::{
             postgres::{
-                connection::helper::map_connection_config, tests::client::TestPostgresClient,
+                connection::helper::{map_connection_config, map_tls_config},
+                tests::client::TestPostgresClient,
             },
             ListOrFilterColumns,
         },
@@ -187,6 +191,7 @@ mod tests {
 
             let snapshotter = PostgresSnapshotter {
                 conn_config,
+                tls: map_tls_config(config).unwrap(),
                 ingestor: &ingestor,
             };
 
@@ -245,6 +250,7 @@ mod tests {
 
             let snapshotter = PostgresSnapshotter {
                 conn_config,
+                tls: map_tls_config(config).unwrap(),
                 ingestor: &ingestor,
             };
 
@@ -290,6 +296,7 @@ mod tests {
 
             let snapshotter = PostgresSnapshotter {
                 conn_config,
+                tls: map_tls_config(config).unwrap(),
                 ingestor: &ingestor,
             };
 
@@ -1,4 +1,6 @@
-use crate::connectors::postgres::connection::helper::{connect, map_connection_config};
+use crate::connectors::postgres::connection::helper::{
+    connect, map_connection_config, map_tls_config, TlsConfig,
+};
 use dozer_types::models::connection::ConnectionConfig;
 use dozer_types::rust_decimal::Decimal;
 use postgres::Client;
@@ -7,26 +9,31 @@ use std::fmt::Write;
 pub struct TestPostgresClient {
     client: Client,
     pub postgres_config: tokio_postgres::Config,
+    pub tls: TlsConfig,
 }
 
 impl TestPostgresClient {
     pub fn new(auth: &ConnectionConfig) -> Self {
         let postgres_config = map_connection_config(auth).unwrap();
+        let tls = map_tls_config(auth).unwrap();
 
-        let client = connect(postgres_config.clone()).unwrap();
+        let client = connect(postgres_config.clone(), &tls).unwrap();
 
         Self {
             client,
             postgres_config,
+            tls,
         }
     }
 
     pub fn new_with_postgres_config(postgres_config: tokio_postgres::Config) -> Self {
-        let client = connect(postgres_config.clone()).unwrap();
+        let tls = TlsConfig::default();
+        let client = connect(postgres_config.clone(), &tls).unwrap();
 
         Self {
             client,
             postgres_config,
+            tls,
         }
     }
 
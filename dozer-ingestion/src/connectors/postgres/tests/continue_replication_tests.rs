@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::connectors::postgres::connection::helper;
-    use crate::connectors::postgres::connection::helper::map_connection_config;
+    use crate::connectors::postgres::connection::helper::{map_connection_config, map_tls_config};
     use crate::connectors::postgres::connector::{PostgresConfig, PostgresConnector};
     use crate::connectors::postgres::replication_slot_helper::ReplicationSlotHelper;
     use crate::connectors::postgres::test_utils::{create_slot, retry_drop_active_slot};
@@ -32,9 +32,12 @@ mod tests {
                 .as_ref()
                 .unwrap();
             let conn_config = map_connection_config(config).unwrap();
+            let tls = map_tls_config(config).unwrap();
             let postgres_config = PostgresConfig {
                 name: "test".to_string(),
                 config: conn_config.clone(),
+                tls: tls.clone(),
+                ..Default::default()
             };
 
             let connector = PostgresConnector::new(postgres_config);
@@ -46,11 +49,11 @@ mod tests {
             replication_conn_config.replication_mode(ReplicationMode::Logical);
 
             // Creating publication
-            let client = helper::connect(replication_conn_config.clone()).unwrap();
+            let client = helper::connect(replication_conn_config.clone(), &tls).unwrap();
             connector.create_publication(client, None).unwrap();
 
             // Creating slot
-            let client = helper::connect(replication_conn_config.clone()).unwrap();
+            let client = helper::connect(replication_conn_config.clone(), &tls).unwrap();
             let client_ref = Arc::new(RefCell::new(client));
             let slot_name = connector.get_slot_name();
             let _parsed_lsn = create_slot(client_ref.clone(), &slot_name);
@@ -87,9 +90,12 @@ mod tests {
             test_client.create_simple_table("public", &table_name);
 
             let conn_config = map_connection_config(config).unwrap();
+            let tls = map_tls_config(config).unwrap();
             let postgres_config = PostgresConfig {
                 name: connector_name,
                 config: conn_config.clone(),
+                tls: tls.clone(),
+                ..Default::default()
             };
 
             let connector = PostgresConnector::new(postgres_config);
@@ -98,7 +104,7 @@ mod tests {
             replication_conn_config.replication_mode(ReplicationMode::Logical);
 
             // Creating publication
-            let client = helper::connect(replication_conn_config.clone()).unwrap();
+            let client = helper::connect(replication_conn_config.clone(), &tls).unwrap();
             let table_identifier = TableIdentifier {
                 schema: Some("public".to_string()),
                 name: table_name.clone(),
@@ -108,7 +114,7 @@ mod tests {
                 .unwrap();
 
             // Creating slot
-            let client = helper::connect(replication_conn_config.clone()).unwrap();
+            let client = helper::connect(replication_conn_config.clone(), &tls).unwrap();
             let client_ref = Arc::new(RefCell::new(client));
 
             let slot_name = connector.get_slot_name();
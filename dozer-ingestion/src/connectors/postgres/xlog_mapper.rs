@@ -1,7 +1,9 @@
 use crate::connectors::postgres::helper;
 use crate::errors::{PostgresConnectorError, PostgresSchemaError};
 use dozer_types::node::OpIdentifier;
-use dozer_types::types::{Field, FieldDefinition, Operation, Record, SourceDefinition};
+use dozer_types::types::{
+    Field, FieldDefinition, Operation, Record, Schema, SchemaIdentifier, SourceDefinition,
+};
 use helper::postgres_type_to_dozer_type;
 use postgres_protocol::message::backend::LogicalReplicationMessage::{
     Begin, Commit, Delete, Insert, Relation, Update,
@@ -59,6 +61,7 @@ pub enum MappedReplicationMessage {
     Begin,
     Commit(OpIdentifier),
     Operation(Operation),
+    SchemaChanged(Schema),
 }
 
 pub struct XlogMapper {
@@ -98,7 +101,9 @@ impl XlogMapper {
                     }
                     Some(table) => {
                         if table.hash != hash {
-                            self.ingest_schema(relation, hash)?;
+                            // The table was altered after replication started.
+                            let schema = self.ingest_schema(relation, hash)?;
+                            return Ok(Some(MappedReplicationMessage::SchemaChanged(schema)));
                         }
                     }
                 }
@@ -189,7 +194,7 @@ impl XlogMapper {
         &mut self,
         relation: &RelationBody,
         hash: u64,
-    ) -> Result<(), PostgresConnectorError> {
+    ) -> Result<Schema, PostgresConnectorError> {
         let rel_id = relation.rel_id();
         let existing_columns = self
             .tables_columns
@@ -230,7 +235,8 @@ impl XlogMapper {
         };
 
         let mut fields = vec![];
-        for c in &table.columns {
+        let mut primary_index = vec![];
+        for (idx, c) in table.columns.iter().enumerate() {
             let typ = c.r#type.clone();
             let typ = typ
                 .map_or(
@@ -245,11 +251,21 @@ impl XlogMapper {
                 nullable: true,
                 source: SourceDefinition::Dynamic,
             });
+            if c.flags == 1 {
+                primary_index.push(idx);
+            }
         }
 
         self.relations_map.insert(rel_id, table);
 
-        Ok(())
+        Ok(Schema {
+            identifier: Some(SchemaIdentifier {
+                id: rel_id,
+                version: 0,
+            }),
+            fields,
+            primary_index,
+        })
     }
 
     fn convert_values_to_fields(
@@ -296,3 +312,107 @@ impl XlogMapper {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dozer_types::bytes::Bytes;
+    use postgres_protocol::message::backend::ReplicationMessage;
+
+    /// Wraps a logical replication message in an XLogData message and parses it back.
+    fn xlog_data(message: Vec<u8>) -> XLogDataBody<LogicalReplicationMessage> {
+        let mut buf = vec![b'w'];
+        buf.extend_from_slice(&0u64.to_be_bytes());
+        buf.extend_from_slice(&0u64.to_be_bytes());
+        buf.extend_from_slice(&0i64.to_be_bytes());
+        buf.extend(message);
+        match ReplicationMessage::parse(&Bytes::from(buf)).unwrap() {
+            ReplicationMessage::XLogData(body) => body
+                .map_data(|data| LogicalReplicationMessage::parse(&data))
+                .unwrap(),
+            _ => panic!("Expected an XLogData message"),
+        }
+    }
+
+    fn cstring(buf: &mut Vec<u8>, value: &str) {
+        buf.extend_from_slice(value.as_bytes());
+        buf.push(0);
+    }
+
+    /// `columns` are (name, type, flags).
+    fn relation(columns: &[(&str, &Type, i8)]) -> Vec<u8> {
+        let mut buf = vec![b'R'];
+        buf.extend_from_slice(&1i32.to_be_bytes());
+        cstring(&mut buf, "public");
+        cstring(&mut buf, "users");
+        buf.push(b'd');
+        buf.extend_from_slice(&(columns.len() as i16).to_be_bytes());
+        for (name, typ, flags) in columns {
+            buf.push(*flags as u8);
+            cstring(&mut buf, name);
+            buf.extend_from_slice(&(typ.oid() as i32).to_be_bytes());
+            buf.extend_from_slice(&(-1i32).to_be_bytes());
+        }
+        buf
+    }
+
+    fn insert(values: &[&str]) -> Vec<u8> {
+        let mut buf = vec![b'I'];
+        buf.extend_from_slice(&1i32.to_be_bytes());
+        buf.push(b'N');
+        buf.extend_from_slice(&(values.len() as i16).to_be_bytes());
+        for value in values {
+            buf.push(b't');
+            buf.extend_from_slice(&(value.len() as i32).to_be_bytes());
+            buf.extend_from_slice(value.as_bytes());
+        }
+        buf
+    }
+
+    #[test]
+    fn test_added_column_changes_the_schema() {
+        let mut mapper = XlogMapper::default();
+        let id = ("id", &Type::INT4, 1);
+        let name = ("name", &Type::TEXT, 0);
+        let email = ("email", &Type::TEXT, 0);
+
+        assert!(mapper
+            .handle_message(xlog_data(relation(&[id, name])))
+            .unwrap()
+            .is_none());
+        // the same relation is sent again before every transaction that touches the table
+        assert!(mapper
+            .handle_message(xlog_data(relation(&[id, name])))
+            .unwrap()
+            .is_none());
+
+        let Some(MappedReplicationMessage::SchemaChanged(schema)) = mapper
+            .handle_message(xlog_data(relation(&[id, name, email])))
+            .unwrap()
+        else {
+            panic!("Expected a schema change");
+        };
+        let names = schema
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "name", "email"]);
+        assert_eq!(schema.primary_index, vec![0]);
+
+        let Some(MappedReplicationMessage::Operation(Operation::Insert { new })) = mapper
+            .handle_message(xlog_data(insert(&["1", "Alice", "alice@example.com"])))
+            .unwrap()
+        else {
+            panic!("Expected an insert");
+        };
+        assert_eq!(
+            new.values,
+            vec![
+                Field::Int(1),
+                Field::String("Alice".to_string()),
+                Field::String("alice@example.com".to_string()),
+            ]
+        );
+    }
+}
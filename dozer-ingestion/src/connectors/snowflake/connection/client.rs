@@ -306,8 +306,9 @@ impl Client {
         conn: &Connection<AutocommitOn>,
         table_name: &String,
     ) -> Result<bool, SnowflakeError> {
-        let query =
-            format!("SELECT * FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_NAME = '{table_name}';");
+        let query = format!(
+            "SELECT * FROM INFORMATION_SCHEMA.TABLES WHERE TABLE_NAME = UPPER('{table_name}');"
+        );
 
         let stmt = Statement::with_parent(conn).map_err(|e| QueryError(Box::new(e)))?;
         stmt.exec_direct(&query)
@@ -316,6 +317,29 @@ impl Client {
             })
     }
 
+    pub fn stream_has_data(
+        &self,
+        conn: &Connection<AutocommitOn>,
+        stream_name: &String,
+    ) -> Result<bool, SnowflakeError> {
+        let query = format!("SELECT SYSTEM$STREAM_HAS_DATA('{stream_name}');");
+
+        let stmt = Statement::with_parent(conn).map_err(|e| QueryError(Box::new(e)))?;
+        match stmt
+            .exec_direct(&query)
+            .map_err(|e| QueryError(Box::new(e)))?
+        {
+            Data(mut data) => match data.fetch().map_err(|e| QueryError(Box::new(e)))? {
+                Some(mut cursor) => Ok(cursor
+                    .get_data::<bool>(1)
+                    .map_err(|e| QueryError(Box::new(e)))?
+                    .unwrap_or(false)),
+                None => Ok(false),
+            },
+            NoData(_) => Ok(false),
+        }
+    }
+
     pub fn drop_stream(
         &self,
         conn: &Connection<AutocommitOn>,
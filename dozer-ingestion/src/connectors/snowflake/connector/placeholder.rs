@@ -52,6 +52,7 @@ impl Connector for PlaceHolderSnowflakeConnector {
         &self,
         _ingestor: &crate::ingestion::Ingestor,
         _tables: Vec<TableInfo>,
+        _last_checkpoint: Option<(u64, u64)>,
     ) -> Result<(), ConnectorError> {
         todo!()
     }
@@ -17,6 +17,8 @@ use tokio::time;
 
 use crate::errors::{SnowflakeError, SnowflakeStreamError};
 
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 5;
+
 #[derive(Debug)]
 pub struct SnowflakeConnector {
     name: String,
@@ -101,14 +103,38 @@ impl Connector for SnowflakeConnector {
             .collect())
     }
 
-    fn start(&self, ingestor: &Ingestor, tables: Vec<TableInfo>) -> Result<(), ConnectorError> {
+    fn can_start_from(
+        &self,
+        tables: &[TableInfo],
+        last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError> {
+        let client = Client::new(&self.config);
+        StreamConsumer::create_offsets_table(&client)?;
+        for table in tables {
+            if !StreamConsumer::can_resume(&client, &table.name, last_checkpoint)? {
+                info!(
+                    "[{}][{}] Cannot continue ingestion from {}/{}",
+                    self.name, table.name, last_checkpoint.0, last_checkpoint.1
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn start(
+        &self,
+        ingestor: &Ingestor,
+        tables: Vec<TableInfo>,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<(), ConnectorError> {
         Runtime::new().unwrap().block_on(async {
             run(
                 self.name.clone(),
                 self.config.clone(),
                 tables,
                 ingestor,
-                None,
+                last_checkpoint,
             )
             .await
         })
@@ -120,50 +146,69 @@ async fn run(
     config: SnowflakeConfig,
     tables: Vec<TableInfo>,
     ingestor: &Ingestor,
-    from_seq: Option<(u64, u64)>,
+    last_checkpoint: Option<(u64, u64)>,
 ) -> Result<(), ConnectorError> {
     let client = Client::new(&config);
 
-    // SNAPSHOT part - run it when stream table doesn't exist
-    let stream_client = Client::new(&config);
-    let mut interval = time::interval(Duration::from_secs(5));
+    let stream_client = Client::new(&SnowflakeConfig {
+        warehouse: config
+            .stream_warehouse
+            .clone()
+            .unwrap_or_else(|| config.warehouse.clone()),
+        ..config.clone()
+    });
+    let mut interval = time::interval(Duration::from_secs(
+        config
+            .poll_interval_seconds
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS),
+    ));
+
+    // SNAPSHOT part - streams are created with their initial rows, unless ingestion continues
+    StreamConsumer::create_offsets_table(&client)?;
+    let mut batch = 0;
+    for table in &tables {
+        match last_checkpoint {
+            None => {
+                info!("[{}][{}] Creating new stream", name, table.name);
+                StreamConsumer::drop_stream(&client, &table.name)?;
+                StreamConsumer::drop_temp_table(&client, &table.name)?;
+                StreamConsumer::clear_offset(&client, &table.name)?;
+                StreamConsumer::create_stream(&client, &table.name)?;
+            }
+            Some((checkpoint_batch, seq)) => {
+                info!(
+                    "[{}][{}] Continuing ingestion from {}/{}",
+                    name, table.name, checkpoint_batch, seq
+                );
+                if let Ok(false) = StreamConsumer::is_stream_created(&client, &table.name) {
+                    return Err(ConnectorError::SnowflakeError(
+                        SnowflakeError::SnowflakeStreamError(SnowflakeStreamError::StreamNotFound),
+                    ));
+                }
+                if let Some((table_batch, _)) = StreamConsumer::get_offset(&client, &table.name)? {
+                    batch = batch.max(table_batch + 1);
+                }
+            }
+        }
+    }
 
     let mut consumer = StreamConsumer::new();
-    let mut iteration = 0;
     loop {
         for (idx, table) in tables.iter().enumerate() {
-            // We only check stream status on first iteration
-            if iteration == 0 {
-                match from_seq {
-                    None | Some((0, _)) => {
-                        info!("[{}][{}] Creating new stream", name, table.name);
-                        StreamConsumer::drop_stream(&client, &table.name)?;
-                        StreamConsumer::create_stream(&client, &table.name)?;
-                    }
-                    Some((lsn, seq)) => {
-                        info!(
-                            "[{}][{}] Continuing ingestion from {}/{}",
-                            name, table.name, lsn, seq
-                        );
-                        iteration = lsn;
-                        if let Ok(false) = StreamConsumer::is_stream_created(&client, &table.name) {
-                            return Err(ConnectorError::SnowflakeError(
-                                SnowflakeError::SnowflakeStreamError(
-                                    SnowflakeStreamError::StreamNotFound,
-                                ),
-                            ));
-                        }
-                    }
-                }
-            }
-
             debug!("[{}][{}] Reading from changes stream", name, table.name);
 
-            consumer.consume_stream(&stream_client, &table.name, ingestor, idx, iteration)?;
-
-            interval.tick().await;
+            if consumer.consume_stream(
+                &stream_client,
+                &table.name,
+                ingestor,
+                idx,
+                batch,
+                last_checkpoint,
+            )? {
+                batch += 1;
+            }
         }
 
-        iteration += 1;
+        interval.tick().await;
     }
 }
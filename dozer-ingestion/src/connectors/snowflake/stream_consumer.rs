@@ -5,8 +5,14 @@ use crate::ingestion::Ingestor;
 use dozer_types::ingestion_types::IngestionMessage;
 
 use crate::errors::SnowflakeStreamError::{CannotDetermineAction, UnsupportedActionInStream};
+use dozer_types::rust_decimal::prelude::ToPrimitive;
 use dozer_types::types::{Field, Operation, Record, SchemaIdentifier};
 use odbc::create_environment_v3;
+use odbc::odbc_safe::AutocommitOn;
+use odbc::Connection;
+
+/// Table recording, for each table, the last batch read from its stream.
+const OFFSETS_TABLE_NAME: &str = "dozer_ingestion_offsets";
 
 #[derive(Default)]
 pub struct StreamConsumer {}
@@ -49,6 +55,124 @@ impl StreamConsumer {
         client.exec(&conn, query)
     }
 
+    pub fn drop_temp_table(
+        client: &Client,
+        table_name: &str,
+    ) -> Result<Option<bool>, SnowflakeError> {
+        let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
+        let conn = env
+            .connect_with_connection_string(&client.get_conn_string())
+            .unwrap();
+
+        let query = format!(
+            "DROP TABLE IF EXISTS {}",
+            Self::get_stream_temp_table_name(table_name),
+        );
+
+        client.exec(&conn, query)
+    }
+
+    pub fn create_offsets_table(client: &Client) -> Result<(), ConnectorError> {
+        let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
+        let conn = env
+            .connect_with_connection_string(&client.get_conn_string())
+            .unwrap();
+
+        let query = format!(
+            "CREATE TABLE IF NOT EXISTS {OFFSETS_TABLE_NAME} (TABLE_NAME VARCHAR, BATCH INTEGER, ROW_COUNT INTEGER)"
+        );
+
+        client.exec(&conn, query)?;
+        Ok(())
+    }
+
+    /// Returns the id and the row count of the last batch read from the stream of `table_name`.
+    pub fn get_offset(
+        client: &Client,
+        table_name: &str,
+    ) -> Result<Option<(u64, u64)>, ConnectorError> {
+        let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
+        let conn = env
+            .connect_with_connection_string(&client.get_conn_string())
+            .unwrap();
+
+        Self::fetch_offset(client, &conn, table_name)
+    }
+
+    pub fn clear_offset(client: &Client, table_name: &str) -> Result<(), ConnectorError> {
+        let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
+        let conn = env
+            .connect_with_connection_string(&client.get_conn_string())
+            .unwrap();
+
+        let query = format!("DELETE FROM {OFFSETS_TABLE_NAME} WHERE TABLE_NAME = '{table_name}'");
+
+        client.exec(&conn, query)?;
+        Ok(())
+    }
+
+    /// Checks that no change of `table_name` that was read from its stream, but is not included in
+    /// `last_checkpoint`, is lost.
+    pub fn can_resume(
+        client: &Client,
+        table_name: &str,
+        last_checkpoint: (u64, u64),
+    ) -> Result<bool, ConnectorError> {
+        if !Self::is_stream_created(client, table_name)? {
+            return Ok(false);
+        }
+
+        let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
+        let conn = env
+            .connect_with_connection_string(&client.get_conn_string())
+            .unwrap();
+
+        // A batch that is still in its temp table is read again.
+        if client.table_exist(&conn, &Self::get_stream_temp_table_name(table_name))? {
+            return Ok(true);
+        }
+
+        // Otherwise the last batch was dropped once forwarded, so all of it must be in the checkpoint.
+        Ok(match Self::fetch_offset(client, &conn, table_name)? {
+            Some((batch, row_count)) if row_count > 0 => (batch, row_count - 1) <= last_checkpoint,
+            _ => true,
+        })
+    }
+
+    fn fetch_offset(
+        client: &Client,
+        conn: &Connection<AutocommitOn>,
+        table_name: &str,
+    ) -> Result<Option<(u64, u64)>, ConnectorError> {
+        let query = format!(
+            "SELECT BATCH, ROW_COUNT FROM {OFFSETS_TABLE_NAME} WHERE TABLE_NAME = '{table_name}';"
+        );
+
+        let row = client
+            .fetch(conn, query)?
+            .and_then(|(_, mut iterator)| iterator.next());
+        Ok(row.and_then(|row| Some((field_to_u64(row.get(0)?)?, field_to_u64(row.get(1)?)?))))
+    }
+
+    fn store_offset(
+        client: &Client,
+        conn: &Connection<AutocommitOn>,
+        table_name: &str,
+        batch: u64,
+        row_count: u64,
+    ) -> Result<(), ConnectorError> {
+        let query = format!(
+            "MERGE INTO {OFFSETS_TABLE_NAME} USING (SELECT '{table_name}' AS TABLE_NAME) source
+                ON {OFFSETS_TABLE_NAME}.TABLE_NAME = source.TABLE_NAME
+                WHEN MATCHED THEN UPDATE SET BATCH = {batch}, ROW_COUNT = {row_count}
+                WHEN NOT MATCHED THEN INSERT (TABLE_NAME, BATCH, ROW_COUNT)
+                    VALUES (source.TABLE_NAME, {batch}, {row_count});"
+        );
+
+        client.exec(conn, query)?;
+        Ok(())
+    }
+
     pub fn create_stream(client: &Client, table_name: &String) -> Result<(), ConnectorError> {
         let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
         let conn = env
@@ -119,14 +243,20 @@ impl StreamConsumer {
         }
     }
 
+    /// Forwards the changes of the stream of `table_name`, skipping those included in `last_checkpoint`.
+    ///
+    /// Changes are read from the stream into a temp table, recorded as batch `batch`, and the temp table is
+    /// dropped once they are forwarded. A temp table left by a previous run is forwarded first, with the id
+    /// it was recorded with. Returns whether `batch` was read from the stream.
     pub fn consume_stream(
         &mut self,
         client: &Client,
         table_name: &str,
         ingestor: &Ingestor,
         table_idx: usize,
-        iteration: u64,
-    ) -> Result<(), ConnectorError> {
+        batch: u64,
+        last_checkpoint: Option<(u64, u64)>,
+    ) -> Result<bool, ConnectorError> {
         let env = create_environment_v3().map_err(|e| e.unwrap()).unwrap();
         let conn = env
             .connect_with_connection_string(&client.get_conn_string())
@@ -136,16 +266,40 @@ impl StreamConsumer {
         let stream_name = Self::get_stream_table_name(table_name);
         let temp_table_exist = client.table_exist(&conn, &temp_table_name)?;
 
-        if !temp_table_exist {
-            let query = format!(
-                "CREATE OR REPLACE TEMP TABLE {temp_table_name} AS
-                    SELECT * FROM {stream_name} ORDER BY METADATA$ACTION;"
-            );
+        let (batch, is_new_batch) = match (
+            temp_table_exist,
+            Self::fetch_offset(client, &conn, table_name)?,
+        ) {
+            (true, Some((temp_table_batch, _))) => (temp_table_batch, false),
+            _ => {
+                // Idle streams are not consumed, so that polling them is cheap.
+                if !client.stream_has_data(&conn, &stream_name)? {
+                    return Ok(false);
+                }
 
-            client.exec(&conn, query)?;
-        }
+                // The batch is recorded before the stream is consumed, so that a temp table always
+                // has an id.
+                Self::store_offset(client, &conn, table_name, batch, 0)?;
+                let query = format!(
+                        "CREATE OR REPLACE TRANSIENT TABLE {temp_table_name} AS SELECT * FROM {stream_name};"
+                    );
+                client.exec(&conn, query)?;
+
+                let row_count = client
+                    .fetch(&conn, format!("SELECT COUNT(*) FROM {temp_table_name};"))?
+                    .and_then(|(_, mut iterator)| iterator.next())
+                    .and_then(|row| row.get(0).and_then(field_to_u64))
+                    .unwrap_or_default();
+                Self::store_offset(client, &conn, table_name, batch, row_count)?;
+                (batch, true)
+            }
+        };
 
-        let result = client.fetch(&conn, format!("SELECT * FROM {temp_table_name};"))?;
+        // Rows are read in the same order every time, so that their ids are stable.
+        let result = client.fetch(
+            &conn,
+            format!("SELECT * FROM {temp_table_name} ORDER BY METADATA$ACTION, METADATA$ROW_ID;"),
+        )?;
         if let Some((schema, iterator)) = result {
             let mut truncated_schema = schema.clone();
             truncated_schema.truncate(schema.len() - 3);
@@ -155,9 +309,14 @@ impl StreamConsumer {
             let action_idx = used_columns_for_schema;
 
             for (idx, row) in iterator.enumerate() {
+                let seq_no = idx as u64;
+                if last_checkpoint.map_or(false, |checkpoint| (batch, seq_no) <= checkpoint) {
+                    continue;
+                }
+
                 let op = Self::get_operation(row, action_idx, used_columns_for_schema, table_idx)?;
                 ingestor
-                    .handle_message(IngestionMessage::new_op(iteration, idx as u64, op))
+                    .handle_message(IngestionMessage::new_op(batch, seq_no, op))
                     .map_err(ConnectorError::IngestorError)?;
             }
         }
@@ -166,7 +325,15 @@ impl StreamConsumer {
 
         client
             .exec(&conn, query)
-            .map_err(ConnectorError::SnowflakeError)
-            .map(|_| ())
+            .map_err(ConnectorError::SnowflakeError)?;
+        Ok(is_new_batch)
+    }
+}
+
+fn field_to_u64(field: &Field) -> Option<u64> {
+    match field {
+        Field::Int(value) => u64::try_from(*value).ok(),
+        Field::Decimal(value) => value.to_u64(),
+        _ => None,
     }
 }
@@ -59,7 +59,7 @@ fn test_disabled_connector_and_read_from_stream() {
             .unwrap();
 
         thread::spawn(move || {
-            let _ = connector.start(&ingestor, tables);
+            let _ = connector.start(&ingestor, tables, None);
         });
 
         let mut i = 0;
@@ -10,15 +10,18 @@ use dozer_types::{rust_decimal, thiserror};
 use base64::DecodeError;
 
 use deltalake::datafusion::error::DataFusionError;
+use deltalake::parquet::errors::ParquetError;
 use deltalake::DeltaTableError;
 #[cfg(feature = "snowflake")]
 use std::num::TryFromIntError;
+use std::path::PathBuf;
 use std::str::Utf8Error;
 use std::string::FromUtf8Error;
 
 use dozer_types::log::error;
 #[cfg(feature = "snowflake")]
 use odbc::DiagnosticRecord;
+use rdkafka::error::KafkaError;
 use schema_registry_converter::error::SRCError;
 use tokio_postgres::Error;
 
@@ -51,6 +54,9 @@ pub enum ConnectorError {
     #[error(transparent)]
     PostgresConnectorError(#[from] PostgresConnectorError),
 
+    #[error(transparent)]
+    MySQLConnectorError(#[from] MySQLConnectorError),
+
     #[cfg(feature = "snowflake")]
     #[error(transparent)]
     SnowflakeError(#[from] SnowflakeError),
@@ -58,6 +64,9 @@ pub enum ConnectorError {
     #[error(transparent)]
     DebeziumError(#[from] DebeziumError),
 
+    #[error(transparent)]
+    KafkaConnectorError(#[from] KafkaConnectorError),
+
     #[error(transparent)]
     ObjectStoreConnectorError(#[from] ObjectStoreConnectorError),
 
@@ -82,6 +91,9 @@ pub enum ConnectorError {
     #[error("Delta table error: {0}")]
     DeltaTableError(#[from] DeltaTableError),
 
+    #[error(transparent)]
+    DeltaLakeError(#[from] DeltaLakeError),
+
     #[error("Datafusion error: {0}")]
     DataFusionError(#[from] DataFusionError),
 
@@ -199,6 +211,72 @@ pub enum PostgresConnectorError {
 
     #[error("Failed to send message on snapshot read channel")]
     SnapshotReadError,
+
+    #[error("Unsupported sslmode \"{0}\", expected one of disable, prefer, require, verify-ca and verify-full")]
+    UnsupportedSslMode(String),
+
+    #[error("Failed to read root certificate {0}: {1}")]
+    RootCertReadError(String, #[source] std::io::Error),
+
+    #[error("Failed to set up TLS: {0}")]
+    TlsError(#[source] native_tls::Error),
+
+    #[error("Publication {0} doesn't exist, it must be created beforehand when reuse_publication is set")]
+    PublicationNotFound(String),
+
+    #[error("Failed to drop replication slot {0}: {1}")]
+    DropSlotError(String, #[source] Error),
+}
+
+#[derive(Error, Debug)]
+pub enum MySQLConnectorError {
+    #[error("Failed to connect to mysql with the specified configuration. {0}")]
+    ConnectionFailure(#[source] mysql_async::Error),
+
+    #[error("Query failed in connector: {0}: {1}")]
+    QueryExecutionError(String, #[source] mysql_async::Error),
+
+    #[error("Server variable {0} must be {1}, but it is {2}")]
+    UnsupportedServerSetting(String, String, String),
+
+    #[error("Column type {1} of column {0} is not supported")]
+    ColumnTypeNotSupported(String, String),
+
+    #[error("Cannot find column {0} in table {1}")]
+    ColumnNotFound(String, String),
+
+    #[error("Failed to convert value of column {0}: {1}")]
+    ValueConversionError(String, String),
+
+    #[error("Binary logging is not enabled on the server")]
+    BinlogNotEnabled,
+
+    #[error("Unexpected binlog file name {0}")]
+    BinlogFileNameError(String),
+
+    #[error("Binlog file {0} to continue ingestion from was purged")]
+    BinlogFileNotFound(u64),
+
+    #[error("Binlog stream error: {0}")]
+    BinlogError(#[source] mysql_async::Error),
+
+    #[error("Failed to read binlog event: {0}")]
+    BinlogReadError(#[source] std::io::Error),
+
+    #[error("Binlog stream ended")]
+    BinlogStreamEnded,
+
+    #[error("Invalid GTID or GTID set {0}")]
+    InvalidGtid(String),
+
+    #[error("Failed to read GTID checkpoint {0:?}: {1}")]
+    GtidStateReadFailed(PathBuf, #[source] std::io::Error),
+
+    #[error("Failed to write GTID checkpoint {0:?}: {1}")]
+    GtidStateWriteFailed(PathBuf, #[source] std::io::Error),
+
+    #[error("Invalid GTID checkpoint {0:?}: {1}")]
+    InvalidGtidState(PathBuf, #[source] serde_json::Error),
 }
 
 #[derive(Error, Debug, Eq, PartialEq)]
@@ -308,7 +386,7 @@ pub enum DebeziumError {
     DebeziumSchemaError(#[from] DebeziumSchemaError),
 
     #[error("Connection error")]
-    DebeziumConnectionError(#[source] kafka::Error),
+    DebeziumConnectionError(#[source] KafkaError),
 
     #[error("JSON decode error")]
     JsonDecodeError(#[source] serde_json::Error),
@@ -329,13 +407,76 @@ pub enum DebeziumError {
 #[derive(Error, Debug)]
 pub enum DebeziumStreamError {
     #[error("Consume commit error")]
-    ConsumeCommitError(#[source] kafka::Error),
+    ConsumeCommitError(#[source] KafkaError),
 
     #[error("Message consume error")]
-    MessageConsumeError(#[source] kafka::Error),
+    MessageConsumeError(#[source] KafkaError),
 
     #[error("Polling error")]
-    PollingError(#[source] kafka::Error),
+    PollingError(#[source] KafkaError),
+}
+
+#[derive(Error, Debug)]
+pub enum KafkaConnectorError {
+    #[error("Failed to create kafka consumer: {0}")]
+    ConsumerCreationError(#[source] KafkaError),
+
+    #[error("Failed to subscribe to topics: {0}")]
+    SubscriptionError(#[source] KafkaError),
+
+    #[error("Failed to fetch topic metadata: {0}")]
+    MetadataError(#[source] KafkaError),
+
+    #[error("Topic {0} not found")]
+    TopicNotFound(String),
+
+    #[error("Failed to assign partitions: {0}")]
+    AssignmentError(#[source] KafkaError),
+
+    #[error("Polling error: {0}")]
+    PollingError(#[source] KafkaError),
+
+    #[error("Failed to commit offset: {0}")]
+    OffsetCommitError(#[source] KafkaError),
+
+    #[error("Failed to fetch committed offsets: {0}")]
+    OffsetFetchError(#[source] KafkaError),
+
+    #[error("Unsupported security protocol \"{0}\", expected one of plaintext, ssl, sasl_plaintext and sasl_ssl")]
+    UnsupportedSecurityProtocol(String),
+
+    #[error("Unsupported format \"{1}\" of topic {0}, expected json or avro")]
+    UnsupportedFormat(String, String),
+
+    #[error("Topic {0} has the avro format, which needs `schema_registry_url`")]
+    SchemaRegistryNotDefined(String),
+
+    #[error("Topic {0} is not declared in `topics`")]
+    TopicNotDeclared(String),
+
+    #[error("Unsupported type \"{1}\" of field {0}")]
+    FieldTypeNotSupported(String, String),
+
+    #[error("Primary key column {1} is not a field of topic {0}")]
+    PrimaryKeyNotFound(String, String),
+
+    #[error("Failed to decode json message of topic {0}: {1}")]
+    JsonDecodeError(String, #[source] serde_json::Error),
+
+    #[error("Failed to decode avro message of topic {0}: {1}")]
+    AvroDecodeError(String, #[source] SRCError),
+
+    #[error("Message of topic {0} is not an object")]
+    MessageNotAnObject(String),
+
+    #[error("Tombstone of topic {0} has no key")]
+    TombstoneWithoutKey(String),
+
+    #[error("Field {0} is null but not nullable")]
+    NullValue(String),
+
+    #[error("Failed to convert value of field {0}: {1}")]
+    ValueConversionError(String, String),
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -370,6 +511,24 @@ pub enum DebeziumSchemaError {
     InvalidTimestampError,
 }
 
+#[derive(Error, Debug)]
+pub enum DeltaLakeError {
+    #[error("Tailing partitioned delta table {0} is not supported")]
+    PartitionedTableNotSupported(String),
+
+    #[error("Failed to read file {0} of the delta table: {1}")]
+    FileReadError(String, #[source] object_store::Error),
+
+    #[error("Failed to parse parquet file {0} of the delta table: {1}")]
+    ParquetError(String, #[source] ParquetError),
+
+    #[error("Column {0} not found in file {1} of the delta table")]
+    ColumnNotFound(String, String),
+
+    #[error("Unknown change type {0} in the change data feed")]
+    UnknownChangeType(String),
+}
+
 #[derive(Error, Debug)]
 pub enum ObjectStoreConnectorError {
     #[error(transparent)]
@@ -389,6 +548,27 @@ pub enum ObjectStoreConnectorError {
 
     #[error(transparent)]
     IngestorError(#[from] IngestorError),
+
+    #[error(transparent)]
+    WatchError(#[from] ObjectStoreWatchError),
+}
+
+#[derive(Error, Debug)]
+pub enum ObjectStoreWatchError {
+    #[error("Failed to list objects: {0}")]
+    ListingFailed(#[source] object_store::Error),
+
+    #[error("File of transaction {0} of the checkpoint is not recorded or no longer listed")]
+    CheckpointFileMissing(u64),
+
+    #[error("Failed to read the consumed files from {0:?}: {1}")]
+    StateReadFailed(PathBuf, #[source] std::io::Error),
+
+    #[error("Failed to write the consumed files to {0:?}: {1}")]
+    StateWriteFailed(PathBuf, #[source] std::io::Error),
+
+    #[error("Invalid consumed file in {0:?}: {1}")]
+    InvalidState(PathBuf, #[source] serde_json::Error),
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -422,6 +602,9 @@ pub enum ObjectStoreObjectError {
 
     #[error("File format unsupported: {0}")]
     FileFormatUnsupportedError(String),
+
+    #[error("Invalid {0} format option: {1}")]
+    InvalidFormatOption(String, String),
 }
 
 #[derive(Error, Debug)]
@@ -19,4 +19,13 @@ fn test_postgres() {
     run_test_suite_basic_cud::<test_suite::PostgresConnectorTest>();
 }
 
+#[test]
+fn test_mysql() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    run_test_suite_basic_data_ready::<test_suite::MySQLConnectorTest>();
+    run_test_suite_basic_insert_only::<test_suite::MySQLConnectorTest>();
+    run_test_suite_basic_cud::<test_suite::MySQLConnectorTest>();
+}
+
 mod test_suite;
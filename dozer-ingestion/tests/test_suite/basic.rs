@@ -40,7 +40,7 @@ pub fn run_test_suite_basic_data_ready<T: DataReadyConnectorTest>() {
     // Run connector.
     let (ingestor, mut iterator) = Ingestor::initialize_channel(Default::default());
     std::thread::spawn(move || {
-        if let Err(e) = connector.start(&ingestor, tables) {
+        if let Err(e) = connector.start(&ingestor, tables, None) {
             error!("Connector `start` returned error: {e}");
         }
     });
@@ -141,7 +141,7 @@ pub fn run_test_suite_basic_insert_only<T: InsertOnlyConnectorTest>() {
         // Run the connector and check data is ingested.
         let (ingestor, mut iterator) = Ingestor::initialize_channel(Default::default());
         std::thread::spawn(move || {
-            if let Err(e) = connector.start(&ingestor, tables) {
+            if let Err(e) = connector.start(&ingestor, tables, None) {
                 error!("Connector `start` returned error: {e}")
             }
         });
@@ -217,7 +217,7 @@ pub fn run_test_suite_basic_cud<T: CudConnectorTest>() {
     // Run the connector.
     let (ingestor, mut iterator) = Ingestor::initialize_channel(Default::default());
     std::thread::spawn(move || {
-        if let Err(e) = connector.start(&ingestor, vec![]) {
+        if let Err(e) = connector.start(&ingestor, vec![], None) {
             error!("Connector `start` returned error: {e}")
         }
     });
@@ -297,6 +297,7 @@ fn assert_record_matches_schema(record: &Record, schema: &Schema, only_match_pk:
             FieldType::Date => assert!(value.as_date().is_some()),
             FieldType::Bson => assert!(value.as_bson().is_some()),
             FieldType::Point => assert!(value.as_point().is_some()),
+            FieldType::Interval => assert!(value.as_interval().is_some()),
         }
     }
 }
@@ -1,7 +1,9 @@
 mod arrow;
+mod mysql;
 mod object_store;
 mod postgres;
 mod sql;
 
+pub use self::mysql::MySQLConnectorTest;
 pub use self::object_store::LocalStorageObjectStoreConnectorTest;
 pub use self::postgres::PostgresConnectorTest;
@@ -0,0 +1,368 @@
+use dozer_ingestion::connectors::mysql::connector::MySQLConnector;
+use dozer_types::ingestion_types::MySQLConfig;
+use dozer_types::types::{Field, FieldDefinition, FieldType};
+use dozer_utils::{process::run_docker_compose, Cleanup};
+use mysql_async::prelude::Queryable;
+use mysql_async::{Conn, Opts, OptsBuilder, Value};
+use tempdir::TempDir;
+use tokio::runtime::Runtime;
+
+use crate::test_suite::{
+    records::Operation, CudConnectorTest, DataReadyConnectorTest, FieldsAndPk,
+    InsertOnlyConnectorTest,
+};
+
+pub struct MySQLConnectorTest {
+    opts: Opts,
+    schema_name: Option<String>,
+    table_name: String,
+    schema: FieldsAndPk,
+    _cleanup: Cleanup,
+    _temp_dir: TempDir,
+}
+
+impl DataReadyConnectorTest for MySQLConnectorTest {
+    type Connector = MySQLConnector;
+
+    fn new() -> (Self, Self::Connector) {
+        let (connector_test, connector) = create_mysql_server();
+        connector_test.execute(vec![(
+            CREATE_TABLE_WITH_ALL_SUPPORTED_DATA_TYPES.to_string(),
+            vec![],
+        )]);
+        (connector_test, connector)
+    }
+}
+
+impl InsertOnlyConnectorTest for MySQLConnectorTest {
+    type Connector = MySQLConnector;
+
+    fn new(
+        schema_name: Option<String>,
+        table_name: String,
+        schema: FieldsAndPk,
+        records: Vec<Vec<Field>>,
+    ) -> Option<(Self, Self::Connector, FieldsAndPk)> {
+        let (mut connector_test, connector) = create_mysql_server();
+
+        let (actual_schema, columns) = schema_to_sql(schema.clone());
+        let full_table_name = full_table_name(schema_name.as_deref(), &table_name);
+
+        let mut statements = vec![];
+        if let Some(schema_name) = &schema_name {
+            statements.push((format!("CREATE DATABASE `{schema_name}`"), vec![]));
+        }
+        statements.push((
+            format!("CREATE TABLE {full_table_name} ({columns})"),
+            vec![],
+        ));
+        for record in records {
+            statements.push(insert_record(&full_table_name, &record, &schema.0));
+        }
+        connector_test.execute(statements);
+
+        connector_test.schema_name = schema_name;
+        connector_test.table_name = table_name;
+        connector_test.schema = schema;
+
+        Some((connector_test, connector, actual_schema))
+    }
+}
+
+impl CudConnectorTest for MySQLConnectorTest {
+    fn start_cud(&self, operations: Vec<Operation>) {
+        let full_table_name = full_table_name(self.schema_name.as_deref(), &self.table_name);
+        let statements = operations
+            .iter()
+            .map(|operation| operation_to_sql(&full_table_name, operation, &self.schema))
+            .collect();
+        let opts = self.opts.clone();
+        std::thread::spawn(move || execute(opts, statements));
+    }
+}
+
+impl MySQLConnectorTest {
+    fn execute(&self, statements: Vec<(String, Vec<Value>)>) {
+        execute(self.opts.clone(), statements);
+    }
+}
+
+fn execute(opts: Opts, statements: Vec<(String, Vec<Value>)>) {
+    Runtime::new().unwrap().block_on(async move {
+        let mut conn = Conn::new(opts).await.unwrap();
+        for (statement, params) in statements {
+            let result = if params.is_empty() {
+                conn.query_drop(&statement).await
+            } else {
+                conn.exec_drop(&statement, params).await
+            };
+            result.unwrap_or_else(|e| panic!("Failed to execute {statement}: {e}"));
+        }
+        conn.disconnect().await.unwrap();
+    });
+}
+
+fn create_mysql_server() -> (MySQLConnectorTest, MySQLConnector) {
+    let host = "localhost";
+    let port = 3306;
+    let user = "root";
+    let password = "mysql";
+    let database = "dozer_test";
+
+    let temp_dir = TempDir::new("mysql").expect("Failed to create temp dir");
+    let docker_compose_path = temp_dir.path().join("docker-compose.yaml");
+    std::fs::write(&docker_compose_path, DOCKER_COMPOSE_YAML)
+        .expect("Failed to write docker compose file");
+    let cleanup = run_docker_compose(&docker_compose_path, "dozer-wait-for-connections-healthy");
+
+    let connector = MySQLConnector::new(
+        "mysql_connector_test".to_string(),
+        MySQLConfig {
+            host: host.to_string(),
+            port,
+            user: user.to_string(),
+            password: password.to_string(),
+            database: database.to_string(),
+            server_id: None,
+        },
+    );
+
+    let opts = OptsBuilder::default()
+        .ip_or_hostname(host)
+        .tcp_port(port as u16)
+        .user(Some(user))
+        .pass(Some(password))
+        .db_name(Some(database))
+        .into();
+
+    (
+        MySQLConnectorTest {
+            opts,
+            schema_name: Default::default(),
+            table_name: Default::default(),
+            schema: Default::default(),
+            _cleanup: cleanup,
+            _temp_dir: temp_dir,
+        },
+        connector,
+    )
+}
+
+fn field_type_to_sql(field_type: FieldType) -> Option<&'static str> {
+    match field_type {
+        FieldType::UInt => Some("BIGINT UNSIGNED"),
+        FieldType::Int => Some("BIGINT"),
+        FieldType::Float => Some("DOUBLE"),
+        FieldType::Boolean => Some("BOOLEAN"),
+        FieldType::String => Some("VARCHAR(255)"),
+        FieldType::Text => Some("TEXT"),
+        FieldType::Binary => Some("VARBINARY(255)"),
+        FieldType::Decimal => Some("DECIMAL(38, 10)"),
+        FieldType::Timestamp => Some("DATETIME(6)"),
+        FieldType::Date => Some("DATE"),
+        FieldType::Bson => Some("JSON"),
+        FieldType::Point => None,
+        FieldType::Interval => None,
+    }
+}
+
+fn schema_to_sql((fields, primary_index): FieldsAndPk) -> (FieldsAndPk, String) {
+    let mut actual_fields = vec![];
+    let mut actual_primary_index = vec![];
+
+    let mut columns = vec![];
+    for (index, field) in fields.into_iter().enumerate() {
+        let Some(typ) = field_type_to_sql(field.typ) else {
+            continue;
+        };
+        if primary_index.contains(&index) {
+            actual_primary_index.push(actual_fields.len());
+        }
+        let nullable = if field.nullable { "" } else { " NOT NULL" };
+        columns.push(format!("`{}` {typ}{nullable}", field.name));
+        actual_fields.push(field);
+    }
+
+    let primary_key = actual_primary_index
+        .iter()
+        .map(|index| format!("`{}`", actual_fields[*index].name))
+        .collect::<Vec<_>>();
+    if !primary_key.is_empty() {
+        columns.push(format!("PRIMARY KEY ({})", primary_key.join(", ")));
+    }
+    ((actual_fields, actual_primary_index), columns.join(", "))
+}
+
+fn full_table_name(schema_name: Option<&str>, table_name: &str) -> String {
+    match schema_name {
+        Some(schema_name) => format!("`{schema_name}`.`{table_name}`"),
+        None => format!("`{table_name}`"),
+    }
+}
+
+fn field_to_value(field: &Field) -> Value {
+    match field {
+        Field::UInt(u) => Value::UInt(*u),
+        Field::Int(i) => Value::Int(*i),
+        Field::Float(f) => Value::Double(f.0),
+        Field::Boolean(b) => Value::Int(*b as i64),
+        Field::String(s) | Field::Text(s) => Value::Bytes(s.as_bytes().to_vec()),
+        Field::Binary(b) => Value::Bytes(b.clone()),
+        Field::Decimal(d) => Value::Bytes(d.to_string().into_bytes()),
+        Field::Timestamp(t) => Value::Bytes(
+            t.naive_utc()
+                .format("%Y-%m-%d %H:%M:%S%.6f")
+                .to_string()
+                .into_bytes(),
+        ),
+        Field::Date(d) => Value::Bytes(d.to_string().into_bytes()),
+        Field::Bson(b) => {
+            let json = bson::from_slice::<dozer_types::serde_json::Value>(b).unwrap();
+            Value::Bytes(json.to_string().into_bytes())
+        }
+        Field::Point(_) | Field::Interval(_) => unreachable!("Not supported by MySQL"),
+        Field::Null => Value::NULL,
+    }
+}
+
+/// Values of the fields that are supported, with their names.
+fn supported_values<'a>(
+    record: &'a [Field],
+    fields: &'a [FieldDefinition],
+) -> impl Iterator<Item = (usize, &'a str, Value)> {
+    record
+        .iter()
+        .zip(fields)
+        .enumerate()
+        .filter(|(_, (_, field))| field_type_to_sql(field.typ).is_some())
+        .map(|(index, (value, field))| (index, field.name.as_str(), field_to_value(value)))
+}
+
+fn insert_record(
+    full_table_name: &str,
+    record: &[Field],
+    fields: &[FieldDefinition],
+) -> (String, Vec<Value>) {
+    let (columns, values): (Vec<_>, Vec<_>) = supported_values(record, fields)
+        .map(|(_, name, value)| (format!("`{name}`"), value))
+        .unzip();
+    let placeholders = vec!["?"; values.len()];
+    (
+        format!(
+            "INSERT INTO {full_table_name} ({}) VALUES ({})",
+            columns.join(", "),
+            placeholders.join(", ")
+        ),
+        values,
+    )
+}
+
+fn operation_to_sql(
+    full_table_name: &str,
+    operation: &Operation,
+    (fields, primary_index): &FieldsAndPk,
+) -> (String, Vec<Value>) {
+    match operation {
+        Operation::Insert { new } => insert_record(full_table_name, new, fields),
+        Operation::Update { old, new } => {
+            let (set, mut values): (Vec<_>, Vec<_>) = supported_values(new, fields)
+                .filter(|(index, _, _)| !primary_index.contains(index))
+                .map(|(_, name, value)| (format!("`{name}` = ?"), value))
+                .unzip();
+            let (where_, where_values): (Vec<_>, Vec<_>) = supported_values(old, fields)
+                .filter(|(index, _, _)| primary_index.contains(index))
+                .map(|(_, name, value)| (format!("`{name}` = ?"), value))
+                .unzip();
+            values.extend(where_values);
+            (
+                format!(
+                    "UPDATE {full_table_name} SET {} WHERE {}",
+                    set.join(", "),
+                    where_.join(" AND ")
+                ),
+                values,
+            )
+        }
+        Operation::Delete { old } => {
+            let (where_, values): (Vec<_>, Vec<_>) = supported_values(old, fields)
+                .filter(|(index, _, _)| primary_index.contains(index))
+                .map(|(_, name, value)| (format!("`{name}` = ?"), value))
+                .unzip();
+            (
+                format!(
+                    "DELETE FROM {full_table_name} WHERE {}",
+                    where_.join(" AND ")
+                ),
+                values,
+            )
+        }
+    }
+}
+
+const CREATE_TABLE_WITH_ALL_SUPPORTED_DATA_TYPES: &str = r#"
+    CREATE TABLE test_table (
+        id BIGINT UNSIGNED NOT NULL PRIMARY KEY,
+        `boolean` BOOLEAN NOT NULL,
+        `boolean_null` BOOLEAN,
+        `tinyint` TINYINT NOT NULL,
+        `int_unsigned` INT UNSIGNED NOT NULL,
+        `bigint` BIGINT NOT NULL,
+        `bigint_null` BIGINT,
+        `year` YEAR NOT NULL,
+        `double` DOUBLE NOT NULL,
+        `double_null` DOUBLE,
+        `decimal` DECIMAL(10, 2) NOT NULL,
+        `decimal_null` DECIMAL(10, 2),
+        `char` CHAR(1) NOT NULL,
+        `varchar` VARCHAR(255) NOT NULL,
+        `varchar_null` VARCHAR(255),
+        `enum` ENUM('small', 'large') NOT NULL,
+        `text` TEXT NOT NULL,
+        `text_null` TEXT,
+        `varbinary` VARBINARY(255) NOT NULL,
+        `blob_null` BLOB,
+        `date` DATE NOT NULL,
+        `date_null` DATE,
+        `datetime` DATETIME(6) NOT NULL,
+        `datetime_null` DATETIME(6),
+        `timestamp` TIMESTAMP NOT NULL,
+        `json` JSON NOT NULL,
+        `json_null` JSON
+    );
+    INSERT INTO test_table VALUES (
+        0, false, false, 0, 0, 0, 0, 1970, 0.0, 0.0, 0, 0, '', '', '', 'small', '', '', '', '',
+        '1970-01-01', '1970-01-01', '1970-01-01 00:00:00', '1970-01-01 00:00:00',
+        '1970-01-01 00:00:01', '{}', '{}'
+    );
+    INSERT INTO test_table VALUES (
+        1, true, null, -1, 4294967295, 1, null, 2023, 1.0, null, 1.5, null, '1', '1', null,
+        'large', '1', null, '1', null, '2023-03-01', null, '2023-03-01 10:20:30.5', null,
+        '2023-03-01 10:20:30', '{ "1": 1 }', null
+    );
+"#;
+
+const DOCKER_COMPOSE_YAML: &str = r#"version: '2.4'
+services:
+  mysql:
+    container_name: mysql
+    image: mysql:8.0
+    ports:
+    - target: 3306
+      published: 3306
+    environment:
+    - MYSQL_DATABASE=dozer_test
+    - MYSQL_ROOT_PASSWORD=mysql
+    healthcheck:
+      test:
+      - CMD-SHELL
+      - mysqladmin ping -h 127.0.0.1 -u root -pmysql
+      interval: 5s
+      timeout: 5s
+      retries: 10
+  dozer-wait-for-connections-healthy:
+    image: alpine
+    command: echo 'All connections are healthy'
+    depends_on:
+      mysql:
+        condition: service_healthy
+"#;
@@ -91,6 +91,8 @@ fn create_connector(
             prefix,
             file_type: "parquet".to_string(),
             extension: ".parquet".to_string(),
+            watch: None,
+            format_options: None,
         }],
     };
     let connector = ObjectStoreConnector::new(0, local_storage);
@@ -119,6 +119,7 @@ fn create_postgres_server() -> (postgres::Client, PostgresConnectorTest, Postgre
     let connector = PostgresConnector::new(PostgresConfig {
         name: "postgres_connector_test".to_string(),
         config: config.clone(),
+        ..Default::default()
     });
 
     let config = postgres::Config::from(config);
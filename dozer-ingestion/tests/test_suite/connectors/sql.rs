@@ -140,6 +140,7 @@ fn field_type_to_sql(field_type: FieldType) -> Option<String> {
         FieldType::Date => Some("DATE".to_string()),
         FieldType::Bson => Some("JSONB".to_string()),
         FieldType::Point => Some("POINT".to_string()),
+        FieldType::Interval => None,
     }
 }
 
@@ -211,6 +212,7 @@ fn field_to_sql(field: &Field) -> String {
             format!("'{}'::json", json)
         }
         Field::Point(p) => format!("'({},{})'", p.0.x(), p.0.y()),
+        Field::Interval(i) => format!("'{}'", i),
         Field::Null => "NULL".to_string(),
     }
 }
@@ -41,4 +41,6 @@ mod records;
 pub use basic::{
     run_test_suite_basic_cud, run_test_suite_basic_data_ready, run_test_suite_basic_insert_only,
 };
-pub use connectors::{LocalStorageObjectStoreConnectorTest, PostgresConnectorTest};
+pub use connectors::{
+    LocalStorageObjectStoreConnectorTest, MySQLConnectorTest, PostgresConnectorTest,
+};
@@ -70,6 +70,8 @@ pub fn generate_connection(connection_name: &str) -> Connection {
                 schema: "schema".to_owned(),
                 warehouse: "warehouse".to_owned(),
                 driver: Some("SnowflakeDSIIDriver".to_owned()),
+                poll_interval_seconds: None,
+                stream_warehouse: None,
             };
             let connection: Connection = Connection {
                 name: "snowflake".to_owned(),
@@ -104,6 +106,7 @@ pub fn generate_connection(connection_name: &str) -> Connection {
                 host: "localhost".to_owned(),
                 port: 5432,
                 database: "users".to_owned(),
+                ..Default::default()
             };
             let connection: Connection = Connection {
                 name: "postgres".to_owned(),
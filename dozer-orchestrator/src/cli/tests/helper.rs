@@ -171,6 +171,7 @@ pub fn test_api_endpoint() -> ApiEndpoint {
         sql: "select id, email, phone from users where 1=1;".to_owned(),
         index: Some(dozer_types::models::api_endpoint::ApiIndex {
             primary_key: vec!["id".to_owned()],
+            secondary: None,
         }),
         ..Default::default()
     }
@@ -10,7 +10,8 @@ use dozer_ingestion::connectors::{get_connector, get_connector_info_table};
 use dozer_sql::pipeline::builder::{OutputNodeInfo, QueryContext, SchemaSQLContext};
 
 use dozer_core::app::App;
-use dozer_sql::pipeline::builder::statement_to_pipeline;
+use dozer_sql::pipeline::builder::{statement_to_pipeline, statement_to_pipeline_with_state};
+use dozer_sql::pipeline::state::StateOptions;
 use dozer_types::models::api_endpoint::ApiEndpoint;
 use dozer_types::models::connection::Connection;
 use dozer_types::models::source::Source;
@@ -45,6 +46,7 @@ pub struct PipelineBuilder<'a> {
     sql: Option<&'a str>,
     api_endpoints: &'a [ApiEndpoint],
     pipeline_dir: &'a Path,
+    state_options: StateOptions,
     progress: MultiProgress,
 }
 impl<'a> PipelineBuilder<'a> {
@@ -54,6 +56,7 @@ impl<'a> PipelineBuilder<'a> {
         sql: Option<&'a str>,
         api_endpoints: &'a [ApiEndpoint],
         pipeline_dir: &'a Path,
+        state_options: StateOptions,
     ) -> Self {
         Self {
             connections,
@@ -61,6 +64,7 @@ impl<'a> PipelineBuilder<'a> {
             sql,
             api_endpoints,
             pipeline_dir,
+            state_options,
             progress: MultiProgress::new(),
         }
     }
@@ -212,8 +216,13 @@ impl<'a> PipelineBuilder<'a> {
         }
 
         if let Some(sql) = &self.sql {
-            let query_context = statement_to_pipeline(sql, &mut pipeline, None)
-                .map_err(OrchestrationError::PipelineError)?;
+            let query_context = statement_to_pipeline_with_state(
+                sql,
+                &mut pipeline,
+                None,
+                self.state_options.clone(),
+            )
+            .map_err(OrchestrationError::PipelineError)?;
 
             for (name, table_info) in query_context.output_tables_map {
                 if available_output_tables.contains_key(name.as_str()) {
@@ -224,7 +233,11 @@ impl<'a> PipelineBuilder<'a> {
             }
         }
 
-        let source_builder = SourceBuilder::new(grouped_connections, Some(&self.progress));
+        let source_builder = SourceBuilder::new(
+            grouped_connections,
+            self.pipeline_dir.join("connectors"),
+            Some(&self.progress),
+        );
 
         let conn_ports = source_builder.get_ports();
 
@@ -14,6 +14,7 @@ use dozer_types::parking_lot::Mutex;
 use dozer_types::tracing::{span, Level};
 use dozer_types::types::{Operation, Schema, SchemaIdentifier, SourceDefinition};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::thread;
 
 fn attach_progress(multi_pb: Option<MultiProgress>) -> ProgressBar {
@@ -68,12 +69,14 @@ impl ConnectorSourceFactory {
     pub fn new(
         table_and_ports: Vec<(TableInfo, PortHandle)>,
         connection: Connection,
+        state_dir: PathBuf,
         progress: Option<MultiProgress>,
     ) -> Result<Self, ExecutionError> {
         let connection_name = connection.name.clone();
 
-        let connector =
+        let mut connector =
             get_connector(connection).map_err(|e| ExecutionError::ConnectorError(Box::new(e)))?;
+        connector.set_state_dir(state_dir);
         let tables: Vec<TableInfo> = table_and_ports
             .iter()
             .map(|(table, _)| table.clone())
@@ -128,12 +131,7 @@ impl SourceFactory<SchemaSQLContext> for ConnectorSourceFactory {
         let table_name = &table.name;
 
         // Add source information to the schema.
-        for field in &mut schema.fields {
-            field.source = SourceDefinition::Table {
-                connection: self.connection_name.clone(),
-                name: table_name.clone(),
-            };
-        }
+        add_source_definition(&mut schema, &self.connection_name, table_name);
 
         use std::println as info;
         info!("Source: Initializing input schema: {table_name}");
@@ -159,9 +157,11 @@ impl SourceFactory<SchemaSQLContext> for ConnectorSourceFactory {
         let (ingestor, iterator) = Ingestor::initialize_channel(IngestionConfig::default());
 
         let mut schema_port_map = HashMap::new();
+        let mut port_table_names = HashMap::new();
         for table in &self.tables {
             let schema_id = get_schema_id(table.schema.identifier)?;
             schema_port_map.insert(schema_id, table.port);
+            port_table_names.insert(table.port, table.name.clone());
         }
 
         let tables = self
@@ -191,6 +191,7 @@ impl SourceFactory<SchemaSQLContext> for ConnectorSourceFactory {
             ingestor,
             iterator: Mutex::new(iterator),
             schema_port_map,
+            port_table_names,
             tables,
             connector,
             connection_name: self.connection_name.clone(),
@@ -204,6 +205,7 @@ pub struct ConnectorSource {
     ingestor: Ingestor,
     iterator: Mutex<IngestionIterator>,
     schema_port_map: HashMap<u32, PortHandle>,
+    port_table_names: HashMap<PortHandle, String>,
     tables: Vec<TableInfo>,
     connector: Box<dyn Connector>,
     connection_name: String,
@@ -211,19 +213,24 @@ pub struct ConnectorSource {
 }
 
 impl Source for ConnectorSource {
-    fn can_start_from(&self, _last_checkpoint: (u64, u64)) -> Result<bool, ExecutionError> {
-        Ok(false)
+    fn can_start_from(&self, last_checkpoint: (u64, u64)) -> Result<bool, ExecutionError> {
+        self.connector
+            .can_start_from(&self.tables, last_checkpoint)
+            .map_err(|e| ExecutionError::ConnectorError(Box::new(e)))
     }
 
     fn start(
         &self,
         fw: &mut dyn SourceChannelForwarder,
-        _last_checkpoint: Option<(u64, u64)>,
+        last_checkpoint: Option<(u64, u64)>,
     ) -> Result<(), ExecutionError> {
         thread::scope(|scope| {
             let mut counter = HashMap::new();
             let t = scope.spawn(|| {
-                match self.connector.start(&self.ingestor, self.tables.clone()) {
+                match self
+                    .connector
+                    .start(&self.ingestor, self.tables.clone(), last_checkpoint)
+                {
                     Ok(_) => {}
                     // If we get a channel error, it means the source sender thread has quit.
                     // Any error handling is done in that thread.
@@ -234,7 +241,11 @@ impl Source for ConnectorSource {
 
             let mut iterator = self.iterator.lock();
 
-            for IngestionMessage { identifier, kind } in iterator.by_ref() {
+            for IngestionMessage {
+                identifier,
+                mut kind,
+            } in iterator.by_ref()
+            {
                 let span = span!(
                     Level::TRACE,
                     "pipeline_source_start",
@@ -255,6 +266,9 @@ impl Source for ConnectorSource {
                         Some(get_schema_id(new.schema_id)?)
                     }
                     IngestionMessageKind::SnapshottingDone => None,
+                    IngestionMessageKind::SchemaChanged(schema) => {
+                        Some(get_schema_id(schema.identifier)?)
+                    }
                 };
                 if let Some(schema_id) = schema_id {
                     let port =
@@ -264,6 +278,18 @@ impl Source for ConnectorSource {
                                 schema_id,
                             )))?;
 
+                    if let IngestionMessageKind::SchemaChanged(schema) = &mut kind {
+                        info!(
+                            "[{}] Schema of table {} changed",
+                            self.connection_name, self.port_table_names[port]
+                        );
+                        add_source_definition(
+                            schema,
+                            &self.connection_name,
+                            &self.port_table_names[port],
+                        );
+                    }
+
                     counter
                         .entry(schema_id)
                         .and_modify(|e| *e += 1)
@@ -299,6 +325,21 @@ impl Source for ConnectorSource {
             Ok(())
         })
     }
+
+    fn on_checkpoint(&self, checkpoint: (u64, u64)) -> Result<(), ExecutionError> {
+        self.connector
+            .on_checkpoint(checkpoint)
+            .map_err(|e| ExecutionError::ConnectorError(Box::new(e)))
+    }
+}
+
+fn add_source_definition(schema: &mut Schema, connection_name: &str, table_name: &str) {
+    for field in &mut schema.fields {
+        field.source = SourceDefinition::Table {
+            connection: connection_name.to_string(),
+            name: table_name.to_string(),
+        };
+    }
 }
 
 fn get_schema_id(op_schema_id: Option<SchemaIdentifier>) -> Result<u32, ExecutionError> {
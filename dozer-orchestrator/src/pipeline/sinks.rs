@@ -1,22 +1,28 @@
 use dozer_api::generator::protoc::generator::ProtoGenerator;
 use dozer_api::grpc::internal::internal_pipeline_server::PipelineEventSenders;
 use dozer_api::grpc::types_helper;
-use dozer_cache::cache::expression::QueryExpression;
+use dozer_cache::cache::expression::{QueryExpression, Skip};
 use dozer_cache::cache::index::get_primary_key;
-use dozer_cache::cache::{CacheManager, RwCache};
+use dozer_cache::cache::{CacheManager, RwCache, SeqNoOffset};
+use dozer_core::epoch::Epoch;
 use dozer_core::errors::{ExecutionError, SinkError};
 use dozer_core::node::{PortHandle, Sink, SinkFactory};
 use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_sql::pipeline::builder::SchemaSQLContext;
 use dozer_types::crossbeam::channel::Sender;
 use dozer_types::grpc_types::internal::AliasRedirected;
+use dozer_types::grpc_types::types::Operation as GrpcOperation;
 use dozer_types::indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use dozer_types::log::{debug, info};
-use dozer_types::models::api_endpoint::{ApiEndpoint, ApiIndex, ConflictResolution};
+use dozer_types::models::api_endpoint::{
+    ApiEndpoint, ApiIndex, ConflictResolution, FullText, SecondaryIndex, SecondaryIndexConfig,
+    SortedInverted, Spatial,
+};
 use dozer_types::models::api_security::ApiSecurity;
 use dozer_types::models::flags::Flags;
+use dozer_types::node::SourceStates;
 use dozer_types::tracing::span;
-use dozer_types::types::{FieldType, SchemaWithIndex};
+use dozer_types::types::{Field, FieldDefinition, FieldType, SchemaWithIndex};
 use dozer_types::types::{IndexDefinition, Operation, Schema, SchemaIdentifier};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -125,38 +131,129 @@ impl CacheSinkFactory {
             version: 1,
         });
 
-        // Automatically create secondary indexes
-        let secondary_indexes = schema
+        let secondary_indexes = create_secondary_indexes(&schema, &self.api_endpoint)?;
+        Ok((schema, secondary_indexes))
+    }
+}
+
+/// Creates the secondary indexes of `api_endpoint`, as configured in `index.secondary`.
+fn create_secondary_indexes(
+    schema: &Schema,
+    api_endpoint: &ApiEndpoint,
+) -> Result<Vec<IndexDefinition>, ExecutionError> {
+    let config = api_endpoint
+        .index
+        .as_ref()
+        .and_then(|index| index.secondary.as_ref());
+    configure_secondary_indexes(schema, config).map_err(|reason| {
+        ExecutionError::InvalidSecondaryIndex {
+            endpoint_name: api_endpoint.name.clone(),
+            reason,
+        }
+    })
+}
+
+/// Creates the default secondary indexes of every field that's not skipped, then the configured ones.
+fn configure_secondary_indexes(
+    schema: &Schema,
+    config: Option<&SecondaryIndexConfig>,
+) -> Result<Vec<IndexDefinition>, String> {
+    let field_index = |name: &String| {
+        schema
             .fields
             .iter()
-            .enumerate()
-            .flat_map(|(idx, f)| match f.typ {
-                // Create sorted inverted indexes for these fields
-                FieldType::UInt
-                | FieldType::Int
-                | FieldType::Float
-                | FieldType::Boolean
-                | FieldType::Decimal
-                | FieldType::Timestamp
-                | FieldType::Date
-                | FieldType::Point => vec![IndexDefinition::SortedInverted(vec![idx])],
-
-                // Create sorted inverted and full text indexes for string fields.
-                FieldType::String => vec![
-                    IndexDefinition::SortedInverted(vec![idx]),
-                    IndexDefinition::FullText(idx),
-                ],
-
-                // Create full text indexes for text fields
-                // FieldType::Text => vec![IndexDefinition::FullText(idx)],
-                FieldType::Text => vec![],
-
-                // Skip creating indexes
-                FieldType::Binary | FieldType::Bson => vec![],
-            })
-            .collect();
-        Ok((schema, secondary_indexes))
+            .position(|field| &field.name == name)
+            .ok_or_else(|| format!("field `{name}` not found"))
+    };
+
+    let mut skip_default = vec![];
+    let mut configured_indexes = vec![];
+    if let Some(config) = config {
+        for name in &config.skip_default {
+            skip_default.push(field_index(name)?);
+        }
+
+        for create in &config.create {
+            let index = match &create.index {
+                Some(SecondaryIndex::SortedInverted(SortedInverted { fields })) => {
+                    if fields.is_empty() {
+                        return Err("sorted inverted index without fields".to_string());
+                    }
+                    IndexDefinition::SortedInverted(
+                        fields.iter().map(field_index).collect::<Result<_, _>>()?,
+                    )
+                }
+                Some(SecondaryIndex::FullText(FullText { field })) => {
+                    let idx = field_index(field)?;
+                    if !matches!(schema.fields[idx].typ, FieldType::String | FieldType::Text) {
+                        return Err(format!(
+                            "full text index on field `{field}`, which is not a string or text"
+                        ));
+                    }
+                    IndexDefinition::FullText(idx)
+                }
+                Some(SecondaryIndex::Spatial(Spatial { field })) => {
+                    let idx = field_index(field)?;
+                    if schema.fields[idx].typ != FieldType::Point {
+                        return Err(format!(
+                            "spatial index on field `{field}`, which is not a point"
+                        ));
+                    }
+                    IndexDefinition::Spatial(idx)
+                }
+                None => return Err("index kind not specified".to_string()),
+            };
+            configured_indexes.push(index);
+        }
+    }
+
+    let mut indexes = create_default_secondary_indexes(schema, &skip_default);
+    for index in configured_indexes {
+        if !indexes.contains(&index) {
+            indexes.push(index);
+        }
     }
+    Ok(indexes)
+}
+
+/// Automatically creates secondary indexes for every field but `skip`, based on the field type.
+fn create_default_secondary_indexes(schema: &Schema, skip: &[usize]) -> Vec<IndexDefinition> {
+    schema
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !skip.contains(idx))
+        .flat_map(|(idx, f)| match f.typ {
+            // Create sorted inverted indexes for these fields
+            FieldType::UInt
+            | FieldType::Int
+            | FieldType::Float
+            | FieldType::Boolean
+            | FieldType::Decimal
+            | FieldType::Timestamp
+            | FieldType::Date
+            | FieldType::Interval => vec![IndexDefinition::SortedInverted(vec![idx])],
+
+            // Create sorted inverted and spatial indexes for point fields.
+            FieldType::Point => vec![
+                IndexDefinition::SortedInverted(vec![idx]),
+                IndexDefinition::Spatial(idx),
+            ],
+
+            // Create sorted inverted and full text indexes for string fields.
+            FieldType::String => vec![
+                IndexDefinition::SortedInverted(vec![idx]),
+                IndexDefinition::FullText(idx),
+            ],
+
+            // Create full text indexes for text fields
+            // FieldType::Text => vec![IndexDefinition::FullText(idx)],
+            FieldType::Text => vec![],
+
+            // Skip creating indexes
+            FieldType::Binary | FieldType::Bson => vec![],
+        })
+        .collect()
 }
 
 impl SinkFactory<SchemaSQLContext> for CacheSinkFactory {
@@ -209,6 +306,7 @@ impl SinkFactory<SchemaSQLContext> for CacheSinkFactory {
             secondary_indexes,
             self.notifier.clone(),
             Some(self.multi_pb.clone()),
+            Some(self.settings.clone()),
         )?))
     }
 }
@@ -239,63 +337,51 @@ fn get_field_names(schema: &Schema, indexes: &[usize]) -> Vec<String> {
         .collect()
 }
 
+/// Opens the cache of the endpoint, or creates it if it doesn't exist yet. Returns whether an
+/// existing cache was opened.
 fn open_or_create_cache(
     cache_manager: &dyn CacheManager,
     name: &str,
     schema: Schema,
     secondary_indexes: Vec<IndexDefinition>,
     conflict_resolution: ConflictResolution,
-) -> Result<(Box<dyn RwCache>, Option<usize>), ExecutionError> {
-    let append_only = schema.is_append_only();
-
-    let create_cache = || {
-        cache_manager
-            .create_cache(schema, secondary_indexes, conflict_resolution)
-            .map_err(|e| {
-                ExecutionError::SinkError(SinkError::CacheCreateFailed(
-                    name.to_string(),
-                    Box::new(e),
-                ))
-            })
-    };
-
+) -> Result<(Box<dyn RwCache>, bool), ExecutionError> {
     let cache = cache_manager
         .open_rw_cache(name, conflict_resolution)
         .map_err(|e| {
             ExecutionError::SinkError(SinkError::CacheOpenFailed(name.to_string(), Box::new(e)))
         })?;
     if let Some(cache) = cache {
-        if append_only {
-            debug!("Cache {} is append only", name);
-            Ok((cache, None))
-        } else {
-            let old_name = cache.name();
-            let old_count = cache
-                .count(&QueryExpression::with_no_limit())
-                .map_err(|e| {
-                    ExecutionError::SinkError(SinkError::CacheCountFailed(
-                        name.to_string(),
-                        Box::new(e),
-                    ))
-                })?;
-            let cache = create_cache()?;
-            debug!("Cache {} is not append only", name);
-            info!(
-                "[pipeline] Cache {} writing to {} while serving {}",
-                name,
-                cache.name(),
-                old_name
-            );
-            Ok((cache, Some(old_count)))
-        }
+        debug!("Cache {} reopened from {}", name, cache.name());
+        Ok((cache, true))
     } else {
         debug!("Cache {} does not exist", name);
-        let cache = create_cache()?;
+        let cache = create_cache(
+            cache_manager,
+            name,
+            schema,
+            secondary_indexes,
+            conflict_resolution,
+        )?;
         create_alias(cache_manager, cache.name(), name)?;
-        Ok((cache, None))
+        Ok((cache, false))
     }
 }
 
+fn create_cache(
+    cache_manager: &dyn CacheManager,
+    name: &str,
+    schema: Schema,
+    secondary_indexes: Vec<IndexDefinition>,
+    conflict_resolution: ConflictResolution,
+) -> Result<Box<dyn RwCache>, ExecutionError> {
+    cache_manager
+        .create_cache(schema, secondary_indexes, conflict_resolution)
+        .map_err(|e| {
+            ExecutionError::SinkError(SinkError::CacheCreateFailed(name.to_string(), Box::new(e)))
+        })
+}
+
 fn create_alias(
     cache_manager: &dyn CacheManager,
     name: &str,
@@ -317,38 +403,86 @@ pub struct CacheSink {
     counter: usize,
     // Number of records in the cache that's currently served, if that's different from the one being written to.
     current_alias_count: Option<usize>,
+    /// Name of the cache that's currently served, if it's to be dropped once the alias is redirected to the one being written to.
+    served_cache: Option<String>,
     api_endpoint: ApiEndpoint,
     pb: ProgressBar,
     notifier: Option<PipelineEventSenders>,
+    /// `seq_no` of the events before the first operation of `cache`.
+    seq_no_offset: u64,
+    /// Events of uncommitted operations. They're sent after the cache commits, so every event can be replayed from the operation log.
+    pending_events: Vec<GrpcOperation>,
+    /// Used to regenerate proto files when the schema changes.
+    settings: Option<CacheSinkSettings>,
+    /// Schema and secondary indexes from the pipeline, kept if the cache was reopened from a previous run.
+    /// A new cache is created from them if the pipeline starts over.
+    reopened: Option<SchemaWithIndex>,
 }
 
 impl Sink for CacheSink {
-    fn commit(&mut self) -> Result<(), ExecutionError> {
-        let endpoint_name = self.api_endpoint.name.clone();
-        // Update Counter on commit
-        self.pb.set_position(self.counter as u64);
-        self.cache.commit().map_err(|e| {
-            if e.is_map_full() {
-                ExecutionError::SinkError(SinkError::CacheFull(endpoint_name))
-            } else {
-                ExecutionError::SinkError(SinkError::CacheCommitTransactionFailed(
-                    endpoint_name,
-                    Box::new(e),
-                ))
-            }
-        })?;
-
-        if let Some(current_alias_count) = self.current_alias_count {
-            // We're comparing number of operations with number of records.
-            // It's not really the same thing but should be good enough.
-            if self.counter >= current_alias_count {
-                self.redirect_alias()?;
-            }
+    fn checkpoint(&self) -> Result<Option<SourceStates>, ExecutionError> {
+        let Some((schema, secondary_indexes)) = &self.reopened else {
+            return Ok(None);
+        };
+        // A cache of an older schema can't be written to, so it's rebuilt from scratch.
+        let (cache_schema, cache_secondary_indexes) = self.cache.get_schema();
+        if cache_schema.fields != schema.fields
+            || cache_schema.primary_index != schema.primary_index
+            || cache_secondary_indexes != secondary_indexes
+        {
+            return Ok(None);
         }
+        self.cache
+            .checkpoint()
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
+    }
+
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        let Some((schema, secondary_indexes)) = self.reopened.take() else {
+            return Ok(());
+        };
 
+        // Write to a new cache while still serving the old one, until the new cache catches up.
+        let old_count = self.count()?;
+        let mut cache = create_cache(
+            &*self.cache_manager,
+            &self.api_endpoint.name,
+            schema,
+            secondary_indexes,
+            self.api_endpoint.conflict_resolution.unwrap_or_default(),
+        )?;
+        info!(
+            "[pipeline] Cache {} writing to {} while serving {}",
+            self.api_endpoint.name,
+            cache.name(),
+            self.cache.name()
+        );
+        // The new cache starts over, so its events continue the `seq_no`s of the old one but can't be resumed from them.
+        let seq_no = self.seq_no()?;
+        let seq_no_offset = SeqNoOffset {
+            offset: seq_no,
+            first_resumable: seq_no + 1,
+        };
+        cache
+            .set_seq_no_offset(seq_no_offset)
+            .and_then(|()| cache.commit())
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+        self.served_cache = Some(self.cache.name().to_string());
+        self.cache = cache;
+        self.seq_no_offset = seq_no_offset.offset;
+        self.counter = 0;
+        self.current_alias_count = Some(old_count);
         Ok(())
     }
 
+    fn commit(&mut self, epoch_details: &Epoch) -> Result<(), ExecutionError> {
+        // The checkpoint is committed in the same transaction as the operations it covers.
+        self.cache
+            .set_checkpoint(Some(&epoch_details.details))
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+        self.commit_operations()
+    }
+
     fn process(&mut self, _from_port: PortHandle, op: Operation) -> Result<(), ExecutionError> {
         self.counter += 1;
 
@@ -372,12 +506,13 @@ impl Sink for CacheSink {
                     Ok(version) => {
                         old.version = Some(version);
 
-                        if let Some(notifier) = &self.notifier {
-                            let op = types_helper::map_delete_operation(
+                        if self.notifier.is_some() {
+                            let seq_no = self.seq_no()?;
+                            self.pending_events.push(types_helper::map_delete_operation(
                                 self.api_endpoint.name.clone(),
                                 old,
-                            );
-                            try_send(&notifier.1, op)?;
+                                seq_no,
+                            ));
                         }
                     }
                     Err(e) => {
@@ -409,13 +544,14 @@ impl Sink for CacheSink {
 
                 match result {
                     Ok(id) => {
-                        if let Some(notifier) = &self.notifier {
-                            let op = types_helper::map_insert_operation(
+                        if self.notifier.is_some() {
+                            let seq_no = self.seq_no()?;
+                            self.pending_events.push(types_helper::map_insert_operation(
                                 self.api_endpoint.name.clone(),
                                 new,
                                 id,
-                            );
-                            try_send(&notifier.1, op)?;
+                                seq_no,
+                            ));
                         }
                     }
                     Err(e) => {
@@ -457,23 +593,25 @@ impl Sink for CacheSink {
                     Ok((Some(old_version), _)) => {
                         old.version = Some(old_version);
 
-                        if let Some(notifier) = &self.notifier {
-                            let op = types_helper::map_update_operation(
+                        if self.notifier.is_some() {
+                            let seq_no = self.seq_no()?;
+                            self.pending_events.push(types_helper::map_update_operation(
                                 self.api_endpoint.name.clone(),
                                 old,
                                 new,
-                            );
-                            try_send(&notifier.1, op)?;
+                                seq_no,
+                            ));
                         }
                     }
                     Ok((_, record_id)) => {
-                        if let Some(notifier) = &self.notifier {
-                            let op = types_helper::map_insert_operation(
+                        if self.notifier.is_some() {
+                            let seq_no = self.seq_no()?;
+                            self.pending_events.push(types_helper::map_insert_operation(
                                 self.api_endpoint.name.clone(),
                                 new,
                                 record_id,
-                            );
-                            try_send(&notifier.1, op)?;
+                                seq_no,
+                            ));
                         }
                     }
                     Err(e) => {
@@ -514,6 +652,28 @@ impl Sink for CacheSink {
     fn on_source_snapshotting_done(&mut self) -> Result<(), ExecutionError> {
         self.redirect_alias()
     }
+
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<(), ExecutionError> {
+        let added_fields = self
+            .cache
+            .get_schema()
+            .0
+            .added_fields(schema)
+            .ok_or_else(|| {
+                ExecutionError::UnsupportedSchemaChange(format!(
+                    "endpoint {} only supports adding nullable columns",
+                    self.api_endpoint.name
+                ))
+            })?;
+        if added_fields.is_empty() {
+            return Ok(());
+        }
+        self.migrate_cache(added_fields.to_vec())
+    }
 }
 
 impl CacheSink {
@@ -524,37 +684,96 @@ impl CacheSink {
         secondary_indexes: Vec<IndexDefinition>,
         notifier: Option<PipelineEventSenders>,
         multi_pb: Option<MultiProgress>,
+        settings: Option<CacheSinkSettings>,
     ) -> Result<Self, ExecutionError> {
-        let query = QueryExpression::with_no_limit();
-        let (cache, current_alias_count) = open_or_create_cache(
+        let (cache, reopened) = open_or_create_cache(
             &*cache_manager,
             &api_endpoint.name,
-            schema,
-            secondary_indexes,
+            schema.clone(),
+            secondary_indexes.clone(),
             api_endpoint.conflict_resolution.unwrap_or_default(),
         )?;
-        let counter = cache.count(&query).map_err(|e| {
-            ExecutionError::SinkError(SinkError::CacheCountFailed(
-                api_endpoint.name.clone(),
-                Box::new(e),
-            ))
-        })?;
 
-        debug!(
-            "SINK: Initialising CacheSink: {} with count: {}",
-            api_endpoint.name, counter
-        );
+        let seq_no_offset = cache
+            .seq_no_offset()
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?
+            .offset;
+
         let pb = attach_progress(multi_pb);
         pb.set_message(api_endpoint.name.clone());
-        Ok(Self {
+        let mut sink = Self {
             cache_manager,
             cache,
-            current_alias_count,
-            counter,
+            seq_no_offset,
+            current_alias_count: None,
+            served_cache: None,
+            counter: 0,
             api_endpoint,
             pb,
             notifier,
-        })
+            pending_events: vec![],
+            settings,
+            reopened: reopened.then_some((schema, secondary_indexes)),
+        };
+        sink.counter = sink.count()?;
+
+        debug!(
+            "SINK: Initialising CacheSink: {} with count: {}",
+            sink.api_endpoint.name, sink.counter
+        );
+        Ok(sink)
+    }
+
+    /// Commits the current transaction of the cache, then sends the events of the committed operations.
+    fn commit_operations(&mut self) -> Result<(), ExecutionError> {
+        let endpoint_name = self.api_endpoint.name.clone();
+        // Update Counter on commit
+        self.pb.set_position(self.counter as u64);
+        self.cache.commit().map_err(|e| {
+            if e.is_map_full() {
+                ExecutionError::SinkError(SinkError::CacheFull(endpoint_name))
+            } else {
+                ExecutionError::SinkError(SinkError::CacheCommitTransactionFailed(
+                    endpoint_name,
+                    Box::new(e),
+                ))
+            }
+        })?;
+
+        if let Some(notifier) = &self.notifier {
+            for event in self.pending_events.drain(..) {
+                try_send(&notifier.1, event)?;
+            }
+        }
+
+        if let Some(current_alias_count) = self.current_alias_count {
+            // We're comparing number of operations with number of records.
+            // It's not really the same thing but should be good enough.
+            if self.counter >= current_alias_count {
+                self.redirect_alias()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn count(&self) -> Result<usize, ExecutionError> {
+        self.cache
+            .count(&QueryExpression::with_no_limit())
+            .map_err(|e| {
+                ExecutionError::SinkError(SinkError::CacheCountFailed(
+                    self.api_endpoint.name.clone(),
+                    Box::new(e),
+                ))
+            })
+    }
+
+    /// Sequence number of the event of the last operation, which is the length of the operation log after the offset of the cache.
+    fn seq_no(&mut self) -> Result<u64, ExecutionError> {
+        self.cache
+            .uncommitted_operation_log_len()
+            .map(|len| self.seq_no_offset + len)
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))
     }
 
     fn redirect_alias(&mut self) -> Result<(), ExecutionError> {
@@ -570,6 +789,124 @@ impl CacheSink {
             try_send(&notifier.0, alias_redirected)?;
         }
 
+        if let Some(served_cache) = self.served_cache.take() {
+            self.drop_cache(&served_cache)?;
+        }
+        Ok(())
+    }
+
+    fn drop_cache(&self, name: &str) -> Result<(), ExecutionError> {
+        self.cache_manager.delete_cache(name).map_err(|e| {
+            ExecutionError::SinkError(SinkError::CacheDropFailed(name.to_string(), Box::new(e)))
+        })?;
+        info!(
+            "[pipeline] Cache {} dropped {}",
+            self.api_endpoint.name, name
+        );
+        Ok(())
+    }
+
+    /// Copies all records to a new cache whose schema has `added_fields` appended, switches to writing to it and drops the old cache.
+    ///
+    /// The new cache gets the next schema version. New fields are `null` in the copied records, which keep their versions.
+    fn migrate_cache(&mut self, added_fields: Vec<FieldDefinition>) -> Result<(), ExecutionError> {
+        // Commit pending operations so they're copied too. They're not covered by the checkpoint,
+        // so it's dropped and the pipeline starts over if it stops before the next commit.
+        self.cache
+            .set_checkpoint(None)
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+        self.commit_operations()?;
+
+        let endpoint_name = self.api_endpoint.name.clone();
+        let migration_error = |e| {
+            ExecutionError::SinkError(SinkError::CacheMigrationFailed(
+                endpoint_name.clone(),
+                Box::new(e),
+            ))
+        };
+
+        let (mut schema, _) = self.cache.get_schema().clone();
+        let num_added_fields = added_fields.len();
+        schema.fields.extend(added_fields);
+        if let Some(identifier) = &mut schema.identifier {
+            identifier.version += 1;
+        }
+        let secondary_indexes = create_secondary_indexes(&schema, &self.api_endpoint)?;
+
+        if let Some(settings) = &self.settings {
+            ProtoGenerator::generate(
+                &settings.api_dir,
+                &self.api_endpoint.name,
+                &schema,
+                &settings.api_security,
+                &settings.flags,
+            )
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+        }
+
+        let mut cache = self
+            .cache_manager
+            .create_cache(
+                schema.clone(),
+                secondary_indexes,
+                self.api_endpoint.conflict_resolution.unwrap_or_default(),
+            )
+            .map_err(migration_error)?;
+        let mut num_copied = 0;
+        loop {
+            let query = QueryExpression::new(
+                None,
+                vec![],
+                Some(MIGRATION_BATCH_SIZE),
+                Skip::Skip(num_copied),
+            );
+            let records = self.cache.query(&query).map_err(migration_error)?;
+            let num_records = records.len();
+            for record in records {
+                let mut record = record.record;
+                record.schema_id = schema.identifier;
+                record
+                    .values
+                    .extend(std::iter::repeat(Field::Null).take(num_added_fields));
+                cache.insert_copy(&record).map_err(migration_error)?;
+            }
+            cache.commit().map_err(migration_error)?;
+            num_copied += num_records;
+            if num_records < MIGRATION_BATCH_SIZE {
+                break;
+            }
+        }
+
+        // The copied records replace the old operation log. Events continue from where it ended, but can't be resumed from before that.
+        let seq_no = self.seq_no()?;
+        let seq_no_offset = SeqNoOffset {
+            // Every copied record is an insert in the new operation log.
+            offset: seq_no - num_copied as u64,
+            first_resumable: seq_no,
+        };
+        cache
+            .set_seq_no_offset(seq_no_offset)
+            .and_then(|()| cache.commit())
+            .map_err(migration_error)?;
+
+        info!(
+            "[pipeline] Cache {} migrated from {} to {} for schema version {}",
+            self.api_endpoint.name,
+            self.cache.name(),
+            cache.name(),
+            schema.identifier.map_or(0, |identifier| identifier.version)
+        );
+        let old_cache_name = std::mem::replace(&mut self.cache, cache).name().to_string();
+        self.seq_no_offset = seq_no_offset.offset;
+
+        if self.current_alias_count.is_none() {
+            // The old cache was being served, so serve the new one instead.
+            self.served_cache = Some(old_cache_name);
+            self.redirect_alias()?;
+        } else {
+            // The old cache was never served. The alias will be redirected once the new cache catches up.
+            self.drop_cache(&old_cache_name)?;
+        }
         Ok(())
     }
 
@@ -579,6 +916,9 @@ impl CacheSink {
     }
 }
 
+/// Number of records copied per transaction when migrating a cache.
+const MIGRATION_BATCH_SIZE: usize = 1000;
+
 fn try_send<T: Send + Sync + 'static>(sender: &Sender<T>, msg: T) -> Result<(), ExecutionError> {
     sender
         .try_send(msg)
@@ -590,11 +930,18 @@ mod tests {
 
     use crate::test_utils;
 
-    use dozer_cache::cache::index;
+    use super::CacheSink;
+    use dozer_cache::cache::expression::QueryExpression;
+    use dozer_cache::cache::{index, SeqNoOffset};
+    use dozer_core::epoch::Epoch;
     use dozer_core::node::Sink;
     use dozer_core::DEFAULT_PORT_HANDLE;
 
-    use dozer_types::types::{Field, IndexDefinition, Operation, Record, SchemaIdentifier};
+    use dozer_types::node::NodeHandle;
+    use dozer_types::types::{
+        Field, FieldDefinition, FieldType, IndexDefinition, Operation, Record, SchemaIdentifier,
+        SourceDefinition,
+    };
 
     #[test]
     // This test cases covers update of records when primary key changes because of value change in primary_key
@@ -643,7 +990,7 @@ mod tests {
         };
 
         sink.process(DEFAULT_PORT_HANDLE, insert_operation).unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
@@ -651,7 +998,7 @@ mod tests {
         assert_eq!(initial_values, record.values);
 
         sink.process(DEFAULT_PORT_HANDLE, update_operation).unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         // Primary key with old values
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
@@ -666,4 +1013,195 @@ mod tests {
 
         assert_eq!(updated_values, record.values);
     }
+
+    #[test]
+    fn resume_from_checkpoint_after_crash() {
+        let schema = test_utils::get_schema();
+        let (cache_manager, mut sink) = test_utils::init_sink(schema.clone(), vec![], None);
+        let source = NodeHandle::new(None, "films".to_string());
+        let insert = |id: i64| Operation::Insert {
+            new: Record::new(
+                schema.identifier,
+                vec![Field::Int(id), Field::String(format!("Film {id}"))],
+                None,
+            ),
+        };
+
+        // The first run commits two films, then crashes before committing the third.
+        sink.process(DEFAULT_PORT_HANDLE, insert(1)).unwrap();
+        sink.process(DEFAULT_PORT_HANDLE, insert(2)).unwrap();
+        let epoch = Epoch::from(0, source.clone(), 2, 0);
+        sink.commit(&epoch).unwrap();
+        sink.process(DEFAULT_PORT_HANDLE, insert(3)).unwrap();
+        drop(sink);
+
+        // The restarted sink resumes from the epoch committed with the first two films.
+        let mut sink = CacheSink::new(
+            cache_manager.clone(),
+            test_utils::init_endpoint(None),
+            schema.clone(),
+            vec![],
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(sink.checkpoint().unwrap(), Some(epoch.details));
+        sink.process(DEFAULT_PORT_HANDLE, insert(3)).unwrap();
+        sink.process(DEFAULT_PORT_HANDLE, insert(4)).unwrap();
+        sink.commit(&Epoch::from(1, source, 4, 0)).unwrap();
+
+        // Every film is in the cache exactly once.
+        let cache = cache_manager.open_ro_cache("films").unwrap().unwrap();
+        let mut ids = cache
+            .query(&QueryExpression::with_no_limit())
+            .unwrap()
+            .into_iter()
+            .map(|record| record.record.values[0].as_int().unwrap())
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn migrate_cache_on_additive_schema_change() {
+        let schema = test_utils::get_schema();
+        let (cache_manager, mut sink) = test_utils::init_sink(schema.clone(), vec![], None);
+        let old_cache_name = sink.get_cache_name().to_string();
+
+        let old_values = vec![Field::Int(1), Field::String("Old film name".to_string())];
+        let values = vec![Field::Int(1), Field::String("Film name".to_string())];
+        sink.process(
+            DEFAULT_PORT_HANDLE,
+            Operation::Insert {
+                new: Record::new(schema.identifier, old_values.clone(), None),
+            },
+        )
+        .unwrap();
+        sink.process(
+            DEFAULT_PORT_HANDLE,
+            Operation::Update {
+                old: Record::new(schema.identifier, old_values, None),
+                new: Record::new(schema.identifier, values.clone(), None),
+            },
+        )
+        .unwrap();
+
+        let mut new_schema = schema.clone();
+        new_schema.fields.push(FieldDefinition {
+            name: "rating".to_string(),
+            typ: FieldType::String,
+            nullable: true,
+            source: SourceDefinition::Dynamic,
+        });
+        // One insert, and a delete and an insert for the update.
+        assert_eq!(sink.seq_no().unwrap(), 3);
+        sink.on_schema_changed(DEFAULT_PORT_HANDLE, &new_schema)
+            .unwrap();
+        assert_ne!(sink.get_cache_name(), old_cache_name);
+        // Events continue after the old operation log, and can't be replayed from before the migration.
+        assert_eq!(sink.seq_no().unwrap(), 3);
+
+        let cache = cache_manager
+            .open_ro_cache(sink.get_cache_name())
+            .unwrap()
+            .unwrap();
+        let (cache_schema, _) = cache.get_schema();
+        assert_eq!(cache_schema.fields.len(), 3);
+        assert_eq!(cache_schema.identifier.unwrap().version, 2);
+        assert_eq!(
+            cache.seq_no_offset().unwrap(),
+            SeqNoOffset {
+                offset: 2,
+                first_resumable: 3
+            }
+        );
+
+        let key = index::get_primary_key(&schema.primary_index, &values);
+        let record = cache.get(&key).unwrap().record;
+        assert_eq!(
+            record.values,
+            vec![
+                Field::Int(1),
+                Field::String("Film name".to_string()),
+                Field::Null
+            ]
+        );
+        assert_eq!(record.version, Some(2));
+        // The old cache is dropped.
+        assert!(cache_manager
+            .open_ro_cache(&old_cache_name)
+            .unwrap()
+            .is_none());
+
+        // Removing a column is not supported.
+        let mut removed_schema = new_schema;
+        removed_schema.fields.remove(1);
+        assert!(sink
+            .on_schema_changed(DEFAULT_PORT_HANDLE, &removed_schema)
+            .is_err());
+    }
+
+    #[test]
+    fn configure_secondary_indexes_skips_and_adds_indexes() {
+        use super::configure_secondary_indexes;
+        use dozer_types::models::api_endpoint::{
+            CreateSecondaryIndex, FullText, SecondaryIndex, SecondaryIndexConfig, SortedInverted,
+        };
+
+        let schema = test_utils::get_schema();
+        assert_eq!(
+            configure_secondary_indexes(&schema, None).unwrap(),
+            vec![
+                IndexDefinition::SortedInverted(vec![0]),
+                IndexDefinition::SortedInverted(vec![1]),
+                IndexDefinition::FullText(1),
+            ]
+        );
+
+        let config = SecondaryIndexConfig {
+            skip_default: vec!["film_name".to_string()],
+            create: vec![
+                CreateSecondaryIndex {
+                    index: Some(SecondaryIndex::SortedInverted(SortedInverted {
+                        fields: vec!["film_name".to_string(), "film_id".to_string()],
+                    })),
+                },
+                CreateSecondaryIndex {
+                    index: Some(SecondaryIndex::FullText(FullText {
+                        field: "film_name".to_string(),
+                    })),
+                },
+                CreateSecondaryIndex {
+                    index: Some(SecondaryIndex::SortedInverted(SortedInverted {
+                        fields: vec!["film_id".to_string()],
+                    })),
+                },
+            ],
+        };
+        assert_eq!(
+            configure_secondary_indexes(&schema, Some(&config)).unwrap(),
+            vec![
+                IndexDefinition::SortedInverted(vec![0]),
+                IndexDefinition::SortedInverted(vec![1, 0]),
+                IndexDefinition::FullText(1),
+            ]
+        );
+
+        let unknown_field = SecondaryIndexConfig {
+            skip_default: vec!["rating".to_string()],
+            create: vec![],
+        };
+        assert!(configure_secondary_indexes(&schema, Some(&unknown_field)).is_err());
+
+        let full_text_on_int = SecondaryIndexConfig {
+            skip_default: vec![],
+            create: vec![CreateSecondaryIndex {
+                index: Some(SecondaryIndex::FullText(FullText {
+                    field: "film_id".to_string(),
+                })),
+            }],
+        };
+        assert!(configure_secondary_indexes(&schema, Some(&full_text_on_int)).is_err());
+    }
 }
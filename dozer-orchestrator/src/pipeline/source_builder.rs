@@ -6,10 +6,13 @@ use dozer_sql::pipeline::builder::SchemaSQLContext;
 use dozer_types::indicatif::MultiProgress;
 use dozer_types::models::source::Source;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub struct SourceBuilder<'a> {
     grouped_connections: HashMap<String, Vec<Source>>,
+    /// Every connector gets a directory of its own in it.
+    state_dir: PathBuf,
     progress: Option<&'a MultiProgress>,
 }
 
@@ -18,10 +21,12 @@ const SOURCE_PORTS_RANGE_START: u16 = 1000;
 impl<'a> SourceBuilder<'a> {
     pub fn new(
         grouped_connections: HashMap<String, Vec<Source>>,
+        state_dir: PathBuf,
         progress: Option<&'a MultiProgress>,
     ) -> Self {
         Self {
             grouped_connections,
+            state_dir,
             progress,
         }
     }
@@ -70,6 +75,7 @@ impl<'a> SourceBuilder<'a> {
                 let source_factory = ConnectorSourceFactory::new(
                     table_and_ports,
                     connection.clone(),
+                    self.state_dir.join(&conn),
                     self.progress.cloned(),
                 )?;
 
@@ -5,6 +5,7 @@ use dozer_types::models::app_config::Config;
 
 use dozer_core::appsource::{AppSourceId, AppSourceMappings};
 use dozer_sql::pipeline::builder::SchemaSQLContext;
+use dozer_sql::pipeline::state::StateOptions;
 use dozer_types::models::connection::{Connection, ConnectionConfig};
 use dozer_types::models::source::Source;
 
@@ -62,11 +63,13 @@ fn load_multi_sources() {
         config.sql.as_deref(),
         &config.endpoints,
         tmpdir.path(),
+        StateOptions::Memory,
     );
 
     let grouped_connections = builder.get_grouped_tables(&used_sources).unwrap();
 
-    let source_builder = SourceBuilder::new(grouped_connections, None);
+    let source_builder =
+        SourceBuilder::new(grouped_connections, tmpdir.path().join("connectors"), None);
     let asm = source_builder.build_source_manager().unwrap();
 
     let conn_name_1 = config.connections.get(0).unwrap().name.clone();
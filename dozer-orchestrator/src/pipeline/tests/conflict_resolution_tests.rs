@@ -4,6 +4,7 @@ mod tests {
     use dozer_cache::cache::expression::QueryExpression;
     use dozer_cache::cache::{index, RoCache};
     use dozer_cache::errors::CacheError;
+    use dozer_core::epoch::Epoch;
     use dozer_core::errors::{ExecutionError, SinkError};
     use dozer_core::node::Sink;
     use dozer_core::DEFAULT_PORT_HANDLE;
@@ -52,7 +53,7 @@ mod tests {
         };
         sink.process(DEFAULT_PORT_HANDLE, insert_operation.clone())
             .unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
@@ -61,7 +62,7 @@ mod tests {
         assert_eq!(Some(1), record.version);
 
         sink.process(DEFAULT_PORT_HANDLE, insert_operation).unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
@@ -88,7 +89,7 @@ mod tests {
             },
         };
         sink.process(DEFAULT_PORT_HANDLE, insert_operation).unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
@@ -110,7 +111,7 @@ mod tests {
 
         sink.process(DEFAULT_PORT_HANDLE, second_insert_operation)
             .unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
@@ -142,7 +143,7 @@ mod tests {
         };
         sink.process(DEFAULT_PORT_HANDLE, insert_operation.clone())
             .unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
@@ -188,7 +189,7 @@ mod tests {
             },
         };
         sink.process(DEFAULT_PORT_HANDLE, update_operation).unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key);
@@ -223,7 +224,7 @@ mod tests {
             },
         };
         sink.process(DEFAULT_PORT_HANDLE, update_operation).unwrap();
-        sink.commit().unwrap();
+        sink.commit(&Epoch::new(0, Default::default())).unwrap();
 
         let key = index::get_primary_key(&schema.primary_index, &initial_values);
         let record = cache.get(&key).unwrap().record;
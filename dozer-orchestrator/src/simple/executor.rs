@@ -12,6 +12,7 @@ use dozer_types::models::source::Source;
 
 use crate::pipeline::{CacheSinkSettings, PipelineBuilder};
 use dozer_core::executor::{DagExecutor, ExecutorOptions};
+use dozer_sql::pipeline::state::StateOptions;
 
 use dozer_ingestion::connectors::{get_connector, SourceSchema, TableInfo};
 
@@ -26,6 +27,7 @@ pub struct Executor<'a> {
     sql: Option<&'a str>,
     api_endpoints: &'a [ApiEndpoint],
     pipeline_dir: &'a Path,
+    state_options: StateOptions,
     running: Arc<AtomicBool>,
 }
 impl<'a> Executor<'a> {
@@ -35,6 +37,7 @@ impl<'a> Executor<'a> {
         sql: Option<&'a str>,
         api_endpoints: &'a [ApiEndpoint],
         pipeline_dir: &'a Path,
+        state_options: StateOptions,
         running: Arc<AtomicBool>,
     ) -> Self {
         Self {
@@ -43,6 +46,7 @@ impl<'a> Executor<'a> {
             sql,
             api_endpoints,
             pipeline_dir,
+            state_options,
             running,
         }
     }
@@ -74,6 +78,7 @@ impl<'a> Executor<'a> {
             self.sql,
             self.api_endpoints,
             self.pipeline_dir,
+            self.state_options.clone(),
         );
 
         let dag = builder.build(notifier, cache_manager, settings)?;
@@ -2,3 +2,6 @@ mod executor;
 pub mod orchestrator;
 pub use orchestrator::SimpleOrchestrator;
 mod helper;
+
+#[cfg(test)]
+mod tests;
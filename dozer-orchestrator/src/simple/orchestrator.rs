@@ -6,7 +6,7 @@ use crate::simple::helper::validate_config;
 use crate::utils::{
     get_api_dir, get_api_security_config, get_app_grpc_config, get_cache_dir,
     get_cache_manager_options, get_executor_options, get_flags, get_grpc_config, get_pipeline_dir,
-    get_rest_config,
+    get_rest_config, get_state_options,
 };
 use crate::{flatten_join_handle, Orchestrator};
 use dozer_api::auth::{Access, Authorizer};
@@ -22,7 +22,7 @@ use dozer_core::app::AppPipeline;
 use dozer_core::dag_schemas::DagSchemas;
 use dozer_core::errors::ExecutionError::InternalError;
 
-use dozer_ingestion::connectors::{SourceSchema, TableInfo};
+use dozer_ingestion::connectors::{get_connector, SourceSchema, TableInfo};
 use dozer_sql::pipeline::builder::statement_to_pipeline;
 use dozer_sql::pipeline::errors::PipelineError;
 use dozer_types::crossbeam::channel::{self, unbounded, Sender};
@@ -50,6 +50,15 @@ impl SimpleOrchestrator {
     pub fn new(config: Config) -> Self {
         Self { config }
     }
+
+    /// Removes the pipeline, cache and generated files. Unlike `clean`, leaves the sources alone.
+    fn remove_home_dir(&self) -> Result<(), OrchestrationError> {
+        let home_dir = PathBuf::from(self.config.home_dir.clone());
+        if home_dir.exists() {
+            fs::remove_dir_all(&home_dir).map_err(|e| InternalError(Box::new(e)))?;
+        };
+        Ok(())
+    }
 }
 
 impl Orchestrator for SimpleOrchestrator {
@@ -102,18 +111,6 @@ impl Orchestrator for SimpleOrchestrator {
                 alias_redirected_receiver,
             ));
 
-            // Initialize API Server
-            let rest_config = get_rest_config(self.config.to_owned());
-            let security = get_api_security_config(self.config.to_owned());
-            let cache_endpoints_for_rest = cache_endpoints.clone();
-            let rest_handle = tokio::spawn(async move {
-                let api_server = rest::ApiServer::new(rest_config, security);
-                api_server
-                    .run(cache_endpoints_for_rest, tx)
-                    .await
-                    .map_err(OrchestrationError::ApiServerFailed)
-            });
-
             // Initialize `PipelineResponse` events.
             let flags = self.config.flags.clone().unwrap_or_default();
             let operation_receiver = if flags.dynamic {
@@ -127,6 +124,19 @@ impl Orchestrator for SimpleOrchestrator {
                 None
             };
 
+            // Initialize API Server
+            let rest_config = get_rest_config(self.config.to_owned());
+            let security = get_api_security_config(self.config.to_owned());
+            let cache_endpoints_for_rest = cache_endpoints.clone();
+            let operation_receiver_for_rest = operation_receiver.as_ref().map(|r| r.resubscribe());
+            let rest_handle = tokio::spawn(async move {
+                let api_server = rest::ApiServer::new(rest_config, security);
+                api_server
+                    .run(cache_endpoints_for_rest, operation_receiver_for_rest, tx)
+                    .await
+                    .map_err(OrchestrationError::ApiServerFailed)
+            });
+
             // Initialize gRPC Server
             let api_dir = get_api_dir(&self.config);
             let grpc_config = get_grpc_config(self.config.to_owned());
@@ -187,6 +197,7 @@ impl Orchestrator for SimpleOrchestrator {
             self.config.sql.as_deref(),
             &self.config.endpoints,
             &pipeline_dir,
+            get_state_options(&self.config),
             running,
         );
         let flags = get_flags(self.config.clone());
@@ -249,7 +260,7 @@ impl Orchestrator for SimpleOrchestrator {
         );
         if api_dir.exists() || pipeline_home_dir.exists() || cache_dir.exists() {
             if force {
-                self.clean()?;
+                self.remove_home_dir()?;
             } else {
                 return Err(OrchestrationError::InitializationFailed(
                     self.config.home_dir.to_string(),
@@ -264,6 +275,7 @@ impl Orchestrator for SimpleOrchestrator {
             self.config.sql.as_deref(),
             &self.config.endpoints,
             &pipeline_home_dir,
+            get_state_options(&self.config),
         );
 
         // Api Path
@@ -307,11 +319,12 @@ impl Orchestrator for SimpleOrchestrator {
     // Cleaning the entire folder as there will be inconsistencies
     // between pipeline, cache and generated proto files.
     fn clean(&mut self) -> Result<(), OrchestrationError> {
-        let home_dir = PathBuf::from(self.config.home_dir.clone());
-        if home_dir.exists() {
-            fs::remove_dir_all(&home_dir).map_err(|e| InternalError(Box::new(e)))?;
-        };
-        Ok(())
+        for connection in &self.config.connections {
+            if let Err(e) = get_connector(connection.clone()).and_then(|c| c.clean()) {
+                warn!("Failed to clean connection {}: {}", connection.name, e);
+            }
+        }
+        self.remove_home_dir()
     }
 
     fn run_all(&mut self, running: Arc<AtomicBool>) -> Result<(), OrchestrationError> {
@@ -319,8 +332,6 @@ impl Orchestrator for SimpleOrchestrator {
         let running_wait = running.clone();
         let cache_manager_app = create_cache_manager(&self.config)?;
         let cache_manager_api = cache_manager_app.clone();
-        // TODO: remove this after checkpointing
-        self.clean()?;
 
         let mut dozer_api = self.clone();
 
@@ -0,0 +1,130 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use dozer_cache::cache::expression::QueryExpression;
+use dozer_cache::cache::{CacheManager, LmdbCacheManager};
+use dozer_core::executor::ExecutorOptions;
+use dozer_sql::pipeline::state::StateOptions;
+use dozer_types::ingestion_types::{LocalDetails, LocalStorage, Table};
+use dozer_types::models::api_endpoint::ApiEndpoint;
+use dozer_types::models::connection::{Connection, ConnectionConfig};
+use dozer_types::models::source::Source;
+use tempdir::TempDir;
+
+use super::executor::Executor;
+use crate::pipeline::CacheSinkSettings;
+
+fn get_connection(data_dir: &Path) -> Connection {
+    Connection {
+        config: Some(ConnectionConfig::LocalStorage(LocalStorage {
+            details: Some(LocalDetails {
+                path: data_dir.to_str().unwrap().to_string(),
+            }),
+            tables: vec![Table {
+                name: "users".to_string(),
+                prefix: "users".to_string(),
+                file_type: "csv".to_string(),
+                extension: "csv".to_string(),
+                watch: None,
+                format_options: None,
+            }],
+        })),
+        name: "local".to_string(),
+    }
+}
+
+/// Waits until the cache served as `users` has `count` records, and returns its real name.
+fn wait_for_count(cache_manager: &dyn CacheManager, count: usize) -> String {
+    for _ in 0..100 {
+        if let Some(cache) = cache_manager.open_ro_cache("users").unwrap() {
+            if cache.count(&QueryExpression::with_no_limit()).unwrap() == count {
+                return cache.name().to_string();
+            }
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    panic!("Cache never reached {count} records");
+}
+
+/// Runs the pipeline until the cache has `count` records.
+fn run_pipeline(home_dir: &Path, cache_manager: Arc<dyn CacheManager>, count: usize) -> String {
+    let connection = get_connection(&home_dir.join("data"));
+    let connections = vec![connection.clone()];
+    let sources = vec![Source {
+        name: "users".to_string(),
+        table_name: "users".to_string(),
+        columns: vec!["id".to_string(), "name".to_string()],
+        connection: Some(connection),
+        schema: None,
+        refresh_config: None,
+    }];
+    let endpoints = vec![ApiEndpoint {
+        name: "users".to_string(),
+        path: "/users".to_string(),
+        index: None,
+        table_name: "users".to_string(),
+        conflict_resolution: None,
+    }];
+    let pipeline_dir = home_dir.join("pipeline");
+    let settings = CacheSinkSettings::new(home_dir.join("api"), None, None);
+    let running = Arc::new(AtomicBool::new(true));
+
+    let executor = Executor::new(
+        &connections,
+        &sources,
+        None,
+        &endpoints,
+        &pipeline_dir,
+        StateOptions::Memory,
+        running.clone(),
+    );
+    let dag_executor = executor
+        .create_dag_executor(
+            None,
+            cache_manager.clone(),
+            settings,
+            ExecutorOptions::default(),
+        )
+        .unwrap();
+
+    thread::scope(|scope| {
+        let pipeline = scope.spawn(|| executor.run_dag_executor(dag_executor));
+        let cache_name = wait_for_count(&*cache_manager, count);
+        running.store(false, Ordering::SeqCst);
+        pipeline.join().unwrap().unwrap();
+        cache_name
+    })
+}
+
+#[test]
+fn restart_resumes_into_the_same_cache() {
+    let home_dir = TempDir::new("restart").unwrap();
+    let table_dir = home_dir.path().join("data").join("users");
+    std::fs::create_dir_all(&table_dir).unwrap();
+    std::fs::create_dir_all(home_dir.path().join("pipeline")).unwrap();
+    std::fs::create_dir_all(home_dir.path().join("api")).unwrap();
+    std::fs::write(table_dir.join("first.csv"), "id,name\n1,a\n2,b\n").unwrap();
+
+    let cache_manager: Arc<dyn CacheManager> =
+        Arc::new(LmdbCacheManager::new(Default::default()).unwrap());
+    let first_cache = run_pipeline(home_dir.path(), cache_manager.clone(), 2);
+
+    // Starting over instead of resuming would write to a new cache.
+    std::fs::write(table_dir.join("second.csv"), "id,name\n3,c\n4,d\n").unwrap();
+    let second_cache = run_pipeline(home_dir.path(), cache_manager.clone(), 4);
+    assert_eq!(first_cache, second_cache);
+
+    // Resuming neither repeats nor skips any record.
+    let cache = cache_manager.open_ro_cache("users").unwrap().unwrap();
+    let mut names = cache
+        .query(&QueryExpression::with_no_limit())
+        .unwrap()
+        .into_iter()
+        .map(|record| record.record.values[1].to_string().unwrap())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, vec!["a", "b", "c", "d"]);
+}
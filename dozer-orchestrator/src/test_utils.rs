@@ -41,6 +41,7 @@ pub fn init_sink(
         secondary_indexes,
         None,
         None,
+        None,
     )
     .unwrap();
     (cache_manager, cache)
@@ -51,6 +52,7 @@ pub fn init_endpoint(conflict_resolution: Option<ConflictResolution>) -> ApiEndp
         path: "/films".to_string(),
         index: Some(ApiIndex {
             primary_key: vec!["film_id".to_string()],
+            secondary: None,
         }),
         table_name: "films".to_string(),
         conflict_resolution,
@@ -1,11 +1,13 @@
 use dozer_cache::cache::CacheManagerOptions;
 use dozer_core::executor::ExecutorOptions;
+use dozer_sql::pipeline::state::StateOptions;
 use dozer_types::models::{
     api_config::{ApiConfig, GrpcApiOptions, RestApiOptions},
     api_security::ApiSecurity,
     app_config::{
-        default_app_buffer_size, default_cache_max_map_size, default_commit_size,
-        default_commit_timeout, Config,
+        default_app_batch_size, default_app_buffer_size, default_app_max_map_size,
+        default_app_parallelism, default_app_state_on_disk, default_cache_max_map_size,
+        default_commit_size, default_commit_timeout, Config,
     },
 };
 use std::{
@@ -29,6 +31,12 @@ fn get_cache_max_map_size(config: &Config) -> u64 {
         .unwrap_or_else(default_cache_max_map_size)
 }
 
+fn get_app_max_map_size(config: &Config) -> u64 {
+    config
+        .app_max_map_size
+        .unwrap_or_else(default_app_max_map_size)
+}
+
 fn get_commit_time_threshold(config: &Config) -> Duration {
     if let Some(commit_time_threshold) = config.commit_timeout {
         Duration::from_millis(commit_time_threshold)
@@ -43,6 +51,16 @@ fn get_buffer_size(config: &Config) -> u32 {
         .unwrap_or_else(default_app_buffer_size)
 }
 
+fn get_batch_size(config: &Config) -> u32 {
+    config.app_batch_size.unwrap_or_else(default_app_batch_size)
+}
+
+fn get_parallelism(config: &Config) -> u32 {
+    config
+        .app_parallelism
+        .unwrap_or_else(default_app_parallelism)
+}
+
 fn get_commit_size(config: &Config) -> u32 {
     config.commit_size.unwrap_or_else(default_commit_size)
 }
@@ -71,6 +89,27 @@ pub fn get_executor_options(config: &Config) -> ExecutorOptions {
         commit_sz: get_commit_size(config),
         channel_buffer_sz: get_buffer_size(config) as usize,
         commit_time_threshold: get_commit_time_threshold(config),
+        batch_sz: get_batch_size(config) as usize,
+        parallelism: get_parallelism(config) as usize,
+        processor_parallelism: config
+            .app_processor_parallelism
+            .iter()
+            .map(|(name, parallelism)| (name.clone(), *parallelism as usize))
+            .collect(),
+    }
+}
+
+pub fn get_state_options(config: &Config) -> StateOptions {
+    if config
+        .app_state_on_disk
+        .unwrap_or_else(default_app_state_on_disk)
+    {
+        StateOptions::Disk {
+            path: get_pipeline_dir(config),
+            max_map_size: get_app_max_map_size(config) as usize,
+        }
+    } else {
+        StateOptions::Memory
     }
 }
 
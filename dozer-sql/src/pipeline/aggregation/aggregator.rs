@@ -1,10 +1,16 @@
 #![allow(clippy::enum_variant_names)]
 
+use crate::pipeline::aggregation::approx_count_distinct::ApproxCountDistinctAggregator;
+use crate::pipeline::aggregation::array_agg::ArrayAggAggregator;
 use crate::pipeline::aggregation::avg::AvgAggregator;
 use crate::pipeline::aggregation::count::CountAggregator;
+use crate::pipeline::aggregation::count_distinct::CountDistinctAggregator;
 use crate::pipeline::aggregation::max::MaxAggregator;
 use crate::pipeline::aggregation::min::MinAggregator;
+use crate::pipeline::aggregation::min_max_by::MinMaxByAggregator;
+use crate::pipeline::aggregation::string_agg::StringAggAggregator;
 use crate::pipeline::aggregation::sum::SumAggregator;
+use crate::pipeline::aggregation::variance::VarianceAggregator;
 use crate::pipeline::errors::PipelineError;
 use enum_dispatch::enum_dispatch;
 use std::collections::BTreeMap;
@@ -12,6 +18,7 @@ use std::collections::BTreeMap;
 use crate::pipeline::expression::aggregate::AggregateFunctionType;
 use crate::pipeline::expression::execution::Expression;
 
+use dozer_types::serde::{Deserialize, Serialize};
 use dozer_types::types::{Field, FieldType, Schema};
 use std::fmt::{Debug, Display, Formatter};
 
@@ -24,13 +31,20 @@ pub trait Aggregator: Send + Sync {
 }
 
 #[enum_dispatch(Aggregator)]
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
 pub enum AggregatorEnum {
     AvgAggregator,
     MinAggregator,
     MaxAggregator,
     SumAggregator,
     CountAggregator,
+    CountDistinctAggregator,
+    ApproxCountDistinctAggregator,
+    VarianceAggregator,
+    StringAggAggregator,
+    ArrayAggAggregator,
+    MinMaxByAggregator,
 }
 
 impl Debug for dyn Aggregator {
@@ -46,6 +60,16 @@ pub enum AggregatorType {
     Max,
     Min,
     Sum,
+    CountDistinct,
+    ApproxCountDistinct,
+    Stddev,
+    StddevPop,
+    Variance,
+    VarPop,
+    StringAgg,
+    ArrayAgg,
+    MinBy,
+    MaxBy,
 }
 
 impl Display for AggregatorType {
@@ -56,6 +80,16 @@ impl Display for AggregatorType {
             AggregatorType::Max => f.write_str("max"),
             AggregatorType::Min => f.write_str("min"),
             AggregatorType::Sum => f.write_str("sum"),
+            AggregatorType::CountDistinct => f.write_str("count distinct"),
+            AggregatorType::ApproxCountDistinct => f.write_str("approx_count_distinct"),
+            AggregatorType::Stddev => f.write_str("stddev"),
+            AggregatorType::StddevPop => f.write_str("stddev_pop"),
+            AggregatorType::Variance => f.write_str("variance"),
+            AggregatorType::VarPop => f.write_str("var_pop"),
+            AggregatorType::StringAgg => f.write_str("string_agg"),
+            AggregatorType::ArrayAgg => f.write_str("array_agg"),
+            AggregatorType::MinBy => f.write_str("min_by"),
+            AggregatorType::MaxBy => f.write_str("max_by"),
         }
     }
 }
@@ -67,6 +101,16 @@ pub fn get_aggregator_from_aggregator_type(typ: AggregatorType) -> AggregatorEnu
         AggregatorType::Max => MaxAggregator::new().into(),
         AggregatorType::Min => MinAggregator::new().into(),
         AggregatorType::Sum => SumAggregator::new().into(),
+        AggregatorType::CountDistinct => CountDistinctAggregator::new().into(),
+        AggregatorType::ApproxCountDistinct => ApproxCountDistinctAggregator::new().into(),
+        AggregatorType::Stddev => VarianceAggregator::new(false, true).into(),
+        AggregatorType::StddevPop => VarianceAggregator::new(true, true).into(),
+        AggregatorType::Variance => VarianceAggregator::new(false, false).into(),
+        AggregatorType::VarPop => VarianceAggregator::new(true, false).into(),
+        AggregatorType::StringAgg => StringAggAggregator::new().into(),
+        AggregatorType::ArrayAgg => ArrayAggAggregator::new().into(),
+        AggregatorType::MinBy => MinMaxByAggregator::new(false).into(),
+        AggregatorType::MaxBy => MinMaxByAggregator::new(true).into(),
     }
 }
 
@@ -135,6 +179,28 @@ pub fn get_aggregator_type_from_aggregation_expression(
                 .clone()],
             AggregatorType::Count,
         )),
+        Expression::AggregateFunction { fun, args } => {
+            // these aggregators take all their arguments
+            let (typ, arg_count) = match fun {
+                AggregateFunctionType::CountDistinct => (AggregatorType::CountDistinct, 1),
+                AggregateFunctionType::ApproxCountDistinct => {
+                    (AggregatorType::ApproxCountDistinct, 1)
+                }
+                AggregateFunctionType::Stddev => (AggregatorType::Stddev, 1),
+                AggregateFunctionType::StddevPop => (AggregatorType::StddevPop, 1),
+                AggregateFunctionType::Variance => (AggregatorType::Variance, 1),
+                AggregateFunctionType::VarPop => (AggregatorType::VarPop, 1),
+                AggregateFunctionType::StringAgg => (AggregatorType::StringAgg, 2),
+                AggregateFunctionType::ArrayAgg => (AggregatorType::ArrayAgg, 1),
+                AggregateFunctionType::MinBy => (AggregatorType::MinBy, 2),
+                AggregateFunctionType::MaxBy => (AggregatorType::MaxBy, 2),
+                _ => return Err(PipelineError::InvalidFunction(e.to_string(schema))),
+            };
+            if args.len() != arg_count {
+                return Err(PipelineError::InvalidFunction(e.to_string(schema)));
+            }
+            Ok((args.clone(), typ))
+        }
         _ => Err(PipelineError::InvalidFunction(e.to_string(schema))),
     }
 }
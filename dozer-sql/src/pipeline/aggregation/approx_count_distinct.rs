@@ -0,0 +1,118 @@
+use crate::argv;
+use crate::pipeline::aggregation::aggregator::Aggregator;
+use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::aggregate::AggregateFunctionType::ApproxCountDistinct;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use crate::pipeline::state::{encode_key, stable_hash};
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
+use std::collections::BTreeMap;
+
+/// Number of bits of the hash selecting the register, giving a standard error of about 1.6%.
+const PRECISION: u32 = 12;
+const REGISTERS: usize = 1 << PRECISION;
+
+pub fn validate_approx_count_distinct(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    argv!(args, 0, ApproxCountDistinct)?.get_type(schema)?;
+    Ok(ExpressionType::new(
+        FieldType::Int,
+        false,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// HyperLogLog estimate of the number of distinct non-NULL values.
+///
+/// A plain HyperLogLog register only keeps the highest rank seen, which cannot be undone by a
+/// delete. Instead, every (register, rank) pair is kept with the number of values that hit it, and
+/// a register's value is its highest rank with a non-zero count.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct ApproxCountDistinctAggregator {
+    current_state: BTreeMap<(u16, u8), u64>,
+}
+
+impl ApproxCountDistinctAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+        }
+    }
+
+    fn get_value(&self) -> Field {
+        let mut registers = vec![0_u8; REGISTERS];
+        // entries are sorted by register then rank, so the last one seen for a register wins
+        for (register, rank) in self.current_state.keys() {
+            registers[*register as usize] = *rank;
+        }
+        Field::Int(estimate(&registers))
+    }
+}
+
+/// The registers are kept in state, so a value must hash the same in every process.
+pub(crate) fn get_register_and_rank(field: &Field) -> Result<(u16, u8), PipelineError> {
+    let hash = stable_hash(&encode_key(std::slice::from_ref(field))?);
+    let register = (hash >> (64 - PRECISION)) as u16;
+    let rank = ((hash << PRECISION).leading_zeros() + 1).min(64 - PRECISION + 1) as u8;
+    Ok((register, rank))
+}
+
+fn estimate(registers: &[u8]) -> i64 {
+    let m = registers.len() as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers
+        .iter()
+        .map(|rank| 2_f64.powi(-(*rank as i32)))
+        .sum();
+    let estimate = alpha * m * m / sum;
+
+    let zeros = registers.iter().filter(|rank| **rank == 0).count();
+    // linear counting is more accurate for small cardinalities
+    if estimate <= 2.5 * m && zeros > 0 {
+        (m * (m / zeros as f64).ln()).round() as i64
+    } else {
+        estimate.round() as i64
+    }
+}
+
+impl Aggregator for ApproxCountDistinctAggregator {
+    fn init(&mut self, _return_type: FieldType) {}
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        for field in old {
+            if field == &Field::Null {
+                continue;
+            }
+            let key = get_register_and_rank(field)?;
+            if let Some(count) = self.current_state.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    self.current_state.remove(&key);
+                }
+            }
+        }
+        Ok(self.get_value())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        for field in new {
+            if field == &Field::Null {
+                continue;
+            }
+            *self
+                .current_state
+                .entry(get_register_and_rank(field)?)
+                .or_insert(0) += 1;
+        }
+        Ok(self.get_value())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::argv;
+use crate::pipeline::aggregation::aggregator::{update_map, Aggregator};
+use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::aggregate::AggregateFunctionType::ArrayAgg;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::serde_json::Value;
+use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
+use std::collections::BTreeMap;
+
+pub fn validate_array_agg(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    argv!(args, 0, ArrayAgg)?.get_type(schema)?;
+    Ok(ExpressionType::new(
+        FieldType::Text,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Collects the non-NULL values into a JSON array, in sorted order.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct ArrayAggAggregator {
+    current_state: BTreeMap<Field, u64>,
+}
+
+impl ArrayAggAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+        }
+    }
+
+    fn get_value(&self) -> Field {
+        if self.current_state.is_empty() {
+            return Field::Null;
+        }
+        let values = self
+            .current_state
+            .iter()
+            .flat_map(|(value, count)| {
+                std::iter::repeat(to_json_value(value)).take(*count as usize)
+            })
+            .collect();
+        Field::Text(Value::Array(values).to_string())
+    }
+}
+
+fn to_json_value(field: &Field) -> Value {
+    match field {
+        Field::Int(i) => Value::from(*i),
+        Field::UInt(u) => Value::from(*u),
+        Field::Float(f) => Value::from(f.0),
+        Field::Boolean(b) => Value::from(*b),
+        Field::Null => Value::Null,
+        field => field.to_string().map_or(Value::Null, Value::String),
+    }
+}
+
+impl Aggregator for ArrayAggAggregator {
+    fn init(&mut self, _return_type: FieldType) {}
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        update_map(old, 1_u64, true, &mut self.current_state);
+        Ok(self.get_value())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        update_map(new, 1_u64, false, &mut self.current_state);
+        Ok(self.get_value())
+    }
+}
@@ -10,6 +10,7 @@ use dozer_core::errors::ExecutionError::InvalidType;
 use dozer_types::arrow::datatypes::ArrowNativeTypeOp;
 use dozer_types::ordered_float::OrderedFloat;
 use dozer_types::rust_decimal::Decimal;
+use dozer_types::serde::{Deserialize, Serialize};
 use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
 use num_traits::FromPrimitive;
 
@@ -46,7 +47,8 @@ pub fn validate_avg(args: &[Expression], schema: &Schema) -> Result<ExpressionTy
     ))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
 pub struct AvgAggregator {
     current_state: SumState,
     current_count: u64,
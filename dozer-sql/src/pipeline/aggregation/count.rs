@@ -6,6 +6,7 @@ use crate::pipeline::expression::execution::{Expression, ExpressionType};
 use dozer_core::errors::ExecutionError::InvalidType;
 use dozer_types::ordered_float::OrderedFloat;
 use dozer_types::rust_decimal::Decimal;
+use dozer_types::serde::{Deserialize, Serialize};
 use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
 use num_traits::FromPrimitive;
 
@@ -21,7 +22,8 @@ pub fn validate_count(
     ))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
 pub struct CountAggregator {
     current_state: u64,
     return_type: Option<FieldType>,
@@ -0,0 +1,56 @@
+use crate::argv;
+use crate::pipeline::aggregation::aggregator::{update_map, Aggregator};
+use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::aggregate::AggregateFunctionType::CountDistinct;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
+use std::collections::BTreeMap;
+
+pub fn validate_count_distinct(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    argv!(args, 0, CountDistinct)?.get_type(schema)?;
+    Ok(ExpressionType::new(
+        FieldType::Int,
+        false,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Counts the distinct non-NULL values, keeping how many times each was seen so that deletes can
+/// be applied.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct CountDistinctAggregator {
+    current_state: BTreeMap<Field, u64>,
+}
+
+impl CountDistinctAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+        }
+    }
+}
+
+impl Aggregator for CountDistinctAggregator {
+    fn init(&mut self, _return_type: FieldType) {}
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        update_map(old, 1_u64, true, &mut self.current_state);
+        Ok(Field::Int(self.current_state.len() as i64))
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        update_map(new, 1_u64, false, &mut self.current_state);
+        Ok(Field::Int(self.current_state.len() as i64))
+    }
+}
@@ -1,27 +1,38 @@
-use crate::pipeline::aggregation::processor::AggregationProcessor;
+use crate::pipeline::aggregation::processor::{AggregationProcessor, GroupByPartitionKey};
 use crate::pipeline::builder::SchemaSQLContext;
 use crate::pipeline::planner::projection::CommonPlanner;
 use crate::pipeline::projection::processor::ProjectionProcessor;
+use crate::pipeline::state::StateOptions;
 use dozer_core::{
     errors::ExecutionError,
-    node::{OutputPortDef, OutputPortType, PortHandle, Processor, ProcessorFactory},
+    node::{OutputPortDef, OutputPortType, Partitioning, PortHandle, Processor, ProcessorFactory},
     DEFAULT_PORT_HANDLE,
 };
 use dozer_types::types::Schema;
 use sqlparser::ast::Select;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct AggregationProcessorFactory {
     projection: Select,
     _stateful: bool,
+    state_options: StateOptions,
+    state_name: String,
 }
 
 impl AggregationProcessorFactory {
-    pub fn new(projection: Select, stateful: bool) -> Self {
+    pub fn new(
+        projection: Select,
+        stateful: bool,
+        state_options: StateOptions,
+        state_name: String,
+    ) -> Self {
         Self {
             projection,
             _stateful: stateful,
+            state_options,
+            state_name,
         }
     }
 
@@ -32,6 +43,42 @@ impl AggregationProcessorFactory {
             .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
         Ok(projection_planner)
     }
+
+    fn build_with_state_name(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        state_name: &str,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        let input_schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(ExecutionError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
+
+        let planner = self.get_planner(input_schema.clone())?;
+
+        let is_projection = planner.aggregation_output.is_empty() && planner.groupby.is_empty();
+        let processor: Box<dyn Processor> = if is_projection {
+            Box::new(ProjectionProcessor::with_select(
+                input_schema.clone(),
+                self.projection.clone(),
+                planner,
+            ))
+        } else {
+            Box::new(
+                AggregationProcessor::new(
+                    planner.groupby,
+                    planner.aggregation_output,
+                    planner.projection_output,
+                    planner.having,
+                    input_schema.clone(),
+                    planner.post_aggregation_schema,
+                    &self.state_options,
+                    state_name,
+                )
+                .map_err(|e| ExecutionError::InternalError(Box::new(e)))?,
+            )
+        };
+        Ok(processor)
+    }
 }
 
 impl ProcessorFactory<SchemaSQLContext> for AggregationProcessorFactory {
@@ -64,31 +111,48 @@ impl ProcessorFactory<SchemaSQLContext> for AggregationProcessorFactory {
         input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
     ) -> Result<Box<dyn Processor>, ExecutionError> {
+        self.build_with_state_name(input_schemas, &self.state_name)
+    }
+
+    fn partitioning(
+        &self,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Partitioning, ExecutionError> {
         let input_schema = input_schemas
             .get(&DEFAULT_PORT_HANDLE)
             .ok_or(ExecutionError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
 
         let planner = self.get_planner(input_schema.clone())?;
-
-        let is_projection = planner.aggregation_output.is_empty() && planner.groupby.is_empty();
-        let processor: Box<dyn Processor> = if is_projection {
-            Box::new(ProjectionProcessor::new(
-                input_schema.clone(),
-                planner.projection_output,
-            ))
+        Ok(if planner.groupby.is_empty() {
+            if planner.aggregation_output.is_empty() {
+                Partitioning::RoundRobin
+            } else {
+                // A single group for all records.
+                Partitioning::Single
+            }
         } else {
-            Box::new(
-                AggregationProcessor::new(
-                    planner.groupby,
-                    planner.aggregation_output,
-                    planner.projection_output,
-                    planner.having,
-                    input_schema.clone(),
-                    planner.post_aggregation_schema,
-                )
-                .map_err(|e| ExecutionError::InternalError(Box::new(e)))?,
-            )
-        };
-        Ok(processor)
+            Partitioning::Hash(Arc::new(GroupByPartitionKey::new(
+                planner.groupby,
+                input_schema.clone(),
+            )))
+        })
+    }
+
+    fn build_partition(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        partition: usize,
+        num_partitions: usize,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        // A different number of partitions gets new state, which doesn't match the checkpoint,
+        // so the pipeline starts over.
+        self.build_with_state_name(
+            input_schemas,
+            &format!(
+                "{}_partition_{partition}_of_{num_partitions}",
+                self.state_name
+            ),
+        )
     }
 }
@@ -6,6 +6,7 @@ use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, Exp
 use crate::{argv, calculate_err, calculate_err_field};
 use dozer_core::errors::ExecutionError::InvalidType;
 use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::serde::{Deserialize, Serialize};
 use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
 use std::collections::BTreeMap;
 
@@ -43,7 +44,8 @@ pub fn validate_max(args: &[Expression], schema: &Schema) -> Result<ExpressionTy
     ))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
 pub struct MaxAggregator {
     current_state: BTreeMap<Field, u64>,
     return_type: Option<FieldType>,
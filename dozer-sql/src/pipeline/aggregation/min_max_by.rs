@@ -0,0 +1,87 @@
+use crate::argv;
+use crate::pipeline::aggregation::aggregator::Aggregator;
+use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::aggregate::AggregateFunctionType;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
+use std::collections::BTreeMap;
+
+pub fn validate_min_max_by(
+    function: &AggregateFunctionType,
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let value = argv!(args, 0, function)?.get_type(schema)?;
+    argv!(args, 1, function)?.get_type(schema)?;
+    Ok(ExpressionType::new(
+        value.return_type,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// `MIN_BY(value, key)` and `MAX_BY(value, key)`: the value of the row with the smallest or
+/// largest key. Rows with a NULL key are ignored, and ties are broken on the value.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct MinMaxByAggregator {
+    current_state: BTreeMap<(Field, Field), u64>,
+    max: bool,
+}
+
+impl MinMaxByAggregator {
+    pub fn new(max: bool) -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+            max,
+        }
+    }
+
+    fn get_value(&self) -> Field {
+        let entry = if self.max {
+            self.current_state.keys().next_back()
+        } else {
+            self.current_state.keys().next()
+        };
+        entry.map_or(Field::Null, |(_key, value)| value.clone())
+    }
+}
+
+/// The (key, value) pair of a row's arguments.
+fn get_entry(fields: &[Field]) -> Option<(Field, Field)> {
+    match fields {
+        [_, Field::Null] => None,
+        [value, key] => Some((key.clone(), value.clone())),
+        _ => None,
+    }
+}
+
+impl Aggregator for MinMaxByAggregator {
+    fn init(&mut self, _return_type: FieldType) {}
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        if let Some(entry) = get_entry(old) {
+            if let Some(count) = self.current_state.get_mut(&entry) {
+                *count -= 1;
+                if *count == 0 {
+                    self.current_state.remove(&entry);
+                }
+            }
+        }
+        Ok(self.get_value())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        if let Some(entry) = get_entry(new) {
+            *self.current_state.entry(entry).or_insert(0) += 1;
+        }
+        Ok(self.get_value())
+    }
+}
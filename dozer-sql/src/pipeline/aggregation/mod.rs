@@ -1,9 +1,16 @@
 pub mod aggregator;
+pub mod approx_count_distinct;
+pub mod array_agg;
 pub mod avg;
 pub mod count;
+pub mod count_distinct;
 pub mod factory;
 pub mod max;
 pub mod min;
+pub mod min_max_by;
 pub mod processor;
+pub mod state;
+pub mod string_agg;
 pub mod sum;
 mod tests;
+pub mod variance;
@@ -1,28 +1,31 @@
 #![allow(clippy::too_many_arguments)]
 
+use crate::pipeline::aggregation::state::AggregationStates;
 use crate::pipeline::errors::PipelineError;
 use crate::pipeline::expression::execution::ExpressionExecutor;
+use crate::pipeline::state::{encode_key, stable_hash, StateOptions};
 use crate::pipeline::{aggregation::aggregator::Aggregator, expression::execution::Expression};
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::errors::ExecutionError;
 use dozer_core::errors::ExecutionError::InternalError;
-use dozer_core::node::{PortHandle, Processor};
+use dozer_core::node::{PartitionKey, PortHandle, Processor};
 use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::node::SourceStates;
 use dozer_types::types::{Field, FieldType, Operation, Record, Schema};
-use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
 
 use crate::pipeline::aggregation::aggregator::{
     get_aggregator_from_aggregator_type, get_aggregator_type_from_aggregation_expression,
     AggregatorEnum, AggregatorType,
 };
-use ahash::AHasher;
 use dozer_core::epoch::Epoch;
-use hashbrown::HashMap;
+use dozer_types::serde::{Deserialize, Serialize};
 
 const DEFAULT_SEGMENT_KEY: &str = "DOZER_DEFAULT_SEGMENT_KEY";
 
-#[derive(Debug)]
-struct AggregationState {
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct AggregationState {
     count: usize,
     states: Vec<AggregatorEnum>,
     values: Option<Vec<Field>>,
@@ -55,8 +58,8 @@ pub struct AggregationProcessor {
     having: Option<Expression>,
     input_schema: Schema,
     aggregation_schema: Schema,
-    states: HashMap<u64, AggregationState>,
-    default_segment_key: u64,
+    states: AggregationStates,
+    default_segment_key: Vec<u8>,
     having_eval_schema: Schema,
 }
 
@@ -74,6 +77,8 @@ impl AggregationProcessor {
         having: Option<Expression>,
         input_schema: Schema,
         aggregation_schema: Schema,
+        state_options: &StateOptions,
+        state_name: &str,
     ) -> Result<Self, PipelineError> {
         let mut aggr_types = Vec::new();
         let mut aggr_measures = Vec::new();
@@ -87,9 +92,6 @@ impl AggregationProcessor {
             aggr_measures_ret_types.push(measure.get_type(&input_schema)?.return_type)
         }
 
-        let mut hasher = AHasher::default();
-        DEFAULT_SEGMENT_KEY.hash(&mut hasher);
-
         let mut having_eval_schema_fields = input_schema.fields.clone();
         having_eval_schema_fields.extend(aggregation_schema.fields.clone());
 
@@ -98,12 +100,12 @@ impl AggregationProcessor {
             projections,
             input_schema,
             aggregation_schema,
-            states: HashMap::new(),
+            states: AggregationStates::new(state_options, state_name)?,
             measures: aggr_measures,
             having,
             measures_types: aggr_types,
             measures_return_types: aggr_measures_ret_types,
-            default_segment_key: hasher.finish(),
+            default_segment_key: DEFAULT_SEGMENT_KEY.as_bytes().to_vec(),
             having_eval_schema: Schema {
                 fields: having_eval_schema_fields,
                 primary_index: vec![],
@@ -176,13 +178,9 @@ impl AggregationProcessor {
         let mut out_rec_delete: Vec<Field> = Vec::with_capacity(self.measures.len());
         let mut out_rec_insert: Vec<Field> = Vec::with_capacity(self.measures.len());
 
-        let key = if !self.dimensions.is_empty() {
-            get_key(&self.input_schema, old, &self.dimensions)?
-        } else {
-            self.default_segment_key
-        };
+        let key = self.get_key(old)?;
 
-        let curr_state_opt = self.states.get_mut(&key);
+        let curr_state_opt = self.states.take(&key)?;
         assert!(
             curr_state_opt.is_some(),
             "Unable to find aggregator state during DELETE operation"
@@ -190,7 +188,7 @@ impl AggregationProcessor {
         let mut curr_state = curr_state_opt.unwrap();
 
         let new_values = Self::calc_and_fill_measures(
-            curr_state,
+            &mut curr_state,
             Some(old),
             None,
             &mut out_rec_delete,
@@ -220,7 +218,7 @@ impl AggregationProcessor {
         };
 
         let res = if curr_state.count == 1 {
-            self.states.remove(&key);
+            self.states.remove(&key)?;
             if out_rec_delete_having_satisfied {
                 vec![Operation::Delete {
                     old: Self::build_projection(
@@ -236,6 +234,7 @@ impl AggregationProcessor {
         } else {
             curr_state.count -= 1;
             curr_state.values = Some(new_values);
+            self.states.put(key, curr_state)?;
 
             Self::generate_op_for_existing_segment(
                 out_rec_delete_having_satisfied,
@@ -255,19 +254,15 @@ impl AggregationProcessor {
         let mut out_rec_delete: Vec<Field> = Vec::with_capacity(self.measures.len());
         let mut out_rec_insert: Vec<Field> = Vec::with_capacity(self.measures.len());
 
-        let key = if !self.dimensions.is_empty() {
-            get_key(&self.input_schema, new, &self.dimensions)?
-        } else {
-            self.default_segment_key
-        };
+        let key = self.get_key(new)?;
 
-        let curr_state = self.states.entry(key).or_insert(AggregationState::new(
-            &self.measures_types,
-            &self.measures_return_types,
-        ));
+        let mut curr_state = match self.states.take(&key)? {
+            Some(state) => state,
+            None => AggregationState::new(&self.measures_types, &self.measures_return_types),
+        };
 
         let new_values = Self::calc_and_fill_measures(
-            curr_state,
+            &mut curr_state,
             None,
             Some(new),
             &mut out_rec_delete,
@@ -323,6 +318,7 @@ impl AggregationProcessor {
 
         curr_state.count += 1;
         curr_state.values = Some(new_values);
+        self.states.put(key, curr_state)?;
 
         Ok(res)
     }
@@ -409,12 +405,12 @@ impl AggregationProcessor {
         &mut self,
         old: &mut Record,
         new: &mut Record,
-        key: u64,
+        key: Vec<u8>,
     ) -> Result<Vec<Operation>, PipelineError> {
         let mut out_rec_delete: Vec<Field> = Vec::with_capacity(self.measures.len());
         let mut out_rec_insert: Vec<Field> = Vec::with_capacity(self.measures.len());
 
-        let curr_state_opt = self.states.get_mut(&key);
+        let curr_state_opt = self.states.take(&key)?;
         assert!(
             curr_state_opt.is_some(),
             "Unable to find aggregator state during UPDATE operation"
@@ -422,7 +418,7 @@ impl AggregationProcessor {
         let mut curr_state = curr_state_opt.unwrap();
 
         let new_values = Self::calc_and_fill_measures(
-            curr_state,
+            &mut curr_state,
             Some(old),
             Some(new),
             &mut out_rec_delete,
@@ -489,6 +485,7 @@ impl AggregationProcessor {
         };
 
         curr_state.values = Some(new_values);
+        self.states.put(key, curr_state)?;
         Ok(res)
     }
 
@@ -508,6 +505,15 @@ impl AggregationProcessor {
         Ok(Record::new(None, output, None))
     }
 
+    /// The key of the segment `record` belongs to.
+    fn get_key(&self, record: &Record) -> Result<Vec<u8>, PipelineError> {
+        if self.dimensions.is_empty() {
+            Ok(self.default_segment_key.clone())
+        } else {
+            get_key(&self.input_schema, record, &self.dimensions)
+        }
+    }
+
     pub fn aggregate(&mut self, mut op: Operation) -> Result<Vec<Operation>, PipelineError> {
         match op {
             Operation::Insert { ref mut new } => Ok(self.agg_insert(new)?),
@@ -516,17 +522,11 @@ impl AggregationProcessor {
                 ref mut old,
                 ref mut new,
             } => {
-                let (old_record_hash, new_record_hash) = if self.dimensions.is_empty() {
-                    (self.default_segment_key, self.default_segment_key)
-                } else {
-                    (
-                        get_key(&self.input_schema, old, &self.dimensions)?,
-                        get_key(&self.input_schema, new, &self.dimensions)?,
-                    )
-                };
-
-                if old_record_hash == new_record_hash {
-                    Ok(self.agg_update(old, new, old_record_hash)?)
+                let old_key = self.get_key(old)?;
+                let new_key = self.get_key(new)?;
+
+                if old_key == new_key {
+                    Ok(self.agg_update(old, new, old_key)?)
                 } else {
                     let mut r = Vec::with_capacity(2);
                     r.extend(self.agg_delete(old)?);
@@ -542,20 +542,51 @@ fn get_key(
     schema: &Schema,
     record: &Record,
     dimensions: &[Expression],
-) -> Result<u64, PipelineError> {
+) -> Result<Vec<u8>, PipelineError> {
     let mut key = Vec::<Field>::with_capacity(dimensions.len());
     for dimension in dimensions.iter() {
         key.push(dimension.evaluate(record, schema)?);
     }
-    let mut hasher = AHasher::default();
-    key.hash(&mut hasher);
-    let v = hasher.finish();
-    Ok(v)
+    Ok(encode_key(&key)?)
+}
+
+/// Partitions records on their group, so every group is aggregated by a single instance of a parallel aggregation.
+#[derive(Debug)]
+pub struct GroupByPartitionKey {
+    dimensions: Vec<Expression>,
+    input_schema: Schema,
+}
+
+impl GroupByPartitionKey {
+    pub fn new(dimensions: Vec<Expression>, input_schema: Schema) -> Self {
+        Self {
+            dimensions,
+            input_schema,
+        }
+    }
+}
+
+impl PartitionKey for GroupByPartitionKey {
+    fn hash_key(&self, _port: PortHandle, record: &Record) -> Result<u64, ExecutionError> {
+        let key = get_key(&self.input_schema, record, &self.dimensions)
+            .map_err(|e| InternalError(Box::new(e)))?;
+        Ok(stable_hash(&key))
+    }
 }
 
 impl Processor for AggregationProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
-        Ok(())
+    fn commit(&mut self, epoch: &Epoch) -> Result<(), ExecutionError> {
+        self.states
+            .commit(&epoch.details)
+            .map_err(|e| InternalError(Box::new(e)))
+    }
+
+    fn is_resumable(&self, checkpoint: &SourceStates) -> bool {
+        self.states.is_resumable(checkpoint)
+    }
+
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        self.states.reset().map_err(|e| InternalError(Box::new(e)))
     }
 
     fn process(
@@ -570,4 +601,19 @@ impl Processor for AggregationProcessor {
         }
         Ok(())
     }
+
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        // Dimensions and measures only refer to existing columns, so additive changes don't affect the output.
+        if self.input_schema.added_fields(schema).is_none() {
+            return Err(ExecutionError::UnsupportedSchemaChange(
+                "aggregation input columns changed".to_string(),
+            ));
+        }
+        self.input_schema = schema.clone();
+        Ok(HashMap::new())
+    }
 }
@@ -0,0 +1,135 @@
+use dozer_storage::{LmdbMap, RwLmdbEnvironment};
+use dozer_types::bincode;
+use dozer_types::borrow::Borrow;
+use dozer_types::node::SourceStates;
+use hashbrown::HashMap;
+
+use crate::pipeline::aggregation::processor::AggregationState;
+use crate::pipeline::errors::StateStoreError;
+use crate::pipeline::state::{CommittedEpoch, StateOptions};
+
+/// Aggregation states of all segments, keyed by the encoded segment key.
+///
+/// A state is taken out with `take`, updated, then either `put` back or `remove`d.
+#[derive(Debug)]
+pub enum AggregationStates {
+    Memory(HashMap<Vec<u8>, AggregationState>),
+    Lmdb(LmdbAggregationStates),
+}
+
+impl AggregationStates {
+    pub fn new(state_options: &StateOptions, name: &str) -> Result<Self, StateStoreError> {
+        Ok(match state_options.open_environment(name)? {
+            None => AggregationStates::Memory(HashMap::new()),
+            Some(env) => AggregationStates::Lmdb(LmdbAggregationStates::new(env)?),
+        })
+    }
+
+    pub fn take(&mut self, key: &[u8]) -> Result<Option<AggregationState>, StateStoreError> {
+        match self {
+            AggregationStates::Memory(states) => Ok(states.remove(key)),
+            AggregationStates::Lmdb(states) => states.get(key),
+        }
+    }
+
+    pub fn put(&mut self, key: Vec<u8>, state: AggregationState) -> Result<(), StateStoreError> {
+        match self {
+            AggregationStates::Memory(states) => {
+                states.insert(key, state);
+                Ok(())
+            }
+            AggregationStates::Lmdb(states) => states.put(&key, &state),
+        }
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> Result<(), StateStoreError> {
+        match self {
+            // Already removed by `take`.
+            AggregationStates::Memory(_) => Ok(()),
+            AggregationStates::Lmdb(states) => states.remove(key),
+        }
+    }
+
+    pub fn commit(&mut self, source_states: &SourceStates) -> Result<(), StateStoreError> {
+        match self {
+            AggregationStates::Memory(_) => Ok(()),
+            AggregationStates::Lmdb(states) => states.commit(source_states),
+        }
+    }
+
+    /// Whether the states are the ones after the epoch with source states `checkpoint`.
+    pub fn is_resumable(&self, checkpoint: &SourceStates) -> bool {
+        match self {
+            // Memory states start empty.
+            AggregationStates::Memory(_) => false,
+            AggregationStates::Lmdb(states) => states.committed_epoch.is(checkpoint),
+        }
+    }
+
+    /// Removes all states.
+    pub fn reset(&mut self) -> Result<(), StateStoreError> {
+        match self {
+            AggregationStates::Memory(states) => {
+                states.clear();
+                Ok(())
+            }
+            AggregationStates::Lmdb(states) => states.reset(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LmdbAggregationStates {
+    env: RwLmdbEnvironment,
+    states: LmdbMap<Vec<u8>, Vec<u8>>,
+    committed_epoch: CommittedEpoch,
+}
+
+impl LmdbAggregationStates {
+    fn new(mut env: RwLmdbEnvironment) -> Result<Self, StateStoreError> {
+        let states = LmdbMap::create(&mut env, Some("states"))?;
+        let committed_epoch = CommittedEpoch::create(&mut env)?;
+        Ok(Self {
+            env,
+            states,
+            committed_epoch,
+        })
+    }
+
+    fn get(&mut self, key: &[u8]) -> Result<Option<AggregationState>, StateStoreError> {
+        let txn = self.env.txn_mut()?;
+        let Some(bytes) = self.states.get(txn, key)? else {
+            return Ok(None);
+        };
+        bincode::deserialize(bytes.borrow())
+            .map(Some)
+            .map_err(StateStoreError::Deserialization)
+    }
+
+    fn put(&mut self, key: &[u8], state: &AggregationState) -> Result<(), StateStoreError> {
+        let bytes = bincode::serialize(state).map_err(StateStoreError::Serialization)?;
+        let txn = self.env.txn_mut()?;
+        self.states.insert_overwrite(txn, key, &bytes)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &[u8]) -> Result<(), StateStoreError> {
+        let txn = self.env.txn_mut()?;
+        self.states.remove(txn, key)?;
+        Ok(())
+    }
+
+    fn commit(&mut self, source_states: &SourceStates) -> Result<(), StateStoreError> {
+        self.committed_epoch.store(&mut self.env, source_states)?;
+        self.env.commit()?;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), StateStoreError> {
+        let txn = self.env.txn_mut()?;
+        self.states.clear(txn)?;
+        self.committed_epoch.clear(&mut self.env)?;
+        self.env.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::argv;
+use crate::pipeline::aggregation::aggregator::Aggregator;
+use crate::pipeline::errors::{FieldTypes, PipelineError};
+use crate::pipeline::expression::aggregate::AggregateFunctionType::StringAgg;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
+use std::collections::BTreeMap;
+
+pub fn validate_string_agg(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    for idx in 0..2 {
+        let arg = argv!(args, idx, StringAgg)?.get_type(schema)?;
+        if arg.return_type != FieldType::String && arg.return_type != FieldType::Text {
+            return Err(PipelineError::InvalidFunctionArgumentType(
+                StringAgg.to_string(),
+                arg.return_type,
+                FieldTypes::new(vec![FieldType::String, FieldType::Text]),
+                idx,
+            ));
+        }
+    }
+    Ok(ExpressionType::new(
+        FieldType::String,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Concatenates the non-NULL values with the separator given as second argument.
+///
+/// Values are kept with their number of occurrences so that deletes can be applied, and are
+/// concatenated in sorted order so that the result does not depend on the order of operations.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct StringAggAggregator {
+    current_state: BTreeMap<String, u64>,
+    separator: String,
+}
+
+impl StringAggAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+            separator: String::new(),
+        }
+    }
+
+    fn get_value(&self) -> Field {
+        if self.current_state.is_empty() {
+            return Field::Null;
+        }
+        let values = self
+            .current_state
+            .iter()
+            .flat_map(|(value, count)| std::iter::repeat(value.as_str()).take(*count as usize))
+            .collect::<Vec<_>>();
+        Field::String(values.join(&self.separator))
+    }
+}
+
+fn get_string(field: Option<&Field>) -> Option<String> {
+    match field {
+        Some(Field::String(s) | Field::Text(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+impl Aggregator for StringAggAggregator {
+    fn init(&mut self, _return_type: FieldType) {}
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        if let Some(value) = get_string(old.get(0)) {
+            if let Some(count) = self.current_state.get_mut(&value) {
+                *count -= 1;
+                if *count == 0 {
+                    self.current_state.remove(&value);
+                }
+            }
+        }
+        Ok(self.get_value())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        if let Some(separator) = get_string(new.get(1)) {
+            self.separator = separator;
+        }
+        if let Some(value) = get_string(new.get(0)) {
+            *self.current_state.entry(value).or_insert(0) += 1;
+        }
+        Ok(self.get_value())
+    }
+}
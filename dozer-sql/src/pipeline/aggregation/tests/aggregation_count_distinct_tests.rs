@@ -0,0 +1,92 @@
+use crate::output;
+use crate::pipeline::aggregation::tests::aggregation_tests_utils::{
+    delete_field, init_input_schema, init_processor, insert_exp, insert_field, update_exp,
+    update_field, FIELD_100_INT, FIELD_1_INT, FIELD_200_INT, FIELD_2_INT, FIELD_50_INT, FIELD_NULL,
+    ITALY,
+};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::types::FieldType::Int;
+use std::collections::HashMap;
+
+#[test]
+fn test_count_distinct_aggregation() {
+    let schema = init_input_schema(Int, "COUNT");
+    let mut processor = init_processor(
+        "SELECT Country, COUNT(DISTINCT Salary) \
+        FROM Users GROUP BY Country",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    // Insert 100 for segment Italy
+    /*
+        Italy, 100
+        -------------
+        COUNT DISTINCT = 1
+    */
+    let mut inp = insert_field(ITALY, FIELD_100_INT);
+    let mut out = output!(processor, inp);
+    let mut exp = vec![insert_exp(ITALY, FIELD_1_INT)];
+    assert_eq!(out, exp);
+
+    // Insert another 100 for segment Italy
+    /*
+        Italy, 100
+        Italy, 100
+        -------------
+        COUNT DISTINCT = 1
+    */
+    inp = insert_field(ITALY, FIELD_100_INT);
+    out = output!(processor, inp);
+    exp = vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_1_INT)];
+    assert_eq!(out, exp);
+
+    // Insert NULL, which is not counted
+    inp = insert_field(ITALY, FIELD_NULL);
+    out = output!(processor, inp);
+    exp = vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_1_INT)];
+    assert_eq!(out, exp);
+
+    // Insert 50 for segment Italy
+    /*
+        Italy, 100
+        Italy, 100
+        Italy, 50
+        -------------
+        COUNT DISTINCT = 2
+    */
+    inp = insert_field(ITALY, FIELD_50_INT);
+    out = output!(processor, inp);
+    exp = vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_2_INT)];
+    assert_eq!(out, exp);
+
+    // Delete one 100, the other one is still counted
+    inp = delete_field(ITALY, FIELD_100_INT);
+    out = output!(processor, inp);
+    exp = vec![update_exp(ITALY, ITALY, FIELD_2_INT, FIELD_2_INT)];
+    assert_eq!(out, exp);
+
+    // Update 100 -> 50
+    /*
+        Italy, 50
+        Italy, 50
+        -------------
+        COUNT DISTINCT = 1
+    */
+    inp = update_field(ITALY, ITALY, FIELD_100_INT, FIELD_50_INT);
+    out = output!(processor, inp);
+    exp = vec![update_exp(ITALY, ITALY, FIELD_2_INT, FIELD_1_INT)];
+    assert_eq!(out, exp);
+
+    // Update 50 -> 200
+    /*
+        Italy, 50
+        Italy, 200
+        -------------
+        COUNT DISTINCT = 2
+    */
+    inp = update_field(ITALY, ITALY, FIELD_50_INT, FIELD_200_INT);
+    out = output!(processor, inp);
+    exp = vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_2_INT)];
+    assert_eq!(out, exp);
+}
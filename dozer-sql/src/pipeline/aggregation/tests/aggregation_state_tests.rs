@@ -0,0 +1,265 @@
+use crate::output;
+use crate::pipeline::aggregation::factory::AggregationProcessorFactory;
+use crate::pipeline::aggregation::tests::aggregation_tests_utils::{
+    delete_exp, delete_field, init_input_schema, init_processor_with_state, insert_exp,
+    insert_field, update_exp, FIELD_100_INT, FIELD_150_INT, FIELD_200_INT, FIELD_250_INT,
+    FIELD_50_INT, ITALY, SINGAPORE,
+};
+use crate::pipeline::state::{encode_key, StateOptions};
+use crate::pipeline::tests::utils::get_select;
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::epoch::Epoch;
+use dozer_core::errors::ExecutionError;
+use dozer_core::node::{Partitioning, PortHandle, Processor, ProcessorFactory};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::node::NodeHandle;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::rust_decimal::Decimal;
+use dozer_types::types::FieldType::Int;
+use dozer_types::types::{Field, Operation};
+use std::collections::HashMap;
+use tempdir::TempDir;
+
+const SQL: &str = "SELECT Country, SUM(Salary) \
+    FROM Users \
+    WHERE Salary >= 1 GROUP BY Country";
+
+struct TestChannelForwarder {
+    operations: Vec<Operation>,
+}
+
+impl ProcessorChannelForwarder for TestChannelForwarder {
+    fn send(&mut self, op: Operation, _port: PortHandle) -> Result<(), ExecutionError> {
+        self.operations.push(op);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_aggregation_state_survives_restart() {
+    let tmp_dir = TempDir::new("aggregation_state").unwrap();
+    let state_options = StateOptions::disk(tmp_dir.path().to_path_buf());
+    let epoch = Epoch::from(0, NodeHandle::new(None, "source".to_string()), 0, 0);
+
+    let mut processor = init_processor_with_state(
+        SQL,
+        HashMap::from([(DEFAULT_PORT_HANDLE, init_input_schema(Int, "SUM"))]),
+        &state_options,
+    )
+    .unwrap();
+
+    let mut out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(out, vec![insert_exp(ITALY, FIELD_100_INT)]);
+    out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_100_INT, FIELD_200_INT)]
+    );
+    out = output!(processor, insert_field(SINGAPORE, FIELD_50_INT));
+    assert_eq!(out, vec![insert_exp(SINGAPORE, FIELD_50_INT)]);
+    processor.commit(&epoch).unwrap();
+    drop(processor);
+
+    // A new processor picks up the committed state.
+    let mut processor = init_processor_with_state(
+        SQL,
+        HashMap::from([(DEFAULT_PORT_HANDLE, init_input_schema(Int, "SUM"))]),
+        &state_options,
+    )
+    .unwrap();
+    assert!(processor.is_resumable(&epoch.details));
+
+    out = output!(processor, insert_field(ITALY, FIELD_50_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_200_INT, FIELD_250_INT)]
+    );
+    out = output!(processor, delete_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_250_INT, FIELD_150_INT)]
+    );
+    out = output!(processor, delete_field(SINGAPORE, FIELD_50_INT));
+    assert_eq!(out, vec![delete_exp(SINGAPORE, FIELD_50_INT)]);
+}
+
+#[test]
+fn test_state_keys_are_stable() {
+    // State on disk is looked up by these bytes after a restart, so they must not depend on the
+    // process that wrote them.
+    assert_eq!(
+        encode_key(&[Field::Int(1), Field::String("a".to_string())]).unwrap(),
+        vec![
+            2, 0, 0, 0, 0, 0, 0, 0, // number of values
+            1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, // Field::Int(1)
+            4, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, b'a', // Field::String("a")
+        ]
+    );
+
+    // Equal values are the same key.
+    assert_eq!(
+        encode_key(&[Field::Float(OrderedFloat(-0.0))]).unwrap(),
+        encode_key(&[Field::Float(OrderedFloat(0.0))]).unwrap()
+    );
+    assert_eq!(
+        encode_key(&[Field::Decimal(Decimal::new(150, 2))]).unwrap(),
+        encode_key(&[Field::Decimal(Decimal::new(15, 1))]).unwrap()
+    );
+
+    // Different values never share a key.
+    assert_ne!(
+        encode_key(&[
+            Field::String("ab".to_string()),
+            Field::String("c".to_string())
+        ])
+        .unwrap(),
+        encode_key(&[
+            Field::String("a".to_string()),
+            Field::String("bc".to_string())
+        ])
+        .unwrap()
+    );
+    assert_ne!(
+        encode_key(&[Field::Int(1)]).unwrap(),
+        encode_key(&[Field::UInt(1)]).unwrap()
+    );
+    assert!(!encode_key(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_aggregation_state_reset() {
+    let tmp_dir = TempDir::new("aggregation_state").unwrap();
+    let state_options = StateOptions::disk(tmp_dir.path().to_path_buf());
+    let epoch = Epoch::from(0, NodeHandle::new(None, "source".to_string()), 0, 0);
+
+    let mut processor = init_processor_with_state(
+        SQL,
+        HashMap::from([(DEFAULT_PORT_HANDLE, init_input_schema(Int, "SUM"))]),
+        &state_options,
+    )
+    .unwrap();
+    output!(processor, insert_field(ITALY, FIELD_100_INT));
+    processor.commit(&epoch).unwrap();
+    drop(processor);
+
+    // Uncommitted epochs don't match the state.
+    let mut processor = init_processor_with_state(
+        SQL,
+        HashMap::from([(DEFAULT_PORT_HANDLE, init_input_schema(Int, "SUM"))]),
+        &state_options,
+    )
+    .unwrap();
+    let next_epoch = Epoch::from(1, NodeHandle::new(None, "source".to_string()), 1, 0);
+    assert!(!processor.is_resumable(&next_epoch.details));
+
+    // The pipeline starts over with empty state.
+    processor.reset().unwrap();
+    assert!(!processor.is_resumable(&epoch.details));
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(out, vec![insert_exp(ITALY, FIELD_100_INT)]);
+}
+
+#[test]
+fn test_aggregation_partition_state_depends_on_num_partitions() {
+    let tmp_dir = TempDir::new("aggregation_state").unwrap();
+    let factory = AggregationProcessorFactory::new(
+        *get_select(SQL).unwrap(),
+        false,
+        StateOptions::disk(tmp_dir.path().to_path_buf()),
+        "aggregation".to_string(),
+    );
+    let build_partition = |num_partitions| {
+        factory
+            .build_partition(
+                HashMap::from([(DEFAULT_PORT_HANDLE, init_input_schema(Int, "SUM"))]),
+                HashMap::new(),
+                0,
+                num_partitions,
+            )
+            .unwrap()
+    };
+    let epoch = Epoch::from(0, NodeHandle::new(None, "source".to_string()), 0, 0);
+
+    let mut processor = build_partition(2);
+    processor.commit(&epoch).unwrap();
+    drop(processor);
+
+    assert!(build_partition(2).is_resumable(&epoch.details));
+    // Records would be partitioned differently, so the state of 2 partitions isn't reused.
+    assert!(!build_partition(4).is_resumable(&epoch.details));
+}
+
+#[test]
+fn test_aggregation_partitions_survive_restart() {
+    const NUM_PARTITIONS: usize = 2;
+    let tmp_dir = TempDir::new("aggregation_state").unwrap();
+    let factory = AggregationProcessorFactory::new(
+        *get_select(SQL).unwrap(),
+        false,
+        StateOptions::disk(tmp_dir.path().to_path_buf()),
+        "aggregation".to_string(),
+    );
+    let input_schemas = HashMap::from([(DEFAULT_PORT_HANDLE, init_input_schema(Int, "SUM"))]);
+    let Partitioning::Hash(key) = factory.partitioning(&input_schemas).unwrap() else {
+        panic!("GROUP BY must partition by hash");
+    };
+    let build_partitions = || {
+        (0..NUM_PARTITIONS)
+            .map(|partition| {
+                factory
+                    .build_partition(
+                        input_schemas.clone(),
+                        HashMap::new(),
+                        partition,
+                        NUM_PARTITIONS,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    let process = |partitions: &mut Vec<Box<dyn Processor>>, op: Operation| {
+        let Operation::Insert { new } = &op else {
+            panic!("Only inserts are routed in this test");
+        };
+        let hash = key.hash_key(DEFAULT_PORT_HANDLE, new).unwrap();
+        let mut fw = TestChannelForwarder { operations: vec![] };
+        partitions[(hash % NUM_PARTITIONS as u64) as usize]
+            .process(DEFAULT_PORT_HANDLE, op, &mut fw)
+            .unwrap();
+        fw.operations
+    };
+    let epoch = Epoch::from(0, NodeHandle::new(None, "source".to_string()), 0, 0);
+
+    // The route of a group doesn't depend on the process, and the groups land in both partitions.
+    let Operation::Insert { new } = insert_field(ITALY, FIELD_100_INT) else {
+        unreachable!()
+    };
+    assert_eq!(
+        key.hash_key(DEFAULT_PORT_HANDLE, &new).unwrap(),
+        5299912801712538376
+    );
+
+    let mut partitions = build_partitions();
+    for country in [ITALY, SINGAPORE] {
+        assert_eq!(
+            process(&mut partitions, insert_field(country, FIELD_100_INT)),
+            vec![insert_exp(country, FIELD_100_INT)]
+        );
+    }
+    for partition in partitions.iter_mut() {
+        partition.commit(&epoch).unwrap();
+    }
+    drop(partitions);
+
+    // After the restart, every group is routed to the partition that holds its state.
+    let mut partitions = build_partitions();
+    assert!(partitions
+        .iter()
+        .all(|partition| partition.is_resumable(&epoch.details)));
+    for country in [ITALY, SINGAPORE] {
+        assert_eq!(
+            process(&mut partitions, insert_field(country, FIELD_100_INT)),
+            vec![update_exp(country, country, FIELD_100_INT, FIELD_200_INT)]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::pipeline::aggregation::aggregator::{
+    get_aggregator_from_aggregator_type, Aggregator, AggregatorType,
+};
+use crate::pipeline::aggregation::approx_count_distinct::get_register_and_rank;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::{Field, FieldType};
+
+fn float(value: f64) -> Field {
+    Field::Float(OrderedFloat(value))
+}
+
+fn assert_float(field: Field, expected: f64) {
+    let value = field.to_float().unwrap();
+    assert!(
+        (value - expected).abs() < 1e-9,
+        "expected {expected}, got {value}"
+    );
+}
+
+#[test]
+fn test_variance_and_stddev() {
+    let values = [2_f64, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let cases = [
+        (AggregatorType::VarPop, 4.0),
+        (AggregatorType::StddevPop, 2.0),
+        (AggregatorType::Variance, 32.0 / 7.0),
+        (AggregatorType::Stddev, (32_f64 / 7.0).sqrt()),
+    ];
+
+    for (typ, expected) in cases {
+        let mut aggregator = get_aggregator_from_aggregator_type(typ);
+        aggregator.init(FieldType::Float);
+        let mut result = Field::Null;
+        for value in values {
+            result = aggregator.insert(&[float(value)]).unwrap();
+        }
+        assert_float(result, expected);
+
+        // NULLs are ignored
+        assert_float(aggregator.insert(&[Field::Null]).unwrap(), expected);
+
+        // deleting a value gives the same result as never inserting it
+        aggregator.insert(&[float(100.0)]).unwrap();
+        assert_float(aggregator.delete(&[float(100.0)]).unwrap(), expected);
+
+        for value in values {
+            result = aggregator.delete(&[float(value)]).unwrap();
+        }
+        assert_eq!(result, Field::Null);
+    }
+
+    // a sample variance needs two values
+    let mut aggregator = get_aggregator_from_aggregator_type(AggregatorType::Variance);
+    assert_eq!(aggregator.insert(&[Field::Int(1)]).unwrap(), Field::Null);
+    assert_float(aggregator.insert(&[Field::Int(3)]).unwrap(), 2.0);
+    assert_float(
+        aggregator
+            .update(&[Field::Int(3)], &[Field::Int(5)])
+            .unwrap(),
+        8.0,
+    );
+}
+
+#[test]
+fn test_approx_count_distinct() {
+    let mut aggregator = get_aggregator_from_aggregator_type(AggregatorType::ApproxCountDistinct);
+    let mut result = Field::Null;
+    for value in 0..10_000 {
+        // every value twice
+        aggregator.insert(&[Field::Int(value)]).unwrap();
+        result = aggregator.insert(&[Field::Int(value)]).unwrap();
+    }
+    let estimate = result.to_int().unwrap();
+    assert!((9_500..=10_500).contains(&estimate), "estimate {estimate}");
+
+    for value in 0..10_000 {
+        aggregator.delete(&[Field::Int(value)]).unwrap();
+        result = aggregator.delete(&[Field::Int(value)]).unwrap();
+    }
+    assert_eq!(result, Field::Int(0));
+}
+
+#[test]
+fn test_approx_count_distinct_registers_are_stable() {
+    // The registers are kept in state, so they mustn't depend on the process.
+    assert_eq!(get_register_and_rank(&Field::Int(1)).unwrap(), (1798, 4));
+    assert_eq!(get_register_and_rank(&Field::Int(2)).unwrap(), (3430, 2));
+    // Equal values count once.
+    assert_eq!(
+        get_register_and_rank(&float(-0.0)).unwrap(),
+        get_register_and_rank(&float(0.0)).unwrap()
+    );
+}
+
+#[test]
+fn test_string_agg() {
+    let mut aggregator = get_aggregator_from_aggregator_type(AggregatorType::StringAgg);
+    let separator = Field::String(", ".to_string());
+    let value = |s: &str| [Field::String(s.to_string()), separator.clone()];
+
+    assert_eq!(
+        aggregator.insert(&value("b")).unwrap(),
+        Field::String("b".to_string())
+    );
+    aggregator.insert(&value("a")).unwrap();
+    aggregator
+        .insert(&[Field::Null, separator.clone()])
+        .unwrap();
+    assert_eq!(
+        aggregator.insert(&value("b")).unwrap(),
+        Field::String("a, b, b".to_string())
+    );
+    assert_eq!(
+        aggregator.update(&value("b"), &value("c")).unwrap(),
+        Field::String("a, b, c".to_string())
+    );
+    aggregator.delete(&value("a")).unwrap();
+    aggregator.delete(&value("b")).unwrap();
+    assert_eq!(aggregator.delete(&value("c")).unwrap(), Field::Null);
+}
+
+#[test]
+fn test_array_agg() {
+    let mut aggregator = get_aggregator_from_aggregator_type(AggregatorType::ArrayAgg);
+    aggregator.insert(&[Field::Int(3)]).unwrap();
+    aggregator.insert(&[Field::Int(1)]).unwrap();
+    assert_eq!(
+        aggregator.insert(&[Field::Int(3)]).unwrap(),
+        Field::Text("[1,3,3]".to_string())
+    );
+    assert_eq!(
+        aggregator.delete(&[Field::Int(3)]).unwrap(),
+        Field::Text("[1,3]".to_string())
+    );
+    aggregator.delete(&[Field::Int(3)]).unwrap();
+    assert_eq!(aggregator.delete(&[Field::Int(1)]).unwrap(), Field::Null);
+}
+
+#[test]
+fn test_min_max_by() {
+    let row = |value: &str, key: i64| [Field::String(value.to_string()), Field::Int(key)];
+
+    let mut min_by = get_aggregator_from_aggregator_type(AggregatorType::MinBy);
+    let mut max_by = get_aggregator_from_aggregator_type(AggregatorType::MaxBy);
+    for (value, key) in [("b", 2), ("a", 1), ("c", 3)] {
+        min_by.insert(&row(value, key)).unwrap();
+        max_by.insert(&row(value, key)).unwrap();
+    }
+    // a NULL key is ignored
+    assert_eq!(
+        min_by
+            .insert(&[Field::String("z".to_string()), Field::Null])
+            .unwrap(),
+        Field::String("a".to_string())
+    );
+    assert_eq!(
+        max_by.insert(&row("d", 0)).unwrap(),
+        Field::String("c".to_string())
+    );
+
+    assert_eq!(
+        min_by.delete(&row("a", 1)).unwrap(),
+        Field::String("b".to_string())
+    );
+    assert_eq!(
+        max_by.update(&row("c", 3), &row("c", -1)).unwrap(),
+        Field::String("b".to_string())
+    );
+}
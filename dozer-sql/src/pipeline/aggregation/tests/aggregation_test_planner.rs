@@ -1,6 +1,10 @@
+use crate::pipeline::aggregation::factory::AggregationProcessorFactory;
 use crate::pipeline::aggregation::processor::AggregationProcessor;
 use crate::pipeline::planner::projection::CommonPlanner;
+use crate::pipeline::state::StateOptions;
 use crate::pipeline::tests::utils::get_select;
+use dozer_core::node::{Partitioning, ProcessorFactory};
+use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_types::types::{
     Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
 };
@@ -83,6 +87,8 @@ fn test_planner_with_aggregator() {
         projection_planner.having,
         schema,
         projection_planner.post_aggregation_schema,
+        &StateOptions::Memory,
+        "aggregation",
     )
     .unwrap();
 
@@ -118,3 +124,66 @@ fn test_planner_with_aggregator() {
         })
         .unwrap();
 }
+
+#[test]
+fn test_aggregation_partitioning() {
+    let schema = Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "city".to_string(),
+                FieldType::String,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "adults_count".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone();
+    let input_schemas = [(DEFAULT_PORT_HANDLE, schema)].into_iter().collect();
+    let partitioning = |sql: &str| {
+        AggregationProcessorFactory::new(
+            *get_select(sql).unwrap(),
+            false,
+            StateOptions::Memory,
+            "aggregation".to_string(),
+        )
+        .partitioning(&input_schemas)
+        .unwrap()
+    };
+
+    let Partitioning::Hash(key) =
+        partitioning("SELECT city, SUM(adults_count) FROM households GROUP BY city")
+    else {
+        panic!("Grouped aggregation should be hash partitioned");
+    };
+    let record = |city: &str, adults_count| {
+        Record::new(
+            None,
+            vec![Field::String(city.to_string()), Field::Int(adults_count)],
+            None,
+        )
+    };
+    assert_eq!(
+        key.hash_key(DEFAULT_PORT_HANDLE, &record("Johor", 1))
+            .unwrap(),
+        key.hash_key(DEFAULT_PORT_HANDLE, &record("Johor", 2))
+            .unwrap()
+    );
+
+    assert!(matches!(
+        partitioning("SELECT SUM(adults_count) FROM households"),
+        Partitioning::Single
+    ));
+    assert!(matches!(
+        partitioning("SELECT city FROM households"),
+        Partitioning::RoundRobin
+    ));
+}
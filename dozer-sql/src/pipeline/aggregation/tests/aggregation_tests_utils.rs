@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use crate::pipeline::aggregation::processor::AggregationProcessor;
 use crate::pipeline::errors::PipelineError;
 use crate::pipeline::planner::projection::CommonPlanner;
+use crate::pipeline::state::StateOptions;
 use crate::pipeline::tests::utils::get_select;
 use dozer_types::chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use dozer_types::ordered_float::OrderedFloat;
@@ -16,6 +17,14 @@ use std::ops::Div;
 pub(crate) fn init_processor(
     sql: &str,
     input_schemas: HashMap<PortHandle, Schema>,
+) -> Result<AggregationProcessor, PipelineError> {
+    init_processor_with_state(sql, input_schemas, &StateOptions::Memory)
+}
+
+pub(crate) fn init_processor_with_state(
+    sql: &str,
+    input_schemas: HashMap<PortHandle, Schema>,
+    state_options: &StateOptions,
 ) -> Result<AggregationProcessor, PipelineError> {
     let input_schema = input_schemas
         .get(&DEFAULT_PORT_HANDLE)
@@ -33,6 +42,8 @@ pub(crate) fn init_processor(
         projection_planner.having,
         input_schema.clone(),
         projection_planner.post_aggregation_schema,
+        state_options,
+        "aggregation",
     )
     .unwrap_or_else(|e| panic!("{}", e.to_string()));
 
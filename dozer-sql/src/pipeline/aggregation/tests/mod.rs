@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod aggregation_avg_tests;
 #[cfg(test)]
+mod aggregation_count_distinct_tests;
+#[cfg(test)]
 mod aggregation_count_tests;
 #[cfg(test)]
 mod aggregation_having_tests;
@@ -11,6 +13,10 @@ mod aggregation_min_tests;
 #[cfg(test)]
 mod aggregation_null;
 #[cfg(test)]
+mod aggregation_state_tests;
+#[cfg(test)]
+mod aggregation_statistics_tests;
+#[cfg(test)]
 mod aggregation_sum_tests;
 #[cfg(test)]
 mod aggregation_test_planner;
@@ -0,0 +1,144 @@
+use crate::pipeline::aggregation::aggregator::Aggregator;
+use crate::pipeline::errors::{FieldTypes, PipelineError};
+use crate::pipeline::expression::aggregate::AggregateFunctionType;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use crate::{argv, calculate_err_field};
+use dozer_core::errors::ExecutionError::InvalidType;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::serde::{Deserialize, Serialize};
+use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
+
+pub fn validate_variance(
+    function: &AggregateFunctionType,
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let arg = &argv!(args, 0, function)?.get_type(schema)?;
+
+    match arg.return_type {
+        FieldType::Decimal | FieldType::Int | FieldType::UInt | FieldType::Float => Ok(
+            ExpressionType::new(FieldType::Float, true, SourceDefinition::Dynamic, false),
+        ),
+        r => Err(PipelineError::InvalidFunctionArgumentType(
+            function.to_string(),
+            r,
+            FieldTypes::new(vec![
+                FieldType::Decimal,
+                FieldType::UInt,
+                FieldType::Int,
+                FieldType::Float,
+            ]),
+            0,
+        )),
+    }
+}
+
+/// Variance and standard deviation, sample or population.
+///
+/// Keeps the running count, mean and sum of squared differences from the mean (Welford's
+/// algorithm), which can be updated for both inserted and deleted values.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(crate = "dozer_types::serde")]
+pub struct VarianceAggregator {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    population: bool,
+    stddev: bool,
+}
+
+impl VarianceAggregator {
+    pub fn new(population: bool, stddev: bool) -> Self {
+        Self {
+            count: 0,
+            mean: 0_f64,
+            m2: 0_f64,
+            population,
+            stddev,
+        }
+    }
+
+    fn function(&self) -> AggregateFunctionType {
+        match (self.population, self.stddev) {
+            (false, false) => AggregateFunctionType::Variance,
+            (true, false) => AggregateFunctionType::VarPop,
+            (false, true) => AggregateFunctionType::Stddev,
+            (true, true) => AggregateFunctionType::StddevPop,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn remove(&mut self, value: f64) {
+        if self.count <= 1 {
+            self.count = 0;
+            self.mean = 0_f64;
+            self.m2 = 0_f64;
+            return;
+        }
+        let count = self.count as f64;
+        let mean = (count * self.mean - value) / (count - 1_f64);
+        // rounding errors must not make the variance negative
+        self.m2 = (self.m2 - (value - self.mean) * (value - mean)).max(0_f64);
+        self.mean = mean;
+        self.count -= 1;
+    }
+
+    fn get_value(&self) -> Field {
+        let count = if self.population {
+            self.count
+        } else {
+            self.count.saturating_sub(1)
+        };
+        if count == 0 {
+            return Field::Null;
+        }
+        let variance = self.m2 / count as f64;
+        let value = if self.stddev {
+            variance.sqrt()
+        } else {
+            variance
+        };
+        Field::Float(OrderedFloat(value))
+    }
+
+    fn values(&self, fields: &[Field]) -> Result<Vec<f64>, PipelineError> {
+        let function = self.function();
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            if field == &Field::Null {
+                continue;
+            }
+            values.push(calculate_err_field!(field.to_float(), function, field));
+        }
+        Ok(values)
+    }
+}
+
+impl Aggregator for VarianceAggregator {
+    fn init(&mut self, _return_type: FieldType) {}
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        for value in self.values(old)? {
+            self.remove(value);
+        }
+        Ok(self.get_value())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        for value in self.values(new)? {
+            self.add(value);
+        }
+        Ok(self.get_value())
+    }
+}
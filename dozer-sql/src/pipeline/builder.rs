@@ -3,6 +3,11 @@ use crate::pipeline::builder::PipelineError::InvalidQuery;
 use crate::pipeline::errors::PipelineError;
 use crate::pipeline::expression::builder::{ExpressionBuilder, NameOrAlias};
 use crate::pipeline::selection::factory::SelectionProcessorFactory;
+use crate::pipeline::state::StateOptions;
+use crate::pipeline::top_n::builder::{
+    is_aggregation, row_number_limit, take_row_number, top_n_from_query,
+};
+use crate::pipeline::top_n::factory::TopNProcessorFactory;
 use dozer_core::app::AppPipeline;
 use dozer_core::app::PipelineEntryPoint;
 use dozer_core::appsource::AppSourceId;
@@ -53,6 +58,35 @@ pub struct QueryContext {
 
     // Used Sources
     pub used_sources: Vec<String>,
+
+    // Where JOIN and aggregation processors keep their state
+    pub state_options: StateOptions,
+
+    // Number of state stores handed out so far, used to give each one a stable name
+    pub state_store_count: usize,
+
+    // Bound on the ROW_NUMBER() of the subquery being built, pushed down from the enclosing query
+    pub row_number_limit: Option<usize>,
+}
+
+impl QueryContext {
+    pub fn new(state_options: StateOptions) -> Self {
+        Self {
+            state_options,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the names of a new stateful processor and of its state store. Names only depend on
+    /// the SQL, so a pipeline built from the same SQL finds the state it left on disk, and the
+    /// processor can be referred to in the config.
+    pub(crate) fn next_processor_name(&mut self, prefix: &str) -> (String, String) {
+        self.state_store_count += 1;
+        (
+            format!("{prefix}_{}", self.state_store_count),
+            format!("{prefix}_state_{}", self.state_store_count),
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -64,9 +98,20 @@ pub fn statement_to_pipeline(
     sql: &str,
     pipeline: &mut AppPipeline<SchemaSQLContext>,
     override_name: Option<String>,
+) -> Result<QueryContext, PipelineError> {
+    statement_to_pipeline_with_state(sql, pipeline, override_name, StateOptions::default())
+}
+
+/// Same as [`statement_to_pipeline`], with JOIN and aggregation processors keeping their state as
+/// described by `state_options`.
+pub fn statement_to_pipeline_with_state(
+    sql: &str,
+    pipeline: &mut AppPipeline<SchemaSQLContext>,
+    override_name: Option<String>,
+    state_options: StateOptions,
 ) -> Result<QueryContext, PipelineError> {
     let dialect = AnsiDialect {};
-    let mut ctx = QueryContext::default();
+    let mut ctx = QueryContext::new(state_options);
 
     let ast = Parser::parse_sql(&dialect, sql)
         .map_err(|err| PipelineError::InternalError(Box::new(err)))?;
@@ -107,18 +152,7 @@ fn query_to_pipeline(
     stateful: bool,
     pipeline_idx: usize,
 ) -> Result<(), PipelineError> {
-    // return error if there is unsupported syntax
-    if !query.order_by.is_empty() {
-        return Err(PipelineError::UnsupportedSqlError(
-            UnsupportedSqlError::OrderByError,
-        ));
-    }
-
-    if query.limit.is_some() || query.offset.is_some() {
-        return Err(PipelineError::UnsupportedSqlError(
-            UnsupportedSqlError::LimitOffsetError,
-        ));
-    }
+    let mut top_n = top_n_from_query(query)?;
 
     // Attach the first pipeline if there is with clause
     if let Some(with) = &query.with {
@@ -163,6 +197,7 @@ fn query_to_pipeline(
             select_to_pipeline(
                 table_info,
                 *select,
+                top_n.take(),
                 pipeline,
                 query_ctx,
                 stateful,
@@ -171,7 +206,11 @@ fn query_to_pipeline(
         }
         SetExpr::Query(query) => {
             let query_name = format!("subquery_{}", uuid::Uuid::new_v4());
-            let mut ctx = QueryContext::default();
+            let mut ctx = QueryContext {
+                state_options: query_ctx.state_options.clone(),
+                state_store_count: query_ctx.state_store_count,
+                ..Default::default()
+            };
             query_to_pipeline(
                 &TableInfo {
                     name: NameOrAlias(query_name, None),
@@ -183,7 +222,8 @@ fn query_to_pipeline(
                 &mut ctx,
                 stateful,
                 pipeline_idx,
-            )?
+            )?;
+            query_ctx.state_store_count = ctx.state_store_count;
         }
         SetExpr::SetOperation {
             op,
@@ -211,17 +251,27 @@ fn query_to_pipeline(
             ))
         }
     };
+
+    if let Some(top_n) = top_n {
+        insert_top_n_to_pipeline(&table_info.name.0, top_n, pipeline, query_ctx, pipeline_idx)?;
+    }
     Ok(())
 }
 
+/// Builds the pipeline of a SELECT. `top_n` is the `ORDER BY ... LIMIT` of the query, if any.
+#[allow(clippy::too_many_arguments)]
 fn select_to_pipeline(
     table_info: &TableInfo,
-    select: Select,
+    mut select: Select,
+    top_n: Option<TopNProcessorFactory>,
     pipeline: &mut AppPipeline<SchemaSQLContext>,
     query_ctx: &mut QueryContext,
     stateful: bool,
     pipeline_idx: usize,
 ) -> Result<String, PipelineError> {
+    let limit = query_ctx.row_number_limit.take();
+    let row_number = take_row_number(&mut select, limit)?;
+
     // FROM clause
     if select.from.len() != 1 {
         return Err(PipelineError::UnsupportedSqlError(
@@ -238,13 +288,15 @@ fn select_to_pipeline(
     //     pipeline_idx,
     // )?;
 
+    query_ctx.row_number_limit = row_number_limit(&select);
     let connection_info =
         insert_from_to_pipeline(&select.from[0], pipeline, pipeline_idx, query_ctx)?;
+    query_ctx.row_number_limit = None;
 
     let input_nodes = connection_info.input_nodes;
     let output_node = connection_info.output_node;
 
-    let gen_agg_name = format!("agg_{}", uuid::Uuid::new_v4());
+    let (gen_agg_name, agg_state_name) = query_ctx.next_processor_name("agg");
     let gen_selection_name = format!("select_{}", uuid::Uuid::new_v4());
     let (gen_product_name, product_output_port) = output_node;
 
@@ -266,10 +318,28 @@ fn select_to_pipeline(
         }
     }
 
-    let aggregation = AggregationProcessorFactory::new(select.clone(), stateful);
+    // Without aggregation, rows are ranked before the projection, so that PARTITION BY and
+    // ORDER BY can refer to any column of the input. ORDER BY can also refer to column aliases.
+    let (top_n, pre_projection_top_n) = match top_n {
+        Some(top_n) if is_aggregation(&select) => (Some(top_n), None),
+        Some(mut top_n) => {
+            top_n.resolve_aliases(&select.projection);
+            (None, Some(top_n))
+        }
+        None => (None, None),
+    };
+
+    let aggregation = AggregationProcessorFactory::new(
+        select.clone(),
+        stateful,
+        query_ctx.state_options.clone(),
+        agg_state_name,
+    );
 
     pipeline.add_processor(Arc::new(aggregation), &gen_agg_name, vec![]);
 
+    let mut input = (gen_product_name, product_output_port);
+
     // Where clause
     if let Some(selection) = select.selection {
         let selection = SelectionProcessorFactory::new(selection);
@@ -277,30 +347,36 @@ fn select_to_pipeline(
         pipeline.add_processor(Arc::new(selection), &gen_selection_name, vec![]);
 
         pipeline.connect_nodes(
-            &gen_product_name,
-            Some(product_output_port),
+            &input.0,
+            Some(input.1),
             &gen_selection_name,
             Some(DEFAULT_PORT_HANDLE),
             true,
         )?;
+        input = (gen_selection_name, DEFAULT_PORT_HANDLE);
+    }
 
+    for top_n in row_number.into_iter().chain(pre_projection_top_n) {
+        let gen_top_n_name = format!("top_n_{}", uuid::Uuid::new_v4());
+        pipeline.add_processor(Arc::new(top_n), &gen_top_n_name, vec![]);
         pipeline.connect_nodes(
-            &gen_selection_name,
-            Some(DEFAULT_PORT_HANDLE),
-            &gen_agg_name,
-            Some(DEFAULT_PORT_HANDLE),
-            true,
-        )?;
-    } else {
-        pipeline.connect_nodes(
-            &gen_product_name,
-            Some(product_output_port),
-            &gen_agg_name,
+            &input.0,
+            Some(input.1),
+            &gen_top_n_name,
             Some(DEFAULT_PORT_HANDLE),
             true,
         )?;
+        input = (gen_top_n_name, DEFAULT_PORT_HANDLE);
     }
 
+    pipeline.connect_nodes(
+        &input.0,
+        Some(input.1),
+        &gen_agg_name,
+        Some(DEFAULT_PORT_HANDLE),
+        true,
+    )?;
+
     query_ctx.pipeline_map.insert(
         (pipeline_idx, table_info.name.0.to_string()),
         OutputNodeInfo {
@@ -326,7 +402,51 @@ fn select_to_pipeline(
         );
     }
 
-    Ok(gen_agg_name)
+    match top_n {
+        Some(top_n) => {
+            insert_top_n_to_pipeline(&table_info.name.0, top_n, pipeline, query_ctx, pipeline_idx)
+        }
+        None => Ok(gen_agg_name),
+    }
+}
+
+/// Appends a Top-N processor to the output of `table_name`, so that the tables reading from
+/// that output read from the Top-N processor instead.
+fn insert_top_n_to_pipeline(
+    table_name: &str,
+    top_n: TopNProcessorFactory,
+    pipeline: &mut AppPipeline<SchemaSQLContext>,
+    query_ctx: &mut QueryContext,
+    pipeline_idx: usize,
+) -> Result<String, PipelineError> {
+    let input = query_ctx
+        .pipeline_map
+        .get(&(pipeline_idx, table_name.to_string()))
+        .cloned()
+        .ok_or_else(|| InvalidQuery(format!("No output found for {table_name}")))?;
+
+    let gen_top_n_name = format!("top_n_{}", uuid::Uuid::new_v4());
+    pipeline.add_processor(Arc::new(top_n), &gen_top_n_name, vec![]);
+    pipeline.connect_nodes(
+        &input.node,
+        Some(input.port),
+        &gen_top_n_name,
+        Some(DEFAULT_PORT_HANDLE),
+        true,
+    )?;
+
+    for output in query_ctx
+        .pipeline_map
+        .values_mut()
+        .chain(query_ctx.output_tables_map.values_mut())
+    {
+        if output.node == input.node && output.port == input.port {
+            output.node = gen_top_n_name.clone();
+            output.port = DEFAULT_PORT_HANDLE;
+        }
+    }
+
+    Ok(gen_top_n_name)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -357,6 +477,7 @@ fn set_to_pipeline(
         SetExpr::Select(select) => select_to_pipeline(
             &left_table_info,
             *select,
+            None,
             pipeline,
             query_ctx,
             stateful,
@@ -388,6 +509,7 @@ fn set_to_pipeline(
         SetExpr::Select(select) => select_to_pipeline(
             &right_table_info,
             *select,
+            None,
             pipeline,
             query_ctx,
             stateful,
@@ -1,6 +1,8 @@
 #![allow(clippy::enum_variant_names)]
 
 use dozer_core::errors::ExecutionError;
+use dozer_storage::errors::StorageError;
+use dozer_types::bincode;
 use dozer_types::chrono::RoundingError;
 use dozer_types::errors::internal::BoxedError;
 use dozer_types::errors::types::TypeError;
@@ -105,6 +107,12 @@ pub enum PipelineError {
     #[error("Window: {0}")]
     WindowError(#[from] WindowError),
 
+    #[error("Top-N: {0}")]
+    TopNError(#[from] TopNError),
+
+    #[error("State store: {0}")]
+    StateStoreError(#[from] StateStoreError),
+
     #[error("Table Function is not supported")]
     UnsupportedTableFunction,
 
@@ -120,17 +128,12 @@ pub enum PipelineError {
     #[error("Invalid JOIN: {0}")]
     InvalidJoin(String),
 
-    #[error("The JOIN clause is not supported. In this version only INNER, LEFT and RIGHT OUTER JOINs are supported")]
+    #[error("The JOIN clause is not supported. In this version only INNER, LEFT, RIGHT and FULL OUTER and CROSS JOINs are supported")]
     UnsupportedJoinType,
 
-    #[error(
-        "Unsupported JOIN constraint, only ON is allowed as the JOIN constraint using \'=\' and \'AND\' operators"
-    )]
+    #[error("Unsupported JOIN constraint, only ON is allowed as the JOIN constraint")]
     UnsupportedJoinConstraintType,
 
-    #[error("Unsupported JOIN constraint {0} only comparison of fields with \'=\' and \'AND\' operators are allowed in the JOIN ON constraint")]
-    UnsupportedJoinConstraint(String),
-
     #[error("Invalid JOIN constraint on: {0}")]
     InvalidJoinConstraint(String),
 
@@ -169,9 +172,9 @@ pub enum UnsupportedSqlError {
 
     #[error("FROM clause doesn't support \"Comma Syntax\"")]
     FromCommaSyntax,
-    #[error("ORDER BY is not supported in SQL. You could achieve the same by using the ORDER BY operator in the cache and APIs")]
+    #[error("ORDER BY is only supported in SQL together with LIMIT. You could achieve the same by using the ORDER BY operator in the cache and APIs")]
     OrderByError,
-    #[error("Limit and Offset are not supported in SQL. You could achieve the same by using the LIMIT and OFFSET operators in the cache and APIs")]
+    #[error("LIMIT and OFFSET are only supported in SQL together with ORDER BY. You could achieve the same by using the LIMIT and OFFSET operators in the cache and APIs")]
     LimitOffsetError,
     #[error("Select statements should specify INTO for creating output tables")]
     IntoError,
@@ -198,6 +201,8 @@ pub enum OperationError {
     DivisionByZeroOrOverflow,
     #[error("SQL Error: Modulo operation cannot be done.")]
     ModuloByZeroOrOverflow,
+    #[error("SQL Error: Date/time operation cannot be done due to overflow.")]
+    DateTimeOverflow,
 }
 
 #[derive(Error, Debug)]
@@ -218,24 +223,16 @@ pub enum SetError {
 pub enum JoinError {
     #[error("Currently join supports two level of namespacing. For example, `connection1.field1` is valid, but `connection1.n1.field1` is not.")]
     NameSpaceTooLong(String),
-    #[error("Invalid Join constraint on : {0}")]
-    InvalidJoinConstraint(String),
     #[error("Ambigous field specified in join : {0}")]
     AmbiguousField(String),
-    #[error("Invalid Field specified in join : {0}")]
-    InvalidFieldSpecified(String),
-    #[error("Unsupported Join constraint {0} only comparison of fields with \'=\' and \'AND\' operators are allowed in the JOIN ON constraint")]
-    UnsupportedJoinConstraint(String),
-    #[error(
-        "Unsupported Join constraint operator {0}, only \'=\' and \'AND\' operators are allowed in the JOIN ON constraint"
-    )]
-    UnsupportedJoinConstraintOperator(String),
-    #[error(
-        "Unsupported Join constraint, only ON is allowed as the JOIN constraint using \'=\' and \'AND\' operators"
-    )]
+    #[error("Unsupported Join constraint, only ON is allowed as the JOIN constraint")]
     UnsupportedJoinConstraintType,
     #[error("Unsupported Join type")]
     UnsupportedJoinType,
+    #[error("Join state: {0}")]
+    StateStoreError(#[from] StateStoreError),
+    #[error("Error evaluating the JOIN condition: {0}")]
+    ConditionEvaluation(#[source] BoxedError),
 }
 
 #[derive(Error, Debug)]
@@ -329,3 +326,42 @@ pub enum WindowError {
     #[error("WINDOW functions require alias")]
     NoAlias,
 }
+
+#[derive(Error, Debug)]
+pub enum StateStoreError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("Failed to serialize processor state: {0}")]
+    Serialization(#[source] bincode::Error),
+    #[error("Failed to deserialize processor state: {0}")]
+    Deserialization(#[source] bincode::Error),
+    #[error("Processor state is corrupted, record {0} is missing")]
+    MissingRecord(u64),
+}
+
+#[derive(Error, Debug)]
+pub enum TopNError {
+    #[error("LIMIT and OFFSET must be non-negative integers, found {0}")]
+    InvalidRowCount(String),
+
+    #[error("NULLS FIRST and NULLS LAST are not supported in ORDER BY")]
+    UnsupportedNullsOrdering,
+
+    #[error("Window function {0} is not supported, only ROW_NUMBER() is")]
+    UnsupportedWindowFunction(String),
+
+    #[error("Only one ROW_NUMBER() is supported per SELECT")]
+    MultipleRowNumbers,
+
+    #[error("ROW_NUMBER() doesn't take arguments")]
+    RowNumberArguments,
+
+    #[error("Window frames are not supported with ROW_NUMBER()")]
+    UnsupportedWindowFrame,
+
+    #[error("ROW_NUMBER() requires an ORDER BY in its OVER clause")]
+    RowNumberWithoutOrderBy,
+
+    #[error("ROW_NUMBER() is not supported in a SELECT with aggregations or GROUP BY")]
+    RowNumberWithAggregation,
+}
@@ -9,6 +9,16 @@ pub enum AggregateFunctionType {
     Max,
     Min,
     Sum,
+    CountDistinct,
+    ApproxCountDistinct,
+    Stddev,
+    StddevPop,
+    Variance,
+    VarPop,
+    StringAgg,
+    ArrayAgg,
+    MinBy,
+    MaxBy,
 }
 
 impl AggregateFunctionType {
@@ -19,9 +29,30 @@ impl AggregateFunctionType {
             "max" => Ok(AggregateFunctionType::Max),
             "min" => Ok(AggregateFunctionType::Min),
             "sum" => Ok(AggregateFunctionType::Sum),
+            "approx_count_distinct" => Ok(AggregateFunctionType::ApproxCountDistinct),
+            "stddev" | "stddev_samp" => Ok(AggregateFunctionType::Stddev),
+            "stddev_pop" => Ok(AggregateFunctionType::StddevPop),
+            "variance" | "var_samp" => Ok(AggregateFunctionType::Variance),
+            "var_pop" => Ok(AggregateFunctionType::VarPop),
+            "string_agg" => Ok(AggregateFunctionType::StringAgg),
+            "array_agg" => Ok(AggregateFunctionType::ArrayAgg),
+            "min_by" => Ok(AggregateFunctionType::MinBy),
+            "max_by" => Ok(AggregateFunctionType::MaxBy),
             _ => Err(InvalidFunction(name.to_string())),
         }
     }
+
+    /// The aggregate computed by `name(DISTINCT ...)`.
+    pub(crate) fn distinct(self) -> Result<AggregateFunctionType, PipelineError> {
+        match self {
+            AggregateFunctionType::Count => Ok(AggregateFunctionType::CountDistinct),
+            // The result does not depend on duplicates.
+            AggregateFunctionType::Max
+            | AggregateFunctionType::Min
+            | AggregateFunctionType::ApproxCountDistinct => Ok(self),
+            _ => Err(InvalidFunction(format!("{self}(DISTINCT ...)"))),
+        }
+    }
 }
 
 impl Display for AggregateFunctionType {
@@ -32,6 +63,16 @@ impl Display for AggregateFunctionType {
             AggregateFunctionType::Max => f.write_str("MAX"),
             AggregateFunctionType::Min => f.write_str("MIN"),
             AggregateFunctionType::Sum => f.write_str("SUM"),
+            AggregateFunctionType::CountDistinct => f.write_str("COUNT DISTINCT"),
+            AggregateFunctionType::ApproxCountDistinct => f.write_str("APPROX_COUNT_DISTINCT"),
+            AggregateFunctionType::Stddev => f.write_str("STDDEV"),
+            AggregateFunctionType::StddevPop => f.write_str("STDDEV_POP"),
+            AggregateFunctionType::Variance => f.write_str("VARIANCE"),
+            AggregateFunctionType::VarPop => f.write_str("VAR_POP"),
+            AggregateFunctionType::StringAgg => f.write_str("STRING_AGG"),
+            AggregateFunctionType::ArrayAgg => f.write_str("ARRAY_AGG"),
+            AggregateFunctionType::MinBy => f.write_str("MIN_BY"),
+            AggregateFunctionType::MaxBy => f.write_str("MAX_BY"),
         }
     }
 }
@@ -1,10 +1,11 @@
 use dozer_types::{
     ordered_float::OrderedFloat,
-    types::{Field, FieldDefinition, Schema, SourceDefinition},
+    types::{DozerInterval, Field, FieldDefinition, FieldType, Schema, SourceDefinition},
 };
 use sqlparser::ast::{
-    BinaryOperator as SqlBinaryOperator, DataType, Expr as SqlExpr, Expr, Function, FunctionArg,
-    FunctionArgExpr, Ident, TrimWhereField, UnaryOperator as SqlUnaryOperator, Value as SqlValue,
+    BinaryOperator as SqlBinaryOperator, DataType, DateTimeField, Expr as SqlExpr, Expr, Function,
+    FunctionArg, FunctionArgExpr, Ident, TrimWhereField, UnaryOperator as SqlUnaryOperator,
+    Value as SqlValue,
 };
 
 use crate::pipeline::errors::PipelineError::{
@@ -13,10 +14,11 @@ use crate::pipeline::errors::PipelineError::{
 };
 use crate::pipeline::errors::{PipelineError, SqlError};
 use crate::pipeline::expression::aggregate::AggregateFunctionType;
+use crate::pipeline::expression::conditional::{get_case_type, get_coalesce_type};
 use crate::pipeline::expression::datetime::DateTimeFunctionType;
 
-use crate::pipeline::expression::execution::Expression;
 use crate::pipeline::expression::execution::Expression::{GeoFunction, ScalarFunction};
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
 use crate::pipeline::expression::geo::common::GeoFunctionType;
 use crate::pipeline::expression::operator::{BinaryOperatorType, UnaryOperatorType};
 use crate::pipeline::expression::scalar::common::ScalarFunctionType;
@@ -103,12 +105,96 @@ impl ExpressionBuilder {
                 escape_char,
                 schema,
             ),
+            SqlExpr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => self.parse_sql_between_operator(
+                parse_aggregations,
+                negated,
+                expr,
+                low,
+                high,
+                schema,
+            ),
+            SqlExpr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.parse_sql_case_operator(
+                parse_aggregations,
+                operand,
+                conditions,
+                results,
+                else_result,
+                schema,
+            ),
+            SqlExpr::InList {
+                expr,
+                list,
+                negated,
+            } => self.parse_sql_in_list_operator(parse_aggregations, expr, list, negated, schema),
+            SqlExpr::IsDistinctFrom(left, right) => self.parse_sql_distinct_operator(
+                parse_aggregations,
+                left,
+                BinaryOperatorType::IsDistinctFrom,
+                right,
+                schema,
+            ),
+            SqlExpr::IsNotDistinctFrom(left, right) => self.parse_sql_distinct_operator(
+                parse_aggregations,
+                left,
+                BinaryOperatorType::IsNotDistinctFrom,
+                right,
+                schema,
+            ),
+            SqlExpr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => self.parse_sql_substring_function(
+                parse_aggregations,
+                expr,
+                substring_from,
+                substring_for,
+                schema,
+            ),
+            SqlExpr::Position { expr, r#in } => {
+                let args = vec![
+                    self.parse_sql_expression(parse_aggregations, expr, schema)?,
+                    self.parse_sql_expression(parse_aggregations, r#in, schema)?,
+                ];
+                Ok(Expression::ScalarFunction {
+                    fun: ScalarFunctionType::Position,
+                    args,
+                })
+            }
             SqlExpr::Cast { expr, data_type } => {
                 self.parse_sql_cast_operator(parse_aggregations, expr, data_type, schema)
             }
             SqlExpr::Extract { field, expr } => {
                 self.parse_sql_extract_operator(parse_aggregations, field, expr, schema)
             }
+            SqlExpr::Interval {
+                value,
+                leading_field,
+                last_field,
+                ..
+            } => Self::parse_sql_interval(value, leading_field, last_field),
+            SqlExpr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => {
+                let arg = self.parse_sql_expression(parse_aggregations, timestamp, schema)?;
+                Ok(Expression::DateTimeFunction {
+                    fun: DateTimeFunctionType::AtTimeZone {
+                        time_zone: time_zone.clone(),
+                    },
+                    args: vec![arg],
+                })
+            }
             _ => Err(InvalidExpression(format!("{expression:?}"))),
         }
     }
@@ -234,6 +320,29 @@ impl ExpressionBuilder {
         Ok(Expression::Trim { arg, what, typ })
     }
 
+    fn parse_sql_substring_function(
+        &mut self,
+        parse_aggregations: bool,
+        expr: &Expr,
+        substring_from: &Option<Box<Expr>>,
+        substring_for: &Option<Box<Expr>>,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        let mut args = vec![self.parse_sql_expression(parse_aggregations, expr, schema)?];
+        // `SUBSTRING(x FOR n)` starts at the first character
+        args.push(match substring_from {
+            Some(from) => self.parse_sql_expression(parse_aggregations, from, schema)?,
+            None => Expression::Literal(Field::Int(1)),
+        });
+        if let Some(count) = substring_for {
+            args.push(self.parse_sql_expression(parse_aggregations, count, schema)?);
+        }
+        Ok(Expression::ScalarFunction {
+            fun: ScalarFunctionType::Substring,
+            args,
+        })
+    }
+
     fn parse_sql_function(
         &mut self,
         parse_aggregations: bool,
@@ -254,6 +363,11 @@ impl ExpressionBuilder {
             parse_aggregations,
         ) {
             (Ok(aggr), true) => {
+                let aggr = if sql_function.distinct {
+                    aggr.distinct()?
+                } else {
+                    aggr
+                };
                 let mut arg_expr: Vec<Expression> = Vec::new();
                 for arg in &sql_function.args {
                     let aggregation = self.parse_sql_function_arg(true, arg, schema)?;
@@ -290,18 +404,33 @@ impl ExpressionBuilder {
                     )?);
                 }
 
-                match ScalarFunctionType::new(function_name.as_str()) {
-                    Ok(sft) => Ok(ScalarFunction {
+                if function_name == "coalesce" {
+                    let return_type =
+                        get_coalesce_type(&function_args, &self.typing_schema(schema)?)?
+                            .return_type;
+                    return Ok(Expression::Coalesce {
+                        args: function_args,
+                        return_type,
+                    });
+                }
+                if let Ok(sft) = ScalarFunctionType::new(function_name.as_str()) {
+                    return Ok(ScalarFunction {
                         fun: sft,
-                        args: function_args.clone(),
+                        args: function_args,
+                    });
+                }
+                if let Ok(dft) = DateTimeFunctionType::new(function_name.as_str()) {
+                    return Ok(Expression::DateTimeFunction {
+                        fun: dft,
+                        args: function_args,
+                    });
+                }
+                match GeoFunctionType::new(function_name.as_str()) {
+                    Ok(gft) => Ok(GeoFunction {
+                        fun: gft,
+                        args: function_args,
                     }),
-                    Err(_d) => match GeoFunctionType::new(function_name.as_str()) {
-                        Ok(gft) => Ok(GeoFunction {
-                            fun: gft,
-                            args: function_args.clone(),
-                        }),
-                        Err(_err) => Err(InvalidNestedAggregationFunction(function_name)),
-                    },
+                    Err(_err) => Err(InvalidNestedAggregationFunction(function_name)),
                 }
             }
         }
@@ -427,6 +556,141 @@ impl ExpressionBuilder {
         }
     }
 
+    fn parse_sql_between_operator(
+        &mut self,
+        parse_aggregations: bool,
+        negated: &bool,
+        expr: &Expr,
+        low: &Expr,
+        high: &Expr,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        let arg = self.parse_sql_expression(parse_aggregations, expr, schema)?;
+        let low = self.parse_sql_expression(parse_aggregations, low, schema)?;
+        let high = self.parse_sql_expression(parse_aggregations, high, schema)?;
+        Ok(Expression::Between {
+            arg: Box::new(arg),
+            low: Box::new(low),
+            high: Box::new(high),
+            negated: *negated,
+        })
+    }
+
+    fn parse_sql_case_operator(
+        &mut self,
+        parse_aggregations: bool,
+        operand: &Option<Box<Expr>>,
+        conditions: &[Expr],
+        results: &[Expr],
+        else_result: &Option<Box<Expr>>,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        let operand = match operand {
+            Some(operand) => Some(Box::new(self.parse_sql_expression(
+                parse_aggregations,
+                operand,
+                schema,
+            )?)),
+            None => None,
+        };
+        let conditions = conditions
+            .iter()
+            .map(|condition| self.parse_sql_expression(parse_aggregations, condition, schema))
+            .collect::<Result<Vec<_>, PipelineError>>()?;
+        let results = results
+            .iter()
+            .map(|result| self.parse_sql_expression(parse_aggregations, result, schema))
+            .collect::<Result<Vec<_>, PipelineError>>()?;
+        let else_result = match else_result {
+            Some(else_result) => Some(Box::new(self.parse_sql_expression(
+                parse_aggregations,
+                else_result,
+                schema,
+            )?)),
+            None => None,
+        };
+        let return_type =
+            get_case_type(&results, &else_result, &self.typing_schema(schema)?)?.return_type;
+        Ok(Expression::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+            return_type,
+        })
+    }
+
+    /// `schema` followed by the aggregations built so far, which the built expressions refer to
+    /// as columns from `offset`.
+    fn typing_schema(&self, schema: &Schema) -> Result<Schema, PipelineError> {
+        let mut typing_schema = schema.clone();
+        if self.aggregations.is_empty() || self.offset < schema.fields.len() {
+            return Ok(typing_schema);
+        }
+        // the columns in between are aggregations of other expressions
+        while typing_schema.fields.len() < self.offset {
+            typing_schema.field(
+                FieldDefinition::new(
+                    String::new(),
+                    FieldType::Binary,
+                    true,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            );
+        }
+        for aggregation in &self.aggregations {
+            let aggregation_type = aggregation.get_type(schema)?;
+            typing_schema.field(
+                FieldDefinition::new(
+                    aggregation.to_string(schema),
+                    aggregation_type.return_type,
+                    aggregation_type.nullable,
+                    aggregation_type.source,
+                ),
+                false,
+            );
+        }
+        Ok(typing_schema)
+    }
+
+    fn parse_sql_in_list_operator(
+        &mut self,
+        parse_aggregations: bool,
+        expr: &Expr,
+        list: &[Expr],
+        negated: &bool,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        let arg = self.parse_sql_expression(parse_aggregations, expr, schema)?;
+        let list = list
+            .iter()
+            .map(|item| self.parse_sql_expression(parse_aggregations, item, schema))
+            .collect::<Result<Vec<_>, PipelineError>>()?;
+        Ok(Expression::InList {
+            arg: Box::new(arg),
+            list,
+            negated: *negated,
+        })
+    }
+
+    fn parse_sql_distinct_operator(
+        &mut self,
+        parse_aggregations: bool,
+        left: &Expr,
+        operator: BinaryOperatorType,
+        right: &Expr,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        let left = self.parse_sql_expression(parse_aggregations, left, schema)?;
+        let right = self.parse_sql_expression(parse_aggregations, right, schema)?;
+        Ok(Expression::BinaryOperator {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        })
+    }
+
     fn parse_sql_extract_operator(
         &mut self,
         parse_aggregations: bool,
@@ -437,10 +701,39 @@ impl ExpressionBuilder {
         let right = self.parse_sql_expression(parse_aggregations, expr, schema)?;
         Ok(Expression::DateTimeFunction {
             fun: DateTimeFunctionType::Extract { field: *field },
-            arg: Box::new(right),
+            args: vec![right],
         })
     }
 
+    fn parse_sql_interval(
+        value: &Expr,
+        leading_field: &Option<DateTimeField>,
+        last_field: &Option<DateTimeField>,
+    ) -> Result<Expression, PipelineError> {
+        let value = match value {
+            SqlExpr::Value(SqlValue::SingleQuotedString(s) | SqlValue::Number(s, _)) => s,
+            _ => return Err(InvalidExpression(format!("INTERVAL {value}"))),
+        };
+        if last_field.is_some() {
+            return Err(InvalidExpression(format!(
+                "INTERVAL '{value}' with a field range is not supported"
+            )));
+        }
+        let interval = match leading_field {
+            // INTERVAL '3' DAY
+            Some(field) => value
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .and_then(|amount| DozerInterval::from_unit(amount, &field.to_string())),
+            // INTERVAL '3 days 4 hours'
+            None => value.parse::<DozerInterval>().ok(),
+        };
+        interval
+            .map(|interval| Expression::Literal(Field::Interval(interval)))
+            .ok_or_else(|| InvalidValue(format!("INTERVAL '{value}'")))
+    }
+
     fn parse_sql_cast_operator(
         &mut self,
         parse_aggregations: bool,
@@ -510,7 +803,6 @@ impl ExpressionBuilder {
         // First, get python function define by name.
         // Then, transfer python function to Expression::PythonUDF
 
-        use dozer_types::types::FieldType;
         use PipelineError::InvalidQuery;
 
         let args = function
@@ -0,0 +1,330 @@
+use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::comparison::{evaluate_eq, evaluate_gte, evaluate_lte};
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::{Field, FieldType, Record, Schema, SourceDefinition};
+
+type ComparisonFunction =
+    fn(&Schema, &Expression, &Expression, &Record) -> Result<Field, PipelineError>;
+
+/// Compares two values with SQL semantics: the result is unknown (`None`) if either is NULL.
+fn compare(
+    function: ComparisonFunction,
+    schema: &Schema,
+    left: Field,
+    right: Field,
+    record: &Record,
+) -> Result<Option<bool>, PipelineError> {
+    if left == Field::Null || right == Field::Null {
+        return Ok(None);
+    }
+    match function(
+        schema,
+        &Expression::Literal(left),
+        &Expression::Literal(right),
+        record,
+    )? {
+        Field::Boolean(result) => Ok(Some(result)),
+        _ => Ok(None),
+    }
+}
+
+fn to_field(value: Option<bool>) -> Field {
+    value.map_or(Field::Null, Field::Boolean)
+}
+
+pub fn evaluate_case(
+    schema: &Schema,
+    operand: &Option<Box<Expression>>,
+    conditions: &[Expression],
+    results: &[Expression],
+    else_result: &Option<Box<Expression>>,
+    return_type: FieldType,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let operand = match operand {
+        Some(operand) => Some(operand.evaluate(record, schema)?),
+        None => None,
+    };
+
+    for (condition, result) in conditions.iter().zip(results) {
+        let value = condition.evaluate(record, schema)?;
+        let matched = match &operand {
+            // `CASE x WHEN value ...` compares the operand with each value
+            Some(operand) => {
+                compare(evaluate_eq, schema, operand.clone(), value, record)? == Some(true)
+            }
+            None => value == Field::Boolean(true),
+        };
+        if matched {
+            return promote(result.evaluate(record, schema)?, return_type);
+        }
+    }
+
+    match else_result {
+        Some(else_result) => promote(else_result.evaluate(record, schema)?, return_type),
+        None => Ok(Field::Null),
+    }
+}
+
+pub fn evaluate_in_list(
+    schema: &Schema,
+    arg: &Expression,
+    list: &[Expression],
+    negated: bool,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let value = arg.evaluate(record, schema)?;
+    if value == Field::Null {
+        return Ok(Field::Null);
+    }
+
+    let mut has_null = false;
+    for item in list {
+        let item = item.evaluate(record, schema)?;
+        match compare(evaluate_eq, schema, value.clone(), item, record)? {
+            Some(true) => return Ok(Field::Boolean(!negated)),
+            Some(false) => {}
+            None => has_null = true,
+        }
+    }
+
+    // a NULL in the list makes a non-match unknown
+    if has_null {
+        Ok(Field::Null)
+    } else {
+        Ok(Field::Boolean(negated))
+    }
+}
+
+pub fn evaluate_between(
+    schema: &Schema,
+    arg: &Expression,
+    low: &Expression,
+    high: &Expression,
+    negated: bool,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let value = arg.evaluate(record, schema)?;
+    let low = low.evaluate(record, schema)?;
+    let high = high.evaluate(record, schema)?;
+
+    let above_low = compare(evaluate_gte, schema, value.clone(), low, record)?;
+    let below_high = compare(evaluate_lte, schema, value, high, record)?;
+    let between = match (above_low, below_high) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    };
+    Ok(to_field(between.map(|between| between != negated)))
+}
+
+pub fn evaluate_coalesce(
+    schema: &Schema,
+    args: &[Expression],
+    return_type: FieldType,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    for arg in args {
+        let value = arg.evaluate(record, schema)?;
+        if value != Field::Null {
+            return promote(value, return_type);
+        }
+    }
+    Ok(Field::Null)
+}
+
+pub fn evaluate_nullif(
+    schema: &Schema,
+    left: &Expression,
+    right: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let left = left.evaluate(record, schema)?;
+    let right = right.evaluate(record, schema)?;
+    if compare(evaluate_eq, schema, left.clone(), right, record)? == Some(true) {
+        Ok(Field::Null)
+    } else {
+        Ok(left)
+    }
+}
+
+fn is_distinct_from(
+    schema: &Schema,
+    left: &Expression,
+    right: &Expression,
+    record: &Record,
+) -> Result<bool, PipelineError> {
+    let left = left.evaluate(record, schema)?;
+    let right = right.evaluate(record, schema)?;
+    Ok(match (&left, &right) {
+        (Field::Null, Field::Null) => false,
+        (Field::Null, _) | (_, Field::Null) => true,
+        _ => compare(evaluate_eq, schema, left, right, record)? != Some(true),
+    })
+}
+
+pub fn evaluate_is_distinct_from(
+    schema: &Schema,
+    left: &Expression,
+    right: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    Ok(Field::Boolean(is_distinct_from(
+        schema, left, right, record,
+    )?))
+}
+
+pub fn evaluate_is_not_distinct_from(
+    schema: &Schema,
+    left: &Expression,
+    right: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    Ok(Field::Boolean(!is_distinct_from(
+        schema, left, right, record,
+    )?))
+}
+
+fn is_null_literal(expression: &Expression) -> bool {
+    expression == &Expression::Literal(Field::Null)
+}
+
+fn is_nullable(expression: &Expression, schema: &Schema) -> Result<bool, PipelineError> {
+    if is_null_literal(expression) {
+        return Ok(true);
+    }
+    Ok(expression.get_type(schema)?.nullable)
+}
+
+/// The type both numeric types are promoted to when mixed: UINT and INT to INT, and integers to
+/// FLOAT or DECIMAL.
+fn get_promoted_type(left: FieldType, right: FieldType) -> Option<FieldType> {
+    match (left, right) {
+        (left, right) if left == right => Some(left),
+        (FieldType::UInt, FieldType::Int) | (FieldType::Int, FieldType::UInt) => {
+            Some(FieldType::Int)
+        }
+        (FieldType::UInt | FieldType::Int, promoted @ (FieldType::Float | FieldType::Decimal))
+        | (promoted @ (FieldType::Float | FieldType::Decimal), FieldType::UInt | FieldType::Int) => {
+            Some(promoted)
+        }
+        _ => None,
+    }
+}
+
+/// Converts a value of one of the types promoted to `field_type` to `field_type`.
+fn promote(value: Field, field_type: FieldType) -> Result<Field, PipelineError> {
+    let promoted = match (&value, field_type) {
+        (Field::UInt(_), FieldType::Int) => value.to_int().map(Field::Int),
+        (Field::UInt(_) | Field::Int(_), FieldType::Float) => value
+            .to_float()
+            .map(|value| Field::Float(OrderedFloat(value))),
+        (Field::UInt(_) | Field::Int(_), FieldType::Decimal) => {
+            value.to_decimal().map(Field::Decimal)
+        }
+        _ => return Ok(value),
+    };
+    promoted.ok_or(PipelineError::InvalidCast {
+        from: value,
+        to: field_type,
+    })
+}
+
+/// The type shared by `expressions`, NULL literals aside, with the nullability of each expression.
+/// Mixed numeric types are promoted as by [`get_promoted_type`].
+fn get_common_type<'a>(
+    expressions: impl IntoIterator<Item = &'a Expression>,
+    schema: &Schema,
+    name: &str,
+) -> Result<(FieldType, Vec<bool>), PipelineError> {
+    let mut common_type = None;
+    let mut nullables = vec![];
+
+    for expression in expressions {
+        if is_null_literal(expression) {
+            nullables.push(true);
+            continue;
+        }
+        let expression_type = expression.get_type(schema)?;
+        nullables.push(expression_type.nullable);
+        common_type = match common_type {
+            None => Some(expression_type.return_type),
+            Some(common_type) => Some(
+                get_promoted_type(common_type, expression_type.return_type).ok_or_else(|| {
+                    PipelineError::InvalidExpression(format!(
+                        "{name} values have different types {common_type:?} and {:?}",
+                        expression_type.return_type
+                    ))
+                })?,
+            ),
+        };
+    }
+
+    let common_type = common_type.ok_or_else(|| {
+        PipelineError::InvalidExpression(format!(
+            "cannot infer the type of {name}, all values are NULL"
+        ))
+    })?;
+    Ok((common_type, nullables))
+}
+
+pub fn get_case_type(
+    results: &[Expression],
+    else_result: &Option<Box<Expression>>,
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let (return_type, nullables) =
+        get_common_type(results.iter().chain(else_result.as_deref()), schema, "CASE")?;
+    // without ELSE, no matching condition gives NULL
+    let nullable = else_result.is_none() || nullables.contains(&true);
+    Ok(ExpressionType::new(
+        return_type,
+        nullable,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+pub fn get_coalesce_type(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let (return_type, nullables) = get_common_type(args, schema, "COALESCE")?;
+    Ok(ExpressionType::new(
+        return_type,
+        !nullables.contains(&false),
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+pub fn get_nullif_type(
+    left: &Expression,
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let (return_type, _) = get_common_type([left], schema, "NULLIF")?;
+    Ok(ExpressionType::new(
+        return_type,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Type of a predicate that is NULL when any of `args` is.
+pub fn get_predicate_type<'a>(
+    args: impl IntoIterator<Item = &'a Expression>,
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let mut nullable = false;
+    for arg in args {
+        nullable |= is_nullable(arg, schema)?;
+    }
+    Ok(ExpressionType::new(
+        FieldType::Boolean,
+        nullable,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
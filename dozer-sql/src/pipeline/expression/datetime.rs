@@ -1,21 +1,45 @@
+use crate::argv;
+use crate::pipeline::errors::OperationError;
 use crate::pipeline::errors::PipelineError::{
     InvalidFunctionArgument, InvalidFunctionArgumentType,
 };
+use crate::pipeline::errors::SqlError::Operation;
 use crate::pipeline::errors::{FieldTypes, PipelineError};
 
 use crate::pipeline::expression::datetime::PipelineError::InvalidValue;
 use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
-use dozer_types::chrono::{DateTime, Datelike, Offset, Timelike, Utc};
-use dozer_types::types::{Field, FieldType, Record, Schema};
+use crate::pipeline::expression::operator::BinaryOperatorType;
+use chrono_tz::Tz;
+use dozer_types::chrono::format::{Item, StrftimeItems};
+use dozer_types::chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Months, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Utc,
+};
+use dozer_types::types::{DozerInterval, Field, FieldType, Record, Schema, SourceDefinition};
 use num_traits::ToPrimitive;
 use sqlparser::ast::DateTimeField;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write};
+
+const NANOS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
 pub enum DateTimeFunctionType {
     Extract {
         field: sqlparser::ast::DateTimeField,
     },
+    DateTrunc,
+    DateAdd,
+    DateSub,
+    DateDiff,
+    ToChar,
+    ToTimestamp,
+    /// The time the record is processed at. Not deterministic: the old record of an update or
+    /// delete, and records replayed after a restart, get a different value than when they were
+    /// first processed, so results that depend on it may not be retracted correctly.
+    Now,
+    AtTimeZone {
+        time_zone: String,
+    },
 }
 
 impl Display for DateTimeFunctionType {
@@ -24,47 +48,656 @@ impl Display for DateTimeFunctionType {
             DateTimeFunctionType::Extract { field } => {
                 f.write_str(format!("EXTRACT {field}").as_str())
             }
+            DateTimeFunctionType::DateTrunc => f.write_str("DATE_TRUNC"),
+            DateTimeFunctionType::DateAdd => f.write_str("DATE_ADD"),
+            DateTimeFunctionType::DateSub => f.write_str("DATE_SUB"),
+            DateTimeFunctionType::DateDiff => f.write_str("DATEDIFF"),
+            DateTimeFunctionType::ToChar => f.write_str("TO_CHAR"),
+            DateTimeFunctionType::ToTimestamp => f.write_str("TO_TIMESTAMP"),
+            DateTimeFunctionType::Now => f.write_str("NOW"),
+            DateTimeFunctionType::AtTimeZone { time_zone } => {
+                f.write_str(format!("AT TIME ZONE '{time_zone}'").as_str())
+            }
         }
     }
 }
 
-pub(crate) fn get_datetime_function_type(
+fn validate_arg(
     function: &DateTimeFunctionType,
-    arg: &Expression,
+    args: &[Expression],
+    idx: usize,
+    expected: Vec<FieldType>,
     schema: &Schema,
 ) -> Result<ExpressionType, PipelineError> {
-    let return_type = arg.get_type(schema)?.return_type;
-    if return_type != FieldType::Date && return_type != FieldType::Timestamp {
+    let arg_type = argv!(args, idx, function)?.get_type(schema)?;
+    if !expected.contains(&arg_type.return_type) {
         return Err(InvalidFunctionArgumentType(
             function.to_string(),
-            return_type,
-            FieldTypes::new(vec![FieldType::Date, FieldType::Timestamp]),
-            0,
+            arg_type.return_type,
+            FieldTypes::new(expected),
+            idx,
         ));
     }
-    match function {
-        DateTimeFunctionType::Extract { field: _ } => Ok(ExpressionType::new(
+    Ok(arg_type)
+}
+
+fn datetime_types() -> Vec<FieldType> {
+    vec![FieldType::Date, FieldType::Timestamp]
+}
+
+fn string_types() -> Vec<FieldType> {
+    vec![FieldType::String, FieldType::Text]
+}
+
+pub(crate) fn get_datetime_function_type(
+    function: &DateTimeFunctionType,
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let (return_type, arg_types) = match function {
+        DateTimeFunctionType::Extract { field: _ } => (
             FieldType::Int,
-            false,
-            dozer_types::types::SourceDefinition::Dynamic,
-            false,
-        )),
-    }
+            vec![validate_arg(function, args, 0, datetime_types(), schema)?],
+        ),
+        DateTimeFunctionType::DateTrunc => {
+            let unit = validate_arg(function, args, 0, string_types(), schema)?;
+            let arg = validate_arg(function, args, 1, datetime_types(), schema)?;
+            (arg.return_type, vec![unit, arg])
+        }
+        DateTimeFunctionType::DateAdd | DateTimeFunctionType::DateSub => (
+            FieldType::Timestamp,
+            vec![
+                validate_arg(function, args, 0, datetime_types(), schema)?,
+                validate_arg(function, args, 1, vec![FieldType::Interval], schema)?,
+            ],
+        ),
+        DateTimeFunctionType::DateDiff => (
+            FieldType::Int,
+            vec![
+                validate_arg(function, args, 0, string_types(), schema)?,
+                validate_arg(function, args, 1, datetime_types(), schema)?,
+                validate_arg(function, args, 2, datetime_types(), schema)?,
+            ],
+        ),
+        DateTimeFunctionType::ToChar => (
+            FieldType::String,
+            vec![
+                validate_arg(function, args, 0, datetime_types(), schema)?,
+                validate_arg(function, args, 1, string_types(), schema)?,
+            ],
+        ),
+        DateTimeFunctionType::ToTimestamp => {
+            let mut arg_types = vec![];
+            if args.len() > 1 {
+                arg_types.push(validate_arg(function, args, 0, string_types(), schema)?);
+                arg_types.push(validate_arg(function, args, 1, string_types(), schema)?);
+            } else {
+                let mut expected = string_types();
+                expected.extend([FieldType::Int, FieldType::UInt, FieldType::Float]);
+                arg_types.push(validate_arg(function, args, 0, expected, schema)?);
+            }
+            (FieldType::Timestamp, arg_types)
+        }
+        DateTimeFunctionType::Now => (FieldType::Timestamp, vec![]),
+        DateTimeFunctionType::AtTimeZone { time_zone } => {
+            parse_time_zone(time_zone)?;
+            (
+                FieldType::Timestamp,
+                vec![validate_arg(
+                    function,
+                    args,
+                    0,
+                    vec![FieldType::Timestamp],
+                    schema,
+                )?],
+            )
+        }
+    };
+
+    Ok(ExpressionType::new(
+        return_type,
+        arg_types.iter().any(|arg_type| arg_type.nullable),
+        SourceDefinition::Dynamic,
+        false,
+    ))
 }
 
 impl DateTimeFunctionType {
+    pub fn new(name: &str) -> Result<DateTimeFunctionType, PipelineError> {
+        match name {
+            "date_trunc" => Ok(DateTimeFunctionType::DateTrunc),
+            "date_add" | "dateadd" => Ok(DateTimeFunctionType::DateAdd),
+            "date_sub" => Ok(DateTimeFunctionType::DateSub),
+            "datediff" | "date_diff" => Ok(DateTimeFunctionType::DateDiff),
+            "to_char" => Ok(DateTimeFunctionType::ToChar),
+            "to_timestamp" => Ok(DateTimeFunctionType::ToTimestamp),
+            "now" | "current_timestamp" => Ok(DateTimeFunctionType::Now),
+            _ => Err(PipelineError::InvalidFunction(name.to_string())),
+        }
+    }
+
     pub(crate) fn evaluate(
         &self,
         schema: &Schema,
-        arg: &Expression,
+        args: &[Expression],
         record: &Record,
     ) -> Result<Field, PipelineError> {
         match self {
+            DateTimeFunctionType::Now => return Ok(Field::Timestamp(Utc::now().into())),
             DateTimeFunctionType::Extract { field } => {
-                evaluate_date_part(schema, field, arg, record)
+                return evaluate_date_part(schema, field, argv!(args, 0, self)?, record)
+            }
+            _ => {}
+        }
+
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let value = arg.evaluate(record, schema)?;
+            if value == Field::Null {
+                return Ok(Field::Null);
+            }
+            values.push(value);
+        }
+
+        match self {
+            DateTimeFunctionType::DateTrunc => {
+                let unit = get_string(self, argv!(values, 0, self)?, 0)?;
+                match argv!(values, 1, self)? {
+                    Field::Date(date) => {
+                        let truncated = truncate(date_to_naive(*date)?, self, &unit)?;
+                        Ok(Field::Date(truncated.date()))
+                    }
+                    value => {
+                        let ts = get_timestamp(self, value, 1)?;
+                        let truncated = truncate(ts.naive_local(), self, &unit)?;
+                        Ok(Field::Timestamp(from_local(ts.offset(), truncated)?))
+                    }
+                }
+            }
+            DateTimeFunctionType::DateAdd | DateTimeFunctionType::DateSub => {
+                let ts = get_timestamp(self, argv!(values, 0, self)?, 0)?;
+                let interval = get_interval(self, argv!(values, 1, self)?, 1)?;
+                let interval = if *self == DateTimeFunctionType::DateSub {
+                    interval.checked_neg().ok_or_else(overflow)?
+                } else {
+                    interval
+                };
+                add_interval(ts, &interval).map(Field::Timestamp)
+            }
+            DateTimeFunctionType::DateDiff => {
+                let unit = get_string(self, argv!(values, 0, self)?, 0)?;
+                let start = get_timestamp(self, argv!(values, 1, self)?, 1)?;
+                let end = get_timestamp(self, argv!(values, 2, self)?, 2)?;
+                date_diff(self, &unit, start, end).map(Field::Int)
+            }
+            DateTimeFunctionType::ToChar => {
+                let format = to_chrono_format(&get_string(self, argv!(values, 1, self)?, 1)?);
+                let items = StrftimeItems::new(&format).collect::<Vec<_>>();
+                if items.contains(&Item::Error) {
+                    return Err(InvalidValue(format!("Invalid {self} format {format}")));
+                }
+
+                // Formatting fails if the format refers to a field the value doesn't have, like the hour of a date.
+                let mut formatted = String::new();
+                match argv!(values, 0, self)? {
+                    Field::Date(date) => {
+                        write!(formatted, "{}", date.format_with_items(items.iter()))
+                    }
+                    value => write!(
+                        formatted,
+                        "{}",
+                        get_timestamp(self, value, 0)?.format_with_items(items.iter())
+                    ),
+                }
+                .map_err(|_| InvalidValue(format!("Unable to format value with {format}")))?;
+                Ok(Field::String(formatted))
+            }
+            DateTimeFunctionType::ToTimestamp => {
+                let value = argv!(values, 0, self)?;
+                let ts = match values.get(1) {
+                    Some(format) => {
+                        let format = to_chrono_format(&get_string(self, format, 1)?);
+                        parse_timestamp(&get_string(self, value, 0)?, &format)
+                    }
+                    None => match value {
+                        Field::String(s) | Field::Text(s) => {
+                            parse_timestamp(s, "%Y-%m-%d %H:%M:%S%.f")
+                        }
+                        value => from_epoch_seconds(self, value),
+                    },
+                }?;
+                Ok(Field::Timestamp(ts))
+            }
+            DateTimeFunctionType::AtTimeZone { time_zone } => {
+                let ts = get_timestamp(self, argv!(values, 0, self)?, 0)?;
+                at_time_zone(ts, time_zone).map(Field::Timestamp)
+            }
+            DateTimeFunctionType::Now | DateTimeFunctionType::Extract { .. } => {
+                unreachable!("{self} is evaluated above")
+            }
+        }
+    }
+}
+
+fn overflow() -> PipelineError {
+    PipelineError::SqlError(Operation(OperationError::DateTimeOverflow))
+}
+
+fn get_string(
+    function: &DateTimeFunctionType,
+    value: &Field,
+    idx: usize,
+) -> Result<String, PipelineError> {
+    match value {
+        Field::String(s) | Field::Text(s) => Ok(s.clone()),
+        _ => Err(InvalidFunctionArgument(
+            function.to_string(),
+            value.clone(),
+            idx,
+        )),
+    }
+}
+
+fn get_interval(
+    function: &DateTimeFunctionType,
+    value: &Field,
+    idx: usize,
+) -> Result<DozerInterval, PipelineError> {
+    value
+        .to_interval()
+        .ok_or_else(|| InvalidFunctionArgument(function.to_string(), value.clone(), idx))
+}
+
+fn date_to_naive(date: NaiveDate) -> Result<NaiveDateTime, PipelineError> {
+    date.and_hms_milli_opt(0, 0, 0, 0)
+        .ok_or(InvalidValue(format!(
+            "Unable to cast date {date} to timestamp"
+        )))
+}
+
+fn from_local(
+    offset: &FixedOffset,
+    local: NaiveDateTime,
+) -> Result<DateTime<FixedOffset>, PipelineError> {
+    offset
+        .from_local_datetime(&local)
+        .single()
+        .ok_or_else(|| InvalidValue(format!("Unable to convert {local} to timestamp")))
+}
+
+/// Dates are treated as midnight UTC.
+fn get_timestamp(
+    function: &DateTimeFunctionType,
+    value: &Field,
+    idx: usize,
+) -> Result<DateTime<FixedOffset>, PipelineError> {
+    match value {
+        Field::Timestamp(ts) => Ok(*ts),
+        Field::Date(d) => Ok(DateTime::from_utc(date_to_naive(*d)?, Utc.fix())),
+        _ => Err(InvalidFunctionArgument(
+            function.to_string(),
+            value.clone(),
+            idx,
+        )),
+    }
+}
+
+pub(crate) fn add_interval(
+    ts: DateTime<FixedOffset>,
+    interval: &DozerInterval,
+) -> Result<DateTime<FixedOffset>, PipelineError> {
+    let months = Months::new(interval.months.unsigned_abs());
+    let ts = if interval.months >= 0 {
+        ts.checked_add_months(months)
+    } else {
+        ts.checked_sub_months(months)
+    };
+    ts.and_then(|ts| ts.checked_add_signed(Duration::days(interval.days as i64)))
+        .and_then(|ts| ts.checked_add_signed(Duration::nanoseconds(interval.nanos)))
+        .ok_or_else(overflow)
+}
+
+fn truncate(
+    ts: NaiveDateTime,
+    function: &DateTimeFunctionType,
+    unit: &str,
+) -> Result<NaiveDateTime, PipelineError> {
+    let date = ts.date();
+    let time = ts.time();
+    let (hour, minute, second, nano) =
+        (time.hour(), time.minute(), time.second(), time.nanosecond());
+    let midnight = NaiveTime::from_hms_opt(0, 0, 0);
+    let truncated = match unit.to_lowercase().as_str() {
+        "year" => NaiveDate::from_ymd_opt(date.year(), 1, 1).zip(midnight),
+        "quarter" => {
+            NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1).zip(midnight)
+        }
+        "month" => date.with_day(1).zip(midnight),
+        "week" => {
+            Some(date - Duration::days(date.weekday().num_days_from_monday() as i64)).zip(midnight)
+        }
+        "day" => Some(date).zip(midnight),
+        "hour" => Some(date).zip(NaiveTime::from_hms_opt(hour, 0, 0)),
+        "minute" => Some(date).zip(NaiveTime::from_hms_opt(hour, minute, 0)),
+        "second" => Some(date).zip(NaiveTime::from_hms_opt(hour, minute, second)),
+        "millisecond" => Some(date).zip(NaiveTime::from_hms_milli_opt(
+            hour,
+            minute,
+            second,
+            nano / 1_000_000,
+        )),
+        "microsecond" => Some(date).zip(NaiveTime::from_hms_micro_opt(
+            hour,
+            minute,
+            second,
+            nano / 1_000,
+        )),
+        _ => {
+            return Err(InvalidFunctionArgument(
+                function.to_string(),
+                Field::String(unit.to_string()),
+                0,
+            ))
+        }
+    };
+    truncated
+        .map(|(date, time)| NaiveDateTime::new(date, time))
+        .ok_or_else(|| InvalidValue(format!("Unable to truncate {ts} to {unit}")))
+}
+
+/// Number of `unit` boundaries crossed between `start` and `end`, in the time zone of `start`.
+fn date_diff(
+    function: &DateTimeFunctionType,
+    unit: &str,
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+) -> Result<i64, PipelineError> {
+    let end = end.with_timezone(start.offset()).naive_local();
+    let start = start.naive_local();
+    let months = |ts: &NaiveDateTime| ts.year() as i64 * 12 + ts.month0() as i64;
+    let diff = |unit: &str| -> Result<Duration, PipelineError> {
+        Ok(truncate(end, function, unit)? - truncate(start, function, unit)?)
+    };
+    match unit.to_lowercase().as_str() {
+        "year" => Ok(end.year() as i64 - start.year() as i64),
+        "quarter" => Ok(months(&end) / 3 - months(&start) / 3),
+        "month" => Ok(months(&end) - months(&start)),
+        "week" => Ok(diff("week")?.num_weeks()),
+        "day" => Ok(diff("day")?.num_days()),
+        "hour" => Ok(diff("hour")?.num_hours()),
+        "minute" => Ok(diff("minute")?.num_minutes()),
+        "second" => Ok(diff("second")?.num_seconds()),
+        "millisecond" => Ok(diff("millisecond")?.num_milliseconds()),
+        "microsecond" => diff("microsecond")?.num_microseconds().ok_or_else(overflow),
+        _ => Err(InvalidFunctionArgument(
+            function.to_string(),
+            Field::String(unit.to_string()),
+            0,
+        )),
+    }
+}
+
+/// Translates SQL format patterns such as `YYYY-MM-DD HH24:MI:SS` to `chrono` format strings.
+/// Formats containing `%` are taken to be `chrono` format strings already.
+pub(crate) fn to_chrono_format(format: &str) -> String {
+    if format.contains('%') {
+        return format.to_string();
+    }
+
+    const PATTERNS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("HH24", "%H"),
+        ("HH12", "%I"),
+        ("MONTH", "%B"),
+        ("DDD", "%j"),
+        ("MON", "%b"),
+        ("DAY", "%A"),
+        ("YY", "%y"),
+        ("MM", "%m"),
+        ("DD", "%d"),
+        ("DY", "%a"),
+        ("HH", "%I"),
+        ("MI", "%M"),
+        ("SS", "%S"),
+        ("MS", "%3f"),
+        ("US", "%6f"),
+        ("AM", "%p"),
+        ("PM", "%p"),
+        ("TZ", "%Z"),
+        ("OF", "%:z"),
+    ];
+
+    let mut result = String::new();
+    let mut rest = format;
+    while let Some(c) = rest.chars().next() {
+        // double-quoted text is copied as is
+        if c == '"' {
+            let quoted = &rest[1..];
+            let end = quoted.find('"').unwrap_or(quoted.len());
+            result.push_str(&quoted[..end]);
+            rest = quoted.get(end + 1..).unwrap_or("");
+            continue;
+        }
+        let upper = rest.to_uppercase();
+        match PATTERNS
+            .iter()
+            .find(|(pattern, _)| upper.starts_with(pattern))
+        {
+            Some((pattern, replacement)) => {
+                result.push_str(replacement);
+                rest = &rest[pattern.len()..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
             }
         }
     }
+    result
+}
+
+/// Parses `value` with `format`, in UTC unless the format has a time zone.
+fn parse_timestamp(value: &str, format: &str) -> Result<DateTime<FixedOffset>, PipelineError> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts);
+    }
+    if let Ok(ts) = DateTime::parse_from_str(value, format) {
+        return Ok(ts);
+    }
+    if let Ok(ts) = NaiveDateTime::parse_from_str(value, format) {
+        return Ok(DateTime::from_utc(ts, Utc.fix()));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+        return Ok(DateTime::from_utc(date_to_naive(date)?, Utc.fix()));
+    }
+    Err(InvalidValue(format!(
+        "Unable to parse {value} as a timestamp with format {format}"
+    )))
+}
+
+fn from_epoch_seconds(
+    function: &DateTimeFunctionType,
+    value: &Field,
+) -> Result<DateTime<FixedOffset>, PipelineError> {
+    let seconds = value
+        .to_float()
+        .ok_or_else(|| InvalidFunctionArgument(function.to_string(), value.clone(), 0))?;
+    let nanos = (seconds.fract() * 1_000_000_000.0).round() as u32;
+    match Utc.timestamp_opt(seconds.trunc() as i64, nanos) {
+        dozer_types::chrono::LocalResult::Single(ts) => Ok(ts.into()),
+        _ => Err(InvalidValue(format!(
+            "Unable to convert {seconds} to timestamp"
+        ))),
+    }
+}
+
+enum TimeZoneKind {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+/// Accepts `UTC`, offsets such as `+08:00` and IANA names such as `Europe/Paris`.
+fn parse_time_zone(time_zone: &str) -> Result<TimeZoneKind, PipelineError> {
+    let upper = time_zone.trim().to_uppercase();
+    if upper == "UTC" || upper == "Z" || upper == "GMT" {
+        return Ok(TimeZoneKind::Fixed(Utc.fix()));
+    }
+    if let Some(sign) = upper.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let mut parts = upper[1..].split(':');
+        let hours = parts.next().and_then(|h| h.parse::<i32>().ok());
+        let minutes = parts.next().map_or(Some(0), |m| m.parse::<i32>().ok());
+        let offset = hours
+            .zip(minutes)
+            .map(|(h, m)| (h * 3600 + m * 60) * if sign == '-' { -1 } else { 1 })
+            .and_then(FixedOffset::east_opt);
+        return offset
+            .map(TimeZoneKind::Fixed)
+            .ok_or_else(|| InvalidValue(format!("Invalid time zone {time_zone}")));
+    }
+    time_zone
+        .trim()
+        .parse::<Tz>()
+        .map(TimeZoneKind::Named)
+        .map_err(|_| InvalidValue(format!("Invalid time zone {time_zone}")))
+}
+
+/// The same instant, with the UTC offset of `time_zone` at that instant.
+fn at_time_zone(
+    ts: DateTime<FixedOffset>,
+    time_zone: &str,
+) -> Result<DateTime<FixedOffset>, PipelineError> {
+    Ok(match parse_time_zone(time_zone)? {
+        TimeZoneKind::Fixed(offset) => ts.with_timezone(&offset),
+        TimeZoneKind::Named(tz) => {
+            let local = ts.with_timezone(&tz);
+            local.with_timezone(&local.offset().fix())
+        }
+    })
+}
+
+/// Arithmetic on intervals, and on dates and timestamps with intervals, or `None` if neither
+/// operand is an interval or a date.
+pub(crate) fn evaluate_interval_arithmetic(
+    operator: &str,
+    left: &Field,
+    right: &Field,
+) -> Result<Option<Field>, PipelineError> {
+    let invalid = || {
+        Err(PipelineError::InvalidTypeComparison(
+            left.clone(),
+            right.clone(),
+            operator.to_string(),
+        ))
+    };
+    let sign = |interval: &DozerInterval| match operator {
+        "-" => interval.checked_neg().ok_or_else(overflow),
+        _ => Ok(*interval),
+    };
+    let date_add_days = |date: &NaiveDate, days: i64| {
+        let days = if operator == "-" { -days } else { days };
+        date.checked_add_signed(Duration::days(days))
+            .map(Field::Date)
+            .ok_or_else(overflow)
+    };
+
+    let result = match (left, right, operator) {
+        (Field::Interval(_), Field::Null, _)
+        | (Field::Null, Field::Interval(_), _)
+        | (Field::Date(_), Field::Null, _)
+        | (Field::Null, Field::Date(_), _) => Field::Null,
+
+        (Field::Timestamp(ts), Field::Interval(interval), "+" | "-") => {
+            Field::Timestamp(add_interval(*ts, &sign(interval)?)?)
+        }
+        (Field::Interval(interval), Field::Timestamp(ts), "+") => {
+            Field::Timestamp(add_interval(*ts, interval)?)
+        }
+        (Field::Date(date), Field::Interval(interval), "+" | "-") => {
+            Field::Timestamp(add_interval(
+                DateTime::from_utc(date_to_naive(*date)?, Utc.fix()),
+                &sign(interval)?,
+            )?)
+        }
+        (Field::Interval(interval), Field::Date(date), "+") => Field::Timestamp(add_interval(
+            DateTime::from_utc(date_to_naive(*date)?, Utc.fix()),
+            interval,
+        )?),
+
+        (Field::Date(date), Field::Int(days), "+" | "-") => date_add_days(date, *days)?,
+        (Field::Date(date), Field::UInt(days), "+" | "-") => {
+            date_add_days(date, days.to_i64().ok_or_else(overflow)?)?
+        }
+        (Field::Int(days), Field::Date(date), "+") => date_add_days(date, *days)?,
+        (Field::UInt(days), Field::Date(date), "+") => {
+            date_add_days(date, days.to_i64().ok_or_else(overflow)?)?
+        }
+        (Field::Date(left), Field::Date(right), "-") => Field::Int((*left - *right).num_days()),
+
+        (Field::Interval(left), Field::Interval(right), "+" | "-") => {
+            Field::Interval(left.checked_add(&sign(right)?).ok_or_else(overflow)?)
+        }
+        (Field::Interval(interval), Field::Int(_) | Field::UInt(_), "*" | "/")
+        | (Field::Int(_) | Field::UInt(_), Field::Interval(interval), "*") => {
+            let factor = match (left, right) {
+                (Field::Interval(_), factor) | (factor, _) => {
+                    factor.to_int().ok_or_else(overflow)?
+                }
+            };
+            if operator == "*" {
+                Field::Interval(interval.checked_mul(factor).ok_or_else(overflow)?)
+            } else {
+                Field::Interval(divide_interval(interval, factor)?)
+            }
+        }
+
+        (Field::Interval(_), _, _)
+        | (_, Field::Interval(_), _)
+        | (Field::Date(_), _, _)
+        | (_, Field::Date(_), _) => return invalid(),
+        _ => return Ok(None),
+    };
+    Ok(Some(result))
+}
+
+/// Return type of arithmetic on intervals and dates, matching `evaluate_interval_arithmetic`.
+pub(crate) fn get_interval_arithmetic_type(
+    operator: &BinaryOperatorType,
+    left: FieldType,
+    right: FieldType,
+) -> Option<FieldType> {
+    use BinaryOperatorType::{Add, Div, Mul, Sub};
+    use FieldType::{Date, Int, Interval, Timestamp, UInt};
+    match (left, operator, right) {
+        (Timestamp | Date, Add | Sub, Interval) | (Interval, Add, Timestamp | Date) => {
+            Some(Timestamp)
+        }
+        (Date, Add | Sub, Int | UInt) | (Int | UInt, Add, Date) => Some(Date),
+        (Date, Sub, Date) => Some(Int),
+        (Interval, Add | Sub, Interval)
+        | (Interval, Mul | Div, Int | UInt)
+        | (Int | UInt, Mul, Interval) => Some(Interval),
+        _ => None,
+    }
+}
+
+/// Divides each part, carrying remainders down with 30-day months.
+fn divide_interval(interval: &DozerInterval, divisor: i64) -> Result<DozerInterval, PipelineError> {
+    if divisor == 0 {
+        return Err(PipelineError::SqlError(Operation(
+            OperationError::DivisionByZeroOrOverflow,
+        )));
+    }
+    let months = interval.months as i64;
+    let days = interval.days as i64 + months % divisor * 30;
+    let nanos = (days % divisor)
+        .checked_mul(NANOS_PER_DAY)
+        .and_then(|carry| carry.checked_add(interval.nanos))
+        .ok_or_else(overflow)?;
+    Ok(DozerInterval::new(
+        (months / divisor) as i32,
+        (days / divisor).to_i32().ok_or_else(overflow)?,
+        nanos / divisor,
+    ))
 }
 
 pub(crate) fn evaluate_date_part(
@@ -74,23 +707,11 @@ pub(crate) fn evaluate_date_part(
     record: &Record,
 ) -> Result<Field, PipelineError> {
     let value = arg.evaluate(record, schema)?;
+    if value == Field::Null {
+        return Ok(Field::Null);
+    }
 
-    let ts = match value {
-        Field::Timestamp(ts) => Ok(ts),
-        Field::Date(d) => d
-            .and_hms_milli_opt(0, 0, 0, 0)
-            .map(|ts| DateTime::from_utc(ts, Utc.fix()))
-            .ok_or(InvalidValue(format!(
-                "Unable to cast date {d} to timestamp"
-            ))),
-        _ => {
-            return Err(InvalidFunctionArgument(
-                DateTimeFunctionType::Extract { field: *field }.to_string(),
-                value,
-                0,
-            ))
-        }
-    }?;
+    let ts = get_timestamp(&DateTimeFunctionType::Extract { field: *field }, &value, 0)?;
 
     match field {
         DateTimeField::Dow => ts.weekday().num_days_from_monday().to_i64(),
@@ -1,10 +1,22 @@
+use crate::pipeline::aggregation::approx_count_distinct::validate_approx_count_distinct;
+use crate::pipeline::aggregation::array_agg::validate_array_agg;
 use crate::pipeline::aggregation::avg::validate_avg;
 use crate::pipeline::aggregation::count::validate_count;
+use crate::pipeline::aggregation::count_distinct::validate_count_distinct;
 use crate::pipeline::aggregation::max::validate_max;
 use crate::pipeline::aggregation::min::validate_min;
+use crate::pipeline::aggregation::min_max_by::validate_min_max_by;
+use crate::pipeline::aggregation::string_agg::validate_string_agg;
 use crate::pipeline::aggregation::sum::validate_sum;
+use crate::pipeline::aggregation::variance::validate_variance;
 use crate::pipeline::errors::PipelineError;
-use crate::pipeline::expression::datetime::{get_datetime_function_type, DateTimeFunctionType};
+use crate::pipeline::expression::conditional::{
+    evaluate_between, evaluate_case, evaluate_coalesce, evaluate_in_list, get_case_type,
+    get_coalesce_type, get_predicate_type,
+};
+use crate::pipeline::expression::datetime::{
+    get_datetime_function_type, get_interval_arithmetic_type, DateTimeFunctionType,
+};
 use crate::pipeline::expression::geo::common::{get_geo_function_type, GeoFunctionType};
 use crate::pipeline::expression::operator::{BinaryOperatorType, UnaryOperatorType};
 use crate::pipeline::expression::scalar::common::{get_scalar_function_type, ScalarFunctionType};
@@ -41,7 +53,7 @@ pub enum Expression {
     },
     DateTimeFunction {
         fun: DateTimeFunctionType,
-        arg: Box<Expression>,
+        args: Vec<Expression>,
     },
     AggregateFunction {
         fun: AggregateFunctionType,
@@ -61,6 +73,30 @@ pub enum Expression {
         pattern: Box<Expression>,
         escape: Option<char>,
     },
+    Case {
+        operand: Option<Box<Expression>>,
+        conditions: Vec<Expression>,
+        results: Vec<Expression>,
+        else_result: Option<Box<Expression>>,
+        /// The type the results are promoted to.
+        return_type: FieldType,
+    },
+    Coalesce {
+        args: Vec<Expression>,
+        /// The type the arguments are promoted to.
+        return_type: FieldType,
+    },
+    InList {
+        arg: Box<Expression>,
+        list: Vec<Expression>,
+        negated: bool,
+    },
+    Between {
+        arg: Box<Expression>,
+        low: Box<Expression>,
+        high: Box<Expression>,
+        negated: bool,
+    },
     #[cfg(feature = "python")]
     PythonUDF {
         name: String,
@@ -99,6 +135,19 @@ impl Expression {
                         .as_str()
                     + ")"
             }
+            Expression::AggregateFunction {
+                fun: AggregateFunctionType::CountDistinct,
+                args,
+            } => {
+                "COUNT(DISTINCT ".to_string()
+                    + args
+                        .iter()
+                        .map(|e| e.to_string(schema))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                        .as_str()
+                    + ")"
+            }
             Expression::AggregateFunction { fun, args } => {
                 fun.to_string()
                     + "("
@@ -152,6 +201,66 @@ impl Expression {
                 pattern,
                 escape: _,
             } => arg.to_string(schema) + " LIKE " + pattern.to_string(schema).as_str(),
+            Expression::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+                ..
+            } => {
+                let mut result = "CASE".to_string();
+                if let Some(operand) = operand {
+                    result = result + " " + operand.to_string(schema).as_str();
+                }
+                for (condition, value) in conditions.iter().zip(results) {
+                    result = result
+                        + " WHEN "
+                        + condition.to_string(schema).as_str()
+                        + " THEN "
+                        + value.to_string(schema).as_str();
+                }
+                if let Some(else_result) = else_result {
+                    result = result + " ELSE " + else_result.to_string(schema).as_str();
+                }
+                result + " END"
+            }
+            Expression::Coalesce { args, .. } => {
+                "COALESCE(".to_string()
+                    + args
+                        .iter()
+                        .map(|e| e.to_string(schema))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                        .as_str()
+                    + ")"
+            }
+            Expression::InList { arg, list, negated } => {
+                arg.to_string(schema)
+                    + if *negated { " NOT IN (" } else { " IN (" }
+                    + list
+                        .iter()
+                        .map(|e| e.to_string(schema))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                        .as_str()
+                    + ")"
+            }
+            Expression::Between {
+                arg,
+                low,
+                high,
+                negated,
+            } => {
+                arg.to_string(schema)
+                    + if *negated {
+                        " NOT BETWEEN "
+                    } else {
+                        " BETWEEN "
+                    }
+                    + low.to_string(schema).as_str()
+                    + " AND "
+                    + high.to_string(schema).as_str()
+            }
             Expression::GeoFunction { fun, args } => {
                 fun.to_string()
                     + "("
@@ -163,8 +272,75 @@ impl Expression {
                         .as_str()
                     + ")"
             }
-            Expression::DateTimeFunction { fun, arg } => {
-                fun.to_string() + "(" + arg.to_string(schema).as_str() + ")"
+            Expression::DateTimeFunction { fun, args } => {
+                fun.to_string()
+                    + "("
+                    + args
+                        .iter()
+                        .map(|e| e.to_string(schema))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                        .as_str()
+                    + ")"
+            }
+        }
+    }
+
+    /// Calls `f` with the index of every column read by the expression.
+    pub fn visit_columns(&self, f: &mut impl FnMut(usize)) {
+        match self {
+            Expression::Column { index } => f(*index),
+            Expression::Literal(_) => {}
+            Expression::UnaryOperator { arg, .. } | Expression::Cast { arg, .. } => {
+                arg.visit_columns(f)
+            }
+            Expression::BinaryOperator { left, right, .. } => {
+                left.visit_columns(f);
+                right.visit_columns(f);
+            }
+            Expression::ScalarFunction { args, .. }
+            | Expression::Coalesce { args, .. }
+            | Expression::GeoFunction { args, .. }
+            | Expression::DateTimeFunction { args, .. }
+            | Expression::AggregateFunction { args, .. } => {
+                args.iter().for_each(|arg| arg.visit_columns(f))
+            }
+            #[cfg(feature = "python")]
+            Expression::PythonUDF { args, .. } => args.iter().for_each(|arg| arg.visit_columns(f)),
+            Expression::Trim { arg, what, .. } => {
+                arg.visit_columns(f);
+                if let Some(what) = what {
+                    what.visit_columns(f);
+                }
+            }
+            Expression::Like { arg, pattern, .. } => {
+                arg.visit_columns(f);
+                pattern.visit_columns(f);
+            }
+            Expression::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+                ..
+            } => {
+                if let Some(operand) = operand {
+                    operand.visit_columns(f);
+                }
+                conditions.iter().for_each(|arg| arg.visit_columns(f));
+                results.iter().for_each(|arg| arg.visit_columns(f));
+                if let Some(else_result) = else_result {
+                    else_result.visit_columns(f);
+                }
+            }
+            Expression::InList { arg, list, .. } => {
+                arg.visit_columns(f);
+                list.iter().for_each(|arg| arg.visit_columns(f));
+            }
+            Expression::Between { arg, low, high, .. } => {
+                arg.visit_columns(f);
+                low.visit_columns(f);
+                high.visit_columns(f);
             }
         }
     }
@@ -239,9 +415,36 @@ impl ExpressionExecutor for Expression {
                 pattern,
                 escape,
             } => evaluate_like(schema, arg, pattern, *escape, record),
+            Expression::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+                return_type,
+            } => evaluate_case(
+                schema,
+                operand,
+                conditions,
+                results,
+                else_result,
+                *return_type,
+                record,
+            ),
+            Expression::Coalesce { args, return_type } => {
+                evaluate_coalesce(schema, args, *return_type, record)
+            }
+            Expression::InList { arg, list, negated } => {
+                evaluate_in_list(schema, arg, list, *negated, record)
+            }
+            Expression::Between {
+                arg,
+                low,
+                high,
+                negated,
+            } => evaluate_between(schema, arg, low, high, *negated, record),
             Expression::Cast { arg, typ } => typ.evaluate(schema, arg, record),
             Expression::GeoFunction { fun, args } => fun.evaluate(schema, args, record),
-            Expression::DateTimeFunction { fun, arg } => fun.evaluate(schema, arg, record),
+            Expression::DateTimeFunction { fun, args } => fun.evaluate(schema, args, record),
         }
     }
 
@@ -293,10 +496,22 @@ impl ExpressionExecutor for Expression {
                 pattern,
                 escape: _,
             } => get_like_operator_type(arg, pattern, schema),
+            Expression::Case {
+                results,
+                else_result,
+                ..
+            } => get_case_type(results, else_result, schema),
+            Expression::Coalesce { args, .. } => get_coalesce_type(args, schema),
+            Expression::InList { arg, list, .. } => {
+                get_predicate_type(std::iter::once(arg.as_ref()).chain(list), schema)
+            }
+            Expression::Between { arg, low, high, .. } => {
+                get_predicate_type([arg.as_ref(), low.as_ref(), high.as_ref()], schema)
+            }
             Expression::Cast { arg, typ } => typ.get_return_type(schema, arg),
             Expression::GeoFunction { fun, args } => get_geo_function_type(fun, args, schema),
-            Expression::DateTimeFunction { fun, arg } => {
-                get_datetime_function_type(fun, arg, schema)
+            Expression::DateTimeFunction { fun, args } => {
+                get_datetime_function_type(fun, args, schema)
             }
             #[cfg(feature = "python")]
             Expression::PythonUDF { return_type, .. } => Ok(ExpressionType::new(
@@ -324,6 +539,7 @@ fn get_field_type(field: &Field) -> Option<FieldType> {
         Field::Text(_) => Some(FieldType::Text),
         Field::Date(_) => Some(FieldType::Date),
         Field::Point(_) => Some(FieldType::Point),
+        Field::Interval(_) => Some(FieldType::Interval),
     }
 }
 
@@ -351,15 +567,39 @@ fn get_binary_operator_type(
     right: &Expression,
     schema: &Schema,
 ) -> Result<ExpressionType, PipelineError> {
+    if let BinaryOperatorType::IsDistinctFrom | BinaryOperatorType::IsNotDistinctFrom = operator {
+        // never NULL, and either side may be a NULL literal
+        return Ok(ExpressionType::new(
+            FieldType::Boolean,
+            false,
+            SourceDefinition::Dynamic,
+            false,
+        ));
+    }
+
     let left_field_type = left.get_type(schema)?;
     let right_field_type = right.get_type(schema)?;
+    if let Some(return_type) = get_interval_arithmetic_type(
+        operator,
+        left_field_type.return_type,
+        right_field_type.return_type,
+    ) {
+        return Ok(ExpressionType::new(
+            return_type,
+            false,
+            SourceDefinition::Dynamic,
+            false,
+        ));
+    }
     match operator {
         BinaryOperatorType::Eq
         | BinaryOperatorType::Ne
         | BinaryOperatorType::Gt
         | BinaryOperatorType::Gte
         | BinaryOperatorType::Lt
-        | BinaryOperatorType::Lte => Ok(ExpressionType::new(
+        | BinaryOperatorType::Lte
+        | BinaryOperatorType::IsDistinctFrom
+        | BinaryOperatorType::IsNotDistinctFrom => Ok(ExpressionType::new(
             FieldType::Boolean,
             false,
             SourceDefinition::Dynamic,
@@ -485,5 +725,16 @@ fn get_aggregate_function_type(
         AggregateFunctionType::Max => validate_max(args, schema),
         AggregateFunctionType::Min => validate_min(args, schema),
         AggregateFunctionType::Sum => validate_sum(args, schema),
+        AggregateFunctionType::CountDistinct => validate_count_distinct(args, schema),
+        AggregateFunctionType::ApproxCountDistinct => validate_approx_count_distinct(args, schema),
+        AggregateFunctionType::Stddev
+        | AggregateFunctionType::StddevPop
+        | AggregateFunctionType::Variance
+        | AggregateFunctionType::VarPop => validate_variance(function, args, schema),
+        AggregateFunctionType::StringAgg => validate_string_agg(args, schema),
+        AggregateFunctionType::ArrayAgg => validate_array_agg(args, schema),
+        AggregateFunctionType::MinBy | AggregateFunctionType::MaxBy => {
+            validate_min_max_by(function, args, schema)
+        }
     }
 }
@@ -1,6 +1,7 @@
 use crate::pipeline::errors::OperationError;
 use crate::pipeline::errors::PipelineError;
 use crate::pipeline::errors::SqlError::Operation;
+use crate::pipeline::expression::datetime::evaluate_interval_arithmetic;
 use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
 use dozer_types::rust_decimal::Decimal;
 use dozer_types::types::Schema;
@@ -24,6 +25,10 @@ macro_rules! define_math_operator {
             let left_p = left.evaluate(&record, schema)?;
             let right_p = right.evaluate(&record, schema)?;
 
+            if let Some(result) = evaluate_interval_arithmetic($op, &left_p, &right_p)? {
+                return Ok(result);
+            }
+
             match left_p {
                 Field::Timestamp(left_v) => match right_p {
                     Field::Timestamp(right_v) => match $op {
@@ -614,6 +619,12 @@ pub fn evaluate_minus(
         Field::Int(v) => Ok(Field::Int(-v)),
         Field::Float(v) => Ok(Field::Float(-v)),
         Field::Decimal(v) => Ok(Field::Decimal(v.neg())),
+        Field::Interval(v) => v
+            .checked_neg()
+            .map(Field::Interval)
+            .ok_or(PipelineError::SqlError(Operation(
+                OperationError::DateTimeOverflow,
+            ))),
         not_supported_field => Err(PipelineError::InvalidType(
             not_supported_field,
             "-".to_string(),
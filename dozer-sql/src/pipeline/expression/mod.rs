@@ -3,6 +3,7 @@ mod arg_utils;
 pub mod builder;
 pub mod cast;
 pub mod comparison;
+pub mod conditional;
 mod datetime;
 pub mod execution;
 pub mod geo;
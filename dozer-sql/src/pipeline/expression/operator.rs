@@ -1,5 +1,8 @@
 use crate::pipeline::errors::PipelineError;
 use crate::pipeline::expression::comparison::*;
+use crate::pipeline::expression::conditional::{
+    evaluate_is_distinct_from, evaluate_is_not_distinct_from,
+};
 use crate::pipeline::expression::execution::Expression;
 use crate::pipeline::expression::logical::*;
 use crate::pipeline::expression::mathematical::*;
@@ -47,6 +50,8 @@ pub enum BinaryOperatorType {
     Gte,
     Lt,
     Lte,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
 
     // Logical
     And,
@@ -69,6 +74,8 @@ impl Display for BinaryOperatorType {
             BinaryOperatorType::Gte => f.write_str(">="),
             BinaryOperatorType::Lt => f.write_str("<"),
             BinaryOperatorType::Lte => f.write_str("<="),
+            BinaryOperatorType::IsDistinctFrom => f.write_str(" IS DISTINCT FROM "),
+            BinaryOperatorType::IsNotDistinctFrom => f.write_str(" IS NOT DISTINCT FROM "),
             BinaryOperatorType::And => f.write_str(" AND "),
             BinaryOperatorType::Or => f.write_str(" OR "),
             BinaryOperatorType::Add => f.write_str("+"),
@@ -95,6 +102,12 @@ impl BinaryOperatorType {
             BinaryOperatorType::Gte => evaluate_gte(schema, left, right, record),
             BinaryOperatorType::Lt => evaluate_lt(schema, left, right, record),
             BinaryOperatorType::Lte => evaluate_lte(schema, left, right, record),
+            BinaryOperatorType::IsDistinctFrom => {
+                evaluate_is_distinct_from(schema, left, right, record)
+            }
+            BinaryOperatorType::IsNotDistinctFrom => {
+                evaluate_is_not_distinct_from(schema, left, right, record)
+            }
 
             BinaryOperatorType::And => evaluate_and(schema, left, right, record),
             BinaryOperatorType::Or => evaluate_or(schema, left, right, record),
@@ -1,9 +1,14 @@
 use crate::argv;
 use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::conditional::{evaluate_nullif, get_nullif_type};
 use crate::pipeline::expression::execution::{Expression, ExpressionExecutor, ExpressionType};
 use crate::pipeline::expression::scalar::number::{evaluate_abs, evaluate_round};
 use crate::pipeline::expression::scalar::string::{
-    evaluate_concat, evaluate_length, evaluate_ucase, validate_concat, validate_ucase,
+    evaluate_concat, evaluate_hash, evaluate_initcap, evaluate_left_right, evaluate_length,
+    evaluate_lower, evaluate_pad, evaluate_position, evaluate_regexp_match,
+    evaluate_regexp_replace, evaluate_replace, evaluate_reverse, evaluate_split_part,
+    evaluate_substring, evaluate_ucase, validate_concat, validate_hash, validate_position,
+    validate_string_function, validate_ucase,
 };
 use dozer_types::types::{Field, FieldType, Record, Schema};
 use std::fmt::{Display, Formatter};
@@ -15,6 +20,22 @@ pub enum ScalarFunctionType {
     Ucase,
     Concat,
     Length,
+    Nullif,
+    Lower,
+    Substring,
+    Replace,
+    SplitPart,
+    Position,
+    Lpad,
+    Rpad,
+    RegexpMatch,
+    RegexpReplace,
+    Left,
+    Right,
+    Reverse,
+    Initcap,
+    Md5,
+    Sha256,
 }
 
 impl Display for ScalarFunctionType {
@@ -25,6 +46,22 @@ impl Display for ScalarFunctionType {
             ScalarFunctionType::Ucase => f.write_str("UCASE"),
             ScalarFunctionType::Concat => f.write_str("CONCAT"),
             ScalarFunctionType::Length => f.write_str("LENGTH"),
+            ScalarFunctionType::Nullif => f.write_str("NULLIF"),
+            ScalarFunctionType::Lower => f.write_str("LOWER"),
+            ScalarFunctionType::Substring => f.write_str("SUBSTRING"),
+            ScalarFunctionType::Replace => f.write_str("REPLACE"),
+            ScalarFunctionType::SplitPart => f.write_str("SPLIT_PART"),
+            ScalarFunctionType::Position => f.write_str("POSITION"),
+            ScalarFunctionType::Lpad => f.write_str("LPAD"),
+            ScalarFunctionType::Rpad => f.write_str("RPAD"),
+            ScalarFunctionType::RegexpMatch => f.write_str("REGEXP_MATCH"),
+            ScalarFunctionType::RegexpReplace => f.write_str("REGEXP_REPLACE"),
+            ScalarFunctionType::Left => f.write_str("LEFT"),
+            ScalarFunctionType::Right => f.write_str("RIGHT"),
+            ScalarFunctionType::Reverse => f.write_str("REVERSE"),
+            ScalarFunctionType::Initcap => f.write_str("INITCAP"),
+            ScalarFunctionType::Md5 => f.write_str("MD5"),
+            ScalarFunctionType::Sha256 => f.write_str("SHA256"),
         }
     }
 }
@@ -57,6 +94,48 @@ pub(crate) fn get_scalar_function_type(
             dozer_types::types::SourceDefinition::Dynamic,
             false,
         )),
+        ScalarFunctionType::Nullif => {
+            argv!(args, 1, ScalarFunctionType::Nullif)?;
+            get_nullif_type(argv!(args, 0, ScalarFunctionType::Nullif)?, schema)
+        }
+        ScalarFunctionType::Lower | ScalarFunctionType::Reverse | ScalarFunctionType::Initcap => {
+            validate_string_function(function.clone(), args, 1..=1, &[], schema)
+        }
+        ScalarFunctionType::Substring => {
+            validate_string_function(function.clone(), args, 2..=3, &[1, 2], schema)
+        }
+        ScalarFunctionType::Replace => {
+            validate_string_function(function.clone(), args, 3..=3, &[], schema)
+        }
+        // the optional fourth argument holds the flags
+        ScalarFunctionType::RegexpReplace => {
+            validate_string_function(function.clone(), args, 3..=4, &[], schema)
+        }
+        ScalarFunctionType::SplitPart => {
+            validate_string_function(function.clone(), args, 3..=3, &[2], schema)
+        }
+        ScalarFunctionType::Position => validate_position(args, schema),
+        // the optional third argument is the fill string
+        ScalarFunctionType::Lpad | ScalarFunctionType::Rpad => {
+            validate_string_function(function.clone(), args, 2..=3, &[1], schema)
+        }
+        ScalarFunctionType::Left | ScalarFunctionType::Right => {
+            validate_string_function(function.clone(), args, 2..=2, &[1], schema)
+        }
+        ScalarFunctionType::RegexpMatch => {
+            let return_type =
+                validate_string_function(function.clone(), args, 2..=2, &[], schema)?.return_type;
+            // NULL when the pattern does not match
+            Ok(ExpressionType::new(
+                return_type,
+                true,
+                dozer_types::types::SourceDefinition::Dynamic,
+                false,
+            ))
+        }
+        ScalarFunctionType::Md5 | ScalarFunctionType::Sha256 => {
+            validate_hash(function.clone(), args, schema)
+        }
     }
 }
 
@@ -68,6 +147,22 @@ impl ScalarFunctionType {
             "ucase" => Ok(ScalarFunctionType::Ucase),
             "concat" => Ok(ScalarFunctionType::Concat),
             "length" => Ok(ScalarFunctionType::Length),
+            "nullif" => Ok(ScalarFunctionType::Nullif),
+            "lower" | "lcase" => Ok(ScalarFunctionType::Lower),
+            "substring" | "substr" => Ok(ScalarFunctionType::Substring),
+            "replace" => Ok(ScalarFunctionType::Replace),
+            "split_part" => Ok(ScalarFunctionType::SplitPart),
+            "position" => Ok(ScalarFunctionType::Position),
+            "lpad" => Ok(ScalarFunctionType::Lpad),
+            "rpad" => Ok(ScalarFunctionType::Rpad),
+            "regexp_match" => Ok(ScalarFunctionType::RegexpMatch),
+            "regexp_replace" => Ok(ScalarFunctionType::RegexpReplace),
+            "left" => Ok(ScalarFunctionType::Left),
+            "right" => Ok(ScalarFunctionType::Right),
+            "reverse" => Ok(ScalarFunctionType::Reverse),
+            "initcap" => Ok(ScalarFunctionType::Initcap),
+            "md5" => Ok(ScalarFunctionType::Md5),
+            "sha256" => Ok(ScalarFunctionType::Sha256),
             _ => Err(PipelineError::InvalidFunction(name.to_string())),
         }
     }
@@ -95,6 +190,73 @@ impl ScalarFunctionType {
             ScalarFunctionType::Length => {
                 evaluate_length(schema, argv!(args, 0, ScalarFunctionType::Length)?, record)
             }
+            ScalarFunctionType::Nullif => evaluate_nullif(
+                schema,
+                argv!(args, 0, ScalarFunctionType::Nullif)?,
+                argv!(args, 1, ScalarFunctionType::Nullif)?,
+                record,
+            ),
+            ScalarFunctionType::Lower => {
+                evaluate_lower(schema, argv!(args, 0, ScalarFunctionType::Lower)?, record)
+            }
+            ScalarFunctionType::Substring => evaluate_substring(
+                schema,
+                argv!(args, 0, ScalarFunctionType::Substring)?,
+                argv!(args, 1, ScalarFunctionType::Substring)?,
+                args.get(2),
+                record,
+            ),
+            ScalarFunctionType::Replace => evaluate_replace(
+                schema,
+                argv!(args, 0, ScalarFunctionType::Replace)?,
+                argv!(args, 1, ScalarFunctionType::Replace)?,
+                argv!(args, 2, ScalarFunctionType::Replace)?,
+                record,
+            ),
+            ScalarFunctionType::SplitPart => evaluate_split_part(
+                schema,
+                argv!(args, 0, ScalarFunctionType::SplitPart)?,
+                argv!(args, 1, ScalarFunctionType::SplitPart)?,
+                argv!(args, 2, ScalarFunctionType::SplitPart)?,
+                record,
+            ),
+            ScalarFunctionType::Position => evaluate_position(
+                schema,
+                argv!(args, 0, ScalarFunctionType::Position)?,
+                argv!(args, 1, ScalarFunctionType::Position)?,
+                record,
+            ),
+            ScalarFunctionType::Lpad | ScalarFunctionType::Rpad => evaluate_pad(
+                schema,
+                self.clone(),
+                argv!(args, 0, self)?,
+                argv!(args, 1, self)?,
+                args.get(2),
+                record,
+            ),
+            ScalarFunctionType::RegexpMatch => evaluate_regexp_match(
+                schema,
+                argv!(args, 0, ScalarFunctionType::RegexpMatch)?,
+                argv!(args, 1, ScalarFunctionType::RegexpMatch)?,
+                record,
+            ),
+            ScalarFunctionType::RegexpReplace => evaluate_regexp_replace(schema, args, record),
+            ScalarFunctionType::Left | ScalarFunctionType::Right => evaluate_left_right(
+                schema,
+                self.clone(),
+                argv!(args, 0, self)?,
+                argv!(args, 1, self)?,
+                record,
+            ),
+            ScalarFunctionType::Reverse => {
+                evaluate_reverse(schema, argv!(args, 0, ScalarFunctionType::Reverse)?, record)
+            }
+            ScalarFunctionType::Initcap => {
+                evaluate_initcap(schema, argv!(args, 0, ScalarFunctionType::Initcap)?, record)
+            }
+            ScalarFunctionType::Md5 | ScalarFunctionType::Sha256 => {
+                evaluate_hash(schema, self.clone(), argv!(args, 0, self)?, record)
+            }
         }
     }
 }
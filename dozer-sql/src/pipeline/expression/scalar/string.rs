@@ -1,5 +1,8 @@
-use crate::arg_str;
+use crate::{arg_int, arg_str, argv};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
 
 use crate::pipeline::errors::PipelineError;
 
@@ -10,6 +13,9 @@ use crate::pipeline::expression::scalar::common::ScalarFunctionType;
 
 use dozer_types::types::{Field, FieldType, Record, Schema};
 use like::{Escape, Like};
+use md5::Md5;
+use regex::{Regex, RegexBuilder};
+use sha2::{Digest, Sha256};
 
 pub(crate) fn validate_ucase(
     arg: &Expression,
@@ -211,3 +217,506 @@ pub(crate) fn evaluate_like(
         .map_err(|e| PipelineError::InvalidArgument(e.to_string()))?;
     Ok(result)
 }
+
+fn string_field(return_type: FieldType, value: String) -> Field {
+    match return_type {
+        FieldType::String => Field::String(value),
+        _ => Field::Text(value),
+    }
+}
+
+fn validate_string_arg(
+    arg: &Expression,
+    schema: &Schema,
+    fct: ScalarFunctionType,
+    idx: usize,
+) -> Result<ExpressionType, PipelineError> {
+    validate_arg_type(
+        arg,
+        vec![FieldType::String, FieldType::Text],
+        schema,
+        fct,
+        idx,
+    )
+}
+
+fn validate_int_arg(
+    arg: &Expression,
+    schema: &Schema,
+    fct: ScalarFunctionType,
+    idx: usize,
+) -> Result<ExpressionType, PipelineError> {
+    validate_arg_type(arg, vec![FieldType::Int, FieldType::UInt], schema, fct, idx)
+}
+
+/// Validates that there are `arg_count` `args`, that they are strings except for the integer
+/// arguments at `int_args`, and returns the type of the first argument, nullable if any argument is.
+pub(crate) fn validate_string_function(
+    fct: ScalarFunctionType,
+    args: &[Expression],
+    arg_count: RangeInclusive<usize>,
+    int_args: &[usize],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    if args.len() < *arg_count.start() {
+        return Err(PipelineError::NotEnoughArguments(fct.to_string()));
+    }
+    if args.len() > *arg_count.end() {
+        return Err(PipelineError::TooManyArguments(fct.to_string()));
+    }
+
+    let mut nullable = false;
+    for (idx, arg) in args.iter().enumerate() {
+        let arg_type = if int_args.contains(&idx) {
+            validate_int_arg(arg, schema, fct.clone(), idx)?
+        } else {
+            validate_string_arg(arg, schema, fct.clone(), idx)?
+        };
+        nullable |= arg_type.nullable;
+    }
+
+    let return_type = args[0].get_type(schema)?.return_type;
+    Ok(ExpressionType::new(
+        return_type,
+        nullable,
+        dozer_types::types::SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Evaluates `args`, or returns `None` if any of them is NULL.
+fn evaluate_args(
+    schema: &Schema,
+    args: &[&Expression],
+    record: &Record,
+) -> Result<Option<Vec<Field>>, PipelineError> {
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args {
+        let value = arg.evaluate(record, schema)?;
+        if value == Field::Null {
+            return Ok(None);
+        }
+        values.push(value);
+    }
+    Ok(Some(values))
+}
+
+pub(crate) fn evaluate_lower(
+    schema: &Schema,
+    arg: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let f = arg.evaluate(record, schema)?;
+    if f == Field::Null {
+        return Ok(Field::Null);
+    }
+    let v = arg_str!(f, ScalarFunctionType::Lower, 0)?;
+    Ok(string_field(
+        arg.get_type(schema)?.return_type,
+        v.to_lowercase(),
+    ))
+}
+
+pub(crate) fn evaluate_substring(
+    schema: &Schema,
+    arg: &Expression,
+    from: &Expression,
+    count: Option<&Expression>,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let fct = ScalarFunctionType::Substring;
+    let values = match evaluate_args(schema, &[arg, from], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    // positions are 1-based, and a start before the string shortens the result
+    let start = arg_int!(values[1].clone(), fct, 1)?;
+    let end = match count {
+        Some(count) => {
+            let count = count.evaluate(record, schema)?;
+            if count == Field::Null {
+                return Ok(Field::Null);
+            }
+            let count = arg_int!(count, fct, 2)?;
+            if count < 0 {
+                return Err(PipelineError::InvalidArgument(format!(
+                    "{fct}: negative substring length {count}"
+                )));
+            }
+            Some(start.saturating_add(count))
+        }
+        None => None,
+    };
+
+    let first = start.max(1);
+    let chars = value.chars().skip((first - 1) as usize);
+    let result: String = match end {
+        Some(end) => chars
+            .take(end.saturating_sub(first).max(0) as usize)
+            .collect(),
+        None => chars.collect(),
+    };
+    Ok(string_field(arg.get_type(schema)?.return_type, result))
+}
+
+pub(crate) fn evaluate_replace(
+    schema: &Schema,
+    arg: &Expression,
+    from: &Expression,
+    to: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let fct = ScalarFunctionType::Replace;
+    let values = match evaluate_args(schema, &[arg, from, to], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    let from = arg_str!(values[1].clone(), fct, 1)?;
+    let to = arg_str!(values[2].clone(), fct, 2)?;
+    let result = if from.is_empty() {
+        value
+    } else {
+        value.replace(&from, &to)
+    };
+    Ok(string_field(arg.get_type(schema)?.return_type, result))
+}
+
+pub(crate) fn evaluate_split_part(
+    schema: &Schema,
+    arg: &Expression,
+    delimiter: &Expression,
+    part: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let fct = ScalarFunctionType::SplitPart;
+    let values = match evaluate_args(schema, &[arg, delimiter, part], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    let delimiter = arg_str!(values[1].clone(), fct, 1)?;
+    let part = arg_int!(values[2].clone(), fct, 2)?;
+
+    let parts: Vec<&str> = if delimiter.is_empty() {
+        vec![value.as_str()]
+    } else {
+        value.split(delimiter.as_str()).collect()
+    };
+    // negative parts count from the end
+    let index = match part {
+        0 => {
+            return Err(PipelineError::InvalidArgument(format!(
+                "{fct}: field position must not be zero"
+            )))
+        }
+        part if part > 0 => usize::try_from(part - 1).ok(),
+        part => parts.len().checked_sub(part.unsigned_abs() as usize),
+    };
+    let result = index
+        .and_then(|index| parts.get(index))
+        .map_or(String::new(), |part| part.to_string());
+    Ok(string_field(arg.get_type(schema)?.return_type, result))
+}
+
+pub(crate) fn validate_position(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let nullable =
+        validate_string_function(ScalarFunctionType::Position, args, 2..=2, &[], schema)?.nullable;
+    Ok(ExpressionType::new(
+        FieldType::UInt,
+        nullable,
+        dozer_types::types::SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// 1-based character position of `substring` in `arg`, or 0 if it does not occur.
+pub(crate) fn evaluate_position(
+    schema: &Schema,
+    substring: &Expression,
+    arg: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let fct = ScalarFunctionType::Position;
+    let values = match evaluate_args(schema, &[substring, arg], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let substring = arg_str!(values[0].clone(), fct, 0)?;
+    let value = arg_str!(values[1].clone(), fct, 1)?;
+    let position = value
+        .find(substring.as_str())
+        .map_or(0, |index| value[..index].chars().count() as u64 + 1);
+    Ok(Field::UInt(position))
+}
+
+pub(crate) fn evaluate_pad(
+    schema: &Schema,
+    fct: ScalarFunctionType,
+    arg: &Expression,
+    length: &Expression,
+    fill: Option<&Expression>,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let values = match evaluate_args(schema, &[arg, length], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    let length = arg_int!(values[1].clone(), fct, 1)?.max(0) as usize;
+    let fill: Vec<char> = match fill {
+        Some(fill) => {
+            let fill = fill.evaluate(record, schema)?;
+            if fill == Field::Null {
+                return Ok(Field::Null);
+            }
+            arg_str!(fill, fct, 2)?.chars().collect()
+        }
+        None => vec![' '],
+    };
+
+    let chars: Vec<char> = value.chars().collect();
+    // the string is truncated to `length` when it is already longer
+    let result: String = if chars.len() >= length || fill.is_empty() {
+        chars.into_iter().take(length).collect()
+    } else {
+        let padding = fill.iter().cycle().take(length - chars.len());
+        if fct == ScalarFunctionType::Lpad {
+            padding.chain(chars.iter()).collect()
+        } else {
+            chars.iter().chain(padding).collect()
+        }
+    };
+    Ok(string_field(arg.get_type(schema)?.return_type, result))
+}
+
+thread_local! {
+    /// Compiled literal patterns, by pattern and case insensitivity. Literal patterns come from
+    /// the SQL, so there are only a few of them.
+    static LITERAL_REGEXES: RefCell<HashMap<(String, bool), Regex>> = RefCell::new(HashMap::new());
+}
+
+fn build_regex(
+    fct: &ScalarFunctionType,
+    pattern: &str,
+    case_insensitive: bool,
+) -> Result<Regex, PipelineError> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| PipelineError::InvalidArgument(format!("{fct}: {e}")))
+}
+
+/// Compiles `pattern`, the value of `pattern_expression`, only once if it's a literal.
+fn get_regex(
+    fct: &ScalarFunctionType,
+    pattern_expression: &Expression,
+    pattern: &str,
+    case_insensitive: bool,
+) -> Result<Regex, PipelineError> {
+    if !matches!(pattern_expression, Expression::Literal(_)) {
+        return build_regex(fct, pattern, case_insensitive);
+    }
+
+    LITERAL_REGEXES.with(|regexes| {
+        let key = (pattern.to_string(), case_insensitive);
+        if let Some(regex) = regexes.borrow().get(&key) {
+            return Ok(regex.clone());
+        }
+        let regex = build_regex(fct, pattern, case_insensitive)?;
+        regexes.borrow_mut().insert(key, regex.clone());
+        Ok(regex)
+    })
+}
+
+/// The first match of `pattern` in `arg`, or its first capture group if it has one.
+pub(crate) fn evaluate_regexp_match(
+    schema: &Schema,
+    arg: &Expression,
+    pattern: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let fct = ScalarFunctionType::RegexpMatch;
+    let values = match evaluate_args(schema, &[arg, pattern], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    let pattern_value = arg_str!(values[1].clone(), fct, 1)?;
+
+    let regex = get_regex(&fct, pattern, &pattern_value, false)?;
+    let result = regex.captures(&value).and_then(|captures| {
+        captures
+            .get(1)
+            .or_else(|| captures.get(0))
+            .map(|m| m.as_str().to_string())
+    });
+    Ok(match result {
+        Some(result) => string_field(arg.get_type(schema)?.return_type, result),
+        None => Field::Null,
+    })
+}
+
+/// Replaces the first match of `pattern`, or every match with the `g` flag. The `i` flag makes
+/// the match case insensitive.
+pub(crate) fn evaluate_regexp_replace(
+    schema: &Schema,
+    args: &[Expression],
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let fct = ScalarFunctionType::RegexpReplace;
+    let arg = argv!(args, 0, fct)?;
+    let pattern = argv!(args, 1, fct)?;
+    let replacement = argv!(args, 2, fct)?;
+    let values = match evaluate_args(schema, &[arg, pattern, replacement], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    let pattern_value = arg_str!(values[1].clone(), fct, 1)?;
+    let replacement = arg_str!(values[2].clone(), fct, 2)?;
+
+    let flags = match args.get(3) {
+        Some(flags) => {
+            let flags = flags.evaluate(record, schema)?;
+            if flags == Field::Null {
+                return Ok(Field::Null);
+            }
+            arg_str!(flags, fct, 3)?
+        }
+        None => String::new(),
+    };
+    let mut global = false;
+    let mut case_insensitive = false;
+    for flag in flags.chars() {
+        match flag {
+            'g' => global = true,
+            'i' => case_insensitive = true,
+            flag => {
+                return Err(PipelineError::InvalidArgument(format!(
+                    "{fct}: unsupported flag '{flag}'"
+                )))
+            }
+        }
+    }
+
+    let regex = get_regex(&fct, pattern, &pattern_value, case_insensitive)?;
+    let result = if global {
+        regex.replace_all(&value, replacement.as_str())
+    } else {
+        regex.replace(&value, replacement.as_str())
+    };
+    Ok(string_field(
+        arg.get_type(schema)?.return_type,
+        result.into_owned(),
+    ))
+}
+
+/// `LEFT` and `RIGHT`: the first or last `n` characters, or all but the last or first `-n`
+/// characters when `n` is negative.
+pub(crate) fn evaluate_left_right(
+    schema: &Schema,
+    fct: ScalarFunctionType,
+    arg: &Expression,
+    n: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let values = match evaluate_args(schema, &[arg, n], record)? {
+        Some(values) => values,
+        None => return Ok(Field::Null),
+    };
+    let value = arg_str!(values[0].clone(), fct, 0)?;
+    let n = arg_int!(values[1].clone(), fct, 1)?;
+
+    let length = value.chars().count();
+    let count = if n >= 0 {
+        (n as usize).min(length)
+    } else {
+        length.saturating_sub(n.unsigned_abs() as usize)
+    };
+    let result: String = if fct == ScalarFunctionType::Left {
+        value.chars().take(count).collect()
+    } else {
+        value.chars().skip(length - count).collect()
+    };
+    Ok(string_field(arg.get_type(schema)?.return_type, result))
+}
+
+pub(crate) fn evaluate_reverse(
+    schema: &Schema,
+    arg: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let f = arg.evaluate(record, schema)?;
+    if f == Field::Null {
+        return Ok(Field::Null);
+    }
+    let v = arg_str!(f, ScalarFunctionType::Reverse, 0)?;
+    Ok(string_field(
+        arg.get_type(schema)?.return_type,
+        v.chars().rev().collect(),
+    ))
+}
+
+/// Upper-cases the first letter of each word and lower-cases the rest, words being runs of
+/// alphanumeric characters.
+pub(crate) fn evaluate_initcap(
+    schema: &Schema,
+    arg: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let f = arg.evaluate(record, schema)?;
+    if f == Field::Null {
+        return Ok(Field::Null);
+    }
+    let v = arg_str!(f, ScalarFunctionType::Initcap, 0)?;
+
+    let mut result = String::with_capacity(v.len());
+    let mut word_start = true;
+    for c in v.chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = !c.is_alphanumeric();
+    }
+    Ok(string_field(arg.get_type(schema)?.return_type, result))
+}
+
+pub(crate) fn validate_hash(
+    fct: ScalarFunctionType,
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, PipelineError> {
+    let nullable = validate_string_function(fct, args, 1..=1, &[], schema)?.nullable;
+    Ok(ExpressionType::new(
+        FieldType::String,
+        nullable,
+        dozer_types::types::SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Lower-case hex digest of the argument's UTF-8 bytes.
+pub(crate) fn evaluate_hash(
+    schema: &Schema,
+    fct: ScalarFunctionType,
+    arg: &Expression,
+    record: &Record,
+) -> Result<Field, PipelineError> {
+    let f = arg.evaluate(record, schema)?;
+    if f == Field::Null {
+        return Ok(Field::Null);
+    }
+    let v = arg_str!(f, fct, 0)?;
+    let digest = match fct {
+        ScalarFunctionType::Md5 => hex::encode(Md5::digest(v.as_bytes())),
+        _ => hex::encode(Sha256::digest(v.as_bytes())),
+    };
+    Ok(Field::String(digest))
+}
@@ -0,0 +1,332 @@
+use crate::pipeline::expression::conditional::{
+    evaluate_between, evaluate_in_list, evaluate_is_distinct_from, evaluate_is_not_distinct_from,
+};
+use crate::pipeline::expression::execution::Expression::Literal;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
+use crate::pipeline::expression::scalar::tests::scalar_common::run_scalar_fct;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::rust_decimal::Decimal;
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+
+fn nullable_schema() -> Schema {
+    Schema::empty()
+        .field(
+            FieldDefinition::new(
+                String::from("a"),
+                FieldType::Int,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                String::from("b"),
+                FieldType::Int,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+#[test]
+fn test_case() {
+    let sql = "SELECT CASE WHEN a > 10 THEN 'big' WHEN a > 0 THEN 'small' ELSE 'none' END FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(20), Field::Null]),
+        Field::String("big".to_string())
+    );
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(5), Field::Null]),
+        Field::String("small".to_string())
+    );
+    // a NULL condition does not match
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Null, Field::Null]),
+        Field::String("none".to_string())
+    );
+
+    let sql = "SELECT CASE a WHEN 1 THEN 'one' WHEN 2 THEN 'two' END FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(2), Field::Null]),
+        Field::String("two".to_string())
+    );
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(3), Field::Null]),
+        Field::Null
+    );
+}
+
+#[test]
+fn test_case_type() {
+    let schema = nullable_schema();
+    let case = Expression::Case {
+        operand: None,
+        conditions: vec![Literal(Field::Boolean(true))],
+        results: vec![Literal(Field::Int(1))],
+        else_result: Some(Box::new(Literal(Field::Null))),
+        return_type: FieldType::Int,
+    };
+    let expression_type = case.get_type(&schema).unwrap();
+    assert_eq!(expression_type.return_type, FieldType::Int);
+    assert!(expression_type.nullable);
+
+    let case = Expression::Case {
+        operand: None,
+        conditions: vec![Literal(Field::Boolean(true))],
+        results: vec![Literal(Field::Int(1))],
+        else_result: Some(Box::new(Literal(Field::String("1".to_string())))),
+        return_type: FieldType::Int,
+    };
+    assert!(case.get_type(&schema).is_err());
+}
+
+#[test]
+fn test_coalesce() {
+    let sql = "SELECT COALESCE(a, b, 0) FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Null, Field::Int(2)]),
+        Field::Int(2)
+    );
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(1), Field::Int(2)]),
+        Field::Int(1)
+    );
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Null, Field::Null]),
+        Field::Int(0)
+    );
+
+    let coalesce = Expression::Coalesce {
+        args: vec![Expression::Column { index: 0 }, Literal(Field::Int(0))],
+        return_type: FieldType::Int,
+    };
+    assert!(!coalesce.get_type(&nullable_schema()).unwrap().nullable);
+}
+
+#[test]
+fn test_numeric_promotion() {
+    let schema = Schema::empty()
+        .field(
+            FieldDefinition::new(
+                String::from("u"),
+                FieldType::UInt,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                String::from("f"),
+                FieldType::Float,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone();
+
+    let sql = "SELECT COALESCE(u, 0) FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, schema.clone(), vec![Field::UInt(5), Field::Null]),
+        Field::Int(5)
+    );
+    assert_eq!(
+        run_scalar_fct(sql, schema.clone(), vec![Field::Null, Field::Null]),
+        Field::Int(0)
+    );
+
+    let sql = "SELECT CASE WHEN f > 1.0 THEN u ELSE 0.5 END FROM t";
+    assert_eq!(
+        run_scalar_fct(
+            sql,
+            schema.clone(),
+            vec![Field::UInt(2), Field::Float(OrderedFloat(1.5))]
+        ),
+        Field::Float(OrderedFloat(2.0))
+    );
+    assert_eq!(
+        run_scalar_fct(
+            sql,
+            schema.clone(),
+            vec![Field::UInt(2), Field::Float(OrderedFloat(0.5))]
+        ),
+        Field::Float(OrderedFloat(0.5))
+    );
+
+    let coalesce = Expression::Coalesce {
+        args: vec![
+            Expression::Column { index: 0 },
+            Literal(Field::Decimal(Decimal::new(5, 1))),
+        ],
+        return_type: FieldType::Decimal,
+    };
+    assert_eq!(
+        coalesce.get_type(&schema).unwrap().return_type,
+        FieldType::Decimal
+    );
+
+    let coalesce = Expression::Coalesce {
+        args: vec![
+            Expression::Column { index: 1 },
+            Literal(Field::Decimal(Decimal::new(5, 1))),
+        ],
+        return_type: FieldType::Decimal,
+    };
+    assert!(coalesce.get_type(&schema).is_err());
+}
+
+#[test]
+fn test_nullif() {
+    let sql = "SELECT NULLIF(a, b) FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(1), Field::Int(1)]),
+        Field::Null
+    );
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(1), Field::Int(2)]),
+        Field::Int(1)
+    );
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(1), Field::Null]),
+        Field::Int(1)
+    );
+}
+
+#[test]
+fn test_in_list() {
+    let row = Record::new(None, vec![], None);
+    let schema = Schema::empty();
+    let list = vec![Literal(Field::Int(1)), Literal(Field::Int(2))];
+
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Int(2)), &list, false, &row).unwrap(),
+        Field::Boolean(true)
+    );
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Int(3)), &list, false, &row).unwrap(),
+        Field::Boolean(false)
+    );
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Int(3)), &list, true, &row).unwrap(),
+        Field::Boolean(true)
+    );
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Null), &list, false, &row).unwrap(),
+        Field::Null
+    );
+
+    // no match against a list containing NULL is unknown
+    let list = vec![Literal(Field::Int(1)), Literal(Field::Null)];
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Int(3)), &list, false, &row).unwrap(),
+        Field::Null
+    );
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Int(3)), &list, true, &row).unwrap(),
+        Field::Null
+    );
+    assert_eq!(
+        evaluate_in_list(&schema, &Literal(Field::Int(1)), &list, false, &row).unwrap(),
+        Field::Boolean(true)
+    );
+
+    let sql = "SELECT a IN (1, 2, 3) FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(3), Field::Null]),
+        Field::Boolean(true)
+    );
+    let sql = "SELECT a NOT IN (1, 2, 3) FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(3), Field::Null]),
+        Field::Boolean(false)
+    );
+}
+
+#[test]
+fn test_between() {
+    let row = Record::new(None, vec![], None);
+    let schema = Schema::empty();
+    let low = Literal(Field::Int(1));
+    let high = Literal(Field::Int(10));
+
+    assert_eq!(
+        evaluate_between(&schema, &Literal(Field::Int(5)), &low, &high, false, &row).unwrap(),
+        Field::Boolean(true)
+    );
+    assert_eq!(
+        evaluate_between(&schema, &Literal(Field::Int(10)), &low, &high, false, &row).unwrap(),
+        Field::Boolean(true)
+    );
+    assert_eq!(
+        evaluate_between(&schema, &Literal(Field::Int(11)), &low, &high, true, &row).unwrap(),
+        Field::Boolean(true)
+    );
+    assert_eq!(
+        evaluate_between(&schema, &Literal(Field::Null), &low, &high, false, &row).unwrap(),
+        Field::Null
+    );
+    assert_eq!(
+        evaluate_between(&schema, &Literal(Field::Null), &low, &high, true, &row).unwrap(),
+        Field::Null
+    );
+    // a value below a known lower bound is out of range whatever the upper bound
+    assert_eq!(
+        evaluate_between(
+            &schema,
+            &Literal(Field::Int(0)),
+            &low,
+            &Literal(Field::Null),
+            false,
+            &row
+        )
+        .unwrap(),
+        Field::Boolean(false)
+    );
+
+    let sql = "SELECT a BETWEEN 1 AND b FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(3), Field::Int(5)]),
+        Field::Boolean(true)
+    );
+}
+
+#[test]
+fn test_is_distinct_from() {
+    let row = Record::new(None, vec![], None);
+    let schema = Schema::empty();
+    let cases = [
+        (Field::Null, Field::Null, false),
+        (Field::Int(1), Field::Null, true),
+        (Field::Null, Field::Int(1), true),
+        (Field::Int(1), Field::Int(1), false),
+        (Field::Int(1), Field::Int(2), true),
+    ];
+
+    for (left, right, distinct) in cases {
+        let left = Literal(left);
+        let right = Literal(right);
+        assert_eq!(
+            evaluate_is_distinct_from(&schema, &left, &right, &row).unwrap(),
+            Field::Boolean(distinct)
+        );
+        assert_eq!(
+            evaluate_is_not_distinct_from(&schema, &left, &right, &row).unwrap(),
+            Field::Boolean(!distinct)
+        );
+    }
+
+    let sql = "SELECT a IS DISTINCT FROM b FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Null, Field::Null]),
+        Field::Boolean(false)
+    );
+    let sql = "SELECT a IS NOT DISTINCT FROM b FROM t";
+    assert_eq!(
+        run_scalar_fct(sql, nullable_schema(), vec![Field::Int(1), Field::Null]),
+        Field::Boolean(false)
+    );
+}
@@ -1,11 +1,13 @@
-use crate::pipeline::expression::datetime::evaluate_date_part;
+use crate::pipeline::expression::datetime::{evaluate_date_part, DateTimeFunctionType};
 use crate::pipeline::expression::execution::Expression;
 use dozer_types::chrono::{
     DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike,
 };
 
 use crate::pipeline::expression::scalar::tests::scalar_common::run_scalar_fct;
-use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+use dozer_types::types::{
+    DozerInterval, Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition,
+};
 use num_traits::ToPrimitive;
 use proptest::prelude::*;
 use sqlparser::ast::DateTimeField;
@@ -165,6 +167,242 @@ fn test_timestamp_diff() {
     assert_eq!(f, Field::Int(1000 * 1000 * 1000));
 }
 
+fn timestamp_schema() -> Schema {
+    Schema::empty()
+        .field(
+            FieldDefinition::new(
+                String::from("ts"),
+                FieldType::Timestamp,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                String::from("d"),
+                FieldType::Date,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn timestamp(value: &str) -> Field {
+    Field::Timestamp(DateTime::parse_from_rfc3339(value).unwrap())
+}
+
+fn run_timestamp_fct(sql: &str, ts: Field, d: Field) -> Field {
+    run_scalar_fct(sql, timestamp_schema(), vec![ts, d])
+}
+
+#[test]
+fn test_interval_arithmetic() {
+    let ts = timestamp("2023-01-31T10:00:00Z");
+    let d = Field::Date(NaiveDate::from_ymd_opt(2023, 1, 31).unwrap());
+
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT ts + INTERVAL '1' MONTH FROM t",
+            ts.clone(),
+            d.clone()
+        ),
+        timestamp("2023-02-28T10:00:00Z")
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT ts - INTERVAL '1 day 2 hours' FROM t",
+            ts.clone(),
+            d.clone()
+        ),
+        timestamp("2023-01-30T08:00:00Z")
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT (INTERVAL '90' MINUTE) + ts FROM t",
+            ts.clone(),
+            d.clone()
+        ),
+        timestamp("2023-01-31T11:30:00Z")
+    );
+    assert_eq!(
+        run_timestamp_fct("SELECT d + 1 FROM t", ts.clone(), d.clone()),
+        Field::Date(NaiveDate::from_ymd_opt(2023, 2, 1).unwrap())
+    );
+    assert_eq!(
+        run_timestamp_fct("SELECT d + INTERVAL '1' HOUR FROM t", ts.clone(), d.clone()),
+        timestamp("2023-01-31T01:00:00Z")
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT (INTERVAL '1' HOUR) * 3 - INTERVAL '30' MINUTE FROM t",
+            ts.clone(),
+            d.clone()
+        ),
+        Field::Interval(DozerInterval::from_unit(150, "minute").unwrap())
+    );
+    assert_eq!(
+        run_timestamp_fct("SELECT (INTERVAL '1' MONTH) / 2 FROM t", ts, d),
+        Field::Interval(DozerInterval::new(0, 15, 0))
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT ts + INTERVAL '1' DAY FROM t",
+            Field::Null,
+            Field::Null
+        ),
+        Field::Null
+    );
+}
+
+#[test]
+fn test_date_trunc() {
+    let ts = timestamp("2023-05-17T10:42:13.123+02:00");
+    let cases = [
+        ("year", "2023-01-01T00:00:00+02:00"),
+        ("quarter", "2023-04-01T00:00:00+02:00"),
+        ("month", "2023-05-01T00:00:00+02:00"),
+        ("week", "2023-05-15T00:00:00+02:00"),
+        ("day", "2023-05-17T00:00:00+02:00"),
+        ("hour", "2023-05-17T10:00:00+02:00"),
+        ("minute", "2023-05-17T10:42:00+02:00"),
+        ("second", "2023-05-17T10:42:13+02:00"),
+    ];
+    for (unit, expected) in cases {
+        assert_eq!(
+            run_timestamp_fct(
+                &format!("SELECT DATE_TRUNC('{unit}', ts) FROM t"),
+                ts.clone(),
+                Field::Null
+            ),
+            timestamp(expected)
+        );
+    }
+}
+
+#[test]
+fn test_datediff() {
+    let ts = timestamp("2022-12-31T23:59:00Z");
+    let d = Field::Date(NaiveDate::from_ymd_opt(2023, 1, 2).unwrap());
+    let cases = [("year", 1), ("month", 1), ("day", 2), ("hour", 25)];
+    for (unit, expected) in cases {
+        assert_eq!(
+            run_timestamp_fct(
+                &format!("SELECT DATEDIFF('{unit}', ts, d) FROM t"),
+                ts.clone(),
+                d.clone()
+            ),
+            Field::Int(expected)
+        );
+    }
+}
+
+#[test]
+fn test_to_char_and_to_timestamp() {
+    let ts = timestamp("2023-03-04T05:06:07Z");
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT TO_CHAR(ts, 'YYYY-MM-DD HH24:MI:SS') FROM t",
+            ts.clone(),
+            Field::Null
+        ),
+        Field::String("2023-03-04 05:06:07".to_string())
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT TO_CHAR(ts, 'DD \"of\" Month') FROM t",
+            ts.clone(),
+            Field::Null
+        ),
+        Field::String("04 of March".to_string())
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT TO_TIMESTAMP('04/03/2023 05:06:07', 'DD/MM/YYYY HH24:MI:SS') FROM t",
+            Field::Null,
+            Field::Null
+        ),
+        ts
+    );
+    assert_eq!(
+        run_timestamp_fct(
+            "SELECT TO_TIMESTAMP(1677906367) FROM t",
+            Field::Null,
+            Field::Null
+        ),
+        timestamp("2023-03-04T05:06:07Z")
+    );
+}
+
+#[test]
+fn test_to_char_rejects_bad_formats() {
+    let to_char = |value: Field, format: &str| {
+        DateTimeFunctionType::ToChar.evaluate(
+            &Schema::empty(),
+            &[
+                Expression::Literal(value),
+                Expression::Literal(Field::String(format.to_string())),
+            ],
+            &Record::new(None, vec![], None),
+        )
+    };
+
+    assert!(to_char(timestamp("2023-03-04T05:06:07Z"), "%Q").is_err());
+    // A date has no time to format.
+    assert!(to_char(
+        Field::Date(NaiveDate::from_ymd_opt(2023, 3, 4).unwrap()),
+        "%H"
+    )
+    .is_err());
+    assert_eq!(
+        to_char(
+            Field::Date(NaiveDate::from_ymd_opt(2023, 3, 4).unwrap()),
+            "%d/%m"
+        )
+        .unwrap(),
+        Field::String("04/03".to_string())
+    );
+}
+
+#[test]
+fn test_at_time_zone() {
+    let ts = timestamp("2023-07-01T12:00:00Z");
+    let result = run_timestamp_fct(
+        "SELECT ts AT TIME ZONE 'Europe/Paris' FROM t",
+        ts.clone(),
+        Field::Null,
+    );
+    assert_eq!(result, timestamp("2023-07-01T14:00:00+02:00"));
+    assert_eq!(
+        result
+            .to_timestamp()
+            .unwrap()
+            .unwrap()
+            .offset()
+            .local_minus_utc(),
+        2 * 60 * 60
+    );
+
+    let result = run_timestamp_fct("SELECT ts AT TIME ZONE '-05:30' FROM t", ts, Field::Null);
+    assert_eq!(
+        result
+            .to_timestamp()
+            .unwrap()
+            .unwrap()
+            .offset()
+            .local_minus_utc(),
+        -(5 * 60 + 30) * 60
+    );
+}
+
+#[test]
+fn test_now() {
+    let result = run_timestamp_fct("SELECT NOW() FROM t", Field::Null, Field::Null);
+    assert!(matches!(result, Field::Timestamp(_)));
+}
+
 // #[test]
 // fn test_timestamp_add() {
 //     let f = run_scalar_fct(
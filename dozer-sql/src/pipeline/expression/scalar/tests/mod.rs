@@ -3,6 +3,8 @@ mod cast;
 #[cfg(test)]
 mod comparison;
 #[cfg(test)]
+mod conditional;
+#[cfg(test)]
 mod datetime;
 #[cfg(test)]
 mod logical;
@@ -1,7 +1,10 @@
+use crate::pipeline::errors::PipelineError::{NotEnoughArguments, TooManyArguments};
+use crate::pipeline::expression::execution::Expression;
 use crate::pipeline::expression::execution::Expression::Literal;
+use crate::pipeline::expression::scalar::common::{get_scalar_function_type, ScalarFunctionType};
 use crate::pipeline::expression::scalar::string::{
-    evaluate_concat, evaluate_like, evaluate_trim, evaluate_ucase, validate_concat, validate_trim,
-    TrimType,
+    evaluate_concat, evaluate_like, evaluate_substring, evaluate_trim, evaluate_ucase,
+    validate_concat, validate_trim, TrimType,
 };
 use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
 
@@ -690,3 +693,298 @@ fn test_like_escape() {
     );
     assert_eq!(f, Field::String("J%".to_string()));
 }
+
+fn run_string_fct(sql: &str, value: Field) -> Field {
+    run_scalar_fct(
+        sql,
+        Schema::empty()
+            .field(
+                FieldDefinition::new(
+                    String::from("s"),
+                    FieldType::String,
+                    true,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![value],
+    )
+}
+
+fn string(s: &str) -> Field {
+    Field::String(s.to_string())
+}
+
+#[test]
+fn test_lower() {
+    assert_eq!(
+        run_string_fct("SELECT LOWER(s) FROM t", string("Hello World")),
+        string("hello world")
+    );
+    assert_eq!(
+        run_string_fct("SELECT LOWER(s) FROM t", Field::Null),
+        Field::Null
+    );
+}
+
+#[test]
+fn test_substring() {
+    let value = string("Hello World");
+    assert_eq!(
+        run_string_fct("SELECT SUBSTRING(s, 7) FROM t", value.clone()),
+        string("World")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SUBSTRING(s FROM 1 FOR 5) FROM t", value.clone()),
+        string("Hello")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SUBSTRING(s, 0, 3) FROM t", value.clone()),
+        string("He")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SUBSTRING(s, 20) FROM t", value.clone()),
+        string("")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SUBSTRING(s, 2) FROM t", Field::Null),
+        Field::Null
+    );
+
+    // positions far out of range don't overflow
+    let row = Record::new(None, vec![], None);
+    assert_eq!(
+        evaluate_substring(
+            &Schema::empty(),
+            &Literal(value.clone()),
+            &Literal(Field::Int(i64::MIN)),
+            Some(&Literal(Field::Int(0))),
+            &row,
+        )
+        .unwrap(),
+        string("")
+    );
+    assert_eq!(
+        evaluate_substring(
+            &Schema::empty(),
+            &Literal(value),
+            &Literal(Field::Int(7)),
+            Some(&Literal(Field::Int(i64::MAX))),
+            &row,
+        )
+        .unwrap(),
+        string("World")
+    );
+}
+
+#[test]
+fn test_replace() {
+    assert_eq!(
+        run_string_fct("SELECT REPLACE(s, 'o', '0') FROM t", string("foo boo")),
+        string("f00 b00")
+    );
+}
+
+#[test]
+fn test_split_part() {
+    let value = string("a,b,c");
+    assert_eq!(
+        run_string_fct("SELECT SPLIT_PART(s, ',', 2) FROM t", value.clone()),
+        string("b")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SPLIT_PART(s, ',', -1) FROM t", value.clone()),
+        string("c")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SPLIT_PART(s, ',', 4) FROM t", value),
+        string("")
+    );
+}
+
+#[test]
+fn test_position() {
+    assert_eq!(
+        run_string_fct("SELECT POSITION('lo' IN s) FROM t", string("Hello")),
+        Field::UInt(4)
+    );
+    assert_eq!(
+        run_string_fct("SELECT POSITION('x' IN s) FROM t", string("Hello")),
+        Field::UInt(0)
+    );
+    assert_eq!(
+        run_string_fct("SELECT POSITION('x' IN s) FROM t", Field::Null),
+        Field::Null
+    );
+}
+
+#[test]
+fn test_pad() {
+    assert_eq!(
+        run_string_fct("SELECT LPAD(s, 5, '0') FROM t", string("42")),
+        string("00042")
+    );
+    assert_eq!(
+        run_string_fct("SELECT RPAD(s, 6, 'ab') FROM t", string("x")),
+        string("xababa")
+    );
+    assert_eq!(
+        run_string_fct("SELECT LPAD(s, 3) FROM t", string("Hello")),
+        string("Hel")
+    );
+    assert_eq!(
+        run_string_fct("SELECT RPAD(s, 4) FROM t", string("ab")),
+        string("ab  ")
+    );
+}
+
+#[test]
+fn test_regexp() {
+    assert_eq!(
+        run_string_fct(
+            "SELECT REGEXP_MATCH(s, '([0-9]+)-[0-9]+') FROM t",
+            string("call 555-1234")
+        ),
+        string("555")
+    );
+    assert_eq!(
+        run_string_fct("SELECT REGEXP_MATCH(s, '[0-9]+') FROM t", string("none")),
+        Field::Null
+    );
+    assert_eq!(
+        run_string_fct(
+            "SELECT REGEXP_REPLACE(s, '[aeiou]', '_') FROM t",
+            string("banana")
+        ),
+        string("b_nana")
+    );
+    assert_eq!(
+        run_string_fct(
+            "SELECT REGEXP_REPLACE(s, '[AEIOU]', '_', 'gi') FROM t",
+            string("banana")
+        ),
+        string("b_n_n_")
+    );
+    // the compiled literal pattern is reused, case insensitivity aside
+    assert_eq!(
+        run_string_fct(
+            "SELECT REGEXP_REPLACE(s, '[AEIOU]', '_', 'g') FROM t",
+            string("banana")
+        ),
+        string("banana")
+    );
+}
+
+#[test]
+fn test_left_right() {
+    let value = string("Hello");
+    assert_eq!(
+        run_string_fct("SELECT LEFT(s, 2) FROM t", value.clone()),
+        string("He")
+    );
+    assert_eq!(
+        run_string_fct("SELECT RIGHT(s, 3) FROM t", value.clone()),
+        string("llo")
+    );
+    assert_eq!(
+        run_string_fct("SELECT LEFT(s, -2) FROM t", value.clone()),
+        string("Hel")
+    );
+    assert_eq!(
+        run_string_fct("SELECT RIGHT(s, -1) FROM t", value.clone()),
+        string("ello")
+    );
+    assert_eq!(
+        run_string_fct("SELECT RIGHT(s, 10) FROM t", value),
+        string("Hello")
+    );
+}
+
+#[test]
+fn test_reverse_initcap() {
+    assert_eq!(
+        run_string_fct("SELECT REVERSE(s) FROM t", string("abc")),
+        string("cba")
+    );
+    assert_eq!(
+        run_string_fct("SELECT INITCAP(s) FROM t", string("hELLO wORLD-wide")),
+        string("Hello World-Wide")
+    );
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(
+        run_string_fct("SELECT MD5(s) FROM t", string("abc")),
+        string("900150983cd24fb0d6963f7d28e17f72")
+    );
+    assert_eq!(
+        run_string_fct("SELECT SHA256(s) FROM t", string("abc")),
+        string("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    assert_eq!(
+        run_string_fct("SELECT MD5(s) FROM t", Field::Null),
+        Field::Null
+    );
+}
+
+#[test]
+fn test_string_function_arity() {
+    let schema = Schema::empty()
+        .field(
+            FieldDefinition::new(
+                String::from("s"),
+                FieldType::String,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone();
+    let s = || Expression::Column { index: 0 };
+    let int = |value| Literal(Field::Int(value));
+    let validate = |fct: ScalarFunctionType, args: &[Expression]| {
+        get_scalar_function_type(&fct, args, &schema)
+    };
+
+    assert!(matches!(
+        validate(ScalarFunctionType::Lower, &[s(), s()]),
+        Err(TooManyArguments(_))
+    ));
+    assert!(matches!(
+        validate(ScalarFunctionType::Left, &[s(), int(1), int(2)]),
+        Err(TooManyArguments(_))
+    ));
+    assert!(matches!(
+        validate(ScalarFunctionType::Replace, &[s(), s(), s(), s()]),
+        Err(TooManyArguments(_))
+    ));
+    assert!(matches!(
+        validate(
+            ScalarFunctionType::Substring,
+            &[s(), int(1), int(2), int(3)]
+        ),
+        Err(TooManyArguments(_))
+    ));
+    assert!(matches!(
+        validate(ScalarFunctionType::Md5, &[s(), s()]),
+        Err(TooManyArguments(_))
+    ));
+    assert!(matches!(
+        validate(ScalarFunctionType::Position, &[s()]),
+        Err(NotEnoughArguments(_))
+    ));
+
+    // optional trailing arguments
+    assert!(validate(ScalarFunctionType::Substring, &[s(), int(1), int(2)]).is_ok());
+    assert!(validate(ScalarFunctionType::Lpad, &[s(), int(5), s()]).is_ok());
+    assert!(validate(ScalarFunctionType::RegexpReplace, &[s(), s(), s(), s()]).is_ok());
+    assert!(matches!(
+        validate(
+            ScalarFunctionType::RegexpReplace,
+            &[s(), s(), s(), s(), s()]
+        ),
+        Err(TooManyArguments(_))
+    ));
+}
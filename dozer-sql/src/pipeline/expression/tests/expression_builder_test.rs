@@ -5,6 +5,7 @@ use crate::pipeline::expression::scalar::common::ScalarFunctionType;
 use crate::pipeline::tests::utils::get_select;
 
 use crate::pipeline::expression::aggregate::AggregateFunctionType;
+use dozer_types::ordered_float::OrderedFloat;
 use dozer_types::types::{Field, FieldDefinition, FieldType, Schema, SourceDefinition};
 use sqlparser::ast::SelectItem;
 
@@ -508,3 +509,66 @@ fn test_alias_resolution() {
         }
     );
 }
+
+#[test]
+fn test_promoted_type() {
+    let schema = Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "a".to_string(),
+                FieldType::Int,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "b".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .to_owned();
+    let build = |builder: &mut ExpressionBuilder, sql: &str| match &get_select(sql)
+        .unwrap()
+        .projection[0]
+    {
+        SelectItem::UnnamedExpr(e) => builder.build(true, e, &schema).unwrap(),
+        _ => panic!("Invalid expr"),
+    };
+
+    let mut builder = ExpressionBuilder::new(schema.fields.len());
+    let e = build(&mut builder, "SELECT COALESCE(a, 1.5) FROM t0");
+    assert_eq!(
+        e,
+        Expression::Coalesce {
+            args: vec![
+                Expression::Column { index: 0 },
+                Expression::Literal(Field::Float(OrderedFloat(1.5)))
+            ],
+            return_type: FieldType::Float
+        }
+    );
+
+    // the aggregation is a column after the one of an aggregation of another expression
+    let offset = schema.fields.len() + 1;
+    let mut builder = ExpressionBuilder::new(offset);
+    let e = build(
+        &mut builder,
+        "SELECT CASE WHEN a > 0 THEN SUM(b) ELSE 0.5 END FROM t0",
+    );
+    match e {
+        Expression::Case {
+            results,
+            return_type,
+            ..
+        } => {
+            assert_eq!(results, vec![Expression::Column { index: offset }]);
+            assert_eq!(return_type, FieldType::Float);
+        }
+        _ => panic!("Invalid expr"),
+    }
+}
@@ -7,6 +7,8 @@ mod planner;
 mod product;
 mod projection;
 mod selection;
+pub mod state;
+mod top_n;
 mod window;
 
 #[cfg(test)]
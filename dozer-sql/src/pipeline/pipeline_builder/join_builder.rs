@@ -49,12 +49,14 @@ pub(crate) fn insert_join_to_pipeline(
             query_context,
         )?;
 
+        let (join_processor_name, join_state_name) = query_context.next_processor_name("join");
         let join_processor_factory = JoinProcessorFactory::new(
             left_name_or_alias.clone(),
             right_name_or_alias,
             join.join_operator.clone(),
+            query_context.state_options.clone(),
+            join_state_name,
         );
-        let join_processor_name = format!("join_{}", uuid::Uuid::new_v4());
 
         let mut pipeline_entry_points = vec![];
         if let JoinSource::Table(ref source_table) = left_join_source {
@@ -3,6 +3,7 @@
 use crate::pipeline::errors::PipelineError;
 use crate::pipeline::expression::builder::ExpressionBuilder;
 use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
+use crate::pipeline::top_n::factory::ROW_NUMBER_COLUMN;
 use dozer_types::types::{FieldDefinition, Schema};
 use sqlparser::ast::{Expr, Ident, Select, SelectItem};
 
@@ -51,6 +52,7 @@ impl CommonPlanner {
                 .input_schema
                 .fields
                 .iter()
+                .filter(|col| col.name != ROW_NUMBER_COLUMN)
                 .map(|col| (Expr::Identifier(Ident::new(col.to_owned().name)), None))
                 .collect(),
         };
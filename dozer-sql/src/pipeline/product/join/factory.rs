@@ -1,23 +1,32 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use dozer_core::{
     errors::ExecutionError,
-    node::{OutputPortDef, OutputPortType, PortHandle, Processor, ProcessorFactory},
+    node::{OutputPortDef, OutputPortType, Partitioning, PortHandle, Processor, ProcessorFactory},
     DEFAULT_PORT_HANDLE,
 };
-use dozer_types::types::{FieldDefinition, Record, Schema};
+use dozer_types::log::warn;
+use dozer_types::types::Schema;
 use sqlparser::ast::{
-    BinaryOperator, Expr as SqlExpr, Ident, JoinConstraint as SqlJoinConstraint,
+    BinaryOperator, Expr as SqlExpr, JoinConstraint as SqlJoinConstraint,
     JoinOperator as SqlJoinOperator,
 };
 
 use crate::pipeline::expression::builder::ExpressionBuilder;
+use crate::pipeline::expression::execution::Expression;
+use crate::pipeline::expression::operator::BinaryOperatorType;
+use crate::pipeline::state::StateOptions;
 use crate::pipeline::{builder::SchemaSQLContext, expression::builder::extend_schema_source_def};
-use crate::pipeline::{errors::JoinError, expression::builder::NameOrAlias};
+use crate::pipeline::{
+    errors::{JoinError, PipelineError},
+    expression::builder::NameOrAlias,
+};
 
 use super::{
-    operator::{JoinOperator, JoinType},
+    operator::{JoinBranch, JoinCondition, JoinOperator, JoinPartitionKey, JoinType},
     processor::ProductProcessor,
+    state::JoinTable,
 };
 
 pub(crate) const LEFT_JOIN_PORT: PortHandle = 0;
@@ -28,6 +37,8 @@ pub struct JoinProcessorFactory {
     left: Option<NameOrAlias>,
     right: Option<NameOrAlias>,
     join_operator: SqlJoinOperator,
+    state_options: StateOptions,
+    state_name: String,
 }
 
 impl JoinProcessorFactory {
@@ -35,11 +46,15 @@ impl JoinProcessorFactory {
         left: Option<NameOrAlias>,
         right: Option<NameOrAlias>,
         join_operator: SqlJoinOperator,
+        state_options: StateOptions,
+        state_name: String,
     ) -> Self {
         Self {
             left,
             right,
             join_operator,
+            state_options,
+            state_name,
         }
     }
 }
@@ -93,31 +108,49 @@ impl ProcessorFactory<SchemaSQLContext> for JoinProcessorFactory {
         input_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
         _output_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
     ) -> Result<Box<dyn Processor>, ExecutionError> {
-        let (join_type, join_constraint) = match &self.join_operator {
-            SqlJoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
-            SqlJoinOperator::LeftOuter(constraint) => (JoinType::LeftOuter, constraint),
-            SqlJoinOperator::RightOuter(constraint) => (JoinType::RightOuter, constraint),
-            _ => {
-                return Err(ExecutionError::InternalError(Box::new(
-                    JoinError::UnsupportedJoinType,
-                )))
-            }
-        };
+        self.build_with_state_name(input_schemas, &self.state_name)
+    }
 
-        let expression = match join_constraint {
-            SqlJoinConstraint::On(expression) => expression,
-            _ => {
-                return Err(ExecutionError::InternalError(Box::new(
-                    JoinError::UnsupportedJoinConstraintType,
-                )))
-            }
-        };
+    fn partitioning(
+        &self,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Partitioning, ExecutionError> {
+        let (left_schema, right_schema) = self.get_join_schemas(input_schemas)?;
+        let (_, condition) = self.get_join_type_and_condition(&left_schema, &right_schema)?;
+        // Without keys every record may match every record of the other branch.
+        if condition.left_keys.is_empty() {
+            return Ok(Partitioning::Single);
+        }
+        Ok(Partitioning::Hash(Arc::new(JoinPartitionKey::new(
+            condition,
+            &left_schema,
+            &right_schema,
+        ))))
+    }
 
-        // let left_name = self
-        //     .left
-        //     .clone()
-        //     .unwrap_or(NameOrAlias("Left".to_owned(), None));
+    fn build_partition(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        partition: usize,
+        num_partitions: usize,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        self.build_with_state_name(
+            input_schemas,
+            &format!(
+                "{}_partition_{partition}_of_{num_partitions}",
+                self.state_name
+            ),
+        )
+    }
+}
 
+impl JoinProcessorFactory {
+    /// Returns the schemas of the left and right branches, with their source definitions set to the table names.
+    fn get_join_schemas(
+        &self,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<(Schema, Schema), ExecutionError> {
         let mut left_schema = input_schemas
             .get(&LEFT_JOIN_PORT)
             .ok_or(ExecutionError::InternalError(
@@ -128,11 +161,6 @@ impl ProcessorFactory<SchemaSQLContext> for JoinProcessorFactory {
             left_schema = extend_schema_source_def(&left_schema, left_table_name);
         }
 
-        // let right_name = self
-        //     .right
-        //     .clone()
-        //     .unwrap_or(NameOrAlias("Right".to_owned(), None));
-
         let mut right_schema = input_schemas
             .get(&RIGHT_JOIN_PORT)
             .ok_or(ExecutionError::InternalError(
@@ -143,23 +171,85 @@ impl ProcessorFactory<SchemaSQLContext> for JoinProcessorFactory {
             right_schema = extend_schema_source_def(&right_schema, right_table_name);
         }
 
-        let (left_join_key_indexes, right_join_key_indexes) =
-            parse_join_constraint(expression, &left_schema, &right_schema)
-                .map_err(|err| ExecutionError::InternalError(Box::new(err)))?;
+        Ok((left_schema, right_schema))
+    }
+
+    fn get_join_type_and_condition(
+        &self,
+        left_schema: &Schema,
+        right_schema: &Schema,
+    ) -> Result<(JoinType, JoinCondition), ExecutionError> {
+        let (join_type, join_constraint) = match &self.join_operator {
+            SqlJoinOperator::Inner(constraint) => (JoinType::Inner, Some(constraint)),
+            SqlJoinOperator::LeftOuter(constraint) => (JoinType::LeftOuter, Some(constraint)),
+            SqlJoinOperator::RightOuter(constraint) => (JoinType::RightOuter, Some(constraint)),
+            SqlJoinOperator::FullOuter(constraint) => (JoinType::FullOuter, Some(constraint)),
+            SqlJoinOperator::CrossJoin => (JoinType::Cross, None),
+            _ => {
+                return Err(ExecutionError::InternalError(Box::new(
+                    JoinError::UnsupportedJoinType,
+                )))
+            }
+        };
+
+        let expression = match join_constraint {
+            Some(SqlJoinConstraint::On(expression)) => Some(expression),
+            // every record of one branch joins every record of the other
+            None => None,
+            _ => {
+                return Err(ExecutionError::InternalError(Box::new(
+                    JoinError::UnsupportedJoinConstraintType,
+                )))
+            }
+        };
+
+        let condition = match expression {
+            Some(expression) => parse_join_constraint(expression, left_schema, right_schema)
+                .map_err(|err| ExecutionError::InternalError(Box::new(err)))?,
+            None => JoinCondition::default(),
+        };
+
+        Ok((join_type, condition))
+    }
+
+    fn build_with_state_name(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        state_name: &str,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        let (left_schema, right_schema) = self.get_join_schemas(&input_schemas)?;
+        let (join_type, condition) =
+            self.get_join_type_and_condition(&left_schema, &right_schema)?;
+        if condition.left_keys.is_empty() && join_type != JoinType::Cross {
+            warn!(
+                "JOIN condition has no equality between the two sides, \
+                every operation will be matched against every record of the other side"
+            );
+        }
+
+        let left_table = JoinTable::new(&self.state_options, &format!("{state_name}_left"))
+            .map_err(|err| ExecutionError::InternalError(Box::new(err)))?;
+        let right_table = JoinTable::new(&self.state_options, &format!("{state_name}_right"))
+            .map_err(|err| ExecutionError::InternalError(Box::new(err)))?;
 
         let join_operator = JoinOperator::new(
             join_type,
-            left_join_key_indexes,
-            right_join_key_indexes,
-            Record::from_schema(&left_schema),
-            Record::from_schema(&right_schema),
+            condition,
+            left_schema,
+            right_schema,
+            left_table,
+            right_table,
         );
 
-        Ok(Box::new(ProductProcessor::new(join_operator)))
+        Ok(Box::new(ProductProcessor::new(
+            join_operator,
+            self.left.clone(),
+            self.right.clone(),
+        )))
     }
 }
 
-fn append_schema(left_schema: &Schema, right_schema: &Schema) -> Schema {
+pub(crate) fn append_schema(left_schema: &Schema, right_schema: &Schema) -> Schema {
     let mut output_schema = Schema::empty();
 
     let left_len = left_schema.fields.len();
@@ -183,147 +273,93 @@ fn append_schema(left_schema: &Schema, right_schema: &Schema) -> Schema {
     output_schema
 }
 
-fn parse_join_constraint(
-    expression: &sqlparser::ast::Expr,
+pub(crate) fn parse_join_constraint(
+    expression: &SqlExpr,
     left_join_table: &Schema,
     right_join_table: &Schema,
-) -> Result<(Vec<usize>, Vec<usize>), JoinError> {
-    match expression {
-        SqlExpr::BinaryOp {
-            ref left,
-            op,
-            ref right,
-        } => match op {
-            BinaryOperator::And => {
-                let (mut left_keys, mut right_keys) =
-                    parse_join_constraint(left, left_join_table, right_join_table)?;
-
-                let (mut left_keys_from_right, mut right_keys_from_right) =
-                    parse_join_constraint(right, left_join_table, right_join_table)?;
-                left_keys.append(&mut left_keys_from_right);
-                right_keys.append(&mut right_keys_from_right);
-
-                Ok((left_keys, right_keys))
-            }
-            BinaryOperator::Eq => {
-                let mut left_key_indexes = vec![];
-                let mut right_key_indexes = vec![];
-
-                let (left_arr, right_arr) =
-                    parse_join_eq_expression(left, left_join_table, right_join_table)?;
-                left_key_indexes.extend(left_arr);
-                right_key_indexes.extend(right_arr);
-
-                let (left_arr, right_arr) =
-                    parse_join_eq_expression(right, left_join_table, right_join_table)?;
-                left_key_indexes.extend(left_arr);
-                right_key_indexes.extend(right_arr);
-
-                Ok((left_key_indexes, right_key_indexes))
+) -> Result<JoinCondition, PipelineError> {
+    let joined_schema = append_schema(left_join_table, right_join_table);
+    let left_len = left_join_table.fields.len();
+
+    let mut condition = JoinCondition::default();
+    let mut residual = vec![];
+    for conjunct in split_conjunction(expression) {
+        let expression = ExpressionBuilder::new(joined_schema.fields.len()).build(
+            false,
+            conjunct,
+            &joined_schema,
+        )?;
+        match get_join_keys(&expression, left_len) {
+            Some((left_key, right_key)) => {
+                condition.left_keys.push(left_key);
+                condition.right_keys.push(right_key);
             }
-            _ => Err(JoinError::UnsupportedJoinConstraintOperator(op.to_string())),
-        },
-        _ => Err(JoinError::UnsupportedJoinConstraint(expression.to_string())),
-    }
-}
-
-fn parse_join_eq_expression(
-    expr: &SqlExpr,
-    left_join_table: &Schema,
-    right_join_table: &Schema,
-) -> Result<(Vec<usize>, Vec<usize>), JoinError> {
-    let mut left_key_indexes = vec![];
-    let mut right_key_indexes = vec![];
-    let (left_keys, right_keys) = match expr.clone() {
-        SqlExpr::Identifier(ident) => parse_identifier(&[ident], left_join_table, right_join_table),
-        SqlExpr::CompoundIdentifier(ident) => {
-            parse_identifier(&ident, left_join_table, right_join_table)
+            None => residual.push(expression),
         }
-        _ => {
-            return Err(JoinError::UnsupportedJoinConstraint(
-                expr.clone().to_string(),
-            ))
-        }
-    }?;
-
-    match (left_keys, right_keys) {
-        (Some(left_key), None) => left_key_indexes.push(left_key),
-        (None, Some(right_key)) => right_key_indexes.push(right_key),
-        _ => return Err(JoinError::UnsupportedJoinConstraint("".to_string())),
     }
 
-    Ok((left_key_indexes, right_key_indexes))
+    condition.residual = residual
+        .into_iter()
+        .reduce(|left, right| Expression::BinaryOperator {
+            left: Box::new(left),
+            operator: BinaryOperatorType::And,
+            right: Box::new(right),
+        });
+    Ok(condition)
 }
 
-fn parse_identifier(
-    ident: &[Ident],
-    left_join_schema: &Schema,
-    right_join_schema: &Schema,
-) -> Result<(Option<usize>, Option<usize>), JoinError> {
-    let left_idx = get_field_index(ident, left_join_schema)?;
-
-    let right_idx = get_field_index(ident, right_join_schema)?;
-
-    match (left_idx, right_idx) {
-        (None, None) => Err(JoinError::InvalidFieldSpecified(
-            ExpressionBuilder::fullname_from_ident(ident),
-        )),
-        (None, Some(idx)) => Ok((None, Some(idx))),
-        (Some(idx), None) => Ok((Some(idx), None)),
-        (Some(_), Some(_)) => Err(JoinError::InvalidJoinConstraint(
-            ExpressionBuilder::fullname_from_ident(ident),
-        )),
+pub(crate) fn split_conjunction(expression: &SqlExpr) -> Vec<&SqlExpr> {
+    match expression {
+        SqlExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut conjuncts = split_conjunction(left);
+            conjuncts.extend(split_conjunction(right));
+            conjuncts
+        }
+        SqlExpr::Nested(expression) => split_conjunction(expression),
+        _ => vec![expression],
     }
 }
 
-pub fn get_field_index(ident: &[Ident], schema: &Schema) -> Result<Option<usize>, JoinError> {
-    let tables_matches = |table_ident: &Ident, fd: &FieldDefinition| -> bool {
-        match fd.source.clone() {
-            dozer_types::types::SourceDefinition::Table {
-                connection: _,
-                name,
-            } => name == table_ident.value,
-            dozer_types::types::SourceDefinition::Alias { name } => name == table_ident.value,
-            dozer_types::types::SourceDefinition::Dynamic => false,
-        }
+/// Splits an equality between an expression of each branch into the left and right join keys.
+fn get_join_keys(expression: &Expression, left_len: usize) -> Option<(Expression, Expression)> {
+    let Expression::BinaryOperator {
+        left,
+        operator: BinaryOperatorType::Eq,
+        right,
+    } = expression
+    else {
+        return None;
     };
 
-    let field_index = match ident.len() {
-        1 => {
-            let field_index = schema
-                .fields
-                .iter()
-                .enumerate()
-                .find(|(_, f)| f.name == ident[0].value)
-                .map(|(idx, fd)| (idx, fd.clone()));
-            field_index
+    match (get_branch(left, left_len), get_branch(right, left_len)) {
+        (Some(JoinBranch::Left), Some(JoinBranch::Right)) => {
+            Some((left.as_ref().clone(), right.as_ref().clone()))
         }
-        2 => {
-            let table_name = ident.first().expect("table_name is expected");
-            let field_name = ident.last().expect("field_name is expected");
-
-            let index = schema
-                .fields
-                .iter()
-                .enumerate()
-                .find(|(_, f)| tables_matches(table_name, f) && f.name == field_name.value)
-                .map(|(idx, fd)| (idx, fd.clone()));
-            index
+        (Some(JoinBranch::Right), Some(JoinBranch::Left)) => {
+            Some((right.as_ref().clone(), left.as_ref().clone()))
         }
-        // 3 => {
-        //     let connection_name = comp_ident.get(0).expect("connection_name is expected");
-        //     let table_name = comp_ident.get(1).expect("table_name is expected");
-        //     let field_name = comp_ident.get(2).expect("field_name is expected");
-        // }
-        _ => {
-            return Err(JoinError::NameSpaceTooLong(
-                ident
-                    .iter()
-                    .map(|a| a.value.clone())
-                    .collect::<Vec<String>>()
-                    .join("."),
-            ));
+        _ => None,
+    }
+}
+
+/// The branch whose columns the expression reads, if it reads the columns of exactly one.
+fn get_branch(expression: &Expression, left_len: usize) -> Option<JoinBranch> {
+    let (mut reads_left, mut reads_right) = (false, false);
+    expression.visit_columns(&mut |index| {
+        if index < left_len {
+            reads_left = true;
+        } else {
+            reads_right = true;
         }
-    };
-    field_index.map_or(Ok(None), |(i, _fd)| Ok(Some(i)))
+    });
+
+    match (reads_left, reads_right) {
+        (true, false) => Some(JoinBranch::Left),
+        (false, true) => Some(JoinBranch::Right),
+        _ => None,
+    }
 }
@@ -1,12 +1,20 @@
-use ahash::AHasher;
-use dozer_types::types::Record;
-use multimap::MultiMap;
-use std::{
-    fmt::Debug,
-    hash::{Hash, Hasher},
+use dozer_core::errors::ExecutionError;
+use dozer_core::node::{PartitionKey, PortHandle};
+use dozer_types::node::SourceStates;
+use dozer_types::types::{Field, Record, Schema};
+use std::{fmt::Debug, iter::repeat};
+
+use crate::pipeline::{
+    errors::JoinError,
+    expression::execution::{Expression, ExpressionExecutor},
+    state::{encode_key, stable_hash},
 };
 
-use super::JoinResult;
+use super::{
+    factory::{append_schema, LEFT_JOIN_PORT},
+    state::JoinTable,
+    JoinResult,
+};
 
 pub enum JoinBranch {
     Left,
@@ -24,6 +32,8 @@ pub enum JoinType {
     Inner,
     LeftOuter,
     RightOuter,
+    FullOuter,
+    Cross,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -32,115 +42,268 @@ pub enum JoinAction {
     Delete,
 }
 
-#[derive(Debug, Clone)]
+/// How the records of the two branches are matched.
+///
+/// Records are looked up by the hash of their join keys, then the candidates are filtered with
+/// the residual condition. Without keys every record of one branch is a candidate for every record
+/// of the other, so every operation scans the whole other branch and a join of N and M records
+/// costs O(N·M). Such joins are only suitable for small inputs. All expressions are evaluated on the joined record; a key only reads the columns
+/// of its own branch, the other branch being padded with nulls.
+#[derive(Debug, Clone, Default)]
+pub struct JoinCondition {
+    pub left_keys: Vec<Expression>,
+    pub right_keys: Vec<Expression>,
+    pub residual: Option<Expression>,
+}
+
+#[derive(Debug)]
 pub struct JoinOperator {
     join_type: JoinType,
 
-    left_join_key_indexes: Vec<usize>,
-    right_join_key_indexes: Vec<usize>,
+    condition: JoinCondition,
+
+    /// The schema the condition was planned on.
+    joined_schema: Schema,
+    /// Current schemas of the branches, which may have columns added since the condition was
+    /// planned.
+    left_schema: Schema,
+    right_schema: Schema,
 
+    /// Null records of the branches as planned. The condition is evaluated on records of these
+    /// widths.
     left_default_record: Record,
     right_default_record: Record,
 
-    left_map: MultiMap<Vec<u8>, Record>,
-    right_map: MultiMap<Vec<u8>, Record>,
+    left_map: JoinTable,
+    right_map: JoinTable,
 }
 
 impl JoinOperator {
     pub fn new(
         join_type: JoinType,
-        left_join_key_indexes: Vec<usize>,
-        right_join_key_indexes: Vec<usize>,
-        left_default_record: Record,
-        right_default_record: Record,
+        condition: JoinCondition,
+        left_schema: Schema,
+        right_schema: Schema,
+        left_map: JoinTable,
+        right_map: JoinTable,
     ) -> Self {
+        let mut joined_schema = left_schema.clone();
+        joined_schema
+            .fields
+            .extend(right_schema.fields.iter().cloned());
+
         Self {
             join_type,
-            left_join_key_indexes,
-            right_join_key_indexes,
-            left_default_record,
-            right_default_record,
-            left_map: MultiMap::new(),
-            right_map: MultiMap::new(),
+            condition,
+            left_default_record: Record::from_schema(&left_schema),
+            right_default_record: Record::from_schema(&right_schema),
+            joined_schema,
+            left_schema,
+            right_schema,
+            left_map,
+            right_map,
+        }
+    }
+
+    /// Accepts columns added to a branch and returns the new output schema, or `None` if the
+    /// change isn't additive. The condition keeps reading the columns it was planned on, and
+    /// records stored before the change are padded with nulls in the output.
+    pub fn on_schema_changed(&mut self, from: &JoinBranch, schema: &Schema) -> Option<Schema> {
+        let current = match from {
+            JoinBranch::Left => &mut self.left_schema,
+            JoinBranch::Right => &mut self.right_schema,
+        };
+        current.added_fields(schema)?;
+        *current = schema.clone();
+        Some(append_schema(&self.left_schema, &self.right_schema))
+    }
+
+    /// The joined record the condition is evaluated on.
+    fn condition_record(&self, left_record: &Record, right_record: &Record) -> Record {
+        join_records(
+            left_record,
+            self.left_default_record.values.len(),
+            right_record,
+            self.right_default_record.values.len(),
+        )
+    }
+
+    /// The joined record that is sent downstream.
+    fn output_record(&self, left_record: &Record, right_record: &Record) -> Record {
+        join_records(
+            left_record,
+            self.left_schema.fields.len(),
+            right_record,
+            self.right_schema.fields.len(),
+        )
+    }
+
+    fn left_join_key(&self, record: &Record) -> JoinResult<Vec<u8>> {
+        let padded_record = self.condition_record(record, &self.right_default_record);
+        get_join_key(
+            &padded_record,
+            &self.condition.left_keys,
+            &self.joined_schema,
+        )
+    }
+
+    fn right_join_key(&self, record: &Record) -> JoinResult<Vec<u8>> {
+        let padded_record = self.condition_record(&self.left_default_record, record);
+        get_join_key(
+            &padded_record,
+            &self.condition.right_keys,
+            &self.joined_schema,
+        )
+    }
+
+    fn is_match(&self, left_record: &Record, right_record: &Record) -> JoinResult<bool> {
+        let Some(residual) = &self.condition.residual else {
+            return Ok(true);
+        };
+        let join_record = self.condition_record(left_record, right_record);
+        let result = residual
+            .evaluate(&join_record, &self.joined_schema)
+            .map_err(|err| JoinError::ConditionEvaluation(Box::new(err)))?;
+        Ok(result == Field::Boolean(true))
+    }
+
+    /// Records of the right branch with `join_key` that satisfy the residual condition.
+    fn matching_right_records(
+        &mut self,
+        join_key: &[u8],
+        left_record: &Record,
+    ) -> JoinResult<Vec<Record>> {
+        let mut right_records = self.right_map.get(join_key)?;
+        if self.condition.residual.is_some() {
+            let mut matching = Vec::with_capacity(right_records.len());
+            for right_record in right_records {
+                if self.is_match(left_record, &right_record)? {
+                    matching.push(right_record);
+                }
+            }
+            right_records = matching;
+        }
+        Ok(right_records)
+    }
+
+    /// Records of the left branch with `join_key` that satisfy the residual condition.
+    fn matching_left_records(
+        &mut self,
+        join_key: &[u8],
+        right_record: &Record,
+    ) -> JoinResult<Vec<Record>> {
+        let mut left_records = self.left_map.get(join_key)?;
+        if self.condition.residual.is_some() {
+            let mut matching = Vec::with_capacity(left_records.len());
+            for left_record in left_records {
+                if self.is_match(&left_record, right_record)? {
+                    matching.push(left_record);
+                }
+            }
+            left_records = matching;
         }
+        Ok(left_records)
     }
 
     fn inner_join_from_left(
-        &self,
+        &mut self,
         action: &JoinAction,
         join_key: &[u8],
         left_record: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
-        let empty_vec = vec![];
-        let right_records = self.right_map.get_vec(join_key).unwrap_or(&empty_vec);
+        let right_records = self.matching_right_records(join_key, left_record)?;
 
         let output_records = right_records
             .iter()
-            .map(|right_record| (action.clone(), join_records(left_record, right_record)))
+            .map(|right_record| {
+                (
+                    action.clone(),
+                    self.output_record(left_record, right_record),
+                )
+            })
             .collect::<Vec<(JoinAction, Record)>>();
 
         Ok(output_records)
     }
 
     fn inner_join_from_right(
-        &self,
+        &mut self,
         action: &JoinAction,
         join_key: &[u8],
         right_record: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
-        let empty_vec = vec![];
-        let left_records = self.left_map.get_vec(join_key).unwrap_or(&empty_vec);
+        let left_records = self.matching_left_records(join_key, right_record)?;
 
         let output_records = left_records
             .iter()
-            .map(|left_record| (action.clone(), join_records(left_record, right_record)))
+            .map(|left_record| {
+                (
+                    action.clone(),
+                    self.output_record(left_record, right_record),
+                )
+            })
             .collect::<Vec<(JoinAction, Record)>>();
 
         Ok(output_records)
     }
 
     fn left_join_from_left(
-        &self,
+        &mut self,
         action: &JoinAction,
         join_key: &[u8],
         left_record: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
-        let empty_vec = vec![];
-        let right_records = self.right_map.get_vec(join_key).unwrap_or(&empty_vec);
+        let right_records = self.matching_right_records(join_key, left_record)?;
 
         // no joining records on the right branch
         if right_records.is_empty() {
-            let join_record = join_records(left_record, &self.right_default_record);
+            let join_record = self.output_record(left_record, &self.right_default_record);
             return Ok(vec![(action.clone(), join_record)]);
         }
 
         let output_records = right_records
             .iter()
-            .map(|right_record| (action.clone(), join_records(left_record, right_record)))
+            .map(|right_record| {
+                (
+                    action.clone(),
+                    self.output_record(left_record, right_record),
+                )
+            })
             .collect::<Vec<(JoinAction, Record)>>();
 
         Ok(output_records)
     }
 
     fn left_join_from_right(
-        &self,
+        &mut self,
         action: &JoinAction,
         join_key: &[u8],
         right_record: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
-        let empty_vec = vec![];
-        let left_records = self.left_map.get_vec(join_key).unwrap_or(&empty_vec);
+        let left_records = self.matching_left_records(join_key, right_record)?;
 
         // if there are no matching records on the left branch, no records will be returned
         if left_records.is_empty() {
             return Ok(vec![]);
         }
 
+        self.join_preserved_left_records(action, right_record, &left_records)
+    }
+
+    /// Joins `right_record` with `left_records` whose rows are preserved by the outer join.
+    /// A left record that gains its first match has its null-padded row retracted, and one that
+    /// loses its last match has it restored.
+    fn join_preserved_left_records(
+        &mut self,
+        action: &JoinAction,
+        right_record: &Record,
+        left_records: &[Record],
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
         let mut output_records = vec![];
 
         for left_record in left_records.iter() {
             let right_matching_count = self.get_right_matching_count(action, left_record)?;
-            let join_record = join_records(left_record, right_record);
+            let join_record = self.output_record(left_record, right_record);
 
             if right_matching_count > 0 {
                 // if there are multiple matching records on the right branch, the left record will be just returned
@@ -148,7 +311,8 @@ impl JoinOperator {
             } else {
                 match action {
                     JoinAction::Insert => {
-                        let old_join_record = join_records(left_record, &self.right_default_record);
+                        let old_join_record =
+                            self.output_record(left_record, &self.right_default_record);
 
                         // delete the "first left join" record
                         output_records.push((JoinAction::Delete, old_join_record));
@@ -156,7 +320,8 @@ impl JoinOperator {
                         output_records.push((action.clone(), join_record));
                     }
                     JoinAction::Delete => {
-                        let new_join_record = join_records(left_record, &self.right_default_record);
+                        let new_join_record =
+                            self.output_record(left_record, &self.right_default_record);
 
                         output_records.push((JoinAction::Delete, join_record));
                         output_records.push((JoinAction::Insert, new_join_record));
@@ -168,24 +333,35 @@ impl JoinOperator {
     }
 
     fn right_join_from_left(
-        &self,
+        &mut self,
         action: &JoinAction,
         join_key: &[u8],
         left_record: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
-        let empty_vec = vec![];
-        let right_records = self.right_map.get_vec(join_key).unwrap_or(&empty_vec);
+        let right_records = self.matching_right_records(join_key, left_record)?;
 
         // if there are no matching records on the left branch, no records will be returned
         if right_records.is_empty() {
             return Ok(vec![]);
         }
 
+        self.join_preserved_right_records(action, left_record, &right_records)
+    }
+
+    /// Joins `left_record` with `right_records` whose rows are preserved by the outer join.
+    /// A right record that gains its first match has its null-padded row retracted, and one that
+    /// loses its last match has it restored.
+    fn join_preserved_right_records(
+        &mut self,
+        action: &JoinAction,
+        left_record: &Record,
+        right_records: &[Record],
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
         let mut output_records = vec![];
 
         for right_record in right_records.iter() {
             let left_matching_count = self.get_left_matching_count(action, right_record)?;
-            let join_record = join_records(left_record, right_record);
+            let join_record = self.output_record(left_record, right_record);
 
             if left_matching_count > 0 {
                 // if there are multiple matching records on the left branch, the right record will be just returned
@@ -193,7 +369,8 @@ impl JoinOperator {
             } else {
                 match action {
                     JoinAction::Insert => {
-                        let old_join_record = join_records(&self.left_default_record, right_record);
+                        let old_join_record =
+                            self.output_record(&self.left_default_record, right_record);
 
                         // delete the "first left join" record
                         output_records.push((JoinAction::Delete, old_join_record));
@@ -201,7 +378,8 @@ impl JoinOperator {
                         output_records.push((action.clone(), join_record));
                     }
                     JoinAction::Delete => {
-                        let new_join_record = join_records(&self.left_default_record, right_record);
+                        let new_join_record =
+                            self.output_record(&self.left_default_record, right_record);
 
                         output_records.push((JoinAction::Delete, join_record));
                         output_records.push((JoinAction::Insert, new_join_record));
@@ -213,32 +391,74 @@ impl JoinOperator {
     }
 
     fn right_join_from_right(
-        &self,
+        &mut self,
         action: &JoinAction,
         join_key: &[u8],
         right_record: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
-        let empty_vec = vec![];
-        let left_records = self.left_map.get_vec(join_key).unwrap_or(&empty_vec);
+        let left_records = self.matching_left_records(join_key, right_record)?;
 
         // no joining records on the right branch
         if left_records.is_empty() {
-            let join_record = join_records(right_record, &self.left_default_record);
+            let join_record = self.output_record(&self.left_default_record, right_record);
             return Ok(vec![(action.clone(), join_record)]);
         }
 
         let output_records = left_records
             .iter()
-            .map(|left_record| (action.clone(), join_records(left_record, right_record)))
+            .map(|left_record| {
+                (
+                    action.clone(),
+                    self.output_record(left_record, right_record),
+                )
+            })
             .collect::<Vec<(JoinAction, Record)>>();
 
         Ok(output_records)
     }
 
-    fn get_left_matching_count(&self, action: &JoinAction, record: &Record) -> JoinResult<usize> {
-        let join_key: Vec<u8> = get_join_key(record, &self.right_join_key_indexes);
+    fn full_join_from_left(
+        &mut self,
+        action: &JoinAction,
+        join_key: &[u8],
+        left_record: &Record,
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
+        let right_records = self.matching_right_records(join_key, left_record)?;
+
+        // no joining records on the right branch
+        if right_records.is_empty() {
+            let join_record = self.output_record(left_record, &self.right_default_record);
+            return Ok(vec![(action.clone(), join_record)]);
+        }
+
+        self.join_preserved_right_records(action, left_record, &right_records)
+    }
+
+    fn full_join_from_right(
+        &mut self,
+        action: &JoinAction,
+        join_key: &[u8],
+        right_record: &Record,
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
+        let left_records = self.matching_left_records(join_key, right_record)?;
+
+        // no joining records on the left branch
+        if left_records.is_empty() {
+            let join_record = self.output_record(&self.left_default_record, right_record);
+            return Ok(vec![(action.clone(), join_record)]);
+        }
+
+        self.join_preserved_left_records(action, right_record, &left_records)
+    }
+
+    fn get_left_matching_count(
+        &mut self,
+        action: &JoinAction,
+        record: &Record,
+    ) -> JoinResult<usize> {
+        let join_key: Vec<u8> = self.right_join_key(record)?;
 
-        let mut matching_count = self.left_map.get_vec(&join_key).unwrap_or(&vec![]).len();
+        let mut matching_count = self.matching_left_records(&join_key, record)?.len();
 
         if action == &JoinAction::Insert {
             matching_count -= 1;
@@ -246,10 +466,14 @@ impl JoinOperator {
         Ok(matching_count)
     }
 
-    fn get_right_matching_count(&self, action: &JoinAction, record: &Record) -> JoinResult<usize> {
-        let join_key: Vec<u8> = get_join_key(record, &self.left_join_key_indexes);
+    fn get_right_matching_count(
+        &mut self,
+        action: &JoinAction,
+        record: &Record,
+    ) -> JoinResult<usize> {
+        let join_key: Vec<u8> = self.left_join_key(record)?;
 
-        let mut matching_count = self.right_map.get_vec(&join_key).unwrap_or(&vec![]).len();
+        let mut matching_count = self.matching_right_records(&join_key, record)?.len();
 
         if action == &JoinAction::Insert {
             matching_count -= 1;
@@ -257,72 +481,74 @@ impl JoinOperator {
         Ok(matching_count)
     }
 
+    pub fn commit(&mut self, source_states: &SourceStates) -> JoinResult<()> {
+        self.left_map.commit(source_states)?;
+        self.right_map.commit(source_states)
+    }
+
+    pub fn is_resumable(&self, checkpoint: &SourceStates) -> bool {
+        self.left_map.is_resumable(checkpoint) && self.right_map.is_resumable(checkpoint)
+    }
+
+    pub fn reset(&mut self) -> JoinResult<()> {
+        self.left_map.reset()?;
+        self.right_map.reset()
+    }
+
     pub fn delete(
         &mut self,
         from: &JoinBranch,
         old: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
         match (&self.join_type, from) {
-            (JoinType::Inner, JoinBranch::Left) => {
-                let join_key: Vec<u8> = get_join_key(old, &self.left_join_key_indexes);
-                if let Some(map_records) = self.left_map.get_vec_mut(&join_key) {
-                    if let Some(index) = map_records.iter().position(|x| x == old) {
-                        map_records.remove(index);
-                    }
-                }
+            (JoinType::Inner | JoinType::Cross, JoinBranch::Left) => {
+                let join_key: Vec<u8> = self.left_join_key(old)?;
+                self.left_map.remove(&join_key, old)?;
                 let records = self.inner_join_from_left(&JoinAction::Delete, &join_key, old)?;
                 Ok(records)
             }
-            (JoinType::Inner, JoinBranch::Right) => {
-                let join_key: Vec<u8> = get_join_key(old, &self.right_join_key_indexes);
-                if let Some(map_records) = self.right_map.get_vec_mut(&join_key) {
-                    if let Some(index) = map_records.iter().position(|x| x == old) {
-                        map_records.remove(index);
-                    }
-                }
+            (JoinType::Inner | JoinType::Cross, JoinBranch::Right) => {
+                let join_key: Vec<u8> = self.right_join_key(old)?;
+                self.right_map.remove(&join_key, old)?;
                 let records = self.inner_join_from_right(&JoinAction::Delete, &join_key, old)?;
                 Ok(records)
             }
             (JoinType::LeftOuter, JoinBranch::Left) => {
-                let join_key: Vec<u8> = get_join_key(old, &self.left_join_key_indexes);
-                if let Some(map_records) = self.left_map.get_vec_mut(&join_key) {
-                    if let Some(index) = map_records.iter().position(|x| x == old) {
-                        map_records.remove(index);
-                    }
-                }
+                let join_key: Vec<u8> = self.left_join_key(old)?;
+                self.left_map.remove(&join_key, old)?;
                 let records = self.left_join_from_left(&JoinAction::Delete, &join_key, old)?;
                 Ok(records)
             }
             (JoinType::LeftOuter, JoinBranch::Right) => {
-                let join_key: Vec<u8> = get_join_key(old, &self.right_join_key_indexes);
-                if let Some(map_records) = self.right_map.get_vec_mut(&join_key) {
-                    if let Some(index) = map_records.iter().position(|x| x == old) {
-                        map_records.remove(index);
-                    }
-                }
+                let join_key: Vec<u8> = self.right_join_key(old)?;
+                self.right_map.remove(&join_key, old)?;
                 let records = self.left_join_from_right(&JoinAction::Delete, &join_key, old)?;
                 Ok(records)
             }
             (JoinType::RightOuter, JoinBranch::Left) => {
-                let join_key: Vec<u8> = get_join_key(old, &self.left_join_key_indexes);
-                if let Some(map_records) = self.left_map.get_vec_mut(&join_key) {
-                    if let Some(index) = map_records.iter().position(|x| x == old) {
-                        map_records.remove(index);
-                    }
-                }
+                let join_key: Vec<u8> = self.left_join_key(old)?;
+                self.left_map.remove(&join_key, old)?;
                 let records = self.right_join_from_left(&JoinAction::Delete, &join_key, old)?;
                 Ok(records)
             }
             (JoinType::RightOuter, JoinBranch::Right) => {
-                let join_key: Vec<u8> = get_join_key(old, &self.right_join_key_indexes);
-                if let Some(map_records) = self.right_map.get_vec_mut(&join_key) {
-                    if let Some(index) = map_records.iter().position(|x| x == old) {
-                        map_records.remove(index);
-                    }
-                }
+                let join_key: Vec<u8> = self.right_join_key(old)?;
+                self.right_map.remove(&join_key, old)?;
                 let records = self.right_join_from_right(&JoinAction::Delete, &join_key, old)?;
                 Ok(records)
             }
+            (JoinType::FullOuter, JoinBranch::Left) => {
+                let join_key: Vec<u8> = self.left_join_key(old)?;
+                self.left_map.remove(&join_key, old)?;
+                let records = self.full_join_from_left(&JoinAction::Delete, &join_key, old)?;
+                Ok(records)
+            }
+            (JoinType::FullOuter, JoinBranch::Right) => {
+                let join_key: Vec<u8> = self.right_join_key(old)?;
+                self.right_map.remove(&join_key, old)?;
+                let records = self.full_join_from_right(&JoinAction::Delete, &join_key, old)?;
+                Ok(records)
+            }
         }
     }
 
@@ -332,66 +558,148 @@ impl JoinOperator {
         new: &Record,
     ) -> JoinResult<Vec<(JoinAction, Record)>> {
         match (&self.join_type, from) {
-            (JoinType::Inner, JoinBranch::Left) => {
-                let join_key: Vec<u8> = get_join_key(new, &self.left_join_key_indexes);
-                self.left_map.insert(join_key.clone(), new.to_owned());
+            (JoinType::Inner | JoinType::Cross, JoinBranch::Left) => {
+                let join_key: Vec<u8> = self.left_join_key(new)?;
+                self.left_map.insert(join_key.clone(), new.to_owned())?;
                 let records = self.inner_join_from_left(&JoinAction::Insert, &join_key, new)?;
                 Ok(records)
             }
-            (JoinType::Inner, JoinBranch::Right) => {
-                let join_key: Vec<u8> = get_join_key(new, &self.right_join_key_indexes);
-                self.right_map.insert(join_key.clone(), new.to_owned());
+            (JoinType::Inner | JoinType::Cross, JoinBranch::Right) => {
+                let join_key: Vec<u8> = self.right_join_key(new)?;
+                self.right_map.insert(join_key.clone(), new.to_owned())?;
                 let records = self.inner_join_from_right(&JoinAction::Insert, &join_key, new)?;
 
                 Ok(records)
             }
             (JoinType::LeftOuter, JoinBranch::Left) => {
-                let join_key: Vec<u8> = get_join_key(new, &self.left_join_key_indexes);
-                self.left_map.insert(join_key.clone(), new.to_owned());
+                let join_key: Vec<u8> = self.left_join_key(new)?;
+                self.left_map.insert(join_key.clone(), new.to_owned())?;
                 let records = self.left_join_from_left(&JoinAction::Insert, &join_key, new)?;
 
                 Ok(records)
             }
             (JoinType::LeftOuter, JoinBranch::Right) => {
-                let join_key: Vec<u8> = get_join_key(new, &self.right_join_key_indexes);
-                self.right_map.insert(join_key.clone(), new.to_owned());
+                let join_key: Vec<u8> = self.right_join_key(new)?;
+                self.right_map.insert(join_key.clone(), new.to_owned())?;
                 let records = self.left_join_from_right(&JoinAction::Insert, &join_key, new)?;
 
                 Ok(records)
             }
             (JoinType::RightOuter, JoinBranch::Left) => {
-                let join_key: Vec<u8> = get_join_key(new, &self.left_join_key_indexes);
-                self.left_map.insert(join_key.clone(), new.to_owned());
+                let join_key: Vec<u8> = self.left_join_key(new)?;
+                self.left_map.insert(join_key.clone(), new.to_owned())?;
                 let records = self.right_join_from_left(&JoinAction::Insert, &join_key, new)?;
 
                 Ok(records)
             }
             (JoinType::RightOuter, JoinBranch::Right) => {
-                let join_key: Vec<u8> = get_join_key(new, &self.right_join_key_indexes);
-                self.right_map.insert(join_key.clone(), new.to_owned());
+                let join_key: Vec<u8> = self.right_join_key(new)?;
+                self.right_map.insert(join_key.clone(), new.to_owned())?;
                 let records = self.right_join_from_right(&JoinAction::Insert, &join_key, new)?;
 
+                Ok(records)
+            }
+            (JoinType::FullOuter, JoinBranch::Left) => {
+                let join_key: Vec<u8> = self.left_join_key(new)?;
+                self.left_map.insert(join_key.clone(), new.to_owned())?;
+                let records = self.full_join_from_left(&JoinAction::Insert, &join_key, new)?;
+
+                Ok(records)
+            }
+            (JoinType::FullOuter, JoinBranch::Right) => {
+                let join_key: Vec<u8> = self.right_join_key(new)?;
+                self.right_map.insert(join_key.clone(), new.to_owned())?;
+                let records = self.full_join_from_right(&JoinAction::Insert, &join_key, new)?;
+
                 Ok(records)
             }
         }
     }
 }
 
-fn get_join_key(old: &Record, join_key_indexes: &[usize]) -> Vec<u8> {
-    let mut hasher = AHasher::default();
-    for index in join_key_indexes.iter() {
-        old.values[*index].hash(&mut hasher);
+/// Partitions the records of both branches on their join keys, so that matching records reach the same instance of a parallel join.
+#[derive(Debug)]
+pub struct JoinPartitionKey {
+    condition: JoinCondition,
+    joined_schema: Schema,
+    left_default_record: Record,
+    right_default_record: Record,
+}
+
+impl JoinPartitionKey {
+    pub fn new(condition: JoinCondition, left_schema: &Schema, right_schema: &Schema) -> Self {
+        let mut joined_schema = left_schema.clone();
+        joined_schema
+            .fields
+            .extend(right_schema.fields.iter().cloned());
+
+        Self {
+            condition,
+            joined_schema,
+            left_default_record: Record::from_schema(left_schema),
+            right_default_record: Record::from_schema(right_schema),
+        }
     }
-    let join_key = hasher.finish();
+}
 
-    join_key.to_be_bytes().to_vec()
+impl PartitionKey for JoinPartitionKey {
+    fn hash_key(&self, port: PortHandle, record: &Record) -> Result<u64, ExecutionError> {
+        let (left_record, right_record, join_keys) = if port == LEFT_JOIN_PORT {
+            (
+                record,
+                &self.right_default_record,
+                &self.condition.left_keys,
+            )
+        } else {
+            (
+                &self.left_default_record,
+                record,
+                &self.condition.right_keys,
+            )
+        };
+        let padded_record = join_records(
+            left_record,
+            self.left_default_record.values.len(),
+            right_record,
+            self.right_default_record.values.len(),
+        );
+        let join_key = get_join_key(&padded_record, join_keys, &self.joined_schema)
+            .map_err(|err| ExecutionError::InternalError(Box::new(err)))?;
+        Ok(stable_hash(&join_key))
+    }
+}
+
+/// Encodes the values of the join key expressions, so equal keys match in the join state.
+fn get_join_key(record: &Record, join_keys: &[Expression], schema: &Schema) -> JoinResult<Vec<u8>> {
+    let mut values = Vec::with_capacity(join_keys.len());
+    for expression in join_keys.iter() {
+        values.push(
+            expression
+                .evaluate(record, schema)
+                .map_err(|err| JoinError::ConditionEvaluation(Box::new(err)))?,
+        );
+    }
+    Ok(encode_key(&values)?)
 }
 
-fn join_records(left_record: &Record, right_record: &Record) -> Record {
-    let concat_values = [
-        left_record.values.as_slice(),
-        right_record.values.as_slice(),
-    ]
-    .concat();
-    Record::new(None, concat_values, None)
+/// Concatenates the values of two records, cutting or padding each with nulls to the given width.
+fn join_records(
+    left_record: &Record,
+    left_width: usize,
+    right_record: &Record,
+    right_width: usize,
+) -> Record {
+    let left_values = left_record
+        .values
+        .iter()
+        .cloned()
+        .chain(repeat(Field::Null))
+        .take(left_width);
+    let right_values = right_record
+        .values
+        .iter()
+        .cloned()
+        .chain(repeat(Field::Null))
+        .take(right_width);
+    Record::new(None, left_values.chain(right_values).collect(), None)
 }
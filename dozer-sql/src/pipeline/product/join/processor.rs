@@ -3,24 +3,73 @@ use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
 use dozer_core::node::{PortHandle, Processor};
 use dozer_core::DEFAULT_PORT_HANDLE;
-use dozer_types::types::Operation;
+use dozer_types::node::SourceStates;
+use dozer_types::types::{Operation, Schema};
+use std::collections::HashMap;
+
+use crate::pipeline::expression::builder::{extend_schema_source_def, NameOrAlias};
 
 use super::operator::{JoinAction, JoinBranch, JoinOperator};
 
 #[derive(Debug)]
 pub struct ProductProcessor {
     join_operator: JoinOperator,
+    left: Option<NameOrAlias>,
+    right: Option<NameOrAlias>,
 }
 
 impl ProductProcessor {
-    pub fn new(join_operator: JoinOperator) -> Self {
-        Self { join_operator }
+    pub fn new(
+        join_operator: JoinOperator,
+        left: Option<NameOrAlias>,
+        right: Option<NameOrAlias>,
+    ) -> Self {
+        Self {
+            join_operator,
+            left,
+            right,
+        }
     }
 }
 
 impl Processor for ProductProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
-        Ok(())
+    fn commit(&mut self, epoch: &Epoch) -> Result<(), ExecutionError> {
+        self.join_operator
+            .commit(&epoch.details)
+            .map_err(|err| ExecutionError::InternalError(Box::new(err)))
+    }
+
+    fn is_resumable(&self, checkpoint: &SourceStates) -> bool {
+        self.join_operator.is_resumable(checkpoint)
+    }
+
+    fn reset(&mut self) -> Result<(), ExecutionError> {
+        self.join_operator
+            .reset()
+            .map_err(|err| ExecutionError::InternalError(Box::new(err)))
+    }
+
+    fn on_schema_changed(
+        &mut self,
+        from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        let (from_branch, table_name) = match from_port {
+            0 => (&JoinBranch::Left, &self.left),
+            1 => (&JoinBranch::Right, &self.right),
+            _ => return Err(ExecutionError::InvalidPort(from_port)),
+        };
+        let schema = match table_name {
+            Some(table_name) => extend_schema_source_def(schema, table_name),
+            None => schema.clone(),
+        };
+        let output_schema = self
+            .join_operator
+            .on_schema_changed(from_branch, &schema)
+            .ok_or_else(|| {
+                ExecutionError::UnsupportedSchemaChange("JOIN input columns changed".to_string())
+            })?;
+        Ok(HashMap::from([(DEFAULT_PORT_HANDLE, output_schema)]))
     }
 
     fn process(
@@ -0,0 +1,190 @@
+use std::ops::Bound;
+
+use dozer_storage::{LmdbCounter, LmdbMap, LmdbMultimap, RwLmdbEnvironment};
+use dozer_types::borrow::{Borrow, IntoOwned};
+use dozer_types::node::SourceStates;
+use dozer_types::types::{Field, Record};
+use multimap::MultiMap;
+
+use crate::pipeline::errors::StateStoreError;
+use crate::pipeline::state::{CommittedEpoch, StateOptions};
+
+use super::JoinResult;
+
+/// The records of one branch of a JOIN, grouped by join key.
+#[derive(Debug)]
+pub enum JoinTable {
+    Memory(MultiMap<Vec<u8>, Record>),
+    Lmdb(LmdbJoinTable),
+}
+
+impl JoinTable {
+    pub fn new(state_options: &StateOptions, name: &str) -> JoinResult<Self> {
+        Ok(match state_options.open_environment(name)? {
+            None => JoinTable::Memory(MultiMap::new()),
+            Some(env) => JoinTable::Lmdb(LmdbJoinTable::new(env)?),
+        })
+    }
+
+    pub fn get(&mut self, join_key: &[u8]) -> JoinResult<Vec<Record>> {
+        match self {
+            JoinTable::Memory(map) => Ok(map.get_vec(join_key).cloned().unwrap_or_default()),
+            JoinTable::Lmdb(table) => Ok(table.get(join_key)?),
+        }
+    }
+
+    pub fn insert(&mut self, join_key: Vec<u8>, record: Record) -> JoinResult<()> {
+        match self {
+            JoinTable::Memory(map) => {
+                map.insert(join_key, record);
+                Ok(())
+            }
+            JoinTable::Lmdb(table) => Ok(table.insert(&join_key, &record)?),
+        }
+    }
+
+    /// Removes one record that is the same as `record`, if any.
+    pub fn remove(&mut self, join_key: &[u8], record: &Record) -> JoinResult<()> {
+        match self {
+            JoinTable::Memory(map) => {
+                if let Some(map_records) = map.get_vec_mut(join_key) {
+                    if let Some(index) = map_records.iter().position(|x| is_same_record(x, record))
+                    {
+                        map_records.remove(index);
+                    }
+                }
+                Ok(())
+            }
+            JoinTable::Lmdb(table) => Ok(table.remove(join_key, record)?),
+        }
+    }
+
+    pub fn commit(&mut self, source_states: &SourceStates) -> JoinResult<()> {
+        match self {
+            JoinTable::Memory(_) => Ok(()),
+            JoinTable::Lmdb(table) => Ok(table.commit(source_states)?),
+        }
+    }
+
+    /// Whether the table holds the records of the epoch with source states `checkpoint`.
+    pub fn is_resumable(&self, checkpoint: &SourceStates) -> bool {
+        match self {
+            // Memory tables start empty.
+            JoinTable::Memory(_) => false,
+            JoinTable::Lmdb(table) => table.committed_epoch.is(checkpoint),
+        }
+    }
+
+    /// Removes all records.
+    pub fn reset(&mut self) -> JoinResult<()> {
+        match self {
+            JoinTable::Memory(map) => {
+                map.clear();
+                Ok(())
+            }
+            JoinTable::Lmdb(table) => Ok(table.reset()?),
+        }
+    }
+}
+
+/// Whether `stored` is `record`. Records stored before columns were added to the branch don't have
+/// the added columns, which are null in `record` if it wasn't updated since.
+fn is_same_record(stored: &Record, record: &Record) -> bool {
+    let width = stored.values.len();
+    stored.version == record.version
+        && record.values.len() >= width
+        && record.values[..width] == stored.values[..]
+        && record.values[width..]
+            .iter()
+            .all(|value| value == &Field::Null)
+}
+
+/// Join table stored in LMDB. Records are stored by id so the multimap values stay small.
+#[derive(Debug)]
+pub struct LmdbJoinTable {
+    env: RwLmdbEnvironment,
+    keys: LmdbMultimap<Vec<u8>, u64>,
+    records: LmdbMap<u64, Record>,
+    next_id: LmdbCounter,
+    committed_epoch: CommittedEpoch,
+}
+
+impl LmdbJoinTable {
+    fn new(mut env: RwLmdbEnvironment) -> Result<Self, StateStoreError> {
+        let keys = LmdbMultimap::create(&mut env, Some("keys"))?;
+        let records = LmdbMap::create(&mut env, Some("records"))?;
+        let next_id = LmdbCounter::create(&mut env, Some("next_id"))?;
+        let committed_epoch = CommittedEpoch::create(&mut env)?;
+        Ok(Self {
+            env,
+            keys,
+            records,
+            next_id,
+            committed_epoch,
+        })
+    }
+
+    fn get(&mut self, join_key: &[u8]) -> Result<Vec<Record>, StateStoreError> {
+        Ok(self
+            .get_with_ids(join_key)?
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect())
+    }
+
+    fn get_with_ids(&mut self, join_key: &[u8]) -> Result<Vec<(u64, Record)>, StateStoreError> {
+        let txn = self.env.txn_mut()?;
+        let mut result = vec![];
+        for entry in self.keys.range(txn, Bound::Included(join_key), true)? {
+            let (key, id) = entry?;
+            if key.borrow() != join_key {
+                break;
+            }
+            let id = id.into_owned();
+            let record = self
+                .records
+                .get(txn, &id)?
+                .ok_or(StateStoreError::MissingRecord(id))?
+                .into_owned();
+            result.push((id, record));
+        }
+        Ok(result)
+    }
+
+    fn insert(&mut self, join_key: &[u8], record: &Record) -> Result<(), StateStoreError> {
+        let txn = self.env.txn_mut()?;
+        let id = self.next_id.fetch_add(txn, 1)?;
+        self.keys.insert(txn, join_key, &id)?;
+        self.records.insert(txn, &id, record)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, join_key: &[u8], record: &Record) -> Result<(), StateStoreError> {
+        let existing = self
+            .get_with_ids(join_key)?
+            .into_iter()
+            .find(|(_, existing)| is_same_record(existing, record));
+        if let Some((id, _)) = existing {
+            let txn = self.env.txn_mut()?;
+            self.keys.remove(txn, join_key, &id)?;
+            self.records.remove(txn, &id)?;
+        }
+        Ok(())
+    }
+
+    fn commit(&mut self, source_states: &SourceStates) -> Result<(), StateStoreError> {
+        self.committed_epoch.store(&mut self.env, source_states)?;
+        self.env.commit()?;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), StateStoreError> {
+        let txn = self.env.txn_mut()?;
+        self.keys.clear(txn)?;
+        self.records.clear(txn)?;
+        self.next_id.store(txn, 0)?;
+        self.committed_epoch.clear(&mut self.env)?;
+        self.env.commit()?;
+        Ok(())
+    }
+}
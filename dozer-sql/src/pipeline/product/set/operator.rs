@@ -1,6 +1,6 @@
 use crate::pipeline::errors::PipelineError;
 use bloom::{CountingBloomFilter, ASMS};
-use dozer_types::types::Record;
+use dozer_types::types::{Field, Record};
 use sqlparser::ast::{SetOperator, SetQuantifier};
 
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
@@ -80,12 +80,21 @@ impl SetOperation {
     }
 
     fn update_map(&self, record: &Record, decr: bool, record_map: &mut CountingBloomFilter) -> u32 {
+        // Records counted before columns were added don't have the added columns, which are
+        // null in the same records now, so trailing nulls don't count.
+        let width = record
+            .values
+            .iter()
+            .rposition(|value| value != &Field::Null)
+            .map_or(0, |index| index + 1);
+        let values = &record.values[..width];
+
         if decr {
-            record_map.remove(&record);
+            record_map.remove(&values);
         } else {
-            record_map.insert(&record);
+            record_map.insert(&values);
         }
 
-        record_map.estimate_count(&record)
+        record_map.estimate_count(&values)
     }
 }
@@ -69,14 +69,21 @@ impl ProcessorFactory<SchemaSQLContext> for SetProcessorFactory {
 
     fn build(
         &self,
-        _input_schemas: HashMap<PortHandle, Schema>,
-        _output_schemas: HashMap<PortHandle, Schema>,
+        input_schemas: HashMap<PortHandle, Schema>,
+        mut output_schemas: HashMap<PortHandle, Schema>,
     ) -> Result<Box<dyn Processor>, ExecutionError> {
+        let output_schema = output_schemas
+            .remove(&DEFAULT_PORT_HANDLE)
+            .ok_or(ExecutionError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
         Ok(Box::new(
-            SetProcessor::new(SetOperation {
-                op: SetOperator::Union,
-                quantifier: self.set_quantifier,
-            })
+            SetProcessor::new(
+                SetOperation {
+                    op: SetOperator::Union,
+                    quantifier: self.set_quantifier,
+                },
+                input_schemas,
+                output_schema,
+            )
             .map_err(|err| ExecutionError::InternalError(Box::new(err)))?,
         ))
     }
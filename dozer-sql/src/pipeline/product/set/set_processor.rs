@@ -5,8 +5,10 @@ use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
 use dozer_core::node::{PortHandle, Processor};
 use dozer_core::DEFAULT_PORT_HANDLE;
-use dozer_types::types::{Operation, Record};
+use dozer_types::node::SourceStates;
+use dozer_types::types::{Field, FieldDefinition, Operation, Record, Schema, SourceDefinition};
 use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 
 use super::operator::{SetAction, SetOperation};
@@ -16,6 +18,10 @@ pub struct SetProcessor {
     operator: SetOperation,
     /// Hashmap containing records with its occurrence
     record_map: CountingBloomFilter,
+    /// Current schema of each input
+    input_schemas: HashMap<PortHandle, Schema>,
+    /// Current output schema. Inputs that don't have all of its columns are padded with nulls.
+    output_schema: Schema,
 }
 
 const BITS_PER_ENTRY: usize = 8;
@@ -24,7 +30,11 @@ const EXPECTED_NUM_ITEMS: u32 = 10000000;
 
 impl SetProcessor {
     /// Creates a new [`SetProcessor`].
-    pub fn new(operator: SetOperation) -> Result<Self, PipelineError> {
+    pub fn new(
+        operator: SetOperation,
+        input_schemas: HashMap<PortHandle, Schema>,
+        output_schema: Schema,
+    ) -> Result<Self, PipelineError> {
         let _s = RandomState::new();
         Ok(Self {
             operator,
@@ -33,9 +43,18 @@ impl SetProcessor {
                 FALSE_POSITIVE_RATE,
                 EXPECTED_NUM_ITEMS,
             ),
+            input_schemas,
+            output_schema,
         })
     }
 
+    fn pad(&self, record: &mut Record) {
+        let width = self.output_schema.fields.len();
+        if record.values.len() < width {
+            record.values.resize(width, Field::Null);
+        }
+    }
+
     fn delete(&mut self, record: &Record) -> Result<Vec<(SetAction, Record)>, ProductError> {
         self.operator
             .execute(SetAction::Delete, record, &mut self.record_map)
@@ -83,16 +102,71 @@ impl Debug for SetProcessor {
 }
 
 impl Processor for SetProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
+    fn is_resumable(&self, _checkpoint: &SourceStates) -> bool {
+        // The state is kept in memory, so it's empty after a restart.
+        false
+    }
+
+    /// Columns are matched by position. A column added to one input is added to the output, and
+    /// is null in the records of the other input until the same column is added to it.
+    fn on_schema_changed(
+        &mut self,
+        from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        let unsupported =
+            || ExecutionError::UnsupportedSchemaChange("UNION input columns changed".to_string());
+        let input_schema = self
+            .input_schemas
+            .get_mut(&from_port)
+            .ok_or(ExecutionError::InvalidPort(from_port))?;
+        let existing = input_schema.fields.len();
+        input_schema.added_fields(schema).ok_or_else(unsupported)?;
+        *input_schema = schema.clone();
+
+        let mut widened = false;
+        for (index, field) in schema.fields.iter().enumerate().skip(existing) {
+            match self.output_schema.fields.get(index) {
+                Some(output_field)
+                    if output_field.name == field.name && output_field.typ == field.typ => {}
+                Some(_) => return Err(unsupported()),
+                None => {
+                    self.output_schema.fields.push(FieldDefinition::new(
+                        field.name.clone(),
+                        field.typ,
+                        true,
+                        SourceDefinition::Dynamic,
+                    ));
+                    widened = true;
+                }
+            }
+        }
+
+        Ok(if widened {
+            HashMap::from([(DEFAULT_PORT_HANDLE, self.output_schema.clone())])
+        } else {
+            HashMap::new()
+        })
+    }
+
     fn process(
         &mut self,
         _from_port: PortHandle,
-        op: Operation,
+        mut op: Operation,
         fw: &mut dyn ProcessorChannelForwarder,
     ) -> Result<(), ExecutionError> {
+        match &mut op {
+            Operation::Delete { old } => self.pad(old),
+            Operation::Insert { new } => self.pad(new),
+            Operation::Update { old, new } => {
+                self.pad(old);
+                self.pad(new);
+            }
+        }
         match op {
             Operation::Delete { ref old } => {
                 let records = self
@@ -59,7 +59,8 @@ impl ProcessorFactory<SchemaSQLContext> for TableProcessorFactory {
         _input_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
         _output_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
     ) -> Result<Box<dyn Processor>, ExecutionError> {
-        Ok(Box::new(TableProcessor::new()))
+        let table = get_name_or_alias(&self.relation)?;
+        Ok(Box::new(TableProcessor::new(table)))
     }
 }
 
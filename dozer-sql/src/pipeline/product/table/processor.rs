@@ -1,21 +1,27 @@
+use std::collections::HashMap;
+
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
 use dozer_core::node::{PortHandle, Processor};
 use dozer_core::DEFAULT_PORT_HANDLE;
-use dozer_types::types::Operation;
+use dozer_types::types::{Operation, Schema};
+
+use crate::pipeline::expression::builder::{extend_schema_source_def, NameOrAlias};
 
 #[derive(Debug)]
-pub struct TableProcessor {}
+pub struct TableProcessor {
+    table: NameOrAlias,
+}
 
 impl TableProcessor {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(table: NameOrAlias) -> Self {
+        Self { table }
     }
 }
 
 impl Processor for TableProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
@@ -28,4 +34,15 @@ impl Processor for TableProcessor {
         fw.send(op, DEFAULT_PORT_HANDLE)?;
         Ok(())
     }
+
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        Ok(HashMap::from([(
+            DEFAULT_PORT_HANDLE,
+            extend_schema_source_def(schema, &self.table),
+        )]))
+    }
 }
@@ -0,0 +1,267 @@
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+
+use crate::pipeline::expression::execution::Expression;
+use crate::pipeline::product::join::operator::{
+    JoinAction, JoinBranch, JoinCondition, JoinOperator, JoinType,
+};
+use crate::pipeline::product::join::state::JoinTable;
+use crate::pipeline::state::StateOptions;
+
+fn build_operator(join_type: JoinType, condition: JoinCondition) -> JoinOperator {
+    JoinOperator::new(
+        join_type,
+        condition,
+        schema(),
+        schema(),
+        JoinTable::new(&StateOptions::Memory, "join_left").unwrap(),
+        JoinTable::new(&StateOptions::Memory, "join_right").unwrap(),
+    )
+}
+
+fn schema() -> Schema {
+    Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "id".to_string(),
+                FieldType::Int,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "name".to_string(),
+                FieldType::String,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn null_record() -> Record {
+    Record::new(None, vec![Field::Null, Field::Null], None)
+}
+
+fn record(id: i64, name: &str) -> Record {
+    Record::new(
+        None,
+        vec![Field::Int(id), Field::String(name.to_string())],
+        None,
+    )
+}
+
+fn joined(left: &Record, right: &Record) -> Record {
+    Record::new(
+        None,
+        [left.values.as_slice(), right.values.as_slice()].concat(),
+        None,
+    )
+}
+
+#[test]
+fn test_full_outer_join() {
+    let condition = JoinCondition {
+        left_keys: vec![Expression::Column { index: 0 }],
+        right_keys: vec![Expression::Column { index: 2 }],
+        residual: None,
+    };
+    let mut operator = build_operator(JoinType::FullOuter, condition);
+    let alice = record(1, "Alice");
+    let bob = record(1, "Bob");
+    let engineering = record(1, "Engineering");
+    let sales = record(2, "Sales");
+
+    // unmatched records on either side are padded with nulls
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &alice).unwrap(),
+        vec![(JoinAction::Insert, joined(&alice, &null_record()))]
+    );
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &sales).unwrap(),
+        vec![(JoinAction::Insert, joined(&null_record(), &sales))]
+    );
+
+    // the first match retracts the null-padded row
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &engineering).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&alice, &null_record())),
+            (JoinAction::Insert, joined(&alice, &engineering)),
+        ]
+    );
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &bob).unwrap(),
+        vec![(JoinAction::Insert, joined(&bob, &engineering))]
+    );
+
+    // losing the last match restores the null-padded row
+    assert_eq!(
+        operator.delete(&JoinBranch::Right, &engineering).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&alice, &engineering)),
+            (JoinAction::Insert, joined(&alice, &null_record())),
+            (JoinAction::Delete, joined(&bob, &engineering)),
+            (JoinAction::Insert, joined(&bob, &null_record())),
+        ]
+    );
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &engineering).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&alice, &null_record())),
+            (JoinAction::Insert, joined(&alice, &engineering)),
+            (JoinAction::Delete, joined(&bob, &null_record())),
+            (JoinAction::Insert, joined(&bob, &engineering)),
+        ]
+    );
+    assert_eq!(
+        operator.delete(&JoinBranch::Left, &alice).unwrap(),
+        vec![(JoinAction::Delete, joined(&alice, &engineering))]
+    );
+    assert_eq!(
+        operator.delete(&JoinBranch::Left, &bob).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&bob, &engineering)),
+            (JoinAction::Insert, joined(&null_record(), &engineering)),
+        ]
+    );
+
+    // deleting an unmatched record retracts its null-padded row
+    assert_eq!(
+        operator.delete(&JoinBranch::Right, &sales).unwrap(),
+        vec![(JoinAction::Delete, joined(&null_record(), &sales))]
+    );
+}
+
+#[test]
+fn test_cross_join() {
+    let mut operator = build_operator(JoinType::Cross, JoinCondition::default());
+    let alice = record(1, "Alice");
+    let bob = record(2, "Bob");
+    let engineering = record(1, "Engineering");
+    let sales = record(2, "Sales");
+
+    assert!(operator
+        .insert(&JoinBranch::Right, &engineering)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &alice).unwrap(),
+        vec![(JoinAction::Insert, joined(&alice, &engineering))]
+    );
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &bob).unwrap(),
+        vec![(JoinAction::Insert, joined(&bob, &engineering))]
+    );
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &sales).unwrap(),
+        vec![
+            (JoinAction::Insert, joined(&alice, &sales)),
+            (JoinAction::Insert, joined(&bob, &sales)),
+        ]
+    );
+    assert_eq!(
+        operator.delete(&JoinBranch::Left, &alice).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&alice, &engineering)),
+            (JoinAction::Delete, joined(&alice, &sales)),
+        ]
+    );
+}
+
+#[test]
+fn test_right_outer_join_pads_the_left_side() {
+    let condition = JoinCondition {
+        left_keys: vec![Expression::Column { index: 0 }],
+        right_keys: vec![Expression::Column { index: 2 }],
+        residual: None,
+    };
+    let mut operator = build_operator(JoinType::RightOuter, condition);
+    let sales = record(2, "Sales");
+
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &sales).unwrap(),
+        vec![(JoinAction::Insert, joined(&null_record(), &sales))]
+    );
+}
+
+#[test]
+fn test_join_with_added_column() {
+    let condition = JoinCondition {
+        left_keys: vec![Expression::Column { index: 0 }],
+        right_keys: vec![Expression::Column { index: 2 }],
+        residual: None,
+    };
+    let mut operator = build_operator(JoinType::FullOuter, condition);
+    let alice = record(1, "Alice");
+    operator.insert(&JoinBranch::Left, &alice).unwrap();
+
+    let mut left_schema = schema();
+    left_schema.field(
+        FieldDefinition::new(
+            "email".to_string(),
+            FieldType::String,
+            true,
+            SourceDefinition::Dynamic,
+        ),
+        false,
+    );
+    let output_schema = operator
+        .on_schema_changed(&JoinBranch::Left, &left_schema)
+        .unwrap();
+    assert_eq!(output_schema.fields.len(), 5);
+    assert_eq!(output_schema.fields[2].name, "email");
+    assert_eq!(output_schema.fields[3].name, "id");
+
+    // records stored before the change are padded, and the condition still reads the right id
+    let padded_alice = Record::new(
+        None,
+        vec![
+            Field::Int(1),
+            Field::String("Alice".to_string()),
+            Field::Null,
+        ],
+        None,
+    );
+    let engineering = record(1, "Engineering");
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &engineering).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&padded_alice, &null_record())),
+            (JoinAction::Insert, joined(&padded_alice, &engineering)),
+        ]
+    );
+    let carol = Record::new(
+        None,
+        vec![
+            Field::Int(1),
+            Field::String("Carol".to_string()),
+            Field::String("carol@example.com".to_string()),
+        ],
+        None,
+    );
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &carol).unwrap(),
+        vec![(JoinAction::Insert, joined(&carol, &engineering))]
+    );
+
+    // the padded record removes the record stored before the change
+    assert_eq!(
+        operator.delete(&JoinBranch::Left, &padded_alice).unwrap(),
+        vec![(JoinAction::Delete, joined(&padded_alice, &engineering))]
+    );
+    assert_eq!(
+        operator.delete(&JoinBranch::Right, &engineering).unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&carol, &engineering)),
+            (JoinAction::Insert, joined(&carol, &null_record())),
+        ]
+    );
+
+    // columns can't be removed
+    assert!(operator
+        .on_schema_changed(&JoinBranch::Left, &schema())
+        .is_none());
+}
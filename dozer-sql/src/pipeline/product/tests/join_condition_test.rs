@@ -0,0 +1,192 @@
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+use sqlparser::ast::{JoinConstraint, JoinOperator as SqlJoinOperator};
+
+use crate::pipeline::expression::execution::Expression;
+use crate::pipeline::expression::scalar::common::ScalarFunctionType;
+use crate::pipeline::product::join::factory::parse_join_constraint;
+use crate::pipeline::product::join::operator::{
+    JoinAction, JoinBranch, JoinCondition, JoinOperator, JoinType,
+};
+use crate::pipeline::product::join::state::JoinTable;
+use crate::pipeline::state::StateOptions;
+use crate::pipeline::tests::utils::get_select;
+
+fn field(name: &str, typ: FieldType, alias: &str) -> FieldDefinition {
+    FieldDefinition::new(
+        name.to_string(),
+        typ,
+        true,
+        SourceDefinition::Alias {
+            name: alias.to_string(),
+        },
+    )
+}
+
+fn user_schema() -> Schema {
+    Schema::empty()
+        .field(field("id", FieldType::Int, "a"), true)
+        .field(field("email", FieldType::String, "a"), false)
+        .field(field("ts", FieldType::Int, "a"), false)
+        .clone()
+}
+
+fn session_schema() -> Schema {
+    Schema::empty()
+        .field(field("email", FieldType::String, "b"), false)
+        .field(field("start_ts", FieldType::Int, "b"), false)
+        .field(field("end_ts", FieldType::Int, "b"), false)
+        .clone()
+}
+
+fn parse_condition(sql: &str) -> JoinCondition {
+    let select = get_select(sql).unwrap();
+    let SqlJoinOperator::Inner(JoinConstraint::On(expression)) =
+        &select.from[0].joins[0].join_operator
+    else {
+        panic!("Expected an INNER JOIN with an ON constraint");
+    };
+    parse_join_constraint(expression, &user_schema(), &session_schema()).unwrap()
+}
+
+fn user(id: i64, email: &str, ts: i64) -> Record {
+    Record::new(
+        None,
+        vec![
+            Field::Int(id),
+            Field::String(email.to_string()),
+            Field::Int(ts),
+        ],
+        None,
+    )
+}
+
+fn session(email: &str, start_ts: i64, end_ts: i64) -> Record {
+    Record::new(
+        None,
+        vec![
+            Field::String(email.to_string()),
+            Field::Int(start_ts),
+            Field::Int(end_ts),
+        ],
+        None,
+    )
+}
+
+fn joined(left: &Record, right: &Record) -> Record {
+    Record::new(
+        None,
+        [left.values.as_slice(), right.values.as_slice()].concat(),
+        None,
+    )
+}
+
+#[test]
+fn test_expression_join_keys_and_residual() {
+    let condition = parse_condition(
+        "SELECT * FROM a JOIN b \
+        ON UCASE(a.email) = UCASE(b.email) AND a.ts >= b.start_ts AND a.ts <= b.end_ts",
+    );
+    assert_eq!(
+        condition.left_keys,
+        vec![Expression::ScalarFunction {
+            fun: ScalarFunctionType::Ucase,
+            args: vec![Expression::Column { index: 1 }],
+        }]
+    );
+    assert_eq!(
+        condition.right_keys,
+        vec![Expression::ScalarFunction {
+            fun: ScalarFunctionType::Ucase,
+            args: vec![Expression::Column { index: 3 }],
+        }]
+    );
+    assert!(condition.residual.is_some());
+
+    let condition = parse_condition("SELECT * FROM a JOIN b ON a.ts > b.start_ts AND a.id = 1");
+    assert!(condition.left_keys.is_empty());
+    assert!(condition.right_keys.is_empty());
+    assert!(condition.residual.is_some());
+}
+
+#[test]
+fn test_left_join_with_residual_condition() {
+    let condition = parse_condition(
+        "SELECT * FROM a JOIN b \
+        ON UCASE(a.email) = UCASE(b.email) AND a.ts >= b.start_ts AND a.ts <= b.end_ts",
+    );
+    let mut operator = JoinOperator::new(
+        JoinType::LeftOuter,
+        condition,
+        user_schema(),
+        session_schema(),
+        JoinTable::new(&StateOptions::Memory, "join_left").unwrap(),
+        JoinTable::new(&StateOptions::Memory, "join_right").unwrap(),
+    );
+    let no_session = Record::from_schema(&session_schema());
+
+    let alice = user(1, "alice@example.com", 10);
+    let earlier_session = session("ALICE@EXAMPLE.COM", 0, 5);
+    let current_session = session("Alice@Example.com", 5, 15);
+
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &alice).unwrap(),
+        vec![(JoinAction::Insert, joined(&alice, &no_session))]
+    );
+    // same key, but the residual condition doesn't hold
+    assert!(operator
+        .insert(&JoinBranch::Right, &earlier_session)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        operator
+            .insert(&JoinBranch::Right, &current_session)
+            .unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&alice, &no_session)),
+            (JoinAction::Insert, joined(&alice, &current_session)),
+        ]
+    );
+    assert!(operator
+        .delete(&JoinBranch::Right, &earlier_session)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        operator
+            .delete(&JoinBranch::Right, &current_session)
+            .unwrap(),
+        vec![
+            (JoinAction::Delete, joined(&alice, &current_session)),
+            (JoinAction::Insert, joined(&alice, &no_session)),
+        ]
+    );
+}
+
+#[test]
+fn test_range_join_without_keys() {
+    let condition = parse_condition("SELECT * FROM a JOIN b ON a.ts >= b.start_ts");
+    let mut operator = JoinOperator::new(
+        JoinType::Inner,
+        condition,
+        user_schema(),
+        session_schema(),
+        JoinTable::new(&StateOptions::Memory, "join_left").unwrap(),
+        JoinTable::new(&StateOptions::Memory, "join_right").unwrap(),
+    );
+
+    let early = session("alice@example.com", 0, 5);
+    let late = session("bob@example.com", 20, 25);
+    assert!(operator
+        .insert(&JoinBranch::Right, &early)
+        .unwrap()
+        .is_empty());
+    assert!(operator
+        .insert(&JoinBranch::Right, &late)
+        .unwrap()
+        .is_empty());
+
+    let alice = user(1, "alice@example.com", 10);
+    assert_eq!(
+        operator.insert(&JoinBranch::Left, &alice).unwrap(),
+        vec![(JoinAction::Insert, joined(&alice, &early))]
+    );
+}
@@ -0,0 +1,150 @@
+use dozer_types::node::{NodeHandle, OpIdentifier, SourceStates};
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+use tempdir::TempDir;
+
+use crate::pipeline::expression::execution::Expression;
+use crate::pipeline::product::join::operator::{
+    JoinAction, JoinBranch, JoinCondition, JoinOperator, JoinType,
+};
+use crate::pipeline::product::join::state::JoinTable;
+use crate::pipeline::state::StateOptions;
+
+fn build_operator(join_type: JoinType, state_options: &StateOptions) -> JoinOperator {
+    let condition = JoinCondition {
+        left_keys: vec![Expression::Column { index: 0 }],
+        right_keys: vec![Expression::Column { index: 2 }],
+        residual: None,
+    };
+    JoinOperator::new(
+        join_type,
+        condition,
+        schema(),
+        schema(),
+        JoinTable::new(state_options, "join_left").unwrap(),
+        JoinTable::new(state_options, "join_right").unwrap(),
+    )
+}
+
+fn schema() -> Schema {
+    Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "id".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "name".to_string(),
+                FieldType::String,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn record(id: i64, name: &str) -> Record {
+    Record::new(
+        None,
+        vec![Field::Int(id), Field::String(name.to_string())],
+        None,
+    )
+}
+
+fn joined(left: &Record, right: &Record) -> Record {
+    Record::new(
+        None,
+        [left.values.as_slice(), right.values.as_slice()].concat(),
+        None,
+    )
+}
+
+fn source_states(txid: u64) -> SourceStates {
+    SourceStates::from([(
+        NodeHandle::new(None, "source".to_string()),
+        OpIdentifier::new(txid, 0),
+    )])
+}
+
+#[test]
+fn test_join_state_on_disk() {
+    let tmp_dir = TempDir::new("join_state").unwrap();
+    let state_options = StateOptions::disk(tmp_dir.path().to_path_buf());
+
+    let mut operator = build_operator(JoinType::Inner, &state_options);
+    let alice = record(1, "Alice");
+    let bob = record(1, "Bob");
+    let carol = record(2, "Carol");
+    assert!(operator
+        .insert(&JoinBranch::Left, &alice)
+        .unwrap()
+        .is_empty());
+    assert!(operator.insert(&JoinBranch::Left, &bob).unwrap().is_empty());
+    assert!(operator
+        .insert(&JoinBranch::Left, &carol)
+        .unwrap()
+        .is_empty());
+    operator.delete(&JoinBranch::Left, &carol).unwrap();
+    let source_states = source_states(3);
+    operator.commit(&source_states).unwrap();
+    drop(operator);
+
+    // A new operator joins against the committed left branch.
+    let mut operator = build_operator(JoinType::Inner, &state_options);
+    assert!(operator.is_resumable(&source_states));
+    assert!(!operator.is_resumable(&source_states(4)));
+    let department = record(1, "Engineering");
+    assert_eq!(
+        operator.insert(&JoinBranch::Right, &department).unwrap(),
+        vec![
+            (JoinAction::Insert, joined(&alice, &department)),
+            (JoinAction::Insert, joined(&bob, &department)),
+        ]
+    );
+    assert!(operator
+        .insert(&JoinBranch::Right, &record(2, "Sales"))
+        .unwrap()
+        .is_empty());
+
+    assert_eq!(
+        operator.delete(&JoinBranch::Left, &alice).unwrap(),
+        vec![(JoinAction::Delete, joined(&alice, &department))]
+    );
+    assert_eq!(
+        operator.delete(&JoinBranch::Right, &department).unwrap(),
+        vec![(JoinAction::Delete, joined(&bob, &department))]
+    );
+}
+
+#[test]
+fn test_join_state_reset() {
+    let tmp_dir = TempDir::new("join_state").unwrap();
+    let state_options = StateOptions::disk(tmp_dir.path().to_path_buf());
+
+    let mut operator = build_operator(JoinType::Inner, &state_options);
+    operator
+        .insert(&JoinBranch::Left, &record(1, "Alice"))
+        .unwrap();
+    operator.commit(&source_states(1)).unwrap();
+    drop(operator);
+
+    let mut operator = build_operator(JoinType::Inner, &state_options);
+    operator.reset().unwrap();
+    assert!(!operator.is_resumable(&source_states(1)));
+    assert!(operator
+        .insert(&JoinBranch::Right, &record(1, "Engineering"))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_join_state_in_memory_is_not_resumable() {
+    let mut operator = build_operator(JoinType::Inner, &StateOptions::Memory);
+    operator.commit(&source_states(1)).unwrap();
+    assert!(!operator.is_resumable(&source_states(1)));
+}
@@ -1,6 +1,7 @@
 use dozer_core::app::{App, AppPipeline};
 use dozer_core::appsource::{AppSource, AppSourceManager};
 use dozer_core::channels::SourceChannelForwarder;
+use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
 use dozer_core::executor::{DagExecutor, ExecutorOptions};
 use dozer_core::node::{
@@ -491,7 +492,7 @@ impl Sink for TestSink {
         Ok(())
     }
 
-    fn commit(&mut self) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod factory_tests;
 #[cfg(test)]
+mod full_cross_join_test;
+#[cfg(test)]
+mod join_condition_test;
+#[cfg(test)]
+mod join_state_test;
+#[cfg(test)]
 mod left_join_test;
 #[cfg(test)]
 mod pipeline_test;
@@ -1,11 +1,14 @@
 use crate::pipeline::builder::{statement_to_pipeline, SchemaSQLContext};
+use crate::pipeline::product::set::set_factory::SetProcessorFactory;
 use dozer_core::app::{App, AppPipeline};
 use dozer_core::appsource::{AppSource, AppSourceManager};
-use dozer_core::channels::SourceChannelForwarder;
+use dozer_core::channels::{ProcessorChannelForwarder, SourceChannelForwarder};
+use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
 use dozer_core::executor::{DagExecutor, ExecutorOptions};
 use dozer_core::node::{
-    OutputPortDef, OutputPortType, PortHandle, Sink, SinkFactory, Source, SourceFactory,
+    OutputPortDef, OutputPortType, PortHandle, ProcessorFactory, Sink, SinkFactory, Source,
+    SourceFactory,
 };
 use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_types::chrono::NaiveDate;
@@ -14,6 +17,7 @@ use dozer_types::log::debug;
 use dozer_types::types::{
     Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
 };
+use sqlparser::ast::SetQuantifier;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -466,7 +470,7 @@ impl Sink for TestSink {
         Ok(())
     }
 
-    fn commit(&mut self) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
@@ -474,3 +478,104 @@ impl Sink for TestSink {
         Ok(())
     }
 }
+
+struct TestChannelForwarder {
+    operations: Vec<Operation>,
+}
+
+impl ProcessorChannelForwarder for TestChannelForwarder {
+    fn send(&mut self, op: Operation, _port: PortHandle) -> Result<(), ExecutionError> {
+        self.operations.push(op);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_set_union_with_added_column() {
+    let id = FieldDefinition::new(
+        "id".to_string(),
+        FieldType::Int,
+        false,
+        SourceDefinition::Dynamic,
+    );
+    let name = FieldDefinition::new(
+        "name".to_string(),
+        FieldType::String,
+        true,
+        SourceDefinition::Dynamic,
+    );
+    let schema = Schema::empty().field(id, false).clone();
+    let wider_schema = schema.clone().field(name, false).clone();
+
+    let mut processor = SetProcessorFactory::new(SetQuantifier::None)
+        .build(
+            HashMap::from([(0, schema.clone()), (1, schema.clone())]),
+            HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+        )
+        .unwrap();
+    let mut fw = TestChannelForwarder { operations: vec![] };
+    let record = |values: Vec<Field>| Record::new(None, values, None);
+
+    processor
+        .process(
+            0,
+            Operation::Insert {
+                new: record(vec![Field::Int(1)]),
+            },
+            &mut fw,
+        )
+        .unwrap();
+
+    // the column added to the first input is added to the output
+    let output_schemas = processor.on_schema_changed(0, &wider_schema).unwrap();
+    assert_eq!(
+        output_schemas.get(&DEFAULT_PORT_HANDLE),
+        Some(&wider_schema)
+    );
+
+    // records of the second input are padded, and count as the records counted before the change
+    processor
+        .process(
+            1,
+            Operation::Insert {
+                new: record(vec![Field::Int(1)]),
+            },
+            &mut fw,
+        )
+        .unwrap();
+    processor
+        .process(
+            0,
+            Operation::Delete {
+                old: record(vec![Field::Int(1), Field::Null]),
+            },
+            &mut fw,
+        )
+        .unwrap();
+    processor
+        .process(
+            1,
+            Operation::Delete {
+                old: record(vec![Field::Int(1)]),
+            },
+            &mut fw,
+        )
+        .unwrap();
+    assert_eq!(
+        fw.operations,
+        vec![
+            Operation::Insert {
+                new: record(vec![Field::Int(1)]),
+            },
+            Operation::Delete {
+                old: record(vec![Field::Int(1), Field::Null]),
+            },
+        ]
+    );
+
+    // the same column added to the second input leaves the output as it is
+    assert!(processor
+        .on_schema_changed(1, &wider_schema)
+        .unwrap()
+        .is_empty());
+}
@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
+use crate::pipeline::planner::projection::CommonPlanner;
 
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::epoch::Epoch;
@@ -7,11 +10,15 @@ use dozer_core::errors::ExecutionError::InternalError;
 use dozer_core::node::{PortHandle, Processor};
 use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_types::types::{Operation, Record, Schema};
+use sqlparser::ast::Select;
 
 #[derive(Debug)]
 pub struct ProjectionProcessor {
     expressions: Vec<Expression>,
     input_schema: Schema,
+    /// The query the expressions were planned from and the output schema, if the expressions are
+    /// planned again when columns are added to the input, so that wildcards select them.
+    select: Option<(Select, Schema)>,
 }
 
 impl ProjectionProcessor {
@@ -19,6 +26,16 @@ impl ProjectionProcessor {
         Self {
             input_schema,
             expressions,
+            select: None,
+        }
+    }
+
+    /// A projection that `planner` planned from `select`.
+    pub fn with_select(input_schema: Schema, select: Select, planner: CommonPlanner) -> Self {
+        Self {
+            input_schema,
+            expressions: planner.projection_output,
+            select: Some((select, planner.post_projection_schema)),
         }
     }
 
@@ -89,7 +106,38 @@ impl Processor for ProjectionProcessor {
         Ok(())
     }
 
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
+
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        if self.input_schema.added_fields(schema).is_none() {
+            return Err(ExecutionError::UnsupportedSchemaChange(
+                "projection input columns changed".to_string(),
+            ));
+        }
+        self.input_schema = schema.clone();
+
+        let Some((select, output_schema)) = &mut self.select else {
+            // Projected expressions only refer to existing columns, so additive changes don't affect the output.
+            return Ok(HashMap::new());
+        };
+        let mut planner = CommonPlanner::new(schema.clone());
+        planner
+            .plan(select.clone())
+            .map_err(|e| InternalError(Box::new(e)))?;
+        self.expressions = planner.projection_output;
+        if &planner.post_projection_schema == output_schema {
+            return Ok(HashMap::new());
+        }
+        *output_schema = planner.post_projection_schema.clone();
+        Ok(HashMap::from([(
+            DEFAULT_PORT_HANDLE,
+            planner.post_projection_schema,
+        )]))
+    }
 }
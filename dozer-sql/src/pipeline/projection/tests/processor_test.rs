@@ -0,0 +1,120 @@
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::errors::ExecutionError;
+use dozer_core::node::{PortHandle, Processor};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::types::{
+    Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
+};
+
+use crate::pipeline::planner::projection::CommonPlanner;
+use crate::pipeline::projection::processor::ProjectionProcessor;
+use crate::pipeline::tests::utils::get_select;
+
+struct TestChannelForwarder {
+    operations: Vec<Operation>,
+}
+
+impl ProcessorChannelForwarder for TestChannelForwarder {
+    fn send(&mut self, op: Operation, _port: PortHandle) -> Result<(), ExecutionError> {
+        self.operations.push(op);
+        Ok(())
+    }
+}
+
+fn schema() -> Schema {
+    Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "id".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            true,
+        )
+        .field(
+            FieldDefinition::new(
+                "name".to_string(),
+                FieldType::String,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn wider_schema() -> Schema {
+    schema()
+        .field(
+            FieldDefinition::new(
+                "email".to_string(),
+                FieldType::String,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn build_processor(sql: &str) -> ProjectionProcessor {
+    let select = *get_select(sql).unwrap();
+    let mut planner = CommonPlanner::new(schema());
+    planner.plan(select.clone()).unwrap();
+    ProjectionProcessor::with_select(schema(), select, planner)
+}
+
+#[test]
+fn test_wildcard_selects_added_column() {
+    let mut processor = build_processor("SELECT * FROM users");
+
+    let output_schema = processor
+        .on_schema_changed(DEFAULT_PORT_HANDLE, &wider_schema())
+        .unwrap()
+        .remove(&DEFAULT_PORT_HANDLE)
+        .unwrap();
+    let names = output_schema
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["id", "name", "email"]);
+
+    let values = vec![
+        Field::Int(1),
+        Field::String("Alice".to_string()),
+        Field::String("alice@example.com".to_string()),
+    ];
+    let mut fw = TestChannelForwarder { operations: vec![] };
+    processor
+        .process(
+            DEFAULT_PORT_HANDLE,
+            Operation::Insert {
+                new: Record::new(None, values.clone(), None),
+            },
+            &mut fw,
+        )
+        .unwrap();
+    assert_eq!(
+        fw.operations,
+        vec![Operation::Insert {
+            new: Record::new(None, values, None),
+        }]
+    );
+}
+
+#[test]
+fn test_named_columns_ignore_added_column() {
+    let mut processor = build_processor("SELECT name FROM users");
+
+    assert!(processor
+        .on_schema_changed(DEFAULT_PORT_HANDLE, &wider_schema())
+        .unwrap()
+        .is_empty());
+
+    // columns can't be removed
+    assert!(processor
+        .on_schema_changed(DEFAULT_PORT_HANDLE, &schema())
+        .is_err());
+}
@@ -4,7 +4,7 @@ use crate::pipeline::builder::SchemaSQLContext;
 use crate::pipeline::expression::builder::ExpressionBuilder;
 use dozer_core::{
     errors::ExecutionError,
-    node::{OutputPortDef, OutputPortType, PortHandle, Processor, ProcessorFactory},
+    node::{OutputPortDef, OutputPortType, Partitioning, PortHandle, Processor, ProcessorFactory},
     DEFAULT_PORT_HANDLE,
 };
 use dozer_types::types::Schema;
@@ -64,4 +64,11 @@ impl ProcessorFactory<SchemaSQLContext> for SelectionProcessorFactory {
             Err(e) => Err(ExecutionError::InternalStringError(e.to_string())),
         }
     }
+
+    fn partitioning(
+        &self,
+        _input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Partitioning, ExecutionError> {
+        Ok(Partitioning::RoundRobin)
+    }
 }
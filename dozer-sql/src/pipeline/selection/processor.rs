@@ -1,4 +1,6 @@
 use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
+use std::collections::HashMap;
+
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
@@ -35,7 +37,7 @@ impl SelectionProcessor {
 }
 
 impl Processor for SelectionProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
@@ -98,4 +100,14 @@ impl Processor for SelectionProcessor {
         }
         Ok(())
     }
+
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        // Records are forwarded as is, so the output schema follows the input schema.
+        self.input_schema = schema.clone();
+        Ok(HashMap::from([(DEFAULT_PORT_HANDLE, schema.clone())]))
+    }
 }
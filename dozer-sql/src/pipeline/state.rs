@@ -0,0 +1,136 @@
+use std::hash::Hasher;
+use std::path::PathBuf;
+
+use dozer_storage::{
+    lmdb_storage::{LmdbEnvironmentManager, LmdbEnvironmentOptions},
+    LmdbOption, RwLmdbEnvironment,
+};
+use dozer_types::chrono::{Offset, Utc};
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::Field;
+use dozer_types::{bincode, borrow::Borrow, node::SourceStates};
+
+use twox_hash::XxHash64;
+
+use super::errors::StateStoreError;
+
+pub const DEFAULT_STATE_MAX_MAP_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Where stateful processors (JOIN and aggregation) keep their state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StateOptions {
+    /// State is kept in memory and is lost when the pipeline stops, so the pipeline starts over
+    /// on every run.
+    #[default]
+    Memory,
+    /// State is kept in LMDB environments under `path`. It can grow beyond available memory
+    /// and is committed together with every epoch, so the pipeline can resume with it.
+    Disk { path: PathBuf, max_map_size: usize },
+}
+
+impl StateOptions {
+    pub fn disk(path: PathBuf) -> Self {
+        Self::Disk {
+            path,
+            max_map_size: DEFAULT_STATE_MAX_MAP_SIZE,
+        }
+    }
+
+    /// Opens the environment of the state store `name`. Returns `None` if state is kept in memory.
+    pub(crate) fn open_environment(
+        &self,
+        name: &str,
+    ) -> Result<Option<RwLmdbEnvironment>, StateStoreError> {
+        match self {
+            StateOptions::Memory => Ok(None),
+            StateOptions::Disk { path, max_map_size } => {
+                let options = LmdbEnvironmentOptions {
+                    max_map_sz: *max_map_size,
+                    ..Default::default()
+                };
+                let env = LmdbEnvironmentManager::create_rw(path, name, options)?;
+                Ok(Some(env))
+            }
+        }
+    }
+}
+
+/// Encodes the values of a state key, such as a group or a join key.
+///
+/// Equal values have the same encoding, and the encoding doesn't depend on the process, so state
+/// written to disk is found again after a restart. It's never empty, as LMDB doesn't accept empty
+/// keys.
+pub(crate) fn encode_key(values: &[Field]) -> Result<Vec<u8>, StateStoreError> {
+    let values = values.iter().map(normalize_key_value).collect::<Vec<_>>();
+    bincode::serialize(&values).map_err(StateStoreError::Serialization)
+}
+
+/// Hashes an encoded key with a fixed seed.
+///
+/// Unlike the std and ahash hashers, the hash doesn't depend on the process, so it can pick the
+/// partition a key is routed to, or be kept in state, and still be valid after a restart.
+pub(crate) fn stable_hash(key: &[u8]) -> u64 {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(key);
+    hasher.finish()
+}
+
+/// Picks one representation of the values that are equal but serialize differently.
+fn normalize_key_value(value: &Field) -> Field {
+    match value {
+        Field::Float(float) if float.0 == 0.0 => Field::Float(OrderedFloat(0.0)),
+        Field::Float(float) if float.is_nan() => Field::Float(OrderedFloat(f64::NAN)),
+        Field::Decimal(decimal) => Field::Decimal(decimal.normalize()),
+        Field::Timestamp(timestamp) => Field::Timestamp(timestamp.with_timezone(&Utc.fix())),
+        value => value.clone(),
+    }
+}
+
+/// The source states of the last epoch a state store committed.
+///
+/// It's stored in the environment of the state, so it's committed atomically with it. The
+/// pipeline only resumes if it matches the checkpoint of the sinks.
+#[derive(Debug)]
+pub(crate) struct CommittedEpoch {
+    database: LmdbOption<Vec<u8>>,
+    source_states: Option<SourceStates>,
+}
+
+impl CommittedEpoch {
+    pub fn create(env: &mut RwLmdbEnvironment) -> Result<Self, StateStoreError> {
+        let database = LmdbOption::create(env, Some("committed_epoch"))?;
+        let source_states = match database.load(env.txn_mut()?)? {
+            Some(bytes) => Some(
+                bincode::deserialize(bytes.borrow()).map_err(StateStoreError::Deserialization)?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            database,
+            source_states,
+        })
+    }
+
+    pub fn is(&self, checkpoint: &SourceStates) -> bool {
+        self.source_states.as_ref() == Some(checkpoint)
+    }
+
+    /// Records `source_states` in the current transaction of `env`.
+    pub fn store(
+        &mut self,
+        env: &mut RwLmdbEnvironment,
+        source_states: &SourceStates,
+    ) -> Result<(), StateStoreError> {
+        let bytes = bincode::serialize(source_states).map_err(StateStoreError::Serialization)?;
+        self.database.store(env.txn_mut()?, &bytes)?;
+        self.source_states = Some(source_states.clone());
+        Ok(())
+    }
+
+    /// Forgets the committed epoch in the current transaction of `env`.
+    pub fn clear(&mut self, env: &mut RwLmdbEnvironment) -> Result<(), StateStoreError> {
+        self.database.clear(env.txn_mut()?)?;
+        self.source_states = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use dozer_types::log::warn;
+use sqlparser::ast::{
+    BinaryOperator, Expr as SqlExpr, FunctionArg, FunctionArgExpr, Ident, Query, Select,
+    SelectItem, SetExpr, TableFactor, Value as SqlValue,
+};
+
+use crate::pipeline::errors::{PipelineError, TopNError, UnsupportedSqlError};
+use crate::pipeline::expression::aggregate::AggregateFunctionType;
+use crate::pipeline::product::join::factory::split_conjunction;
+
+use super::factory::{TopNProcessorFactory, ROW_NUMBER_COLUMN};
+
+/// Returns the processor for the `ORDER BY ... LIMIT ... OFFSET ...` of a query, if it has one.
+pub(crate) fn top_n_from_query(
+    query: &Query,
+) -> Result<Option<TopNProcessorFactory>, PipelineError> {
+    if query.order_by.is_empty() && query.limit.is_none() && query.offset.is_none() {
+        return Ok(None);
+    }
+    if query.order_by.is_empty() {
+        return Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::LimitOffsetError,
+        ));
+    }
+    let Some(limit) = &query.limit else {
+        return Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::OrderByError,
+        ));
+    };
+
+    let limit = parse_row_count(limit)?;
+    let offset = match &query.offset {
+        Some(offset) => parse_row_count(&offset.value)?,
+        None => 0,
+    };
+    Ok(Some(TopNProcessorFactory::limit(
+        query.order_by.clone(),
+        offset,
+        limit,
+    )))
+}
+
+/// Takes `ROW_NUMBER() OVER (...)` out of the projection and returns the processor computing it.
+/// The projection reads the row number at the position of the `ROW_NUMBER()` item. `limit` is the
+/// bound on the row number pushed down from the enclosing query, if any.
+pub(crate) fn take_row_number(
+    select: &mut Select,
+    limit: Option<usize>,
+) -> Result<Option<TopNProcessorFactory>, PipelineError> {
+    let aggregated = is_aggregation(select);
+    let mut row_number = None;
+
+    for item in select.projection.iter_mut() {
+        let (expression, column_name) = match &*item {
+            SelectItem::UnnamedExpr(expression) => (expression, expression.to_string()),
+            SelectItem::ExprWithAlias { expr, alias } => (expr, alias.value.clone()),
+            _ => continue,
+        };
+
+        let SqlExpr::Function(function) = expression else {
+            continue;
+        };
+        let Some(window) = &function.over else {
+            continue;
+        };
+        if function.name.to_string().to_lowercase() != "row_number" {
+            return Err(PipelineError::TopNError(
+                TopNError::UnsupportedWindowFunction(function.name.to_string()),
+            ));
+        }
+
+        if row_number.is_some() {
+            return Err(PipelineError::TopNError(TopNError::MultipleRowNumbers));
+        }
+        if !function.args.is_empty() {
+            return Err(PipelineError::TopNError(TopNError::RowNumberArguments));
+        }
+        if window.window_frame.is_some() {
+            return Err(PipelineError::TopNError(TopNError::UnsupportedWindowFrame));
+        }
+        if window.order_by.is_empty() {
+            return Err(PipelineError::TopNError(TopNError::RowNumberWithoutOrderBy));
+        }
+        if aggregated {
+            return Err(PipelineError::TopNError(
+                TopNError::RowNumberWithAggregation,
+            ));
+        }
+        if limit.is_none() {
+            warn!(
+                "{column_name} is computed for every row, so every row is kept in memory. \
+                Filter on it with `WHERE {column_name} <= N` in the enclosing query to only keep \
+                the first N rows of each partition"
+            );
+        }
+
+        row_number = Some(TopNProcessorFactory::row_number(
+            window.partition_by.clone(),
+            window.order_by.clone(),
+            limit,
+        ));
+        *item = SelectItem::ExprWithAlias {
+            expr: SqlExpr::Identifier(Ident::new(ROW_NUMBER_COLUMN)),
+            alias: Ident::new(column_name),
+        };
+    }
+
+    Ok(row_number)
+}
+
+/// Returns the bound of a `WHERE rn <= N` filter of `select`, if `select` reads from a single
+/// subquery computing `rn` with `ROW_NUMBER()`. Only the first N row numbers of each partition
+/// then need to be computed.
+pub(crate) fn row_number_limit(select: &Select) -> Option<usize> {
+    let selection = select.selection.as_ref()?;
+    let [from] = select.from.as_slice() else {
+        return None;
+    };
+    if !from.joins.is_empty() {
+        return None;
+    }
+    let TableFactor::Derived { subquery, .. } = &from.relation else {
+        return None;
+    };
+    // `ORDER BY ... LIMIT` of the subquery would pick its rows among all row numbers.
+    if subquery.with.is_some()
+        || !subquery.order_by.is_empty()
+        || subquery.limit.is_some()
+        || subquery.offset.is_some()
+    {
+        return None;
+    }
+    let SetExpr::Select(subquery) = subquery.body.as_ref() else {
+        return None;
+    };
+    let column = subquery.projection.iter().find_map(|item| match item {
+        SelectItem::ExprWithAlias {
+            expr: SqlExpr::Function(function),
+            alias,
+        } if function.over.is_some() => Some(alias.value.as_str()),
+        _ => None,
+    })?;
+
+    split_conjunction(selection)
+        .into_iter()
+        .filter_map(|conjunct| row_number_bound(conjunct, column))
+        .min()
+}
+
+fn row_number_bound(expression: &SqlExpr, column: &str) -> Option<usize> {
+    let SqlExpr::BinaryOp { left, op, right } = expression else {
+        return None;
+    };
+    let is_column = match left.as_ref() {
+        SqlExpr::Identifier(ident) => ident.value == column,
+        SqlExpr::CompoundIdentifier(idents) => {
+            matches!(idents.last(), Some(ident) if ident.value == column)
+        }
+        _ => false,
+    };
+    if !is_column {
+        return None;
+    }
+    let SqlExpr::Value(SqlValue::Number(bound, _)) = right.as_ref() else {
+        return None;
+    };
+
+    let bound: usize = bound.parse().ok()?;
+    match op {
+        BinaryOperator::LtEq | BinaryOperator::Eq => Some(bound),
+        BinaryOperator::Lt => Some(bound.saturating_sub(1)),
+        _ => None,
+    }
+}
+
+/// Whether `select` groups or aggregates its rows.
+pub(crate) fn is_aggregation(select: &Select) -> bool {
+    !select.group_by.is_empty()
+        || select.having.is_some()
+        || select.projection.iter().any(|item| match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                contains_aggregation(expr)
+            }
+            _ => false,
+        })
+}
+
+fn contains_aggregation(expression: &SqlExpr) -> bool {
+    match expression {
+        SqlExpr::Function(function) => {
+            let name = function.name.to_string().to_lowercase();
+            if function.over.is_none() && AggregateFunctionType::new(&name).is_ok() {
+                return true;
+            }
+            function.args.iter().any(|arg| match arg {
+                FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(expr),
+                    ..
+                }
+                | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => contains_aggregation(expr),
+                _ => false,
+            })
+        }
+        SqlExpr::BinaryOp { left, right, .. } => {
+            contains_aggregation(left) || contains_aggregation(right)
+        }
+        SqlExpr::UnaryOp { expr, .. }
+        | SqlExpr::Nested(expr)
+        | SqlExpr::Cast { expr, .. }
+        | SqlExpr::IsNull(expr)
+        | SqlExpr::IsNotNull(expr) => contains_aggregation(expr),
+        SqlExpr::Between {
+            expr, low, high, ..
+        } => contains_aggregation(expr) || contains_aggregation(low) || contains_aggregation(high),
+        SqlExpr::InList { expr, list, .. } => {
+            contains_aggregation(expr) || list.iter().any(contains_aggregation)
+        }
+        SqlExpr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            operand
+                .iter()
+                .chain(else_result)
+                .any(|expr| contains_aggregation(expr))
+                || conditions.iter().chain(results).any(contains_aggregation)
+        }
+        _ => false,
+    }
+}
+
+fn parse_row_count(expression: &SqlExpr) -> Result<usize, PipelineError> {
+    match expression {
+        SqlExpr::Value(SqlValue::Number(number, _)) => number
+            .parse()
+            .map_err(|_| PipelineError::TopNError(TopNError::InvalidRowCount(number.clone()))),
+        _ => Err(PipelineError::TopNError(TopNError::InvalidRowCount(
+            expression.to_string(),
+        ))),
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use dozer_core::{
+    errors::ExecutionError,
+    node::{OutputPortDef, OutputPortType, PortHandle, Processor, ProcessorFactory},
+    DEFAULT_PORT_HANDLE,
+};
+use dozer_types::types::{FieldDefinition, FieldType, Schema, SourceDefinition};
+use sqlparser::ast::{Expr as SqlExpr, OrderByExpr, SelectItem};
+
+use crate::pipeline::{
+    builder::SchemaSQLContext,
+    errors::{PipelineError, TopNError},
+    expression::builder::ExpressionBuilder,
+};
+
+use super::{
+    operator::{OrderByExpression, TopNOperator, TopNOutput},
+    processor::TopNProcessor,
+};
+
+/// Name of the column `ROW_NUMBER()` is computed into. The projection reads it under the alias of
+/// the `ROW_NUMBER()` item, and wildcards skip it.
+pub const ROW_NUMBER_COLUMN: &str = "__dozer_row_number";
+
+pub(crate) fn row_number_field() -> FieldDefinition {
+    FieldDefinition::new(
+        ROW_NUMBER_COLUMN.to_string(),
+        FieldType::Int,
+        false,
+        SourceDefinition::Dynamic,
+    )
+}
+
+#[derive(Debug)]
+pub struct TopNProcessorFactory {
+    partition_by: Vec<SqlExpr>,
+    order_by: Vec<OrderByExpr>,
+    output: TopNOutput,
+}
+
+impl TopNProcessorFactory {
+    /// `ORDER BY ... LIMIT ... OFFSET ...` of a query.
+    pub fn limit(order_by: Vec<OrderByExpr>, offset: usize, limit: usize) -> Self {
+        Self {
+            partition_by: vec![],
+            order_by,
+            output: TopNOutput::Limit { offset, limit },
+        }
+    }
+
+    /// `ROW_NUMBER() OVER (PARTITION BY ... ORDER BY ...)` of a projection, only computed for the
+    /// first `limit` rows of each partition if a filter on the row number was pushed down.
+    pub fn row_number(
+        partition_by: Vec<SqlExpr>,
+        order_by: Vec<OrderByExpr>,
+        limit: Option<usize>,
+    ) -> Self {
+        Self {
+            partition_by,
+            order_by,
+            output: TopNOutput::RowNumber { limit },
+        }
+    }
+
+    /// Replaces the `ORDER BY` items naming a column alias of `projection` with the aliased
+    /// expression, so that they can be evaluated before the projection.
+    pub fn resolve_aliases(&mut self, projection: &[SelectItem]) {
+        for order_by in &mut self.order_by {
+            let SqlExpr::Identifier(ident) = &order_by.expr else {
+                continue;
+            };
+            let aliased = projection.iter().find_map(|item| match item {
+                SelectItem::ExprWithAlias { expr, alias } if alias.value == ident.value => {
+                    Some(expr)
+                }
+                _ => None,
+            });
+            if let Some(expr) = aliased {
+                order_by.expr = expr.clone();
+            }
+        }
+    }
+
+    fn build_operator(&self, schema: &Schema) -> Result<TopNOperator, PipelineError> {
+        let mut builder = ExpressionBuilder::new(schema.fields.len());
+
+        let partition_by = self
+            .partition_by
+            .iter()
+            .map(|expression| builder.build(false, expression, schema))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut order_by = vec![];
+        for expression in &self.order_by {
+            if expression.nulls_first.is_some() {
+                return Err(PipelineError::TopNError(
+                    TopNError::UnsupportedNullsOrdering,
+                ));
+            }
+            order_by.push(OrderByExpression {
+                expression: builder.build(false, &expression.expr, schema)?,
+                descending: expression.asc == Some(false),
+            });
+        }
+
+        Ok(TopNOperator::new(
+            partition_by,
+            order_by,
+            self.output.clone(),
+            schema.clone(),
+        ))
+    }
+}
+
+impl ProcessorFactory<SchemaSQLContext> for TopNProcessorFactory {
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<OutputPortDef> {
+        vec![OutputPortDef::new(
+            DEFAULT_PORT_HANDLE,
+            OutputPortType::Stateless,
+        )]
+    }
+
+    fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, (Schema, SchemaSQLContext)>,
+    ) -> Result<(Schema, SchemaSQLContext), ExecutionError> {
+        let (mut schema, context) = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(ExecutionError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?
+            .clone();
+
+        if let TopNOutput::RowNumber { .. } = &self.output {
+            schema.field(row_number_field(), false);
+        }
+        Ok((schema, context))
+    }
+
+    fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Box<dyn Processor>, ExecutionError> {
+        let schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(ExecutionError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
+
+        let operator = self
+            .build_operator(schema)
+            .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+        Ok(Box::new(TopNProcessor::new(operator)))
+    }
+}
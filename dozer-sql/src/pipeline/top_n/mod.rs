@@ -0,0 +1,5 @@
+pub(crate) mod builder;
+pub(crate) mod factory;
+pub(crate) mod operator;
+mod processor;
+mod tests;
@@ -0,0 +1,298 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use dozer_types::types::{Field, Operation, Record, Schema};
+
+use crate::pipeline::errors::PipelineError;
+use crate::pipeline::expression::execution::{Expression, ExpressionExecutor};
+
+use super::factory::row_number_field;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderByExpression {
+    pub expression: Expression,
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopNOutput {
+    /// Only the rows at positions `offset..offset + limit` of each partition.
+    Limit { offset: usize, limit: usize },
+    /// Every row, or only the first `limit` rows, with its 1-based position in the partition
+    /// appended.
+    RowNumber { limit: Option<usize> },
+}
+
+/// A sort column value. Descending values are reversed so rows always sort in ascending order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Ascending(Field),
+    Descending(Reverse<Field>),
+}
+
+type SortKey = Vec<SortValue>;
+
+/// The rows of a partition, sorted. Rows with equal sort keys keep their arrival order.
+#[derive(Debug, Default)]
+struct Partition {
+    rows: Vec<(SortKey, Record)>,
+}
+
+/// Keeps the rows of each partition sorted and turns every change into the deltas of the ranked
+/// output.
+///
+/// Every row is retained, also when the output is bounded to the first N rows of a partition, so
+/// that a row deleted from the output is always replaced by the next row of the partition.
+#[derive(Debug)]
+pub struct TopNOperator {
+    partition_by: Vec<Expression>,
+    order_by: Vec<OrderByExpression>,
+    output: TopNOutput,
+    schema: Schema,
+    /// Position of the row number in the output. It stays after the columns the operator was
+    /// built with, so that columns added later are appended after it.
+    row_number_index: usize,
+
+    partitions: HashMap<Vec<Field>, Partition>,
+}
+
+impl TopNOperator {
+    pub fn new(
+        partition_by: Vec<Expression>,
+        order_by: Vec<OrderByExpression>,
+        output: TopNOutput,
+        schema: Schema,
+    ) -> Self {
+        Self {
+            partition_by,
+            order_by,
+            output,
+            row_number_index: schema.fields.len(),
+            schema,
+            partitions: HashMap::new(),
+        }
+    }
+
+    pub fn output_schema(&self) -> Schema {
+        let mut schema = self.schema.clone();
+        if let TopNOutput::RowNumber { .. } = &self.output {
+            schema
+                .fields
+                .insert(self.row_number_index, row_number_field());
+            for index in schema.primary_index.iter_mut() {
+                if *index >= self.row_number_index {
+                    *index += 1;
+                }
+            }
+        }
+        schema
+    }
+
+    /// Accepts columns added to the input and returns the new output schema, or `None` if the
+    /// change isn't additive. The retained rows are padded with nulls.
+    pub fn on_schema_changed(&mut self, schema: &Schema) -> Option<Schema> {
+        self.schema.added_fields(schema)?;
+        self.schema = schema.clone();
+        let width = schema.fields.len();
+        for partition in self.partitions.values_mut() {
+            for (_, record) in partition.rows.iter_mut() {
+                record.values.resize(width, Field::Null);
+            }
+        }
+        Some(self.output_schema())
+    }
+
+    pub fn insert(&mut self, record: &Record) -> Result<Vec<Operation>, PipelineError> {
+        let partition_key = self.get_partition_key(record)?;
+        let sort_key = self.get_sort_key(record)?;
+
+        let rows = &mut self.partitions.entry(partition_key).or_default().rows;
+        let position = rows.partition_point(|(key, _)| key <= &sort_key);
+        rows.insert(position, (sort_key, record.clone()));
+
+        Ok(match &self.output {
+            TopNOutput::Limit { offset, limit } => {
+                limit_insert_operations(rows, position, *offset, *limit)
+            }
+            TopNOutput::RowNumber { limit } => {
+                row_number_insert_operations(rows, position, *limit, self.row_number_index)
+            }
+        })
+    }
+
+    pub fn delete(&mut self, record: &Record) -> Result<Vec<Operation>, PipelineError> {
+        let partition_key = self.get_partition_key(record)?;
+        let sort_key = self.get_sort_key(record)?;
+
+        let Some(partition) = self.partitions.get_mut(&partition_key) else {
+            return Ok(vec![]);
+        };
+        let rows = &mut partition.rows;
+        let start = rows.partition_point(|(key, _)| key < &sort_key);
+        let end = rows.partition_point(|(key, _)| key <= &sort_key);
+        let Some(position) = (start..end).find(|index| &rows[*index].1 == record) else {
+            // The record was never inserted.
+            return Ok(vec![]);
+        };
+        rows.remove(position);
+
+        let operations = match &self.output {
+            TopNOutput::Limit { offset, limit } => {
+                limit_delete_operations(rows, position, record, *offset, *limit)
+            }
+            TopNOutput::RowNumber { limit } => {
+                row_number_delete_operations(rows, position, record, *limit, self.row_number_index)
+            }
+        };
+
+        if rows.is_empty() {
+            self.partitions.remove(&partition_key);
+        }
+        Ok(operations)
+    }
+
+    fn get_partition_key(&self, record: &Record) -> Result<Vec<Field>, PipelineError> {
+        self.partition_by
+            .iter()
+            .map(|expression| expression.evaluate(record, &self.schema))
+            .collect()
+    }
+
+    fn get_sort_key(&self, record: &Record) -> Result<SortKey, PipelineError> {
+        self.order_by
+            .iter()
+            .map(|order_by| {
+                let value = order_by.expression.evaluate(record, &self.schema)?;
+                Ok(if order_by.descending {
+                    SortValue::Descending(Reverse(value))
+                } else {
+                    SortValue::Ascending(value)
+                })
+            })
+            .collect()
+    }
+}
+
+/// `rows` already contains the new row at `position`.
+fn limit_insert_operations(
+    rows: &[(SortKey, Record)],
+    position: usize,
+    offset: usize,
+    limit: usize,
+) -> Vec<Operation> {
+    let end = offset + limit;
+    if limit == 0 || position >= end {
+        return vec![];
+    }
+
+    let mut operations = vec![];
+    // the last row of the window is pushed out
+    if let Some((_, old)) = rows.get(end) {
+        operations.push(Operation::Delete { old: old.clone() });
+    }
+    // either the new row, or the row it pushed into the window
+    if let Some((_, new)) = rows.get(position.max(offset)) {
+        operations.push(Operation::Insert { new: new.clone() });
+    }
+    operations
+}
+
+/// `rows` no longer contains `record`, which was at `position`.
+fn limit_delete_operations(
+    rows: &[(SortKey, Record)],
+    position: usize,
+    record: &Record,
+    offset: usize,
+    limit: usize,
+) -> Vec<Operation> {
+    let end = offset + limit;
+    if limit == 0 || position >= end {
+        return vec![];
+    }
+
+    let mut operations = vec![];
+    // either the deleted row, or the row it pulled out of the window
+    let old = if position >= offset {
+        record
+    } else {
+        match rows.get(offset - 1) {
+            Some((_, old)) => old,
+            None => return vec![],
+        }
+    };
+    operations.push(Operation::Delete { old: old.clone() });
+    // the first row after the window moves in
+    if let Some((_, new)) = rows.get(end - 1) {
+        operations.push(Operation::Insert { new: new.clone() });
+    }
+    operations
+}
+
+/// `rows` already contains the new row at `position`. Every row after it moves down by one, and
+/// the row moving past `limit` leaves the output.
+fn row_number_insert_operations(
+    rows: &[(SortKey, Record)],
+    position: usize,
+    limit: Option<usize>,
+    column: usize,
+) -> Vec<Operation> {
+    let end = limit.unwrap_or(usize::MAX).min(rows.len());
+    if position >= end {
+        return vec![];
+    }
+
+    let mut operations = vec![];
+    if let Some((_, old)) = rows.get(end).filter(|_| limit.is_some()) {
+        operations.push(Operation::Delete {
+            old: with_row_number(column, old, end),
+        });
+    }
+    for index in (position + 1..end).rev() {
+        operations.push(Operation::Update {
+            old: with_row_number(column, &rows[index].1, index),
+            new: with_row_number(column, &rows[index].1, index + 1),
+        });
+    }
+    operations.push(Operation::Insert {
+        new: with_row_number(column, &rows[position].1, position + 1),
+    });
+    operations
+}
+
+/// `rows` no longer contains `record`, which was at `position`. Every row after it moves up by
+/// one, and the row moving up to `limit` enters the output.
+fn row_number_delete_operations(
+    rows: &[(SortKey, Record)],
+    position: usize,
+    record: &Record,
+    limit: Option<usize>,
+    column: usize,
+) -> Vec<Operation> {
+    let end = limit.unwrap_or(usize::MAX);
+    if position >= end {
+        return vec![];
+    }
+
+    let mut operations = vec![Operation::Delete {
+        old: with_row_number(column, record, position + 1),
+    }];
+    let moved = rows.len().min(end - 1);
+    for (index, (_, row)) in rows.iter().enumerate().take(moved).skip(position) {
+        operations.push(Operation::Update {
+            old: with_row_number(column, row, index + 2),
+            new: with_row_number(column, row, index + 1),
+        });
+    }
+    if let Some((_, new)) = rows.get(end - 1).filter(|_| limit.is_some()) {
+        operations.push(Operation::Insert {
+            new: with_row_number(column, new, end),
+        });
+    }
+    operations
+}
+
+fn with_row_number(column: usize, record: &Record, row_number: usize) -> Record {
+    let mut values = record.values.clone();
+    values.insert(column, Field::Int(row_number as i64));
+    Record::new(record.schema_id, values, record.version)
+}
@@ -0,0 +1,78 @@
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::epoch::Epoch;
+use dozer_core::errors::ExecutionError;
+use dozer_core::node::{PortHandle, Processor};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::node::SourceStates;
+use dozer_types::types::{Operation, Schema};
+use std::collections::HashMap;
+
+use super::operator::TopNOperator;
+
+#[derive(Debug)]
+pub struct TopNProcessor {
+    operator: TopNOperator,
+}
+
+impl TopNProcessor {
+    pub fn new(operator: TopNOperator) -> Self {
+        Self { operator }
+    }
+}
+
+impl Processor for TopNProcessor {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn is_resumable(&self, _checkpoint: &SourceStates) -> bool {
+        // The state is kept in memory, so it's empty after a restart.
+        false
+    }
+
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        let output_schema = self.operator.on_schema_changed(schema).ok_or_else(|| {
+            ExecutionError::UnsupportedSchemaChange("Top-N input columns changed".to_string())
+        })?;
+        Ok(HashMap::from([(DEFAULT_PORT_HANDLE, output_schema)]))
+    }
+
+    fn process(
+        &mut self,
+        _from_port: PortHandle,
+        op: Operation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), ExecutionError> {
+        let operations = match op {
+            Operation::Delete { ref old } => self
+                .operator
+                .delete(old)
+                .map_err(|e| ExecutionError::InternalError(Box::new(e)))?,
+            Operation::Insert { ref new } => self
+                .operator
+                .insert(new)
+                .map_err(|e| ExecutionError::InternalError(Box::new(e)))?,
+            Operation::Update { ref old, ref new } => {
+                let mut operations = self
+                    .operator
+                    .delete(old)
+                    .map_err(|e| ExecutionError::InternalError(Box::new(e)))?;
+                operations.extend(
+                    self.operator
+                        .insert(new)
+                        .map_err(|e| ExecutionError::InternalError(Box::new(e)))?,
+                );
+                operations
+            }
+        };
+
+        for operation in operations {
+            fw.send(operation, DEFAULT_PORT_HANDLE)?;
+        }
+        Ok(())
+    }
+}
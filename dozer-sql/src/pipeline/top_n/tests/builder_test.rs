@@ -0,0 +1,137 @@
+use dozer_core::app::AppPipeline;
+use sqlparser::ast::{Select, SetExpr, Statement};
+use sqlparser::dialect::AnsiDialect;
+use sqlparser::parser::Parser;
+
+use crate::pipeline::builder::{statement_to_pipeline, SchemaSQLContext};
+use crate::pipeline::errors::{PipelineError, TopNError, UnsupportedSqlError};
+use crate::pipeline::top_n::builder::{row_number_limit, take_row_number};
+use crate::pipeline::top_n::factory::ROW_NUMBER_COLUMN;
+
+fn build(sql: &str) -> Result<String, PipelineError> {
+    let mut pipeline: AppPipeline<SchemaSQLContext> = AppPipeline::new();
+    let context = statement_to_pipeline(sql, &mut pipeline, Some("results".to_string()))?;
+    Ok(context.output_tables_map["results"].node.clone())
+}
+
+fn parse_select(sql: &str) -> Select {
+    let statement = Parser::parse_sql(&AnsiDialect {}, sql).unwrap().remove(0);
+    let Statement::Query(query) = statement else {
+        panic!("{sql} is not a query");
+    };
+    let SetExpr::Select(select) = *query.body else {
+        panic!("{sql} is not a SELECT");
+    };
+    *select
+}
+
+#[test]
+fn test_order_by_limit() {
+    // Rows are ranked before the projection, which can then refer to any column.
+    let output = build("SELECT name FROM products ORDER BY sales DESC LIMIT 10").unwrap();
+    assert!(output.starts_with("agg_"));
+    let output =
+        build("SELECT name, sales * 2 AS score FROM products ORDER BY score DESC LIMIT 10")
+            .unwrap();
+    assert!(output.starts_with("agg_"));
+
+    // Aggregated rows are ranked after the aggregation.
+    let output = build(
+        "SELECT category, SUM(sales) AS total FROM products \
+        GROUP BY category ORDER BY total DESC LIMIT 10",
+    )
+    .unwrap();
+    assert!(output.starts_with("top_n_"));
+}
+
+#[test]
+fn test_row_number() {
+    let output = build(
+        "SELECT category, name, sales, \
+        ROW_NUMBER() OVER (PARTITION BY category ORDER BY sales DESC) AS rn \
+        FROM products",
+    )
+    .unwrap();
+    assert!(output.starts_with("agg_"));
+
+    let output = build(
+        "SELECT category, name FROM (\
+            SELECT category, name, sales, \
+            ROW_NUMBER() OVER (PARTITION BY category ORDER BY sales DESC) AS rn \
+            FROM products\
+        ) WHERE rn <= 10",
+    )
+    .unwrap();
+    assert!(output.starts_with("agg_"));
+}
+
+#[test]
+fn test_unsupported_top_n() {
+    assert!(matches!(
+        build("SELECT name FROM products ORDER BY sales"),
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::OrderByError
+        ))
+    ));
+    assert!(matches!(
+        build("SELECT name FROM products LIMIT 10"),
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::LimitOffsetError
+        ))
+    ));
+    assert!(matches!(
+        build("SELECT name, ROW_NUMBER() OVER (PARTITION BY category) FROM products"),
+        Err(PipelineError::TopNError(TopNError::RowNumberWithoutOrderBy))
+    ));
+    assert!(matches!(
+        build(
+            "SELECT category, COUNT(name), \
+            ROW_NUMBER() OVER (ORDER BY category) FROM products GROUP BY category"
+        ),
+        Err(PipelineError::TopNError(
+            TopNError::RowNumberWithAggregation
+        ))
+    ));
+}
+
+#[test]
+fn test_row_number_keeps_its_position() {
+    let mut select = parse_select(
+        "SELECT name, ROW_NUMBER() OVER (ORDER BY sales DESC) AS rn, sales FROM products",
+    );
+    assert!(take_row_number(&mut select, None).unwrap().is_some());
+
+    let projection = select
+        .projection
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        projection,
+        vec![
+            "name".to_string(),
+            format!("{ROW_NUMBER_COLUMN} AS rn"),
+            "sales".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_row_number_limit() {
+    let subquery = "SELECT category, name, \
+        ROW_NUMBER() OVER (PARTITION BY category ORDER BY sales DESC) AS rn FROM products";
+
+    let select = parse_select(&format!("SELECT name FROM ({subquery}) WHERE rn <= 3"));
+    assert_eq!(row_number_limit(&select), Some(3));
+    let select = parse_select(&format!(
+        "SELECT name FROM ({subquery}) AS t WHERE category = 'a' AND t.rn < 3"
+    ));
+    assert_eq!(row_number_limit(&select), Some(2));
+
+    let select = parse_select(&format!("SELECT name FROM ({subquery}) WHERE rn > 3"));
+    assert_eq!(row_number_limit(&select), None);
+    let select = parse_select(&format!(
+        "SELECT name FROM ({subquery} ORDER BY name LIMIT 10) WHERE rn <= 3"
+    ));
+    assert_eq!(row_number_limit(&select), None);
+}
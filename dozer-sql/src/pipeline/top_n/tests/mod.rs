@@ -0,0 +1,5 @@
+#[cfg(test)]
+mod builder_test;
+
+#[cfg(test)]
+mod operator_test;
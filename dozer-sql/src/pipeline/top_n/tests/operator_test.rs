@@ -0,0 +1,378 @@
+use dozer_types::types::{
+    Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
+};
+
+use crate::pipeline::expression::execution::Expression;
+use crate::pipeline::top_n::operator::{OrderByExpression, TopNOperator, TopNOutput};
+
+fn schema() -> Schema {
+    Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "category".to_string(),
+                FieldType::String,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "product".to_string(),
+                FieldType::String,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            true,
+        )
+        .field(
+            FieldDefinition::new(
+                "sales".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn product(category: &str, name: &str, sales: i64) -> Record {
+    Record::new(
+        None,
+        vec![
+            Field::String(category.to_string()),
+            Field::String(name.to_string()),
+            Field::Int(sales),
+        ],
+        None,
+    )
+}
+
+fn ranked(record: &Record, row_number: i64) -> Record {
+    let mut record = record.clone();
+    record.values.push(Field::Int(row_number));
+    record
+}
+
+fn by_sales(descending: bool) -> Vec<OrderByExpression> {
+    vec![OrderByExpression {
+        expression: Expression::Column { index: 2 },
+        descending,
+    }]
+}
+
+#[test]
+fn test_top_n() {
+    let mut operator = TopNOperator::new(
+        vec![],
+        by_sales(true),
+        TopNOutput::Limit {
+            offset: 0,
+            limit: 2,
+        },
+        schema(),
+    );
+    let a = product("A", "a", 10);
+    let b = product("A", "b", 20);
+    let c = product("B", "c", 15);
+    let d = product("B", "d", 5);
+
+    assert_eq!(
+        operator.insert(&a).unwrap(),
+        vec![Operation::Insert { new: a.clone() }]
+    );
+    assert_eq!(
+        operator.insert(&b).unwrap(),
+        vec![Operation::Insert { new: b.clone() }]
+    );
+    assert_eq!(
+        operator.insert(&c).unwrap(),
+        vec![
+            Operation::Delete { old: a.clone() },
+            Operation::Insert { new: c.clone() },
+        ]
+    );
+    assert!(operator.insert(&d).unwrap().is_empty());
+
+    assert_eq!(
+        operator.delete(&b).unwrap(),
+        vec![Operation::Delete { old: b }, Operation::Insert { new: a },]
+    );
+    assert!(operator.delete(&d).unwrap().is_empty());
+}
+
+#[test]
+fn test_top_n_with_offset() {
+    let mut operator = TopNOperator::new(
+        vec![],
+        by_sales(false),
+        TopNOutput::Limit {
+            offset: 1,
+            limit: 1,
+        },
+        schema(),
+    );
+    let a = product("A", "a", 10);
+    let b = product("A", "b", 20);
+    let c = product("A", "c", 5);
+
+    assert!(operator.insert(&a).unwrap().is_empty());
+    assert_eq!(
+        operator.insert(&b).unwrap(),
+        vec![Operation::Insert { new: b.clone() }]
+    );
+    assert_eq!(
+        operator.insert(&c).unwrap(),
+        vec![
+            Operation::Delete { old: b.clone() },
+            Operation::Insert { new: a.clone() },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&c).unwrap(),
+        vec![Operation::Delete { old: a }, Operation::Insert { new: b },]
+    );
+}
+
+#[test]
+fn test_top_n_refills_the_window_after_deletes() {
+    let mut operator = TopNOperator::new(
+        vec![],
+        by_sales(true),
+        TopNOutput::Limit {
+            offset: 0,
+            limit: 1,
+        },
+        schema(),
+    );
+    let a = product("A", "a", 10);
+    let b = product("A", "b", 20);
+    let c = product("A", "c", 5);
+    let d = product("A", "d", 3);
+    let e = product("A", "e", 7);
+
+    assert_eq!(
+        operator.insert(&a).unwrap(),
+        vec![Operation::Insert { new: a.clone() }]
+    );
+    assert_eq!(
+        operator.insert(&b).unwrap(),
+        vec![
+            Operation::Delete { old: a.clone() },
+            Operation::Insert { new: b.clone() },
+        ]
+    );
+    assert!(operator.insert(&c).unwrap().is_empty());
+    assert!(operator.insert(&d).unwrap().is_empty());
+
+    // Every delete from the window is refilled with the next row of the partition.
+    assert_eq!(
+        operator.delete(&b).unwrap(),
+        vec![
+            Operation::Delete { old: b },
+            Operation::Insert { new: a.clone() },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&a).unwrap(),
+        vec![
+            Operation::Delete { old: a },
+            Operation::Insert { new: c.clone() },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&c).unwrap(),
+        vec![
+            Operation::Delete { old: c },
+            Operation::Insert { new: d.clone() },
+        ]
+    );
+
+    assert_eq!(
+        operator.insert(&e).unwrap(),
+        vec![
+            Operation::Delete { old: d.clone() },
+            Operation::Insert { new: e.clone() },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&e).unwrap(),
+        vec![Operation::Delete { old: e }, Operation::Insert { new: d }]
+    );
+}
+
+#[test]
+fn test_row_number() {
+    let mut operator = TopNOperator::new(
+        vec![Expression::Column { index: 0 }],
+        by_sales(true),
+        TopNOutput::RowNumber { limit: None },
+        schema(),
+    );
+    let x = product("A", "x", 10);
+    let y = product("B", "y", 7);
+    let z = product("A", "z", 20);
+
+    assert_eq!(
+        operator.insert(&x).unwrap(),
+        vec![Operation::Insert { new: ranked(&x, 1) }]
+    );
+    assert_eq!(
+        operator.insert(&y).unwrap(),
+        vec![Operation::Insert { new: ranked(&y, 1) }]
+    );
+    assert_eq!(
+        operator.insert(&z).unwrap(),
+        vec![
+            Operation::Update {
+                old: ranked(&x, 1),
+                new: ranked(&x, 2),
+            },
+            Operation::Insert { new: ranked(&z, 1) },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&z).unwrap(),
+        vec![
+            Operation::Delete { old: ranked(&z, 1) },
+            Operation::Update {
+                old: ranked(&x, 2),
+                new: ranked(&x, 1),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_row_number_with_limit() {
+    let mut operator = TopNOperator::new(
+        vec![],
+        by_sales(true),
+        TopNOutput::RowNumber { limit: Some(2) },
+        schema(),
+    );
+    let x = product("A", "x", 10);
+    let y = product("A", "y", 20);
+    let z = product("A", "z", 30);
+
+    assert_eq!(
+        operator.insert(&x).unwrap(),
+        vec![Operation::Insert { new: ranked(&x, 1) }]
+    );
+    assert_eq!(
+        operator.insert(&y).unwrap(),
+        vec![
+            Operation::Update {
+                old: ranked(&x, 1),
+                new: ranked(&x, 2),
+            },
+            Operation::Insert { new: ranked(&y, 1) },
+        ]
+    );
+    assert_eq!(
+        operator.insert(&z).unwrap(),
+        vec![
+            Operation::Delete { old: ranked(&x, 2) },
+            Operation::Update {
+                old: ranked(&y, 1),
+                new: ranked(&y, 2),
+            },
+            Operation::Insert { new: ranked(&z, 1) },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&z).unwrap(),
+        vec![
+            Operation::Delete { old: ranked(&z, 1) },
+            Operation::Update {
+                old: ranked(&y, 2),
+                new: ranked(&y, 1),
+            },
+            Operation::Insert { new: ranked(&x, 2) },
+        ]
+    );
+    assert_eq!(
+        operator.delete(&y).unwrap(),
+        vec![
+            Operation::Delete { old: ranked(&y, 1) },
+            Operation::Update {
+                old: ranked(&x, 2),
+                new: ranked(&x, 1),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_row_number_with_added_column() {
+    let mut operator = TopNOperator::new(
+        vec![Expression::Column { index: 0 }],
+        by_sales(true),
+        TopNOutput::RowNumber { limit: None },
+        schema(),
+    );
+    let x = product("A", "x", 10);
+    operator.insert(&x).unwrap();
+
+    let mut input_schema = schema();
+    input_schema.field(
+        FieldDefinition::new(
+            "region".to_string(),
+            FieldType::String,
+            true,
+            SourceDefinition::Dynamic,
+        ),
+        false,
+    );
+    let output_schema = operator.on_schema_changed(&input_schema).unwrap();
+    // the row number keeps its position and the new column is appended after it
+    let names = output_schema
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            "category",
+            "product",
+            "sales",
+            "__dozer_row_number",
+            "region"
+        ]
+    );
+    assert_eq!(output_schema.primary_index, vec![1]);
+
+    let ranked_with_region = |record: &Record, row_number: i64, region: Field| {
+        let mut record = ranked(record, row_number);
+        record.values.push(region);
+        record
+    };
+    let mut z = product("A", "z", 20);
+    z.values.push(Field::String("EU".to_string()));
+    let z_values = product("A", "z", 20);
+    assert_eq!(
+        operator.insert(&z).unwrap(),
+        vec![
+            Operation::Update {
+                old: ranked_with_region(&x, 1, Field::Null),
+                new: ranked_with_region(&x, 2, Field::Null),
+            },
+            Operation::Insert {
+                new: ranked_with_region(&z_values, 1, Field::String("EU".to_string())),
+            },
+        ]
+    );
+
+    let mut padded_x = x.clone();
+    padded_x.values.push(Field::Null);
+    assert_eq!(
+        operator.delete(&padded_x).unwrap(),
+        vec![Operation::Delete {
+            old: ranked_with_region(&x, 2, Field::Null),
+        }]
+    );
+
+    assert!(operator.on_schema_changed(&schema()).is_none());
+}
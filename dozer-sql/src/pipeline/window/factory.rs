@@ -88,7 +88,7 @@ impl ProcessorFactory<SchemaSQLContext> for WindowProcessorFactory {
         match window_from_table_operator(&self.table, &input_schema)
             .map_err(|e| ExecutionError::WindowProcessorFactoryError(Box::new(e)))?
         {
-            Some(window) => Ok(Box::new(WindowProcessor::new(window))),
+            Some(window) => Ok(Box::new(WindowProcessor::new(window, input_schema))),
             None => Err(ExecutionError::WindowProcessorFactoryError(Box::new(
                 WindowError::InvalidWindow(),
             ))),
@@ -4,30 +4,82 @@ use dozer_core::epoch::Epoch;
 use dozer_core::errors::ExecutionError;
 use dozer_core::node::{PortHandle, Processor};
 use dozer_core::DEFAULT_PORT_HANDLE;
-use dozer_types::types::{Operation, Record};
+use dozer_types::types::{Field, Operation, Record, Schema};
+use std::collections::HashMap;
 
 use super::operator::WindowType;
 
+/// Number of columns the window appends to its input.
+const WINDOW_COLUMNS: usize = 2;
+
 #[derive(Debug)]
 pub struct WindowProcessor {
     window: WindowType,
+    input_schema: Schema,
+    /// Position of the window columns in the output. They stay after the columns the processor
+    /// was built with, so that columns added later are appended after them.
+    window_index: usize,
 }
 
 impl WindowProcessor {
-    pub fn new(window: WindowType) -> Self {
-        Self { window }
+    pub fn new(window: WindowType, input_schema: Schema) -> Self {
+        Self {
+            window,
+            window_index: input_schema.fields.len(),
+            input_schema,
+        }
     }
 
     fn execute(&self, record: &Record) -> Result<Vec<Record>, WindowError> {
-        self.window.execute(record)
+        let mut records = if record.values.len() < self.input_schema.fields.len() {
+            let mut record = record.clone();
+            record
+                .values
+                .resize(self.input_schema.fields.len(), Field::Null);
+            self.window.execute(&record)?
+        } else {
+            self.window.execute(record)?
+        };
+        for record in records.iter_mut() {
+            record.values[self.window_index..].rotate_right(WINDOW_COLUMNS);
+        }
+        Ok(records)
     }
 }
 
 impl Processor for WindowProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), ExecutionError> {
+    fn commit(&mut self, _epoch: &Epoch) -> Result<(), ExecutionError> {
         Ok(())
     }
 
+    fn on_schema_changed(
+        &mut self,
+        _from_port: PortHandle,
+        schema: &Schema,
+    ) -> Result<HashMap<PortHandle, Schema>, ExecutionError> {
+        if self.input_schema.added_fields(schema).is_none() {
+            return Err(ExecutionError::UnsupportedSchemaChange(
+                "window input columns changed".to_string(),
+            ));
+        }
+        self.input_schema = schema.clone();
+
+        let mut output_schema = self
+            .window
+            .get_output_schema(schema)
+            .map_err(|e| ExecutionError::WindowProcessorError(Box::new(e)))?;
+        let window_start = schema.fields.len();
+        output_schema.fields[self.window_index..].rotate_right(WINDOW_COLUMNS);
+        for index in output_schema.primary_index.iter_mut() {
+            if *index == window_start {
+                *index = self.window_index;
+            } else if *index >= self.window_index {
+                *index += WINDOW_COLUMNS;
+            }
+        }
+        Ok(HashMap::from([(DEFAULT_PORT_HANDLE, output_schema)]))
+    }
+
     fn process(
         &mut self,
         _from_port: PortHandle,
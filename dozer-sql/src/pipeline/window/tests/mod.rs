@@ -3,3 +3,6 @@ mod operator_test;
 
 #[cfg(test)]
 mod pipeline_test;
+
+#[cfg(test)]
+mod processor_test;
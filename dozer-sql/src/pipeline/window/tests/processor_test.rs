@@ -0,0 +1,129 @@
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::errors::ExecutionError;
+use dozer_core::node::{PortHandle, Processor};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::{
+    chrono::{DateTime, Duration},
+    types::{Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition},
+};
+
+use crate::pipeline::window::operator::WindowType;
+use crate::pipeline::window::processor::WindowProcessor;
+
+struct TestChannelForwarder {
+    operations: Vec<Operation>,
+}
+
+impl ProcessorChannelForwarder for TestChannelForwarder {
+    fn send(&mut self, op: Operation, _port: PortHandle) -> Result<(), ExecutionError> {
+        self.operations.push(op);
+        Ok(())
+    }
+}
+
+fn timestamp(value: &str) -> Field {
+    Field::Timestamp(DateTime::parse_from_rfc3339(value).unwrap())
+}
+
+#[test]
+fn test_window_with_added_column() {
+    let schema = Schema::empty()
+        .field(
+            FieldDefinition::new(
+                "id".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            true,
+        )
+        .field(
+            FieldDefinition::new(
+                "time".to_string(),
+                FieldType::Timestamp,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone();
+    let window = WindowType::Tumble {
+        column_index: 1,
+        interval: Duration::minutes(5),
+    };
+    let mut processor = WindowProcessor::new(window, schema.clone());
+
+    let mut wider_schema = schema;
+    wider_schema.field(
+        FieldDefinition::new(
+            "name".to_string(),
+            FieldType::String,
+            true,
+            SourceDefinition::Dynamic,
+        ),
+        false,
+    );
+    let output_schema = processor
+        .on_schema_changed(DEFAULT_PORT_HANDLE, &wider_schema)
+        .unwrap()
+        .remove(&DEFAULT_PORT_HANDLE)
+        .unwrap();
+    // the window columns keep their position and the new column is appended after them
+    let names = output_schema
+        .fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec!["id", "time", "window_start", "window_end", "name"]
+    );
+    assert_eq!(output_schema.primary_index, vec![0, 2]);
+
+    let mut fw = TestChannelForwarder { operations: vec![] };
+    let time = timestamp("2020-01-01T00:13:00Z");
+    processor
+        .process(
+            DEFAULT_PORT_HANDLE,
+            Operation::Insert {
+                new: Record::new(
+                    None,
+                    vec![Field::Int(0), time.clone(), Field::String("a".to_string())],
+                    None,
+                ),
+            },
+            &mut fw,
+        )
+        .unwrap();
+    // records sent before the change are padded
+    processor
+        .process(
+            DEFAULT_PORT_HANDLE,
+            Operation::Delete {
+                old: Record::new(None, vec![Field::Int(0), time.clone()], None),
+            },
+            &mut fw,
+        )
+        .unwrap();
+
+    let window_values = |name: Field| {
+        vec![
+            Field::Int(0),
+            time.clone(),
+            timestamp("2020-01-01T00:10:00Z"),
+            timestamp("2020-01-01T00:15:00Z"),
+            name,
+        ]
+    };
+    assert_eq!(
+        fw.operations,
+        vec![
+            Operation::Insert {
+                new: Record::new(None, window_values(Field::String("a".to_string())), None),
+            },
+            Operation::Delete {
+                old: Record::new(None, window_values(Field::Null), None),
+            },
+        ]
+    );
+}
@@ -93,6 +93,10 @@ impl<K: LmdbKey, V: LmdbKey> LmdbMultimap<K, V> {
         }
     }
 
+    pub fn clear(&self, txn: &mut RwTransaction) -> Result<(), StorageError> {
+        txn.clear_db(self.db).map_err(Into::into)
+    }
+
     pub fn iter<'txn, T: Transaction>(
         &self,
         txn: &'txn T,
@@ -39,6 +39,10 @@ impl<V: LmdbVal> LmdbOption<V> {
     pub fn store(&self, txn: &mut RwTransaction, value: V::Encode<'_>) -> Result<(), StorageError> {
         self.0.insert_overwrite(txn, &KEY, value)
     }
+
+    pub fn clear(&self, txn: &mut RwTransaction) -> Result<(), StorageError> {
+        self.0.clear(txn)
+    }
 }
 
 const KEY: u8 = 0;
@@ -61,6 +61,16 @@ fn convert_filter(filter: Option<&FilterExpression>) -> Document {
                     insert_filter_to_document_recursive(document, filter)
                 }
             }
+            FilterExpression::Or(filters) => {
+                let filters = filters
+                    .iter()
+                    .map(|filter| convert_filter(Some(filter)))
+                    .collect::<Vec<_>>();
+                document.insert("$or", filters);
+            }
+            FilterExpression::Not(filter) => {
+                document.insert("$nor", vec![convert_filter(Some(filter))]);
+            }
         }
     }
 
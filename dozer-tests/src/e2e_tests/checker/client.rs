@@ -330,6 +330,7 @@ fn grpc_type_matches(grpc_type: i32, field_type: FieldType) -> bool {
         FieldType::Date => grpc_type == Type::Date as i32,
         FieldType::Bson => grpc_type == Type::Bson as i32,
         FieldType::Point => grpc_type == Type::Point as i32,
+        FieldType::Interval => grpc_type == Type::String as i32,
     }
 }
 
@@ -346,7 +347,8 @@ fn oapi_type_matches(oapi_type: &dozer_api::openapiv3::Type, field_type: FieldTy
             | FieldType::Text
             | FieldType::Decimal
             | FieldType::Timestamp
-            | FieldType::Date,
+            | FieldType::Date
+            | FieldType::Interval,
         ) => {
             if field_type == FieldType::Timestamp {
                 string_type.format == VariantOrUnknownOrEmpty::Item(StringFormat::DateTime)
@@ -349,6 +349,10 @@ fn write_dozer_config_for_running_in_docker_compose(
             ConnectionConfig::S3Storage(_) => {}
             ConnectionConfig::LocalStorage(_) => {}
             ConnectionConfig::DeltaLake(_) => {}
+            ConnectionConfig::MySQL(mysql) => {
+                mysql.host = connection.name.clone();
+                mysql.port = map_port(mysql.port as u16) as u32;
+            }
         }
     }
 
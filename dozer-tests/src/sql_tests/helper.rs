@@ -150,7 +150,7 @@ pub fn map_sqlite_to_record(
                 Field::Decimal(Decimal::from_str(&val).expect("decimal parse error"))
             },
             FieldType::Date =>  convert_type!(Field::String, f, row, idx),
-            FieldType::Bson | FieldType::Point => {
+            FieldType::Bson | FieldType::Point | FieldType::Interval => {
                 panic!("type not supported : {:?}", f.typ.to_owned())
             }
         };
@@ -230,6 +230,7 @@ pub fn map_field_to_string(f: &Field) -> String {
         Field::Decimal(i) => i.to_string(),
         Field::Null => "null".to_string(),
         Field::Point(p) => format!("'{:?}'", p.0.x_y()),
+        Field::Interval(i) => format!("'{i}'"),
     }
 }
 
@@ -117,6 +117,7 @@ pub fn map_field_to_string(f: &Field) -> String {
         Field::Decimal(i) => i.to_string(),
         Field::Null => "null".to_string(),
         Field::Point(p) => format!("'{:?}'", p.0.x_y()),
+        Field::Interval(i) => format!("'{i}'"),
     }
 }
 
@@ -171,7 +172,7 @@ pub fn map_sqlite_to_record(schema: &Schema, row: &rusqlite::Row) -> Result<Reco
                 Field::Decimal(Decimal::from_str(&val).expect("decimal parse error"))
             },
             FieldType::Date =>  convert_type!(Field::String, f, row, idx),
-            FieldType::Bson | FieldType::Point => {
+            FieldType::Bson | FieldType::Point | FieldType::Interval => {
                 panic!("type not supported : {:?}", f.typ.to_owned())
             }
         };
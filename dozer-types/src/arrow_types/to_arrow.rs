@@ -112,6 +112,12 @@ pub fn map_record_to_arrow(
                     None as Option<&[u8]>,
                 ])) as ArrayRef
             }
+            (Field::Interval(v), FieldType::Interval) => {
+                Arc::new(arrow_array::StringArray::from_iter_values([v.to_string()])) as ArrayRef
+            }
+            (Field::Null, FieldType::Interval) => {
+                Arc::new(arrow_array::StringArray::from(vec![None as Option<String>])) as ArrayRef
+            }
             (a, b) => Err(arrow::error::ArrowError::InvalidArgumentError(format!(
                 "Invalid field type {b:?} for the field: {a:?}",
             )))?,
@@ -148,6 +154,10 @@ pub fn map_field_type(typ: FieldType, metadata: Option<&mut HashMap<String, Stri
             metadata.map(|m| m.insert("logical_type".to_string(), "Point".to_string()));
             DataType::Binary
         }
+        FieldType::Interval => {
+            metadata.map(|m| m.insert("logical_type".to_string(), "Interval".to_string()));
+            DataType::Utf8
+        }
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::errors::types::{DeserializationError, TypeError};
-use crate::types::{DozerPoint, DATE_FORMAT};
+use crate::types::{DozerInterval, DozerPoint, DATE_FORMAT};
 use crate::types::{Field, FieldType};
 use chrono::{DateTime, NaiveDate};
 use ordered_float::OrderedFloat;
@@ -44,6 +44,7 @@ pub fn json_value_to_field(
         (FieldType::Decimal, Value::String(str)) => return Field::from_str(str, typ, nullable),
         (FieldType::Timestamp, Value::String(str)) => return Field::from_str(str, typ, nullable),
         (FieldType::Date, Value::String(str)) => return Field::from_str(str, typ, nullable),
+        (FieldType::Interval, Value::String(str)) => return Field::from_str(str, typ, nullable),
         (FieldType::Bson, _) => serde_json::from_value(value)
             .map_err(DeserializationError::Json)
             .map(Field::Bson),
@@ -186,6 +187,13 @@ impl Field {
                     value.parse::<DozerPoint>().map(Field::Point)
                 }
             }
+            FieldType::Interval => {
+                if nullable && (value.is_empty() || value == "null") {
+                    Ok(Field::Null)
+                } else {
+                    value.parse::<DozerInterval>().map(Field::Interval)
+                }
+            }
         }
     }
 }
@@ -246,6 +254,12 @@ mod tests {
                 false,
                 Field::Point(DozerPoint(Point::new(OrderedFloat(1.0), OrderedFloat(1.0)))),
             ),
+            (
+                "P1DT2H",
+                FieldType::Interval,
+                false,
+                Field::Interval(DozerInterval::new(0, 1, 2 * 60 * 60 * 1_000_000_000)),
+            ),
             ("null", FieldType::UInt, true, Field::Null),
             ("null", FieldType::Int, true, Field::Null),
             ("null", FieldType::Float, true, Field::Null),
@@ -268,6 +282,7 @@ mod tests {
             ("null", FieldType::Date, true, Field::Null),
             ("null", FieldType::Bson, true, Field::Null),
             ("null", FieldType::Point, true, Field::Null),
+            ("null", FieldType::Interval, true, Field::Null),
             ("", FieldType::UInt, true, Field::Null),
             ("", FieldType::Int, true, Field::Null),
             ("", FieldType::Float, true, Field::Null),
@@ -280,6 +295,7 @@ mod tests {
             ("", FieldType::Date, true, Field::Null),
             ("", FieldType::Bson, true, Field::Null),
             ("", FieldType::Point, true, Field::Null),
+            ("", FieldType::Interval, true, Field::Null),
         ];
 
         for case in ok_cases {
@@ -297,6 +313,7 @@ mod tests {
             ("null", FieldType::Date, false),
             ("null", FieldType::Bson, false),
             ("null", FieldType::Point, false),
+            ("null", FieldType::Interval, false),
             ("", FieldType::UInt, false),
             ("", FieldType::Int, false),
             ("", FieldType::Float, false),
@@ -307,6 +324,7 @@ mod tests {
             ("", FieldType::Date, false),
             ("", FieldType::Bson, false),
             ("", FieldType::Point, false),
+            ("", FieldType::Interval, false),
         ];
         for err_case in err_cases {
             assert!(Field::from_str(err_case.0, err_case.1, err_case.2).is_err());
@@ -4,7 +4,11 @@ use std::fmt::Debug;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{errors::internal::BoxedError, node::OpIdentifier, types::Operation};
+use crate::{
+    errors::internal::BoxedError,
+    node::OpIdentifier,
+    types::{Operation, Schema},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 /// Messages that connectors send to Dozer.
@@ -29,6 +33,13 @@ impl IngestionMessage {
             kind: IngestionMessageKind::SnapshottingDone,
         }
     }
+
+    pub fn new_schema_changed(txn: u64, seq_no: u64, schema: Schema) -> Self {
+        Self {
+            identifier: OpIdentifier::new(txn, seq_no),
+            kind: IngestionMessageKind::SchemaChanged(schema),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -39,6 +50,11 @@ pub enum IngestionMessageKind {
     /// A connector uses this message kind to notify Dozer that a initial snapshot of the source table is done,
     /// and the data is up-to-date until next CDC event.
     SnapshottingDone,
+    /// A connector uses this message kind to notify Dozer that the schema of a source table has changed.
+    /// The schema identifier tells which table it is. Operations after this message follow the new schema.
+    ///
+    /// Only additive changes, i.e. new nullable columns appended to the table, can be handled by the pipeline.
+    SchemaChanged(Schema),
 }
 
 #[derive(Error, Debug)]
@@ -186,6 +202,16 @@ pub struct KafkaConfig {
     pub broker: String,
     #[prost(string, optional, tag = "3")]
     pub schema_registry_url: Option<String>,
+    /// Consumer group whose committed offsets are resumed from, `dozer-{connection name}` when
+    /// omitted.
+    #[prost(string, optional, tag = "4")]
+    pub group_id: Option<String>,
+    #[prost(message, optional, tag = "5")]
+    pub security: Option<KafkaSecurity>,
+    /// Topics of plain JSON or Avro messages. Without them, topics carry Debezium envelopes.
+    #[prost(message, repeated, tag = "6")]
+    #[serde(default)]
+    pub topics: Vec<KafkaTopic>,
 }
 
 impl KafkaConfig {
@@ -197,11 +223,80 @@ impl KafkaConfig {
                 self.schema_registry_url
                     .as_ref()
                     .map_or("--------", |url| url)
+            ],
+            [
+                "group id",
+                self.group_id.as_ref().map_or("--------", |id| id)
+            ],
+            [
+                "security protocol",
+                self.security
+                    .as_ref()
+                    .map_or("plaintext", |security| &security.protocol)
+            ],
+            [
+                "topics",
+                self.topics
+                    .iter()
+                    .map(|topic| topic.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
             ]
         )
     }
 }
 
+/// Authentication and encryption of the broker connections.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
+pub struct KafkaSecurity {
+    /// One of `plaintext`, `ssl`, `sasl_plaintext` or `sasl_ssl`.
+    #[prost(string, tag = "1")]
+    pub protocol: String,
+    /// One of `PLAIN`, `SCRAM-SHA-256` or `SCRAM-SHA-512`.
+    #[prost(string, optional, tag = "2")]
+    pub sasl_mechanism: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub sasl_username: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub sasl_password: Option<String>,
+    /// Path of the certificate of the authority that signed the broker certificates.
+    #[prost(string, optional, tag = "5")]
+    pub ssl_ca_location: Option<String>,
+    /// Path of the client certificate, for brokers that authenticate clients with TLS.
+    #[prost(string, optional, tag = "6")]
+    pub ssl_certificate_location: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub ssl_key_location: Option<String>,
+}
+
+/// A topic ingested as a table, with the schema of its messages.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
+pub struct KafkaTopic {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    /// `json`, or `avro` for messages in the Confluent wire format, which needs
+    /// `schema_registry_url`.
+    #[prost(string, tag = "2")]
+    pub format: String,
+    #[prost(message, repeated, tag = "3")]
+    pub fields: Vec<KafkaField>,
+    #[prost(string, repeated, tag = "4")]
+    #[serde(default)]
+    pub primary_key: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
+pub struct KafkaField {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    /// A Dozer type, like `int`, `string` or `timestamp`.
+    #[prost(string, tag = "2")]
+    pub typ: String,
+    #[prost(bool, tag = "3")]
+    #[serde(default)]
+    pub nullable: bool,
+}
+
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
 pub struct SnowflakeConfig {
     #[prost(string, tag = "1")]
@@ -220,6 +315,12 @@ pub struct SnowflakeConfig {
     pub warehouse: String,
     #[prost(string, optional, tag = "8")]
     pub driver: Option<String>,
+    /// Seconds between two reads of the table streams, 5 by default.
+    #[prost(uint64, optional, tag = "9")]
+    pub poll_interval_seconds: Option<u64>,
+    /// Warehouse the table streams are read with, `warehouse` by default.
+    #[prost(string, optional, tag = "10")]
+    pub stream_warehouse: Option<String>,
 }
 
 impl SnowflakeConfig {
@@ -232,7 +333,51 @@ impl SnowflakeConfig {
             ["database", self.database],
             ["schema", self.schema],
             ["warehouse", self.warehouse],
-            ["driver", self.driver.as_ref().map_or("default", |d| d)]
+            ["driver", self.driver.as_ref().map_or("default", |d| d)],
+            [
+                "poll_interval_seconds",
+                self.poll_interval_seconds
+                    .map_or("default".to_string(), |s| s.to_string())
+            ],
+            [
+                "stream_warehouse",
+                self.stream_warehouse.as_ref().unwrap_or(&self.warehouse)
+            ]
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
+pub struct MySQLConfig {
+    #[prost(string, tag = "1")]
+    pub host: String,
+    #[prost(uint32, tag = "2")]
+    pub port: u32,
+    #[prost(string, tag = "3")]
+    pub user: String,
+    #[prost(string, tag = "4")]
+    pub password: String,
+    #[prost(string, tag = "5")]
+    pub database: String,
+    /// Server id the connector uses as a replica. It must be unique among the replicas of the
+    /// server and is derived from the connection name when omitted.
+    #[prost(uint32, optional, tag = "6")]
+    pub server_id: Option<u32>,
+}
+
+impl MySQLConfig {
+    pub fn convert_to_table(&self) -> PrettyTable {
+        table!(
+            ["host", self.host],
+            ["port", self.port],
+            ["user", self.user],
+            ["password", "************"],
+            ["database", self.database],
+            [
+                "server_id",
+                self.server_id
+                    .map_or("default".to_string(), |id| id.to_string())
+            ]
         )
     }
 }
@@ -270,6 +415,39 @@ pub struct Table {
     pub file_type: String,
     #[prost(string, tag = "4")]
     pub extension: String,
+    #[prost(message, optional, tag = "5")]
+    pub watch: Option<TableWatch>,
+    #[prost(message, optional, tag = "6")]
+    pub format_options: Option<FileFormatOptions>,
+}
+
+/// Options of the table file format. Unset options keep the format defaults.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
+pub struct FileFormatOptions {
+    /// Csv only. A single character.
+    #[prost(string, optional, tag = "1")]
+    pub delimiter: Option<String>,
+    /// Csv only.
+    #[prost(bool, optional, tag = "2")]
+    pub has_header: Option<bool>,
+    /// Csv and json only. One of `gzip`, `bzip2`, `xz` or `uncompressed`.
+    #[prost(string, optional, tag = "4")]
+    pub compression: Option<String>,
+    /// Csv and json only. Number of records read to infer the schema.
+    #[prost(uint64, optional, tag = "5")]
+    pub schema_infer_max_records: Option<u64>,
+}
+
+/// Keeps polling a table after the initial read and ingests the data that appears later.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
+pub struct TableWatch {
+    #[prost(uint64, tag = "1", default = "5000")]
+    #[serde(default = "default_watch_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_watch_interval_ms() -> u64 {
+    5000
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
@@ -336,6 +514,9 @@ pub struct DeltaTable {
     pub path: String,
     #[prost(string, tag = "2")]
     pub name: String,
+    /// Tails `_delta_log` after the snapshot and ingests the changes of every new commit.
+    #[prost(message, optional, tag = "3")]
+    pub watch: Option<TableWatch>,
 }
 
 impl DeltaTable {
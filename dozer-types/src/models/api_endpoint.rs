@@ -8,6 +8,64 @@ use serde_yaml::Value;
 pub struct ApiIndex {
     #[prost(string, repeated, tag = "1")]
     pub primary_key: Vec<String>,
+
+    #[prost(message, optional, tag = "2")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<SecondaryIndexConfig>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message)]
+pub struct SecondaryIndexConfig {
+    /// Fields that don't get the indexes every field gets by default.
+    #[prost(string, repeated, tag = "1")]
+    #[serde(default)]
+    pub skip_default: Vec<String>,
+
+    /// Indexes created in addition to the default ones.
+    #[prost(message, repeated, tag = "2")]
+    #[serde(default)]
+    pub create: Vec<CreateSecondaryIndex>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message)]
+pub struct CreateSecondaryIndex {
+    #[prost(oneof = "SecondaryIndex", tags = "1,2,3")]
+    pub index: Option<SecondaryIndex>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Oneof)]
+pub enum SecondaryIndex {
+    #[prost(message, tag = "1")]
+    /// In yaml, present as tag: `!SortedInverted`
+    SortedInverted(SortedInverted),
+    #[prost(message, tag = "2")]
+    /// In yaml, present as tag: `!FullText`
+    FullText(FullText),
+    #[prost(message, tag = "3")]
+    /// In yaml, present as tag: `!Spatial`
+    Spatial(Spatial),
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message)]
+pub struct SortedInverted {
+    /// Fields of the index, in order. Queries use it when they filter its first fields with `$eq`,
+    /// and at most the next one with a range or a sort.
+    #[prost(string, repeated, tag = "1")]
+    pub fields: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message)]
+pub struct FullText {
+    /// A `String` or `Text` field.
+    #[prost(string, tag = "1")]
+    pub field: String,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message)]
+pub struct Spatial {
+    /// A `Point` field. Queries use it when they filter the field with `$near` or `$within`.
+    #[prost(string, tag = "1")]
+    pub field: String,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, ::prost::Enumeration)]
@@ -3,6 +3,8 @@ use super::{
     source::Source, telemetry::TelemetryConfig,
 };
 use crate::{constants::DEFAULT_HOME_DIR, models::api_config::default_api_config};
+use std::collections::HashMap;
+
 use serde::{
     de::{self, IgnoredAny, Visitor},
     Deserialize, Deserializer, Serialize,
@@ -52,6 +54,11 @@ pub struct Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_max_map_size: Option<u64>,
 
+    /// Pipeline lmdb max map size, used for JOIN and aggregation state kept on disk
+    #[prost(uint64, optional, tag = "11")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_max_map_size: Option<u64>,
+
     /// Pipeline buffer size
     #[prost(uint32, optional, tag = "12")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,6 +78,28 @@ pub struct Config {
     /// Instrument using Dozer
     #[serde(skip_serializing_if = "Option::is_none")]
     pub telemetry: Option<TelemetryConfig>,
+
+    /// Keep JOIN and aggregation state on disk instead of in memory
+    #[prost(bool, optional, tag = "18")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_state_on_disk: Option<bool>,
+
+    /// Number of operations sent between pipeline nodes in one message
+    #[prost(uint32, optional, tag = "19")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_batch_size: Option<u32>,
+
+    /// Number of threads each JOIN, aggregation and projection runs on
+    #[prost(uint32, optional, tag = "20")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_parallelism: Option<u32>,
+
+    /// Number of threads of individual JOIN and aggregation processors, overriding `app_parallelism`.
+    /// Processors are named `join_<n>` and `agg_<n>`, where `n` counts both in the order they appear in `sql`.
+    /// Changing the number of threads of a processor restarts the pipeline from scratch
+    #[prost(map = "string, uint32", tag = "21")]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub app_processor_parallelism: HashMap<String, u32>,
 }
 
 pub fn default_home_dir() -> String {
@@ -81,10 +110,26 @@ pub fn default_cache_max_map_size() -> u64 {
     1024 * 1024 * 1024
 }
 
+pub fn default_app_max_map_size() -> u64 {
+    1024 * 1024 * 1024
+}
+
+pub fn default_app_state_on_disk() -> bool {
+    false
+}
+
 pub fn default_app_buffer_size() -> u32 {
     20_000
 }
 
+pub fn default_app_batch_size() -> u32 {
+    1
+}
+
+pub fn default_app_parallelism() -> u32 {
+    1
+}
+
 pub fn default_commit_size() -> u32 {
     10_000
 }
@@ -123,7 +168,12 @@ impl<'de> Deserialize<'de> for Config {
                 let mut home_dir: String = default_home_dir();
 
                 let mut cache_max_map_size: Option<u64> = Some(default_cache_max_map_size());
+                let mut app_max_map_size: Option<u64> = Some(default_app_max_map_size());
+                let mut app_state_on_disk: Option<bool> = Some(default_app_state_on_disk());
                 let mut app_buffer_size: Option<u32> = Some(default_app_buffer_size());
+                let mut app_batch_size: Option<u32> = Some(default_app_batch_size());
+                let mut app_parallelism: Option<u32> = Some(default_app_parallelism());
+                let mut app_processor_parallelism: HashMap<String, u32> = HashMap::new();
                 let mut commit_size: Option<u32> = Some(default_commit_size());
                 let mut commit_timeout: Option<u64> = Some(default_commit_timeout());
 
@@ -156,9 +206,25 @@ impl<'de> Deserialize<'de> for Config {
                         "cache_max_map_size" => {
                             cache_max_map_size = access.next_value::<Option<u64>>()?;
                         }
+                        "app_max_map_size" => {
+                            app_max_map_size = access.next_value::<Option<u64>>()?;
+                        }
+                        "app_state_on_disk" => {
+                            app_state_on_disk = access.next_value::<Option<bool>>()?;
+                        }
                         "app_buffer_size" => {
                             app_buffer_size = access.next_value::<Option<u32>>()?;
                         }
+                        "app_batch_size" => {
+                            app_batch_size = access.next_value::<Option<u32>>()?;
+                        }
+                        "app_parallelism" => {
+                            app_parallelism = access.next_value::<Option<u32>>()?;
+                        }
+                        "app_processor_parallelism" => {
+                            app_processor_parallelism =
+                                access.next_value::<HashMap<String, u32>>()?;
+                        }
                         "commit_size" => {
                             commit_size = access.next_value::<Option<u32>>()?;
                         }
@@ -234,10 +300,15 @@ impl<'de> Deserialize<'de> for Config {
                     home_dir,
                     flags,
                     cache_max_map_size,
+                    app_max_map_size,
                     app_buffer_size,
                     commit_size,
                     commit_timeout,
                     telemetry,
+                    app_state_on_disk,
+                    app_batch_size,
+                    app_parallelism,
+                    app_processor_parallelism,
                 })
             }
         }
@@ -1,5 +1,6 @@
 use crate::ingestion_types::{
-    DeltaLakeConfig, EthConfig, GrpcConfig, KafkaConfig, LocalStorage, S3Storage, SnowflakeConfig,
+    DeltaLakeConfig, EthConfig, GrpcConfig, KafkaConfig, LocalStorage, MySQLConfig, S3Storage,
+    SnowflakeConfig,
 };
 use serde::{Deserialize, Serialize};
 
@@ -7,7 +8,7 @@ use prettytable::Table;
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, ::prost::Message, Hash)]
 pub struct Connection {
-    #[prost(oneof = "ConnectionConfig", tags = "1,2,3,4,5,6,7,8")]
+    #[prost(oneof = "ConnectionConfig", tags = "1,2,3,4,5,6,7,8,10")]
     /// authentication config - depends on db_type
     pub config: Option<ConnectionConfig>,
     #[prost(string, tag = "9")]
@@ -26,6 +27,32 @@ pub struct PostgresConfig {
     pub port: u32,
     #[prost(string, tag = "5")]
     pub database: String,
+    /// One of `disable`, `prefer`, `require`, `verify-ca` and `verify-full`, as in libpq.
+    /// Connections aren't encrypted when omitted.
+    #[prost(string, optional, tag = "6")]
+    pub sslmode: Option<String>,
+    /// Path to the PEM encoded certificate of the authority that signed the server certificate.
+    #[prost(string, optional, tag = "7")]
+    pub ssl_root_cert: Option<String>,
+    /// Replication slot to use, `dozer_slot_{connection name}` by default.
+    #[prost(string, optional, tag = "8")]
+    pub slot_name: Option<String>,
+    /// Publication to use, `dozer_publication_{connection name}` by default.
+    #[prost(string, optional, tag = "9")]
+    pub publication_name: Option<String>,
+    /// Use the publication as it exists instead of recreating it for the ingested tables.
+    #[prost(bool, tag = "10")]
+    #[serde(default)]
+    pub reuse_publication: bool,
+    /// Schemas whose tables are listed, all of them when empty.
+    #[prost(string, repeated, tag = "11")]
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    /// Other replication slots `dozer clean` drops if they're inactive, like the ones left
+    /// behind by renamed or removed connections.
+    #[prost(string, repeated, tag = "12")]
+    #[serde(default)]
+    pub clean_slots: Vec<String>,
 }
 
 impl PostgresConfig {
@@ -35,7 +62,13 @@ impl PostgresConfig {
             ["password", "*************"],
             ["host", self.host],
             ["port", self.port],
-            ["database", self.database]
+            ["database", self.database],
+            ["sslmode", self.sslmode.as_deref().unwrap_or("disable")],
+            ["slot_name", self.slot_name.as_deref().unwrap_or("-")],
+            [
+                "publication_name",
+                self.publication_name.as_deref().unwrap_or("-")
+            ]
         )
     }
 }
@@ -66,4 +99,8 @@ pub enum ConnectionConfig {
     #[prost(message, tag = "8")]
     /// In yaml, present as tag" `!DeltaLake`
     DeltaLake(DeltaLakeConfig),
+    // Tag 9 is taken by `Connection::name`.
+    #[prost(message, tag = "10")]
+    /// In yaml, present as tag: `!MySQL`
+    MySQL(MySQLConfig),
 }
@@ -4,7 +4,9 @@ use std::{
     str::from_utf8,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodeHandle {
     pub ns: Option<u16>,
     pub id: String,
@@ -59,7 +61,9 @@ impl Display for NodeHandle {
     }
 }
 
-#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(
+    Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 /// A identifier made of two `u64`s.
 pub struct OpIdentifier {
     /// High 64 bits of the identifier.
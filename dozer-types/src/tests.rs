@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod api_config_yaml_deserialize;
 #[cfg(test)]
+mod api_endpoint_yaml_deserialize;
+#[cfg(test)]
 mod dozer_yaml_deserialize;
 #[cfg(test)]
 mod eth_yaml_deserialize;
@@ -9,4 +11,10 @@ mod field_serialize_test;
 #[cfg(test)]
 mod flags_config_yaml_deserialize;
 #[cfg(test)]
+mod kafka_yaml_deserialize;
+#[cfg(test)]
+mod mysql_yaml_deserialize;
+#[cfg(test)]
 mod postgres_yaml_deserialize;
+#[cfg(test)]
+mod snowflake_yaml_deserialize;
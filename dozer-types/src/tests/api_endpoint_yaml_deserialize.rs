@@ -0,0 +1,63 @@
+use crate::models::api_endpoint::{
+    ApiIndex, CreateSecondaryIndex, FullText, SecondaryIndex, SecondaryIndexConfig, SortedInverted,
+    Spatial,
+};
+
+#[test]
+fn secondary_index_config() {
+    let index = r#"
+    primary_key:
+      - id
+    secondary:
+      skip_default:
+        - description
+      create:
+        - index: !SortedInverted
+            fields:
+              - city
+              - created_at
+        - index: !FullText
+            field: description
+        - index: !Spatial
+            field: location
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ApiIndex>(index).unwrap();
+    let expected = ApiIndex {
+        primary_key: vec!["id".to_owned()],
+        secondary: Some(SecondaryIndexConfig {
+            skip_default: vec!["description".to_owned()],
+            create: vec![
+                CreateSecondaryIndex {
+                    index: Some(SecondaryIndex::SortedInverted(SortedInverted {
+                        fields: vec!["city".to_owned(), "created_at".to_owned()],
+                    })),
+                },
+                CreateSecondaryIndex {
+                    index: Some(SecondaryIndex::FullText(FullText {
+                        field: "description".to_owned(),
+                    })),
+                },
+                CreateSecondaryIndex {
+                    index: Some(SecondaryIndex::Spatial(Spatial {
+                        field: "location".to_owned(),
+                    })),
+                },
+            ],
+        }),
+    };
+    assert_eq!(expected, deserializer_result);
+}
+
+#[test]
+fn without_secondary_index_config() {
+    let index = r#"
+    primary_key:
+      - id
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ApiIndex>(index).unwrap();
+    let expected = ApiIndex {
+        primary_key: vec!["id".to_owned()],
+        secondary: None,
+    };
+    assert_eq!(expected, deserializer_result);
+}
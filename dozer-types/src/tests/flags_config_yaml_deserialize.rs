@@ -41,12 +41,25 @@ fn test_storage_params_config() {
     commit_timeout: 100
     app_buffer_size: 10000
     commit_size: 1000
+    app_state_on_disk: true
+    app_batch_size: 500
+    app_parallelism: 4
+    app_processor_parallelism:
+      join_1: 8
 "#;
     let deserializer_result = serde_yaml::from_str::<Config>(input_config_without_flag).unwrap();
     assert_eq!(deserializer_result.cache_max_map_size, Some(1073741824));
+    assert_eq!(deserializer_result.app_max_map_size, Some(1073741824));
+    assert_eq!(deserializer_result.app_state_on_disk, Some(true));
     assert_eq!(deserializer_result.commit_timeout, Some(100));
     assert_eq!(deserializer_result.app_buffer_size, Some(10000));
     assert_eq!(deserializer_result.commit_size, Some(1000));
+    assert_eq!(deserializer_result.app_batch_size, Some(500));
+    assert_eq!(deserializer_result.app_parallelism, Some(4));
+    assert_eq!(
+        deserializer_result.app_processor_parallelism,
+        [("join_1".to_string(), 8)].into_iter().collect()
+    );
 }
 
 #[test]
@@ -59,4 +72,9 @@ fn test_default_storage_params_config() {
     assert_eq!(deserializer_result.commit_timeout, Some(50));
     assert_eq!(deserializer_result.app_buffer_size, Some(20000));
     assert_eq!(deserializer_result.commit_size, Some(10000));
+    assert_eq!(deserializer_result.app_max_map_size, Some(1073741824));
+    assert_eq!(deserializer_result.app_state_on_disk, Some(false));
+    assert_eq!(deserializer_result.app_batch_size, Some(1));
+    assert_eq!(deserializer_result.app_parallelism, Some(1));
+    assert!(deserializer_result.app_processor_parallelism.is_empty());
 }
@@ -0,0 +1,79 @@
+use crate::{
+    ingestion_types::{KafkaConfig, KafkaField, KafkaSecurity, KafkaTopic},
+    models::connection::ConnectionConfig,
+};
+#[test]
+fn debezium() {
+    let kafka_config = r#"
+    !Kafka
+    broker: localhost:9092
+    schema_registry_url: http://localhost:8081
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(kafka_config).unwrap();
+    let expected = ConnectionConfig::Kafka(KafkaConfig {
+        broker: "localhost:9092".to_owned(),
+        schema_registry_url: Some("http://localhost:8081".to_owned()),
+        group_id: None,
+        security: None,
+        topics: vec![],
+    });
+    assert_eq!(expected, deserializer_result);
+}
+
+#[test]
+fn plain_topics_with_sasl() {
+    let kafka_config = r#"
+    !Kafka
+    broker: broker.example.com:9093
+    group_id: trips_app
+    security:
+      protocol: sasl_ssl
+      sasl_mechanism: SCRAM-SHA-256
+      sasl_username: dozer
+      sasl_password: secret
+    topics:
+      - name: trips
+        format: json
+        fields:
+          - name: id
+            typ: uint
+          - name: fare
+            typ: decimal
+            nullable: true
+        primary_key:
+          - id
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(kafka_config).unwrap();
+    let expected = ConnectionConfig::Kafka(KafkaConfig {
+        broker: "broker.example.com:9093".to_owned(),
+        schema_registry_url: None,
+        group_id: Some("trips_app".to_owned()),
+        security: Some(KafkaSecurity {
+            protocol: "sasl_ssl".to_owned(),
+            sasl_mechanism: Some("SCRAM-SHA-256".to_owned()),
+            sasl_username: Some("dozer".to_owned()),
+            sasl_password: Some("secret".to_owned()),
+            ssl_ca_location: None,
+            ssl_certificate_location: None,
+            ssl_key_location: None,
+        }),
+        topics: vec![KafkaTopic {
+            name: "trips".to_owned(),
+            format: "json".to_owned(),
+            fields: vec![
+                KafkaField {
+                    name: "id".to_owned(),
+                    typ: "uint".to_owned(),
+                    nullable: false,
+                },
+                KafkaField {
+                    name: "fare".to_owned(),
+                    typ: "decimal".to_owned(),
+                    nullable: true,
+                },
+            ],
+            primary_key: vec!["id".to_owned()],
+        }],
+    });
+    assert_eq!(expected, deserializer_result);
+}
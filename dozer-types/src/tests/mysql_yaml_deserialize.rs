@@ -0,0 +1,40 @@
+use crate::{ingestion_types::MySQLConfig, models::connection::ConnectionConfig};
+#[test]
+fn standard() {
+    let mysql_config = r#"
+    !MySQL
+    host: localhost
+    port: 3306
+    user: root
+    password: mysql
+    database: users
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(mysql_config).unwrap();
+    let expected = ConnectionConfig::MySQL(MySQLConfig {
+        host: "localhost".to_owned(),
+        port: 3306,
+        user: "root".to_owned(),
+        password: "mysql".to_owned(),
+        database: "users".to_owned(),
+        server_id: None,
+    });
+    assert_eq!(expected, deserializer_result);
+}
+
+#[test]
+fn with_server_id() {
+    let mysql_config = r#"
+    !MySQL
+    host: localhost
+    port: 3306
+    user: root
+    password: mysql
+    database: users
+    server_id: 1234
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(mysql_config).unwrap();
+    let ConnectionConfig::MySQL(config) = deserializer_result else {
+        panic!("Expected a MySQL config");
+    };
+    assert_eq!(config.server_id, Some(1234));
+}
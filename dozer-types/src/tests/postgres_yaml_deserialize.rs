@@ -16,6 +16,52 @@ fn standard() {
         host: "localhost".to_owned(),
         port: 5432,
         database: "users".to_owned(),
+        sslmode: None,
+        ssl_root_cert: None,
+        slot_name: None,
+        publication_name: None,
+        reuse_publication: false,
+        schemas: vec![],
+        clean_slots: vec![],
+    };
+    let expected = ConnectionConfig::Postgres(postgres_auth);
+    assert_eq!(expected, deserializer_result);
+}
+
+#[test]
+fn tls_and_replication_options() {
+    let posgres_config = r#"
+    !Postgres
+    user: postgres
+    password: postgres
+    host: localhost
+    port: 5432
+    database: users
+    sslmode: verify-full
+    ssl_root_cert: /etc/ssl/root.crt
+    slot_name: users_slot
+    publication_name: users_publication
+    reuse_publication: true
+    schemas:
+      - public
+      - billing
+    clean_slots:
+      - dozer_slot_old_users
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(posgres_config).unwrap();
+    let postgres_auth = PostgresConfig {
+        user: "postgres".to_owned(),
+        password: "postgres".to_owned(),
+        host: "localhost".to_owned(),
+        port: 5432,
+        database: "users".to_owned(),
+        sslmode: Some("verify-full".to_owned()),
+        ssl_root_cert: Some("/etc/ssl/root.crt".to_owned()),
+        slot_name: Some("users_slot".to_owned()),
+        publication_name: Some("users_publication".to_owned()),
+        reuse_publication: true,
+        schemas: vec!["public".to_owned(), "billing".to_owned()],
+        clean_slots: vec!["dozer_slot_old_users".to_owned()],
     };
     let expected = ConnectionConfig::Postgres(postgres_auth);
     assert_eq!(expected, deserializer_result);
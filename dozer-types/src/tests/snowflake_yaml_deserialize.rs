@@ -0,0 +1,50 @@
+use crate::{ingestion_types::SnowflakeConfig, models::connection::ConnectionConfig};
+#[test]
+fn standard() {
+    let snowflake_config = r#"
+    !Snowflake
+    server: example.snowflakecomputing.com
+    port: "443"
+    user: bob
+    password: password
+    database: database
+    schema: PUBLIC
+    warehouse: COMPUTE_WH
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(snowflake_config).unwrap();
+    let expected = ConnectionConfig::Snowflake(SnowflakeConfig {
+        server: "example.snowflakecomputing.com".to_owned(),
+        port: "443".to_owned(),
+        user: "bob".to_owned(),
+        password: "password".to_owned(),
+        database: "database".to_owned(),
+        schema: "PUBLIC".to_owned(),
+        warehouse: "COMPUTE_WH".to_owned(),
+        driver: None,
+        poll_interval_seconds: None,
+        stream_warehouse: None,
+    });
+    assert_eq!(expected, deserializer_result);
+}
+
+#[test]
+fn with_polling() {
+    let snowflake_config = r#"
+    !Snowflake
+    server: example.snowflakecomputing.com
+    port: "443"
+    user: bob
+    password: password
+    database: database
+    schema: PUBLIC
+    warehouse: COMPUTE_WH
+    poll_interval_seconds: 60
+    stream_warehouse: STREAMS_WH
+  "#;
+    let deserializer_result = serde_yaml::from_str::<ConnectionConfig>(snowflake_config).unwrap();
+    let ConnectionConfig::Snowflake(config) = deserializer_result else {
+        panic!("Expected a Snowflake config");
+    };
+    assert_eq!(config.poll_interval_seconds, Some(60));
+    assert_eq!(config.stream_warehouse, Some("STREAMS_WH".to_owned()));
+}
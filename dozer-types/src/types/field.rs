@@ -7,7 +7,7 @@ use rust_decimal::Decimal;
 use serde::{self, Deserialize, Serialize};
 use std::borrow::Cow;
 
-use crate::types::DozerPoint;
+use crate::types::{DozerInterval, DozerPoint};
 use std::fmt::{Display, Formatter};
 
 pub const DATE_FORMAT: &str = "%Y-%m-%d";
@@ -26,6 +26,7 @@ pub enum Field {
     Bson(Vec<u8>),
     Point(DozerPoint),
     Null,
+    Interval(DozerInterval),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
@@ -43,6 +44,7 @@ pub enum FieldBorrow<'a> {
     Bson(&'a [u8]),
     Point(DozerPoint),
     Null,
+    Interval(DozerInterval),
 }
 
 impl Field {
@@ -60,6 +62,7 @@ impl Field {
             Field::Date(_) => 10,
             Field::Bson(b) => b.len(),
             Field::Point(_p) => 16,
+            Field::Interval(_i) => 16,
             Field::Null => 0,
         }
     }
@@ -79,6 +82,7 @@ impl Field {
             Field::Bson(b) => Cow::Borrowed(b),
             Field::Null => Cow::Owned([].into()),
             Field::Point(p) => Cow::Owned(p.to_bytes().into()),
+            Field::Interval(i) => Cow::Owned(i.to_bytes().into()),
         }
     }
 
@@ -113,6 +117,7 @@ impl Field {
             Field::Date(t) => FieldBorrow::Date(*t),
             Field::Bson(b) => FieldBorrow::Bson(b),
             Field::Point(p) => FieldBorrow::Point(*p),
+            Field::Interval(i) => FieldBorrow::Interval(*i),
             Field::Null => FieldBorrow::Null,
         }
     }
@@ -170,6 +175,9 @@ impl Field {
                 DozerPoint::from_bytes(val).map_err(|_| DeserializationError::BadDataLength)?,
             )),
             12 => Ok(FieldBorrow::Null),
+            13 => Ok(FieldBorrow::Interval(
+                DozerInterval::from_bytes(val).map_err(|_| DeserializationError::BadDataLength)?,
+            )),
             other => Err(DeserializationError::UnrecognisedFieldType(other)),
         }
     }
@@ -189,6 +197,7 @@ impl Field {
             Field::Bson(_) => 10,
             Field::Point(_) => 11,
             Field::Null => 12,
+            Field::Interval(_) => 13,
         }
     }
 
@@ -276,6 +285,13 @@ impl Field {
         }
     }
 
+    pub fn as_interval(&self) -> Option<DozerInterval> {
+        match self {
+            Field::Interval(i) => Some(*i),
+            _ => None,
+        }
+    }
+
     pub fn as_null(&self) -> Option<()> {
         match self {
             Field::Null => Some(()),
@@ -343,6 +359,7 @@ impl Field {
             Field::Date(d) => Some(d.format("%Y-%m-%d").to_string()),
             Field::Timestamp(t) => Some(t.to_rfc3339()),
             Field::Binary(b) => Some(format!("{b:X?}")),
+            Field::Interval(i) => Some(i.to_string()),
             Field::Null => Some("".to_string()),
             _ => None,
         }
@@ -364,6 +381,7 @@ impl Field {
             Field::Date(d) => Some(d.format("%Y-%m-%d").to_string()),
             Field::Timestamp(t) => Some(t.to_rfc3339()),
             Field::Binary(b) => Some(format!("{b:X?}")),
+            Field::Interval(i) => Some(i.to_string()),
             Field::Null => Some("".to_string()),
             _ => None,
         }
@@ -428,6 +446,14 @@ impl Field {
         }
     }
 
+    pub fn to_interval(&self) -> Option<DozerInterval> {
+        match self {
+            Field::Interval(i) => Some(*i),
+            Field::String(s) | Field::Text(s) => s.parse::<DozerInterval>().ok(),
+            _ => None,
+        }
+    }
+
     pub fn to_null(&self) -> Option<()> {
         match self {
             Field::Null => Some(()),
@@ -452,6 +478,7 @@ impl Display for Field {
             Field::Bson(v) => f.write_str(&format!("{v:x?}")),
             Field::Null => f.write_str("NULL"),
             Field::Point(v) => f.write_str(&format!("{v} (Point)")),
+            Field::Interval(v) => f.write_str(&format!("{v} (Interval)")),
         }
     }
 }
@@ -471,6 +498,7 @@ impl<'a> FieldBorrow<'a> {
             FieldBorrow::Date(d) => Field::Date(d),
             FieldBorrow::Bson(b) => Field::Bson(b.to_owned()),
             FieldBorrow::Point(p) => Field::Point(p),
+            FieldBorrow::Interval(i) => Field::Interval(i),
             FieldBorrow::Null => Field::Null,
         }
     }
@@ -506,6 +534,8 @@ pub enum FieldType {
     Bson,
     /// A geographic point.
     Point,
+    /// A time interval of months, days and nanoseconds.
+    Interval,
 }
 
 impl TryFrom<&str> for FieldType {
@@ -524,6 +554,7 @@ impl TryFrom<&str> for FieldType {
             "timestamp" => FieldType::Timestamp,
             "date" => FieldType::Date,
             "bson" => FieldType::Bson,
+            "interval" => FieldType::Interval,
             _ => return Err(format!("Unsupported '{value}' type")),
         };
 
@@ -546,6 +577,7 @@ impl Display for FieldType {
             FieldType::Date => f.write_str("date"),
             FieldType::Bson => f.write_str("bson"),
             FieldType::Point => f.write_str("point"),
+            FieldType::Interval => f.write_str("interval"),
         }
     }
 }
@@ -578,6 +610,8 @@ pub fn field_test_cases() -> impl Iterator<Item = Field> {
             // BSON representation of `{"abc":"foo"}`
             123, 34, 97, 98, 99, 34, 58, 34, 102, 111, 111, 34, 125,
         ]),
+        Field::Interval(DozerInterval::default()),
+        Field::Interval(DozerInterval::new(1, 2, 3)),
         Field::Null,
     ]
     .into_iter()
@@ -604,6 +638,7 @@ impl pyo3::ToPyObject for Field {
             Field::Bson(val) => val.to_object(py),
             Field::Null => unreachable!(),
             Field::Point(_val) => todo!(),
+            Field::Interval(val) => val.to_string().to_object(py),
         }
     }
 }
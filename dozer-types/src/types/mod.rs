@@ -117,6 +117,26 @@ impl Schema {
     pub fn is_append_only(&self) -> bool {
         self.primary_index.is_empty()
     }
+
+    /// Returns the fields that `new` adds to this schema, or `None` if `new` is not an additive change of this schema.
+    ///
+    /// A change is additive if all existing fields keep their names and types, and the new fields are nullable and appended at the end.
+    pub fn added_fields<'a>(&self, new: &'a Schema) -> Option<&'a [FieldDefinition]> {
+        if new.fields.len() < self.fields.len() {
+            return None;
+        }
+        let (existing, added) = new.fields.split_at(self.fields.len());
+        let existing_unchanged = self
+            .fields
+            .iter()
+            .zip(existing)
+            .all(|(old, new)| old.name == new.name && old.typ == new.typ);
+        if existing_unchanged && added.iter().all(|field| field.nullable) {
+            Some(added)
+        } else {
+            None
+        }
+    }
 }
 
 impl Display for Schema {
@@ -132,6 +152,8 @@ pub enum IndexDefinition {
     SortedInverted(Vec<usize>),
     /// Full text index, supporting `Contains`, `MatchesAny` and `MatchesAll` filter on exactly one field.
     FullText(usize),
+    /// Spatial index, supporting `Near` and `Within` filter on exactly one `Point` field.
+    Spatial(usize),
 }
 
 pub type SchemaWithIndex = (Schema, Vec<IndexDefinition>);
@@ -334,3 +356,193 @@ impl DozerPoint {
         Ok(DozerPoint::from((x, y)))
     }
 }
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_MINUTE: i64 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MINUTE;
+
+/// A time interval. Months and days are kept apart from the exact part because their length
+/// depends on the date the interval is added to.
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord,
+)]
+pub struct DozerInterval {
+    pub months: i32,
+    pub days: i32,
+    pub nanos: i64,
+}
+
+impl DozerInterval {
+    pub fn new(months: i32, days: i32, nanos: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanos,
+        }
+    }
+
+    /// `amount` of a unit such as `hour` or `days`, or `None` if the unit is unknown or the
+    /// interval overflows.
+    pub fn from_unit(amount: i64, unit: &str) -> Option<Self> {
+        let months = |n: i64| Some(Self::new(i32::try_from(n).ok()?, 0, 0));
+        let days = |n: i64| Some(Self::new(0, i32::try_from(n).ok()?, 0));
+        let nanos = |n: i64, unit: i64| Some(Self::new(0, 0, n.checked_mul(unit)?));
+        match unit.to_lowercase().as_str() {
+            "year" | "years" | "y" => months(amount.checked_mul(12)?),
+            "month" | "months" | "mon" | "mons" => months(amount),
+            "week" | "weeks" | "w" => days(amount.checked_mul(7)?),
+            "day" | "days" | "d" => days(amount),
+            "hour" | "hours" | "h" => nanos(amount, NANOS_PER_HOUR),
+            "minute" | "minutes" | "min" | "mins" | "m" => nanos(amount, NANOS_PER_MINUTE),
+            "second" | "seconds" | "sec" | "secs" | "s" => nanos(amount, NANOS_PER_SECOND),
+            "millisecond" | "milliseconds" | "ms" => nanos(amount, 1_000_000),
+            "microsecond" | "microseconds" | "us" => nanos(amount, 1_000),
+            "nanosecond" | "nanoseconds" | "ns" => nanos(amount, 1),
+            _ => None,
+        }
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.months.checked_add(other.months)?,
+            self.days.checked_add(other.days)?,
+            self.nanos.checked_add(other.nanos)?,
+        ))
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self::new(
+            self.months.checked_neg()?,
+            self.days.checked_neg()?,
+            self.nanos.checked_neg()?,
+        ))
+    }
+
+    pub fn checked_mul(&self, factor: i64) -> Option<Self> {
+        let factor_i32 = i32::try_from(factor).ok()?;
+        Some(Self::new(
+            self.months.checked_mul(factor_i32)?,
+            self.days.checked_mul(factor_i32)?,
+            self.nanos.checked_mul(factor)?,
+        ))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut result = [0_u8; 16];
+        result[0..4].copy_from_slice(&self.months.to_be_bytes());
+        result[4..8].copy_from_slice(&self.days.to_be_bytes());
+        result[8..16].copy_from_slice(&self.nanos.to_be_bytes());
+        result
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TryFromSliceError> {
+        let months = i32::from_be_bytes(bytes[0..4].try_into()?);
+        let days = i32::from_be_bytes(bytes[4..8].try_into()?);
+        let nanos = i64::from_be_bytes(bytes[8..16].try_into()?);
+        Ok(Self::new(months, days, nanos))
+    }
+
+    /// Parses an ISO 8601 duration such as `P1Y2M3DT4H5M6.5S`.
+    fn parse_iso_8601(str: &str) -> Option<Self> {
+        let mut result = Self::default();
+        let mut in_time = false;
+        let mut number = String::new();
+        for c in str.strip_prefix('P')?.chars() {
+            if c == 'T' {
+                in_time = true;
+                continue;
+            }
+            if c.is_ascii_digit() || c == '-' || c == '.' {
+                number.push(c);
+                continue;
+            }
+            let part = match (in_time, c) {
+                (true, 'S') => {
+                    let seconds = number.parse::<f64>().ok()?;
+                    Self::new(0, 0, (seconds * NANOS_PER_SECOND as f64).round() as i64)
+                }
+                (false, 'Y') => Self::from_unit(number.parse().ok()?, "year")?,
+                (false, 'M') => Self::from_unit(number.parse().ok()?, "month")?,
+                (false, 'W') => Self::from_unit(number.parse().ok()?, "week")?,
+                (false, 'D') => Self::from_unit(number.parse().ok()?, "day")?,
+                (true, 'H') => Self::from_unit(number.parse().ok()?, "hour")?,
+                (true, 'M') => Self::from_unit(number.parse().ok()?, "minute")?,
+                _ => return None,
+            };
+            result = result.checked_add(&part)?;
+            number.clear();
+        }
+        number.is_empty().then_some(result)
+    }
+
+    /// Parses a list of amounts and units such as `1 day 2 hours`.
+    fn parse_units(str: &str) -> Option<Self> {
+        let words = str.split_whitespace().collect::<Vec<_>>();
+        if words.is_empty() || words.len() % 2 != 0 {
+            return None;
+        }
+        let mut result = Self::default();
+        for pair in words.chunks(2) {
+            result = result.checked_add(&Self::from_unit(pair[0].parse().ok()?, pair[1])?)?;
+        }
+        Some(result)
+    }
+}
+
+impl FromStr for DozerInterval {
+    type Err = TypeError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let str = str.trim();
+        let result = if str.starts_with('P') {
+            Self::parse_iso_8601(str)
+        } else {
+            Self::parse_units(str)
+        };
+        result.ok_or_else(|| InvalidFieldValue {
+            field_type: FieldType::Interval,
+            nullable: false,
+            value: str.to_string(),
+        })
+    }
+}
+
+/// Formats as an ISO 8601 duration.
+impl Display for DozerInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut result = "P".to_string();
+        if self.months != 0 {
+            result.push_str(&format!("{}M", self.months));
+        }
+        if self.days != 0 {
+            result.push_str(&format!("{}D", self.days));
+        }
+        if self.nanos != 0 || result.len() == 1 {
+            result.push('T');
+            let hours = self.nanos / NANOS_PER_HOUR;
+            let minutes = self.nanos % NANOS_PER_HOUR / NANOS_PER_MINUTE;
+            let nanos = self.nanos % NANOS_PER_MINUTE;
+            if hours != 0 {
+                result.push_str(&format!("{hours}H"));
+            }
+            if minutes != 0 {
+                result.push_str(&format!("{minutes}M"));
+            }
+            if nanos != 0 || (hours == 0 && minutes == 0) {
+                let seconds = nanos / NANOS_PER_SECOND;
+                let fraction = (nanos % NANOS_PER_SECOND).abs();
+                if fraction == 0 {
+                    result.push_str(&format!("{seconds}S"));
+                } else {
+                    let sign = if nanos < 0 && seconds == 0 { "-" } else { "" };
+                    let fraction = format!("{fraction:09}");
+                    result.push_str(&format!(
+                        "{sign}{seconds}.{}S",
+                        fraction.trim_end_matches('0')
+                    ));
+                }
+            }
+        }
+        f.write_str(&result)
+    }
+}
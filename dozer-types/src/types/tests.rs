@@ -405,4 +405,34 @@ pub mod tests {
         assert!(field.to_point().is_none());
         assert!(field.to_null().is_some());
     }
+
+    #[test]
+    fn test_interval_parse_and_display() {
+        let hour = 60 * 60 * 1_000_000_000;
+        let cases = [
+            ("1 hour", DozerInterval::new(0, 0, hour), "PT1H"),
+            ("2 days 3 hours", DozerInterval::new(0, 2, 3 * hour), "P2DT3H"),
+            ("1 year -1 month", DozerInterval::new(11, 0, 0), "P11M"),
+            ("90 seconds", DozerInterval::new(0, 0, 90_000_000_000), "PT1M30S"),
+            ("1500 ms", DozerInterval::new(0, 0, 1_500_000_000), "PT1.5S"),
+            ("0 days", DozerInterval::default(), "PT0S"),
+        ];
+        for (input, interval, display) in cases {
+            assert_eq!(input.parse::<DozerInterval>().unwrap(), interval);
+            assert_eq!(interval.to_string(), display);
+            assert_eq!(display.parse::<DozerInterval>().unwrap(), interval);
+        }
+
+        assert!("1 fortnight".parse::<DozerInterval>().is_err());
+        assert!("1".parse::<DozerInterval>().is_err());
+        assert!("P1X".parse::<DozerInterval>().is_err());
+
+        let field = Field::Interval(DozerInterval::new(0, 1, 0));
+        assert!(field.as_interval().is_some());
+        assert!(field.as_point().is_none());
+        assert_eq!(
+            Field::String("P1D".to_string()).to_interval(),
+            field.as_interval()
+        );
+    }
 }